@@ -3,5 +3,7 @@
 use libfuzzer_sys::fuzz_target;
 
 fuzz_target!(|data: &[u8]| {
-	let _ = classfile::ClassFile::read_from(&mut &data[..]);
+	if let Ok(class) = classfile::ClassFile::read_from(&mut &data[..]) {
+		let _ = class.check_format();
+	}
 });
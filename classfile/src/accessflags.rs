@@ -117,7 +117,7 @@ impl ClassAccessFlags {
 	pub const ACC_MODULE    : ClassAccessFlags = Self(0x8000);
 	
 	/// Returns the `u2` representation of the access flags
-	pub fn as_u2(self) -> u16 {
+	pub const fn as_u2(self) -> u16 {
 		self.0
 	}
 }
@@ -175,7 +175,7 @@ impl MethodAccessFlags {
 	pub const ACC_SYNTHETIC   : MethodAccessFlags = Self(0x1000);
 	
 	/// Returns the `u2` representation of the access flags
-	pub fn as_u2(self) -> u16 {
+	pub const fn as_u2(self) -> u16 {
 		self.0
 	}
 }
@@ -229,7 +229,7 @@ impl FieldAccessFlags {
 	pub const ACC_ENUM 	   : FieldAccessFlags = Self(0x4000);
 	
 	/// Returns the `u2` representation of the access flags
-	pub fn as_u2(self) -> u16 {
+	pub const fn as_u2(self) -> u16 {
 		self.0
 	}
 }
@@ -85,7 +85,7 @@ attribute_getter_methods! {
 	PermittedSubclasses,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum AttributeTag {
 	ConstantValue,
 	Code,
@@ -119,6 +119,44 @@ pub enum AttributeTag {
 	PermittedSubclasses,
 }
 
+impl AttributeTag {
+	/// The name of the attribute, as it appears in the class file
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::ConstantValue => "ConstantValue",
+			Self::Code => "Code",
+			Self::StackMapTable => "StackMapTable",
+			Self::Exceptions => "Exceptions",
+			Self::InnerClasses => "InnerClasses",
+			Self::EnclosingMethod => "EnclosingMethod",
+			Self::Synthetic => "Synthetic",
+			Self::Signature => "Signature",
+			Self::SourceFile => "SourceFile",
+			Self::SourceDebugExtension => "SourceDebugExtension",
+			Self::LineNumberTable => "LineNumberTable",
+			Self::LocalVariableTable => "LocalVariableTable",
+			Self::LocalVariableTypeTable => "LocalVariableTypeTable",
+			Self::Deprecated => "Deprecated",
+			Self::RuntimeVisibleAnnotations => "RuntimeVisibleAnnotations",
+			Self::RuntimeInvisibleAnnotations => "RuntimeInvisibleAnnotations",
+			Self::RuntimeVisibleParameterAnnotations => "RuntimeVisibleParameterAnnotations",
+			Self::RuntimeInvisibleParameterAnnotations => "RuntimeInvisibleParameterAnnotations",
+			Self::RuntimeVisibleTypeAnnotations => "RuntimeVisibleTypeAnnotations",
+			Self::RuntimeInvisibleTypeAnnotations => "RuntimeInvisibleTypeAnnotations",
+			Self::AnnotationDefault => "AnnotationDefault",
+			Self::BootstrapMethods => "BootstrapMethods",
+			Self::MethodParameters => "MethodParameters",
+			Self::Module => "Module",
+			Self::ModulePackages => "ModulePackages",
+			Self::ModuleMainClass => "ModuleMainClass",
+			Self::NestHost => "NestHost",
+			Self::NestMembers => "NestMembers",
+			Self::Record => "Record",
+			Self::PermittedSubclasses => "PermittedSubclasses",
+		}
+	}
+}
+
 impl TryFrom<&[u1]> for AttributeTag {
 	type Error = ClassFileParseError;
 
@@ -226,6 +264,53 @@ pub enum AttributeType {
 	PermittedSubclasses(PermittedSubclasses),
 }
 
+impl AttributeType {
+	pub fn tag(&self) -> AttributeTag {
+		match self {
+			AttributeType::ConstantValue(_) => AttributeTag::ConstantValue,
+			AttributeType::Code(_) => AttributeTag::Code,
+			AttributeType::StackMapTable(_) => AttributeTag::StackMapTable,
+			AttributeType::Exceptions(_) => AttributeTag::Exceptions,
+			AttributeType::InnerClasses(_) => AttributeTag::InnerClasses,
+			AttributeType::EnclosingMethod(_) => AttributeTag::EnclosingMethod,
+			AttributeType::Synthetic => AttributeTag::Synthetic,
+			AttributeType::Signature(_) => AttributeTag::Signature,
+			AttributeType::SourceFile(_) => AttributeTag::SourceFile,
+			AttributeType::SourceDebugExtension(_) => AttributeTag::SourceDebugExtension,
+			AttributeType::LineNumberTable(_) => AttributeTag::LineNumberTable,
+			AttributeType::LocalVariableTable(_) => AttributeTag::LocalVariableTable,
+			AttributeType::LocalVariableTypeTable(_) => AttributeTag::LocalVariableTypeTable,
+			AttributeType::Deprecated => AttributeTag::Deprecated,
+			AttributeType::RuntimeVisibleAnnotations(_) => AttributeTag::RuntimeVisibleAnnotations,
+			AttributeType::RuntimeInvisibleAnnotations(_) => {
+				AttributeTag::RuntimeInvisibleAnnotations
+			},
+			AttributeType::RuntimeVisibleParameterAnnotations(_) => {
+				AttributeTag::RuntimeVisibleParameterAnnotations
+			},
+			AttributeType::RuntimeInvisibleParameterAnnotations(_) => {
+				AttributeTag::RuntimeInvisibleParameterAnnotations
+			},
+			AttributeType::RuntimeVisibleTypeAnnotations(_) => {
+				AttributeTag::RuntimeVisibleTypeAnnotations
+			},
+			AttributeType::RuntimeInvisibleTypeAnnotations(_) => {
+				AttributeTag::RuntimeInvisibleTypeAnnotations
+			},
+			AttributeType::AnnotationDefault(_) => AttributeTag::AnnotationDefault,
+			AttributeType::BootstrapMethods(_) => AttributeTag::BootstrapMethods,
+			AttributeType::MethodParameters(_) => AttributeTag::MethodParameters,
+			AttributeType::Module(_) => AttributeTag::Module,
+			AttributeType::ModulePackages(_) => AttributeTag::ModulePackages,
+			AttributeType::ModuleMainClass(_) => AttributeTag::ModuleMainClass,
+			AttributeType::NestHost(_) => AttributeTag::NestHost,
+			AttributeType::NestMembers(_) => AttributeTag::NestMembers,
+			AttributeType::Record(_) => AttributeTag::Record,
+			AttributeType::PermittedSubclasses(_) => AttributeTag::PermittedSubclasses,
+		}
+	}
+}
+
 #[repr(transparent)]
 #[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct ConstantValue {
@@ -336,6 +421,12 @@ pub struct RuntimeInvisibleAnnotations {
 	pub annotations: Vec<Annotation>,
 }
 
+impl RuntimeInvisibleAnnotations {
+	pub fn as_bytes(&self) -> Box<[u1]> {
+		encode_annotations(self.annotations.as_slice())
+	}
+}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, PartialEq, Default)]
 // https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.18
@@ -343,51 +434,67 @@ pub struct ParameterAnnotations {
 	pub annotations: Box<[Box<[Annotation]>]>,
 }
 
-#[repr(transparent)]
-#[derive(Debug, Clone, PartialEq, Default)]
-pub struct RuntimeVisibleParameterAnnotations {
-	pub annotations: ParameterAnnotations,
-}
-
-impl RuntimeVisibleParameterAnnotations {
+impl ParameterAnnotations {
 	pub fn as_bytes(&self) -> Box<[u1]> {
-		let num_parameters = self.annotations.annotations.len() as u2;
-		let num_annotations: usize = self.annotations.annotations.iter().map(|a| a.len()).sum();
+		let num_parameters = self.annotations.len() as u1;
+		let num_annotations: usize = self.annotations.iter().map(|a| a.len()).sum();
 		let mut ret = Vec::with_capacity(
 			(num_parameters as usize) * (num_annotations * size_of::<Annotation>()),
 		);
-		ret.extend(num_parameters.to_be_bytes());
+		ret.push(num_parameters);
 
-		for annotation in &self.annotations.annotations {
+		for annotation in &self.annotations {
 			ret.extend(encode_annotations(annotation))
 		}
 		ret.into_boxed_slice()
 	}
 }
 
+#[repr(transparent)]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RuntimeVisibleParameterAnnotations {
+	pub annotations: ParameterAnnotations,
+}
+
+impl RuntimeVisibleParameterAnnotations {
+	pub fn as_bytes(&self) -> Box<[u1]> {
+		self.annotations.as_bytes()
+	}
+}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct RuntimeInvisibleParameterAnnotations {
 	pub annotations: ParameterAnnotations,
 }
 
+impl RuntimeInvisibleParameterAnnotations {
+	pub fn as_bytes(&self) -> Box<[u1]> {
+		self.annotations.as_bytes()
+	}
+}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct RuntimeVisibleTypeAnnotations {
 	pub annotations: Vec<TypeAnnotation>,
 }
 
-impl RuntimeVisibleTypeAnnotations {
-	pub fn as_bytes(&self) -> Box<[u1]> {
-		let num_annotations = self.annotations.len() as u2;
-		let mut ret = Vec::with_capacity((num_annotations as usize) * size_of::<Annotation>());
-		ret.extend(num_annotations.to_be_bytes());
+fn encode_type_annotations(annotations: &[TypeAnnotation]) -> Box<[u1]> {
+	let num_annotations = annotations.len() as u2;
+	let mut ret = Vec::with_capacity((num_annotations as usize) * size_of::<Annotation>());
+	ret.extend(num_annotations.to_be_bytes());
 
-		for annotation in &self.annotations {
-			ret.extend(annotation.as_bytes());
-		}
+	for annotation in annotations {
+		ret.extend(annotation.as_bytes());
+	}
 
-		ret.into_boxed_slice()
+	ret.into_boxed_slice()
+}
+
+impl RuntimeVisibleTypeAnnotations {
+	pub fn as_bytes(&self) -> Box<[u1]> {
+		encode_type_annotations(self.annotations.as_slice())
 	}
 }
 
@@ -397,6 +504,12 @@ pub struct RuntimeInvisibleTypeAnnotations {
 	pub annotations: Vec<TypeAnnotation>,
 }
 
+impl RuntimeInvisibleTypeAnnotations {
+	pub fn as_bytes(&self) -> Box<[u1]> {
+		encode_type_annotations(self.annotations.as_slice())
+	}
+}
+
 #[repr(transparent)]
 #[derive(Debug, Clone, PartialEq)]
 pub struct AnnotationDefault {
@@ -503,6 +616,8 @@ pub enum StackMapFrame {
 	},
 	ChopFrame {
 		offset_delta: u2,
+		/// The number of trailing locals that are absent (`251 - frame_type`)
+		absent_locals: u1,
 	},
 	SameFrameExtended {
 		offset_delta: u2,
@@ -1,6 +1,6 @@
 use super::{
-	Annotation, ElementValue, ElementValuePair, ElementValueTag, ElementValueType, EnclosingMethod,
-	InnerClass,
+	Annotation, Attribute, ElementValue, ElementValuePair, ElementValueTag, ElementValueType,
+	EnclosingMethod, InnerClass, RecordComponentInfo,
 };
 use crate::attribute::BootstrapMethod;
 use crate::constant_pool::ConstantPool;
@@ -272,3 +272,38 @@ impl<'a> ResolvedInnerClass<'a> {
 		})
 	}
 }
+
+pub struct ResolvedRecordComponent<'a> {
+	pub name: <types::raw::RawConstantUtf8 as types::CpEntry<'a>>::Entry,
+	pub descriptor: <types::raw::RawConstantUtf8 as types::CpEntry<'a>>::Entry,
+	pub signature: Option<<types::raw::RawConstantUtf8 as types::CpEntry<'a>>::Entry>,
+	pub attributes: &'a [Attribute],
+}
+
+impl<'a> ResolvedRecordComponent<'a> {
+	pub(crate) fn resolve_from(
+		raw: &'a RecordComponentInfo,
+		constant_pool: &'a ConstantPool,
+	) -> Result<Self, ConstantPoolEntryError> {
+		let name = constant_pool.get::<types::raw::RawConstantUtf8>(raw.name_index)?;
+		let descriptor = constant_pool.get::<types::raw::RawConstantUtf8>(raw.descriptor_index)?;
+
+		let signature;
+		match raw.attributes.iter().find_map(Attribute::signature) {
+			Some(signature_attr) => {
+				signature = Some(
+					constant_pool
+						.get::<types::raw::RawConstantUtf8>(signature_attr.signature_index)?,
+				);
+			},
+			None => signature = None,
+		}
+
+		Ok(Self {
+			name,
+			descriptor,
+			signature,
+			attributes: &raw.attributes,
+		})
+	}
+}
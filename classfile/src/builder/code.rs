@@ -0,0 +1,199 @@
+use super::ConstantPoolBuilder;
+use crate::attribute::{AttributeType, Code, CodeException};
+
+use common::int_types::{u1, u2, u4};
+use instructions::OpCode;
+
+/// A position in the code, which may be bound after it is referenced
+///
+/// Created by [`CodeBuilder::new_label()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Label(usize);
+
+/// A reference to a label that needs to be patched once all labels are bound
+struct Fixup {
+	/// The pc of the branch instruction, offsets are relative to it
+	instruction_pc: usize,
+	/// Where the offset is written in the code
+	position: usize,
+	label: Label,
+	/// Whether the offset is 4 bytes (`goto_w`, `jsr_w`) rather than 2
+	wide: bool,
+}
+
+/// A builder for a [`Code`] attribute
+///
+/// The `max_stack` and `max_locals` values are *not* computed, and must be provided.
+#[derive(Default)]
+pub struct CodeBuilder {
+	max_stack: u2,
+	max_locals: u2,
+	code: Vec<u1>,
+	labels: Vec<Option<usize>>,
+	fixups: Vec<Fixup>,
+	exception_handlers: Vec<(Label, Label, Label, u2)>,
+	attributes: Vec<AttributeType>,
+}
+
+impl CodeBuilder {
+	/// Create a new `CodeBuilder`
+	///
+	/// This is equivalent to [`Self::default`].
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the maximum depth of the operand stack
+	pub fn max_stack(&mut self, max_stack: u2) -> &mut Self {
+		self.max_stack = max_stack;
+		self
+	}
+
+	/// Set the number of local variables, including parameters
+	pub fn max_locals(&mut self, max_locals: u2) -> &mut Self {
+		self.max_locals = max_locals;
+		self
+	}
+
+	/// The pc of the next instruction
+	pub fn pc(&self) -> usize {
+		self.code.len()
+	}
+
+	/// Emit an opcode
+	///
+	/// Any operands need to be emitted separately, see [`Self::u1()`], [`Self::u2()`], and
+	/// [`Self::u4()`]. For branches, use [`Self::branch()`].
+	pub fn op(&mut self, opcode: OpCode) -> &mut Self {
+		let byte = opcode.as_u1().expect("opcode should be known");
+		self.code.push(byte);
+		self
+	}
+
+	pub fn u1(&mut self, value: u1) -> &mut Self {
+		self.code.push(value);
+		self
+	}
+
+	pub fn u2(&mut self, value: u2) -> &mut Self {
+		self.code.extend(value.to_be_bytes());
+		self
+	}
+
+	pub fn u4(&mut self, value: u4) -> &mut Self {
+		self.code.extend(value.to_be_bytes());
+		self
+	}
+
+	/// Create a new, unbound label
+	pub fn new_label(&mut self) -> Label {
+		self.labels.push(None);
+		Label(self.labels.len() - 1)
+	}
+
+	/// Bind `label` to the current pc
+	///
+	/// # Panics
+	///
+	/// This will panic if `label` is already bound.
+	pub fn bind(&mut self, label: Label) -> &mut Self {
+		let pc = self.pc();
+		let slot = &mut self.labels[label.0];
+		assert!(slot.is_none(), "label bound twice");
+		*slot = Some(pc);
+		self
+	}
+
+	/// Emit a branch instruction targeting `label`
+	///
+	/// The offset is patched once the builder is finished, so `label` may be bound later on.
+	pub fn branch(&mut self, opcode: OpCode, label: Label) -> &mut Self {
+		let instruction_pc = self.pc();
+		self.op(opcode);
+
+		let wide = matches!(opcode, OpCode::goto_w | OpCode::jsr_w);
+		self.fixups.push(Fixup {
+			instruction_pc,
+			position: self.pc(),
+			label,
+			wide,
+		});
+
+		if wide { self.u4(0) } else { self.u2(0) }
+	}
+
+	/// Add an exception handler, active for the code in `start..end`
+	///
+	/// A `catch_type` of 0 catches all exceptions.
+	pub fn exception_handler(
+		&mut self,
+		start: Label,
+		end: Label,
+		handler: Label,
+		catch_type: u2,
+	) -> &mut Self {
+		self.exception_handlers
+			.push((start, end, handler, catch_type));
+		self
+	}
+
+	/// Add an attribute to the code, such as a `LineNumberTable` or `StackMapTable`
+	pub fn attribute(&mut self, attribute: AttributeType) -> &mut Self {
+		self.attributes.push(attribute);
+		self
+	}
+
+	fn label_pc(&self, label: Label) -> usize {
+		self.labels[label.0].expect("label should be bound before finishing")
+	}
+
+	/// Construct the `Code` attribute, resolving all labels
+	///
+	/// The constant pool is needed for the names of any attributes.
+	///
+	/// # Panics
+	///
+	/// This will panic if any referenced label was never bound, or if a branch offset doesn't fit
+	/// in its instruction.
+	pub fn finish(mut self, constant_pool: &mut ConstantPoolBuilder) -> Code {
+		for fixup in &self.fixups {
+			let target = self.label_pc(fixup.label);
+			let offset = target as isize - fixup.instruction_pc as isize;
+
+			if fixup.wide {
+				let offset = i32::try_from(offset).expect("branch offset too large");
+				self.code[fixup.position..fixup.position + 4]
+					.copy_from_slice(&offset.to_be_bytes());
+			} else {
+				let offset = i16::try_from(offset).expect("branch offset too large");
+				self.code[fixup.position..fixup.position + 2]
+					.copy_from_slice(&offset.to_be_bytes());
+			}
+		}
+
+		let exception_table = self
+			.exception_handlers
+			.iter()
+			.map(|&(start, end, handler, catch_type)| CodeException {
+				start_pc: self.label_pc(start) as u2,
+				end_pc: self.label_pc(end) as u2,
+				handler_pc: self.label_pc(handler) as u2,
+				catch_type,
+			})
+			.collect();
+
+		let attributes = self
+			.attributes
+			.into_iter()
+			.map(|attribute| constant_pool.add_attribute(attribute))
+			.collect();
+
+		Code {
+			max_stack: self.max_stack,
+			max_locals: self.max_locals,
+			code: self.code.into_boxed_slice(),
+			exception_table,
+			attributes,
+		}
+	}
+}
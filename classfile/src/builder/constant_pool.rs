@@ -0,0 +1,308 @@
+use crate::attribute::{Attribute, AttributeType};
+use crate::constant_pool::types::ReferenceKind;
+use crate::constant_pool::{ConstantPool, ConstantPoolValueInfo};
+
+use std::collections::HashMap;
+
+use common::int_types::{s4, s8, u2};
+
+/// A builder for a [`ConstantPool`]
+///
+/// Identical entries are only ever added once, so adding an entry that already exists will return
+/// the index of the existing one.
+pub struct ConstantPoolBuilder {
+	entries: Vec<ConstantPoolValueInfo>,
+	indices: HashMap<ConstantPoolValueInfo, u2>,
+}
+
+impl Default for ConstantPoolBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl From<ConstantPool> for ConstantPoolBuilder {
+	/// Continue building on an existing constant pool, keeping all existing indices intact
+	fn from(constant_pool: ConstantPool) -> Self {
+		let entries = constant_pool.into_inner();
+
+		let mut indices = HashMap::with_capacity(entries.len());
+		for (index, entry) in entries.iter().enumerate() {
+			if matches!(entry, ConstantPoolValueInfo::Unusable) {
+				continue;
+			}
+
+			indices.entry(entry.clone()).or_insert(index as u2);
+		}
+
+		Self { entries, indices }
+	}
+}
+
+impl ConstantPoolBuilder {
+	/// Create an empty `ConstantPoolBuilder`
+	///
+	/// This is equivalent to [`Self::default`].
+	pub fn new() -> Self {
+		Self {
+			// The constant_pool table is indexed from 1 to constant_pool_count - 1
+			entries: vec![ConstantPoolValueInfo::Unusable],
+			indices: HashMap::new(),
+		}
+	}
+
+	/// The number of slots in the constant pool, including the unusable ones
+	///
+	/// This is the `constant_pool_count` of the resulting class file.
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether no entries have been added yet
+	pub fn is_empty(&self) -> bool {
+		self.entries.len() == 1
+	}
+
+	/// Add an entry, returning its index
+	///
+	/// # Panics
+	///
+	/// This will panic if `entry` is [`ConstantPoolValueInfo::Unusable`], or if the constant pool
+	/// has no slots left.
+	pub fn add(&mut self, entry: ConstantPoolValueInfo) -> u2 {
+		assert!(
+			!matches!(entry, ConstantPoolValueInfo::Unusable),
+			"unusable entries are inserted automatically"
+		);
+
+		if let Some(index) = self.indices.get(&entry) {
+			return *index;
+		}
+
+		// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.5:
+		// All 8-byte constants take up two entries in the constant_pool table of the class file.
+		let needs_unusable_entry = matches!(
+			entry,
+			ConstantPoolValueInfo::Long { .. } | ConstantPoolValueInfo::Double { .. }
+		);
+
+		let slots = if needs_unusable_entry { 2 } else { 1 };
+		assert!(
+			self.entries.len() + slots <= u2::MAX as usize,
+			"constant pool overflow"
+		);
+
+		let index = self.entries.len() as u2;
+		self.indices.insert(entry.clone(), index);
+		self.entries.push(entry);
+
+		if needs_unusable_entry {
+			self.entries.push(ConstantPoolValueInfo::Unusable);
+		}
+
+		index
+	}
+
+	/// Add a `CONSTANT_Utf8_info` entry, encoding `value` as modified UTF-8
+	pub fn add_utf8(&mut self, value: &str) -> u2 {
+		let bytes: Box<[u8]> = common::unicode::encode(value).into();
+		self.add(ConstantPoolValueInfo::Utf8 {
+			length: bytes.len() as u2,
+			bytes,
+		})
+	}
+
+	/// Add a `CONSTANT_Class_info` entry, `name` being in internal form (`java/lang/Object`)
+	pub fn add_class(&mut self, name: &str) -> u2 {
+		let name_index = self.add_utf8(name);
+		self.add(ConstantPoolValueInfo::Class { name_index })
+	}
+
+	pub fn add_string(&mut self, value: &str) -> u2 {
+		let string_index = self.add_utf8(value);
+		self.add(ConstantPoolValueInfo::String { string_index })
+	}
+
+	pub fn add_integer(&mut self, value: s4) -> u2 {
+		self.add(ConstantPoolValueInfo::Integer {
+			bytes: value as u32,
+		})
+	}
+
+	pub fn add_float(&mut self, value: f32) -> u2 {
+		self.add(ConstantPoolValueInfo::Float {
+			bytes: value.to_bits(),
+		})
+	}
+
+	pub fn add_long(&mut self, value: s8) -> u2 {
+		let value = value as u64;
+		self.add(ConstantPoolValueInfo::Long {
+			high_bytes: (value >> 32) as u32,
+			low_bytes: value as u32,
+		})
+	}
+
+	pub fn add_double(&mut self, value: f64) -> u2 {
+		let value = value.to_bits();
+		self.add(ConstantPoolValueInfo::Double {
+			high_bytes: (value >> 32) as u32,
+			low_bytes: value as u32,
+		})
+	}
+
+	pub fn add_name_and_type(&mut self, name: &str, descriptor: &str) -> u2 {
+		let name_index = self.add_utf8(name);
+		let descriptor_index = self.add_utf8(descriptor);
+		self.add(ConstantPoolValueInfo::NameAndType {
+			name_index,
+			descriptor_index,
+		})
+	}
+
+	pub fn add_field_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u2 {
+		let class_index = self.add_class(class);
+		let name_and_type_index = self.add_name_and_type(name, descriptor);
+		self.add(ConstantPoolValueInfo::Fieldref {
+			class_index,
+			name_and_type_index,
+		})
+	}
+
+	pub fn add_method_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u2 {
+		let class_index = self.add_class(class);
+		let name_and_type_index = self.add_name_and_type(name, descriptor);
+		self.add(ConstantPoolValueInfo::Methodref {
+			class_index,
+			name_and_type_index,
+		})
+	}
+
+	pub fn add_interface_method_ref(&mut self, class: &str, name: &str, descriptor: &str) -> u2 {
+		let class_index = self.add_class(class);
+		let name_and_type_index = self.add_name_and_type(name, descriptor);
+		self.add(ConstantPoolValueInfo::InterfaceMethodref {
+			class_index,
+			name_and_type_index,
+		})
+	}
+
+	/// Add a `CONSTANT_MethodHandle_info` entry
+	///
+	/// `reference_index` is the index of the field or method reference, see [`Self::add_field_ref()`],
+	/// [`Self::add_method_ref()`], and [`Self::add_interface_method_ref()`].
+	pub fn add_method_handle(&mut self, reference_kind: ReferenceKind, reference_index: u2) -> u2 {
+		self.add(ConstantPoolValueInfo::MethodHandle {
+			reference_kind: reference_kind as u8,
+			reference_index,
+		})
+	}
+
+	pub fn add_method_type(&mut self, descriptor: &str) -> u2 {
+		let descriptor_index = self.add_utf8(descriptor);
+		self.add(ConstantPoolValueInfo::MethodType { descriptor_index })
+	}
+
+	/// Add a `CONSTANT_Dynamic_info` entry
+	///
+	/// `bootstrap_method_attr_index` is an index into the `BootstrapMethods` attribute of the class.
+	pub fn add_dynamic(
+		&mut self,
+		bootstrap_method_attr_index: u2,
+		name: &str,
+		descriptor: &str,
+	) -> u2 {
+		let name_and_type_index = self.add_name_and_type(name, descriptor);
+		self.add(ConstantPoolValueInfo::Dynamic {
+			bootstrap_method_attr_index,
+			name_and_type_index,
+		})
+	}
+
+	/// Add a `CONSTANT_InvokeDynamic_info` entry
+	///
+	/// `bootstrap_method_attr_index` is an index into the `BootstrapMethods` attribute of the class.
+	pub fn add_invoke_dynamic(
+		&mut self,
+		bootstrap_method_attr_index: u2,
+		name: &str,
+		descriptor: &str,
+	) -> u2 {
+		let name_and_type_index = self.add_name_and_type(name, descriptor);
+		self.add(ConstantPoolValueInfo::InvokeDynamic {
+			bootstrap_method_attr_index,
+			name_and_type_index,
+		})
+	}
+
+	pub fn add_module(&mut self, name: &str) -> u2 {
+		let name_index = self.add_utf8(name);
+		self.add(ConstantPoolValueInfo::Module { name_index })
+	}
+
+	/// Add a `CONSTANT_Package_info` entry, `name` being in internal form (`java/lang`)
+	pub fn add_package(&mut self, name: &str) -> u2 {
+		let name_index = self.add_utf8(name);
+		self.add(ConstantPoolValueInfo::Package { name_index })
+	}
+
+	/// Create an [`Attribute`], adding its name to the constant pool
+	pub fn add_attribute(&mut self, info: AttributeType) -> Attribute {
+		Attribute {
+			attribute_name_index: self.add_utf8(info.tag().name()),
+			info,
+		}
+	}
+
+	/// Construct the `ConstantPool`
+	pub fn finish(self) -> ConstantPool {
+		let mut constant_pool = ConstantPool::with_capacity(self.entries.len());
+		for entry in self.entries {
+			constant_pool.push(entry);
+		}
+
+		constant_pool
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ConstantPoolBuilder;
+	use crate::constant_pool::ConstantPoolValueInfo;
+
+	#[test]
+	fn entries_are_deduplicated() {
+		let mut builder = ConstantPoolBuilder::new();
+
+		let object = builder.add_class("java/lang/Object");
+		let method = builder.add_method_ref("java/lang/Object", "<init>", "()V");
+		assert_eq!(builder.add_class("java/lang/Object"), object);
+		assert_eq!(
+			builder.add_method_ref("java/lang/Object", "<init>", "()V"),
+			method
+		);
+
+		// Utf8 (name), Class, Utf8 (<init>), Utf8 (()V), NameAndType, Methodref
+		assert_eq!(builder.len(), 7);
+	}
+
+	#[test]
+	fn wide_entries_take_two_slots() {
+		let mut builder = ConstantPoolBuilder::new();
+
+		let long = builder.add_long(-1);
+		let double = builder.add_double(1.5);
+		let int = builder.add_integer(1);
+		assert_eq!((long, double, int), (1, 3, 5));
+
+		let constant_pool = builder.finish();
+		assert!(matches!(
+			constant_pool[2_u16],
+			ConstantPoolValueInfo::Unusable
+		));
+		assert!(matches!(
+			constant_pool[4_u16],
+			ConstantPoolValueInfo::Unusable
+		));
+	}
+}
@@ -0,0 +1,218 @@
+//! Builders for assembling class files from scratch
+//!
+//! See [`ClassFileBuilder`].
+
+mod code;
+mod constant_pool;
+
+pub use code::{CodeBuilder, Label};
+pub use constant_pool::ConstantPoolBuilder;
+
+use crate::accessflags::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use crate::attribute::AttributeType;
+use crate::classfile::ClassFile;
+use crate::fieldinfo::FieldInfo;
+use crate::methodinfo::MethodInfo;
+
+use common::int_types::u2;
+
+/// A builder for a [`ClassFile`]
+///
+/// The constant pool is built alongside the class, see [`Self::constant_pool()`].
+pub struct ClassFileBuilder {
+	minor_version: u2,
+	major_version: u2,
+	constant_pool: ConstantPoolBuilder,
+	access_flags: ClassAccessFlags,
+	this_class: u2,
+	super_class: u2,
+	interfaces: Vec<u2>,
+	fields: Vec<FieldInfo>,
+	methods: Vec<MethodInfo>,
+	attributes: Vec<crate::attribute::Attribute>,
+}
+
+impl ClassFileBuilder {
+	/// The class file version used unless otherwise specified, matching Java SE 21
+	pub const DEFAULT_MAJOR_VERSION: u2 = 65;
+
+	/// Create a new `ClassFileBuilder`
+	///
+	/// `name` and `super_name` are in internal form (`java/lang/Object`). Only `java/lang/Object`
+	/// and `module-info` have no superclass.
+	pub fn new(name: &str, super_name: Option<&str>, access_flags: ClassAccessFlags) -> Self {
+		let mut constant_pool = ConstantPoolBuilder::new();
+		let this_class = constant_pool.add_class(name);
+		let super_class = super_name.map_or(0, |super_name| constant_pool.add_class(super_name));
+
+		Self {
+			minor_version: 0,
+			major_version: Self::DEFAULT_MAJOR_VERSION,
+			constant_pool,
+			access_flags,
+			this_class,
+			super_class,
+			interfaces: Vec::new(),
+			fields: Vec::new(),
+			methods: Vec::new(),
+			attributes: Vec::new(),
+		}
+	}
+
+	/// Set the class file version
+	pub fn version(&mut self, major_version: u2, minor_version: u2) -> &mut Self {
+		self.major_version = major_version;
+		self.minor_version = minor_version;
+		self
+	}
+
+	/// The constant pool of the class, for any entries referenced by its members
+	pub fn constant_pool(&mut self) -> &mut ConstantPoolBuilder {
+		&mut self.constant_pool
+	}
+
+	/// Add a direct superinterface, `name` being in internal form
+	pub fn add_interface(&mut self, name: &str) -> &mut Self {
+		let index = self.constant_pool.add_class(name);
+		self.interfaces.push(index);
+		self
+	}
+
+	pub fn add_field(
+		&mut self,
+		access_flags: FieldAccessFlags,
+		name: &str,
+		descriptor: &str,
+		attributes: impl IntoIterator<Item = AttributeType>,
+	) -> &mut Self {
+		let name_index = self.constant_pool.add_utf8(name);
+		let descriptor_index = self.constant_pool.add_utf8(descriptor);
+		let attributes = attributes
+			.into_iter()
+			.map(|attribute| self.constant_pool.add_attribute(attribute))
+			.collect();
+
+		self.fields.push(FieldInfo {
+			access_flags,
+			name_index,
+			descriptor_index,
+			attributes,
+		});
+		self
+	}
+
+	/// Add a method
+	///
+	/// For methods with a body, the attributes should include a `Code` attribute, see
+	/// [`CodeBuilder`].
+	pub fn add_method(
+		&mut self,
+		access_flags: MethodAccessFlags,
+		name: &str,
+		descriptor: &str,
+		attributes: impl IntoIterator<Item = AttributeType>,
+	) -> &mut Self {
+		let name_index = self.constant_pool.add_utf8(name);
+		let descriptor_index = self.constant_pool.add_utf8(descriptor);
+		let attributes = attributes
+			.into_iter()
+			.map(|attribute| self.constant_pool.add_attribute(attribute))
+			.collect();
+
+		self.methods.push(MethodInfo {
+			access_flags,
+			name_index,
+			descriptor_index,
+			attributes,
+		});
+		self
+	}
+
+	/// Add a class attribute, such as `SourceFile` or `InnerClasses`
+	pub fn add_attribute(&mut self, attribute: AttributeType) -> &mut Self {
+		let attribute = self.constant_pool.add_attribute(attribute);
+		self.attributes.push(attribute);
+		self
+	}
+
+	/// Construct the `ClassFile`
+	pub fn finish(self) -> ClassFile {
+		ClassFile {
+			minor_version: self.minor_version,
+			major_version: self.major_version,
+			constant_pool: self.constant_pool.finish(),
+			access_flags: self.access_flags,
+			this_class: self.this_class,
+			super_class: self.super_class,
+			interfaces: self.interfaces,
+			fields: self.fields,
+			methods: self.methods,
+			attributes: self.attributes,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{ClassFileBuilder, CodeBuilder};
+	use crate::ClassFile;
+	use crate::accessflags::{ClassAccessFlags, MethodAccessFlags};
+	use crate::attribute::AttributeType;
+
+	use instructions::OpCode;
+
+	fn abs_code(builder: &mut ClassFileBuilder) -> AttributeType {
+		let mut code = CodeBuilder::new();
+		let positive = code.new_label();
+		code.max_stack(1)
+			.max_locals(1)
+			.op(OpCode::iload_0)
+			.branch(OpCode::ifge, positive)
+			.op(OpCode::iload_0)
+			.op(OpCode::ineg)
+			.op(OpCode::ireturn)
+			.bind(positive)
+			.op(OpCode::iload_0)
+			.op(OpCode::ireturn);
+
+		AttributeType::Code(code.finish(builder.constant_pool()))
+	}
+
+	#[test]
+	fn branch_offsets_are_resolved() {
+		let mut builder = ClassFileBuilder::new(
+			"Abs",
+			Some("java/lang/Object"),
+			ClassAccessFlags::ACC_PUBLIC,
+		);
+		let AttributeType::Code(code) = abs_code(&mut builder) else {
+			unreachable!()
+		};
+
+		// ifge at pc 1, the label is bound at pc 7
+		assert_eq!(&code.code[1..4], &[0x9C, 0x00, 0x06]);
+	}
+
+	#[test]
+	fn built_class_round_trips() {
+		let mut builder = ClassFileBuilder::new(
+			"Abs",
+			Some("java/lang/Object"),
+			ClassAccessFlags::ACC_PUBLIC | ClassAccessFlags::ACC_SUPER,
+		);
+		builder.add_interface("java/io/Serializable");
+
+		let code = abs_code(&mut builder);
+		builder.add_method(
+			MethodAccessFlags::ACC_PUBLIC | MethodAccessFlags::ACC_STATIC,
+			"abs",
+			"(I)I",
+			[code],
+		);
+
+		let class = builder.finish();
+		let bytes = class.to_bytes();
+		let reparsed = ClassFile::read_from(&mut &bytes[..]).unwrap();
+		assert_eq!(class, reparsed);
+	}
+}
@@ -1,6 +1,6 @@
 use crate::accessflags::ClassAccessFlags;
 use crate::attribute::resolved::{
-	ResolvedBootstrapMethod, ResolvedEnclosingMethod, ResolvedInnerClass,
+	ResolvedBootstrapMethod, ResolvedEnclosingMethod, ResolvedInnerClass, ResolvedRecordComponent,
 };
 use crate::attribute::{Attribute, AttributeType, SourceFile};
 use crate::constant_pool::types::{ClassNameEntry, ConstantPoolEntryError, raw as raw_types};
@@ -10,7 +10,7 @@ use crate::methodinfo::MethodInfo;
 use crate::parse::error::Result;
 
 use std::borrow::Cow;
-use std::io::Read;
+use std::io::{Read, Write};
 
 use common::int_types::{u1, u2};
 
@@ -34,6 +34,25 @@ impl ClassFile {
 		crate::parse::parse_class(reader)
 	}
 
+	/// Perform the format checks (§4.8) on this class file
+	///
+	/// See [`crate::format_check`].
+	pub fn check_format(&self) -> crate::format_check::Result<()> {
+		crate::format_check::check(self)
+	}
+
+	pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+		crate::write::write_class(writer, self)
+	}
+
+	/// Serialize the class file
+	pub fn to_bytes(&self) -> Vec<u1> {
+		let mut bytes = Vec::new();
+		self.write_to(&mut bytes)
+			.expect("writing to a Vec should never fail");
+		bytes
+	}
+
 	pub fn super_class(
 		&self,
 	) -> std::result::Result<Option<Cow<'_, [u1]>>, ConstantPoolEntryError> {
@@ -148,6 +167,66 @@ impl ClassFile {
 		None
 	}
 
+	pub fn permitted_subclasses(
+		&self,
+	) -> Option<
+		impl Iterator<Item = std::result::Result<ClassNameEntry<'_>, ConstantPoolEntryError>> + use<'_>,
+	> {
+		for attr in &self.attributes {
+			let Some(permitted_subclasses) = attr.permitted_subclasses() else {
+				continue;
+			};
+
+			let iter = permitted_subclasses
+				.classes
+				.iter()
+				.map(|index| self.constant_pool.get::<raw_types::RawClassName>(*index));
+
+			return Some(iter);
+		}
+
+		None
+	}
+
+	pub fn record_components(
+		&self,
+	) -> Option<
+		impl ExactSizeIterator<
+			Item = std::result::Result<ResolvedRecordComponent<'_>, ConstantPoolEntryError>,
+		> + use<'_>,
+	> {
+		for attr in &self.attributes {
+			let Some(record) = attr.record() else {
+				continue;
+			};
+
+			let iter = record.components.iter().map(move |component| {
+				ResolvedRecordComponent::resolve_from(component, &self.constant_pool)
+			});
+
+			return Some(iter);
+		}
+
+		None
+	}
+
+	pub fn generic_signature(
+		&self,
+	) -> std::result::Result<Option<Cow<'_, [u1]>>, ConstantPoolEntryError> {
+		for attr in &self.attributes {
+			let Some(signature) = attr.signature() else {
+				continue;
+			};
+
+			return Ok(Some(
+				self.constant_pool
+					.get::<raw_types::RawConstantUtf8>(signature.signature_index)?,
+			));
+		}
+
+		Ok(None)
+	}
+
 	pub fn bootstrap_methods(
 		&self,
 	) -> Option<
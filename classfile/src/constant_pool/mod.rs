@@ -156,7 +156,7 @@ impl Deref for ConstantPool {
 
 // https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4-140
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[rustfmt::skip]
 pub enum ConstantPoolTag {
 	Unusable, /* Used when storing longs/doubles (https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.5) */
@@ -197,6 +197,7 @@ impl TryFrom<u1> for ConstantPoolTag {
 			12 => Ok(ConstantPoolTag::NameAndType),
 			15 => Ok(ConstantPoolTag::MethodHandle),
 			16 => Ok(ConstantPoolTag::MethodType),
+			17 => Ok(ConstantPoolTag::Dynamic),
 			18 => Ok(ConstantPoolTag::InvokeDynamic),
 			19 => Ok(ConstantPoolTag::Module),
 			20 => Ok(ConstantPoolTag::Package),
@@ -205,7 +206,7 @@ impl TryFrom<u1> for ConstantPoolTag {
 	}
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ConstantPoolValueInfo {
 	Unusable,
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.1
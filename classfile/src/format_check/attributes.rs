@@ -0,0 +1,587 @@
+use super::error::{FormatError, Result};
+use super::{Checker, names};
+use crate::attribute::{
+	Annotation, Attribute, AttributeTag, AttributeType, Code, ElementValue, ElementValueType,
+	StackMapFrame, VerificationTypeInfo,
+};
+use crate::constant_pool::ConstantPoolTag;
+
+use std::collections::HashSet;
+
+use common::int_types::u2;
+
+/// The maximum length of the `code` array (§4.7.3)
+const MAX_CODE_LENGTH: usize = 65535;
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7-320
+/// The first class file version in which `tag` is a predefined attribute (Table 4.7-A)
+///
+/// Attributes appearing in an older class file are not predefined, and must be silently ignored.
+fn first_major_version(tag: AttributeTag) -> u2 {
+	match tag {
+		AttributeTag::ConstantValue
+		| AttributeTag::Code
+		| AttributeTag::Exceptions
+		| AttributeTag::InnerClasses
+		| AttributeTag::Synthetic
+		| AttributeTag::SourceFile
+		| AttributeTag::LineNumberTable
+		| AttributeTag::LocalVariableTable
+		| AttributeTag::Deprecated => 45,
+		AttributeTag::EnclosingMethod
+		| AttributeTag::Signature
+		| AttributeTag::SourceDebugExtension
+		| AttributeTag::LocalVariableTypeTable
+		| AttributeTag::RuntimeVisibleAnnotations
+		| AttributeTag::RuntimeInvisibleAnnotations
+		| AttributeTag::RuntimeVisibleParameterAnnotations
+		| AttributeTag::RuntimeInvisibleParameterAnnotations
+		| AttributeTag::AnnotationDefault => 49,
+		AttributeTag::StackMapTable => 50,
+		AttributeTag::BootstrapMethods => 51,
+		AttributeTag::RuntimeVisibleTypeAnnotations
+		| AttributeTag::RuntimeInvisibleTypeAnnotations
+		| AttributeTag::MethodParameters => 52,
+		AttributeTag::Module | AttributeTag::ModulePackages | AttributeTag::ModuleMainClass => 53,
+		AttributeTag::NestHost | AttributeTag::NestMembers => 55,
+		AttributeTag::Record => 60,
+		AttributeTag::PermittedSubclasses => 61,
+	}
+}
+
+/// Whether there may be more than one `tag` attribute in a single `attributes` table
+fn may_repeat(tag: AttributeTag) -> bool {
+	matches!(
+		tag,
+		AttributeTag::LineNumberTable
+			| AttributeTag::LocalVariableTable
+			| AttributeTag::LocalVariableTypeTable
+			| AttributeTag::Synthetic
+			| AttributeTag::Deprecated
+	)
+}
+
+/// The attributes that may appear in a module descriptor (§4.1)
+fn allowed_in_module(tag: AttributeTag) -> bool {
+	matches!(
+		tag,
+		AttributeTag::Module
+			| AttributeTag::ModulePackages
+			| AttributeTag::ModuleMainClass
+			| AttributeTag::InnerClasses
+			| AttributeTag::SourceFile
+			| AttributeTag::SourceDebugExtension
+			| AttributeTag::RuntimeVisibleAnnotations
+			| AttributeTag::RuntimeInvisibleAnnotations
+	)
+}
+
+impl Checker<'_> {
+	fn is_predefined(&self, attribute: &Attribute) -> bool {
+		self.major_version >= first_major_version(attribute.info.tag())
+	}
+
+	/// Check the attributes shared between all locations, and that no attribute is repeated
+	///
+	/// Attributes that depend on their location (`Code`, `ConstantValue` and the attributes of
+	/// `Code`) are left up to the caller, only their counts are checked here.
+	pub(super) fn check_attributes(
+		&self,
+		attributes: &[Attribute],
+		location: &'static str,
+	) -> Result<()> {
+		let mut seen = HashSet::new();
+		for attribute in attributes.iter().filter(|attr| self.is_predefined(attr)) {
+			let tag = attribute.info.tag();
+			if !seen.insert(tag) && !may_repeat(tag) {
+				return Err(FormatError::DuplicateAttribute { tag, location });
+			}
+
+			self.check_attribute(attribute)?;
+		}
+
+		Ok(())
+	}
+
+	#[allow(clippy::match_same_arms)]
+	fn check_attribute(&self, attribute: &Attribute) -> Result<()> {
+		match &attribute.info {
+			// Checked by the caller
+			AttributeType::ConstantValue(_)
+			| AttributeType::Code(_)
+			| AttributeType::StackMapTable(_)
+			| AttributeType::LineNumberTable(_)
+			| AttributeType::LocalVariableTable(_)
+			| AttributeType::LocalVariableTypeTable(_) => {},
+			// Nothing to check
+			AttributeType::Synthetic
+			| AttributeType::Deprecated
+			| AttributeType::SourceDebugExtension(_) => {},
+			AttributeType::Exceptions(exceptions) => {
+				for &index in &exceptions.exception_index_table {
+					self.expect(index, ConstantPoolTag::Class)?;
+				}
+			},
+			AttributeType::InnerClasses(inner_classes) => {
+				for class in &inner_classes.classes {
+					self.expect(class.inner_class_info_index, ConstantPoolTag::Class)?;
+					self.expect_optional(class.outer_class_info_index, ConstantPoolTag::Class)?;
+					self.expect_optional(class.inner_name_index, ConstantPoolTag::Utf8)?;
+
+					if class.inner_class_info_index == class.outer_class_info_index {
+						return Err(FormatError::InvalidAttribute {
+							tag: AttributeTag::InnerClasses,
+							reason: "class is both outer and inner class",
+						});
+					}
+				}
+			},
+			AttributeType::EnclosingMethod(enclosing_method) => {
+				self.expect(enclosing_method.class_index, ConstantPoolTag::Class)?;
+				self.expect_optional(enclosing_method.method_index, ConstantPoolTag::NameAndType)?;
+			},
+			AttributeType::Signature(signature) => {
+				self.utf8(signature.signature_index)?;
+			},
+			AttributeType::SourceFile(source_file) => {
+				self.utf8(source_file.sourcefile_index)?;
+			},
+			AttributeType::RuntimeVisibleAnnotations(annotations) => {
+				self.check_annotations(&annotations.annotations)?;
+			},
+			AttributeType::RuntimeInvisibleAnnotations(annotations) => {
+				self.check_annotations(&annotations.annotations)?;
+			},
+			AttributeType::RuntimeVisibleParameterAnnotations(annotations) => {
+				for parameter in &annotations.annotations.annotations {
+					self.check_annotations(parameter)?;
+				}
+			},
+			AttributeType::RuntimeInvisibleParameterAnnotations(annotations) => {
+				for parameter in &annotations.annotations.annotations {
+					self.check_annotations(parameter)?;
+				}
+			},
+			AttributeType::RuntimeVisibleTypeAnnotations(annotations) => {
+				for type_annotation in &annotations.annotations {
+					self.check_annotation(&type_annotation.annotation)?;
+				}
+			},
+			AttributeType::RuntimeInvisibleTypeAnnotations(annotations) => {
+				for type_annotation in &annotations.annotations {
+					self.check_annotation(&type_annotation.annotation)?;
+				}
+			},
+			AttributeType::AnnotationDefault(annotation_default) => {
+				self.check_element_value(&annotation_default.default_value)?;
+			},
+			AttributeType::BootstrapMethods(bootstrap_methods) => {
+				for bootstrap_method in &bootstrap_methods.bootstrap_methods {
+					self.expect(
+						bootstrap_method.bootstrap_method_ref,
+						ConstantPoolTag::MethodHandle,
+					)?;
+
+					for &argument in &bootstrap_method.bootstrap_arguments {
+						let tag = self.entry(argument)?.tag();
+						if !is_loadable(tag) {
+							return Err(FormatError::UnexpectedConstantPoolTag {
+								index: argument,
+								expected: "loadable constant",
+								found: tag,
+							});
+						}
+					}
+				}
+			},
+			AttributeType::MethodParameters(method_parameters) => {
+				for parameter in &method_parameters.parameters {
+					if parameter.name_index == 0 {
+						continue;
+					}
+
+					let name = self.utf8(parameter.name_index)?;
+					if !names::is_unqualified_name(&name) {
+						return Err(FormatError::IllegalName {
+							kind: "parameter name",
+							name: name.into_owned(),
+						});
+					}
+				}
+			},
+			// Only meaningful in a module descriptor, see `check_module_attributes()`
+			AttributeType::Module(_)
+			| AttributeType::ModulePackages(_)
+			| AttributeType::ModuleMainClass(_) => {},
+			AttributeType::NestHost(nest_host) => {
+				self.expect(nest_host.host_class_index, ConstantPoolTag::Class)?;
+			},
+			AttributeType::NestMembers(nest_members) => {
+				for &index in &nest_members.classes {
+					self.expect(index, ConstantPoolTag::Class)?;
+				}
+			},
+			AttributeType::Record(record) => {
+				for component in &record.components {
+					let name = self.utf8(component.name_index)?;
+					let descriptor = self.utf8(component.descriptor_index)?;
+
+					if !names::is_unqualified_name(&name) {
+						return Err(FormatError::IllegalName {
+							kind: "record component name",
+							name: name.into_owned(),
+						});
+					}
+
+					if !names::is_field_descriptor(&descriptor) {
+						return Err(FormatError::IllegalFieldDescriptor {
+							name: name.into_owned(),
+							descriptor: descriptor.into_owned(),
+						});
+					}
+
+					self.check_attributes(&component.attributes, "record component")?;
+				}
+			},
+			AttributeType::PermittedSubclasses(permitted_subclasses) => {
+				for &index in &permitted_subclasses.classes {
+					self.expect(index, ConstantPoolTag::Class)?;
+				}
+			},
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.16
+	fn check_annotations(&self, annotations: &[Annotation]) -> Result<()> {
+		for annotation in annotations {
+			self.check_annotation(annotation)?;
+		}
+
+		Ok(())
+	}
+
+	fn check_annotation(&self, annotation: &Annotation) -> Result<()> {
+		let type_name = self.utf8(annotation.type_index)?;
+		if !names::is_field_descriptor(&type_name) {
+			return Err(FormatError::IllegalName {
+				kind: "annotation type",
+				name: type_name.into_owned(),
+			});
+		}
+
+		for pair in &annotation.element_value_pairs {
+			self.utf8(pair.element_name_index)?;
+			self.check_element_value(&pair.value)?;
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.16.1
+	fn check_element_value(&self, value: &ElementValue) -> Result<()> {
+		let (index, tag) = match &value.ty {
+			ElementValueType::Byte { const_value_index }
+			| ElementValueType::Char { const_value_index }
+			| ElementValueType::Int { const_value_index }
+			| ElementValueType::Short { const_value_index }
+			| ElementValueType::Boolean { const_value_index } => {
+				(*const_value_index, ConstantPoolTag::Integer)
+			},
+			ElementValueType::Double { const_value_index } => {
+				(*const_value_index, ConstantPoolTag::Double)
+			},
+			ElementValueType::Float { const_value_index } => {
+				(*const_value_index, ConstantPoolTag::Float)
+			},
+			ElementValueType::Long { const_value_index } => {
+				(*const_value_index, ConstantPoolTag::Long)
+			},
+			ElementValueType::String { const_value_index } => {
+				(*const_value_index, ConstantPoolTag::Utf8)
+			},
+			ElementValueType::Enum {
+				type_name_index,
+				const_value_index,
+			} => {
+				self.utf8(*type_name_index)?;
+				(*const_value_index, ConstantPoolTag::Utf8)
+			},
+			ElementValueType::Class { class_info_index } => {
+				(*class_info_index, ConstantPoolTag::Utf8)
+			},
+			ElementValueType::Annotation { annotation } => {
+				return self.check_annotation(annotation);
+			},
+			ElementValueType::Array { values } => {
+				for value in values {
+					self.check_element_value(value)?;
+				}
+
+				return Ok(());
+			},
+		};
+
+		self.expect(index, tag)?;
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.3
+	pub(super) fn check_code(&self, code: &Code, method: &str) -> Result<()> {
+		let invalid = |reason| FormatError::InvalidCode {
+			method: method.to_owned(),
+			reason,
+		};
+
+		let code_length = code.code.len();
+		if code_length == 0 || code_length > MAX_CODE_LENGTH {
+			return Err(invalid("code length must be between 1 and 65535"));
+		}
+
+		for handler in &code.exception_table {
+			let (start_pc, end_pc, handler_pc) = (
+				handler.start_pc as usize,
+				handler.end_pc as usize,
+				handler.handler_pc as usize,
+			);
+
+			if start_pc >= end_pc || end_pc > code_length || handler_pc >= code_length {
+				return Err(invalid("exception handler out of bounds"));
+			}
+
+			self.expect_optional(handler.catch_type, ConstantPoolTag::Class)?;
+		}
+
+		self.check_attributes(&code.attributes, "Code")?;
+
+		for attribute in code
+			.attributes
+			.iter()
+			.filter(|attr| self.is_predefined(attr))
+		{
+			self.check_code_attribute(code, attribute)?;
+		}
+
+		Ok(())
+	}
+
+	fn check_code_attribute(&self, code: &Code, attribute: &Attribute) -> Result<()> {
+		let code_length = code.code.len();
+		let tag = attribute.info.tag();
+		let invalid = |reason| FormatError::InvalidAttribute { tag, reason };
+
+		// A local variable is live from `start_pc` to `start_pc + length`, and both must be within
+		// the code array (or just past its end, for the latter).
+		let check_local = |start_pc: u2, length: u2, index: u2, size: u2| {
+			let start_pc = start_pc as usize;
+			if start_pc >= code_length || start_pc + length as usize > code_length {
+				return Err(invalid("local variable range out of bounds"));
+			}
+
+			if u32::from(index) + u32::from(size) > u32::from(code.max_locals) {
+				return Err(invalid("local variable index out of bounds"));
+			}
+
+			Ok(())
+		};
+
+		match &attribute.info {
+			AttributeType::LineNumberTable(table) => {
+				if table
+					.line_number_table
+					.iter()
+					.any(|entry| entry.start_pc as usize >= code_length)
+				{
+					return Err(invalid("start_pc out of bounds"));
+				}
+			},
+			AttributeType::LocalVariableTable(table) => {
+				for local in &table.local_variable_table {
+					let name = self.utf8(local.name_index)?;
+					let descriptor = self.utf8(local.descriptor_index)?;
+
+					if !names::is_unqualified_name(&name) {
+						return Err(FormatError::IllegalName {
+							kind: "local variable name",
+							name: name.into_owned(),
+						});
+					}
+
+					if !names::is_field_descriptor(&descriptor) {
+						return Err(FormatError::IllegalFieldDescriptor {
+							name: name.into_owned(),
+							descriptor: descriptor.into_owned(),
+						});
+					}
+
+					let size = if matches!(&*descriptor, "J" | "D") {
+						2
+					} else {
+						1
+					};
+					check_local(local.start_pc, local.length, local.index, size)?;
+				}
+			},
+			AttributeType::LocalVariableTypeTable(table) => {
+				for local in &table.local_variable_type_table {
+					self.utf8(local.name_index)?;
+					self.utf8(local.signature_index)?;
+
+					// Only reference types can have a signature
+					check_local(local.start_pc, local.length, local.index, 1)?;
+				}
+			},
+			AttributeType::StackMapTable(table) => {
+				for frame in &table.entries {
+					let types: &[VerificationTypeInfo] = match frame {
+						StackMapFrame::SameLocals1StackItemFrame {
+							verification_type_info,
+							..
+						}
+						| StackMapFrame::SameLocals1StackItemFrameExtended {
+							verification_type_info,
+							..
+						} => verification_type_info,
+						StackMapFrame::AppendFrame { locals, .. } => locals,
+						StackMapFrame::FullFrame { locals, stack, .. } => {
+							self.check_verification_types(stack)?;
+							locals
+						},
+						_ => &[],
+					};
+
+					self.check_verification_types(types)?;
+				}
+			},
+			_ => {},
+		}
+
+		Ok(())
+	}
+
+	fn check_verification_types(&self, types: &[VerificationTypeInfo]) -> Result<()> {
+		for ty in types {
+			if let VerificationTypeInfo::ObjectVariableInfo { cpool_index } = ty {
+				self.expect(*cpool_index, ConstantPoolTag::Class)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	pub(super) fn check_class_attributes(&self) -> Result<()> {
+		let attributes = &self.class.attributes;
+		self.check_attributes(attributes, "class")?;
+
+		if self.is_module {
+			return self.check_module_attributes();
+		}
+
+		let has = |tag| {
+			attributes
+				.iter()
+				.any(|attr| attr.info.tag() == tag && self.is_predefined(attr))
+		};
+
+		if has(AttributeTag::NestHost) && has(AttributeTag::NestMembers) {
+			return Err(FormatError::ConflictingAttributes(
+				AttributeTag::NestHost,
+				AttributeTag::NestMembers,
+			));
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.25
+	fn check_module_attributes(&self) -> Result<()> {
+		let mut module = None;
+		for attribute in &self.class.attributes {
+			let tag = attribute.info.tag();
+			if !allowed_in_module(tag) {
+				return Err(FormatError::InvalidAttribute {
+					tag,
+					reason: "not permitted in a module descriptor",
+				});
+			}
+
+			match &attribute.info {
+				AttributeType::Module(attribute) => module = Some(attribute),
+				AttributeType::ModulePackages(packages) => {
+					for &index in &packages.package_index {
+						self.expect(index, ConstantPoolTag::Package)?;
+					}
+				},
+				AttributeType::ModuleMainClass(main_class) => {
+					self.expect(main_class.main_class_index, ConstantPoolTag::Class)?;
+				},
+				_ => {},
+			}
+		}
+
+		let Some(module) = module else {
+			return Err(FormatError::IllegalModule("missing Module attribute"));
+		};
+
+		self.expect(module.module_name_index, ConstantPoolTag::Module)?;
+		self.expect_optional(module.module_version_index, ConstantPoolTag::Utf8)?;
+
+		for requires in &module.requires {
+			self.expect(requires.requires_index, ConstantPoolTag::Module)?;
+			self.expect_optional(requires.requires_version_index, ConstantPoolTag::Utf8)?;
+		}
+
+		for (package, targets) in module
+			.exports
+			.iter()
+			.map(|exports| (exports.exports_index, &exports.exports_to_index))
+			.chain(
+				module
+					.opens
+					.iter()
+					.map(|opens| (opens.opens_index, &opens.opens_to_index)),
+			) {
+			self.expect(package, ConstantPoolTag::Package)?;
+			for &target in targets {
+				self.expect(target, ConstantPoolTag::Module)?;
+			}
+		}
+
+		for &uses in &module.uses_index {
+			self.expect(uses, ConstantPoolTag::Class)?;
+		}
+
+		for provides in &module.provides {
+			self.expect(provides.provides_index, ConstantPoolTag::Class)?;
+			if provides.provides_with_index.is_empty() {
+				return Err(FormatError::InvalidAttribute {
+					tag: AttributeTag::Module,
+					reason: "provides directive without any implementations",
+				});
+			}
+
+			for &implementation in &provides.provides_with_index {
+				self.expect(implementation, ConstantPoolTag::Class)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4-310
+fn is_loadable(tag: ConstantPoolTag) -> bool {
+	matches!(
+		tag,
+		ConstantPoolTag::Integer
+			| ConstantPoolTag::Float
+			| ConstantPoolTag::Long
+			| ConstantPoolTag::Double
+			| ConstantPoolTag::Class
+			| ConstantPoolTag::String
+			| ConstantPoolTag::MethodHandle
+			| ConstantPoolTag::MethodType
+			| ConstantPoolTag::Dynamic
+	)
+}
@@ -0,0 +1,313 @@
+use super::error::{FormatError, Result};
+use super::{Checker, names};
+use crate::constant_pool::types::ReferenceKind;
+use crate::constant_pool::{ConstantPoolTag, ConstantPoolValueInfo};
+
+use std::borrow::Cow;
+
+use common::int_types::u2;
+
+/// The kind of member that a `CONSTANT_NameAndType_info` entry is used for
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Member {
+	Field,
+	Method,
+}
+
+impl<'a> Checker<'a> {
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4
+	pub(super) fn check_constant_pool(&self) -> Result<()> {
+		for (index, entry) in self.class.constant_pool.iter().enumerate() {
+			let index = index as u2;
+			self.check_constant_pool_entry(index, entry)?;
+		}
+
+		Ok(())
+	}
+
+	fn check_constant_pool_entry(&self, index: u2, entry: &ConstantPoolValueInfo) -> Result<()> {
+		let tag = entry.tag();
+
+		// Table 4.4-B: Constant pool tags (by section)
+		let required_major_version = match tag {
+			ConstantPoolTag::MethodHandle
+			| ConstantPoolTag::MethodType
+			| ConstantPoolTag::InvokeDynamic => 51,
+			ConstantPoolTag::Module | ConstantPoolTag::Package => 53,
+			ConstantPoolTag::Dynamic => 55,
+			_ => 45,
+		};
+
+		if self.major_version < required_major_version {
+			return Err(FormatError::UnsupportedConstantPoolTag {
+				index,
+				tag,
+				major_version: self.major_version,
+			});
+		}
+
+		match entry {
+			ConstantPoolValueInfo::Unusable
+			| ConstantPoolValueInfo::Integer { .. }
+			| ConstantPoolValueInfo::Float { .. }
+			| ConstantPoolValueInfo::Long { .. }
+			| ConstantPoolValueInfo::Double { .. } => {},
+			ConstantPoolValueInfo::Utf8 { bytes, .. } => {
+				if !names::is_modified_utf8(bytes) {
+					return Err(FormatError::InvalidUtf8(index));
+				}
+			},
+			ConstantPoolValueInfo::Class { name_index } => {
+				let name = self.utf8(*name_index)?;
+				if !names::is_class_name(&name) {
+					return Err(FormatError::IllegalName {
+						kind: "class name",
+						name: name.into_owned(),
+					});
+				}
+			},
+			ConstantPoolValueInfo::String { string_index } => {
+				self.utf8(*string_index)?;
+			},
+			ConstantPoolValueInfo::Fieldref {
+				class_index,
+				name_and_type_index,
+			} => {
+				self.expect(*class_index, ConstantPoolTag::Class)?;
+				self.member_name_and_type(*name_and_type_index, Member::Field)?;
+			},
+			ConstantPoolValueInfo::Methodref {
+				class_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::InterfaceMethodref {
+				class_index,
+				name_and_type_index,
+			} => {
+				self.expect(*class_index, ConstantPoolTag::Class)?;
+
+				let (name, _) = self.member_name_and_type(*name_and_type_index, Member::Method)?;
+				if name == "<clinit>" {
+					return Err(FormatError::IllegalName {
+						kind: "method name",
+						name: name.into_owned(),
+					});
+				}
+			},
+			ConstantPoolValueInfo::NameAndType {
+				name_index,
+				descriptor_index,
+			} => {
+				// The contents are checked by the entries referring to this one, since they
+				// depend on whether it describes a field or a method.
+				self.utf8(*name_index)?;
+				self.utf8(*descriptor_index)?;
+			},
+			ConstantPoolValueInfo::MethodHandle {
+				reference_kind,
+				reference_index,
+			} => self.check_method_handle(index, *reference_kind, *reference_index)?,
+			ConstantPoolValueInfo::MethodType { descriptor_index } => {
+				let descriptor = self.utf8(*descriptor_index)?;
+				if names::method_descriptor(&descriptor).is_none() {
+					return Err(FormatError::IllegalName {
+						kind: "method type descriptor",
+						name: descriptor.into_owned(),
+					});
+				}
+			},
+			ConstantPoolValueInfo::Dynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			} => {
+				self.check_bootstrap_method_index(*bootstrap_method_attr_index)?;
+				self.member_name_and_type(*name_and_type_index, Member::Field)?;
+			},
+			ConstantPoolValueInfo::InvokeDynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			} => {
+				self.check_bootstrap_method_index(*bootstrap_method_attr_index)?;
+
+				let (name, _) = self.member_name_and_type(*name_and_type_index, Member::Method)?;
+				if name.starts_with('<') {
+					return Err(FormatError::IllegalName {
+						kind: "method name",
+						name: name.into_owned(),
+					});
+				}
+			},
+			ConstantPoolValueInfo::Module { name_index } => {
+				if !self.is_module {
+					return Err(FormatError::ConstantOutsideOfModule(index));
+				}
+
+				let name = self.utf8(*name_index)?;
+				if !names::is_module_name(&name) {
+					return Err(FormatError::IllegalName {
+						kind: "module name",
+						name: name.into_owned(),
+					});
+				}
+			},
+			ConstantPoolValueInfo::Package { name_index } => {
+				if !self.is_module {
+					return Err(FormatError::ConstantOutsideOfModule(index));
+				}
+
+				let name = self.utf8(*name_index)?;
+				if !names::is_binary_name(&name) {
+					return Err(FormatError::IllegalName {
+						kind: "package name",
+						name: name.into_owned(),
+					});
+				}
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Get the name and descriptor of the `CONSTANT_NameAndType_info` entry at `index`, checking
+	/// that they are valid for a `member`
+	///
+	/// For methods, `<init>` must return `void`.
+	fn member_name_and_type(
+		&self,
+		index: u2,
+		member: Member,
+	) -> Result<(Cow<'a, str>, Cow<'a, str>)> {
+		let ConstantPoolValueInfo::NameAndType {
+			name_index,
+			descriptor_index,
+		} = self.expect(index, ConstantPoolTag::NameAndType)?
+		else {
+			unreachable!()
+		};
+
+		let name = self.utf8(*name_index)?;
+		let descriptor = self.utf8(*descriptor_index)?;
+
+		match member {
+			Member::Field => {
+				if !names::is_unqualified_name(&name) {
+					return Err(FormatError::IllegalName {
+						kind: "field name",
+						name: name.into_owned(),
+					});
+				}
+
+				if !names::is_field_descriptor(&descriptor) {
+					return Err(FormatError::IllegalFieldDescriptor {
+						name: name.into_owned(),
+						descriptor: descriptor.into_owned(),
+					});
+				}
+			},
+			Member::Method => {
+				if !names::is_method_name(&name) {
+					return Err(FormatError::IllegalName {
+						kind: "method name",
+						name: name.into_owned(),
+					});
+				}
+
+				let valid = names::method_descriptor(&descriptor)
+					.is_some_and(|shape| shape.returns_void || name != "<init>");
+				if !valid {
+					return Err(FormatError::IllegalMethodDescriptor {
+						name: name.into_owned(),
+						descriptor: descriptor.into_owned(),
+					});
+				}
+			},
+		}
+
+		Ok((name, descriptor))
+	}
+
+	fn check_bootstrap_method_index(&self, bootstrap_method_attr_index: u2) -> Result<()> {
+		if bootstrap_method_attr_index as usize >= self.bootstrap_method_count {
+			return Err(FormatError::InvalidBootstrapMethodIndex(
+				bootstrap_method_attr_index,
+			));
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.8
+	fn check_method_handle(
+		&self,
+		index: u2,
+		reference_kind: u8,
+		reference_index: u2,
+	) -> Result<()> {
+		let Some(kind) = ReferenceKind::from_u8(reference_kind) else {
+			return Err(FormatError::InvalidMethodHandleKind {
+				index,
+				reference_kind,
+			});
+		};
+
+		let entry = self.entry(reference_index)?;
+		let expected = match kind {
+			ReferenceKind::GetField
+			| ReferenceKind::GetStatic
+			| ReferenceKind::PutField
+			| ReferenceKind::PutStatic => ConstantPoolTag::FieldRef,
+			// In a class file whose version number is 52.0 or above, these may also refer to
+			// interface methods
+			ReferenceKind::InvokeStatic | ReferenceKind::InvokeSpecial
+				if self.major_version >= 52
+					&& entry.tag() == ConstantPoolTag::InterfaceMethodref =>
+			{
+				ConstantPoolTag::InterfaceMethodref
+			},
+			ReferenceKind::InvokeVirtual
+			| ReferenceKind::NewInvokeSpecial
+			| ReferenceKind::InvokeStatic
+			| ReferenceKind::InvokeSpecial => ConstantPoolTag::MethodRef,
+			ReferenceKind::InvokeInterface => ConstantPoolTag::InterfaceMethodref,
+		};
+
+		let (ConstantPoolValueInfo::Fieldref {
+			name_and_type_index,
+			..
+		}
+		| ConstantPoolValueInfo::Methodref {
+			name_and_type_index,
+			..
+		}
+		| ConstantPoolValueInfo::InterfaceMethodref {
+			name_and_type_index,
+			..
+		}) = self.expect(reference_index, expected)?
+		else {
+			unreachable!()
+		};
+
+		if kind.is_field() {
+			return Ok(());
+		}
+
+		// REF_newInvokeSpecial must refer to an instance initialization method, and the rest of
+		// the method kinds must not refer to any initialization method.
+		let ConstantPoolValueInfo::NameAndType { name_index, .. } =
+			self.expect(*name_and_type_index, ConstantPoolTag::NameAndType)?
+		else {
+			unreachable!()
+		};
+
+		let name = self.utf8(*name_index)?;
+		let is_init = name == "<init>";
+		if (kind == ReferenceKind::NewInvokeSpecial) != is_init || name == "<clinit>" {
+			return Err(FormatError::IllegalName {
+				kind: "method handle target",
+				name: name.into_owned(),
+			});
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,228 @@
+use crate::attribute::AttributeTag;
+use crate::constant_pool::ConstantPoolTag;
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use common::int_types::{u1, u2};
+
+pub type Result<T> = std::result::Result<T, FormatError>;
+
+/// A violation of the format checks in [§4.8]
+///
+/// Any name or descriptor in an error is lossily decoded, since it may not be valid modified UTF-8.
+///
+/// [§4.8]: https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+	/// An index that is out of bounds, or points to the second slot of a long or double
+	InvalidConstantPoolIndex(u2),
+	/// An entry of the wrong kind at `index`
+	UnexpectedConstantPoolTag {
+		index: u2,
+		expected: &'static str,
+		found: ConstantPoolTag,
+	},
+	/// An entry that is not allowed in a class file of `major_version`
+	UnsupportedConstantPoolTag {
+		index: u2,
+		tag: ConstantPoolTag,
+		major_version: u2,
+	},
+	/// A `CONSTANT_Module_info` or `CONSTANT_Package_info` entry outside of a module descriptor
+	ConstantOutsideOfModule(u2),
+	InvalidUtf8(u2),
+	InvalidMethodHandleKind {
+		index: u2,
+		reference_kind: u1,
+	},
+	InvalidBootstrapMethodIndex(u2),
+
+	/// An illegal name or descriptor, `kind` describing what it names (e.g. "class name")
+	IllegalName {
+		kind: &'static str,
+		name: String,
+	},
+	IllegalFieldDescriptor {
+		name: String,
+		descriptor: String,
+	},
+	IllegalMethodDescriptor {
+		name: String,
+		descriptor: String,
+	},
+	TooManyArguments(String),
+
+	IllegalClassModifiers(u2),
+	IllegalFieldModifiers {
+		name: String,
+		flags: u2,
+	},
+	IllegalMethodModifiers {
+		name: String,
+		flags: u2,
+	},
+
+	InvalidSuperclass(&'static str),
+	DuplicateInterface(String),
+	DuplicateField {
+		name: String,
+		descriptor: String,
+	},
+	DuplicateMethod {
+		name: String,
+		descriptor: String,
+	},
+	IllegalModule(&'static str),
+
+	DuplicateAttribute {
+		tag: AttributeTag,
+		location: &'static str,
+	},
+	ConflictingAttributes(AttributeTag, AttributeTag),
+	InvalidAttribute {
+		tag: AttributeTag,
+		reason: &'static str,
+	},
+	MissingCode(String),
+	UnexpectedCode(String),
+	InvalidCode {
+		method: String,
+		reason: &'static str,
+	},
+	InconsistentConstantValue(String),
+}
+
+impl Display for FormatError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::InvalidConstantPoolIndex(index) => {
+				write!(f, "Invalid constant pool index {index}")
+			},
+			Self::UnexpectedConstantPoolTag {
+				index,
+				expected,
+				found,
+			} => write!(
+				f,
+				"Invalid constant pool entry {index}: expected {expected}, found {}",
+				tag_name(*found)
+			),
+			Self::UnsupportedConstantPoolTag {
+				index,
+				tag,
+				major_version,
+			} => write!(
+				f,
+				"Constant pool entry {index} of type {} is not supported in class file version \
+				 {major_version}",
+				tag_name(*tag)
+			),
+			Self::ConstantOutsideOfModule(index) => write!(
+				f,
+				"Constant pool entry {index} is only permitted in a module descriptor"
+			),
+			Self::InvalidUtf8(index) => {
+				write!(f, "Illegal UTF8 string in constant pool entry {index}")
+			},
+			Self::InvalidMethodHandleKind {
+				index,
+				reference_kind,
+			} => write!(
+				f,
+				"Bad method handle kind {reference_kind} at constant pool index {index}"
+			),
+			Self::InvalidBootstrapMethodIndex(index) => write!(
+				f,
+				"Invalid bootstrap method index {index}, or missing BootstrapMethods attribute"
+			),
+
+			Self::IllegalName { kind, name } => write!(f, "Illegal {kind} \"{name}\""),
+			Self::IllegalFieldDescriptor { name, descriptor } => {
+				write!(f, "Field \"{name}\" has illegal signature \"{descriptor}\"")
+			},
+			Self::IllegalMethodDescriptor { name, descriptor } => write!(
+				f,
+				"Method \"{name}\" has illegal signature \"{descriptor}\""
+			),
+			Self::TooManyArguments(name) => {
+				write!(f, "Too many arguments in method signature of \"{name}\"")
+			},
+
+			Self::IllegalClassModifiers(flags) => {
+				write!(f, "Illegal class modifiers: {flags:#X}")
+			},
+			Self::IllegalFieldModifiers { name, flags } => {
+				write!(f, "Illegal field modifiers for \"{name}\": {flags:#X}")
+			},
+			Self::IllegalMethodModifiers { name, flags } => {
+				write!(f, "Method \"{name}\" has illegal modifiers: {flags:#X}")
+			},
+
+			Self::InvalidSuperclass(reason) => write!(f, "Invalid superclass: {reason}"),
+			Self::DuplicateInterface(name) => write!(f, "Duplicate interface name \"{name}\""),
+			Self::DuplicateField { name, descriptor } => write!(
+				f,
+				"Duplicate field name \"{name}\" with signature \"{descriptor}\""
+			),
+			Self::DuplicateMethod { name, descriptor } => write!(
+				f,
+				"Duplicate method name \"{name}\" with signature \"{descriptor}\""
+			),
+			Self::IllegalModule(reason) => write!(f, "Illegal module descriptor: {reason}"),
+
+			Self::DuplicateAttribute { tag, location } => {
+				write!(f, "Multiple {} attributes in {location}", tag.name())
+			},
+			Self::ConflictingAttributes(first, second) => write!(
+				f,
+				"Conflicting {} and {} attributes",
+				first.name(),
+				second.name()
+			),
+			Self::InvalidAttribute { tag, reason } => {
+				write!(f, "Invalid {} attribute: {reason}", tag.name())
+			},
+			Self::MissingCode(method) => write!(
+				f,
+				"Absent Code attribute in method \"{method}\" that is not native or abstract"
+			),
+			Self::UnexpectedCode(method) => write!(
+				f,
+				"Code attribute in native or abstract method \"{method}\""
+			),
+			Self::InvalidCode { method, reason } => {
+				write!(f, "Invalid Code attribute in method \"{method}\": {reason}")
+			},
+			Self::InconsistentConstantValue(field) => {
+				write!(f, "Inconsistent constant value type for field \"{field}\"")
+			},
+		}
+	}
+}
+
+impl Error for FormatError {}
+
+/// The name of the structure for `tag`, as used in the JVMS
+pub(super) fn tag_name(tag: ConstantPoolTag) -> &'static str {
+	match tag {
+		ConstantPoolTag::Unusable => "unusable entry",
+		ConstantPoolTag::Utf8 => "CONSTANT_Utf8_info",
+		ConstantPoolTag::Integer => "CONSTANT_Integer_info",
+		ConstantPoolTag::Float => "CONSTANT_Float_info",
+		ConstantPoolTag::Long => "CONSTANT_Long_info",
+		ConstantPoolTag::Double => "CONSTANT_Double_info",
+		ConstantPoolTag::Class => "CONSTANT_Class_info",
+		ConstantPoolTag::String => "CONSTANT_String_info",
+		ConstantPoolTag::FieldRef => "CONSTANT_Fieldref_info",
+		ConstantPoolTag::MethodRef => "CONSTANT_Methodref_info",
+		ConstantPoolTag::InterfaceMethodref => "CONSTANT_InterfaceMethodref_info",
+		ConstantPoolTag::NameAndType => "CONSTANT_NameAndType_info",
+		ConstantPoolTag::MethodHandle => "CONSTANT_MethodHandle_info",
+		ConstantPoolTag::MethodType => "CONSTANT_MethodType_info",
+		ConstantPoolTag::Dynamic => "CONSTANT_Dynamic_info",
+		ConstantPoolTag::InvokeDynamic => "CONSTANT_InvokeDynamic_info",
+		ConstantPoolTag::Module => "CONSTANT_Module_info",
+		ConstantPoolTag::Package => "CONSTANT_Package_info",
+	}
+}
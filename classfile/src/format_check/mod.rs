@@ -0,0 +1,681 @@
+//! Format checking (§4.8)
+//!
+//! Parsing a class file only ensures that it can be decoded. The format checks go further, and
+//! ensure that the class file is internally consistent:
+//!
+//! * Constant pool entries refer to entries of the right kinds, and hold valid names and descriptors
+//! * Access flags are legal combinations for the class file version
+//! * Fields and methods are unique, and methods have a `Code` attribute if (and only if) they need one
+//! * Predefined attributes appear at most once where required, and refer to valid entries
+//!
+//! Any class file that fails these checks must be rejected with a `ClassFormatError`.
+//!
+//! [§4.8]: https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.8
+
+mod attributes;
+mod constant_pool;
+mod error;
+mod names;
+
+pub use error::{FormatError, Result};
+
+use crate::accessflags::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use crate::attribute::{Attribute, AttributeType};
+use crate::classfile::ClassFile;
+use crate::constant_pool::{ConstantPoolTag, ConstantPoolValueInfo};
+use crate::fieldinfo::FieldInfo;
+use crate::methodinfo::MethodInfo;
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use common::int_types::u2;
+
+/// The maximum number of local variable slots taken up by a method's parameters, including `this` (§4.3.3)
+const MAX_PARAMETER_SLOTS: u16 = 255;
+
+/// All method flags defined in Table 4.6-A, any other bits are ignored
+const KNOWN_METHOD_FLAGS: u2 = 0x1DFF;
+
+/// Perform the format checks on `class`
+///
+/// The checks are performed in order, and only the first violation is reported.
+///
+/// # Errors
+///
+/// See [`FormatError`].
+pub fn check(class: &ClassFile) -> Result<()> {
+	let checker = Checker::new(class);
+
+	checker.check_constant_pool()?;
+	checker.check_class()?;
+	checker.check_fields()?;
+	checker.check_methods()?;
+	checker.check_class_attributes()
+}
+
+struct Checker<'a> {
+	class: &'a ClassFile,
+	major_version: u2,
+	is_interface: bool,
+	is_module: bool,
+	bootstrap_method_count: usize,
+}
+
+impl<'a> Checker<'a> {
+	fn new(class: &'a ClassFile) -> Self {
+		let bootstrap_method_count = class
+			.attributes
+			.iter()
+			.find_map(|attribute| match &attribute.info {
+				AttributeType::BootstrapMethods(bootstrap_methods) => {
+					Some(bootstrap_methods.bootstrap_methods.len())
+				},
+				_ => None,
+			})
+			.unwrap_or(0);
+
+		Self {
+			class,
+			major_version: class.major_version,
+			is_interface: class.access_flags.is_interface(),
+			is_module: class.access_flags.is_module(),
+			bootstrap_method_count,
+		}
+	}
+
+	/// Get the entry at `index`, which must be in bounds and usable
+	fn entry(&self, index: u2) -> Result<&'a ConstantPoolValueInfo> {
+		let constant_pool = &self.class.constant_pool;
+		if index == 0 || index as usize >= constant_pool.len() {
+			return Err(FormatError::InvalidConstantPoolIndex(index));
+		}
+
+		match &constant_pool[index] {
+			ConstantPoolValueInfo::Unusable => Err(FormatError::InvalidConstantPoolIndex(index)),
+			entry => Ok(entry),
+		}
+	}
+
+	/// Get the entry at `index`, which must be of type `tag`
+	fn expect(&self, index: u2, tag: ConstantPoolTag) -> Result<&'a ConstantPoolValueInfo> {
+		let entry = self.entry(index)?;
+		if entry.tag() != tag {
+			return Err(FormatError::UnexpectedConstantPoolTag {
+				index,
+				expected: error::tag_name(tag),
+				found: entry.tag(),
+			});
+		}
+
+		Ok(entry)
+	}
+
+	/// Same as [`Self::expect()`], but an index of 0 is allowed
+	fn expect_optional(&self, index: u2, tag: ConstantPoolTag) -> Result<()> {
+		if index != 0 {
+			self.expect(index, tag)?;
+		}
+
+		Ok(())
+	}
+
+	/// Get the contents of the `CONSTANT_Utf8_info` entry at `index`
+	///
+	/// The encoding of every entry is checked up front, but unpaired surrogates can't be represented
+	/// in a `str`, so they are replaced. No name or descriptor is invalid because of them.
+	fn utf8(&self, index: u2) -> Result<Cow<'a, str>> {
+		let ConstantPoolValueInfo::Utf8 { bytes, .. } =
+			self.expect(index, ConstantPoolTag::Utf8)?
+		else {
+			unreachable!()
+		};
+
+		Ok(common::unicode::decode(bytes).unwrap_or_else(|_| String::from_utf8_lossy(bytes)))
+	}
+
+	/// Get the name of the `CONSTANT_Class_info` entry at `index`
+	fn class_name(&self, index: u2) -> Result<Cow<'a, str>> {
+		let ConstantPoolValueInfo::Class { name_index } =
+			self.expect(index, ConstantPoolTag::Class)?
+		else {
+			unreachable!()
+		};
+
+		self.utf8(*name_index)
+	}
+
+	/// Get the name of the `CONSTANT_Class_info` entry at `index`, which must not be an array class
+	fn non_array_class_name(&self, index: u2) -> Result<Cow<'a, str>> {
+		let name = self.class_name(index)?;
+		if name.starts_with('[') {
+			return Err(FormatError::IllegalName {
+				kind: "class name",
+				name: name.into_owned(),
+			});
+		}
+
+		Ok(name)
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.1
+	fn check_class(&self) -> Result<()> {
+		let flags = self.class.access_flags;
+		let illegal_modifiers = FormatError::IllegalClassModifiers(flags.as_u2());
+
+		if self.is_module {
+			return self.check_module_class();
+		}
+
+		let this_name = self.non_array_class_name(self.class.this_class)?;
+
+		// Flags introduced in Java 5 (49.0) are only checked from then on
+		let checks_java_5_flags = self.major_version >= 49;
+		if self.is_interface {
+			// Prior to Java 6 (50.0), interfaces are implicitly abstract
+			let is_abstract = flags.is_abstract() || self.major_version < 50;
+			if !is_abstract
+				|| flags.is_final()
+				|| (checks_java_5_flags && (flags.is_super() || flags.is_enum()))
+			{
+				return Err(illegal_modifiers);
+			}
+		} else if (flags.is_final() && flags.is_abstract())
+			|| (checks_java_5_flags && flags.is_annotation())
+		{
+			return Err(illegal_modifiers);
+		}
+
+		if self.class.super_class == 0 {
+			if this_name != "java/lang/Object" {
+				return Err(FormatError::InvalidSuperclass(
+					"only java/lang/Object may omit a superclass",
+				));
+			}
+		} else {
+			let super_name = self.non_array_class_name(self.class.super_class)?;
+			if self.is_interface && super_name != "java/lang/Object" {
+				return Err(FormatError::InvalidSuperclass(
+					"the superclass of an interface must be java/lang/Object",
+				));
+			}
+		}
+
+		let mut interfaces = HashSet::with_capacity(self.class.interfaces.len());
+		for &interface in &self.class.interfaces {
+			let name = self.non_array_class_name(interface)?;
+			if !interfaces.insert(name.clone()) {
+				return Err(FormatError::DuplicateInterface(name.into_owned()));
+			}
+		}
+
+		Ok(())
+	}
+
+	/// A class file with `ACC_MODULE` set declares a module, and has a very limited structure
+	fn check_module_class(&self) -> Result<()> {
+		let class = self.class;
+
+		if class.access_flags != ClassAccessFlags::ACC_MODULE {
+			return Err(FormatError::IllegalClassModifiers(
+				class.access_flags.as_u2(),
+			));
+		}
+
+		if self.major_version < 53 {
+			return Err(FormatError::IllegalModule(
+				"class file version must be 53.0 or above",
+			));
+		}
+
+		if self.class_name(class.this_class)? != "module-info" {
+			return Err(FormatError::IllegalModule("this_class must be module-info"));
+		}
+
+		if class.super_class != 0 {
+			return Err(FormatError::IllegalModule("super_class must be zero"));
+		}
+
+		if !class.interfaces.is_empty() || !class.fields.is_empty() || !class.methods.is_empty() {
+			return Err(FormatError::IllegalModule(
+				"a module cannot have interfaces, fields, or methods",
+			));
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.5
+	fn check_fields(&self) -> Result<()> {
+		let mut seen = HashSet::with_capacity(self.class.fields.len());
+		for field in &self.class.fields {
+			let name = self.utf8(field.name_index)?;
+			let descriptor = self.utf8(field.descriptor_index)?;
+
+			if !names::is_unqualified_name(&name) {
+				return Err(FormatError::IllegalName {
+					kind: "field name",
+					name: name.into_owned(),
+				});
+			}
+
+			if !names::is_field_descriptor(&descriptor) {
+				return Err(FormatError::IllegalFieldDescriptor {
+					name: name.into_owned(),
+					descriptor: descriptor.into_owned(),
+				});
+			}
+
+			if !self.field_flags_legal(field.access_flags) {
+				return Err(FormatError::IllegalFieldModifiers {
+					name: name.into_owned(),
+					flags: field.access_flags.as_u2(),
+				});
+			}
+
+			if !seen.insert((name.clone(), descriptor.clone())) {
+				return Err(FormatError::DuplicateField {
+					name: name.into_owned(),
+					descriptor: descriptor.into_owned(),
+				});
+			}
+
+			self.check_field_attributes(field, &name, &descriptor)?;
+		}
+
+		Ok(())
+	}
+
+	fn field_flags_legal(&self, flags: FieldAccessFlags) -> bool {
+		if self.is_interface {
+			// Fields of interfaces must have their ACC_PUBLIC, ACC_STATIC, and ACC_FINAL flags set;
+			// they may have their ACC_SYNTHETIC flag set and must not have any of the other flags
+			// in Table 4.5-A set.
+			return flags.is_public()
+				&& flags.is_static()
+				&& flags.is_final()
+				&& !flags.is_private()
+				&& !flags.is_protected()
+				&& !flags.is_volatile()
+				&& !flags.is_transient()
+				&& !(self.major_version >= 49 && flags.is_enum());
+		}
+
+		// Each field of a class may have at most one of its ACC_PUBLIC, ACC_PRIVATE, and
+		// ACC_PROTECTED flags set, and must not have both its ACC_FINAL and ACC_VOLATILE flags set.
+		let access_flags_set = [flags.is_public(), flags.is_private(), flags.is_protected()]
+			.into_iter()
+			.filter(|set| *set)
+			.count();
+
+		access_flags_set <= 1 && !(flags.is_final() && flags.is_volatile())
+	}
+
+	fn check_field_attributes(
+		&self,
+		field: &FieldInfo,
+		name: &str,
+		descriptor: &str,
+	) -> Result<()> {
+		self.check_attributes(&field.attributes, "field")?;
+
+		// If the ACC_STATIC flag is not set, the ConstantValue attribute must be silently ignored
+		if !field.access_flags.is_static() {
+			return Ok(());
+		}
+
+		let Some(constant_value) = field.attributes.iter().find_map(Attribute::constant_value)
+		else {
+			return Ok(());
+		};
+
+		let expected = match descriptor {
+			"J" => ConstantPoolTag::Long,
+			"F" => ConstantPoolTag::Float,
+			"D" => ConstantPoolTag::Double,
+			"I" | "S" | "C" | "B" | "Z" => ConstantPoolTag::Integer,
+			"Ljava/lang/String;" => ConstantPoolTag::String,
+			_ => return Err(FormatError::InconsistentConstantValue(name.to_owned())),
+		};
+
+		if self.entry(constant_value.constantvalue_index)?.tag() != expected {
+			return Err(FormatError::InconsistentConstantValue(name.to_owned()));
+		}
+
+		Ok(())
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.6
+	fn check_methods(&self) -> Result<()> {
+		let mut seen = HashSet::with_capacity(self.class.methods.len());
+		for method in &self.class.methods {
+			let name = self.utf8(method.name_index)?;
+			let descriptor = self.utf8(method.descriptor_index)?;
+
+			if !names::is_method_name(&name) {
+				return Err(FormatError::IllegalName {
+					kind: "method name",
+					name: name.into_owned(),
+				});
+			}
+
+			if self.is_interface && name == "<init>" {
+				return Err(FormatError::IllegalName {
+					kind: "interface method name",
+					name: name.into_owned(),
+				});
+			}
+
+			let Some(shape) = names::method_descriptor(&descriptor)
+				.filter(|shape| shape.returns_void || !name.starts_with('<'))
+			else {
+				return Err(FormatError::IllegalMethodDescriptor {
+					name: name.into_owned(),
+					descriptor: descriptor.into_owned(),
+				});
+			};
+
+			let receiver_slots = u16::from(!method.access_flags.is_static());
+			if shape.parameter_slots + receiver_slots > MAX_PARAMETER_SLOTS {
+				return Err(FormatError::TooManyArguments(name.into_owned()));
+			}
+
+			if !self.method_flags_legal(method.access_flags, &name) {
+				return Err(FormatError::IllegalMethodModifiers {
+					name: name.into_owned(),
+					flags: method.access_flags.as_u2(),
+				});
+			}
+
+			if !seen.insert((name.clone(), descriptor.clone())) {
+				return Err(FormatError::DuplicateMethod {
+					name: name.into_owned(),
+					descriptor: descriptor.into_owned(),
+				});
+			}
+
+			self.check_method_attributes(method, &name)?;
+		}
+
+		Ok(())
+	}
+
+	fn method_flags_legal(&self, flags: MethodAccessFlags, name: &str) -> bool {
+		// Class and interface initialization methods are called implicitly by the Java Virtual
+		// Machine. The value of their access_flags item is ignored except for the setting of the
+		// ACC_STATIC and ACC_STRICT flags, and the method is exempt from the preceding rules.
+		//
+		// In a class file whose version number is 51.0 or above, the method must have its
+		// ACC_STATIC flag set to be an initialization method, otherwise it is an ordinary method.
+		if name == "<clinit>" && (self.major_version < 51 || flags.is_static()) {
+			return true;
+		}
+
+		let access_flags_set = [flags.is_public(), flags.is_private(), flags.is_protected()]
+			.into_iter()
+			.filter(|set| *set)
+			.count();
+		if access_flags_set > 1 {
+			return false;
+		}
+
+		if name == "<init>" {
+			// Each instance initialization method may have at most one of its ACC_PUBLIC,
+			// ACC_PRIVATE, and ACC_PROTECTED flags set, and may also have its ACC_VARARGS,
+			// ACC_STRICT, and ACC_SYNTHETIC flags set, but must not have any of the other flags
+			// in Table 4.6-A set.
+			let allowed = MethodAccessFlags::ACC_PUBLIC
+				| MethodAccessFlags::ACC_PRIVATE
+				| MethodAccessFlags::ACC_PROTECTED
+				| MethodAccessFlags::ACC_VARARGS
+				| MethodAccessFlags::ACC_STRICT
+				| MethodAccessFlags::ACC_SYNTHETIC;
+			return flags.as_u2() & KNOWN_METHOD_FLAGS & !allowed.as_u2() == 0;
+		}
+
+		if self.is_interface {
+			// Methods of interfaces may have any of the flags in Table 4.6-A set except
+			// ACC_PROTECTED, ACC_FINAL, ACC_SYNCHRONIZED, and ACC_NATIVE.
+			if flags.is_protected()
+				|| flags.is_final()
+				|| flags.is_synchronized()
+				|| flags.is_native()
+			{
+				return false;
+			}
+
+			// In a class file whose version number is less than 52.0, each method of an interface
+			// must have its ACC_PUBLIC and ACC_ABSTRACT flags set; in a class file whose version
+			// number is 52.0 or above, each method of an interface must have exactly one of its
+			// ACC_PUBLIC and ACC_PRIVATE flags set.
+			if self.major_version < 52 {
+				if !flags.is_public() || !flags.is_abstract() {
+					return false;
+				}
+			} else if flags.is_public() == flags.is_private() {
+				return false;
+			}
+		}
+
+		// If a method of a class or interface has its ACC_ABSTRACT flag set, it must not have any
+		// of its ACC_PRIVATE, ACC_STATIC, ACC_FINAL, ACC_SYNCHRONIZED, or ACC_NATIVE flags set (nor
+		// ACC_STRICT, in a class file whose version number is at least 46.0 and below 61.0).
+		if flags.is_abstract() {
+			let strict_is_illegal = (46..61).contains(&self.major_version);
+			return !(flags.is_private()
+				|| flags.is_static()
+				|| flags.is_final()
+				|| flags.is_synchronized()
+				|| flags.is_native()
+				|| (strict_is_illegal && flags.is_strict()));
+		}
+
+		true
+	}
+
+	fn check_method_attributes(&self, method: &MethodInfo, name: &str) -> Result<()> {
+		self.check_attributes(&method.attributes, "method")?;
+
+		let code = method.attributes.iter().find_map(Attribute::code);
+
+		// If the method is either native or abstract, its method_info structure must not have a
+		// Code attribute in its attributes table. Otherwise, it must have exactly one.
+		let flags = method.access_flags;
+		match code {
+			Some(_) if flags.is_native() || flags.is_abstract() => {
+				Err(FormatError::UnexpectedCode(name.to_owned()))
+			},
+			None if !flags.is_native() && !flags.is_abstract() => {
+				Err(FormatError::MissingCode(name.to_owned()))
+			},
+			Some(code) => self.check_code(code, name),
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::FormatError;
+	use crate::ClassFile;
+	use crate::accessflags::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+	use crate::attribute::{AttributeTag, AttributeType, ConstantValue, SourceFile};
+	use crate::builder::{ClassFileBuilder, CodeBuilder};
+
+	use instructions::OpCode;
+
+	fn new_class(access_flags: ClassAccessFlags) -> ClassFileBuilder {
+		ClassFileBuilder::new("Test", Some("java/lang/Object"), access_flags)
+	}
+
+	fn return_code(builder: &mut ClassFileBuilder) -> AttributeType {
+		let mut code = CodeBuilder::new();
+		code.max_locals(1).op(OpCode::r#return);
+		AttributeType::Code(code.finish(builder.constant_pool()))
+	}
+
+	#[test]
+	fn fixtures_pass() {
+		for bytes in [
+			&include_bytes!("../../tests/data/RoundTrip.class")[..],
+			&include_bytes!("../../tests/data/RoundTrip$Visible.class")[..],
+			&include_bytes!("../../tests/data/module/module-info.class")[..],
+		] {
+			let class = ClassFile::read_from(&mut &bytes[..]).unwrap();
+			assert_eq!(class.check_format(), Ok(()));
+		}
+	}
+
+	#[test]
+	fn illegal_class_modifiers() {
+		let flags = ClassAccessFlags::ACC_FINAL | ClassAccessFlags::ACC_ABSTRACT;
+		let class = new_class(flags).finish();
+		assert_eq!(
+			class.check_format(),
+			Err(FormatError::IllegalClassModifiers(flags.as_u2()))
+		);
+
+		let flags = ClassAccessFlags::ACC_INTERFACE;
+		let class = new_class(flags).finish();
+		assert_eq!(
+			class.check_format(),
+			Err(FormatError::IllegalClassModifiers(flags.as_u2()))
+		);
+	}
+
+	#[test]
+	fn illegal_names() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		builder.add_field(FieldAccessFlags::ACC_PUBLIC, "a.b", "I", []);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::IllegalName {
+				kind: "field name",
+				name: String::from("a.b"),
+			})
+		);
+
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		builder.add_method(MethodAccessFlags::ACC_NATIVE, "<init>", "()I", []);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::IllegalMethodDescriptor {
+				name: String::from("<init>"),
+				descriptor: String::from("()I"),
+			})
+		);
+	}
+
+	#[test]
+	fn duplicate_members() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		builder
+			.add_field(FieldAccessFlags::ACC_PUBLIC, "a", "I", [])
+			.add_field(FieldAccessFlags::ACC_PRIVATE, "a", "I", []);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::DuplicateField {
+				name: String::from("a"),
+				descriptor: String::from("I"),
+			})
+		);
+	}
+
+	#[test]
+	fn code_presence() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		builder.add_method(MethodAccessFlags::ACC_PUBLIC, "run", "()V", []);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::MissingCode(String::from("run")))
+		);
+
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC | ClassAccessFlags::ACC_ABSTRACT);
+		let code = return_code(&mut builder);
+		builder.add_method(
+			MethodAccessFlags::ACC_PUBLIC | MethodAccessFlags::ACC_ABSTRACT,
+			"run",
+			"()V",
+			[code],
+		);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::UnexpectedCode(String::from("run")))
+		);
+	}
+
+	#[test]
+	fn interface_methods_depend_on_version() {
+		let flags = ClassAccessFlags::ACC_INTERFACE | ClassAccessFlags::ACC_ABSTRACT;
+
+		let build = |major_version| {
+			let mut builder = new_class(flags);
+			builder.version(major_version, 0);
+			let code = return_code(&mut builder);
+			builder.add_method(MethodAccessFlags::ACC_PRIVATE, "helper", "()V", [code]);
+			builder.finish()
+		};
+
+		assert_eq!(build(52).check_format(), Ok(()));
+		assert!(matches!(
+			build(51).check_format(),
+			Err(FormatError::IllegalMethodModifiers { .. })
+		));
+	}
+
+	#[test]
+	fn inconsistent_constant_value() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		let constantvalue_index = builder.constant_pool().add_long(1);
+		builder.add_field(
+			FieldAccessFlags::ACC_STATIC | FieldAccessFlags::ACC_FINAL,
+			"CONSTANT",
+			"I",
+			[AttributeType::ConstantValue(ConstantValue {
+				constantvalue_index,
+			})],
+		);
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::InconsistentConstantValue(String::from(
+				"CONSTANT"
+			)))
+		);
+	}
+
+	#[test]
+	fn duplicate_attributes() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		let sourcefile_index = builder.constant_pool().add_utf8("Test.java");
+		builder
+			.add_attribute(AttributeType::SourceFile(SourceFile { sourcefile_index }))
+			.add_attribute(AttributeType::SourceFile(SourceFile { sourcefile_index }));
+		assert_eq!(
+			builder.finish().check_format(),
+			Err(FormatError::DuplicateAttribute {
+				tag: AttributeTag::SourceFile,
+				location: "class",
+			})
+		);
+	}
+
+	#[test]
+	fn bad_constant_pool_references() {
+		let mut builder = new_class(ClassAccessFlags::ACC_PUBLIC);
+		let name_index = builder.constant_pool().add_integer(5);
+		builder
+			.constant_pool()
+			.add(crate::constant_pool::ConstantPoolValueInfo::Class { name_index });
+		assert!(matches!(
+			builder.finish().check_format(),
+			Err(FormatError::UnexpectedConstantPoolTag { index, .. }) if index == name_index
+		));
+
+		let mut class = new_class(ClassAccessFlags::ACC_PUBLIC).finish();
+		class.super_class = 100;
+		assert_eq!(
+			class.check_format(),
+			Err(FormatError::InvalidConstantPoolIndex(100))
+		);
+	}
+}
@@ -0,0 +1,230 @@
+//! Validation of names and descriptors (§4.2, §4.3)
+
+/// Arrays can have at most 255 dimensions (§4.3.2)
+const MAX_ARRAY_DIMENSIONS: usize = 255;
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.2.2
+/// Whether `name` is a valid unqualified name, used for fields, local variables, and formal parameters
+pub fn is_unqualified_name(name: &str) -> bool {
+	!name.is_empty() && !name.contains(['.', ';', '[', '/'])
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.2.2
+/// Whether `name` is a valid method name
+///
+/// Method names are further constrained so that, with the exception of the special method names
+/// `<init>` and `<clinit>`, they must not contain the ASCII characters `<` or `>`.
+pub fn is_method_name(name: &str) -> bool {
+	if name == "<init>" || name == "<clinit>" {
+		return true;
+	}
+
+	is_unqualified_name(name) && !name.contains(['<', '>'])
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.2.1
+/// Whether `name` is a valid binary name in internal form (`java/lang/Object`)
+pub fn is_binary_name(name: &str) -> bool {
+	name.split('/').all(is_unqualified_name)
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.1
+/// Whether `name` is valid for a `CONSTANT_Class_info` entry
+///
+/// This is either a binary name in internal form, or an array type descriptor.
+pub fn is_class_name(name: &str) -> bool {
+	if name.starts_with('[') {
+		return is_field_descriptor(name);
+	}
+
+	is_binary_name(name)
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.2.3
+/// Whether `name` is a valid module name
+///
+/// Module names are not encoded in internal form, and may contain any character other than
+/// `\`, `:`, and `@`, unless escaped by a `\`.
+pub fn is_module_name(name: &str) -> bool {
+	if name.is_empty() {
+		return false;
+	}
+
+	let mut chars = name.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' => {
+				if !matches!(chars.next(), Some('\\' | ':' | '@')) {
+					return false;
+				}
+			},
+			':' | '@' => return false,
+			_ => {},
+		}
+	}
+
+	true
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4.7
+/// Whether `bytes` is valid modified UTF-8
+///
+/// This only checks the structure of the encoding. Unpaired surrogates are allowed, since Java
+/// strings may contain them.
+pub fn is_modified_utf8(bytes: &[u8]) -> bool {
+	let is_continuation = |byte: Option<&u8>| byte.is_some_and(|b| b & 0xC0 == 0x80);
+
+	let mut bytes = bytes.iter();
+	while let Some(&byte) = bytes.next() {
+		let continuation_bytes = match byte {
+			0x01..=0x7F => 0,
+			0xC0..=0xDF => 1,
+			0xE0..=0xEF => 2,
+			// No byte may have the value (byte)0 or lie in the range (byte)0xf0 - (byte)0xff, and
+			// continuation bytes can't appear on their own
+			0x00 | 0x80..=0xBF | 0xF0..=0xFF => return false,
+		};
+
+		for _ in 0..continuation_bytes {
+			if !is_continuation(bytes.next()) {
+				return false;
+			}
+		}
+	}
+
+	true
+}
+
+/// Parse a single field type from the start of `descriptor`
+///
+/// Returns the number of local variable slots the type occupies, along with the rest of the
+/// descriptor.
+fn field_type(descriptor: &str, allow_void: bool) -> Option<(u16, &str)> {
+	let dimensions = descriptor.bytes().take_while(|b| *b == b'[').count();
+	if dimensions > MAX_ARRAY_DIMENSIONS {
+		return None;
+	}
+
+	let rest = &descriptor[dimensions..];
+	let (slots, rest) = match rest.as_bytes().first()? {
+		b'B' | b'C' | b'F' | b'I' | b'S' | b'Z' => (1, &rest[1..]),
+		b'D' | b'J' => (2, &rest[1..]),
+		b'V' if allow_void && dimensions == 0 => (0, &rest[1..]),
+		b'L' => {
+			let end = rest.find(';')?;
+			if !is_binary_name(&rest[1..end]) {
+				return None;
+			}
+
+			(1, &rest[end + 1..])
+		},
+		_ => return None,
+	};
+
+	if dimensions > 0 {
+		return Some((1, rest));
+	}
+
+	Some((slots, rest))
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.3.2
+/// Whether `descriptor` is a valid field descriptor
+pub fn is_field_descriptor(descriptor: &str) -> bool {
+	matches!(field_type(descriptor, false), Some((_, "")))
+}
+
+/// The shape of a valid method descriptor, see [`method_descriptor()`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MethodShape {
+	/// The number of local variable slots taken up by the parameters, *not* including `this`
+	pub parameter_slots: u16,
+	pub returns_void: bool,
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.3.3
+/// Validate a method descriptor
+///
+/// Returns `None` if `descriptor` is invalid.
+pub fn method_descriptor(descriptor: &str) -> Option<MethodShape> {
+	let mut rest = descriptor.strip_prefix('(')?;
+
+	let mut parameter_slots: u16 = 0;
+	while !rest.starts_with(')') {
+		let (slots, remaining) = field_type(rest, false)?;
+		parameter_slots = parameter_slots.checked_add(slots)?;
+		rest = remaining;
+	}
+
+	let (return_slots, rest) = field_type(&rest[1..], true)?;
+	if !rest.is_empty() {
+		return None;
+	}
+
+	Some(MethodShape {
+		parameter_slots,
+		returns_void: return_slots == 0,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn names() {
+		assert!(is_binary_name("java/lang/Object"));
+		assert!(!is_binary_name("java.lang.Object"));
+		assert!(!is_binary_name("java//Object"));
+		assert!(!is_binary_name(""));
+
+		assert!(is_class_name("[[Ljava/lang/String;"));
+		assert!(!is_class_name("[V"));
+		assert!(!is_class_name(&format!("{}I", "[".repeat(256))));
+
+		assert!(is_method_name("<init>"));
+		assert!(!is_method_name("<foo>"));
+		assert!(is_unqualified_name("<foo>"));
+
+		assert!(is_module_name("java.base"));
+		assert!(is_module_name("a\\:b"));
+		assert!(!is_module_name("a:b"));
+		assert!(!is_module_name("a\\"));
+	}
+
+	#[test]
+	fn modified_utf8() {
+		assert!(is_modified_utf8(b"java/lang/Object"));
+		assert!(is_modified_utf8(&[0xC0, 0x80]), "encoded null");
+		assert!(is_modified_utf8(&[0xED, 0xA0, 0x80]), "unpaired surrogate");
+		assert!(!is_modified_utf8(&[0x00]));
+		assert!(!is_modified_utf8(&[0xF0, 0x9F, 0x98, 0x80]), "4 byte form");
+		assert!(!is_modified_utf8(&[0xE0, 0x80]), "truncated");
+	}
+
+	#[test]
+	fn descriptors() {
+		assert!(is_field_descriptor("I"));
+		assert!(is_field_descriptor("[J"));
+		assert!(is_field_descriptor("Ljava/lang/Object;"));
+		assert!(!is_field_descriptor("V"));
+		assert!(!is_field_descriptor("Ljava/lang/Object"));
+		assert!(!is_field_descriptor("II"));
+
+		assert_eq!(
+			method_descriptor("(IJ[DLjava/lang/Object;)V"),
+			Some(MethodShape {
+				parameter_slots: 5,
+				returns_void: true,
+			})
+		);
+		assert_eq!(
+			method_descriptor("()[V"),
+			None,
+			"void arrays are not a thing"
+		);
+		assert_eq!(method_descriptor("(V)V"), None);
+		assert_eq!(method_descriptor("()"), None);
+		assert_eq!(method_descriptor("(I)VV"), None);
+	}
+}
@@ -1,10 +1,13 @@
 pub mod accessflags;
 pub mod attribute;
+pub mod builder;
 mod classfile;
 pub mod constant_pool;
 pub mod fieldinfo;
+pub mod format_check;
 mod methodinfo;
 pub mod parse;
+pub mod write;
 
 pub use self::classfile::ClassFile;
 pub use fieldinfo::{FieldInfo, FieldType};
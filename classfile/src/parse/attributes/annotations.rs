@@ -1,6 +1,7 @@
 use super::Location;
 use crate::attribute::{
-	Annotation, AttributeTag, AttributeType, RuntimeInvisibleAnnotations, RuntimeVisibleAnnotations,
+	Annotation, AnnotationDefault, AttributeTag, AttributeType, ElementValue,
+	RuntimeInvisibleAnnotations, RuntimeVisibleAnnotations,
 };
 use crate::constant_pool::ConstantPool;
 use crate::error::Result;
@@ -25,12 +26,10 @@ pub fn read_default<R>(
 where
 	R: Read,
 {
-	location.verify_valid(AttributeTag::AnnotationDefault, VALID_LOCATIONS)?;
-	Ok(AttributeType::RuntimeVisibleAnnotations(
-		RuntimeVisibleAnnotations {
-			annotations: read_attribute_runtime_annotations(reader, constant_pool)?,
-		},
-	))
+	location.verify_valid(AttributeTag::AnnotationDefault, &[Location::MethodInfo])?;
+	Ok(AttributeType::AnnotationDefault(AnnotationDefault {
+		default_value: ElementValue::parse(reader, constant_pool)?,
+	}))
 }
 
 /// Read `RuntimeVisibleAnnotations` attribute
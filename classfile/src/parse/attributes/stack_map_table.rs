@@ -37,6 +37,7 @@ where
 			},
 			248..=250 => StackMapFrame::ChopFrame {
 				offset_delta: reader.read_u2()?,
+				absent_locals: 251 - frame_type,
 			},
 			251 => StackMapFrame::SameFrameExtended {
 				offset_delta: reader.read_u2()?,
@@ -0,0 +1,337 @@
+use crate::attribute::{
+	Attribute, AttributeType, BootstrapMethods, Code, InnerClasses, LineNumberTable,
+	LocalVariableTable, LocalVariableTypeTable, MethodParameters, Module, Record, StackMapFrame,
+	StackMapTable, VerificationTypeInfo,
+};
+use crate::error::Result;
+
+use std::io::Write;
+
+use common::int_types::{u1, u2, u4};
+use common::traits::JavaWriteExt;
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7
+pub fn write_attribute<W>(writer: &mut W, attribute: &Attribute) -> Result<()>
+where
+	W: Write,
+{
+	// The attribute length isn't known up front, so the info is written to a buffer first
+	let mut info = Vec::new();
+	write_attribute_info(&mut info, &attribute.info)?;
+
+	writer.write_u2(attribute.attribute_name_index)?;
+	writer.write_u4(info.len() as u4)?;
+	writer.write_all(&info)?;
+
+	Ok(())
+}
+
+#[rustfmt::skip]
+fn write_attribute_info(writer: &mut Vec<u1>, info: &AttributeType) -> Result<()> {
+	match info {
+		AttributeType::ConstantValue(constant_value) => writer.write_u2(constant_value.constantvalue_index)?,
+		AttributeType::Code(code) => write_code(writer, code)?,
+		AttributeType::StackMapTable(stack_map_table) => write_stack_map_table(writer, stack_map_table)?,
+		AttributeType::Exceptions(exceptions) => write_u2_table(writer, &exceptions.exception_index_table)?,
+		AttributeType::InnerClasses(inner_classes) => write_inner_classes(writer, inner_classes)?,
+		AttributeType::EnclosingMethod(enclosing_method) => {
+			writer.write_u2(enclosing_method.class_index)?;
+			writer.write_u2(enclosing_method.method_index)?;
+		},
+		AttributeType::Synthetic | AttributeType::Deprecated => {},
+		AttributeType::Signature(signature) => writer.write_u2(signature.signature_index)?,
+		AttributeType::SourceFile(source_file) => writer.write_u2(source_file.sourcefile_index)?,
+		AttributeType::SourceDebugExtension(source_debug_extension) => writer.write_all(&source_debug_extension.debug_extension)?,
+		AttributeType::LineNumberTable(line_number_table) => write_line_number_table(writer, line_number_table)?,
+		AttributeType::LocalVariableTable(local_variable_table) => write_local_variable_table(writer, local_variable_table)?,
+		AttributeType::LocalVariableTypeTable(local_variable_type_table) => write_local_variable_type_table(writer, local_variable_type_table)?,
+		AttributeType::RuntimeVisibleAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::RuntimeInvisibleAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::RuntimeVisibleParameterAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::RuntimeInvisibleParameterAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::RuntimeVisibleTypeAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::RuntimeInvisibleTypeAnnotations(annotations) => writer.write_all(&annotations.as_bytes())?,
+		AttributeType::AnnotationDefault(annotation_default) => writer.write_all(&annotation_default.as_bytes())?,
+		AttributeType::BootstrapMethods(bootstrap_methods) => write_bootstrap_methods(writer, bootstrap_methods)?,
+		AttributeType::MethodParameters(method_parameters) => write_method_parameters(writer, method_parameters)?,
+		AttributeType::Module(module) => write_module(writer, module)?,
+		AttributeType::ModulePackages(module_packages) => write_u2_table(writer, &module_packages.package_index)?,
+		AttributeType::ModuleMainClass(module_main_class) => writer.write_u2(module_main_class.main_class_index)?,
+		AttributeType::NestHost(nest_host) => writer.write_u2(nest_host.host_class_index)?,
+		AttributeType::NestMembers(nest_members) => write_u2_table(writer, &nest_members.classes)?,
+		AttributeType::Record(record) => write_record(writer, record)?,
+		AttributeType::PermittedSubclasses(permitted_subclasses) => write_u2_table(writer, &permitted_subclasses.classes)?,
+	}
+
+	Ok(())
+}
+
+/// Write a `u2` count, followed by the `u2` entries
+fn write_u2_table(writer: &mut Vec<u1>, table: &[u2]) -> Result<()> {
+	writer.write_u2(table.len() as u2)?;
+	for entry in table {
+		writer.write_u2(*entry)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.3
+fn write_code(writer: &mut Vec<u1>, code: &Code) -> Result<()> {
+	writer.write_u2(code.max_stack)?;
+	writer.write_u2(code.max_locals)?;
+
+	writer.write_u4(code.code.len() as u4)?;
+	writer.write_all(&code.code)?;
+
+	writer.write_u2(code.exception_table.len() as u2)?;
+	for exception in &code.exception_table {
+		writer.write_u2(exception.start_pc)?;
+		writer.write_u2(exception.end_pc)?;
+		writer.write_u2(exception.handler_pc)?;
+		writer.write_u2(exception.catch_type)?;
+	}
+
+	writer.write_u2(code.attributes.len() as u2)?;
+	for attribute in &code.attributes {
+		write_attribute(writer, attribute)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.4
+fn write_stack_map_table(writer: &mut Vec<u1>, stack_map_table: &StackMapTable) -> Result<()> {
+	writer.write_u2(stack_map_table.entries.len() as u2)?;
+
+	for frame in &stack_map_table.entries {
+		match frame {
+			StackMapFrame::SameFrame { offset_delta } => writer.write_u1(*offset_delta as u1)?,
+			StackMapFrame::SameLocals1StackItemFrame {
+				offset_delta,
+				verification_type_info: [verification_type_info],
+			} => {
+				writer.write_u1(64 + *offset_delta as u1)?;
+				write_verification_type_info(writer, verification_type_info)?;
+			},
+			StackMapFrame::SameLocals1StackItemFrameExtended {
+				offset_delta,
+				verification_type_info: [verification_type_info],
+			} => {
+				writer.write_u1(247)?;
+				writer.write_u2(*offset_delta)?;
+				write_verification_type_info(writer, verification_type_info)?;
+			},
+			StackMapFrame::ChopFrame {
+				offset_delta,
+				absent_locals,
+			} => {
+				writer.write_u1(251 - *absent_locals)?;
+				writer.write_u2(*offset_delta)?;
+			},
+			StackMapFrame::SameFrameExtended { offset_delta } => {
+				writer.write_u1(251)?;
+				writer.write_u2(*offset_delta)?;
+			},
+			StackMapFrame::AppendFrame {
+				offset_delta,
+				locals,
+			} => {
+				writer.write_u1(251 + locals.len() as u1)?;
+				writer.write_u2(*offset_delta)?;
+				for local in locals {
+					write_verification_type_info(writer, local)?;
+				}
+			},
+			StackMapFrame::FullFrame {
+				offset_delta,
+				locals,
+				stack,
+			} => {
+				writer.write_u1(255)?;
+				writer.write_u2(*offset_delta)?;
+
+				writer.write_u2(locals.len() as u2)?;
+				for local in locals {
+					write_verification_type_info(writer, local)?;
+				}
+
+				writer.write_u2(stack.len() as u2)?;
+				for item in stack {
+					write_verification_type_info(writer, item)?;
+				}
+			},
+		}
+	}
+
+	Ok(())
+}
+
+fn write_verification_type_info(
+	writer: &mut Vec<u1>,
+	verification_type_info: &VerificationTypeInfo,
+) -> Result<()> {
+	match verification_type_info {
+		VerificationTypeInfo::TopVariableInfo => writer.write_u1(0)?,
+		VerificationTypeInfo::IntegerVariableInfo => writer.write_u1(1)?,
+		VerificationTypeInfo::FloatVariableInfo => writer.write_u1(2)?,
+		VerificationTypeInfo::DoubleVariableInfo => writer.write_u1(3)?,
+		VerificationTypeInfo::LongVariableInfo => writer.write_u1(4)?,
+		VerificationTypeInfo::NullVariableInfo => writer.write_u1(5)?,
+		VerificationTypeInfo::UninitializedThisVariableInfo => writer.write_u1(6)?,
+		VerificationTypeInfo::ObjectVariableInfo { cpool_index } => {
+			writer.write_u1(7)?;
+			writer.write_u2(*cpool_index)?;
+		},
+		VerificationTypeInfo::UninitializedVariableInfo { offset } => {
+			writer.write_u1(8)?;
+			writer.write_u2(*offset)?;
+		},
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.6
+fn write_inner_classes(writer: &mut Vec<u1>, inner_classes: &InnerClasses) -> Result<()> {
+	writer.write_u2(inner_classes.classes.len() as u2)?;
+	for inner_class in &inner_classes.classes {
+		writer.write_u2(inner_class.inner_class_info_index)?;
+		writer.write_u2(inner_class.outer_class_info_index)?;
+		writer.write_u2(inner_class.inner_name_index)?;
+		writer.write_u2(inner_class.inner_class_access_flags)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.12
+fn write_line_number_table(
+	writer: &mut Vec<u1>,
+	line_number_table: &LineNumberTable,
+) -> Result<()> {
+	writer.write_u2(line_number_table.line_number_table.len() as u2)?;
+	for line_number in &line_number_table.line_number_table {
+		writer.write_u2(line_number.start_pc)?;
+		writer.write_u2(line_number.line_number)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.13
+fn write_local_variable_table(
+	writer: &mut Vec<u1>,
+	local_variable_table: &LocalVariableTable,
+) -> Result<()> {
+	writer.write_u2(local_variable_table.local_variable_table.len() as u2)?;
+	for local_variable in &local_variable_table.local_variable_table {
+		writer.write_u2(local_variable.start_pc)?;
+		writer.write_u2(local_variable.length)?;
+		writer.write_u2(local_variable.name_index)?;
+		writer.write_u2(local_variable.descriptor_index)?;
+		writer.write_u2(local_variable.index)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.14
+fn write_local_variable_type_table(
+	writer: &mut Vec<u1>,
+	local_variable_type_table: &LocalVariableTypeTable,
+) -> Result<()> {
+	writer.write_u2(local_variable_type_table.local_variable_type_table.len() as u2)?;
+	for local_variable_type in &local_variable_type_table.local_variable_type_table {
+		writer.write_u2(local_variable_type.start_pc)?;
+		writer.write_u2(local_variable_type.length)?;
+		writer.write_u2(local_variable_type.name_index)?;
+		writer.write_u2(local_variable_type.signature_index)?;
+		writer.write_u2(local_variable_type.index)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.23
+fn write_bootstrap_methods(
+	writer: &mut Vec<u1>,
+	bootstrap_methods: &BootstrapMethods,
+) -> Result<()> {
+	writer.write_u2(bootstrap_methods.bootstrap_methods.len() as u2)?;
+	for bootstrap_method in &bootstrap_methods.bootstrap_methods {
+		writer.write_u2(bootstrap_method.bootstrap_method_ref)?;
+		write_u2_table(writer, &bootstrap_method.bootstrap_arguments)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.24
+fn write_method_parameters(
+	writer: &mut Vec<u1>,
+	method_parameters: &MethodParameters,
+) -> Result<()> {
+	// Note that `parameters_count` is a `u1`
+	writer.write_u1(method_parameters.parameters.len() as u1)?;
+	for parameter in &method_parameters.parameters {
+		writer.write_u2(parameter.name_index)?;
+		writer.write_u2(parameter.access_flags)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.25
+fn write_module(writer: &mut Vec<u1>, module: &Module) -> Result<()> {
+	writer.write_u2(module.module_name_index)?;
+	writer.write_u2(module.module_flags)?;
+	writer.write_u2(module.module_version_index)?;
+
+	writer.write_u2(module.requires.len() as u2)?;
+	for require in &module.requires {
+		writer.write_u2(require.requires_index)?;
+		writer.write_u2(require.requires_flags)?;
+		writer.write_u2(require.requires_version_index)?;
+	}
+
+	writer.write_u2(module.exports.len() as u2)?;
+	for export in &module.exports {
+		writer.write_u2(export.exports_index)?;
+		writer.write_u2(export.exports_flags)?;
+		write_u2_table(writer, &export.exports_to_index)?;
+	}
+
+	writer.write_u2(module.opens.len() as u2)?;
+	for open in &module.opens {
+		writer.write_u2(open.opens_index)?;
+		writer.write_u2(open.opens_flags)?;
+		write_u2_table(writer, &open.opens_to_index)?;
+	}
+
+	write_u2_table(writer, &module.uses_index)?;
+
+	writer.write_u2(module.provides.len() as u2)?;
+	for provide in &module.provides {
+		writer.write_u2(provide.provides_index)?;
+		write_u2_table(writer, &provide.provides_with_index)?;
+	}
+
+	Ok(())
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.30
+fn write_record(writer: &mut Vec<u1>, record: &Record) -> Result<()> {
+	writer.write_u2(record.components.len() as u2)?;
+	for component in &record.components {
+		writer.write_u2(component.name_index)?;
+		writer.write_u2(component.descriptor_index)?;
+
+		writer.write_u2(component.attributes.len() as u2)?;
+		for attribute in &component.attributes {
+			write_attribute(writer, attribute)?;
+		}
+	}
+
+	Ok(())
+}
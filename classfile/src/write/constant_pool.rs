@@ -0,0 +1,98 @@
+use crate::constant_pool::{ConstantPool, ConstantPoolTag, ConstantPoolValueInfo};
+use crate::error::Result;
+
+use std::io::Write;
+
+use common::int_types::u2;
+use common::traits::JavaWriteExt;
+
+pub fn write_cp_info<W>(writer: &mut W, constant_pool: &ConstantPool) -> Result<()>
+where
+	W: Write,
+{
+	// The constant pool includes the unusable entry at index 0, as well as the unusable entries
+	// following longs and doubles, so its length is exactly `constant_pool_count`.
+	writer.write_u2(constant_pool.len() as u2)?;
+
+	for cp_value_info in constant_pool.iter() {
+		let tag = cp_value_info.tag();
+
+		// Index 0 and the second slot of 8-byte constants have no representation in the class file
+		if tag == ConstantPoolTag::Unusable {
+			continue;
+		}
+
+		writer.write_u1(tag as u8)?;
+
+		match cp_value_info {
+			ConstantPoolValueInfo::Class { name_index }
+			| ConstantPoolValueInfo::Module { name_index }
+			| ConstantPoolValueInfo::Package { name_index } => writer.write_u2(*name_index)?,
+			ConstantPoolValueInfo::Fieldref {
+				class_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::Methodref {
+				class_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::InterfaceMethodref {
+				class_index,
+				name_and_type_index,
+			} => {
+				writer.write_u2(*class_index)?;
+				writer.write_u2(*name_and_type_index)?;
+			},
+			ConstantPoolValueInfo::String { string_index } => writer.write_u2(*string_index)?,
+			ConstantPoolValueInfo::Integer { bytes } | ConstantPoolValueInfo::Float { bytes } => {
+				writer.write_u4(*bytes)?;
+			},
+			ConstantPoolValueInfo::Long {
+				high_bytes,
+				low_bytes,
+			}
+			| ConstantPoolValueInfo::Double {
+				high_bytes,
+				low_bytes,
+			} => {
+				writer.write_u4(*high_bytes)?;
+				writer.write_u4(*low_bytes)?;
+			},
+			ConstantPoolValueInfo::NameAndType {
+				name_index,
+				descriptor_index,
+			} => {
+				writer.write_u2(*name_index)?;
+				writer.write_u2(*descriptor_index)?;
+			},
+			ConstantPoolValueInfo::Utf8 { bytes, .. } => {
+				writer.write_u2(bytes.len() as u2)?;
+				writer.write_all(bytes)?;
+			},
+			ConstantPoolValueInfo::MethodHandle {
+				reference_kind,
+				reference_index,
+			} => {
+				writer.write_u1(*reference_kind)?;
+				writer.write_u2(*reference_index)?;
+			},
+			ConstantPoolValueInfo::MethodType { descriptor_index } => {
+				writer.write_u2(*descriptor_index)?;
+			},
+			ConstantPoolValueInfo::Dynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::InvokeDynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			} => {
+				writer.write_u2(*bootstrap_method_attr_index)?;
+				writer.write_u2(*name_and_type_index)?;
+			},
+			ConstantPoolValueInfo::Unusable => unreachable!("unusable entries are skipped"),
+		}
+	}
+
+	Ok(())
+}
@@ -0,0 +1,24 @@
+use super::attributes::write_attribute;
+use crate::error::Result;
+use crate::fieldinfo::FieldInfo;
+
+use std::io::Write;
+
+use common::int_types::u2;
+use common::traits::JavaWriteExt;
+
+pub fn write_field_info<W>(writer: &mut W, field: &FieldInfo) -> Result<()>
+where
+	W: Write,
+{
+	writer.write_u2(field.access_flags.as_u2())?;
+	writer.write_u2(field.name_index)?;
+	writer.write_u2(field.descriptor_index)?;
+
+	writer.write_u2(field.attributes.len() as u2)?;
+	for attribute in &field.attributes {
+		write_attribute(writer, attribute)?;
+	}
+
+	Ok(())
+}
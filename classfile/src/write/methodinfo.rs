@@ -0,0 +1,24 @@
+use super::attributes::write_attribute;
+use crate::error::Result;
+use crate::methodinfo::MethodInfo;
+
+use std::io::Write;
+
+use common::int_types::u2;
+use common::traits::JavaWriteExt;
+
+pub fn write_method_info<W>(writer: &mut W, method: &MethodInfo) -> Result<()>
+where
+	W: Write,
+{
+	writer.write_u2(method.access_flags.as_u2())?;
+	writer.write_u2(method.name_index)?;
+	writer.write_u2(method.descriptor_index)?;
+
+	writer.write_u2(method.attributes.len() as u2)?;
+	for attribute in &method.attributes {
+		write_attribute(writer, attribute)?;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,97 @@
+mod attributes;
+mod constant_pool;
+mod fieldinfo;
+mod methodinfo;
+
+use crate::classfile::ClassFile;
+use crate::error::Result;
+
+use std::io::Write;
+
+use common::int_types::u2;
+use common::traits::JavaWriteExt;
+
+use attributes::write_attribute;
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.1
+pub fn write_class<W>(writer: &mut W, class: &ClassFile) -> Result<()>
+where
+	W: Write,
+{
+	writer.write_u4(0xCAFE_BABE)?;
+
+	writer.write_u2(class.minor_version)?;
+	writer.write_u2(class.major_version)?;
+
+	constant_pool::write_cp_info(writer, &class.constant_pool)?;
+
+	writer.write_u2(class.access_flags.as_u2())?;
+	writer.write_u2(class.this_class)?;
+	writer.write_u2(class.super_class)?;
+
+	writer.write_u2(class.interfaces.len() as u2)?;
+	for interface in &class.interfaces {
+		writer.write_u2(*interface)?;
+	}
+
+	writer.write_u2(class.fields.len() as u2)?;
+	for field in &class.fields {
+		fieldinfo::write_field_info(writer, field)?;
+	}
+
+	writer.write_u2(class.methods.len() as u2)?;
+	for method in &class.methods {
+		methodinfo::write_method_info(writer, method)?;
+	}
+
+	writer.write_u2(class.attributes.len() as u2)?;
+	for attribute in &class.attributes {
+		write_attribute(writer, attribute)?;
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::ClassFile;
+
+	macro_rules! fixtures {
+		($($name:literal),+ $(,)?) => {
+			[$(($name, include_bytes!(concat!("../../tests/data/", $name)))),+]
+		};
+	}
+
+	// Compiled from `tests/data/RoundTrip.java` and `tests/data/module/module-info.java`
+	const FIXTURES: [(&str, &[u8]); 8] = fixtures!(
+		"RoundTrip.class",
+		"RoundTrip$1.class",
+		"RoundTrip$Color.class",
+		"RoundTrip$Invisible.class",
+		"RoundTrip$Point.class",
+		"RoundTrip$Sub.class",
+		"RoundTrip$Visible.class",
+		"module/module-info.class",
+	);
+
+	#[test]
+	fn round_trip_is_lossless() {
+		for (name, bytes) in FIXTURES {
+			let class = ClassFile::read_from(&mut &bytes[..]).unwrap();
+			assert_eq!(class.to_bytes(), bytes, "{name} changed after a round trip");
+		}
+	}
+
+	#[test]
+	fn round_trip_parses_equal() {
+		for (name, bytes) in FIXTURES {
+			let class = ClassFile::read_from(&mut &bytes[..]).unwrap();
+			let written = class.to_bytes();
+			let reparsed = ClassFile::read_from(&mut &written[..]).unwrap();
+			assert!(
+				class == reparsed,
+				"{name} parsed differently after a round trip"
+			);
+		}
+	}
+}
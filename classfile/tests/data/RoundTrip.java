@@ -0,0 +1,81 @@
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.function.Function;
+
+// Exercises as many attributes as javac will produce, for the writer round-trip tests.
+// Compiled with `javac -g -parameters RoundTrip.java`.
+public sealed class RoundTrip<T extends Comparable<T>> permits RoundTrip.Sub {
+	@Retention(RetentionPolicy.RUNTIME)
+	@Target({ElementType.METHOD, ElementType.TYPE_USE, ElementType.PARAMETER})
+	@interface Visible {
+		String value() default "default";
+		int[] numbers() default {1, 2, 3};
+		ElementType kind() default ElementType.METHOD;
+		Class<?> type() default Object.class;
+	}
+
+	@Retention(RetentionPolicy.CLASS)
+	@interface Invisible {}
+
+	enum Color { RED, GREEN, BLUE }
+
+	record Point(int x, @Visible("component") int y) {}
+
+	static final class Sub extends RoundTrip<String> {}
+
+	static final long LONG_CONSTANT = 0x1234_5678_9ABC_DEF0L;
+	static final double DOUBLE_CONSTANT = 3.14159;
+	static final float FLOAT_CONSTANT = 2.5f;
+	static final String STRING_CONSTANT = "hello";
+
+	private final List<@Visible T> values = new ArrayList<>();
+
+	@Visible(value = "method", numbers = {4}, kind = ElementType.TYPE_USE, type = String.class)
+	@Invisible
+	public int sum(@Visible("param") int a, @Invisible int b) throws IllegalStateException {
+		int total = 0;
+		for (int i = a; i < b; i++) {
+			if (i % 2 == 0) {
+				total += i;
+			} else {
+				total -= 1;
+			}
+		}
+		return total;
+	}
+
+	public String describe(Object o) {
+		switch (o.hashCode() & 3) {
+			case 0: return "zero";
+			case 1: return "one";
+			case 1000: return "big";
+			default: break;
+		}
+
+		try {
+			synchronized (this) {
+				return String.valueOf(o);
+			}
+		} catch (RuntimeException e) {
+			return e.getMessage();
+		} finally {
+			values.clear();
+		}
+	}
+
+	public Function<String, Integer> lambda(int offset) {
+		Runnable local = new Runnable() {
+			public void run() {}
+		};
+		local.run();
+		return s -> s.length() + offset + values.size();
+	}
+
+	public String concat(String a, long b, double c) {
+		return a + b + c + LONG_CONSTANT;
+	}
+}
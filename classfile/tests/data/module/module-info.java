@@ -0,0 +1,9 @@
+// Compiled with `javac -g module-info.java roundtrip/Service.java`.
+module roundtrip {
+	requires java.logging;
+	requires transitive java.compiler;
+	exports roundtrip;
+	opens roundtrip to java.logging;
+	uses roundtrip.Service;
+	provides roundtrip.Service with roundtrip.Service.Impl;
+}
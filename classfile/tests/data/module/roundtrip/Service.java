@@ -0,0 +1,5 @@
+package roundtrip;
+
+public interface Service {
+	final class Impl implements Service {}
+}
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use crate::int_types::{s4, u1, u2, u4, u8};
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 
@@ -86,6 +86,43 @@ pub trait JavaReadExt: Read {
 
 impl<R: Read> JavaReadExt for R {}
 
+/// Big endian write operations for Java integer types
+///
+/// This is the counterpart to [`JavaReadExt`].
+pub trait JavaWriteExt: Write {
+	/// Write a 1-byte unsigned integer to `self`
+	///
+	/// # Errors
+	///
+	/// See [`Write::write_all()`]
+	fn write_u1(&mut self, value: u1) -> Result<()> {
+		self.write_all(&[value])?;
+		Ok(())
+	}
+
+	/// Write a 2-byte, big-endian unsigned integer to `self`
+	///
+	/// # Errors
+	///
+	/// See [`Write::write_all()`]
+	fn write_u2(&mut self, value: u2) -> Result<()> {
+		self.write_all(&value.to_be_bytes())?;
+		Ok(())
+	}
+
+	/// Write a 4-byte, big-endian unsigned integer to `self`
+	///
+	/// # Errors
+	///
+	/// See [`Write::write_all()`]
+	fn write_u4(&mut self, value: u4) -> Result<()> {
+		self.write_all(&value.to_be_bytes())?;
+		Ok(())
+	}
+}
+
+impl<W: Write> JavaWriteExt for W {}
+
 /// Little endian read operations for Java integer types
 ///
 /// See [`JavaReadExt`] for the big endian counterpart.
@@ -18,7 +18,7 @@ use crate::native::intrinsics::IntrinsicFlags;
 use classfile::accessflags::MethodAccessFlags;
 use ::common::int_types::u1;
 
-const NUMBER_OF_INTRINSICS: u8 = {};
+pub(in crate::native) const NUMBER_OF_INTRINSICS: usize = {};
 "
 	};
 }
@@ -184,6 +184,13 @@ impl IntrinsicId {
 			};
 		}
 
+		// The ID is only unique if the name and signature fit into their own bits, which is only guaranteed for
+		// pre-interned symbols. Any others can't belong to an intrinsic anyway.
+		const SYMBOL_LIMIT: u32 = 1 << crate::symbols::Symbol::PRE_INTERNED_LIMIT_LOG2;
+		if method_name.as_u32() >= SYMBOL_LIMIT || signature.as_u32() >= SYMBOL_LIMIT {
+			return IntrinsicId::None;
+		}
+
 		match intrinsics_id3!(class, method_name, signature) {
 ",
 	);
@@ -53,9 +53,8 @@ impl Method {
 		};
 
 		if append_params {
-			ret = format!("{ret}_");
 			for param in &self.params {
-				write!(ret, "{}", param.0.human_readable_name()).unwrap();
+				write!(ret, "_{}", param.0.human_readable_name()).unwrap();
 			}
 		}
 
@@ -138,7 +137,7 @@ impl Method {
 		}
 
 		if self.modifiers.contains(STATIC_NATIVE) && !self.modifiers.contains(SYNCHRONIZED) {
-			return "IntrinsicFlags::Native";
+			return "IntrinsicFlags::StaticNative";
 		}
 
 		panic!("Method contains no relevant modifiers, see `IntrinsicFlags`");
@@ -197,7 +197,7 @@ pub fn define_symbols(input: TokenStream) -> TokenStream {
 		let value_str = symbol.str();
 
 		symbol_value_stream.extend(quote! {
-			&#value_bstr[..],
+			#value_bstr,
 		});
 
 		symbol_const_stream.extend(quote! {
@@ -218,13 +218,10 @@ pub fn define_symbols(input: TokenStream) -> TokenStream {
 			#symbol_const_stream
 		}
 
-		impl SymbolInterner {
-			fn preintern(&mut self) {
-				self.set.extend(&[
-					#symbol_value_stream
-				]);
-			}
-		}
+		/// The strings for every symbol in `generated_symbols`, in index order
+		static PREINTERNED_SYMBOLS: [&[u8]; PREINTERED_SYMBOLS_COUNT as usize] = [
+			#symbol_value_stream
+		];
 	}
 	.into()
 }
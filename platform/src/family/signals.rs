@@ -1,5 +1,13 @@
 use std::borrow::Cow;
 use std::ffi::c_void;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Upper bound (exclusive) for signal numbers that can be marked as pending
+const MAX_PENDING_SIGNAL: usize = 65;
+
+/// The number of times each signal has been raised, but not yet consumed by [`Signal::wait_pending()`]
+static PENDING_SIGNALS: [AtomicU32; MAX_PENDING_SIGNAL] =
+	[const { AtomicU32::new(0) }; MAX_PENDING_SIGNAL];
 
 /// A generic signal handler implementation
 ///
@@ -59,6 +67,18 @@ impl Signal {
 		<Self as SignalOsExt>::registration_allowed_impl(self)
 	}
 
+	/// Whether this `Signal` is currently being ignored by the process
+	pub fn is_ignored(self) -> bool {
+		<Self as SignalOsExt>::is_ignored_impl(&self)
+	}
+
+	/// Send this `Signal` to the current process
+	///
+	/// This returns `false` if the signal could not be raised.
+	pub fn raise(self) -> bool {
+		<Self as SignalOsExt>::raise_impl(self)
+	}
+
 	/// Install `handler` as the handler for this `Signal`
 	///
 	/// # Safety
@@ -67,6 +87,40 @@ impl Signal {
 	pub unsafe fn install<T: Into<SignalHandler>>(self, handler: T) -> Option<SignalHandler> {
 		unsafe { <Self as SignalOsExt>::install_impl(self, handler.into()) }
 	}
+
+	/// Mark this `Signal` as pending, waking up a thread blocked in [`Signal::wait_pending()`]
+	///
+	/// This is async-signal-safe, and is what the [user handler](SignalHandler::user_handler) does
+	/// upon receiving a signal.
+	pub fn notify(self) {
+		let Some(count) = usize::try_from(self.0)
+			.ok()
+			.and_then(|idx| PENDING_SIGNALS.get(idx))
+		else {
+			return;
+		};
+
+		count.fetch_add(1, Ordering::AcqRel);
+		super::imp::signals::post_pending();
+	}
+
+	/// Block the current thread until a signal is pending, and consume it
+	///
+	/// Signals are only ever made pending through [`Signal::notify()`].
+	pub fn wait_pending() -> Signal {
+		loop {
+			for (signal, count) in PENDING_SIGNALS.iter().enumerate() {
+				if count
+					.fetch_update(Ordering::AcqRel, Ordering::Acquire, |c| c.checked_sub(1))
+					.is_ok()
+				{
+					return Signal(signal as i32);
+				}
+			}
+
+			super::imp::signals::wait_pending();
+		}
+	}
 }
 
 impl From<i32> for Signal {
@@ -78,6 +132,8 @@ impl From<i32> for Signal {
 pub trait SignalOsExt: Sized {
 	fn from_name_impl(name: Cow<'_, str>) -> Option<Self>;
 	fn registration_allowed_impl(self) -> bool;
+	fn is_ignored_impl(&self) -> bool;
+	fn raise_impl(self) -> bool;
 
 	/// OS-specific signal handler installation
 	///
@@ -0,0 +1,22 @@
+/// [**UNIX**] Get the number of CPUs that the process may run on
+///
+/// On Linux, this respects the CPU affinity mask (e.g. `taskset`), but *not* any cgroup limits. See
+/// [`Cgroup`] for those.
+///
+/// [`Cgroup`]: crate::linux::container::Cgroup
+pub fn processor_count() -> usize {
+	#[cfg(target_os = "linux")]
+	{
+		let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+		let ret = unsafe { libc::sched_getaffinity(0, size_of::<libc::cpu_set_t>(), &raw mut set) };
+		if ret == 0 {
+			let count = unsafe { libc::CPU_COUNT(&set) };
+			if count > 0 {
+				return count as usize;
+			}
+		}
+	}
+
+	let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+	usize::try_from(count).unwrap_or(1).max(1)
+}
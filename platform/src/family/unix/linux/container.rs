@@ -0,0 +1,471 @@
+//! [**LINUX**] Resource limits imposed through cgroups
+//!
+//! Container runtimes (Docker, Kubernetes, systemd, ...) restrict processes through cgroups, which
+//! aren't reflected in the host-wide numbers from `sysconf`. The limits are read from the cgroup
+//! filesystem, located through `/proc/self/mountinfo` and `/proc/self/cgroup`.
+//!
+//! Both the v1 (one hierarchy per controller) and v2 (unified hierarchy) layouts are supported.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// The layout of the cgroup filesystem
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CgroupVersion {
+	V1,
+	V2,
+}
+
+/// The cgroup controllers that the current process belongs to
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cgroup {
+	version: CgroupVersion,
+	cpu: Option<Controller>,
+	cpuset: Option<Controller>,
+	memory: Option<Controller>,
+	read_only: bool,
+}
+
+/// A single controller's directory for the process, and the mount point it lives under
+#[derive(Debug, PartialEq, Eq)]
+struct Controller {
+	mount_point: PathBuf,
+	path: PathBuf,
+}
+
+impl Controller {
+	fn new(mount_root: &str, mount_point: &str, cgroup_path: &str) -> Self {
+		// Our cgroup path is relative to the root of the hierarchy, which may not be the root of the
+		// mount. Inside a container, the mount root is usually our own cgroup.
+		let relative = if mount_root == "/" {
+			cgroup_path
+		} else {
+			cgroup_path
+				.strip_prefix(mount_root)
+				.filter(|relative| relative.is_empty() || relative.starts_with('/'))
+				.unwrap_or_default()
+		};
+
+		let mut path = PathBuf::from(mount_point);
+		let relative = relative.trim_start_matches('/');
+		if !relative.is_empty() {
+			path.push(relative);
+		}
+
+		Self {
+			mount_point: PathBuf::from(mount_point),
+			path,
+		}
+	}
+
+	/// The cgroup directory, followed by each of its ancestors up to the mount point
+	///
+	/// Limits set on a parent apply to all of its children, so the effective limit is the
+	/// smallest of the bunch.
+	fn hierarchy(&self) -> impl Iterator<Item = &Path> {
+		self.path
+			.ancestors()
+			.take_while(|path| path.starts_with(&self.mount_point))
+	}
+
+	fn read(dir: &Path, file: &str) -> Option<String> {
+		let contents = fs::read_to_string(dir.join(file)).ok()?;
+		Some(contents.trim().to_string())
+	}
+}
+
+impl Cgroup {
+	/// [**LINUX**] Get the cgroup of the current process
+	///
+	/// This returns `None` if no cgroup filesystem is mounted, or none of the cpu, cpuset, or
+	/// memory controllers are available. The layout doesn't change during execution, so the
+	/// result is cached after the first call.
+	pub fn current() -> Option<&'static Self> {
+		static ONCE: OnceLock<Option<Cgroup>> = OnceLock::new();
+		ONCE.get_or_init(|| {
+			let mountinfo = fs::read_to_string("/proc/self/mountinfo").ok()?;
+			let cgroups = fs::read_to_string("/proc/self/cgroup").ok()?;
+			Self::from_proc(&mountinfo, &cgroups)
+		})
+		.as_ref()
+	}
+
+	/// [**LINUX**] Locate the controllers described by the contents of `/proc/self/mountinfo` and
+	/// `/proc/self/cgroup`
+	///
+	/// The v1 controllers take precedence on hybrid systems, where both layouts are mounted.
+	pub fn from_proc(mountinfo: &str, cgroups: &str) -> Option<Self> {
+		let mounts = mountinfo
+			.lines()
+			.filter_map(Mount::parse)
+			.collect::<Vec<_>>();
+
+		let v1 = Self::v1(&mounts, cgroups);
+		if v1.has_controllers() {
+			return Some(v1);
+		}
+
+		let v2 = Self::v2(&mounts, cgroups)?;
+		v2.has_controllers().then_some(v2)
+	}
+
+	fn v1(mounts: &[Mount<'_>], cgroups: &str) -> Self {
+		let mut cgroup = Self {
+			version: CgroupVersion::V1,
+			cpu: None,
+			cpuset: None,
+			memory: None,
+			read_only: false,
+		};
+
+		// Entries are in the form `hierarchy-ID:controller-list:cgroup-path`
+		for line in cgroups.lines() {
+			let mut parts = line.splitn(3, ':');
+			let (Some(_), Some(controllers), Some(path)) =
+				(parts.next(), parts.next(), parts.next())
+			else {
+				continue;
+			};
+
+			for controller in controllers.split(',') {
+				let slot = match controller {
+					"cpu" => &mut cgroup.cpu,
+					"cpuset" => &mut cgroup.cpuset,
+					"memory" => &mut cgroup.memory,
+					_ => continue,
+				};
+
+				let Some(mount) = mounts
+					.iter()
+					.find(|mount| mount.fs_type == "cgroup" && mount.has_controller(controller))
+				else {
+					continue;
+				};
+
+				cgroup.read_only |= mount.read_only;
+				*slot = Some(Controller::new(mount.root, mount.mount_point, path));
+			}
+		}
+
+		cgroup
+	}
+
+	fn v2(mounts: &[Mount<'_>], cgroups: &str) -> Option<Self> {
+		let mount = mounts.iter().find(|mount| mount.fs_type == "cgroup2")?;
+
+		// The unified hierarchy has a single entry, in the form `0::cgroup-path`
+		let path = cgroups.lines().find_map(|line| line.strip_prefix("0::"))?;
+
+		// Only the controllers enabled by the parent are listed
+		let controller = Controller::new(mount.root, mount.mount_point, path);
+		let enabled = Controller::read(&controller.path, "cgroup.controllers").unwrap_or_default();
+		let is_enabled = |name| enabled.split_whitespace().any(|c| c == name);
+
+		let new = || Controller::new(mount.root, mount.mount_point, path);
+		Some(Self {
+			version: CgroupVersion::V2,
+			cpu: is_enabled("cpu").then(new),
+			cpuset: is_enabled("cpuset").then(new),
+			memory: is_enabled("memory").then(new),
+			read_only: mount.read_only,
+		})
+	}
+
+	fn has_controllers(&self) -> bool {
+		self.cpu.is_some() || self.cpuset.is_some() || self.memory.is_some()
+	}
+
+	/// [**LINUX**] The layout of the cgroup filesystem
+	pub fn version(&self) -> CgroupVersion {
+		self.version
+	}
+
+	/// [**LINUX**] Whether the cgroup filesystem is mounted read-only
+	///
+	/// Container runtimes mount it read-only, so this is a good indicator that we are running in a
+	/// container, even if no limits are set.
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+
+	/// [**LINUX**] The number of CPUs that the CFS quota allows for, rounded up
+	///
+	/// This returns `None` if there is no quota.
+	pub fn cpu_quota(&self) -> Option<u32> {
+		let controller = self.cpu.as_ref()?;
+		controller
+			.hierarchy()
+			.filter_map(|dir| {
+				let (quota, period) = match self.version {
+					CgroupVersion::V1 => (
+						Controller::read(dir, "cpu.cfs_quota_us")?,
+						Controller::read(dir, "cpu.cfs_period_us")?,
+					),
+					// `$MAX $PERIOD`, where `$MAX` may be "max"
+					CgroupVersion::V2 => {
+						let max = Controller::read(dir, "cpu.max")?;
+						let (quota, period) = max.split_once(' ')?;
+						(quota.to_string(), period.to_string())
+					},
+				};
+
+				// A negative quota (or "max") means no limit
+				let quota = quota.parse::<u64>().ok()?;
+				let period = period.parse::<u64>().ok().filter(|period| *period > 0)?;
+				u32::try_from(quota.div_ceil(period)).ok()
+			})
+			.min()
+			.map(|count| count.max(1))
+	}
+
+	/// [**LINUX**] The number of CPUs in the cgroup's cpuset
+	///
+	/// This returns `None` if the cpuset is unavailable or empty.
+	pub fn cpuset_count(&self) -> Option<u32> {
+		let controller = self.cpuset.as_ref()?;
+		let cpus = match self.version {
+			CgroupVersion::V1 => Controller::read(&controller.path, "cpuset.cpus")?,
+			CgroupVersion::V2 => Controller::read(&controller.path, "cpuset.cpus.effective")
+				.filter(|cpus| !cpus.is_empty())
+				.or_else(|| Controller::read(&controller.path, "cpuset.cpus"))?,
+		};
+
+		parse_cpu_list(&cpus).filter(|count| *count > 0)
+	}
+
+	/// [**LINUX**] The memory limit of the cgroup, in bytes
+	///
+	/// This returns `None` if there is no limit. Note that the "unlimited" value of v1 is just a
+	/// very large number, so the caller should ignore limits above the physical memory.
+	pub fn memory_limit(&self) -> Option<u64> {
+		let controller = self.memory.as_ref()?;
+		let file = match self.version {
+			CgroupVersion::V1 => "memory.limit_in_bytes",
+			CgroupVersion::V2 => "memory.max",
+		};
+
+		controller
+			.hierarchy()
+			.filter_map(|dir| Controller::read(dir, file)?.parse::<u64>().ok())
+			.min()
+	}
+
+	/// [**LINUX**] The amount of memory currently used by the cgroup, in bytes
+	pub fn memory_usage(&self) -> Option<u64> {
+		let controller = self.memory.as_ref()?;
+		let file = match self.version {
+			CgroupVersion::V1 => "memory.usage_in_bytes",
+			CgroupVersion::V2 => "memory.current",
+		};
+
+		Controller::read(&controller.path, file)?.parse().ok()
+	}
+}
+
+/// An entry in `/proc/self/mountinfo`
+struct Mount<'a> {
+	root: &'a str,
+	mount_point: &'a str,
+	fs_type: &'a str,
+	super_options: &'a str,
+	read_only: bool,
+}
+
+impl<'a> Mount<'a> {
+	/// Parse a line in the form:
+	///
+	/// ```text
+	/// 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+	/// ```
+	///
+	/// There are any number of optional fields before the `-` separator.
+	fn parse(line: &'a str) -> Option<Self> {
+		let (mount, fs) = line.split_once(" - ")?;
+
+		let mut mount = mount.split(' ');
+		let root = mount.nth(3)?;
+		let mount_point = mount.next()?;
+		let options = mount.next()?;
+
+		let mut fs = fs.split(' ');
+		let fs_type = fs.next()?;
+		let super_options = fs.nth(1).unwrap_or_default();
+
+		Some(Self {
+			root,
+			mount_point,
+			fs_type,
+			super_options,
+			read_only: options.split(',').any(|option| option == "ro"),
+		})
+	}
+
+	fn has_controller(&self, controller: &str) -> bool {
+		self.super_options
+			.split(',')
+			.any(|option| option == controller)
+	}
+}
+
+/// Count the CPUs in a list such as `0-3,8,10-11`
+fn parse_cpu_list(list: &str) -> Option<u32> {
+	let mut count = 0;
+	for range in list.split(',').filter(|range| !range.is_empty()) {
+		count += match range.split_once('-') {
+			Some((start, end)) => {
+				let (start, end) = (start.parse::<u32>().ok()?, end.parse::<u32>().ok()?);
+				end.checked_sub(start)? + 1
+			},
+			None => {
+				range.parse::<u32>().ok()?;
+				1
+			},
+		};
+	}
+
+	Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{Cgroup, CgroupVersion, parse_cpu_list};
+
+	use std::fs;
+	use std::path::{Path, PathBuf};
+
+	/// A fake cgroup filesystem, removed on drop
+	struct FakeTree(PathBuf);
+
+	impl FakeTree {
+		fn new(name: &str) -> Self {
+			let root =
+				std::env::temp_dir().join(format!("platform-cgroup-{}-{name}", std::process::id()));
+			let _ = fs::remove_dir_all(&root);
+			fs::create_dir_all(&root).unwrap();
+			Self(root)
+		}
+
+		fn path(&self) -> &Path {
+			&self.0
+		}
+
+		fn write(&self, file: &str, contents: &str) {
+			let path = self.0.join(file);
+			fs::create_dir_all(path.parent().unwrap()).unwrap();
+			fs::write(path, contents).unwrap();
+		}
+	}
+
+	impl Drop for FakeTree {
+		fn drop(&mut self) {
+			let _ = fs::remove_dir_all(&self.0);
+		}
+	}
+
+	#[test]
+	fn cpu_lists() {
+		assert_eq!(parse_cpu_list("0"), Some(1));
+		assert_eq!(parse_cpu_list("0-3"), Some(4));
+		assert_eq!(parse_cpu_list("0-3,8,10-11"), Some(7));
+		assert_eq!(parse_cpu_list(""), Some(0));
+		assert_eq!(parse_cpu_list("3-0"), None);
+		assert_eq!(parse_cpu_list("a"), None);
+	}
+
+	#[test]
+	fn v2_limits() {
+		let tree = FakeTree::new("v2");
+		let mount_point = tree.path().display();
+		tree.write("kubepods/pod/cgroup.controllers", "cpuset cpu memory");
+		tree.write("kubepods/cpu.max", "max 100000");
+		tree.write("kubepods/memory.max", "1073741824");
+		tree.write("kubepods/pod/cpu.max", "150000 100000");
+		tree.write("kubepods/pod/cpuset.cpus.effective", "0-7");
+		tree.write("kubepods/pod/memory.max", "max");
+		tree.write("kubepods/pod/memory.current", "52428800");
+
+		let mountinfo = format!(
+			"30 23 0:26 / {mount_point} rw,nosuid,nodev,noexec,relatime shared:4 - cgroup2 \
+			 cgroup2 rw,nsdelegate"
+		);
+		let cgroup = Cgroup::from_proc(&mountinfo, "0::/kubepods/pod\n").unwrap();
+
+		assert_eq!(cgroup.version(), CgroupVersion::V2);
+		assert!(!cgroup.is_read_only());
+		assert_eq!(cgroup.cpu_quota(), Some(2));
+		assert_eq!(cgroup.cpuset_count(), Some(8));
+		// The parent's limit applies
+		assert_eq!(cgroup.memory_limit(), Some(1024 * 1024 * 1024));
+		assert_eq!(cgroup.memory_usage(), Some(50 * 1024 * 1024));
+	}
+
+	#[test]
+	fn v2_namespaced_without_limits() {
+		let tree = FakeTree::new("v2-ns");
+		let mount_point = tree.path().display();
+		tree.write("cgroup.controllers", "cpu memory");
+		tree.write("cpu.max", "max 100000");
+		tree.write("memory.max", "max");
+
+		// Inside a cgroup namespace, our cgroup is the root of the mount
+		let mountinfo = format!(
+			"1270 1266 0:29 / {mount_point} ro,nosuid,nodev,noexec,relatime - cgroup2 cgroup rw"
+		);
+		let cgroup = Cgroup::from_proc(&mountinfo, "0::/\n").unwrap();
+
+		assert!(cgroup.is_read_only());
+		assert_eq!(cgroup.cpu_quota(), None);
+		assert_eq!(cgroup.cpuset_count(), None);
+		assert_eq!(cgroup.memory_limit(), None);
+	}
+
+	#[test]
+	fn v1_limits() {
+		let tree = FakeTree::new("v1");
+		let root = tree.path().display();
+		tree.write("cpu,cpuacct/cpu.cfs_quota_us", "50000");
+		tree.write("cpu,cpuacct/cpu.cfs_period_us", "100000");
+		tree.write("cpuset/cpuset.cpus", "0-1,4");
+		tree.write("memory/memory.limit_in_bytes", "536870912");
+		tree.write("memory/memory.usage_in_bytes", "1048576");
+
+		// A Docker container, where the mount root is the container's own cgroup
+		let mountinfo = format!(
+			"1 0 0:1 / / rw - overlay overlay rw\n2 1 0:2 /docker/abc {root}/cpu,cpuacct \
+			 ro,nosuid - cgroup cgroup rw,cpu,cpuacct\n3 1 0:3 /docker/abc {root}/cpuset \
+			 ro,nosuid - cgroup cgroup rw,cpuset\n4 1 0:4 /docker/abc {root}/memory ro,nosuid - \
+			 cgroup cgroup rw,memory"
+		);
+		let cgroups = "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n3:cpuset:/docker/abc\n";
+		let cgroup = Cgroup::from_proc(&mountinfo, cgroups).unwrap();
+
+		assert_eq!(cgroup.version(), CgroupVersion::V1);
+		assert!(cgroup.is_read_only());
+		// Rounded up to a whole CPU
+		assert_eq!(cgroup.cpu_quota(), Some(1));
+		assert_eq!(cgroup.cpuset_count(), Some(3));
+		assert_eq!(cgroup.memory_limit(), Some(512 * 1024 * 1024));
+		assert_eq!(cgroup.memory_usage(), Some(1024 * 1024));
+	}
+
+	#[test]
+	fn v1_unlimited_quota() {
+		let tree = FakeTree::new("v1-unlimited");
+		let root = tree.path().display();
+		tree.write("cpu/user.slice/cpu.cfs_quota_us", "-1");
+		tree.write("cpu/user.slice/cpu.cfs_period_us", "100000");
+
+		let mountinfo = format!("2 1 0:2 / {root}/cpu rw,nosuid - cgroup cgroup rw,cpu");
+		let cgroup = Cgroup::from_proc(&mountinfo, "4:cpu:/user.slice\n").unwrap();
+
+		assert_eq!(cgroup.cpu_quota(), None);
+		assert_eq!(cgroup.memory_limit(), None);
+	}
+
+	#[test]
+	fn no_cgroups() {
+		let mountinfo = "1 0 0:1 / / rw - ext4 /dev/sda1 rw";
+		assert_eq!(Cgroup::from_proc(mountinfo, ""), None);
+	}
+}
@@ -28,6 +28,13 @@ pub fn libjvm_path() -> Option<PathBuf> {
 	Some(PathBuf::from(lib_path_osstr))
 }
 
+/// The directory the VM uses for files shared between VM instances, e.g. for the attach mechanism
+///
+/// Unlike `java.io.tmpdir`, this can't be configured, since every VM needs to agree on it.
+pub fn temp_directory() -> String {
+	String::from("/tmp")
+}
+
 pub fn java_library_path() -> String {
 	let ld_library_path = std::env::var("LD_LIBRARY_PATH").map_or_else(
 		|_| String::new(),
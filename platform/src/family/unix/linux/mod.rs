@@ -1,5 +1,7 @@
 pub const JNI_LIB_SUFFIX: &str = ".so";
 
+pub mod container;
 pub mod env;
 mod locale;
 pub mod properties;
+pub mod thread;
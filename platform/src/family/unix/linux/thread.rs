@@ -0,0 +1,109 @@
+use std::ffi::CString;
+use std::io;
+
+/// The maximum length of a thread name, excluding the NUL terminator
+const MAX_NAME_LEN: usize = 15;
+
+/// Mapping of Java thread priorities (`1..=10`, index 0 is unused) to nice values
+///
+/// Same as Hotspot's `-XX:ThreadPriorityPolicy=1`. Raising the priority above the default requires
+/// `CAP_SYS_NICE`, so it is expected to fail for most users.
+const JAVA_TO_NICE: [libc::c_int; 11] = [19, 4, 3, 2, 1, 0, -1, -2, -3, -4, -5];
+
+/// [**LINUX**] The kernel-level identifier of a thread, as seen in `top -H` and `/proc/<pid>/task`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NativeThreadId(libc::pid_t);
+
+impl NativeThreadId {
+	/// [**LINUX**] Get the ID of the calling thread
+	pub fn current() -> Self {
+		Self(unsafe { libc::gettid() })
+	}
+
+	/// [**LINUX**] Get the raw ID as an integer
+	pub fn as_u64(self) -> u64 {
+		self.0 as u64
+	}
+}
+
+/// [**LINUX**] Set the name of the calling thread
+///
+/// Linux limits thread names to 15 bytes, so longer names are shortened to their first and last
+/// few characters, like Hotspot does (`"Signal Dispatcher"` -> `"Signal ..atcher"`).
+pub fn set_current_thread_name(name: &str) {
+	let name = name.replace('\0', "");
+	let name = truncate_name(&name);
+	let Ok(name) = CString::new(name) else {
+		return;
+	};
+
+	// Failure to set the name is of no consequence
+	unsafe {
+		libc::pthread_setname_np(libc::pthread_self(), name.as_ptr());
+	}
+}
+
+fn truncate_name(name: &str) -> String {
+	if name.len() <= MAX_NAME_LEN {
+		return name.to_string();
+	}
+
+	let mut head_end = 7;
+	while !name.is_char_boundary(head_end) {
+		head_end -= 1;
+	}
+
+	let mut tail_start = name.len() - 6;
+	while !name.is_char_boundary(tail_start) {
+		tail_start += 1;
+	}
+
+	format!("{}..{}", &name[..head_end], &name[tail_start..])
+}
+
+/// [**LINUX**] Map a Java thread priority (`1..=10`) to a nice value, and apply it to `thread`
+///
+/// # Errors
+///
+/// This will error if the priority is out of range, or the caller is not permitted to change the
+/// priority (e.g. when raising it without `CAP_SYS_NICE`).
+pub fn set_priority(thread: NativeThreadId, java_priority: i32) -> io::Result<()> {
+	let Some(&nice) = usize::try_from(java_priority)
+		.ok()
+		.filter(|p| (1..=10).contains(p))
+		.and_then(|p| JAVA_TO_NICE.get(p))
+	else {
+		return Err(io::Error::from(io::ErrorKind::InvalidInput));
+	};
+
+	let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, thread.0 as libc::id_t, nice) };
+	if ret != 0 {
+		return Err(io::Error::last_os_error());
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::truncate_name;
+
+	#[test]
+	fn short_names_are_untouched() {
+		assert_eq!(truncate_name("main"), "main");
+		assert_eq!(truncate_name("Reference Handl"), "Reference Handl");
+	}
+
+	#[test]
+	fn long_names_are_shortened() {
+		assert_eq!(truncate_name("Signal Dispatcher"), "Signal ..atcher");
+		assert_eq!(truncate_name("Reference Handler"), "Referen..andler");
+	}
+
+	#[test]
+	fn shortening_respects_char_boundaries() {
+		let name = truncate_name("ééééééééééé");
+		assert!(name.len() <= super::MAX_NAME_LEN);
+		assert_eq!(name, "ééé..ééé");
+	}
+}
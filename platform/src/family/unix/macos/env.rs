@@ -1,3 +1,30 @@
+use std::ffi::CStr;
+
+/// The directory the VM uses for files shared between VM instances, e.g. for the attach mechanism
+///
+/// This is the per-user temporary directory (`$TMPDIR`), falling back to `/tmp/` if it can't be
+/// determined, same as Hotspot.
+pub fn temp_directory() -> String {
+	let mut buf = [0u8; libc::PATH_MAX as usize];
+	let len = unsafe {
+		libc::confstr(
+			libc::_CS_DARWIN_USER_TEMP_DIR,
+			buf.as_mut_ptr().cast(),
+			buf.len(),
+		)
+	};
+
+	// `len` includes the NUL terminator, and is larger than the buffer if the path was truncated
+	if len == 0 || len > buf.len() {
+		return String::from("/tmp/");
+	}
+
+	CStr::from_bytes_until_nul(&buf)
+		.ok()
+		.and_then(|path| path.to_str().ok())
+		.map_or_else(|| String::from("/tmp/"), String::from)
+}
+
 pub fn java_library_path() -> String {
 	unimplemented!("macOS java.library.path loading");
 }
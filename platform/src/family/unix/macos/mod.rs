@@ -1,3 +1,4 @@
 pub const JNI_LIB_SUFFIX: &str = ".dylib";
 
 pub mod properties;
+pub mod thread;
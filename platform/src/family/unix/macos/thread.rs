@@ -0,0 +1,82 @@
+use std::ffi::CString;
+use std::io;
+
+/// The maximum length of a thread name, excluding the NUL terminator
+const MAX_NAME_LEN: usize = 63;
+
+/// Mapping of Java thread priorities (`1..=10`, index 0 is unused) to `SCHED_OTHER` priorities
+///
+/// Same as Hotspot, where the default priority (5) maps to the default of 31.
+const JAVA_TO_OS_PRIORITY: [libc::c_int; 11] = [0, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36];
+
+/// [**MACOS**] The identifier of a thread
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NativeThreadId(libc::pthread_t);
+
+// SAFETY: A `pthread_t` is only an identifier
+unsafe impl Send for NativeThreadId {}
+unsafe impl Sync for NativeThreadId {}
+
+impl NativeThreadId {
+	/// [**MACOS**] Get the ID of the calling thread
+	pub fn current() -> Self {
+		Self(unsafe { libc::pthread_self() })
+	}
+
+	/// [**MACOS**] Get the raw ID as an integer
+	pub fn as_u64(self) -> u64 {
+		let mut id = 0;
+		unsafe {
+			libc::pthread_threadid_np(self.0, &raw mut id);
+		}
+		id
+	}
+}
+
+/// [**MACOS**] Set the name of the calling thread
+///
+/// Names longer than 63 bytes are truncated.
+pub fn set_current_thread_name(name: &str) {
+	let mut name = name.replace('\0', "");
+	if name.len() > MAX_NAME_LEN {
+		let mut end = MAX_NAME_LEN;
+		while !name.is_char_boundary(end) {
+			end -= 1;
+		}
+		name.truncate(end);
+	}
+
+	let Ok(name) = CString::new(name) else {
+		return;
+	};
+
+	// Failure to set the name is of no consequence
+	unsafe {
+		libc::pthread_setname_np(name.as_ptr());
+	}
+}
+
+/// [**MACOS**] Map a Java thread priority (`1..=10`) to a `SCHED_OTHER` priority, and apply it to `thread`
+///
+/// # Errors
+///
+/// This will error if the priority is out of range, or the OS rejects the change.
+pub fn set_priority(thread: NativeThreadId, java_priority: i32) -> io::Result<()> {
+	let Some(&priority) = usize::try_from(java_priority)
+		.ok()
+		.filter(|p| (1..=10).contains(p))
+		.and_then(|p| JAVA_TO_OS_PRIORITY.get(p))
+	else {
+		return Err(io::Error::from(io::ErrorKind::InvalidInput));
+	};
+
+	let mut param = unsafe { std::mem::zeroed::<libc::sched_param>() };
+	param.sched_priority = priority;
+
+	let ret = unsafe { libc::pthread_setschedparam(thread.0, libc::SCHED_OTHER, &raw const param) };
+	if ret != 0 {
+		return Err(io::Error::from_raw_os_error(ret));
+	}
+
+	Ok(())
+}
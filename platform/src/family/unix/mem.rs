@@ -7,3 +7,68 @@ pub fn get_page_size() -> usize {
 	static ONCE: OnceLock<usize> = OnceLock::new();
 	*ONCE.get_or_init(|| unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize })
 }
+
+/// [**UNIX**] Allocate `size` bytes of uninitialized memory
+///
+/// This will return null if the allocation fails.
+///
+/// # Safety
+///
+/// The returned pointer must only be released with [`free()`].
+pub unsafe fn alloc(size: usize) -> *mut u8 {
+	unsafe { libc::malloc(size).cast() }
+}
+
+/// [**UNIX**] Resize an allocation made with [`alloc()`], moving it if necessary
+///
+/// This will return null if the allocation fails, in which case `ptr` is left untouched.
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`alloc()`] or [`realloc()`], and not yet freed.
+pub unsafe fn realloc(ptr: *mut u8, size: usize) -> *mut u8 {
+	unsafe { libc::realloc(ptr.cast(), size).cast() }
+}
+
+/// [**UNIX**] Release an allocation made with [`alloc()`] or [`realloc()`]
+///
+/// # Safety
+///
+/// `ptr` must not be used after this call.
+pub unsafe fn free(ptr: *mut u8) {
+	unsafe { libc::free(ptr.cast()) }
+}
+
+/// [**UNIX**] Get the system load averages over the last 1, 5, and 15 minutes
+///
+/// At most `averages.len()` samples are written. This returns the number of samples written, or
+/// `None` if the load average is unavailable.
+pub fn load_average(averages: &mut [f64]) -> Option<usize> {
+	let count = averages.len().min(3) as libc::c_int;
+	let ret = unsafe { libc::getloadavg(averages.as_mut_ptr(), count) };
+	usize::try_from(ret).ok()
+}
+
+/// [**UNIX**] Get the total amount of physical memory on the host, in bytes
+///
+/// This does *not* account for any cgroup limits.
+pub fn physical_memory() -> u64 {
+	let pages = unsafe { libc::sysconf(libc::_SC_PHYS_PAGES) };
+	u64::try_from(pages).unwrap_or(0) * get_page_size() as u64
+}
+
+/// [**UNIX**] Get the amount of physical memory currently available on the host, in bytes
+///
+/// This returns `None` if the platform doesn't report it.
+pub fn available_memory() -> Option<u64> {
+	#[cfg(target_os = "linux")]
+	{
+		let pages = unsafe { libc::sysconf(libc::_SC_AVPHYS_PAGES) };
+		u64::try_from(pages)
+			.ok()
+			.map(|pages| pages * get_page_size() as u64)
+	}
+
+	#[cfg(not(target_os = "linux"))]
+	None
+}
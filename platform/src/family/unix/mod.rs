@@ -2,7 +2,7 @@
 
 cfg_select! {
 	target_os = "linux" => {
-		mod linux;
+		pub mod linux;
 		use linux as imp;
 	}
 	target_os = "macos" => {
@@ -19,10 +19,11 @@ cfg_select! {
 pub use imp::JNI_LIB_SUFFIX;
 pub const JNI_LIB_PREFIX: &str = "lib";
 
+pub mod cpu;
 pub mod io;
 pub(super) mod libs;
 pub(crate) mod locale;
 pub mod mem;
 pub mod properties;
 pub(super) mod signals;
-pub use imp::env;
+pub use imp::{env, thread};
@@ -1,9 +1,65 @@
 use std::borrow::Cow;
 use std::ffi::{c_int, c_void};
 use std::mem::MaybeUninit;
+use std::sync::Once;
+use std::sync::atomic::{AtomicI32, Ordering};
 
-extern "C" fn default_handler(sig: c_int, info: *mut libc::siginfo_t, context: *mut c_void) {
-	todo!()
+extern "C" fn default_handler(sig: c_int, _info: *mut libc::siginfo_t, _context: *mut c_void) {
+	crate::Signal(sig).notify();
+}
+
+// Self-pipe used to wake up the thread waiting on pending signals. Writing to a pipe is one of the
+// few things that is async-signal-safe.
+static PENDING_PIPE_READ: AtomicI32 = AtomicI32::new(-1);
+static PENDING_PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+
+/// Wake up the thread waiting in [`wait_pending()`]
+///
+/// This must remain async-signal-safe.
+pub(in crate::family) fn post_pending() {
+	let fd = PENDING_PIPE_WRITE.load(Ordering::Acquire);
+	if fd < 0 {
+		// Nobody is waiting yet, the pending count will be picked up on the first wait
+		return;
+	}
+
+	let byte = 1u8;
+	unsafe {
+		libc::write(fd, (&raw const byte).cast(), 1);
+	}
+}
+
+/// Block until [`post_pending()`] is called
+pub(in crate::family) fn wait_pending() {
+	static INIT: Once = Once::new();
+	INIT.call_once(|| {
+		let mut fds = [-1; 2];
+		unsafe {
+			if libc::pipe(fds.as_mut_ptr()) != 0 {
+				return;
+			}
+
+			// The write end is used from signal handlers, which must never block
+			let flags = libc::fcntl(fds[1], libc::F_GETFL);
+			libc::fcntl(fds[1], libc::F_SETFL, flags | libc::O_NONBLOCK);
+		}
+
+		PENDING_PIPE_READ.store(fds[0], Ordering::Release);
+		PENDING_PIPE_WRITE.store(fds[1], Ordering::Release);
+	});
+
+	let fd = PENDING_PIPE_READ.load(Ordering::Acquire);
+	assert!(fd >= 0, "failed to create the pending signal pipe");
+
+	let mut byte = 0u8;
+	loop {
+		let ret = unsafe { libc::read(fd, (&raw mut byte).cast(), 1) };
+		if ret < 0 && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+			continue;
+		}
+
+		return;
+	}
 }
 
 type HandlerFn = extern "C" fn(c_int);
@@ -77,7 +133,17 @@ impl crate::SignalOsExt for crate::Signal {
 			"SIGTSTP" => Some(Self(libc::SIGTSTP)),
 			"SIGTTIN" => Some(Self(libc::SIGTTIN)),
 			"SIGTTOU" => Some(Self(libc::SIGTTOU)),
-			_ => unimplemented!("Signal not supported: {}", name),
+			"SIGBUS" => Some(Self(libc::SIGBUS)),
+			"SIGTRAP" => Some(Self(libc::SIGTRAP)),
+			"SIGURG" => Some(Self(libc::SIGURG)),
+			"SIGXCPU" => Some(Self(libc::SIGXCPU)),
+			"SIGXFSZ" => Some(Self(libc::SIGXFSZ)),
+			"SIGVTALRM" => Some(Self(libc::SIGVTALRM)),
+			"SIGPROF" => Some(Self(libc::SIGPROF)),
+			"SIGWINCH" => Some(Self(libc::SIGWINCH)),
+			"SIGIO" => Some(Self(libc::SIGIO)),
+			"SIGSYS" => Some(Self(libc::SIGSYS)),
+			_ => None,
 		}
 	}
 
@@ -94,6 +160,22 @@ impl crate::SignalOsExt for crate::Signal {
 		!DISALLOWED.contains(&self.0)
 	}
 
+	fn is_ignored_impl(&self) -> bool {
+		let mut current_action = unsafe { MaybeUninit::<libc::sigaction>::zeroed().assume_init() };
+
+		let ok = unsafe { libc::sigaction(self.0, std::ptr::null(), &raw mut current_action) };
+		if ok < 0 {
+			return false;
+		}
+
+		current_action.sa_flags & libc::SA_SIGINFO == 0
+			&& current_action.sa_sigaction == libc::SIG_IGN
+	}
+
+	fn raise_impl(self) -> bool {
+		unsafe { libc::raise(self.0) == 0 }
+	}
+
 	#[allow(non_camel_case_types)]
 	unsafe fn install_impl(self, handler: crate::SignalHandler) -> Option<crate::SignalHandler> {
 		const ERROR_SIGNALS_TO_REMOVE: [libc::c_int; 5] = [
@@ -146,3 +228,39 @@ impl crate::SignalOsExt for crate::Signal {
 		Some(unsafe { crate::SignalHandler::from_raw(old_signal_handler) })
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{Signal, SignalHandler};
+
+	#[test]
+	fn raised_signals_are_pending() {
+		let signal = Signal::from_name("USR2").unwrap();
+		unsafe {
+			signal.install(SignalHandler::user_handler()).unwrap();
+		}
+
+		assert!(signal.raise());
+		assert_eq!(Signal::wait_pending(), signal);
+	}
+
+	#[test]
+	fn ignored_signals() {
+		let signal = Signal::from_name("USR1").unwrap();
+		assert!(!signal.is_ignored());
+
+		unsafe {
+			signal
+				.install(SignalHandler::from_raw(libc::SIG_IGN))
+				.unwrap();
+		}
+		assert!(signal.is_ignored());
+
+		unsafe {
+			signal
+				.install(SignalHandler::from_raw(libc::SIG_DFL))
+				.unwrap();
+		}
+		assert!(!signal.is_ignored());
+	}
+}
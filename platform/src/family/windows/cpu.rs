@@ -0,0 +1,4 @@
+/// [**WINDOWS**] Get the number of CPUs that the process may run on
+pub fn processor_count() -> usize {
+	unimplemented!("Windows::cpu::processor_count")
+}
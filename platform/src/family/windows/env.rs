@@ -1,3 +1,7 @@
+pub fn temp_directory() -> String {
+	unimplemented!("Windows temporary directory");
+}
+
 pub fn java_library_path() -> String {
 	unimplemented!("Windows java.library.path loading");
 }
@@ -4,3 +4,51 @@
 pub fn get_page_size() -> usize {
 	unimplemented!("Windows::mem::get_page_size")
 }
+
+/// [**WINDOWS**] Allocate `size` bytes of uninitialized memory
+///
+/// This will return null if the allocation fails.
+///
+/// # Safety
+///
+/// The returned pointer must only be released with [`free()`].
+pub unsafe fn alloc(size: usize) -> *mut u8 {
+	unimplemented!("Windows::mem::alloc")
+}
+
+/// [**WINDOWS**] Resize an allocation made with [`alloc()`], moving it if necessary
+///
+/// This will return null if the allocation fails, in which case `ptr` is left untouched.
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`alloc()`] or [`realloc()`], and not yet freed.
+pub unsafe fn realloc(ptr: *mut u8, size: usize) -> *mut u8 {
+	unimplemented!("Windows::mem::realloc")
+}
+
+/// [**WINDOWS**] Release an allocation made with [`alloc()`] or [`realloc()`]
+///
+/// # Safety
+///
+/// `ptr` must not be used after this call.
+pub unsafe fn free(ptr: *mut u8) {
+	unimplemented!("Windows::mem::free")
+}
+
+/// [**WINDOWS**] Get the system load averages over the last 1, 5, and 15 minutes
+///
+/// Windows has no equivalent, so this is always `None`.
+pub fn load_average(_averages: &mut [f64]) -> Option<usize> {
+	None
+}
+
+/// [**WINDOWS**] Get the total amount of physical memory on the host, in bytes
+pub fn physical_memory() -> u64 {
+	unimplemented!("Windows::mem::physical_memory")
+}
+
+/// [**WINDOWS**] Get the amount of physical memory currently available on the host, in bytes
+pub fn available_memory() -> Option<u64> {
+	unimplemented!("Windows::mem::available_memory")
+}
@@ -1,7 +1,28 @@
 use std::borrow::Cow;
+use std::sync::{Condvar, Mutex};
 
 extern "C" fn default_handler(sig: libc::c_int) {
-	todo!()
+	crate::Signal(sig).notify();
+}
+
+// Console control handlers are run on their own thread on Windows, so blocking here is fine
+static PENDING_LOCK: Mutex<bool> = Mutex::new(false);
+static PENDING_CONDVAR: Condvar = Condvar::new();
+
+/// Wake up the thread waiting in [`wait_pending()`]
+pub(in crate::family) fn post_pending() {
+	*PENDING_LOCK.lock().unwrap() = true;
+	PENDING_CONDVAR.notify_one();
+}
+
+/// Block until [`post_pending()`] is called
+pub(in crate::family) fn wait_pending() {
+	let mut posted = PENDING_LOCK.lock().unwrap();
+	while !*posted {
+		posted = PENDING_CONDVAR.wait(posted).unwrap();
+	}
+
+	*posted = false;
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -43,6 +64,15 @@ impl crate::SignalOsExt for crate::Signal {
 		self.0 != libc::SIGFPE
 	}
 
+	fn is_ignored_impl(&self) -> bool {
+		// The CRT has no way to query a handler without replacing it
+		false
+	}
+
+	fn raise_impl(self) -> bool {
+		unsafe { libc::raise(self.0) == 0 }
+	}
+
 	unsafe fn install_impl(self, handler: crate::SignalHandler) -> Option<crate::SignalHandler> {
 		todo!("windows::Signal::install")
 	}
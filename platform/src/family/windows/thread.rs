@@ -0,0 +1,31 @@
+use std::io;
+
+/// [**WINDOWS**] The identifier of a thread
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NativeThreadId(());
+
+impl NativeThreadId {
+	/// [**WINDOWS**] Get the ID of the calling thread
+	pub fn current() -> Self {
+		unimplemented!("Windows::thread::NativeThreadId::current")
+	}
+
+	/// [**WINDOWS**] Get the raw ID as an integer
+	pub fn as_u64(self) -> u64 {
+		unimplemented!("Windows::thread::NativeThreadId::as_u64")
+	}
+}
+
+/// [**WINDOWS**] Set the name of the calling thread
+pub fn set_current_thread_name(_name: &str) {
+	unimplemented!("Windows::thread::set_current_thread_name")
+}
+
+/// [**WINDOWS**] Map a Java thread priority (`1..=10`) to an OS priority, and apply it to `thread`
+///
+/// # Errors
+///
+/// Not yet supported on Windows.
+pub fn set_priority(_thread: NativeThreadId, _java_priority: i32) -> io::Result<()> {
+	Err(io::Error::from(io::ErrorKind::Unsupported))
+}
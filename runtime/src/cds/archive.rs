@@ -0,0 +1,419 @@
+//! The shared archive file format
+//!
+//! All integers are little endian. The file is laid out as:
+//!
+//! ```text
+//! magic         [u8; 4]
+//! version       u32
+//! vm_version    u16 length, followed by UTF-8
+//! image_size    u64
+//! image_mtime   u64 (nanoseconds since the Unix epoch)
+//! image_index   u32 (CRC-32 of the image's header and index)
+//! class_count   u32
+//! classes       [class_count] {
+//!     name      u16 length, followed by UTF-8 (`/module/path/to/Class.class`)
+//!     offset    u32 (relative to the start of the data)
+//!     length    u32
+//! }
+//! data          the class files
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use common::int_types::u1;
+use memmap2::Mmap;
+
+const MAGIC: [u8; 4] = *b"SJSA";
+const VERSION: u32 = 2;
+
+/// The VM that created an archive, archives are never shared between builds
+const VM_VERSION: &str = concat!(env!("TARGET_OPENJDK_TAG"), "-", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum ArchiveError {
+	Io(std::io::Error),
+	/// `-Xshare:on` was given without an archive
+	NotSpecified,
+	/// There's no runtime image to validate the archive against
+	NoRuntimeImage,
+	BadMagic,
+	VersionMismatch,
+	/// The archive was created against a different runtime image
+	ImageMismatch,
+	Corrupt,
+}
+
+impl Display for ArchiveError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Io(e) => write!(f, "{e}"),
+			Self::NotSpecified => write!(f, "no archive was specified (-XX:SharedArchiveFile)"),
+			Self::NoRuntimeImage => write!(f, "there is no runtime image to validate against"),
+			Self::BadMagic => write!(f, "not a shared archive file"),
+			Self::VersionMismatch => write!(
+				f,
+				"the archive was created by a different version or build of the VM"
+			),
+			Self::ImageMismatch => write!(
+				f,
+				"the archive was created against a different runtime image"
+			),
+			Self::Corrupt => write!(f, "the archive is corrupt"),
+		}
+	}
+}
+
+impl std::error::Error for ArchiveError {}
+
+impl From<std::io::Error> for ArchiveError {
+	fn from(value: std::io::Error) -> Self {
+		Self::Io(value)
+	}
+}
+
+/// Identifies the runtime image that an archive was created against
+///
+/// Archived classes are only valid for the exact image they were read from, so the image's size and
+/// modification time need to match, along with a checksum of its index.
+///
+/// The index holds the location and size of every resource in the image, so any image built from
+/// different classes will have a different checksum. Checksumming the resources themselves would
+/// mean reading the entire image on startup, which is exactly what the archive is meant to avoid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ImageIdentity {
+	size: u64,
+	modified: u64,
+	index_checksum: u32,
+}
+
+impl ImageIdentity {
+	/// Identify the image at `image`, whose header and index make up its first `index_length` bytes
+	pub fn of(image: &Path, index_length: usize) -> Result<Self, ArchiveError> {
+		let mut file = File::open(image)?;
+		let metadata = file.metadata()?;
+		let modified = metadata
+			.modified()?
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |time| time.as_nanos() as u64);
+
+		let mut index = vec![0; index_length];
+		file.read_exact(&mut index)?;
+
+		Ok(Self {
+			size: metadata.len(),
+			modified,
+			index_checksum: crc32fast::hash(&index),
+		})
+	}
+}
+
+/// A mapped, validated archive
+pub struct SharedArchive {
+	path: PathBuf,
+	map: Mmap,
+	data_start: usize,
+	classes: HashMap<Box<str>, Range<usize>>,
+}
+
+impl SharedArchive {
+	pub fn open(path: &Path, image: ImageIdentity) -> Result<Self, ArchiveError> {
+		let file = File::open(path)?;
+
+		// SAFETY: Archives are only ever replaced, never written in place (see `write()`)
+		let map = unsafe { Mmap::map(&file)? };
+
+		let mut reader = Reader {
+			bytes: &map,
+			pos: 0,
+		};
+		if reader.bytes(MAGIC.len())? != MAGIC {
+			return Err(ArchiveError::BadMagic);
+		}
+
+		if reader.u32()? != VERSION || reader.string()? != VM_VERSION {
+			return Err(ArchiveError::VersionMismatch);
+		}
+
+		let archived_image = ImageIdentity {
+			size: reader.u64()?,
+			modified: reader.u64()?,
+			index_checksum: reader.u32()?,
+		};
+		if archived_image != image {
+			return Err(ArchiveError::ImageMismatch);
+		}
+
+		let class_count = reader.u32()? as usize;
+		let mut entries = Vec::with_capacity(class_count);
+		for _ in 0..class_count {
+			let name = reader.string()?;
+			let offset = reader.u32()? as usize;
+			let length = reader.u32()? as usize;
+			entries.push((name, offset..offset + length));
+		}
+
+		let data_start = reader.pos;
+		let data_len = map.len() - data_start;
+
+		let mut classes = HashMap::with_capacity(class_count);
+		for (name, range) in entries {
+			if range.end > data_len {
+				return Err(ArchiveError::Corrupt);
+			}
+
+			classes.insert(Box::from(name), range);
+		}
+
+		Ok(Self {
+			path: path.to_path_buf(),
+			map,
+			data_start,
+			classes,
+		})
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	pub fn len(&self) -> usize {
+		self.classes.len()
+	}
+
+	/// Get the archived class file for `name`, in the form `/module/path/to/Class.class`
+	///
+	/// This is a view into the mapped archive, nothing is copied.
+	pub fn class_bytes(&self, name: &str) -> Option<&[u1]> {
+		let range = self.classes.get(name)?;
+		Some(&self.map[self.data_start + range.start..self.data_start + range.end])
+	}
+}
+
+/// Write an archive of `classes` to `path`
+///
+/// The archive is written to a temporary file first, and then moved into place. This avoids
+/// clobbering an archive that may currently be mapped, possibly by another VM.
+pub fn write(
+	path: &Path,
+	image: ImageIdentity,
+	classes: &[(String, Box<[u1]>)],
+) -> Result<(), ArchiveError> {
+	fn write_string(out: &mut impl Write, value: &str) -> Result<(), ArchiveError> {
+		let len = u16::try_from(value.len()).map_err(|_| ArchiveError::Corrupt)?;
+		out.write_all(&len.to_le_bytes())?;
+		out.write_all(value.as_bytes())?;
+		Ok(())
+	}
+
+	let mut temp_path = path.as_os_str().to_owned();
+	temp_path.push(".tmp");
+	let temp_path = PathBuf::from(temp_path);
+
+	let mut out = BufWriter::new(File::create(&temp_path)?);
+	out.write_all(&MAGIC)?;
+	out.write_all(&VERSION.to_le_bytes())?;
+	write_string(&mut out, VM_VERSION)?;
+	out.write_all(&image.size.to_le_bytes())?;
+	out.write_all(&image.modified.to_le_bytes())?;
+	out.write_all(&image.index_checksum.to_le_bytes())?;
+
+	let class_count = u32::try_from(classes.len()).map_err(|_| ArchiveError::Corrupt)?;
+	out.write_all(&class_count.to_le_bytes())?;
+
+	let mut offset = 0u32;
+	for (name, bytes) in classes {
+		let length = u32::try_from(bytes.len()).map_err(|_| ArchiveError::Corrupt)?;
+
+		write_string(&mut out, name)?;
+		out.write_all(&offset.to_le_bytes())?;
+		out.write_all(&length.to_le_bytes())?;
+
+		offset = offset.checked_add(length).ok_or(ArchiveError::Corrupt)?;
+	}
+
+	for (_, bytes) in classes {
+		out.write_all(bytes)?;
+	}
+
+	out.into_inner()
+		.map_err(std::io::IntoInnerError::into_error)?
+		.sync_all()?;
+	std::fs::rename(temp_path, path)?;
+
+	Ok(())
+}
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn bytes(&mut self, len: usize) -> Result<&'a [u8], ArchiveError> {
+		let bytes = self
+			.bytes
+			.get(self.pos..self.pos + len)
+			.ok_or(ArchiveError::Corrupt)?;
+		self.pos += len;
+		Ok(bytes)
+	}
+
+	fn u16(&mut self) -> Result<u16, ArchiveError> {
+		Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+	}
+
+	fn u32(&mut self) -> Result<u32, ArchiveError> {
+		Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+	}
+
+	fn u64(&mut self) -> Result<u64, ArchiveError> {
+		Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+	}
+
+	fn string(&mut self) -> Result<&'a str, ArchiveError> {
+		let len = self.u16()? as usize;
+		str::from_utf8(self.bytes(len)?).map_err(|_| ArchiveError::Corrupt)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn temp_archive(name: &str) -> PathBuf {
+		std::env::temp_dir().join(format!("sj-cds-{}-{name}.jsa", std::process::id()))
+	}
+
+	#[test]
+	fn round_trip() {
+		let path = temp_archive("round-trip");
+		let image = ImageIdentity {
+			size: 1234,
+			modified: 5678,
+			index_checksum: 9,
+		};
+
+		let classes = [
+			(
+				String::from("/java.base/java/lang/Object.class"),
+				Box::from(&b"\xCA\xFE\xBA\xBEobject"[..]),
+			),
+			(
+				String::from("/java.base/java/lang/String.class"),
+				Box::from(&b"\xCA\xFE\xBA\xBEstring"[..]),
+			),
+		];
+		write(&path, image, &classes).unwrap();
+
+		let archive = SharedArchive::open(&path, image).unwrap();
+		assert_eq!(archive.len(), 2);
+		assert_eq!(
+			archive.class_bytes("/java.base/java/lang/String.class"),
+			Some(&b"\xCA\xFE\xBA\xBEstring"[..])
+		);
+		assert_eq!(
+			archive.class_bytes("/java.base/java/lang/Object.class"),
+			Some(&b"\xCA\xFE\xBA\xBEobject"[..])
+		);
+		assert!(
+			archive
+				.class_bytes("/java.base/java/lang/Thread.class")
+				.is_none()
+		);
+
+		let other_images = [
+			ImageIdentity {
+				modified: 0,
+				..image
+			},
+			ImageIdentity {
+				index_checksum: 0,
+				..image
+			},
+		];
+		for other_image in other_images {
+			assert!(matches!(
+				SharedArchive::open(&path, other_image),
+				Err(ArchiveError::ImageMismatch)
+			));
+		}
+
+		drop(archive);
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn corrupt() {
+		let path = temp_archive("corrupt");
+		let image = ImageIdentity {
+			size: 1,
+			modified: 1,
+			index_checksum: 1,
+		};
+
+		std::fs::write(&path, b"not an archive").unwrap();
+		assert!(matches!(
+			SharedArchive::open(&path, image),
+			Err(ArchiveError::BadMagic)
+		));
+
+		write(
+			&path,
+			image,
+			&[(String::from("/a/A.class"), Box::from(&b"a"[..]))],
+		)
+		.unwrap();
+		let mut bytes = std::fs::read(&path).unwrap();
+		bytes.pop();
+		std::fs::write(&path, bytes).unwrap();
+		assert!(matches!(
+			SharedArchive::open(&path, image),
+			Err(ArchiveError::Corrupt)
+		));
+
+		std::fs::remove_file(path).unwrap();
+	}
+
+	#[test]
+	fn image_identity() {
+		let path = temp_archive("image");
+		std::fs::write(&path, b"header+index|resources").unwrap();
+		let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+		let identity = ImageIdentity::of(&path, 12).unwrap();
+		assert_eq!(identity.size, 22);
+
+		// Resources outside of the index aren't checksummed
+		std::fs::write(&path, b"header+index|RESOURCES").unwrap();
+		File::options()
+			.write(true)
+			.open(&path)
+			.unwrap()
+			.set_modified(modified)
+			.unwrap();
+		assert_eq!(ImageIdentity::of(&path, 12).unwrap(), identity);
+
+		// A same-sized image with the same modification time, but a different index
+		std::fs::write(&path, b"HEADER+index|resources").unwrap();
+		File::options()
+			.write(true)
+			.open(&path)
+			.unwrap()
+			.set_modified(modified)
+			.unwrap();
+		let other = ImageIdentity::of(&path, 12).unwrap();
+		assert_eq!(
+			(other.size, other.modified),
+			(identity.size, identity.modified)
+		);
+		assert_ne!(other, identity);
+
+		std::fs::remove_file(path).unwrap();
+	}
+}
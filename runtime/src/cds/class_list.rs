@@ -0,0 +1,111 @@
+//! Class lists, as written by `-XX:DumpLoadedClassList` and `jcmd VM.cds`
+//!
+//! A class list names every class loaded by the built-in loaders, one per line, followed by the
+//! `@lambda-proxy` and `@lambda-form-invoker` lines recorded while dumping.
+
+use crate::classpath::loader::ClassLoaderSet;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use classfile::constant_pool::types::ReferenceKind;
+
+static LAMBDA_PROXIES: Mutex<Vec<LambdaProxy>> = Mutex::new(Vec::new());
+static LAMBDA_FORM_INVOKERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// A lambda proxy class spun up for an `invokedynamic` call site
+///
+/// All types are method descriptors, and all class names are internal names.
+pub struct LambdaProxy {
+	pub caller: String,
+	pub interface_method_name: String,
+	pub factory_type: String,
+	pub interface_method_type: String,
+	pub implementation_kind: ReferenceKind,
+	pub implementation_class: String,
+	pub implementation_name: String,
+	pub implementation_type: String,
+	pub dynamic_method_type: String,
+}
+
+impl LambdaProxy {
+	fn write(&self, out: &mut impl Write) -> std::io::Result<()> {
+		let kind = match self.implementation_kind {
+			ReferenceKind::GetField => "REF_getField",
+			ReferenceKind::GetStatic => "REF_getStatic",
+			ReferenceKind::PutField => "REF_putField",
+			ReferenceKind::PutStatic => "REF_putStatic",
+			ReferenceKind::InvokeVirtual => "REF_invokeVirtual",
+			ReferenceKind::InvokeStatic => "REF_invokeStatic",
+			ReferenceKind::InvokeSpecial => "REF_invokeSpecial",
+			ReferenceKind::NewInvokeSpecial => "REF_newInvokeSpecial",
+			ReferenceKind::InvokeInterface => "REF_invokeInterface",
+		};
+
+		writeln!(
+			out,
+			"@lambda-proxy {} {} {} {} {kind} {} {} {} {}",
+			self.caller,
+			self.interface_method_name,
+			self.factory_type,
+			self.interface_method_type,
+			self.implementation_class,
+			self.implementation_name,
+			self.implementation_type,
+			self.dynamic_method_type,
+		)
+	}
+}
+
+/// Record a lambda proxy class, see `java.lang.invoke.LambdaProxyClassArchive#register`
+pub fn register_lambda_proxy(proxy: LambdaProxy) {
+	LAMBDA_PROXIES.lock().unwrap().push(proxy);
+}
+
+/// Record a line from `java.lang.invoke.GenerateJLIClassesHelper`, describing a `LambdaForm`
+/// invoker that was used
+pub fn log_lambda_form_invoker(line: String) {
+	LAMBDA_FORM_INVOKERS.lock().unwrap().push(line);
+}
+
+/// Write the class list to `path`
+pub fn dump(path: &Path) -> std::io::Result<()> {
+	let mut class_names = ClassLoaderSet::loaded_classes()
+		.into_iter()
+		.filter(|class| !class.is_array() && !class.is_hidden())
+		.filter(|class| {
+			let loader = class.loader();
+			loader.is_bootstrap() || loader.is_platform() || loader.is_app()
+		})
+		.map(|class| class.name())
+		.collect::<Vec<_>>();
+
+	// Loaders don't keep track of the order their classes were defined in
+	class_names.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+
+	let mut out = BufWriter::new(File::create(path)?);
+	writeln!(out, "# NOTE: Do not modify this file.")?;
+	writeln!(out, "#")?;
+	writeln!(
+		out,
+		"# This file is generated via the -XX:DumpLoadedClassList=<class_list_file> option"
+	)?;
+	writeln!(out, "# and is used at CDS archive dump time.")?;
+	writeln!(out, "#")?;
+
+	for name in class_names {
+		writeln!(out, "{}", name.as_str())?;
+	}
+
+	for proxy in LAMBDA_PROXIES.lock().unwrap().iter() {
+		proxy.write(&mut out)?;
+	}
+
+	for line in LAMBDA_FORM_INVOKERS.lock().unwrap().iter() {
+		writeln!(out, "@lambda-form-invoker {line}")?;
+	}
+
+	out.flush()
+}
@@ -0,0 +1,196 @@
+//! Class Data Sharing (CDS)
+//!
+//! Hotspot's archives hold parsed and linked class metadata, along with symbols and heap objects,
+//! all mapped straight into memory. Our classes are full of pointers into the heap and the symbol
+//! table, so our archive is far simpler: it holds the class files of the classes that were loaded
+//! from the runtime image. Using one saves locating and decompressing those classes in the jimage,
+//! as they're read straight out of the mapped archive, but they're still parsed and linked as
+//! usual, and symbols and interned strings are created from scratch. No parsed or linked state is
+//! archived.
+//!
+//! Archives are written on exit with `-XX:ArchiveClassesAtExit`, or through
+//! `jcmd <pid> VM.cds dynamic_dump`. They're used with `-XX:SharedArchiveFile`, as long as they
+//! were created by the same VM against the same runtime image. See [`ShareMode`] for what happens
+//! otherwise.
+//!
+//! Lambda proxy classes are never archived, they're only recorded in the class list.
+
+mod archive;
+pub mod class_list;
+
+use crate::classpath::jimage;
+use crate::classpath::loader::ClassLoaderSet;
+use crate::logging::{info, warn};
+use crate::options::{JvmOptions, ShareMode};
+
+use std::path::Path;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use archive::{ImageIdentity, SharedArchive};
+use common::int_types::u1;
+
+pub use archive::ArchiveError;
+
+static SHARED_ARCHIVE: OnceLock<SharedArchive> = OnceLock::new();
+static DUMPED_AT_EXIT: AtomicBool = AtomicBool::new(false);
+
+// Bits for `jdk.internal.misc.CDS#getCDSConfigStatus`
+const IS_DUMPING_ARCHIVE: i32 = 1 << 0;
+const IS_LOGGING_LAMBDA_FORM_INVOKERS: i32 = 1 << 2;
+const IS_USING_ARCHIVE: i32 = 1 << 3;
+
+/// Map the archive given through `-XX:SharedArchiveFile`
+///
+/// This needs to happen after the runtime image is opened, and before any classes are loaded.
+///
+/// # Errors
+///
+/// With `-Xshare:on`, this fails if the archive can't be used. Otherwise, the archive is skipped
+/// with a warning.
+pub fn initialize() -> Result<(), ArchiveError> {
+	let options = JvmOptions::get();
+	if options.share == ShareMode::Off {
+		return Ok(());
+	}
+
+	let result = match &options.shared_archive_file {
+		Some(path) => open_archive(path),
+		None => Err(ArchiveError::NotSpecified),
+	};
+
+	match result {
+		Ok(archive) => {
+			info!(
+				TARGETS: (Cds),
+				"Mapped {} classes from {}",
+				archive.len(),
+				archive.path().display()
+			);
+			let _ = SHARED_ARCHIVE.set(archive);
+			Ok(())
+		},
+		Err(e) if options.share == ShareMode::On => Err(e),
+		// Nothing to complain about if no archive was requested in the first place
+		Err(ArchiveError::NotSpecified) => Ok(()),
+		Err(e) => {
+			warn!(TARGETS: (Cds), "Unable to use shared archive: {e}");
+			Ok(())
+		},
+	}
+}
+
+fn open_archive(path: &Path) -> Result<SharedArchive, ArchiveError> {
+	SharedArchive::open(path, image_identity()?)
+}
+
+fn image_identity() -> Result<ImageIdentity, ArchiveError> {
+	let (Some(path), Some(header)) = (jimage::path(), jimage::header()) else {
+		return Err(ArchiveError::NoRuntimeImage);
+	};
+
+	ImageIdentity::of(path, header.index_length())
+}
+
+/// Whether an archive is mapped
+pub fn is_using_archive() -> bool {
+	SHARED_ARCHIVE.get().is_some()
+}
+
+/// Whether an archive will be written on exit
+pub fn is_dumping_archive() -> bool {
+	JvmOptions::get().archive_classes_at_exit.is_some()
+}
+
+/// The status bits reported by `jdk.internal.misc.CDS#getCDSConfigStatus`
+pub fn config_status() -> i32 {
+	let mut status = 0;
+	if is_dumping_archive() {
+		status |= IS_DUMPING_ARCHIVE;
+	}
+
+	if is_dumping_archive() || JvmOptions::get().dump_loaded_class_list.is_some() {
+		status |= IS_LOGGING_LAMBDA_FORM_INVOKERS;
+	}
+
+	if is_using_archive() {
+		status |= IS_USING_ARCHIVE;
+	}
+
+	status
+}
+
+/// Look up a class file from the runtime image in the archive
+///
+/// `path` is the path of the class file within `module`, such as `java/lang/Object.class`. The
+/// class file is borrowed from the mapped archive, which lives for the rest of the process.
+pub fn lookup_class(module: &str, path: &str) -> Option<&'static [u1]> {
+	let archive = SHARED_ARCHIVE.get()?;
+	archive.class_bytes(&format!("/{module}/{path}"))
+}
+
+/// Write an archive of every class currently loaded from the runtime image to `path`
+///
+/// Returns the number of classes archived.
+pub fn dump_archive(path: &Path) -> Result<usize, ArchiveError> {
+	let image = image_identity()?;
+
+	let mut classes = Vec::new();
+	for class in ClassLoaderSet::loaded_classes() {
+		if !class.loader().is_bootstrap() || class.is_array() || class.is_hidden() {
+			continue;
+		}
+
+		// Classes in the unnamed module come from `-Xbootclasspath/a`, not the runtime image
+		let Some(module) = class.module().name() else {
+			continue;
+		};
+
+		let path = format!("{}.class", class.name().as_str());
+		let bytes = lookup_class(module.as_str(), &path)
+			.map(Box::from)
+			.or_else(|| jimage::lookup_vm_resource(module.as_str(), &path));
+		if let Some(bytes) = bytes {
+			classes.push((format!("/{}/{path}", module.as_str()), bytes));
+		}
+	}
+
+	archive::write(path, image, &classes)?;
+	Ok(classes.len())
+}
+
+/// Write the archive and class list requested through `-XX:ArchiveClassesAtExit` and
+/// `-XX:DumpLoadedClassList`
+///
+/// This only does anything the first time it's called.
+pub fn dump_at_exit() {
+	if DUMPED_AT_EXIT.swap(true, Ordering::AcqRel) {
+		return;
+	}
+
+	let options = JvmOptions::get();
+	if let Some(path) = &options.archive_classes_at_exit {
+		match dump_archive(path) {
+			Ok(count) => {
+				info!(TARGETS: (Cds), "Archived {count} classes to {}", path.display());
+			},
+			Err(e) => {
+				warn!(
+					TARGETS: (Cds),
+					"Unable to write shared archive {}: {e}",
+					path.display()
+				);
+			},
+		}
+	}
+
+	if let Some(path) = &options.dump_loaded_class_list
+		&& let Err(e) = class_list::dump(path)
+	{
+		warn!(
+			TARGETS: (Cds),
+			"Unable to write class list {}: {e}",
+			path.display()
+		);
+	}
+}
@@ -0,0 +1,155 @@
+use crate::classpath::loader::ClassLoader;
+use crate::native::java::lang::String::StringInterner;
+use crate::objects::class::ClassPtr;
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::{
+	Array, ObjectArrayInstance, ObjectArrayInstanceRef, PrimitiveArrayInstance,
+	PrimitiveArrayInstanceRef,
+};
+use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
+use crate::objects::reference::Reference;
+use crate::options::assertions::{AssertionDirective, AssertionOptions};
+use crate::symbols::sym;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use classfile::FieldType;
+use common::int_types::s4;
+use instructions::Operand;
+use jni::sys::{jboolean, jint};
+
+/// Load and initialize `java.lang.AssertionStatusDirectives`, along with its field offsets
+///
+/// This is done lazily, as the class is only needed once per class loader.
+pub fn initialize(thread: &'static JavaThread) -> Throws<ClassPtr> {
+	static INITIALIZED: AtomicBool = AtomicBool::new(false);
+	static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_AssertionStatusDirectives());
+	}
+
+	let _guard = INIT_LOCK.lock().unwrap();
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_AssertionStatusDirectives());
+	}
+
+	let class = ClassLoader::bootstrap().load(sym!(java_lang_AssertionStatusDirectives))?;
+	class.initialize(thread)?;
+
+	unsafe {
+		crate::globals::classes::set_java_lang_AssertionStatusDirectives(class);
+		init_offsets();
+	}
+
+	INITIALIZED.store(true, Ordering::Release);
+	Throws::Ok(class)
+}
+
+/// Create a new `java.lang.AssertionStatusDirectives` describing `options`
+///
+/// The directives are kept in command line order, since `ClassLoader` lets later entries override
+/// earlier ones.
+pub fn new(thread: &'static JavaThread, options: &AssertionOptions) -> Throws<ClassInstanceRef> {
+	let class = initialize(thread)?;
+	let directives = ClassInstance::new(class)?;
+
+	let (classes, class_enabled) = directive_arrays(options.classes())?;
+	set_classes(directives, classes);
+	set_classEnabled(directives, class_enabled);
+
+	let (packages, package_enabled) = directive_arrays(options.packages())?;
+	set_packages(directives, packages);
+	set_packageEnabled(directives, package_enabled);
+
+	set_deflt(directives, options.user_default);
+
+	Throws::Ok(directives)
+}
+
+/// Create the parallel `String[]` and `boolean[]` arrays for `directives`
+fn directive_arrays<'a>(
+	directives: impl Iterator<Item = &'a AssertionDirective>,
+) -> Throws<(ObjectArrayInstanceRef, PrimitiveArrayInstanceRef)> {
+	let directives = directives.collect::<Vec<_>>();
+
+	let names = ObjectArrayInstance::new(
+		directives.len() as s4,
+		crate::globals::classes::java_lang_String(),
+	)?;
+	for (index, directive) in directives.iter().enumerate() {
+		let name = StringInterner::intern(directive.name.as_str())?;
+		unsafe {
+			names.store_unchecked(index, Reference::class(name));
+		}
+	}
+
+	let enabled = directives
+		.iter()
+		.map(|directive| directive.enabled)
+		.collect::<Vec<jboolean>>();
+	let enabled = PrimitiveArrayInstance::new::<jboolean>(&enabled)?;
+
+	Throws::Ok((names, enabled))
+}
+
+pub fn set_classes(instance: ClassInstanceRef, value: ObjectArrayInstanceRef) {
+	instance.put_field_value0(
+		classes_field_index(),
+		Operand::Reference(Reference::object_array(value)),
+	)
+}
+
+pub fn set_classEnabled(instance: ClassInstanceRef, value: PrimitiveArrayInstanceRef) {
+	instance.put_field_value0(
+		classEnabled_field_index(),
+		Operand::Reference(Reference::array(value)),
+	)
+}
+
+pub fn set_packages(instance: ClassInstanceRef, value: ObjectArrayInstanceRef) {
+	instance.put_field_value0(
+		packages_field_index(),
+		Operand::Reference(Reference::object_array(value)),
+	)
+}
+
+pub fn set_packageEnabled(instance: ClassInstanceRef, value: PrimitiveArrayInstanceRef) {
+	instance.put_field_value0(
+		packageEnabled_field_index(),
+		Operand::Reference(Reference::array(value)),
+	)
+}
+
+pub fn set_deflt(instance: ClassInstanceRef, value: jboolean) {
+	instance.put_field_value0(deflt_field_index(), Operand::Int(jint::from(value)))
+}
+
+crate::classes::field_module! {
+	@CLASS java_lang_AssertionStatusDirectives;
+
+	@FIELDSTART
+	/// `java.lang.AssertionStatusDirectives#classes` field offset
+	///
+	/// Expected field type: `Reference` to `String[]`
+	@FIELD classes: FieldType::Array(val) if val.is_class(b"java/lang/String"),
+	/// `java.lang.AssertionStatusDirectives#classEnabled` field offset
+	///
+	/// Expected field type: `Reference` to `boolean[]`
+	@FIELD classEnabled: FieldType::Array(val) if **val == FieldType::Boolean,
+	/// `java.lang.AssertionStatusDirectives#packages` field offset
+	///
+	/// Expected field type: `Reference` to `String[]`
+	@FIELD packages: FieldType::Array(val) if val.is_class(b"java/lang/String"),
+	/// `java.lang.AssertionStatusDirectives#packageEnabled` field offset
+	///
+	/// Expected field type: `Reference` to `boolean[]`
+	@FIELD packageEnabled: FieldType::Array(val) if **val == FieldType::Boolean,
+	/// `java.lang.AssertionStatusDirectives#deflt` field offset
+	///
+	/// Expected field type: `jboolean`
+	@FIELD deflt: FieldType::Boolean,
+}
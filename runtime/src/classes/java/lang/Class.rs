@@ -1,5 +1,6 @@
 use crate::classes::AsMirrorInstanceRef;
 use crate::objects::instance::Instance;
+use crate::objects::instance::mirror::MirrorInstanceRef;
 use crate::objects::reference::Reference;
 
 use classfile::FieldType;
@@ -47,6 +48,28 @@ pub fn set_classLoader<I: AsMirrorInstanceRef>(instance: I, value: Reference) {
 		.put_field_value0(classLoader_field_index(), Operand::Reference(value))
 }
 
+/// `java.lang.Class#protection_domain` injected field
+pub fn protection_domain(instance: MirrorInstanceRef) -> Reference {
+	instance
+		.get_field_value0(protection_domain_field_index())
+		.expect_reference()
+}
+
+pub fn set_protection_domain(instance: MirrorInstanceRef, value: Reference) {
+	instance.put_field_value0(protection_domain_field_index(), Operand::Reference(value))
+}
+
+/// `java.lang.Class#signers` injected field
+pub fn signers(instance: MirrorInstanceRef) -> Reference {
+	instance
+		.get_field_value0(signers_field_index())
+		.expect_reference()
+}
+
+pub fn set_signers(instance: MirrorInstanceRef, value: Reference) {
+	instance.put_field_value0(signers_field_index(), Operand::Reference(value))
+}
+
 crate::classes::field_module! {
 	@CLASS java_lang_Class;
 
@@ -83,4 +106,8 @@ crate::classes::field_module! {
 	///
 	/// Expected type: `Reference` to `java.lang.Class`
 	@FIELD componentType: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Class"),
+	/// Injected `java.security.ProtectionDomain` of the class
+	@INJECTED protection_domain: FieldType::Object((*b"java/lang/Object").into()) => jni::sys::jobject,
+	/// Injected `Object[]` of the class signers
+	@INJECTED signers: FieldType::Object((*b"java/lang/Object").into()) => jni::sys::jobject,
 }
@@ -20,7 +20,7 @@ pub fn register_natives() {
 				continue;
 			}
 
-			if method.name == sym!(hashCode) {
+			if method.name == sym!(hashCode_name) {
 				method.set_entry_point(MethodEntryPoint::NativeMethod(NativeMethodPtr::External(
 					crate::native::jvm::object::JVM_IHashCode as *const c_void,
 				)));
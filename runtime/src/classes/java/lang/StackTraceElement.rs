@@ -1,11 +1,104 @@
+use crate::classpath::loader::ClassLoader;
+use crate::native::java::lang::String::StringInterner;
+use crate::native::java::lang::Throwable::BackTraceElement;
+use crate::objects::class::ClassPtr;
 use crate::objects::instance::Instance;
-use crate::objects::instance::class::ClassInstanceRef;
+use crate::objects::instance::array::{Array, ObjectArrayInstance, ObjectArrayInstanceRef};
+use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
+use crate::objects::method::Method;
 use crate::objects::reference::Reference;
+use crate::symbols::sym;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use classfile::FieldType;
+use common::int_types::{s4, s8};
 use instructions::Operand;
 use jni::sys::jint;
 
+/// Load and initialize `java.lang.StackTraceElement`, along with its field offsets
+///
+/// This is done lazily, as nothing needs the class until the first stack trace is requested.
+pub fn initialize(thread: &'static JavaThread) -> Throws<ClassPtr> {
+	static INITIALIZED: AtomicBool = AtomicBool::new(false);
+	static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_StackTraceElement());
+	}
+
+	let _guard = INIT_LOCK.lock().unwrap();
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_StackTraceElement());
+	}
+
+	let class = ClassLoader::bootstrap().load(sym!(java_lang_StackTraceElement))?;
+	class.initialize(thread)?;
+
+	unsafe {
+		crate::globals::classes::set_java_lang_StackTraceElement(class);
+		init_offsets();
+	}
+
+	INITIALIZED.store(true, Ordering::Release);
+	Throws::Ok(class)
+}
+
+/// Create a `java.lang.StackTraceElement[]` from the given frames
+pub fn new_array(
+	thread: &'static JavaThread,
+	frames: &[BackTraceElement],
+) -> Throws<ObjectArrayInstanceRef> {
+	let class = initialize(thread)?;
+
+	let array = ObjectArrayInstance::new(frames.len() as s4, class)?;
+	for (index, frame) in frames.iter().enumerate() {
+		let element = ClassInstance::new(class)?;
+		fill_in(element, frame.method, frame.pc)?;
+		unsafe {
+			array.store_unchecked(index, Reference::class(element));
+		}
+	}
+
+	Throws::Ok(array)
+}
+
+/// Fill in the fields of `stacktrace_element` to describe `method` at `pc`
+pub fn fill_in(stacktrace_element: ClassInstanceRef, method: &Method, pc: s8) -> Throws<()> {
+	let declaring_class_object = method.class().mirror();
+	set_declaringClassObject(
+		stacktrace_element,
+		Reference::mirror(declaring_class_object),
+	);
+
+	// TODO: classLoaderName
+	// TODO: moduleName
+	// TODO: moduleVersion
+	let declaring_class = StringInterner::intern(method.class().external_name())?;
+	set_declaringClass(stacktrace_element, Reference::class(declaring_class));
+
+	let method_name = StringInterner::intern(method.name)?;
+	set_methodName(stacktrace_element, Reference::class(method_name));
+
+	match method.class().source_file_name() {
+		Some(name) => {
+			let file_name = StringInterner::intern(name)?;
+			set_fileName(stacktrace_element, Reference::class(file_name));
+		},
+		None => {
+			set_fileName(stacktrace_element, Reference::null());
+		},
+	}
+
+	let line_number = method.line_number(pc as isize);
+	set_lineNumber(stacktrace_element, line_number);
+
+	Throws::Ok(())
+}
+
 pub fn set_declaringClassObject(instance: ClassInstanceRef, value: Reference) {
 	assert!(value.is_instance_of(crate::globals::classes::java_lang_Class()));
 	instance.put_field_value0(
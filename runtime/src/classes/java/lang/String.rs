@@ -1,6 +1,9 @@
 use crate::native::java::lang::String::LATIN1;
 use crate::objects::instance::Instance;
-use crate::objects::instance::array::{PrimitiveArrayInstance, PrimitiveArrayInstanceRef};
+use crate::objects::instance::array::{
+	Array, ObjectArrayInstance, ObjectArrayInstanceRef, PrimitiveArrayInstance,
+	PrimitiveArrayInstanceRef,
+};
 use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
 use crate::objects::instance::object::Object;
 use crate::objects::reference::Reference;
@@ -13,7 +16,7 @@ use std::ptr::slice_from_raw_parts;
 
 use byte_slice_cast::AsSliceOf;
 use classfile::FieldType;
-use common::int_types::u2;
+use common::int_types::{s4, u2};
 use instructions::Operand;
 use jni::sys::{jboolean, jbyte, jint, jsize};
 
@@ -79,11 +82,11 @@ impl IntoJavaStringInternable for &[u16] {
 /// Create a new `java.lang.String` instance
 ///
 /// This will automatically handle compacting of Latin-1 strings if enabled.
-pub fn new<T>(content: T) -> ClassInstanceRef
+pub fn new<T>(content: T) -> Throws<ClassInstanceRef>
 where
 	T: IntoJavaStringInternable,
 {
-	fn inner(string: &[u8], is_utf8: bool) -> ClassInstanceRef {
+	fn inner(string: &[u8], is_utf8: bool) -> Throws<ClassInstanceRef> {
 		let mut is_latin1 = false;
 		if is_utf8 && native::java::lang::String::COMPACT_STRINGS {
 			is_latin1 = str_is_latin1(string);
@@ -106,12 +109,12 @@ where
 			encoded_str = Cow::Borrowed(byte_slice);
 		}
 
-		let new_java_string_instance = ClassInstance::new(globals::classes::java_lang_String());
+		let new_java_string_instance = ClassInstance::new(globals::classes::java_lang_String())?;
 
 		// Set `private byte[] value`
 		set_value(
 			new_java_string_instance,
-			Reference::array(PrimitiveArrayInstance::new::<jbyte>(&*encoded_str)),
+			Reference::array(PrimitiveArrayInstance::new::<jbyte>(&*encoded_str)?),
 		);
 
 		// Set `private final byte coder`
@@ -121,7 +124,7 @@ where
 			set_coder(new_java_string_instance, native::java::lang::String::UTF16);
 		}
 
-		new_java_string_instance
+		Throws::Ok(new_java_string_instance)
 	}
 
 	let string = content.byte_slice();
@@ -131,13 +134,33 @@ where
 /// Create a new `java.lang.String` instance with the given hash
 ///
 /// Same as [`new()`], but also sets the hash field. This should only be used in string interning.
-pub fn new_with_hash<T>(content: T, hash: jint) -> ClassInstanceRef
+pub fn new_with_hash<T>(content: T, hash: jint) -> Throws<ClassInstanceRef>
 where
 	T: IntoJavaStringInternable,
 {
-	let java_string = new(content);
+	let java_string = new(content)?;
 	set_hash(java_string, hash);
-	java_string
+	Throws::Ok(java_string)
+}
+
+/// Create a new `java.lang.String[]` holding `contents`
+pub fn new_array<I>(contents: I) -> Throws<ObjectArrayInstanceRef>
+where
+	I: IntoIterator,
+	I::IntoIter: ExactSizeIterator,
+	I::Item: IntoJavaStringInternable,
+{
+	let contents = contents.into_iter();
+	let array =
+		ObjectArrayInstance::new(contents.len() as s4, globals::classes::java_lang_String())?;
+	for (index, content) in contents.enumerate() {
+		let string = new(content)?;
+		unsafe {
+			array.store_unchecked(index, Reference::class(string));
+		}
+	}
+
+	Throws::Ok(array)
 }
 
 fn str_is_latin1(string: &[u8]) -> bool {
@@ -6,7 +6,10 @@ use crate::objects::reference::Reference;
 
 use classfile::FieldType;
 use instructions::Operand;
-use jni::sys::jlong;
+use jni::sys::{jint, jlong};
+
+/// `java.lang.Thread#NORM_PRIORITY`, the default priority
+pub const NORM_PRIORITY: jint = 5;
 
 /// Value for the `java.lang.Thread$FieldHolder#status` field
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -25,6 +28,25 @@ pub enum ThreadStatus {
 
 impl ThreadStatus {
 	const MAX: ThreadStatus = ThreadStatus::Terminated;
+
+	/// The name of the `java.lang.Thread$State` this status maps to
+	pub fn state_name(self) -> &'static str {
+		match self {
+			ThreadStatus::New => "NEW",
+			ThreadStatus::Runnable => "RUNNABLE",
+			ThreadStatus::Sleeping
+			| ThreadStatus::InObjectWaitTimed
+			| ThreadStatus::ParkedTimed => "TIMED_WAITING",
+			ThreadStatus::InObjectWait | ThreadStatus::Parked => "WAITING",
+			ThreadStatus::BlockedOnMonitorEnter => "BLOCKED",
+			ThreadStatus::Terminated => "TERMINATED",
+		}
+	}
+
+	/// Whether a thread with this status is alive
+	pub fn is_alive(self) -> bool {
+		!matches!(self, ThreadStatus::New | ThreadStatus::Terminated)
+	}
 }
 
 pub fn eetop(instance: ClassInstanceRef) -> jlong {
@@ -35,6 +57,11 @@ pub fn set_eetop(instance: ClassInstanceRef, value: jlong) {
 	instance.put_field_value0(eetop_field_index(), Operand::Long(value))
 }
 
+/// `java.lang.Thread#tid` field
+pub fn tid(instance: ClassInstanceRef) -> jlong {
+	instance.get_field_value0(tid_field_index()).expect_long()
+}
+
 /// `java.lang.Thread#name` field
 pub fn name(instance: ClassInstanceRef) -> Reference {
 	instance
@@ -65,6 +92,10 @@ crate::classes::field_module! {
 	///
 	/// Expected type: `jlong`
 	@FIELD eetop: FieldType::Long,
+	/// `java.lang.Thread#tid` field offset
+	///
+	/// Expected type: `jlong`
+	@FIELD tid: FieldType::Long,
 	/// `java.lang.Thread#name` field offset
 	///
 	/// Expected type: `Reference` to `java.lang.String`
@@ -55,6 +55,11 @@ pub fn set_depth<I: AsClassInstanceRef>(instance: I, value: jint) {
 		.put_field_value0(depth_field_index(), Operand::Int(value))
 }
 
+/// `java.lang.Throwable#cause` field
+pub fn set_cause(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(cause_field_index(), Operand::Reference(value))
+}
+
 pub fn detail_message<I: AsClassInstanceRef>(instance: I) -> Reference {
 	instance
 		.as_class_instance_ref()
@@ -188,4 +193,8 @@ crate::classes::field_module! {
 	///
 	/// Expected field type: `jint`
 	@FIELD depth: FieldType::Integer,
+	/// `java.lang.Throwable#cause` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.Throwable`
+	@FIELD cause: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Throwable"),
 }
@@ -20,10 +20,22 @@ pub fn form(instance: ClassInstanceRef) -> ClassInstanceRef {
 		.extract_class()
 }
 
+/// `java.lang.invoke.MethodHandle#type` field
+pub fn type_(instance: ClassInstanceRef) -> ClassInstanceRef {
+	instance
+		.get_field_value0(type__field_index())
+		.expect_reference()
+		.extract_class()
+}
+
 crate::classes::field_module! {
 	@CLASS java_lang_invoke_MethodHandle;
 
 	@FIELDSTART
+	/// `java.lang.invoke.MethodHandle#type` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.invoke.MethodType`
+	[sym: r#type] @FIELD type_: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/invoke/MethodType"),
 	/// `java.lang.invoke.MethodHandle#form` field offset
 	///
 	/// Expected field type: `Reference` to `java.lang.invoke.LambdaForm`
@@ -83,6 +95,13 @@ pub fn init_entry_points() {
 			));
 			continue;
 		}
+
+		if method.name == sym!(linkToNative_name) {
+			method.set_entry_point(MethodEntryPoint::MethodHandleLinker(
+				_dynamic::link_to_native,
+			));
+			continue;
+		}
 	}
 }
 
@@ -213,4 +232,13 @@ mod _dynamic {
 
 		frame.tail(target_method);
 	}
+
+	pub fn link_to_native(frame: &mut Frame, _entry: MethodEntry) {
+		let entry_point = frame.pop_reference().extract_class();
+		let stub_address =
+			classes::jdk::internal::foreign::abi::NativeEntryPoint::downcallStubAddress(
+				entry_point,
+			);
+		crate::native::foreign::link_to_native(frame, stub_address as usize);
+	}
 }
@@ -4,18 +4,19 @@ use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
 use crate::objects::instance::mirror::MirrorInstanceRef;
 use crate::objects::method::Method;
 use crate::objects::reference::Reference;
+use crate::thread::exceptions::Throws;
 
 use classfile::FieldType;
 use instructions::Operand;
 use jni::sys::jlong;
 
-pub fn new(method: &'static Method) -> Reference {
-	let instance = ClassInstance::new(globals::classes::java_lang_invoke_ResolvedMethodName());
+pub fn new(method: &'static Method) -> Throws<Reference> {
+	let instance = ClassInstance::new(globals::classes::java_lang_invoke_ResolvedMethodName())?;
 
 	set_vmtarget(instance, method);
 	set_vmholder(instance, method.class().mirror());
 
-	Reference::class(instance)
+	Throws::Ok(Reference::class(instance))
 }
 
 pub fn vmholder(instance: ClassInstanceRef) -> Reference {
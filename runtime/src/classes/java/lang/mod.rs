@@ -1,3 +1,4 @@
+pub mod AssertionStatusDirectives;
 pub mod Class;
 pub mod ClassLoader;
 pub mod Module;
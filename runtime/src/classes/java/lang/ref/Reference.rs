@@ -1,5 +1,6 @@
 use crate::classes::AsClassInstanceRef;
 use crate::objects::instance::Instance;
+use crate::objects::instance::class::ClassInstanceRef;
 use crate::objects::reference::Reference;
 
 use classfile::FieldType;
@@ -13,6 +14,14 @@ crate::classes::field_module! {
 	///
 	/// Expected type: `Reference`
 	@FIELD referent: FieldType::Object(_),
+	/// `java.lang.ref.Reference#next` field offset
+	///
+	/// Expected type: `Reference` to `java.lang.ref.Reference`
+	@FIELD next: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/ref/Reference"),
+	/// `java.lang.ref.Reference#discovered` field offset
+	///
+	/// Expected type: `Reference` to `java.lang.ref.Reference`
+	@FIELD discovered: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/ref/Reference"),
 }
 
 pub fn referent<I: AsClassInstanceRef>(this: I) -> Reference {
@@ -25,3 +34,20 @@ pub fn set_referent<I: AsClassInstanceRef>(this: I, referent: Reference) {
 	this.as_class_instance_ref()
 		.put_field_value0(referent_field_index(), Operand::Reference(referent));
 }
+
+pub fn next(this: ClassInstanceRef) -> Reference {
+	this.get_field_value0(next_field_index()).expect_reference()
+}
+
+pub fn set_next(this: ClassInstanceRef, next: Reference) {
+	this.put_field_value0(next_field_index(), Operand::Reference(next));
+}
+
+pub fn discovered(this: ClassInstanceRef) -> Reference {
+	this.get_field_value0(discovered_field_index())
+		.expect_reference()
+}
+
+pub fn set_discovered(this: ClassInstanceRef, discovered: Reference) {
+	this.put_field_value0(discovered_field_index(), Operand::Reference(discovered));
+}
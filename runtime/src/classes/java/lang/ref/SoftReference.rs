@@ -0,0 +1,37 @@
+use crate::objects::instance::Instance;
+use crate::objects::instance::class::ClassInstanceRef;
+
+use classfile::FieldType;
+use instructions::Operand;
+use jni::sys::jlong;
+
+crate::classes::field_module! {
+	@CLASS java_lang_ref_SoftReference;
+
+	@FIELDSTART
+	/// `java.lang.ref.SoftReference#clock` field offset
+	///
+	/// Expected type: `jlong`
+	@FIELD clock: FieldType::Long,
+	/// `java.lang.ref.SoftReference#timestamp` field offset
+	///
+	/// Expected type: `jlong`
+	@FIELD timestamp: FieldType::Long,
+}
+
+/// The value of the static `java.lang.ref.SoftReference#clock` field
+pub fn clock() -> jlong {
+	let class = crate::globals::classes::java_lang_ref_SoftReference();
+	class.static_field_value(clock_field_index()).expect_long()
+}
+
+/// Set the static `java.lang.ref.SoftReference#clock` field
+pub fn set_clock(value: jlong) {
+	let class = crate::globals::classes::java_lang_ref_SoftReference();
+	class.set_static_field_volatile(clock_field_index(), Operand::Long(value));
+}
+
+/// The clock value at the last time `get()` was called on this reference
+pub fn timestamp(this: ClassInstanceRef) -> jlong {
+	this.get_field_value0(timestamp_field_index()).expect_long()
+}
@@ -1 +1,2 @@
 pub mod Reference;
+pub mod SoftReference;
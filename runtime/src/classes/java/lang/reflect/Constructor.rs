@@ -18,7 +18,7 @@ use jni::sys::jint;
 pub fn new(method: &Method) -> Throws<ClassInstanceRef> {
 	assert_eq!(method.name, sym!(object_initializer_name));
 
-	let constructor = ClassInstance::new(globals::classes::java_lang_reflect_Constructor());
+	let constructor = ClassInstance::new(globals::classes::java_lang_reflect_Constructor())?;
 
 	// The slot is the method's position in the vtable
 	let slot = method
@@ -37,7 +37,7 @@ pub fn new(method: &Method) -> Throws<ClassInstanceRef> {
 	set_exceptionTypes(constructor, Reference::object_array(exception_types));
 	set_modifiers(constructor, jint::from(method.access_flags.as_u2()));
 	if let Some(generic_signature) = method.generic_signature() {
-		let signature = StringInterner::intern(generic_signature);
+		let signature = StringInterner::intern(generic_signature)?;
 		set_signature(constructor, Reference::class(signature));
 	}
 	// // TODO
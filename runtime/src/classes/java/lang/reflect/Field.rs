@@ -1,4 +1,5 @@
 use crate::globals;
+use crate::native::java::lang::String::StringInterner;
 use crate::objects::field::Field;
 use crate::objects::instance::Instance;
 use crate::objects::instance::array::PrimitiveArrayInstanceRef;
@@ -9,12 +10,46 @@ use crate::thread::exceptions::Throws;
 
 use classfile::FieldType;
 use instructions::Operand;
-use jni::sys::jint;
+use jni::sys::{jboolean, jint};
+
+/// The field modifiers that are exposed through `java.lang.reflect.Field#getModifiers`
+const JVM_RECOGNIZED_FIELD_MODIFIERS: jint = 0x50DF;
 
 /// Create a new `java.lang.reflect.Field` instance for the given field
-pub fn new(_field: &Field) -> Throws<ClassInstanceRef> {
-	let _reflect_field = ClassInstance::new(globals::classes::java_lang_reflect_Field());
-	todo!()
+pub fn new(field: &'static Field) -> Throws<ClassInstanceRef> {
+	let reflect_field = ClassInstance::new(globals::classes::java_lang_reflect_Field())?;
+
+	// The slot is the field's position in the class field list
+	let slot = field
+		.class
+		.fields()
+		.position(|f| std::ptr::eq(f, field))
+		.expect("a field must be present in its class");
+
+	set_clazz(reflect_field, field.class.mirror());
+	set_slot(reflect_field, slot as jint);
+	set_name(reflect_field, StringInterner::intern(field.name)?);
+	set_type_(reflect_field, field.type_mirror()?);
+	set_modifiers(
+		reflect_field,
+		jint::from(field.access_flags.as_u2()) & JVM_RECOGNIZED_FIELD_MODIFIERS,
+	);
+	set_trustedFinal(reflect_field, field.is_trusted_final());
+	if let Some(generic_signature) = field.generic_signature() {
+		set_signature(reflect_field, StringInterner::intern(generic_signature)?);
+	}
+
+	if let Some(annotations) = field.annotations_array()? {
+		set_annotations(reflect_field, annotations);
+	}
+
+	Throws::Ok(reflect_field)
+}
+
+pub fn vmtarget(instance: ClassInstanceRef) -> Option<&'static Field> {
+	let mirror = clazz(instance);
+	let slot = slot(instance);
+	mirror.target_class().fields().nth(slot as usize)
 }
 
 pub fn clazz(instance: ClassInstanceRef) -> MirrorInstanceRef {
@@ -53,6 +88,20 @@ pub fn set_name(instance: ClassInstanceRef, value: ClassInstanceRef) {
 	)
 }
 
+pub fn type_(instance: ClassInstanceRef) -> MirrorInstanceRef {
+	instance
+		.get_field_value0(type__field_index())
+		.expect_reference()
+		.extract_mirror()
+}
+
+pub fn set_type_(instance: ClassInstanceRef, value: MirrorInstanceRef) {
+	instance.put_field_value0(
+		type__field_index(),
+		Operand::Reference(Reference::mirror(value)),
+	)
+}
+
 pub fn modifiers(instance: ClassInstanceRef) -> jint {
 	instance
 		.get_field_value0(modifiers_field_index())
@@ -63,6 +112,17 @@ pub fn set_modifiers(instance: ClassInstanceRef, value: jint) {
 	instance.put_field_value0(modifiers_field_index(), Operand::Int(value))
 }
 
+pub fn trustedFinal(instance: ClassInstanceRef) -> jboolean {
+	instance
+		.get_field_value0(trustedFinal_field_index())
+		.expect_int()
+		!= 0
+}
+
+pub fn set_trustedFinal(instance: ClassInstanceRef, value: jboolean) {
+	instance.put_field_value0(trustedFinal_field_index(), Operand::Int(jint::from(value)))
+}
+
 pub fn signature(instance: ClassInstanceRef) -> ClassInstanceRef {
 	instance
 		.get_field_value0(signature_field_index())
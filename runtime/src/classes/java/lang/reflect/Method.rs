@@ -14,7 +14,7 @@ use jni::sys::jint;
 
 /// Create a new `java.lang.reflect.Method` instance for the given method
 pub fn new(method: &Method) -> Throws<ClassInstanceRef> {
-	let reflect_method = ClassInstance::new(globals::classes::java_lang_reflect_Method());
+	let reflect_method = ClassInstance::new(globals::classes::java_lang_reflect_Method())?;
 
 	// The slot is the method's position in the vtable
 	let slot = method
@@ -26,24 +26,24 @@ pub fn new(method: &Method) -> Throws<ClassInstanceRef> {
 
 	set_clazz(reflect_method, method.class().mirror());
 	set_slot(reflect_method, slot as jint);
-	set_name(reflect_method, StringInterner::intern(method.name));
+	set_name(reflect_method, StringInterner::intern(method.name)?);
 	set_returnType(reflect_method, method.return_type()?);
 	set_parameterTypes(reflect_method, method.parameter_types_array()?);
 	set_exceptionTypes(reflect_method, method.exception_types()?);
 	set_modifiers(reflect_method, jint::from(method.access_flags.as_u2()));
 	if let Some(generic_signature) = method.generic_signature() {
-		set_signature(reflect_method, StringInterner::intern(generic_signature));
+		set_signature(reflect_method, StringInterner::intern(generic_signature)?);
 	}
 
-	if let Some(annotations) = method.annotations_array() {
+	if let Some(annotations) = method.annotations_array()? {
 		set_annotations(reflect_method, annotations);
 	}
 
-	if let Some(parameter_annotations) = method.parameter_annotations_array() {
+	if let Some(parameter_annotations) = method.parameter_annotations_array()? {
 		set_parameterAnnotations(reflect_method, parameter_annotations);
 	}
 
-	if let Some(annotation_default) = method.annotation_default() {
+	if let Some(annotation_default) = method.annotation_default()? {
 		set_annotationDefault(reflect_method, annotation_default);
 	}
 
@@ -0,0 +1,171 @@
+use crate::classpath::loader::ClassLoader;
+use crate::native::java::lang::String::StringInterner;
+use crate::objects::class::{ClassPtr, RecordComponentInfo};
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::{Array, ObjectArrayInstance, ObjectArrayInstanceRef};
+use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
+use crate::objects::method::field_type_mirror;
+use crate::objects::reference::Reference;
+use crate::symbols::{Symbol, sym};
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use classfile::FieldType;
+use common::int_types::s4;
+use instructions::Operand;
+
+/// Load and initialize `java.lang.reflect.RecordComponent`, along with its field offsets
+///
+/// This is done lazily, as nothing needs the class until records are first reflected on.
+pub fn initialize(thread: &'static JavaThread) -> Throws<ClassPtr> {
+	static INITIALIZED: AtomicBool = AtomicBool::new(false);
+	static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_reflect_RecordComponent());
+	}
+
+	let _guard = INIT_LOCK.lock().unwrap();
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::java_lang_reflect_RecordComponent());
+	}
+
+	let class = ClassLoader::bootstrap().load(sym!(java_lang_reflect_RecordComponent))?;
+	class.initialize(thread)?;
+
+	unsafe {
+		crate::globals::classes::set_java_lang_reflect_RecordComponent(class);
+		init_offsets();
+	}
+
+	INITIALIZED.store(true, Ordering::Release);
+	Throws::Ok(class)
+}
+
+/// Create a `java.lang.reflect.RecordComponent[]` for the components of the record `class`
+pub fn new_array(
+	thread: &'static JavaThread,
+	class: ClassPtr,
+	components: &[RecordComponentInfo],
+) -> Throws<ObjectArrayInstanceRef> {
+	let record_component_class = initialize(thread)?;
+
+	let array = ObjectArrayInstance::new(components.len() as s4, record_component_class)?;
+	for (index, component) in components.iter().enumerate() {
+		let record_component = new(record_component_class, class, component)?;
+		unsafe {
+			array.store_unchecked(index, Reference::class(record_component));
+		}
+	}
+
+	Throws::Ok(array)
+}
+
+fn new(
+	record_component_class: ClassPtr,
+	class: ClassPtr,
+	component: &RecordComponentInfo,
+) -> Throws<ClassInstanceRef> {
+	let record_component = ClassInstance::new(record_component_class)?;
+
+	set_clazz(record_component, Reference::mirror(class.mirror()));
+	set_name(
+		record_component,
+		Reference::class(StringInterner::intern(component.name)?),
+	);
+
+	let type_mirror = field_type_mirror(class, &component.descriptor)?;
+	set_type_(record_component, Reference::mirror(type_mirror));
+
+	// The accessor is the method with the same name as the component, taking no arguments
+	let accessor_descriptor = Symbol::intern(format!("(){}", component.descriptor_sym));
+	let accessor = class.vtable().iter_local().find(|method| {
+		method.name == component.name && method.descriptor_sym() == accessor_descriptor
+	});
+	if let Some(accessor) = accessor {
+		let reflect_method = crate::classes::java::lang::reflect::Method::new(accessor)?;
+		set_accessor(record_component, Reference::class(reflect_method));
+	}
+
+	if let Some(signature) = component.signature {
+		set_signature(
+			record_component,
+			Reference::class(StringInterner::intern(signature)?),
+		);
+	}
+
+	if let Some(annotations) = component.annotations_array()? {
+		set_annotations(record_component, Reference::array(annotations));
+	}
+
+	if let Some(type_annotations) = component.type_annotations_array()? {
+		set_typeAnnotations(record_component, Reference::array(type_annotations));
+	}
+
+	Throws::Ok(record_component)
+}
+
+pub fn set_clazz(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(clazz_field_index(), Operand::Reference(value))
+}
+
+pub fn set_name(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(name_field_index(), Operand::Reference(value))
+}
+
+pub fn set_type_(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(type__field_index(), Operand::Reference(value))
+}
+
+pub fn set_accessor(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(accessor_field_index(), Operand::Reference(value))
+}
+
+pub fn set_signature(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(signature_field_index(), Operand::Reference(value))
+}
+
+pub fn set_annotations(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(annotations_field_index(), Operand::Reference(value))
+}
+
+pub fn set_typeAnnotations(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(typeAnnotations_field_index(), Operand::Reference(value))
+}
+
+crate::classes::field_module! {
+	@CLASS java_lang_reflect_RecordComponent;
+
+	@FIELDSTART
+	/// `java.lang.reflect.RecordComponent#clazz` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.Class`
+	@FIELD clazz: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Class"),
+	/// `java.lang.reflect.RecordComponent#name` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.String`
+	@FIELD name: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/String"),
+	/// `java.lang.reflect.RecordComponent#type` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.Class`
+	[sym: r#type] @FIELD type_: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Class"),
+	/// `java.lang.reflect.RecordComponent#accessor` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.reflect.Method`
+	@FIELD accessor: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/reflect/Method"),
+	/// `java.lang.reflect.RecordComponent#signature` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.String`
+	@FIELD signature: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/String"),
+	/// `java.lang.reflect.RecordComponent#annotations` field offset
+	///
+	/// Expected field type: `Reference` to `byte[]`
+	@FIELD annotations: FieldType::Array(val) if **val == FieldType::Byte,
+	/// `java.lang.reflect.RecordComponent#typeAnnotations` field offset
+	///
+	/// Expected field type: `Reference` to `byte[]`
+	@FIELD typeAnnotations: FieldType::Array(val) if **val == FieldType::Byte,
+}
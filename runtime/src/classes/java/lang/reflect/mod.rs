@@ -1,3 +1,4 @@
 pub mod Constructor;
 pub mod Field;
 pub mod Method;
+pub mod RecordComponent;
@@ -0,0 +1,66 @@
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::ObjectArrayInstanceRef;
+use crate::objects::instance::class::ClassInstanceRef;
+
+use classfile::FieldType;
+
+pub fn inputStorage(instance: ClassInstanceRef) -> ObjectArrayInstanceRef {
+	instance
+		.get_field_value0(inputStorage_field_index())
+		.expect_reference()
+		.extract_object_array()
+}
+
+pub fn outputStorage(instance: ClassInstanceRef) -> ObjectArrayInstanceRef {
+	instance
+		.get_field_value0(outputStorage_field_index())
+		.expect_reference()
+		.extract_object_array()
+}
+
+pub fn targetAddrStorage(instance: ClassInstanceRef) -> ClassInstanceRef {
+	instance
+		.get_field_value0(targetAddrStorage_field_index())
+		.expect_reference()
+		.extract_class()
+}
+
+pub fn retBufAddrStorage(instance: ClassInstanceRef) -> ClassInstanceRef {
+	instance
+		.get_field_value0(retBufAddrStorage_field_index())
+		.expect_reference()
+		.extract_class()
+}
+
+pub fn capturedStateStorage(instance: ClassInstanceRef) -> ClassInstanceRef {
+	instance
+		.get_field_value0(capturedStateStorage_field_index())
+		.expect_reference()
+		.extract_class()
+}
+
+crate::classes::field_module! {
+	@CLASS jdk_internal_foreign_abi_ABIDescriptor;
+
+	@FIELDSTART
+	/// `jdk.internal.foreign.abi.ABIDescriptor#inputStorage` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage[][]`
+	@FIELD inputStorage: FieldType::Array(_),
+	/// `jdk.internal.foreign.abi.ABIDescriptor#outputStorage` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage[][]`
+	@FIELD outputStorage: FieldType::Array(_),
+	/// `jdk.internal.foreign.abi.ABIDescriptor#targetAddrStorage` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage`
+	@FIELD targetAddrStorage: ty @ FieldType::Object(_) if ty.is_class(b"jdk/internal/foreign/abi/VMStorage"),
+	/// `jdk.internal.foreign.abi.ABIDescriptor#retBufAddrStorage` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage`
+	@FIELD retBufAddrStorage: ty @ FieldType::Object(_) if ty.is_class(b"jdk/internal/foreign/abi/VMStorage"),
+	/// `jdk.internal.foreign.abi.ABIDescriptor#capturedStateStorage` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage`
+	@FIELD capturedStateStorage: ty @ FieldType::Object(_) if ty.is_class(b"jdk/internal/foreign/abi/VMStorage"),
+}
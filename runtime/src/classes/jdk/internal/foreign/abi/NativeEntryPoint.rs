@@ -0,0 +1,21 @@
+use crate::objects::instance::Instance;
+use crate::objects::instance::class::ClassInstanceRef;
+
+use classfile::FieldType;
+use jni::sys::jlong;
+
+pub fn downcallStubAddress(instance: ClassInstanceRef) -> jlong {
+	instance
+		.get_field_value0(downcallStubAddress_field_index())
+		.expect_long()
+}
+
+crate::classes::field_module! {
+	@CLASS jdk_internal_foreign_abi_NativeEntryPoint;
+
+	@FIELDSTART
+	/// `jdk.internal.foreign.abi.NativeEntryPoint#downcallStubAddress` field offset
+	///
+	/// Expected field type: `jlong`
+	@FIELD downcallStubAddress: FieldType::Long,
+}
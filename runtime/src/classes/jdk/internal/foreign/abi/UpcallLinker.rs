@@ -0,0 +1,37 @@
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::ObjectArrayInstanceRef;
+use crate::objects::instance::class::ClassInstanceRef;
+
+use classfile::FieldType;
+
+pub mod CallRegs {
+	use super::*;
+
+	pub fn argRegs(instance: ClassInstanceRef) -> ObjectArrayInstanceRef {
+		instance
+			.get_field_value0(argRegs_field_index())
+			.expect_reference()
+			.extract_object_array()
+	}
+
+	pub fn retRegs(instance: ClassInstanceRef) -> ObjectArrayInstanceRef {
+		instance
+			.get_field_value0(retRegs_field_index())
+			.expect_reference()
+			.extract_object_array()
+	}
+
+	crate::classes::field_module! {
+		@CLASS jdk_internal_foreign_abi_UpcallLinker_CallRegs;
+
+		@FIELDSTART
+		/// `jdk.internal.foreign.abi.UpcallLinker$CallRegs#argRegs` field offset
+		///
+		/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage[]`
+		@FIELD argRegs: FieldType::Array(_),
+		/// `jdk.internal.foreign.abi.UpcallLinker$CallRegs#retRegs` field offset
+		///
+		/// Expected field type: `Reference` to `jdk.internal.foreign.abi.VMStorage[]`
+		@FIELD retRegs: FieldType::Array(_),
+	}
+}
@@ -0,0 +1,39 @@
+use crate::objects::instance::Instance;
+use crate::objects::instance::class::ClassInstanceRef;
+
+use classfile::FieldType;
+use jni::sys::{jbyte, jint, jshort};
+
+pub fn type_(instance: ClassInstanceRef) -> jbyte {
+	instance.get_field_value0(type__field_index()).expect_int() as jbyte
+}
+
+pub fn segmentMaskOrSize(instance: ClassInstanceRef) -> jshort {
+	instance
+		.get_field_value0(segmentMaskOrSize_field_index())
+		.expect_int() as jshort
+}
+
+pub fn indexOrOffset(instance: ClassInstanceRef) -> jint {
+	instance
+		.get_field_value0(indexOrOffset_field_index())
+		.expect_int()
+}
+
+crate::classes::field_module! {
+	@CLASS jdk_internal_foreign_abi_VMStorage;
+
+	@FIELDSTART
+	/// `jdk.internal.foreign.abi.VMStorage#type` field offset
+	///
+	/// Expected field type: `jbyte`
+	[sym: r#type] @FIELD type_: FieldType::Byte,
+	/// `jdk.internal.foreign.abi.VMStorage#segmentMaskOrSize` field offset
+	///
+	/// Expected field type: `jshort`
+	@FIELD segmentMaskOrSize: FieldType::Short,
+	/// `jdk.internal.foreign.abi.VMStorage#indexOrOffset` field offset
+	///
+	/// Expected field type: `jint`
+	@FIELD indexOrOffset: FieldType::Integer,
+}
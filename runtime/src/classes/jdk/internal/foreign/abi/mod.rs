@@ -0,0 +1,48 @@
+use crate::classpath::loader::ClassLoader;
+use crate::symbols::sym;
+use crate::thread::exceptions::Throws;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub mod ABIDescriptor;
+pub mod NativeEntryPoint;
+pub mod UpcallLinker;
+pub mod VMStorage;
+
+/// Load the `jdk.internal.foreign.abi` classes used by the linker, along with their field offsets
+///
+/// This is done lazily, as nothing needs the classes until the first stub is created.
+pub fn initialize() -> Throws<()> {
+	static INITIALIZED: AtomicBool = AtomicBool::new(false);
+	static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(());
+	}
+
+	let _guard = INIT_LOCK.lock().unwrap();
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(());
+	}
+
+	let loader = ClassLoader::bootstrap();
+	let vm_storage = loader.load(sym!(jdk_internal_foreign_abi_VMStorage))?;
+	let abi_descriptor = loader.load(sym!(jdk_internal_foreign_abi_ABIDescriptor))?;
+	let native_entry_point = loader.load(sym!(jdk_internal_foreign_abi_NativeEntryPoint))?;
+	let call_regs = loader.load(sym!(jdk_internal_foreign_abi_UpcallLinker_CallRegs))?;
+
+	unsafe {
+		crate::globals::classes::set_jdk_internal_foreign_abi_VMStorage(vm_storage);
+		crate::globals::classes::set_jdk_internal_foreign_abi_ABIDescriptor(abi_descriptor);
+		crate::globals::classes::set_jdk_internal_foreign_abi_NativeEntryPoint(native_entry_point);
+		crate::globals::classes::set_jdk_internal_foreign_abi_UpcallLinker_CallRegs(call_regs);
+		VMStorage::init_offsets();
+		ABIDescriptor::init_offsets();
+		NativeEntryPoint::init_offsets();
+		UpcallLinker::CallRegs::init_offsets();
+	}
+
+	INITIALIZED.store(true, Ordering::Release);
+	Throws::Ok(())
+}
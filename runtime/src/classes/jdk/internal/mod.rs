@@ -1,3 +1,5 @@
+pub mod foreign;
 pub mod loader;
 pub mod misc;
 pub mod reflect;
+pub mod vm;
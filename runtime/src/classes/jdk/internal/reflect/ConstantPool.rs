@@ -16,7 +16,7 @@ pub fn new(class: ClassPtr, thread: &'static JavaThread) -> Throws<ClassInstance
 	let cp_class = crate::globals::classes::jdk_internal_reflect_ConstantPool();
 	cp_class.initialize(thread)?;
 
-	let instance = ClassInstance::new(cp_class);
+	let instance = ClassInstance::new(cp_class)?;
 	set_constantPoolOop(instance, class);
 	Throws::Ok(instance)
 }
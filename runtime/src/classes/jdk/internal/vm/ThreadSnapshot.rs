@@ -0,0 +1,202 @@
+use crate::classes;
+use crate::classpath::loader::ClassLoader;
+use crate::objects::class::ClassPtr;
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::{Array, ObjectArrayInstance};
+use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
+use crate::objects::reference::Reference;
+use crate::symbols::sym;
+use crate::thread::exceptions::Throws;
+use crate::thread::{JavaThread, dump};
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use classfile::FieldType;
+use common::int_types::s4;
+use instructions::Operand;
+use jni::sys::jint;
+
+/// `jdk.internal.vm.ThreadSnapshot$BlockerLockType` ordinals
+#[allow(dead_code)]
+#[repr(i32)]
+enum BlockerLockType {
+	ParkBlocker = 0,
+	WaitingToLock = 1,
+	WaitingOn = 2,
+}
+
+/// Load and initialize `jdk.internal.vm.ThreadSnapshot` and `jdk.internal.vm.ThreadSnapshot$ThreadLock`,
+/// along with their field offsets
+///
+/// This is done lazily, as nothing needs the classes until the first snapshot is requested.
+pub fn initialize(thread: &'static JavaThread) -> Throws<ClassPtr> {
+	static INITIALIZED: AtomicBool = AtomicBool::new(false);
+	static INIT_LOCK: Mutex<()> = Mutex::new(());
+
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::jdk_internal_vm_ThreadSnapshot());
+	}
+
+	let _guard = INIT_LOCK.lock().unwrap();
+	if INITIALIZED.load(Ordering::Acquire) {
+		return Throws::Ok(crate::globals::classes::jdk_internal_vm_ThreadSnapshot());
+	}
+
+	let class = ClassLoader::bootstrap().load(sym!(jdk_internal_vm_ThreadSnapshot))?;
+	class.initialize(thread)?;
+
+	let thread_lock_class =
+		ClassLoader::bootstrap().load(sym!(jdk_internal_vm_ThreadSnapshot_ThreadLock))?;
+	thread_lock_class.initialize(thread)?;
+
+	unsafe {
+		crate::globals::classes::set_jdk_internal_vm_ThreadSnapshot(class);
+		crate::globals::classes::set_jdk_internal_vm_ThreadSnapshot_ThreadLock(thread_lock_class);
+		init_offsets();
+		ThreadLock::init_offsets();
+	}
+
+	INITIALIZED.store(true, Ordering::Release);
+	Throws::Ok(class)
+}
+
+/// Create a `jdk.internal.vm.ThreadSnapshot` from `snapshot`
+pub fn new(
+	thread: &'static JavaThread,
+	snapshot: &dump::ThreadSnapshot,
+) -> Throws<ClassInstanceRef> {
+	let class = initialize(thread)?;
+	let instance = ClassInstance::new(class)?;
+
+	let obj = snapshot
+		.thread
+		.obj()
+		.expect("live threads should have an object");
+	set_name(
+		instance,
+		classes::java::lang::Thread::name(obj.extract_class()),
+	);
+	set_threadStatus(instance, snapshot.status as jint);
+
+	let stack_trace = classes::java::lang::StackTraceElement::new_array(thread, &snapshot.frames)?;
+	set_stackTrace(instance, Reference::object_array(stack_trace));
+
+	let thread_lock_class = crate::globals::classes::jdk_internal_vm_ThreadSnapshot_ThreadLock();
+	let locks = ObjectArrayInstance::new(snapshot.locked_monitors.len() as s4, thread_lock_class)?;
+	for (index, (depth, monitor)) in snapshot.locked_monitors.iter().enumerate() {
+		let lock = ClassInstance::new(thread_lock_class)?;
+		ThreadLock::set_depth(lock, *depth as jint);
+		ThreadLock::set_typeOrdinal(lock, ThreadLock::LOCKED_ORDINAL);
+		ThreadLock::set_obj(lock, monitor.object());
+		unsafe {
+			locks.store_unchecked(index, Reference::class(lock));
+		}
+	}
+	set_locks(instance, Reference::object_array(locks));
+
+	let blocker = match (snapshot.contended_monitor, snapshot.waiting_monitor) {
+		(Some(monitor), _) => Some((BlockerLockType::WaitingToLock, monitor)),
+		(None, Some(monitor)) => Some((BlockerLockType::WaitingOn, monitor)),
+		(None, None) => None,
+	};
+	if let Some((ty, monitor)) = blocker {
+		set_blockerTypeOrdinal(instance, ty as jint);
+		set_blockerObject(instance, monitor.object());
+	}
+
+	Throws::Ok(instance)
+}
+
+pub fn set_name(instance: ClassInstanceRef, value: Reference) {
+	assert!(value.is_null() || value.is_instance_of(crate::globals::classes::java_lang_String()));
+	instance.put_field_value0(name_field_index(), Operand::Reference(value))
+}
+
+pub fn set_threadStatus(instance: ClassInstanceRef, value: jint) {
+	instance.put_field_value0(threadStatus_field_index(), Operand::Int(value))
+}
+
+pub fn set_stackTrace(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(stackTrace_field_index(), Operand::Reference(value))
+}
+
+pub fn set_locks(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(locks_field_index(), Operand::Reference(value))
+}
+
+pub fn set_blockerTypeOrdinal(instance: ClassInstanceRef, value: jint) {
+	instance.put_field_value0(blockerTypeOrdinal_field_index(), Operand::Int(value))
+}
+
+pub fn set_blockerObject(instance: ClassInstanceRef, value: Reference) {
+	instance.put_field_value0(blockerObject_field_index(), Operand::Reference(value))
+}
+
+crate::classes::field_module! {
+	@CLASS jdk_internal_vm_ThreadSnapshot;
+	@SUBCLASS ThreadLock;
+
+	@FIELDSTART
+	/// `jdk.internal.vm.ThreadSnapshot#name` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.String`
+	@FIELD name: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/String"),
+	/// `jdk.internal.vm.ThreadSnapshot#threadStatus` field offset
+	///
+	/// Expected field type: `jint`
+	@FIELD threadStatus: FieldType::Integer,
+	/// `jdk.internal.vm.ThreadSnapshot#stackTrace` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.StackTraceElement[]`
+	@FIELD stackTrace: FieldType::Array(val) if val.is_class(b"java/lang/StackTraceElement"),
+	/// `jdk.internal.vm.ThreadSnapshot#locks` field offset
+	///
+	/// Expected field type: `Reference` to `jdk.internal.vm.ThreadSnapshot$ThreadLock[]`
+	@FIELD locks: FieldType::Array(val) if val.is_class(b"jdk/internal/vm/ThreadSnapshot$ThreadLock"),
+	/// `jdk.internal.vm.ThreadSnapshot#blockerTypeOrdinal` field offset
+	///
+	/// Expected field type: `jint`
+	@FIELD blockerTypeOrdinal: FieldType::Integer,
+	/// `jdk.internal.vm.ThreadSnapshot#blockerObject` field offset
+	///
+	/// Expected field type: `Reference` to `java.lang.Object`
+	@FIELD blockerObject: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Object"),
+}
+
+pub mod ThreadLock {
+	use super::*;
+
+	/// `jdk.internal.vm.ThreadSnapshot$OwnedLockType#LOCKED` ordinal
+	pub const LOCKED_ORDINAL: jint = 0;
+
+	pub fn set_depth(instance: ClassInstanceRef, value: jint) {
+		instance.put_field_value0(depth_field_index(), Operand::Int(value))
+	}
+
+	pub fn set_typeOrdinal(instance: ClassInstanceRef, value: jint) {
+		instance.put_field_value0(typeOrdinal_field_index(), Operand::Int(value))
+	}
+
+	pub fn set_obj(instance: ClassInstanceRef, value: Reference) {
+		instance.put_field_value0(obj_field_index(), Operand::Reference(value))
+	}
+
+	crate::classes::field_module! {
+		@CLASS jdk_internal_vm_ThreadSnapshot_ThreadLock;
+
+		@FIELDSTART
+		/// `jdk.internal.vm.ThreadSnapshot$ThreadLock#depth` field offset
+		///
+		/// Expected field type: `jint`
+		@FIELD depth: FieldType::Integer,
+		/// `jdk.internal.vm.ThreadSnapshot$ThreadLock#typeOrdinal` field offset
+		///
+		/// Expected field type: `jint`
+		@FIELD typeOrdinal: FieldType::Integer,
+		/// `jdk.internal.vm.ThreadSnapshot$ThreadLock#obj` field offset
+		///
+		/// Expected field type: `Reference` to `java.lang.Object`
+		@FIELD obj: ty @ FieldType::Object(_) if ty.is_class(b"java/lang/Object"),
+	}
+}
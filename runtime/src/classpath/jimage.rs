@@ -1,18 +1,30 @@
 use crate::native::jdk::internal::util::SystemProps::Raw::SYSTEM_PROPERTIES;
 
 use common::int_types::u1;
-use jimage::JImage;
+use jimage::{JImage, JImageHeader};
 
 use std::cell::SyncUnsafeCell;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 static JIMAGE_FILE: SyncUnsafeCell<Option<JImage>> = SyncUnsafeCell::new(None);
+static JIMAGE_PATH: OnceLock<PathBuf> = OnceLock::new();
 
 pub fn initialized() -> bool {
 	unsafe { (*JIMAGE_FILE.get()).is_some() }
 }
 
+/// The path of the runtime image (`$JAVA_HOME/lib/modules`), if it was loaded
+pub fn path() -> Option<&'static Path> {
+	JIMAGE_PATH.get().map(PathBuf::as_path)
+}
+
+/// The header of the runtime image, if it was loaded
+pub fn header() -> Option<JImageHeader> {
+	unsafe { &*JIMAGE_FILE.get() }.as_ref().map(JImage::header)
+}
+
 pub fn lookup_vm_resource(module_name: &str, path: &str) -> Option<Box<[u1]>> {
 	if let Some(file) = unsafe { &*JIMAGE_FILE.get() }
 		&& let Some((location_offset, size)) = file.find_resource(module_name, path)
@@ -51,12 +63,14 @@ pub fn lookup_vm_options() -> Option<Box<[u1]>> {
 		return None;
 	}
 
-	let mut jimage_file = File::open(modules_path).unwrap();
+	let mut jimage_file = File::open(&modules_path).unwrap();
 	let jimage = JImage::read_from(&mut jimage_file).unwrap(); // TODO: Error handling
 
 	unsafe {
 		*JIMAGE_FILE.get() = Some(jimage);
 	}
 
+	let _ = JIMAGE_PATH.set(modules_path);
+
 	lookup_vm_resource("java.base", "jdk/internal/vm/options")
 }
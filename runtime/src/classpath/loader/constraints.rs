@@ -0,0 +1,374 @@
+//! Loading constraints (§5.3.4)
+//!
+//! When a class or interface is referenced across two loaders (through a field or method
+//! descriptor), both loaders must agree on which class a name refers to. Every such agreement is
+//! recorded as a constraint `N^L1 = N^L2`. Constraints are transitive, so they are kept as sets of
+//! loaders that must all agree, optionally pinned to the class that they have already agreed on.
+//!
+//! A constraint is violated when:
+//!
+//! * Recording it would join two loaders that have already loaded different classes named `N`
+//! * A loader in the set is recorded as the initiating loader of a class named `N`, when the set
+//!   has already settled on a different class
+
+use super::ClassLoader;
+use crate::logging::debug;
+use crate::objects::class::ClassPtr;
+use crate::symbols::Symbol;
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use classfile::{FieldType, MethodDescriptor};
+
+struct LoaderConstraint {
+	/// The loaders that must all agree on the class
+	loaders: Vec<&'static ClassLoader>,
+	/// The class that any of the loaders has already loaded, if any
+	class: Option<ClassPtr>,
+}
+
+impl LoaderConstraint {
+	fn contains(&self, loader: &'static ClassLoader) -> bool {
+		self.loaders.contains(&loader)
+	}
+}
+
+static CONSTRAINTS: LazyLock<Mutex<HashMap<Symbol, Vec<LoaderConstraint>>>> =
+	LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Impose the constraint `name^loader1 = name^loader2`
+///
+/// `name` is the name of a class or interface, *not* a descriptor.
+///
+/// Returns `false` if the constraint would be violated. In that case, nothing is recorded.
+fn add_constraint(
+	name: Symbol,
+	loader1: &'static ClassLoader,
+	loader2: &'static ClassLoader,
+) -> bool {
+	if loader1 == loader2 {
+		return true;
+	}
+
+	let class1 = loader1.lookup_class(name);
+	let class2 = loader2.lookup_class(name);
+
+	let mut constraints = CONSTRAINTS.lock().unwrap();
+	let constraints_for_name = constraints.entry(name).or_default();
+
+	let index1 = constraints_for_name
+		.iter()
+		.position(|constraint| constraint.contains(loader1));
+	let index2 = constraints_for_name
+		.iter()
+		.position(|constraint| constraint.contains(loader2));
+
+	// Every class that the two loaders have agreed on so far needs to be the same one
+	let mut class = None;
+	for candidate in [
+		class1,
+		class2,
+		index1.and_then(|i| constraints_for_name[i].class),
+		index2.and_then(|i| constraints_for_name[i].class),
+	]
+	.into_iter()
+	.flatten()
+	{
+		match class {
+			Some(class) if class != candidate => return false,
+			_ => class = Some(candidate),
+		}
+	}
+
+	match (index1, index2) {
+		(None, None) => constraints_for_name.push(LoaderConstraint {
+			loaders: vec![loader1, loader2],
+			class,
+		}),
+		(Some(index), None) => {
+			let constraint = &mut constraints_for_name[index];
+			constraint.loaders.push(loader2);
+			constraint.class = class;
+		},
+		(None, Some(index)) => {
+			let constraint = &mut constraints_for_name[index];
+			constraint.loaders.push(loader1);
+			constraint.class = class;
+		},
+		(Some(index1), Some(index2)) if index1 == index2 => {
+			constraints_for_name[index1].class = class;
+		},
+		(Some(index1), Some(index2)) => {
+			let merged = constraints_for_name.swap_remove(index1.max(index2));
+			let constraint = &mut constraints_for_name[index1.min(index2)];
+			constraint.loaders.extend(merged.loaders);
+			constraint.class = class;
+		},
+	}
+
+	debug!(
+		TARGETS: (Class, Loader, Constraints),
+		"adding new constraint for name: {}, loader[0]: {}, loader[1]: {}",
+		name,
+		loader1.name_and_id(),
+		loader2.name_and_id()
+	);
+
+	true
+}
+
+/// Check that recording `loader` as an initiating loader of `class` doesn't violate any constraints
+///
+/// If `loader` is constrained for the name of `class`, and the constraint has yet to settle on a
+/// class, it will be pinned to `class`.
+///
+/// Returns `false` if `loader` is constrained to a different class with the same name.
+pub(crate) fn check_or_update(loader: &'static ClassLoader, class: ClassPtr) -> bool {
+	let mut constraints = CONSTRAINTS.lock().unwrap();
+	let Some(constraints_for_name) = constraints.get_mut(&class.name()) else {
+		return true;
+	};
+
+	let Some(constraint) = constraints_for_name
+		.iter_mut()
+		.find(|constraint| constraint.contains(loader))
+	else {
+		return true;
+	};
+
+	match constraint.class {
+		Some(existing) => existing == class,
+		None => {
+			debug!(
+				TARGETS: (Class, Loader, Constraints),
+				"updating constraint for name {}, setting class object",
+				class.name()
+			);
+			constraint.class = Some(class);
+			true
+		},
+	}
+}
+
+/// Impose constraints between `loader1` and `loader2` for every class mentioned in a method descriptor
+///
+/// On a violation, the name of the offending class is returned.
+pub(crate) fn add_method_constraints(
+	descriptor: &MethodDescriptor,
+	loader1: &'static ClassLoader,
+	loader2: &'static ClassLoader,
+) -> Result<(), Symbol> {
+	if loader1 == loader2 {
+		return Ok(());
+	}
+
+	for ty in descriptor
+		.parameters
+		.iter()
+		.chain(std::iter::once(&descriptor.return_type))
+	{
+		add_field_constraint(ty, loader1, loader2)?;
+	}
+
+	Ok(())
+}
+
+/// Impose a constraint between `loader1` and `loader2` for the class mentioned by a field type, if any
+///
+/// On a violation, the name of the offending class is returned.
+pub(crate) fn add_field_constraint(
+	ty: &FieldType,
+	loader1: &'static ClassLoader,
+	loader2: &'static ClassLoader,
+) -> Result<(), Symbol> {
+	let Some(name) = constrained_name(ty) else {
+		return Ok(());
+	};
+
+	if add_constraint(name, loader1, loader2) {
+		Ok(())
+	} else {
+		Err(name)
+	}
+}
+
+/// The name of the class or interface mentioned by `ty`, if any
+///
+/// Constraints on array types are constraints on their element types, so this looks through them.
+fn constrained_name(ty: &FieldType) -> Option<Symbol> {
+	match ty {
+		FieldType::Object(name) => Some(Symbol::intern(&**name)),
+		FieldType::Array(component) => constrained_name(component),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::classpath::loader::ClassLoaderType;
+	use crate::modules::ModuleSet;
+	use crate::objects::reference::Reference;
+
+	use std::cell::SyncUnsafeCell;
+
+	/// A class loader with no backing `java.lang.ClassLoader`, which has loaded `classes`
+	fn loader(classes: &[(Symbol, ClassPtr)]) -> &'static ClassLoader {
+		// Only ever compared by address, never dereferenced
+		let obj = Box::leak(Box::new(0_u64));
+
+		Box::leak(Box::new(ClassLoader {
+			obj: unsafe { Reference::from_raw(std::ptr::from_mut(obj).cast()) },
+			name: None,
+			name_and_id: Symbol::intern("constraintsTests"),
+			inner: ClassLoaderType::Normal {
+				unnamed_module: SyncUnsafeCell::new(None),
+				classes: Mutex::new(classes.iter().copied().collect()),
+				hidden_classes: Mutex::new(Vec::new()),
+				java_base: SyncUnsafeCell::new(None),
+				mirrors_available: SyncUnsafeCell::new(true),
+				modules: ModuleSet::new(),
+				packages: SyncUnsafeCell::new(HashMap::new()),
+			},
+			sealed: Default::default(),
+		}))
+	}
+
+	/// A class that is only ever compared by address, never dereferenced
+	fn class() -> ClassPtr {
+		let class = Box::leak(Box::new(0_u64));
+		unsafe { ClassPtr::from_raw(std::ptr::from_mut(class).cast()) }
+	}
+
+	/// The loader sets constrained for `name`, and the classes they settled on
+	fn constraints_for(name: Symbol) -> Vec<(Vec<&'static ClassLoader>, Option<ClassPtr>)> {
+		CONSTRAINTS
+			.lock()
+			.unwrap()
+			.get(&name)
+			.map(|constraints| {
+				constraints
+					.iter()
+					.map(|constraint| (constraint.loaders.clone(), constraint.class))
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	fn same_loaders(a: &[&'static ClassLoader], b: &[&'static ClassLoader]) -> bool {
+		a.len() == b.len() && a.iter().all(|loader| b.contains(loader))
+	}
+
+	#[test]
+	fn constrained_name() {
+		let name = Symbol::intern("constraintsTests/Name");
+		let object = FieldType::Object(Box::from(&b"constraintsTests/Name"[..]));
+		let array = FieldType::Array(Box::new(FieldType::Array(Box::new(object.clone()))));
+
+		assert_eq!(super::constrained_name(&object), Some(name));
+		assert_eq!(super::constrained_name(&array), Some(name));
+		assert_eq!(super::constrained_name(&FieldType::Integer), None);
+		assert_eq!(
+			super::constrained_name(&FieldType::Array(Box::new(FieldType::Integer))),
+			None
+		);
+	}
+
+	#[test]
+	fn merge() {
+		let name = Symbol::intern("constraintsTests/Merge");
+		let [a, b, c, d, e] = std::array::from_fn(|_| loader(&[]));
+
+		// A loader can always agree with itself
+		assert!(add_constraint(name, a, a));
+		assert!(constraints_for(name).is_empty());
+
+		// Neither loader is constrained yet
+		assert!(add_constraint(name, a, b));
+		// Only the second loader is constrained
+		assert!(add_constraint(name, c, a));
+		// Only the first loader is constrained
+		assert!(add_constraint(name, b, d));
+		// Both loaders are already in the same constraint
+		assert!(add_constraint(name, d, c));
+
+		let constraints = constraints_for(name);
+		assert_eq!(constraints.len(), 1);
+		assert!(same_loaders(&constraints[0].0, &[a, b, c, d]));
+		assert!(constraints[0].1.is_none());
+
+		// Both loaders are in different constraints, which are merged
+		let f = loader(&[]);
+		assert!(add_constraint(name, e, f));
+		assert_eq!(constraints_for(name).len(), 2);
+		assert!(add_constraint(name, f, b));
+
+		let constraints = constraints_for(name);
+		assert_eq!(constraints.len(), 1);
+		assert!(same_loaders(&constraints[0].0, &[a, b, c, d, e, f]));
+
+		// A loader that has already loaded the class settles the constraint for all of them
+		let class = class();
+		assert!(add_constraint(name, loader(&[(name, class)]), e));
+		let constraints = constraints_for(name);
+		assert_eq!(constraints.len(), 1);
+		assert_eq!(constraints[0].0.len(), 7);
+		assert!(constraints[0].1 == Some(class));
+
+		assert!(!add_constraint(name, loader(&[(name, self::class())]), a));
+	}
+
+	#[test]
+	fn violations() {
+		let name = Symbol::intern("constraintsTests/Violations");
+		let class1 = class();
+		let class2 = class();
+
+		let loaded1 = loader(&[(name, class1)]);
+		let also_loaded1 = loader(&[(name, class1)]);
+		let loaded2 = loader(&[(name, class2)]);
+
+		// Two loaders that already disagree
+		assert!(!add_constraint(name, loaded1, loaded2));
+		assert!(constraints_for(name).is_empty());
+
+		// Two loaders that already agree, the constraint settles on their class
+		assert!(add_constraint(name, loaded1, also_loaded1));
+		assert!(constraints_for(name)[0].1 == Some(class1));
+
+		// Joining a constraint that settled on a different class
+		assert!(!add_constraint(name, loaded2, also_loaded1));
+
+		// Merging two constraints that settled on different classes
+		let unloaded = loader(&[]);
+		assert!(add_constraint(name, loaded2, unloaded));
+		assert_eq!(constraints_for(name).len(), 2);
+		assert!(!add_constraint(name, unloaded, loaded1));
+		assert_eq!(constraints_for(name).len(), 2);
+		assert!(constraints_for(name).iter().any(|(loaders, class)| {
+			same_loaders(loaders, &[loaded2, unloaded]) && *class == Some(class2)
+		}));
+	}
+
+	#[test]
+	fn method_constraints() {
+		let name = Symbol::intern("constraintsTests/Method");
+		let descriptor =
+			MethodDescriptor::parse(&mut &b"(I[LconstraintsTests/Method;)V"[..]).unwrap();
+
+		let loaded1 = loader(&[(name, class())]);
+		let loaded2 = loader(&[(name, class())]);
+		let unloaded = loader(&[]);
+
+		assert_eq!(
+			add_method_constraints(&descriptor, loaded1, loaded2),
+			Err(name)
+		);
+		assert_eq!(
+			add_method_constraints(&descriptor, loaded1, unloaded),
+			Ok(())
+		);
+		assert_eq!(constraints_for(name).len(), 1);
+	}
+}
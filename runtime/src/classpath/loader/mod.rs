@@ -1,5 +1,11 @@
+pub(crate) mod constraints;
+mod placeholders;
 mod set;
 pub use set::*;
+#[cfg(test)]
+mod tests;
+
+use placeholders::Placeholder;
 
 use crate::modules::{Module, ModuleLockGuard, ModuleSet, Package};
 use crate::native::java::lang::String::StringInterner;
@@ -7,9 +13,10 @@ use crate::objects::class::{Class, ClassPtr};
 use crate::objects::reference::Reference;
 use crate::symbols::{Symbol, sym};
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{Throws, throw};
+use crate::thread::exceptions::{Exception, ExceptionKind, Throws, throw};
 use crate::{classes, java_call};
 
+use std::borrow::Cow;
 use std::cell::SyncUnsafeCell;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -19,13 +26,14 @@ use std::ops::RangeInclusive;
 use std::sync::atomic::AtomicBool;
 use std::sync::{LazyLock, Mutex};
 
+use classfile::accessflags::MethodAccessFlags;
 use classfile::constant_pool::types::raw as raw_types;
 use classfile::{ClassFile, FieldType};
 use common::int_types::u1;
 use instructions::Operand;
 
 const SUPPORTED_MAJOR_LOWER_BOUND: u1 = 45;
-const SUPPORTED_MAJOR_UPPER_BOUND: u1 = 71;
+pub(crate) const SUPPORTED_MAJOR_UPPER_BOUND: u1 = 71;
 const SUPPORTED_MAJOR_VERSION_RANGE: RangeInclusive<u1> =
 	SUPPORTED_MAJOR_LOWER_BOUND..=SUPPORTED_MAJOR_UPPER_BOUND;
 
@@ -44,6 +52,9 @@ enum ClassLoaderType {
 
 		classes: Mutex<HashMap<Symbol, ClassPtr>>,
 
+		// Hidden classes are not findable by name, so they are kept separate from `classes`
+		hidden_classes: Mutex<Vec<ClassPtr>>,
+
 		// TODO: Is there a better way to do this?
 		// Keep the java.base module separate from the other modules for bootstrapping. This field is only
 		// valid for the bootstrap loader.
@@ -61,6 +72,21 @@ enum ClassLoaderType {
 	Hidden,
 }
 
+/// How a class created by [`ClassLoader::derive_class()`] is linked to its defining loader
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LoaderLink {
+	/// A normal class, recorded in the loader's dictionary
+	Normal,
+	/// A hidden class, which is never recorded in the loader's dictionary
+	Hidden,
+}
+
+impl LoaderLink {
+	pub fn is_hidden(self) -> bool {
+		self == LoaderLink::Hidden
+	}
+}
+
 pub struct ClassLoader {
 	obj: Reference,
 
@@ -116,6 +142,7 @@ impl ClassLoader {
 			inner: ClassLoaderType::Normal {
 				unnamed_module: SyncUnsafeCell::new(Some(Box::leak(Box::new(unnamed_module)))),
 				classes: Mutex::new(HashMap::new()),
+				hidden_classes: Mutex::new(Vec::new()),
 
 				// Never initialized for non-bootstrap loaders
 				java_base: SyncUnsafeCell::new(None),
@@ -227,6 +254,7 @@ impl ClassLoader {
 				inner: ClassLoaderType::Normal {
 					unnamed_module: SyncUnsafeCell::new(None),
 					classes: Mutex::new(HashMap::new()),
+					hidden_classes: Mutex::new(Vec::new()),
 
 					java_base: SyncUnsafeCell::new(None),
 					mirrors_available: SyncUnsafeCell::new(false),
@@ -295,12 +323,26 @@ impl ClassLoader {
 
 impl ClassLoader {
 	pub(crate) fn lookup_class(&self, name: Symbol) -> Option<ClassPtr> {
-		let ClassLoaderType::Normal { classes, .. } = &self.inner else {
+		let ClassLoaderType::Normal {
+			classes,
+			hidden_classes,
+			..
+		} = &self.inner
+		else {
 			unreachable!("should never be called on hidden classloaders")
 		};
 
-		let loaded_classes = classes.lock().unwrap();
-		loaded_classes.get(&name).copied()
+		if let Some(class) = classes.lock().unwrap().get(&name).copied() {
+			return Some(class);
+		}
+
+		// Hidden classes still need to be found by their mangled names, for array class creation
+		hidden_classes
+			.lock()
+			.unwrap()
+			.iter()
+			.find(|class| class.name() == name)
+			.copied()
 	}
 
 	pub fn unnamed_module(&self) -> &'static Module {
@@ -335,6 +377,20 @@ impl ClassLoader {
 		matches!(self.inner, ClassLoaderType::Hidden)
 	}
 
+	/// Whether this is the `jdk.internal.loader.ClassLoaders$PlatformClassLoader`
+	pub fn is_platform(&self) -> bool {
+		!self.is_bootstrap()
+			&& self.obj.extract_target_class().name()
+				== sym!(jdk_internal_loader_ClassLoaders_PlatformClassLoader)
+	}
+
+	/// Whether this is the `jdk.internal.loader.ClassLoaders$AppClassLoader`
+	pub fn is_app(&self) -> bool {
+		!self.is_bootstrap()
+			&& self.obj.extract_target_class().name()
+				== sym!(jdk_internal_loader_ClassLoaders_AppClassLoader)
+	}
+
 	pub fn obj(&self) -> Reference {
 		self.obj
 	}
@@ -352,6 +408,17 @@ impl ClassLoader {
 		self.load_user_defined(name)
 	}
 
+	/// Load a class on behalf of class resolution (§5.4.3.1)
+	///
+	/// This is the same as [`ClassLoader::load()`], except that failing to find the class results
+	/// in a `NoClassDefFoundError`, with the `ClassNotFoundException` as its cause.
+	pub fn resolve_or_fail(&'static self, name: Symbol) -> Throws<ClassPtr> {
+		match self.load(name) {
+			Throws::Ok(class) => Throws::Ok(class),
+			Throws::Exception(e) => Throws::Exception(wrap_class_not_found(name, e)),
+		}
+	}
+
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.3.1
 	fn load_bootstrap(&'static self, name: Symbol) -> Throws<ClassPtr> {
 		// First, the Java Virtual Machine determines whether the bootstrap class loader has
@@ -369,12 +436,14 @@ impl ClassLoader {
 		// Otherwise, the Java Virtual Machine passes the argument N to an invocation of a method on
 		// the bootstrap class loader [...] and then [...] create C, via the algorithm of §5.3.5.
 
-		let is_hidden = false; // hidden class derivation is only handled by direct calls to `derive_class`.
-		let classref = self.derive_class(name, None, is_hidden)?;
+		// Hidden class derivation is only handled by direct calls to `derive_class`.
+		let classref = self.derive_class(name, None, LoaderLink::Normal)?;
 
-		// TODO:
 		// If no purported representation of C is found, the bootstrap class loader throws a ClassNotFoundException.
 		// The process of loading and creating C then fails with a NoClassDefFoundError whose cause is the ClassNotFoundException.
+		//
+		// NOTE: The wrapping is left to `ClassLoader::resolve_or_fail()`, since callers such as `Class#forName`
+		//       expect the bare ClassNotFoundException.
 
 		// If a purported representation of C is found, but deriving C from the purported representation fails,
 		// then the process of loading and creating C fails for the same reason.
@@ -408,7 +477,7 @@ impl ClassLoader {
 		}
 
 		let external_name = name_str.replace('/', ".");
-		let external_name_string = StringInterner::intern(external_name);
+		let external_name_string = StringInterner::intern(external_name)?;
 
 		let load_class_method = self
 			.obj
@@ -442,27 +511,50 @@ impl ClassLoader {
 		&'static self,
 		name: Symbol,
 		classfile_bytes: Option<&[u1]>,
-		is_hidden: bool,
+		link: LoaderLink,
 	) -> Throws<ClassPtr> {
 		let name_str = name.as_str();
 		if name_str.starts_with('[') {
-			assert!(!is_hidden);
+			assert_eq!(link, LoaderLink::Normal);
 			return self.create_array_class(name);
 		}
 
 		match classfile_bytes {
 			Some(classfile_bytes) => {
-				self.derive_class_inner(name, name_str, classfile_bytes, is_hidden)
+				self.derive_class_inner(Some(name), classfile_bytes, link, Reference::null())
 			},
 			None => {
 				let classfile_bytes = self.search_for_class(name)?;
-				self.derive_class_inner(name, name_str, &classfile_bytes, is_hidden)
+				self.derive_class_inner(Some(name), &classfile_bytes, link, Reference::null())
 			},
 		}
 	}
 
+	/// Define a class from a buffer, on behalf of `ClassLoader#defineClass` and friends
+	///
+	/// If `name` is `None`, the name is taken from the class file itself. Otherwise, it must match
+	/// the name in the class file.
+	///
+	/// The `protection_domain` (a `java.security.ProtectionDomain`, or null) is stored in the
+	/// mirror before the class is published.
+	pub fn define_class(
+		&'static self,
+		name: Option<Symbol>,
+		classfile_bytes: &[u1],
+		protection_domain: Reference,
+		link: LoaderLink,
+	) -> Throws<ClassPtr> {
+		if let Some(name) = name
+			&& name.as_str().starts_with('[')
+		{
+			throw!(@DEFER NoClassDefFoundError, "{}", name.as_str().replace('/', "."));
+		}
+
+		self.derive_class_inner(name, classfile_bytes, link, protection_domain)
+	}
+
 	/// Attempt to find the class on disk.
-	fn search_for_class(&self, name: Symbol) -> Throws<Vec<u1>> {
+	fn search_for_class(&self, name: Symbol) -> Throws<Cow<'static, [u1]>> {
 		assert!(
 			self.is_bootstrap(),
 			"search is restricted to the bootloader"
@@ -476,14 +568,19 @@ impl ClassLoader {
 
 	fn derive_class_inner(
 		&'static self,
-		name: Symbol,
-		name_str: &str,
+		name: Option<Symbol>,
 		classfile_bytes: &[u1],
-		is_hidden: bool,
+		link: LoaderLink,
+		protection_domain: Reference,
 	) -> Throws<ClassPtr> {
 		// 1. First, the Java Virtual Machine determines whether L has already been recorded
 		//    as an initiating loader of a class or interface denoted by N. If so, this derivation
 		//    attempt is invalid and derivation throws a LinkageError.
+		//
+		//    (Deferred until after parsing if N was not provided, since it comes from the class file)
+		if let Some(name) = name {
+			self.check_not_defined(name, link)?;
+		}
 
 		// 2. Otherwise, the Java Virtual Machine attempts to parse the purported representation.
 		//    The purported representation may not in fact be a valid representation of C, so
@@ -498,6 +595,15 @@ impl ClassLoader {
 			},
 		}
 
+		let Ok(specified_class_name) = classfile
+			.constant_pool
+			.get::<raw_types::RawClassName>(classfile.this_class)
+		else {
+			throw!(@DEFER ClassFormatError, "Invalid this class index {} in constant pool in class file", classfile.this_class)
+		};
+		let specified_class_name = Symbol::intern(&*specified_class_name.name);
+		let name_str = name.unwrap_or(specified_class_name).as_str();
+
 		//  2.2. Otherwise, if the purported representation is not of a supported major or
 		//       minor version (§4.1), derivation throws an UnsupportedClassVersionError.
 		if !SUPPORTED_MAJOR_VERSION_RANGE.contains(&(classfile.major_version as u1)) {
@@ -513,15 +619,39 @@ impl ClassLoader {
 		//       interface named N, derivation throws a NoClassDefFoundError. This occurs when the
 		//       purported representation has either a this_class item which specifies a name other
 		//       than N, or an access_flags item which has the ACC_MODULE flag set.
-		let Ok(specified_class_name) = classfile
-			.constant_pool
-			.get::<raw_types::RawClassName>(classfile.this_class)
-		else {
-			throw!(@DEFER ClassFormatError, "Invalid this class index {} in constant pool in class file", classfile.this_class)
-		};
-		if name_str.as_bytes() != &*specified_class_name.name || classfile.access_flags.is_module()
+		if classfile.access_flags.is_module() {
+			throw!(@DEFER NoClassDefFoundError, "{name_str} is not a class because access_flag ACC_MODULE is set");
+		}
+
+		// The format checks (§4.8) are deferred until here, so that the errors above take precedence.
+		//
+		// Like Hotspot (see `-XX:-BytecodeVerificationLocal`), classes from the bootstrap loader are
+		// trusted. They come from the runtime image, so checking them would only slow down startup.
+		if !self.is_bootstrap()
+			&& let Err(e) = classfile.check_format()
 		{
-			throw!(@DEFER NoClassDefFoundError);
+			throw!(@DEFER ClassFormatError, "{e} in class file {name_str}");
+		}
+
+		if name.is_some_and(|name| name != specified_class_name) {
+			throw!(@DEFER NoClassDefFoundError,
+				"{name_str} (wrong name: {})",
+				specified_class_name.as_str()
+			);
+		}
+
+		if name.is_none() {
+			self.check_not_defined(specified_class_name, link)?;
+		}
+
+		let name = specified_class_name;
+
+		// Only the bootstrap and platform loaders can define classes in `java/` packages
+		if name_str.starts_with("java/") && !self.is_bootstrap() && !self.is_platform() {
+			let package = name_str
+				.rsplit_once('/')
+				.map_or(name_str, |(package, _)| package);
+			throw!(@DEFER SecurityException, "Prohibited package name: {}", package.replace('/', "."));
 		}
 
 		//  3. If C has a direct superclass, the symbolic reference from C to its direct
@@ -533,8 +663,15 @@ impl ClassLoader {
 			throw!(@DEFER ClassFormatError, "Invalid super class index in constant pool in class file")
 		};
 
+		// Hold a placeholder for C while its supertypes are resolved. If it is encountered again on
+		// this thread, then C is its own superclass or superinterface.
+		let Some(placeholder) = Placeholder::enter(name, self) else {
+			throw!(@DEFER ClassCircularityError, "{}", name_str.replace('/', "."));
+		};
+
 		if let Some(super_class_name) = super_class_name_opt {
-			super_class = Some(self.resolve_super_class(Symbol::intern(&*super_class_name))?);
+			super_class =
+				Some(self.resolve_super_class(name_str, Symbol::intern(&*super_class_name))?);
 		}
 
 		// 4. If C has any direct superinterfaces, the symbolic references from C to its direct
@@ -545,7 +682,7 @@ impl ClassLoader {
                 match interface_name {
                     Ok(interface_name) => {
                         let sym = Symbol::intern(&*interface_name);
-                        self.resolve_interface(sym)
+                        self.resolve_interface(name_str, sym)
                     }
                     Err(_) => {
                         throw!(@DEFER ClassFormatError, "Interface name has bad constant pool index in class file {name}");
@@ -554,56 +691,137 @@ impl ClassLoader {
 			})
 			.collect::<Throws<Vec<_>>>()?;
 
+		drop(placeholder);
+
 		// If no exception is thrown in steps 1-4, then derivation of the class or interface C succeeds.
 		// The Java Virtual Machine marks C to have L as its defining loader, records that L is an initiating
 		// loader of C (§5.3.4), and creates C in the method area (§2.5.4).
 
-		let class =
-			unsafe { Class::new(classfile, super_class, super_interfaces, self, is_hidden)? };
-		init_mirror(class);
+		let class = unsafe {
+			Class::new(
+				classfile,
+				super_class,
+				super_interfaces,
+				self,
+				link.is_hidden(),
+			)?
+		};
 
-		self.add_class(class)?;
+		let created = match check_supertypes(class) {
+			Throws::Ok(()) => init_mirror(class),
+			exception => exception,
+		};
+
+		if let Throws::Exception(e) = created {
+			// SAFETY: The class was never published, nothing else can refer to it
+			unsafe {
+				class.free();
+			}
+
+			return Throws::Exception(e);
+		}
+
+		// Only classes defined through `ClassLoader#defineClass` and friends have a protection domain,
+		// which can only happen once `java.lang.Class` is loaded.
+		if !protection_domain.is_null() {
+			classes::java::lang::Class::set_protection_domain(class.mirror(), protection_domain);
+		}
+
+		match link {
+			LoaderLink::Normal => {
+				// Another thread may have defined the same class since step 1
+				if let Throws::Exception(e) = self.add_class(class) {
+					if self.lookup_class(class.name()) != Some(class) {
+						// SAFETY: The class was never published, nothing else can refer to it
+						unsafe {
+							class.free();
+						}
+					}
+
+					return Throws::Exception(e);
+				}
+			},
+			LoaderLink::Hidden => self.add_hidden_class(class),
+		}
 
-		// Finally, prepare the class (§5.4.2)
+		// Finally, link the class (§5.4), which includes preparation (§5.4.2)
 		// "Preparation may occur at any time following creation but must be completed prior to initialization."
-		class.prepare()?;
+		class.link()?;
 
 		Throws::Ok(class)
 	}
 
-	fn resolve_super_class(&'static self, super_class_name: Symbol) -> Throws<ClassPtr> {
+	/// Step 1 of class derivation (§5.3.5), a loader can only ever define a class with a given name once
+	///
+	/// Hidden classes are never recorded in the loader, and are exempt.
+	fn check_not_defined(&self, name: Symbol, link: LoaderLink) -> Throws<()> {
+		if link.is_hidden() {
+			return Throws::Ok(());
+		}
+
+		if let Some(existing) = self.lookup_class(name) {
+			throw!(@DEFER LinkageError,
+				"loader {} attempted duplicate {} definition for {}. ({})",
+				self.name_and_id,
+				existing.external_kind(),
+				existing.external_name(),
+				existing.in_module_of_loader(false, true)
+			);
+		}
+
+		Throws::Ok(())
+	}
+
+	fn resolve_super_class(
+		&'static self,
+		class_name: &str,
+		super_class_name: Symbol,
+	) -> Throws<ClassPtr> {
 		// Any exception that can be thrown as a result of failure of class or interface resolution
 		// can be thrown as a result of derivation. In addition, derivation must detect the following problems:
 
-		// TODO:
 		//     If any of the superclasses of C is C itself, derivation throws a ClassCircularityError.
+		//
+		//     (Handled by the placeholder held in `derive_class_inner()`)
+		let super_class = self.resolve_or_fail(super_class_name)?;
 
-		// TODO:
 		//     Otherwise, if the class or interface named as the direct superclass of C is in fact an interface
 		//     or a final class, derivation throws an IncompatibleClassChangeError.
+		if super_class.is_interface() {
+			throw!(@DEFER IncompatibleClassChangeError,
+				"class {} has interface {} as super class",
+				class_name.replace('/', "."),
+				super_class.external_name()
+			);
+		}
+
+		if super_class.access_flags().is_final() {
+			throw!(@DEFER IncompatibleClassChangeError,
+				"class {} cannot inherit from final class {}",
+				class_name.replace('/', "."),
+				super_class.external_name()
+			);
+		}
 
-		// TODO:
 		//     Otherwise, if the class named as the direct superclass of C has a PermittedSubclasses attribute (§4.7.31)
 		//     and any of the following is true, derivation throws an IncompatibleClassChangeError:
-
-		// TODO:
+		//
 		//         The superclass is in a different run-time module than C (§5.3.6).
-
-		// TODO:
+		//
 		//         C does not have its ACC_PUBLIC flag set (§4.1) and the superclass is in a different run-time package than C (§5.3).
-
-		// TODO:
+		//
 		//         No entry in the classes array of the superclass's PermittedSubclasses attribute refers to a class or interface with the name N.
-
-		// TODO:
+		//
 		//     Otherwise, if C is a class and some instance method declared in C can override (§5.4.5)
 		//     a final instance method declared in a superclass of C, derivation throws an IncompatibleClassChangeError.
+		//
+		//     (Both require C itself, see `check_supertypes()`)
 
-		self.load(super_class_name)
+		Throws::Ok(super_class)
 	}
 
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4.3.1
-	fn resolve_interface(&'static self, name: Symbol) -> Throws<ClassPtr> {
+	fn resolve_interface(&'static self, class_name: &str, name: Symbol) -> Throws<ClassPtr> {
 		// To resolve an unresolved symbolic reference from D to a class or interface C denoted by N, the following steps are performed:
 
 		// 1. The defining loader of D is used to load and thereby create a class or interface denoted by N.
@@ -611,19 +829,31 @@ impl ClassLoader {
 		//
 		//     Any exception that can be thrown as a result of failure to load and thereby create C can
 		//     thus be thrown as a result of failure of class and interface resolution.
-		let interface = self.load(name)?;
+		let interface = self.resolve_or_fail(name)?;
 
 		// 2. If C is an array class and its element type is a reference type, then a symbolic reference
 		//    to the class or interface representing the element type is resolved by invoking the algorithm in §5.4.3.1 recursively.
 		if interface.is_array() {
 			let component = &interface.unwrap_array_instance().component;
 			if let FieldType::Object(name) = component {
-				return self.resolve_interface(Symbol::intern(name));
+				return self.resolve_interface(class_name, Symbol::intern(name));
 			}
 		}
 
-		// TODO
 		// 3. Finally, access control is applied for the access from D to C (§5.4.4).
+		//
+		// (This requires D itself, see `check_supertypes()`)
+
+		// NON-SPEC: Derivation additionally requires that the direct superinterfaces of C are in
+		//           fact interfaces.
+		if !interface.is_interface() {
+			throw!(@DEFER IncompatibleClassChangeError,
+				"class {} can not implement {}, because it is not an interface ({})",
+				class_name.replace('/', "."),
+				interface.external_name(),
+				interface.in_module_of_loader(false, false)
+			);
+		}
 
 		Throws::Ok(interface)
 	}
@@ -688,7 +918,15 @@ impl ClassLoader {
 			Some(class) => class,
 			None => {
 				let array_class = unsafe { Class::new_array(descriptor, component_type, self)? };
-				init_mirror(array_class);
+				if let Throws::Exception(e) = init_mirror(array_class) {
+					// SAFETY: The class was never published, nothing else can refer to it
+					unsafe {
+						array_class.free();
+					}
+
+					return Throws::Exception(e);
+				}
+
 				array_class
 			},
 		};
@@ -697,14 +935,15 @@ impl ClassLoader {
 
 		self.add_class(array_class)?;
 
-		// TODO:
 		//     If the component type is a reference type, the accessibility of the array class is determined by the accessibility of its component type (§5.4.4).
 		//     Otherwise, the array class is accessible to all classes and interfaces.
+		//
+		// (Handled by `Class::is_accessible_to()`)
 
 		Throws::Ok(array_class)
 	}
 
-	fn add_class(&self, class: ClassPtr) -> Throws<()> {
+	fn add_class(&'static self, class: ClassPtr) -> Throws<()> {
 		let ClassLoaderType::Normal { classes, .. } = &self.inner else {
 			unreachable!("should never be called on hidden classloaders")
 		};
@@ -720,6 +959,18 @@ impl ClassLoader {
 			throw!(@DEFER LinkageError, "loader {} attempted duplicate {} definition for {}. ({})", self.name_and_id, class.external_kind(), class.external_name(), class.in_module_of_loader(false, true));
 		}
 
+		// Recording L as an initiating loader of C must not violate any loading constraints (§5.3.4)
+		if !constraints::check_or_update(self, class) {
+			throw!(@DEFER LinkageError,
+				"loader constraint violation: loader {} wants to load {} {}. A different {} with the same name was previously loaded by another loader. ({})",
+				self.name_and_id,
+				class.external_kind(),
+				class.external_name(),
+				class.external_kind(),
+				class.in_module_of_loader(false, true)
+			);
+		}
+
 		entry.insert_entry(class);
 
 		if self.is_bootstrap() {
@@ -736,8 +987,16 @@ impl ClassLoader {
 		Throws::Ok(())
 	}
 
+	fn add_hidden_class(&self, class: ClassPtr) {
+		let ClassLoaderType::Normal { hidden_classes, .. } = &self.inner else {
+			unreachable!("should never be called on hidden classloaders")
+		};
+
+		hidden_classes.lock().unwrap().push(class);
+	}
+
 	/// Recreate mirrors for all loaded classes
-	pub fn fixup_mirrors() {
+	pub fn fixup_mirrors() -> Throws<()> {
 		let bootstrap_loader = ClassLoader::bootstrap();
 		let ClassLoaderType::Normal {
 			classes,
@@ -751,7 +1010,7 @@ impl ClassLoader {
 		for class in classes.lock().unwrap().values() {
 			// SAFETY: The only condition of `set_mirror` is that the class isn't in use yet.
 			unsafe {
-				class.set_mirror(None);
+				class.set_mirror(None)?;
 			}
 		}
 
@@ -760,6 +1019,8 @@ impl ClassLoader {
 		unsafe {
 			*mirrors_available.get() = true;
 		}
+
+		Throws::Ok(())
 	}
 
 	/// Sets all currently loaded classes to be members of `java.base`
@@ -775,16 +1036,179 @@ impl ClassLoader {
 	}
 }
 
-fn init_mirror(class: ClassPtr) {
+impl ClassLoader {
+	/// Every class defined by this loader, including its hidden classes
+	///
+	/// Classes that this loader merely initiated are owned by other loaders, and are left out.
+	pub fn defined_classes(&self) -> Vec<ClassPtr> {
+		let ClassLoaderType::Normal {
+			classes,
+			hidden_classes,
+			..
+		} = &self.inner
+		else {
+			// Hidden loaders never define any classes
+			return Vec::new();
+		};
+
+		let mut defined = classes
+			.lock()
+			.unwrap()
+			.values()
+			.copied()
+			.filter(|class| std::ptr::eq(class.loader(), self))
+			.collect::<Vec<_>>();
+		defined.extend(hidden_classes.lock().unwrap().iter().copied());
+
+		defined
+	}
+}
+
+/// The derivation checks (§5.3.5) that can only be made once C has been created
+fn check_supertypes(class: ClassPtr) -> Throws<()> {
+	if let Some(super_class) = class.super_class {
+		if !super_class.is_accessible_to(class) {
+			throw!(@DEFER IllegalAccessError,
+				"class {} cannot access its superclass {} ({}; {})",
+				class.external_name(),
+				super_class.external_name(),
+				class.in_module_of_loader(false, false),
+				super_class.in_module_of_loader(false, false)
+			);
+		}
+
+		if !is_permitted_subclass(class, super_class) {
+			throw!(@DEFER IncompatibleClassChangeError,
+				"class {} cannot inherit from sealed class {}",
+				class.external_name(),
+				super_class.external_name()
+			);
+		}
+	}
+
+	for interface in class.interfaces() {
+		if !interface.is_accessible_to(class) {
+			throw!(@DEFER IllegalAccessError,
+				"class {} cannot access its superinterface {} ({}; {})",
+				class.external_name(),
+				interface.external_name(),
+				class.in_module_of_loader(false, false),
+				interface.in_module_of_loader(false, false)
+			);
+		}
+
+		if !is_permitted_subclass(class, *interface) {
+			throw!(@DEFER IncompatibleClassChangeError,
+				"class {} cannot {} sealed interface {}",
+				class.external_name(),
+				if class.is_interface() { "extend" } else { "implement" },
+				interface.external_name()
+			);
+		}
+	}
+
+	if class.is_interface() {
+		return Throws::Ok(());
+	}
+
+	for method in class.vtable().iter_local() {
+		if method.is_static() || method.is_private() || method.is_constructor() {
+			continue;
+		}
+
+		for super_class in class.parent_iter() {
+			let Some(super_method) = super_class.vtable().find_local(
+				method.name,
+				method.descriptor_sym(),
+				MethodAccessFlags::NONE,
+			) else {
+				continue;
+			};
+
+			if super_method.is_final()
+				&& !super_method.is_static()
+				&& !super_method.is_private()
+				&& method.can_override(super_method)
+			{
+				throw!(@DEFER IncompatibleClassChangeError,
+					"class {} overrides final method {}.{}{}",
+					class.external_name(),
+					super_class.external_name(),
+					method.name,
+					method.descriptor_sym()
+				);
+			}
+		}
+	}
+
+	Throws::Ok(())
+}
+
+/// Whether `class` may directly extend or implement `super_type`, as far as its `PermittedSubclasses`
+/// attribute is concerned
+fn is_permitted_subclass(class: ClassPtr, super_type: ClassPtr) -> bool {
+	let Some(permitted_subclasses) = super_type.unwrap_class_instance().permitted_subclasses()
+	else {
+		return true;
+	};
+
+	if !std::ptr::eq(class.module(), super_type.module()) {
+		return false;
+	}
+
+	if !class.access_flags().is_public() && !class.shares_package_with(super_type) {
+		return false;
+	}
+
+	permitted_subclasses.contains(&class.name())
+}
+
+/// Replace a `ClassNotFoundException` with a `NoClassDefFoundError` for `name`, with the original
+/// exception as its cause
+///
+/// Any other exception is returned untouched.
+fn wrap_class_not_found(name: Symbol, exception: Exception) -> Exception {
+	let thread = JavaThread::current();
+
+	let is_class_not_found = match exception.kind() {
+		ExceptionKind::ClassNotFoundException => true,
+		ExceptionKind::PendingException => thread.pending_exception().is_some_and(|pending| {
+			matches!(
+				ExceptionKind::ClassNotFoundException.class(),
+				Throws::Ok(class) if pending.is_instance_of(class)
+			)
+		}),
+		_ => false,
+	};
+
+	if !is_class_not_found {
+		return exception;
+	}
+
+	exception.throw(thread);
+	let cause = thread
+		.take_pending_exception()
+		.expect("exception was just thrown");
+
+	throw!(@CONSTRUCT NoClassDefFoundError, "{name}").throw(thread);
+	let Some(error) = thread.take_pending_exception() else {
+		unreachable!("exception was just thrown");
+	};
+
+	classes::java::lang::Throwable::set_cause(error.extract_class(), cause);
+	thread.set_pending_exception(error);
+
+	Exception::new(ExceptionKind::PendingException)
+}
+
+fn init_mirror(class: ClassPtr) -> Throws<()> {
 	// Set the mirror if `java.lang.Class` is loaded
 	let class_loaded = crate::globals::classes::java_lang_Class_opt().is_some();
 	if !class_loaded {
 		// We cannot do anything to this class until a mirror is available.
-		return;
+		return Throws::Ok(());
 	}
 
 	// SAFETY: The only condition of `set_mirror` is that the class isn't in use yet.
-	unsafe {
-		class.set_mirror(None);
-	}
+	unsafe { class.set_mirror(None) }
 }
@@ -0,0 +1,48 @@
+//! Placeholders for classes in the middle of being derived
+//!
+//! While a class resolves its direct superclass and superinterfaces, a placeholder is held for its
+//! name in its defining loader. If the same thread runs into that placeholder again before it is
+//! released, the class is (transitively) its own superclass or superinterface.
+
+use super::ClassLoader;
+use crate::symbols::Symbol;
+
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
+use std::thread::ThreadId;
+
+type PlaceholderKey = (Symbol, usize, ThreadId);
+
+static PLACEHOLDERS: LazyLock<Mutex<HashSet<PlaceholderKey>>> =
+	LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// A placeholder for a class, released when dropped
+pub(super) struct Placeholder {
+	key: PlaceholderKey,
+}
+
+impl Placeholder {
+	/// Place a placeholder for `name` in `loader`, on the current thread
+	///
+	/// Returns `None` if the current thread already holds a placeholder for `name` in `loader`,
+	/// meaning a class circularity was found.
+	pub(super) fn enter(name: Symbol, loader: &ClassLoader) -> Option<Self> {
+		let key = (
+			name,
+			std::ptr::from_ref(loader) as usize,
+			std::thread::current().id(),
+		);
+
+		if !PLACEHOLDERS.lock().unwrap().insert(key) {
+			return None;
+		}
+
+		Some(Self { key })
+	}
+}
+
+impl Drop for Placeholder {
+	fn drop(&mut self) {
+		PLACEHOLDERS.lock().unwrap().remove(&self.key);
+	}
+}
@@ -1,5 +1,6 @@
 use super::ClassLoader;
 use crate::classes;
+use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
 
 use std::cell::SyncUnsafeCell;
@@ -81,4 +82,15 @@ impl ClassLoaderSet {
 			None => Self::add(loader, is_hidden),
 		}
 	}
+
+	/// Every class defined by a live class loader, including the bootstrap loader
+	pub fn loaded_classes() -> Vec<ClassPtr> {
+		let _guard = CLASS_LOADER_SET.write_mutex.lock().unwrap();
+		let list = unsafe { &*CLASS_LOADER_SET.list.get() };
+
+		list.iter()
+			.chain(std::iter::once(ClassLoader::bootstrap()))
+			.flat_map(ClassLoader::defined_classes)
+			.collect()
+	}
 }
@@ -0,0 +1,162 @@
+use super::{ClassLoader, LoaderLink, is_permitted_subclass};
+use crate::objects::class::ClassPtr;
+use crate::symbols::Symbol;
+use crate::test_utils::init_basic_shared_runtime;
+use crate::thread::exceptions::{ExceptionKind, Throws};
+
+/// A class file for an empty `public class <name> extends <super_name>`
+///
+/// If `permitted_subclasses` is non-empty, the class is sealed.
+fn class_file(name: &str, super_name: &str, permitted_subclasses: &[&str]) -> Vec<u8> {
+	struct ConstantPool {
+		bytes: Vec<u8>,
+		count: u16,
+	}
+
+	impl ConstantPool {
+		fn utf8(&mut self, value: &str) -> u16 {
+			self.bytes.push(1); // CONSTANT_Utf8
+			self.bytes.extend((value.len() as u16).to_be_bytes());
+			self.bytes.extend(value.as_bytes());
+			self.count += 1;
+			self.count
+		}
+
+		fn class(&mut self, name: &str) -> u16 {
+			let name_index = self.utf8(name);
+			self.bytes.push(7); // CONSTANT_Class
+			self.bytes.extend(name_index.to_be_bytes());
+			self.count += 1;
+			self.count
+		}
+	}
+
+	let mut constant_pool = ConstantPool {
+		bytes: Vec::new(),
+		count: 0,
+	};
+	let this_class = constant_pool.class(name);
+	let super_class = constant_pool.class(super_name);
+
+	let mut attributes = Vec::new();
+	let mut attributes_count = 0_u16;
+	if !permitted_subclasses.is_empty() {
+		let attribute_name = constant_pool.utf8("PermittedSubclasses");
+		let classes = permitted_subclasses
+			.iter()
+			.map(|name| constant_pool.class(name))
+			.collect::<Vec<_>>();
+
+		attributes.extend(attribute_name.to_be_bytes());
+		attributes.extend((2 + 2 * classes.len() as u32).to_be_bytes());
+		attributes.extend((classes.len() as u16).to_be_bytes());
+		for class in classes {
+			attributes.extend(class.to_be_bytes());
+		}
+		attributes_count += 1;
+	}
+
+	let mut bytes = Vec::new();
+	bytes.extend(0xCAFE_BABE_u32.to_be_bytes());
+	bytes.extend(0_u16.to_be_bytes()); // minor_version
+	bytes.extend(61_u16.to_be_bytes()); // major_version
+
+	bytes.extend((constant_pool.count + 1).to_be_bytes());
+	bytes.extend(constant_pool.bytes);
+
+	bytes.extend(0x0021_u16.to_be_bytes()); // ACC_PUBLIC | ACC_SUPER
+	bytes.extend(this_class.to_be_bytes());
+	bytes.extend(super_class.to_be_bytes());
+
+	bytes.extend(0_u16.to_be_bytes()); // interfaces_count
+	bytes.extend(0_u16.to_be_bytes()); // fields_count
+	bytes.extend(0_u16.to_be_bytes()); // methods_count
+	bytes.extend(attributes_count.to_be_bytes());
+	bytes.extend(attributes);
+	bytes
+}
+
+fn define(name: &str, super_name: &str, permitted_subclasses: &[&str]) -> Throws<ClassPtr> {
+	let bytes = class_file(name, super_name, permitted_subclasses);
+	ClassLoader::bootstrap().derive_class(Symbol::intern(name), Some(&bytes), LoaderLink::Normal)
+}
+
+#[test]
+fn hidden_classes_are_found_by_mangled_name() {
+	init_basic_shared_runtime();
+
+	let loader = ClassLoader::bootstrap();
+	let name = Symbol::intern("loaderTests/Hidden");
+	let bytes = class_file(name.as_str(), "java/lang/Object", &[]);
+
+	let Throws::Ok(class) = loader.derive_class(name, Some(&bytes), LoaderLink::Hidden) else {
+		panic!("hidden class should be defined");
+	};
+	assert!(class.is_hidden());
+	assert_ne!(class.name(), name);
+	assert_eq!(loader.lookup_class(class.name()), Some(class));
+	assert!(loader.defined_classes().contains(&class));
+
+	// Hidden classes are never recorded under their original name, so it can be reused
+	assert_eq!(loader.lookup_class(name), None);
+	let Throws::Ok(other) = loader.derive_class(name, Some(&bytes), LoaderLink::Hidden) else {
+		panic!("hidden class should be defined");
+	};
+	assert_ne!(other, class);
+	assert_ne!(other.name(), class.name());
+}
+
+#[test]
+fn failed_definitions_are_not_recorded() {
+	init_basic_shared_runtime();
+
+	let loader = ClassLoader::bootstrap();
+	let name = Symbol::intern("loaderTests/ExtendsFinal");
+	let bytes = class_file(name.as_str(), "java/lang/String", &[]);
+
+	for _ in 0..2 {
+		// A failed definition never makes it into the loader, so retrying fails for the same
+		// reason, rather than with a duplicate class definition
+		let Throws::Exception(e) = loader.derive_class(name, Some(&bytes), LoaderLink::Normal)
+		else {
+			panic!("should not be able to extend a final class");
+		};
+		assert_eq!(e.kind(), ExceptionKind::IncompatibleClassChangeError);
+		assert_eq!(loader.lookup_class(name), None);
+	}
+}
+
+#[test]
+fn sealed_classes() {
+	init_basic_shared_runtime();
+
+	let Throws::Ok(sealed) = define(
+		"loaderTests/Sealed",
+		"java/lang/Object",
+		&["loaderTests/Permitted"],
+	) else {
+		panic!("sealed class should be defined");
+	};
+	assert_eq!(
+		sealed.unwrap_class_instance().permitted_subclasses(),
+		Some(&[Symbol::intern("loaderTests/Permitted")][..])
+	);
+
+	let Throws::Ok(permitted) = define("loaderTests/Permitted", "loaderTests/Sealed", &[]) else {
+		panic!("permitted subclass should be defined");
+	};
+	assert!(is_permitted_subclass(permitted, sealed));
+
+	// Classes that aren't sealed permit anything
+	let object = crate::globals::classes::java_lang_Object();
+	assert!(is_permitted_subclass(sealed, object));
+
+	let Throws::Exception(e) = define("loaderTests/NotPermitted", "loaderTests/Sealed", &[]) else {
+		panic!("should not be able to extend a sealed class without permission");
+	};
+	assert_eq!(e.kind(), ExceptionKind::IncompatibleClassChangeError);
+	assert_eq!(
+		ClassLoader::bootstrap().lookup_class(Symbol::intern("loaderTests/NotPermitted")),
+		None
+	);
+}
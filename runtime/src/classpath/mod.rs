@@ -2,10 +2,12 @@ pub mod jar;
 pub mod jimage;
 pub mod loader;
 
+use crate::cds;
 use crate::classpath::loader::ClassLoader;
 use crate::modules::with_module_lock;
 use crate::symbols::{Symbol, sym};
 
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -20,7 +22,7 @@ pub fn add_classpath_entry(entry: ClassPathEntry) {
 	CLASSPATH.write().unwrap().entries.push(entry);
 }
 
-pub fn find_classpath_entry(loader: &ClassLoader, name: Symbol) -> Option<Vec<u1>> {
+pub fn find_classpath_entry(loader: &ClassLoader, name: Symbol) -> Option<Cow<'static, [u1]>> {
 	let mut name = name.as_str().replace('.', "/");
 	name.push_str(".class");
 
@@ -31,10 +33,14 @@ pub fn find_classpath_entry(loader: &ClassLoader, name: Symbol) -> Option<Vec<u1
 		.and_then(|pkg| pkg.module().name())
 		.unwrap_or(sym!(java_base));
 
+	if let Some(class_bytes) = cds::lookup_class(module_name.as_str(), &name) {
+		return Some(Cow::Borrowed(class_bytes));
+	}
+
 	if jimage::initialized()
 		&& let Some(resource) = jimage::lookup_vm_resource(module_name.as_str(), &name)
 	{
-		return Some(resource.into_vec());
+		return Some(Cow::Owned(resource.into_vec()));
 	}
 
 	for entry in &CLASSPATH.read().unwrap().entries {
@@ -42,7 +48,7 @@ pub fn find_classpath_entry(loader: &ClassLoader, name: Symbol) -> Option<Vec<u1
 			ClassPathEntry::Dir(path) => {
 				let class_path = path.join(&name);
 				if class_path.exists() {
-					return Some(std::fs::read(class_path).unwrap());
+					return Some(Cow::Owned(std::fs::read(class_path).unwrap()));
 				}
 			},
 			ClassPathEntry::Zip(archive) => {
@@ -50,7 +56,7 @@ pub fn find_classpath_entry(loader: &ClassLoader, name: Symbol) -> Option<Vec<u1
 				if let Ok(mut file) = archive.by_name(&name) {
 					let mut file_contents = Vec::with_capacity(file.size() as usize);
 					file.read_to_end(&mut file_contents).unwrap();
-					return Some(file_contents);
+					return Some(Cow::Owned(file_contents));
 				}
 			},
 		}
@@ -0,0 +1,105 @@
+//! Heap sizing and accounting
+//!
+//! Objects are taken straight from the system allocator (see [`Object::allocate`]), and since there
+//! is no collector, they are never given back. The heap is just a running total of their sizes,
+//! capped at the maximum heap size (`-Xmx`), so that a runaway allocation ends in an
+//! `OutOfMemoryError`, rather than exhausting the memory of the host.
+//!
+//! [`Object::allocate`]: crate::objects::instance::object::Object::allocate
+
+use crate::options::JvmOptions;
+use crate::os;
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The percentage of physical memory used for the default maximum heap size, same as Hotspot's
+/// `-XX:MaxRAMPercentage`
+const MAX_RAM_PERCENTAGE: usize = 25;
+
+/// The divisor of physical memory used for the default initial heap size, same as Hotspot's
+/// `-XX:InitialRAMPercentage` (1.5625%)
+const INITIAL_RAM_FRACTION: usize = 64;
+
+/// The percentage of the heap kept free as it grows, same as Hotspot's `-XX:MinHeapFreeRatio`
+const MIN_HEAP_FREE_RATIO: usize = 40;
+
+/// The number of bytes currently allocated to objects
+static USED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Copy, Clone)]
+struct HeapSizes {
+	initial: usize,
+	max: usize,
+}
+
+fn sizes() -> &'static HeapSizes {
+	static SIZES: OnceLock<HeapSizes> = OnceLock::new();
+	SIZES.get_or_init(|| {
+		let options = JvmOptions::get();
+		let physical_memory = usize::try_from(os::physical_memory()).unwrap_or(usize::MAX);
+
+		let initial = options
+			.initial_heap_size
+			.unwrap_or(physical_memory / INITIAL_RAM_FRACTION);
+
+		// A larger `-Xms` raises the default maximum, but never an explicit `-Xmx`
+		let max = match options.max_heap_size {
+			Some(max) => max,
+			None => (physical_memory / 100 * MAX_RAM_PERCENTAGE).max(initial),
+		};
+
+		HeapSizes {
+			initial: initial.min(max),
+			max,
+		}
+	})
+}
+
+/// Account for an allocation of `size` bytes
+///
+/// This returns `false` if the allocation would exceed the maximum heap size, in which case
+/// nothing is reserved.
+pub fn reserve(size: usize) -> bool {
+	let max = max_size();
+	USED.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+		used.checked_add(size).filter(|total| *total <= max)
+	})
+	.is_ok()
+}
+
+/// Give back `size` bytes from a previous [`reserve()`]
+pub fn release(size: usize) {
+	USED.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// The maximum size the heap can grow to, in bytes
+pub fn max_size() -> usize {
+	sizes().max
+}
+
+/// The current size of the heap, in bytes
+///
+/// The heap starts out at the initial heap size (`-Xms`), and grows as objects are allocated,
+/// keeping [`MIN_HEAP_FREE_RATIO`] percent of it free until it reaches the maximum size.
+pub fn total_size() -> usize {
+	committed_size(used_size())
+}
+
+fn committed_size(used: usize) -> usize {
+	let HeapSizes { initial, max } = *sizes();
+
+	let desired = used / (100 - MIN_HEAP_FREE_RATIO) * 100;
+	desired.clamp(initial, max).max(used)
+}
+
+/// The number of bytes allocated to objects
+pub fn used_size() -> usize {
+	USED.load(Ordering::Relaxed)
+}
+
+/// The amount of unused memory in the heap, in bytes
+pub fn free_size() -> usize {
+	let used = used_size();
+	committed_size(used) - used
+}
@@ -0,0 +1,751 @@
+//! Heap dumps in the HPROF format
+//!
+//! This is the same format produced by Hotspot (and `jmap`), so the dumps can be opened with any of
+//! the usual tools (VisualVM, Eclipse MAT, ...). See `src/hotspot/share/services/heapDumper.cpp`
+//! in the JDK for the format description.
+//!
+//! Every object reachable from the roots (loaded classes, live threads and their frames) is
+//! written. Other threads keep running while the heap is walked, so the dump is not necessarily a
+//! consistent snapshot.
+
+use super::out_of_memory;
+use crate::classpath::loader::ClassLoaderSet;
+use crate::native::java::lang::Throwable::BackTraceElement;
+use crate::objects::class::ClassPtr;
+use crate::objects::field::Field;
+use crate::objects::instance::Instance;
+use crate::objects::instance::array::{Array, PrimitiveArrayInstanceRef, TypeCode};
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::symbols::Symbol;
+use crate::thread::frame::stack::VisibleStackFrame;
+use crate::thread::pool::ThreadPool;
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use classfile::FieldType;
+use instructions::Operand;
+use jni::sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort};
+
+const HEADER: &[u8] = b"JAVA PROFILE 1.0.2\0";
+
+/// The size of object and string IDs, which are just addresses
+const ID_SIZE: u32 = 8;
+
+/// Heap dump segments are split once they reach this size, as readers may not handle huge records
+const SEGMENT_SIZE: usize = 1 << 20;
+
+/// The serial of the empty stack trace used for every object, as allocation sites aren't tracked
+const DUMMY_STACK_TRACE_SERIAL: u32 = 1;
+
+// Top level record tags
+const TAG_UTF8: u8 = 0x01;
+const TAG_LOAD_CLASS: u8 = 0x02;
+const TAG_FRAME: u8 = 0x04;
+const TAG_TRACE: u8 = 0x05;
+const TAG_HEAP_DUMP_SEGMENT: u8 = 0x1C;
+const TAG_HEAP_DUMP_END: u8 = 0x2C;
+
+// Heap dump sub-record tags
+const TAG_ROOT_UNKNOWN: u8 = 0xFF;
+const TAG_ROOT_JAVA_FRAME: u8 = 0x03;
+const TAG_ROOT_STICKY_CLASS: u8 = 0x05;
+const TAG_ROOT_THREAD_OBJ: u8 = 0x08;
+const TAG_CLASS_DUMP: u8 = 0x20;
+const TAG_INSTANCE_DUMP: u8 = 0x21;
+const TAG_OBJ_ARRAY_DUMP: u8 = 0x22;
+const TAG_PRIM_ARRAY_DUMP: u8 = 0x23;
+
+/// The basic type of object fields and array elements
+///
+/// The primitive types are the same as their [`TypeCode`]s.
+const TYPE_OBJECT: u8 = 2;
+
+// Line numbers for frames that don't have one
+const LINE_UNKNOWN: i32 = -1;
+const LINE_NATIVE: i32 = -3;
+
+/// The outcome of a successful [`write_to()`]
+pub struct Summary {
+	/// The size of the dump file
+	pub bytes: u64,
+	/// How long the dump took to write
+	pub duration: Duration,
+}
+
+/// Dump the heap to a new file at `path`
+///
+/// Like Hotspot, this will not overwrite an existing file.
+pub fn write_to(path: &Path) -> io::Result<Summary> {
+	let start = Instant::now();
+
+	let file = File::create_new(path)?;
+	let mut writer = HprofWriter::new(BufWriter::new(file))?;
+	HeapDumper::default().dump(&mut writer)?;
+	let bytes = writer.finish()?;
+
+	Ok(Summary {
+		bytes,
+		duration: start.elapsed(),
+	})
+}
+
+/// The ID of an object in the dump
+///
+/// Classes are identified by their mirrors, so fields referencing a `java.lang.Class` point to the
+/// matching class dump.
+fn object_id(object: Reference) -> u64 {
+	if object.is_null() {
+		return 0;
+	}
+
+	object.raw_tagged() as u64
+}
+
+fn class_id(class: ClassPtr) -> u64 {
+	object_id(Reference::mirror(class.mirror()))
+}
+
+fn field_type(descriptor: &FieldType) -> u8 {
+	match descriptor {
+		FieldType::Boolean => TypeCode::Boolean as u8,
+		FieldType::Character => TypeCode::Char as u8,
+		FieldType::Float => TypeCode::Float as u8,
+		FieldType::Double => TypeCode::Double as u8,
+		FieldType::Byte => TypeCode::Byte as u8,
+		FieldType::Short => TypeCode::Short as u8,
+		FieldType::Integer => TypeCode::Int as u8,
+		FieldType::Long => TypeCode::Long as u8,
+		FieldType::Object(_) | FieldType::Array(_) => TYPE_OBJECT,
+		FieldType::Void => unreachable!("fields cannot be void"),
+	}
+}
+
+/// The size of a field's value in the dump
+fn field_size(descriptor: &FieldType) -> u32 {
+	match descriptor {
+		FieldType::Boolean | FieldType::Byte => 1,
+		FieldType::Character | FieldType::Short => 2,
+		FieldType::Float | FieldType::Integer => 4,
+		FieldType::Double | FieldType::Long => 8,
+		FieldType::Object(_) | FieldType::Array(_) => ID_SIZE,
+		FieldType::Void => unreachable!("fields cannot be void"),
+	}
+}
+
+/// The instance fields declared by `class` itself, in the order they're dumped
+fn declared_instance_fields(class: ClassPtr) -> Vec<&'static Field> {
+	class
+		.instance_fields()
+		.filter(|field| field.class == class)
+		.collect()
+}
+
+/// `class` and its superclasses, in the order their fields are dumped
+fn hierarchy(class: ClassPtr) -> impl Iterator<Item = ClassPtr> {
+	std::iter::once(class).chain(class.parent_iter())
+}
+
+/// A buffer for the body of a single record
+#[derive(Default)]
+struct Record(Vec<u8>);
+
+impl Record {
+	fn u1(&mut self, value: u8) {
+		self.0.push(value);
+	}
+
+	fn u2(&mut self, value: u16) {
+		self.0.extend_from_slice(&value.to_be_bytes());
+	}
+
+	fn u4(&mut self, value: u32) {
+		self.0.extend_from_slice(&value.to_be_bytes());
+	}
+
+	fn u8(&mut self, value: u64) {
+		self.0.extend_from_slice(&value.to_be_bytes());
+	}
+
+	fn id(&mut self, id: u64) {
+		self.u8(id);
+	}
+
+	fn bytes(&mut self, bytes: &[u8]) {
+		self.0.extend_from_slice(bytes);
+	}
+
+	/// Write a field value, sized according to `descriptor`
+	fn value(&mut self, descriptor: &FieldType, value: Operand<Reference>) {
+		match (descriptor, value) {
+			(FieldType::Boolean | FieldType::Byte, Operand::Int(value)) => self.u1(value as u8),
+			(FieldType::Character | FieldType::Short, Operand::Int(value)) => self.u2(value as u16),
+			(FieldType::Integer, Operand::Int(value)) => self.u4(value as u32),
+			(FieldType::Float, Operand::Float(value)) => self.u4(value.to_bits()),
+			(FieldType::Long, Operand::Long(value)) => self.u8(value as u64),
+			(FieldType::Double, Operand::Double(value)) => self.u8(value.to_bits()),
+			(FieldType::Object(_) | FieldType::Array(_), Operand::Reference(value)) => {
+				self.id(object_id(value))
+			},
+			// Uninitialized, or otherwise not what the descriptor says. Just zero it out.
+			(descriptor, _) => {
+				let size = field_size(descriptor) as usize;
+				self.0.resize(self.0.len() + size, 0);
+			},
+		}
+	}
+}
+
+/// Writes the records of an HPROF file to `W`
+struct HprofWriter<W: Write> {
+	out: W,
+	bytes_written: u64,
+	/// The IDs of the strings written so far
+	strings: HashMap<Symbol, u64>,
+	/// Sub-records waiting to be written as part of a heap dump segment
+	segment: Record,
+}
+
+impl<W: Write> HprofWriter<W> {
+	fn new(out: W) -> io::Result<Self> {
+		let mut this = Self {
+			out,
+			bytes_written: 0,
+			strings: HashMap::new(),
+			segment: Record::default(),
+		};
+
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis() as u64;
+
+		let mut header = Record::default();
+		header.bytes(HEADER);
+		header.u4(ID_SIZE);
+		header.u8(timestamp);
+		this.write_raw(&header.0)?;
+
+		Ok(this)
+	}
+
+	fn write_raw(&mut self, bytes: &[u8]) -> io::Result<()> {
+		self.out.write_all(bytes)?;
+		self.bytes_written += bytes.len() as u64;
+		Ok(())
+	}
+
+	fn record(&mut self, tag: u8, body: &Record) -> io::Result<()> {
+		let length = u32::try_from(body.0.len())
+			.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "HPROF record too large"))?;
+
+		let mut header = Record::default();
+		header.u1(tag);
+		// Microseconds since the header timestamp, which nobody uses
+		header.u4(0);
+		header.u4(length);
+
+		self.write_raw(&header.0)?;
+		self.write_raw(&body.0)
+	}
+
+	/// Get the ID of `string`, writing it first if needed
+	fn string(&mut self, string: Symbol) -> io::Result<u64> {
+		if let Some(id) = self.strings.get(&string) {
+			return Ok(*id);
+		}
+
+		let id = self.strings.len() as u64 + 1;
+		let mut body = Record::default();
+		body.id(id);
+		body.bytes(string.as_bytes());
+		self.record(TAG_UTF8, &body)?;
+
+		self.strings.insert(string, id);
+		Ok(id)
+	}
+
+	/// Add a sub-record to the current heap dump segment
+	fn sub_record(&mut self, build: impl FnOnce(&mut Record)) -> io::Result<()> {
+		build(&mut self.segment);
+		if self.segment.0.len() >= SEGMENT_SIZE {
+			self.flush_segment()?;
+		}
+
+		Ok(())
+	}
+
+	fn flush_segment(&mut self) -> io::Result<()> {
+		if self.segment.0.is_empty() {
+			return Ok(());
+		}
+
+		let segment = std::mem::take(&mut self.segment);
+		self.record(TAG_HEAP_DUMP_SEGMENT, &segment)
+	}
+
+	/// Finish the heap dump, returning the total number of bytes written
+	fn finish(mut self) -> io::Result<u64> {
+		self.flush_segment()?;
+		self.record(TAG_HEAP_DUMP_END, &Record::default())?;
+		self.out.flush()?;
+		Ok(self.bytes_written)
+	}
+}
+
+/// A thread, as captured for the dump
+struct ThreadRoots {
+	object: Reference,
+	frames: Vec<BackTraceElement>,
+	/// The references held by each frame's locals, by frame index
+	locals: Vec<(u32, Reference)>,
+}
+
+#[derive(Default)]
+struct HeapDumper {
+	/// The serials of the `LOAD_CLASS` records, by class ID
+	class_serials: HashMap<u64, u32>,
+	visited: HashSet<u64>,
+	pending: Vec<Reference>,
+	next_frame_id: u64,
+}
+
+impl HeapDumper {
+	fn dump<W: Write>(mut self, writer: &mut HprofWriter<W>) -> io::Result<()> {
+		let classes = ClassLoaderSet::loaded_classes();
+		for class in &classes {
+			self.load_class(writer, *class)?;
+		}
+
+		let mut dummy_trace = Record::default();
+		dummy_trace.u4(DUMMY_STACK_TRACE_SERIAL);
+		dummy_trace.u4(0);
+		dummy_trace.u4(0);
+		writer.record(TAG_TRACE, &dummy_trace)?;
+
+		let threads = ThreadPool::live_threads()
+			.into_iter()
+			.filter_map(|thread| {
+				thread.handshake(|thread| {
+					let object = thread.obj()?;
+					let frames = thread.collect_stack_trace();
+					let locals = thread
+						.frame_stack()
+						.iter()
+						.enumerate()
+						.filter_map(|(index, frame)| match frame {
+							VisibleStackFrame::Regular(frame) => Some((index as u32, frame)),
+							VisibleStackFrame::Native(_) => None,
+						})
+						.flat_map(|(index, frame)| {
+							frame
+								.locals()
+								.into_iter()
+								.filter_map(move |local| match local {
+									Operand::Reference(object) if !object.is_null() => {
+										Some((index, object))
+									},
+									_ => None,
+								})
+						})
+						.collect();
+
+					Some(ThreadRoots {
+						object,
+						frames,
+						locals,
+					})
+				})
+			})
+			.collect::<Vec<_>>();
+
+		for (index, thread) in threads.iter().enumerate() {
+			let thread_serial = index as u32 + 1;
+			self.thread_trace(writer, thread_serial, &thread.frames)?;
+		}
+
+		// Roots
+		for class in &classes {
+			if class.loader().is_bootstrap() {
+				writer.sub_record(|record| {
+					record.u1(TAG_ROOT_STICKY_CLASS);
+					record.id(class_id(*class));
+				})?;
+			}
+		}
+
+		for (index, thread) in threads.iter().enumerate() {
+			let thread_serial = index as u32 + 1;
+			writer.sub_record(|record| {
+				record.u1(TAG_ROOT_THREAD_OBJ);
+				record.id(object_id(thread.object));
+				record.u4(thread_serial);
+				record.u4(Self::trace_serial(thread_serial));
+			})?;
+			self.push(thread.object);
+
+			for (frame_index, object) in &thread.locals {
+				writer.sub_record(|record| {
+					record.u1(TAG_ROOT_JAVA_FRAME);
+					record.id(object_id(*object));
+					record.u4(thread_serial);
+					record.u4(*frame_index);
+				})?;
+				self.push(*object);
+			}
+		}
+
+		if let Some(error) = out_of_memory::try_preallocated() {
+			writer.sub_record(|record| {
+				record.u1(TAG_ROOT_UNKNOWN);
+				record.id(object_id(error));
+			})?;
+			self.push(error);
+		}
+
+		// Everything reachable from the roots
+		for class in &classes {
+			self.class_dump(writer, *class)?;
+		}
+
+		while let Some(object) = self.pending.pop() {
+			self.object_dump(writer, object)?;
+		}
+
+		Ok(())
+	}
+
+	fn trace_serial(thread_serial: u32) -> u32 {
+		DUMMY_STACK_TRACE_SERIAL + thread_serial
+	}
+
+	fn push(&mut self, object: Reference) {
+		if !object.is_null() && self.visited.insert(object_id(object)) {
+			self.pending.push(object);
+		}
+	}
+
+	fn load_class<W: Write>(
+		&mut self,
+		writer: &mut HprofWriter<W>,
+		class: ClassPtr,
+	) -> io::Result<()> {
+		let serial = self.class_serials.len() as u32 + 1;
+		self.class_serials.insert(class_id(class), serial);
+
+		let name = writer.string(class.name())?;
+
+		let mut body = Record::default();
+		body.u4(serial);
+		body.id(class_id(class));
+		body.u4(DUMMY_STACK_TRACE_SERIAL);
+		body.id(name);
+		writer.record(TAG_LOAD_CLASS, &body)
+	}
+
+	fn thread_trace<W: Write>(
+		&mut self,
+		writer: &mut HprofWriter<W>,
+		thread_serial: u32,
+		frames: &[BackTraceElement],
+	) -> io::Result<()> {
+		let mut frame_ids = Vec::with_capacity(frames.len());
+		for frame in frames {
+			let method = frame.method;
+
+			self.next_frame_id += 1;
+			let frame_id = self.next_frame_id;
+			frame_ids.push(frame_id);
+
+			let name = writer.string(method.name)?;
+			let signature = writer.string(method.descriptor_sym())?;
+			let source_file = match method.class().source_file_name() {
+				Some(source_file) => writer.string(source_file)?,
+				None => 0,
+			};
+			let line_number = if method.is_native() {
+				LINE_NATIVE
+			} else if frame.pc < 0 {
+				LINE_UNKNOWN
+			} else {
+				method.line_number(frame.pc as isize)
+			};
+
+			let class_serial = self
+				.class_serials
+				.get(&class_id(method.class()))
+				.copied()
+				.unwrap_or(0);
+
+			let mut body = Record::default();
+			body.id(frame_id);
+			body.id(name);
+			body.id(signature);
+			body.id(source_file);
+			body.u4(class_serial);
+			body.u4(line_number as u32);
+			writer.record(TAG_FRAME, &body)?;
+		}
+
+		let mut body = Record::default();
+		body.u4(Self::trace_serial(thread_serial));
+		body.u4(thread_serial);
+		body.u4(frame_ids.len() as u32);
+		for frame_id in frame_ids {
+			body.id(frame_id);
+		}
+		writer.record(TAG_TRACE, &body)
+	}
+
+	fn class_dump<W: Write>(
+		&mut self,
+		writer: &mut HprofWriter<W>,
+		class: ClassPtr,
+	) -> io::Result<()> {
+		// The mirror itself is represented by this record, but its fields may still lead elsewhere
+		let mirror = Reference::mirror(class.mirror());
+		self.visited.insert(object_id(mirror));
+		self.push_fields(mirror);
+
+		let loader = class.loader().obj();
+		self.push(loader);
+
+		let mut static_fields = Vec::new();
+		for field in class.static_fields() {
+			let value = class.static_field_value(field.index());
+			if let Operand::Reference(object) = value {
+				self.push(object);
+			}
+
+			static_fields.push((writer.string(field.name)?, field, value));
+		}
+
+		let mut instance_fields = Vec::new();
+		for field in declared_instance_fields(class) {
+			instance_fields.push((writer.string(field.name)?, field));
+		}
+
+		let instance_size = hierarchy(class)
+			.flat_map(declared_instance_fields)
+			.map(|field| field_size(&field.descriptor))
+			.sum::<u32>();
+
+		writer.sub_record(|record| {
+			record.u1(TAG_CLASS_DUMP);
+			record.id(class_id(class));
+			record.u4(DUMMY_STACK_TRACE_SERIAL);
+			record.id(class.super_class.map_or(0, class_id));
+			record.id(object_id(loader));
+			// Signers, protection domain, and two reserved IDs
+			for _ in 0..4 {
+				record.id(0);
+			}
+			record.u4(instance_size);
+
+			// Constant pool
+			record.u2(0);
+
+			record.u2(static_fields.len() as u16);
+			for (name, field, value) in static_fields {
+				record.id(name);
+				record.u1(field_type(&field.descriptor));
+				record.value(&field.descriptor, value);
+			}
+
+			record.u2(instance_fields.len() as u16);
+			for (name, field) in instance_fields {
+				record.id(name);
+				record.u1(field_type(&field.descriptor));
+			}
+		})
+	}
+
+	/// Queue every object referenced by the fields of `object`
+	fn push_fields(&mut self, object: Reference) {
+		for field in hierarchy(object.class()).flat_map(declared_instance_fields) {
+			if let Operand::Reference(value) = object.get_field_value(field) {
+				self.push(value);
+			}
+		}
+	}
+
+	fn object_dump<W: Write>(
+		&mut self,
+		writer: &mut HprofWriter<W>,
+		object: Reference,
+	) -> io::Result<()> {
+		if object.is_object_array() {
+			let array = object.extract_object_array();
+			let elements = array.as_slice();
+			for element in elements {
+				self.push(*element);
+			}
+
+			return writer.sub_record(|record| {
+				record.u1(TAG_OBJ_ARRAY_DUMP);
+				record.id(object_id(object));
+				record.u4(DUMMY_STACK_TRACE_SERIAL);
+				record.u4(elements.len() as u32);
+				record.id(class_id(array.class()));
+				for element in elements {
+					record.id(object_id(*element));
+				}
+			});
+		}
+
+		if object.is_primitive_array() {
+			let array = object.extract_primitive_array();
+			return writer.sub_record(|record| {
+				record.u1(TAG_PRIM_ARRAY_DUMP);
+				record.id(object_id(object));
+				record.u4(DUMMY_STACK_TRACE_SERIAL);
+				record.u4(array.len() as u32);
+				record.u1(array.type_code() as u8);
+				primitive_array_elements(record, array);
+			});
+		}
+
+		// Class mirrors are written as class dumps, only primitive mirrors (e.g. `int.class`) are
+		// left as plain instances of `java.lang.Class`.
+		if object.is_mirror() && !object.extract_mirror().is_primitive() {
+			self.push_fields(object);
+			return Ok(());
+		}
+
+		self.push_fields(object);
+
+		let class = object.class();
+		writer.sub_record(|record| {
+			record.u1(TAG_INSTANCE_DUMP);
+			record.id(object_id(object));
+			record.u4(DUMMY_STACK_TRACE_SERIAL);
+			record.id(class_id(class));
+
+			let mut values = Record::default();
+			for field in hierarchy(class).flat_map(declared_instance_fields) {
+				values.value(&field.descriptor, object.get_field_value(field));
+			}
+
+			record.u4(values.0.len() as u32);
+			record.bytes(&values.0);
+		})
+	}
+}
+
+fn primitive_array_elements(record: &mut Record, array: PrimitiveArrayInstanceRef) {
+	macro_rules! elements {
+		($ty:ty, |$element:ident| $write:expr) => {
+			for $element in array.as_slice::<$ty>() {
+				$write;
+			}
+		};
+	}
+
+	match array.type_code() {
+		TypeCode::Boolean => elements!(jboolean, |element| record.u1(u8::from(*element))),
+		TypeCode::Byte => elements!(jbyte, |element| record.u1(*element as u8)),
+		TypeCode::Char => elements!(jchar, |element| record.u2(*element)),
+		TypeCode::Short => elements!(jshort, |element| record.u2(*element as u16)),
+		TypeCode::Int => elements!(jint, |element| record.u4(*element as u32)),
+		TypeCode::Float => elements!(jfloat, |element| record.u4(element.to_bits())),
+		TypeCode::Long => elements!(jlong, |element| record.u8(*element as u64)),
+		TypeCode::Double => elements!(jdouble, |element| record.u8(element.to_bits())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn writer() -> HprofWriter<Vec<u8>> {
+		HprofWriter::new(Vec::new()).unwrap()
+	}
+
+	#[test]
+	fn header() {
+		let writer = writer();
+		let out = &writer.out;
+
+		assert_eq!(out.len(), HEADER.len() + 4 + 8);
+		assert!(out.starts_with(b"JAVA PROFILE 1.0.2\0"));
+		assert_eq!(out[HEADER.len()..HEADER.len() + 4], ID_SIZE.to_be_bytes());
+	}
+
+	#[test]
+	fn strings_are_written_once() {
+		let mut writer = writer();
+		let start = writer.out.len();
+
+		let name = Symbol::intern("heapDumpTest");
+		let id = writer.string(name).unwrap();
+		assert_eq!(writer.string(name).unwrap(), id);
+
+		let record = &writer.out[start..];
+		// tag + time + length + id + bytes
+		assert_eq!(record.len(), 1 + 4 + 4 + 8 + "heapDumpTest".len());
+		assert_eq!(record[0], TAG_UTF8);
+		assert_eq!(
+			record[5..9],
+			(8 + "heapDumpTest".len() as u32).to_be_bytes()
+		);
+		assert_eq!(record[9..17], id.to_be_bytes());
+		assert_eq!(&record[17..], b"heapDumpTest");
+	}
+
+	#[test]
+	fn segments_are_flushed() {
+		let mut writer = writer();
+		let start = writer.out.len();
+
+		writer
+			.sub_record(|record| {
+				record.u1(TAG_ROOT_STICKY_CLASS);
+				record.id(0xCAFE);
+			})
+			.unwrap();
+		// Nothing is written until the segment fills up, or the dump is finished
+		assert_eq!(writer.out.len(), start);
+
+		writer
+			.sub_record(|record| record.bytes(&vec![0; SEGMENT_SIZE]))
+			.unwrap();
+		assert_eq!(writer.out[start], TAG_HEAP_DUMP_SEGMENT);
+		assert_eq!(
+			writer.out[start + 5..start + 9],
+			(1 + 8 + SEGMENT_SIZE as u32).to_be_bytes()
+		);
+		assert!(writer.segment.0.is_empty());
+	}
+
+	#[test]
+	fn finish() {
+		let writer = writer();
+		let start = writer.out.len() as u64;
+
+		let bytes = writer.finish().unwrap();
+		// Just the end record, since the segment was empty
+		assert_eq!(bytes, start + 9);
+	}
+
+	#[test]
+	fn field_values() {
+		let mut record = Record::default();
+		record.value(&FieldType::Boolean, Operand::Int(1));
+		record.value(&FieldType::Character, Operand::Int(0x41));
+		record.value(&FieldType::Integer, Operand::Int(-1));
+		record.value(&FieldType::Long, Operand::Empty);
+		record.value(
+			&FieldType::Object(Box::default()),
+			Operand::Reference(Reference::null()),
+		);
+
+		let mut expected = vec![1, 0, 0x41, 0xFF, 0xFF, 0xFF, 0xFF];
+		expected.extend_from_slice(&[0; 8]);
+		expected.extend_from_slice(&[0; 8]);
+		assert_eq!(record.0, expected);
+	}
+}
@@ -0,0 +1,15 @@
+//! Garbage collection support
+//!
+//! There is currently no collector. Objects are allocated with [`Object::allocate`] and are never
+//! reclaimed, so nothing in here is ever driven by the VM itself. What *does* live here is the
+//! collector-agnostic machinery that a collector needs to cooperate with the class library, such
+//! as the handling of `java.lang.ref.Reference` objects (see [`reference_processor`]).
+//!
+//! [`Object::allocate`]: crate::objects::instance::object::Object::allocate
+
+pub mod heap;
+pub mod heap_dump;
+pub mod out_of_memory;
+pub mod reference_processor;
+
+pub use reference_processor::ReferenceType;
@@ -0,0 +1,143 @@
+//! `OutOfMemoryError` handling
+//!
+//! The first time the heap is exhausted, the `-XX:+HeapDumpOnOutOfMemoryError` and
+//! `-XX:OnOutOfMemoryError` hooks are run. Subsequent failures just throw.
+//!
+//! Constructing an `OutOfMemoryError` needs memory of its own, so one is preallocated during
+//! initialization, for when even that fails. See [`preallocated()`].
+
+use super::heap_dump;
+use crate::classes;
+use crate::objects::reference::Reference;
+use crate::options::JvmOptions;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Exception, ExceptionKind, Throws};
+
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The shell used to run the `-XX:OnOutOfMemoryError` commands
+#[cfg(unix)]
+const SHELL: [&str; 2] = ["/bin/sh", "-c"];
+#[cfg(windows)]
+const SHELL: [&str; 2] = ["cmd", "/C"];
+
+/// The message of the `OutOfMemoryError` thrown when the heap is exhausted
+pub const JAVA_HEAP_SPACE: &str = "Java heap space";
+
+static PREALLOCATED: OnceLock<Reference> = OnceLock::new();
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Create the `OutOfMemoryError` to throw when an object allocation fails, see [`report()`]
+pub fn java_heap_space() -> Exception {
+	report(JAVA_HEAP_SPACE);
+	Exception::with_message(ExceptionKind::OutOfMemoryError, JAVA_HEAP_SPACE)
+}
+
+/// Create the preallocated `OutOfMemoryError`
+///
+/// If the constructor threw, the exception is pending on `thread`. If the heap is too small to
+/// even hold the error, the unconstructed `OutOfMemoryError` is returned.
+pub fn preallocate(thread: &'static JavaThread) -> Throws<()> {
+	let exception = Exception::with_message(ExceptionKind::OutOfMemoryError, JAVA_HEAP_SPACE);
+	let error = exception.construct(thread)?;
+
+	// The stack trace would point at VM initialization, which is of no use to anyone
+	classes::java::lang::Throwable::set_backtrace(error.extract_class(), Reference::null());
+	classes::java::lang::Throwable::set_depth(error.extract_class(), 0);
+
+	PREALLOCATED
+		.set(error)
+		.expect("the OutOfMemoryError should only be preallocated once");
+	Throws::Ok(())
+}
+
+/// The `OutOfMemoryError` to throw when there isn't enough memory left to construct a new one
+///
+/// Unlike a freshly constructed error, this has no stack trace.
+///
+/// If the heap is exhausted during VM initialization, before the error is preallocated, the
+/// unconstructed `OutOfMemoryError` is returned instead. It can't be thrown, see
+/// [`Exception::throw()`].
+pub fn preallocated() -> Throws<Reference> {
+	match try_preallocated() {
+		Some(error) => Throws::Ok(error),
+		None => Throws::Exception(Exception::with_message(
+			ExceptionKind::OutOfMemoryError,
+			JAVA_HEAP_SPACE,
+		)),
+	}
+}
+
+/// The preallocated `OutOfMemoryError`, if it has been created yet
+pub fn try_preallocated() -> Option<Reference> {
+	PREALLOCATED.get().copied()
+}
+
+/// Run the `OutOfMemoryError` hooks, if this is the first time the heap has been exhausted
+pub fn report(message: &str) {
+	if REPORTED.swap(true, Ordering::AcqRel) {
+		return;
+	}
+
+	let options = JvmOptions::get();
+	if options.heap_dump_on_out_of_memory_error {
+		eprintln!("java.lang.OutOfMemoryError: {message}");
+		dump_heap(options.heap_dump_path.as_deref());
+	}
+
+	if let Some(commands) = &options.on_out_of_memory_error {
+		eprintln!("#");
+		eprintln!("# java.lang.OutOfMemoryError: {message}");
+		eprintln!("# -XX:OnOutOfMemoryError=\"{commands}\"");
+		run_commands(commands);
+	}
+}
+
+fn dump_heap(heap_dump_path: Option<&str>) {
+	let pid = std::process::id();
+	let file_name = format!("java_pid{pid}.hprof");
+
+	let path = match heap_dump_path {
+		Some(path) => {
+			let path = PathBuf::from(path.replace("%p", &pid.to_string()));
+			if path.is_dir() {
+				path.join(file_name)
+			} else {
+				path
+			}
+		},
+		None => PathBuf::from(file_name),
+	};
+
+	eprintln!("Dumping heap to {} ...", path.display());
+	match heap_dump::write_to(&path) {
+		Ok(summary) => eprintln!(
+			"Heap dump file created [{} bytes in {:.3} secs]",
+			summary.bytes,
+			summary.duration.as_secs_f64()
+		),
+		Err(e) => eprintln!("Unable to create {}: {e}", path.display()),
+	}
+}
+
+/// Run each of the `;` separated `commands`, replacing `%p` with the current process ID
+fn run_commands(commands: &str) {
+	let pid = std::process::id().to_string();
+	for command in commands.split(';').map(str::trim) {
+		if command.is_empty() {
+			continue;
+		}
+
+		let command = command.replace("%p", &pid);
+		let [shell, flag] = SHELL;
+		eprintln!("#   Executing {shell} {flag} \"{command}\"...");
+
+		let status = Command::new(shell).arg(flag).arg(&command).status();
+		if let Err(e) = status {
+			eprintln!("#   Failed to execute \"{command}\": {e}");
+		}
+	}
+}
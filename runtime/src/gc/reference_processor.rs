@@ -0,0 +1,606 @@
+//! `java.lang.ref.Reference` processing
+//!
+//! There is no collector yet, so nothing calls [`process_references()`]. Until there is, referents
+//! are never cleared and nothing is ever put on the pending list, leaving the `Reference Handler`
+//! thread waiting forever. The natives backing `java.lang.ref.Reference` (`get`, `clear`,
+//! `refersTo`, and the pending list accessors) work regardless.
+//!
+//! Once a collector exists, it is expected to discover references while it marks, the same way
+//! HotSpot does it: any reference object it traces is a candidate. Once marking has finished, it
+//! calls [`process_references()`] with those candidates and a [`ReachabilityOracle`], which will:
+//!
+//! 1. Discover the candidates whose referents are no longer strongly reachable
+//! 2. Clear (or keep alive) their referents, in the order soft → weak → final → phantom
+//! 3. Link the cleared references onto the pending list, and wake up the `Reference Handler` thread
+//!
+//! References that are still active are simply discovered again by the next collection, so nothing
+//! needs to be tracked in between. In particular, allocating a reference object costs nothing extra.
+//!
+//! The pending list is a chain of references linked through their `discovered` field, which is
+//! consumed by `java.lang.ref.Reference#processPendingReferences`.
+
+use crate::classes::java::lang::r#ref::{Reference as JReference, SoftReference};
+use crate::classpath::loader::ClassLoader;
+use crate::logging::debug;
+use crate::objects::class::ClassPtr;
+use crate::objects::instance::class::ClassInstanceRef;
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::symbols::{Symbol, sym};
+use crate::thread::JavaThread;
+
+use std::sync::{Condvar, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use jni::sys::jlong;
+
+/// The kind of `java.lang.ref.Reference` a class represents
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReferenceType {
+	/// Not a reference class, this includes `java.lang.ref.Reference` itself
+	None,
+	/// `java.lang.ref.SoftReference` and its subclasses
+	Soft,
+	/// `java.lang.ref.WeakReference` and its subclasses
+	Weak,
+	/// `java.lang.ref.FinalReference` and its subclasses
+	Final,
+	/// `java.lang.ref.PhantomReference` and its subclasses
+	Phantom,
+}
+
+impl ReferenceType {
+	/// Determine the reference type of a class that is being defined
+	///
+	/// Only the bootstrap loader can define the `java.lang.ref` classes, everything else inherits
+	/// the type of its superclass.
+	pub(crate) fn for_class(
+		name: Symbol,
+		loader: &ClassLoader,
+		super_class: Option<ClassPtr>,
+	) -> Self {
+		if loader.is_bootstrap() {
+			if name == sym!(java_lang_ref_SoftReference) {
+				return ReferenceType::Soft;
+			}
+
+			if name == sym!(java_lang_ref_WeakReference) {
+				return ReferenceType::Weak;
+			}
+
+			if name == sym!(java_lang_ref_FinalReference) {
+				return ReferenceType::Final;
+			}
+
+			if name == sym!(java_lang_ref_PhantomReference) {
+				return ReferenceType::Phantom;
+			}
+		}
+
+		match super_class {
+			Some(super_class) => super_class.reference_type(),
+			None => ReferenceType::None,
+		}
+	}
+}
+
+/// How soft references with unreachable referents are treated
+#[derive(Copy, Clone, Debug)]
+pub enum SoftReferencePolicy {
+	/// Clear every softly reachable referent, used as a last resort before an `OutOfMemoryError`
+	ClearAll,
+	/// Keep a referent alive if its reference was accessed within `max_interval_ms` of the last
+	/// collection
+	///
+	/// HotSpot derives the interval from `-XX:SoftRefLRUPolicyMSPerMB` and the amount of free heap.
+	LeastRecentlyUsed { max_interval_ms: jlong },
+}
+
+impl SoftReferencePolicy {
+	fn should_clear(self, timestamp: jlong, clock: jlong) -> bool {
+		match self {
+			SoftReferencePolicy::ClearAll => true,
+			SoftReferencePolicy::LeastRecentlyUsed { max_interval_ms } => {
+				clock - timestamp > max_interval_ms
+			},
+		}
+	}
+}
+
+/// The collector's view of the heap during reference processing
+pub trait ReachabilityOracle {
+	/// Whether `object` is strongly reachable
+	fn is_alive(&self, object: Reference) -> bool;
+
+	/// Mark `object`, and everything reachable from it, as strongly reachable
+	fn keep_alive(&mut self, object: Reference);
+
+	/// The policy to apply to soft references during this collection
+	fn soft_reference_policy(&self) -> SoftReferencePolicy;
+}
+
+static PENDING_LIST: PendingList = PendingList {
+	head: Mutex::new(None),
+	has_pending: Condvar::new(),
+};
+
+struct PendingList {
+	head: Mutex<Option<ClassInstanceRef>>,
+	has_pending: Condvar,
+}
+
+/// The fields of a `java.lang.ref.Reference` that processing needs
+///
+/// This only exists so that the processing order can be tested without any `java.lang.ref`
+/// classes being loaded.
+trait ReferenceObject: Copy {
+	/// The reference object itself
+	fn object(self) -> Reference;
+	fn reference_type(self) -> ReferenceType;
+	fn referent(self) -> Reference;
+	fn clear_referent(self);
+	/// Whether the reference has been enqueued already, see `Reference#next`
+	fn is_inactive(self) -> bool;
+	/// Mark the reference inactive with a self-looped `next`, so it is never discovered again
+	fn make_inactive(self);
+	/// The `SoftReference#timestamp`, only called for soft references
+	fn timestamp(self) -> jlong;
+}
+
+impl ReferenceObject for ClassInstanceRef {
+	fn object(self) -> Reference {
+		Reference::class(self)
+	}
+
+	fn reference_type(self) -> ReferenceType {
+		self.class().reference_type()
+	}
+
+	fn referent(self) -> Reference {
+		JReference::referent(self)
+	}
+
+	fn clear_referent(self) {
+		JReference::set_referent(self, Reference::null());
+	}
+
+	fn is_inactive(self) -> bool {
+		!JReference::next(self).is_null()
+	}
+
+	fn make_inactive(self) {
+		JReference::set_next(self, Reference::class(self));
+	}
+
+	fn timestamp(self) -> jlong {
+		SoftReference::timestamp(self)
+	}
+}
+
+struct DiscoveredLists<R> {
+	soft: Vec<R>,
+	weak: Vec<R>,
+	final_: Vec<R>,
+	phantom: Vec<R>,
+}
+
+/// Process the reference objects found by the collector, after it has finished marking
+///
+/// `candidates` are all of the reference objects that the collector traced. Any that are still
+/// active afterward need to be passed in again on the next collection.
+///
+/// NOTE: There is no collector yet, so this is never called. See the module docs.
+pub fn process_references(
+	oracle: &mut impl ReachabilityOracle,
+	candidates: impl IntoIterator<Item = ClassInstanceRef>,
+) {
+	let enqueued = process(oracle, candidates, SoftReference::clock());
+
+	SoftReference::set_clock(current_time_millis());
+
+	debug!(TARGETS: (Ref), "Enqueuing {} cleared references", enqueued.len());
+	enqueue(enqueued);
+}
+
+/// Clear the referents of `candidates` as needed, returning the references to enqueue
+fn process<R: ReferenceObject>(
+	oracle: &mut impl ReachabilityOracle,
+	candidates: impl IntoIterator<Item = R>,
+	clock: jlong,
+) -> Vec<R> {
+	let mut discovered = discover(candidates, oracle);
+
+	// Phase 1: Soft references that were recently used survive, per the policy
+	let policy = oracle.soft_reference_policy();
+	discovered.soft.retain(|&reference| {
+		if policy.should_clear(reference.timestamp(), clock) {
+			return true;
+		}
+
+		oracle.keep_alive(reference.referent());
+		false
+	});
+
+	// Phase 2: Anything kept alive by the previous phase is no longer a candidate
+	for list in [
+		&mut discovered.soft,
+		&mut discovered.weak,
+		&mut discovered.final_,
+	] {
+		list.retain(|&reference| !oracle.is_alive(reference.referent()));
+	}
+
+	let mut enqueued = Vec::with_capacity(
+		discovered.soft.len()
+			+ discovered.weak.len()
+			+ discovered.final_.len()
+			+ discovered.phantom.len(),
+	);
+
+	// Phase 3: Soft and weak referents are cleared
+	for reference in discovered.soft.drain(..).chain(discovered.weak.drain(..)) {
+		reference.clear_referent();
+		enqueued.push(reference);
+	}
+
+	// Phase 4: Final referents are resurrected for finalization
+	for reference in discovered.final_.drain(..) {
+		oracle.keep_alive(reference.referent());
+		reference.make_inactive();
+		enqueued.push(reference);
+	}
+
+	// Phase 5: Phantom references are only cleared once their referents are unreachable even from
+	//          finalizers
+	for reference in discovered.phantom.drain(..) {
+		if oracle.is_alive(reference.referent()) {
+			continue;
+		}
+
+		reference.clear_referent();
+		enqueued.push(reference);
+	}
+
+	enqueued
+}
+
+/// Sort the candidates with unreachable referents by their reference type
+fn discover<R: ReferenceObject>(
+	candidates: impl IntoIterator<Item = R>,
+	oracle: &impl ReachabilityOracle,
+) -> DiscoveredLists<R> {
+	let mut discovered = DiscoveredLists {
+		soft: Vec::new(),
+		weak: Vec::new(),
+		final_: Vec::new(),
+		phantom: Vec::new(),
+	};
+
+	for reference in candidates {
+		// The reference object itself is garbage, it goes away with its referent
+		if !oracle.is_alive(reference.object()) {
+			continue;
+		}
+
+		// Cleared through `Reference#clear()` or `Reference#enqueue()`, it can never be discovered
+		let referent = reference.referent();
+		if referent.is_null() || oracle.is_alive(referent) {
+			continue;
+		}
+
+		match reference.reference_type() {
+			ReferenceType::Soft => discovered.soft.push(reference),
+			ReferenceType::Weak => discovered.weak.push(reference),
+			// Already pending finalization
+			ReferenceType::Final if reference.is_inactive() => {},
+			ReferenceType::Final => discovered.final_.push(reference),
+			ReferenceType::Phantom => discovered.phantom.push(reference),
+			ReferenceType::None => unreachable!("only reference objects are candidates"),
+		}
+	}
+
+	debug!(
+		TARGETS: (Ref),
+		"Discovered references: {} soft, {} weak, {} final, {} phantom",
+		discovered.soft.len(),
+		discovered.weak.len(),
+		discovered.final_.len(),
+		discovered.phantom.len()
+	);
+
+	discovered
+}
+
+/// Prepend `references` onto the pending list, and wake up any waiters
+fn enqueue(references: Vec<ClassInstanceRef>) {
+	if references.is_empty() {
+		return;
+	}
+
+	let mut head = PENDING_LIST.head.lock().unwrap();
+	for reference in references.into_iter().rev() {
+		let next = head.map_or_else(Reference::null, Reference::class);
+		JReference::set_discovered(reference, next);
+		*head = Some(reference);
+	}
+
+	PENDING_LIST.has_pending.notify_all();
+}
+
+/// Whether there are any references waiting on the pending list
+pub fn has_pending_list() -> bool {
+	PENDING_LIST.head.lock().unwrap().is_some()
+}
+
+/// Take the entire pending list, linked through the `discovered` field
+pub fn get_and_clear_pending_list() -> Reference {
+	PENDING_LIST
+		.head
+		.lock()
+		.unwrap()
+		.take()
+		.map_or_else(Reference::null, Reference::class)
+}
+
+/// Block until the pending list is non-empty
+pub fn wait_for_pending_list(thread: &'static JavaThread) {
+	let _safe_region = thread.enter_safe_region();
+
+	let mut head = PENDING_LIST.head.lock().unwrap();
+	while head.is_none() {
+		head = PENDING_LIST.has_pending.wait(head).unwrap();
+	}
+}
+
+fn current_time_millis() -> jlong {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("current system time should not be before the UNIX epoch")
+		.as_millis() as jlong
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::cell::{Cell, RefCell};
+	use std::collections::{HashMap, HashSet};
+
+	/// An object that only exists to be compared by address, it is never dereferenced
+	fn object() -> Reference {
+		let object = Box::leak(Box::new(0_u64));
+		unsafe { Reference::from_raw(std::ptr::from_mut(object).cast()) }
+	}
+
+	struct FakeReferenceObject {
+		reference_type: ReferenceType,
+		referent: Cell<Reference>,
+		inactive: Cell<bool>,
+		timestamp: jlong,
+	}
+
+	type FakeReference = &'static FakeReferenceObject;
+
+	fn reference(reference_type: ReferenceType, referent: Reference) -> FakeReference {
+		Box::leak(Box::new(FakeReferenceObject {
+			reference_type,
+			referent: Cell::new(referent),
+			inactive: Cell::new(false),
+			timestamp: 0,
+		}))
+	}
+
+	fn soft_reference(referent: Reference, timestamp: jlong) -> FakeReference {
+		Box::leak(Box::new(FakeReferenceObject {
+			reference_type: ReferenceType::Soft,
+			referent: Cell::new(referent),
+			inactive: Cell::new(false),
+			timestamp,
+		}))
+	}
+
+	impl ReferenceObject for FakeReference {
+		fn object(self) -> Reference {
+			unsafe { Reference::from_raw(std::ptr::from_ref(self).cast_mut().cast()) }
+		}
+
+		fn reference_type(self) -> ReferenceType {
+			self.reference_type
+		}
+
+		fn referent(self) -> Reference {
+			self.referent.get()
+		}
+
+		fn clear_referent(self) {
+			self.referent.set(Reference::null());
+		}
+
+		fn is_inactive(self) -> bool {
+			self.inactive.get()
+		}
+
+		fn make_inactive(self) {
+			self.inactive.set(true);
+		}
+
+		fn timestamp(self) -> jlong {
+			self.timestamp
+		}
+	}
+
+	/// A heap of objects with explicit edges between them
+	struct FakeOracle {
+		alive: RefCell<HashSet<usize>>,
+		edges: HashMap<usize, Vec<Reference>>,
+		policy: SoftReferencePolicy,
+	}
+
+	impl FakeOracle {
+		fn new(policy: SoftReferencePolicy) -> Self {
+			Self {
+				alive: RefCell::new(HashSet::new()),
+				edges: HashMap::new(),
+				policy,
+			}
+		}
+
+		fn mark(&mut self, object: Reference) {
+			self.keep_alive(object);
+		}
+
+		fn mark_references(&mut self, references: &[FakeReference]) {
+			for reference in references {
+				self.mark(reference.object());
+			}
+		}
+
+		fn add_edge(&mut self, from: Reference, to: Reference) {
+			self.edges.entry(key(from)).or_default().push(to);
+		}
+	}
+
+	fn key(object: Reference) -> usize {
+		object.raw_tagged() as usize
+	}
+
+	impl ReachabilityOracle for FakeOracle {
+		fn is_alive(&self, object: Reference) -> bool {
+			self.alive.borrow().contains(&key(object))
+		}
+
+		fn keep_alive(&mut self, object: Reference) {
+			let mut worklist = vec![object];
+			while let Some(object) = worklist.pop() {
+				if !self.alive.borrow_mut().insert(key(object)) {
+					continue;
+				}
+
+				if let Some(edges) = self.edges.get(&key(object)) {
+					worklist.extend(edges);
+				}
+			}
+		}
+
+		fn soft_reference_policy(&self) -> SoftReferencePolicy {
+			self.policy
+		}
+	}
+
+	fn enqueued_objects(enqueued: &[FakeReference]) -> Vec<Reference> {
+		enqueued
+			.iter()
+			.map(|reference| reference.object())
+			.collect()
+	}
+
+	#[test]
+	fn soft_weak_final_phantom_ordering() {
+		let mut oracle = FakeOracle::new(SoftReferencePolicy::LeastRecentlyUsed {
+			max_interval_ms: 1000,
+		});
+
+		// A recently used soft reference keeps its referent, and everything reachable from it, alive
+		let softly_reachable = object();
+		let behind_soft = object();
+		oracle.add_edge(softly_reachable, behind_soft);
+		let soft = soft_reference(softly_reachable, 9500);
+		let weak_behind_soft = reference(ReferenceType::Weak, behind_soft);
+
+		// Weak references are cleared before finalization resurrects the referent, phantom
+		// references only after
+		let finalizable = object();
+		let weak = reference(ReferenceType::Weak, finalizable);
+		let final_ = reference(ReferenceType::Final, finalizable);
+		let phantom_finalizable = reference(ReferenceType::Phantom, finalizable);
+
+		let unreachable = object();
+		let phantom = reference(ReferenceType::Phantom, unreachable);
+
+		let candidates = [
+			phantom,
+			phantom_finalizable,
+			final_,
+			weak,
+			weak_behind_soft,
+			soft,
+		];
+		oracle.mark_references(&candidates);
+
+		let enqueued = process(&mut oracle, candidates, 10_000);
+		assert_eq!(
+			enqueued_objects(&enqueued),
+			[weak.object(), final_.object(), phantom.object()]
+		);
+
+		assert!(soft.referent() == softly_reachable);
+		assert!(weak_behind_soft.referent() == behind_soft);
+		assert!(oracle.is_alive(behind_soft));
+
+		assert!(weak.referent().is_null());
+
+		assert!(final_.referent() == finalizable);
+		assert!(final_.is_inactive());
+		assert!(oracle.is_alive(finalizable));
+
+		assert!(phantom_finalizable.referent() == finalizable);
+		assert!(phantom.referent().is_null());
+		assert!(!oracle.is_alive(unreachable));
+	}
+
+	#[test]
+	fn soft_reference_policy() {
+		let recent = object();
+		let stale = object();
+
+		let mut oracle = FakeOracle::new(SoftReferencePolicy::LeastRecentlyUsed {
+			max_interval_ms: 1000,
+		});
+		let recent_soft = soft_reference(recent, 9500);
+		let stale_soft = soft_reference(stale, 5000);
+		oracle.mark_references(&[recent_soft, stale_soft]);
+
+		let enqueued = process(&mut oracle, [recent_soft, stale_soft], 10_000);
+		assert_eq!(enqueued_objects(&enqueued), [stale_soft.object()]);
+		assert!(recent_soft.referent() == recent);
+		assert!(stale_soft.referent().is_null());
+
+		// Everything goes when the heap is about to run out
+		let mut oracle = FakeOracle::new(SoftReferencePolicy::ClearAll);
+		let recent_soft = soft_reference(recent, 10_000);
+		oracle.mark_references(&[recent_soft]);
+
+		let enqueued = process(&mut oracle, [recent_soft], 10_000);
+		assert_eq!(enqueued_objects(&enqueued), [recent_soft.object()]);
+		assert!(recent_soft.referent().is_null());
+	}
+
+	#[test]
+	fn ineligible_references_are_skipped() {
+		let mut oracle = FakeOracle::new(SoftReferencePolicy::ClearAll);
+
+		let strongly_reachable = object();
+		oracle.mark(strongly_reachable);
+		let live_referent = reference(ReferenceType::Weak, strongly_reachable);
+
+		let cleared = reference(ReferenceType::Weak, Reference::null());
+
+		let already_finalized = reference(ReferenceType::Final, object());
+		already_finalized.make_inactive();
+
+		let candidates = [live_referent, cleared, already_finalized];
+		oracle.mark_references(&candidates);
+
+		// The reference object itself is unreachable
+		let dead = reference(ReferenceType::Weak, object());
+
+		let enqueued = process(
+			&mut oracle,
+			[live_referent, cleared, already_finalized, dead],
+			0,
+		);
+		assert!(enqueued.is_empty());
+		assert!(live_referent.referent() == strongly_reachable);
+		assert!(!dead.referent().is_null());
+	}
+}
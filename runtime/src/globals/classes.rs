@@ -52,6 +52,7 @@ define_classes!(
 	java_lang_Thread_FieldHolder,
 	java_lang_ThreadGroup,
 	java_lang_StackTraceElement,
+	java_lang_AssertionStatusDirectives,
 	java_lang_Throwable,
 	java_lang_Cloneable,
 	java_io_Serializable,
@@ -66,7 +67,9 @@ define_classes!(
 	java_lang_reflect_Constructor,
 	java_lang_reflect_Method,
 	java_lang_reflect_Field,
+	java_lang_reflect_RecordComponent,
 	java_lang_ref_Reference,
+	java_lang_ref_SoftReference,
 	java_lang_ref_Finalizer,
 	java_io_FileDescriptor,
 	java_io_FileInputStream,
@@ -78,6 +81,12 @@ define_classes!(
 	java_lang_VirtualMachineError,
 	jdk_internal_loader_NativeLibraries,
 	jdk_internal_loader_NativeLibraries_NativeLibraryImpl,
+	jdk_internal_vm_ThreadSnapshot,
+	jdk_internal_vm_ThreadSnapshot_ThreadLock,
+	jdk_internal_foreign_abi_VMStorage,
+	jdk_internal_foreign_abi_ABIDescriptor,
+	jdk_internal_foreign_abi_NativeEntryPoint,
+	jdk_internal_foreign_abi_UpcallLinker_CallRegs,
 	// Primitive types
 	java_lang_Boolean,
 	java_lang_Byte,
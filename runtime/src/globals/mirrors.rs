@@ -1,5 +1,6 @@
 use crate::objects::instance::mirror::{MirrorInstance, MirrorInstanceRef};
 use crate::objects::reference::Reference;
+use crate::thread::exceptions::Throws;
 
 use std::cell::SyncUnsafeCell;
 
@@ -32,11 +33,13 @@ macro_rules! define_primitive_mirrors {
             )+
 
             // TODO: Panic on double init
-            pub fn init_primitive_mirrors() {
+            pub fn init_primitive_mirrors() -> Throws<()> {
 				$(
-					let [<$name:lower _mirror>] = MirrorInstance::new_primitive(<FieldType>::$name);
+					let [<$name:lower _mirror>] = MirrorInstance::new_primitive(<FieldType>::$name)?;
 					unsafe { [<set_ $name:lower>](Reference::mirror([<$name:lower _mirror>])); }
 				)+
+
+				Throws::Ok(())
 			}
         }
     };
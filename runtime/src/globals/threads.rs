@@ -5,6 +5,17 @@ use std::sync::OnceLock;
 static SYSTEM_THREAD_GROUP: OnceLock<Reference> = OnceLock::new();
 static MAIN_THREAD_GROUP: OnceLock<Reference> = OnceLock::new();
 
+/// Get the system thread group
+///
+/// # Panics
+///
+/// Panics if [`set_system_thread_group()`] hasn't been called prior.
+pub fn system_thread_group() -> Reference {
+	*SYSTEM_THREAD_GROUP
+		.get()
+		.expect("system thread group not initialized")
+}
+
 /// Set the system thread group
 ///
 /// # Panics
@@ -1,5 +1,6 @@
 use crate::classes::jdk::internal::misc;
 use crate::classpath::loader::ClassLoader;
+use crate::gc::out_of_memory;
 use crate::modules::Module;
 use crate::native::java::lang::String::StringInterner;
 use crate::native::jni::invocation_api::main_java_vm;
@@ -7,9 +8,9 @@ use crate::objects::instance::class::ClassInstance;
 use crate::objects::reference::Reference;
 use crate::options::JvmOptions;
 use crate::symbols::sym;
-use crate::thread::exceptions::{Exception, Throws};
+use crate::thread::exceptions::{Exception, ExceptionKind, Throws, handle_exception};
 use crate::thread::{JavaThread, JavaThreadBuilder};
-use crate::{classes, java_call};
+use crate::{cds, classes, java_call};
 
 use classfile::accessflags::MethodAccessFlags;
 use instructions::Operand;
@@ -39,19 +40,27 @@ pub enum InitializationError {
 /// See [`InitializationError`].
 pub fn create_java_vm(args: Option<&JavaVMInitArgs>) -> Result<JavaVm, InitializationError> {
 	let options = match args {
-		Some(args) => unsafe { JvmOptions::load(args) }
-			.map_err(|_| InitializationError::Other(JniError::InvalidArguments))?,
+		Some(args) => unsafe { JvmOptions::load(args) }.map_err(|e| {
+			eprintln!("Error: {e}");
+			InitializationError::Other(JniError::InvalidArguments)
+		})?,
 		None => JvmOptions::default(),
 	};
 
-	// Initializes the logging
-	options.logs.apply();
+	// Initializes the logging, along with everything else that depends on the options
+	options.apply();
 
 	if let Some(_vm_options) = crate::classpath::jimage::lookup_vm_options() {
 		// TODO: Actually parse the options, for now this is just here to load the JImage
 		// https://github.com/openjdk/jdk/blob/03a9a88efbb68537e24b7de28c5b81d6cd8fdb04/src/hotspot/share/runtime/arguments.cpp#L3322
 	}
 
+	// The archive is validated against the runtime image, so it can only be mapped now
+	if let Err(e) = cds::initialize() {
+		eprintln!("Error: Unable to use shared archive: {e}");
+		return Err(InitializationError::Other(JniError::InvalidArguments));
+	}
+
 	match JavaThreadBuilder::new().finish(false) {
 		Throws::Ok(thread) => {
 			JavaThread::set_current_thread(thread);
@@ -66,6 +75,20 @@ pub fn create_java_vm(args: Option<&JavaVMInitArgs>) -> Result<JavaVm, Initializ
 	Ok(unsafe { main_java_vm() })
 }
 
+/// Report an exception that can't be thrown during initialization, and abort
+///
+/// This is for exceptions that occur before the VM is able to construct them, such as the heap
+/// being exhausted before the `OutOfMemoryError` is preallocated. See
+/// [`InitializationError::EarlyExceptionThrown`].
+pub(crate) fn abort_during_initialization(exception: &Exception) -> ! {
+	eprintln!("Error occurred during initialization of VM");
+	eprintln!("{exception}");
+
+	// If a VM was created and initialized to the point that an exception was thrown,
+	// the entire process just gets aborted like Hotspot.
+	crate::shutdown::abort()
+}
+
 /// The entire initialization stage of the VM
 ///
 /// The bulk of initialization is handled in the `java.lang.System#initPhase{1,2,3}` methods, but there
@@ -105,6 +128,14 @@ pub(crate) fn initialize_thread(
 		return Err(InitializationError::Other(JniError::ExceptionThrown));
 	}
 
+	if let Throws::Exception(e) = out_of_memory::preallocate(thread) {
+		if e.kind() == ExceptionKind::PendingException {
+			return Err(InitializationError::Other(JniError::ExceptionThrown));
+		}
+
+		return Err(InitializationError::EarlyExceptionThrown(e));
+	}
+
 	// SAFETY: Preconditions filled in `init_field_offsets` && `initialize_global_classes`
 	unsafe {
 		misc::UnsafeConstants::init();
@@ -115,6 +146,12 @@ pub(crate) fn initialize_thread(
 
 	if do_java_lang_system_init {
 		init_phase_1(thread).map_err(InitializationError::Other)?;
+
+		if let Throws::Exception(e) = crate::thread::signal_dispatcher::start(thread) {
+			e.throw(thread);
+			return Err(InitializationError::Other(JniError::ExceptionThrown));
+		}
+
 		init_phase_2(thread).map_err(InitializationError::Other)?;
 		init_phase_3(thread).map_err(InitializationError::Other)?;
 	}
@@ -159,7 +196,7 @@ fn load_global_classes() -> Throws<()> {
 	}
 
 	// Fixup mirrors, as we have classes that were loaded before java.lang.Class
-	ClassLoader::fixup_mirrors();
+	ClassLoader::fixup_mirrors()?;
 
 	load!(
 		jdk_internal_misc_UnsafeConstants,
@@ -171,6 +208,7 @@ fn load_global_classes() -> Throws<()> {
 		java_lang_Cloneable,
 		java_io_Serializable,
 		java_lang_ref_Reference,
+		java_lang_ref_SoftReference,
 		java_lang_ref_Finalizer,
 		java_lang_VirtualMachineError,
 	);
@@ -205,7 +243,7 @@ fn load_global_classes() -> Throws<()> {
 	);
 
 	// Create the primitive mirrors (java.lang.Integer, etc...)
-	crate::globals::mirrors::init_primitive_mirrors();
+	crate::globals::mirrors::init_primitive_mirrors()?;
 
 	// Primitive arrays
 	load!(
@@ -244,6 +282,11 @@ fn init_field_offsets() {
 		crate::classes::java::lang::r#ref::Reference::init_offsets();
 	}
 
+	// java.lang.ref.SoftReference
+	unsafe {
+		crate::classes::java::lang::r#ref::SoftReference::init_offsets();
+	}
+
 	// jdk.internal.misc.UnsafeConstants
 	unsafe {
 		misc::UnsafeConstants::init_offsets();
@@ -329,7 +372,11 @@ fn create_thread_object(thread: &'static JavaThread) -> bool {
 	let thread_group_class = crate::globals::classes::java_lang_ThreadGroup();
 
 	// Top-level system group
-	let system_thread_group_instance = Reference::class(ClassInstance::new(thread_group_class));
+	let system_thread_group_instance = Reference::class(handle_exception!(
+		false,
+		thread,
+		ClassInstance::new(thread_group_class)
+	));
 	let system_init_method = thread_group_class
 		.vtable()
 		.find(
@@ -345,7 +392,11 @@ fn create_thread_object(thread: &'static JavaThread) -> bool {
 	);
 
 	// `main` group
-	let main_thread_group_instance = Reference::class(ClassInstance::new(thread_group_class));
+	let main_thread_group_instance = Reference::class(handle_exception!(
+		false,
+		thread,
+		ClassInstance::new(thread_group_class)
+	));
 	let init_method = thread_group_class
 		.vtable()
 		.find(
@@ -354,7 +405,7 @@ fn create_thread_object(thread: &'static JavaThread) -> bool {
 			MethodAccessFlags::NONE,
 		)
 		.expect("java.lang.ThreadGroup should have an initializer");
-	let name = StringInterner::intern("main");
+	let name = handle_exception!(false, thread, StringInterner::intern("main"));
 	java_call!(
 		thread,
 		init_method,
@@ -370,7 +421,11 @@ fn create_thread_object(thread: &'static JavaThread) -> bool {
 	crate::globals::threads::set_system_thread_group(system_thread_group_instance);
 	crate::globals::threads::set_main_thread_group(main_thread_group_instance);
 
-	thread.attach_thread_obj(Some("main"), main_thread_group_instance, false);
+	handle_exception!(
+		false,
+		thread,
+		thread.attach_thread_obj(Some("main"), main_thread_group_instance, false)
+	);
 	true
 }
 
@@ -832,7 +832,7 @@ impl Interpreter {
             // Otherwise, if the run-time constant pool entry is a string constant, that is,
             // a reference to an instance of class String, then value, a reference to that instance, is pushed onto the operand stack.
             Entry::String(string) => {
-                let interned_string = StringInterner::intern(string);
+                let interned_string = handle_exception!(frame.thread(), StringInterner::intern(string));
                 frame.push_reference(Reference::class(interned_string));
             },
 
@@ -1149,7 +1149,7 @@ impl Interpreter {
             return None;
         }
 
-        Some(ClassInstance::new(class))
+        Some(handle_exception!(None, frame.thread(), ClassInstance::new(class)))
     }
 
     fn tableswitch(frame: &mut Frame) -> isize {
@@ -1234,6 +1234,6 @@ impl Interpreter {
             }
         }
         
-        frame.push_reference(Reference::object_array(array_ref));
+        frame.push_reference(array_ref);
 	}
 }
@@ -17,10 +17,12 @@
 #![feature(generic_atomic)]
 
 pub mod calls;
+mod cds;
 pub mod classes;
 pub mod classpath;
 mod dynamic;
 pub mod error;
+pub mod gc;
 pub mod globals;
 mod initialization;
 mod interpreter;
@@ -29,6 +31,8 @@ pub mod modules;
 pub mod native;
 pub mod objects;
 pub mod options;
+pub mod os;
+mod shutdown;
 pub mod stack;
 mod symbols;
 pub mod thread;
@@ -109,6 +109,14 @@ tags! {
 		Class = "class",
 		Init = "init",
 		Exceptions = "exceptions",
+		Os = "os",
+		Thread = "thread",
+		Nestmates = "nestmates",
+		Ref = "ref",
+		Loader = "loader",
+		Constraints = "constraints",
+		Container = "container",
+		Cds = "cds",
 	}
 }
 
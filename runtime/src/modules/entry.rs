@@ -430,6 +430,27 @@ impl Module {
 		true
 	}
 
+	/// Whether this module reads `other`
+	///
+	/// Unnamed modules read every module, and every module reads `java.base`.
+	pub fn can_read(&self, other: &'static Self) -> bool {
+		if self.name.is_none() || std::ptr::eq(self, other) {
+			return true;
+		}
+
+		if std::ptr::eq(other, ClassLoader::bootstrap().java_base()) {
+			return true;
+		}
+
+		super::with_module_lock(|_guard| {
+			if other.name.is_none() && unsafe { &*self.flags.get() }.can_read_all_unnamed {
+				return true;
+			}
+
+			unsafe { &*self.reads.get() }.contains(other)
+		})
+	}
+
 	pub fn add_reads(&self, other: Option<&'static Self>) -> Throws<()> {
 		if self.name.is_none() {
 			// Nothing to do
@@ -63,6 +63,15 @@ impl Package {
 	pub fn add_qualified_export(&self, _guard: &ModuleLockGuard, module: &'static Module) {
 		unsafe { &mut *self.qualified_exports.get() }.insert(module);
 	}
+
+	/// Whether this package is exported to `module`, either unqualifiedly or by a qualified export
+	pub fn is_exported_to(&self, _guard: &ModuleLockGuard, module: &'static Module) -> bool {
+		match unsafe { &*self.export_type.get() } {
+			PackageExportType::Unqualified | PackageExportType::UnqualifiedOrAllUnnamed => true,
+			PackageExportType::AllUnnamed if module.name().is_none() => true,
+			_ => unsafe { &*self.qualified_exports.get() }.contains(module),
+		}
+	}
 }
 
 impl Package {
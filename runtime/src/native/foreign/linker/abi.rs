@@ -0,0 +1,445 @@
+//! Mapping `jdk.internal.foreign.abi.VMStorage`s onto libffi calls
+
+use crate::classes;
+use crate::objects::instance::array::ObjectArrayInstanceRef;
+use crate::objects::instance::class::ClassInstanceRef;
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+
+use classfile::FieldType;
+use instructions::Operand;
+use jni::sys::{jbyte, jint};
+use libffi::middle::{Cif, Type};
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+	use super::Storage;
+
+	/// `rdi`, `rsi`, `rdx`, `rcx`, `r8`, `r9`
+	pub const INTEGER_ARGUMENT_REGISTERS: usize = 6;
+	/// `xmm0`-`xmm7`
+	pub const VECTOR_ARGUMENT_REGISTERS: usize = 8;
+
+	/// `rax`, which holds the number of vector registers used by a variadic call
+	///
+	/// libffi always sets this up itself.
+	pub const EXTRA_INTEGER_REGISTER: Storage = Storage::Implicit;
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+	use super::Storage;
+
+	/// `x0`-`x7`
+	pub const INTEGER_ARGUMENT_REGISTERS: usize = 8;
+	/// `v0`-`v7`
+	pub const VECTOR_ARGUMENT_REGISTERS: usize = 8;
+
+	/// `x8`, the indirect result location register
+	pub const EXTRA_INTEGER_REGISTER: Storage = Storage::IndirectResult;
+}
+
+/// Both ABIs give every stack argument at least 8 bytes
+const STACK_SLOT_SIZE: usize = 8;
+
+/// The most registers a value can be returned in (an AArch64 HFA of 4 doubles)
+pub const MAX_RETURN_REGISTERS: usize = 4;
+
+/// `jdk.internal.foreign.abi.StorageType` ids, shared by both architectures
+const INTEGER_STORAGE_TYPE: usize = 0;
+const VECTOR_STORAGE_TYPE: usize = 1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RegisterClass {
+	Integer,
+	Vector,
+}
+
+impl RegisterClass {
+	fn ffi_type(self) -> Type {
+		match self {
+			RegisterClass::Integer => Type::u64(),
+			RegisterClass::Vector => Type::f64(),
+		}
+	}
+
+	/// The space a register of this class takes up in a return buffer
+	///
+	/// This matches `Architecture#typeSize` on the Java side.
+	pub fn buffer_size(self) -> usize {
+		match self {
+			RegisterClass::Integer => 8,
+			RegisterClass::Vector => 16,
+		}
+	}
+}
+
+/// Where a value lives, as described by a `jdk.internal.foreign.abi.VMStorage`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Storage {
+	/// The `n`th argument (or return) register of a class
+	Register(RegisterClass, usize),
+	/// A stack slot, at a byte offset from the stack pointer at the time of the call
+	Stack(usize),
+	/// The address of the function to call
+	TargetAddress,
+	/// The address of the buffer that a multi-register return value is written to
+	ReturnBuffer,
+	/// The address of the buffer that `errno` is written to
+	CapturedState,
+	/// The address that an in-memory return value is written to, passed out of band (AArch64 only)
+	#[cfg_attr(not(target_arch = "aarch64"), allow(dead_code))]
+	IndirectResult,
+	/// A register that libffi takes care of itself
+	Implicit,
+	/// The offset half of a heap address, which is added to the preceding base object
+	HeapOffset,
+}
+
+/// The register assignments of a `jdk.internal.foreign.abi.ABIDescriptor`
+pub struct Abi {
+	input: [Vec<(jbyte, jint)>; 2],
+	output: [Vec<(jbyte, jint)>; 2],
+	target_address: (jbyte, jint),
+	return_buffer: (jbyte, jint),
+	captured_state: (jbyte, jint),
+}
+
+fn storage_key(storage: ClassInstanceRef) -> (jbyte, jint) {
+	use classes::jdk::internal::foreign::abi::VMStorage;
+	(VMStorage::type_(storage), VMStorage::indexOrOffset(storage))
+}
+
+fn register_keys(storage: ObjectArrayInstanceRef, ty: usize) -> Vec<(jbyte, jint)> {
+	storage.as_slice()[ty]
+		.extract_object_array()
+		.as_slice()
+		.iter()
+		.map(|storage| storage_key(storage.extract_class()))
+		.collect()
+}
+
+impl Abi {
+	pub fn new(descriptor: ClassInstanceRef) -> Self {
+		use classes::jdk::internal::foreign::abi::ABIDescriptor;
+
+		let input = ABIDescriptor::inputStorage(descriptor);
+		let output = ABIDescriptor::outputStorage(descriptor);
+		Self {
+			input: [
+				register_keys(input, INTEGER_STORAGE_TYPE),
+				register_keys(input, VECTOR_STORAGE_TYPE),
+			],
+			output: [
+				register_keys(output, INTEGER_STORAGE_TYPE),
+				register_keys(output, VECTOR_STORAGE_TYPE),
+			],
+			target_address: storage_key(ABIDescriptor::targetAddrStorage(descriptor)),
+			return_buffer: storage_key(ABIDescriptor::retBufAddrStorage(descriptor)),
+			captured_state: storage_key(ABIDescriptor::capturedStateStorage(descriptor)),
+		}
+	}
+
+	/// Classify an argument `VMStorage`, which may be `null` for the offset of a heap address
+	pub fn classify_argument(&self, storage: Reference) -> Storage {
+		if storage.is_null() {
+			return Storage::HeapOffset;
+		}
+
+		let key = storage_key(storage.extract_class());
+		if key == self.target_address {
+			return Storage::TargetAddress;
+		}
+		if key == self.return_buffer {
+			return Storage::ReturnBuffer;
+		}
+		if key == self.captured_state {
+			return Storage::CapturedState;
+		}
+
+		if let Some(position) = self.input[INTEGER_STORAGE_TYPE]
+			.iter()
+			.position(|register| *register == key)
+		{
+			if position >= arch::INTEGER_ARGUMENT_REGISTERS {
+				return arch::EXTRA_INTEGER_REGISTER;
+			}
+
+			return Storage::Register(RegisterClass::Integer, position);
+		}
+
+		if let Some(position) = self.input[VECTOR_STORAGE_TYPE]
+			.iter()
+			.position(|register| *register == key)
+		{
+			return Storage::Register(RegisterClass::Vector, position);
+		}
+
+		Storage::Stack(key.1 as usize)
+	}
+
+	/// Classify a return `VMStorage`, which is always a register
+	pub fn classify_return(&self, storage: Reference) -> RegisterClass {
+		let key = storage_key(storage.extract_class());
+		if self.output[INTEGER_STORAGE_TYPE].contains(&key) {
+			return RegisterClass::Integer;
+		}
+
+		assert!(
+			self.output[VECTOR_STORAGE_TYPE].contains(&key),
+			"return values should only be in registers"
+		);
+		RegisterClass::Vector
+	}
+}
+
+/// The shape of a libffi call equivalent to a set of `VMStorage`s
+///
+/// Every register and stack slot becomes a single 64-bit argument. Integer registers come first,
+/// followed by vector registers, with any gaps filled with dummy values. If anything is passed on
+/// the stack, all argument registers are filled, so that libffi has no choice but to spill the
+/// remaining arguments onto the stack in order.
+pub struct CallLayout {
+	/// Where each Java value goes
+	pub moves: Vec<Storage>,
+	/// The registers a value is returned in
+	pub returns: Vec<RegisterClass>,
+	/// Whether the return registers are copied to/from a return buffer
+	pub buffered_return: bool,
+	/// Whether the value is returned in memory, through [`Storage::IndirectResult`]
+	pub indirect_result: bool,
+	integer_registers: usize,
+	vector_registers: usize,
+	stack_slots: usize,
+}
+
+impl CallLayout {
+	pub fn new(moves: Vec<Storage>, returns: Vec<RegisterClass>, buffered_return: bool) -> Self {
+		let mut integer_registers = 0;
+		let mut vector_registers = 0;
+		let mut stack_slots = 0;
+		let mut indirect_result = false;
+		for storage in &moves {
+			match *storage {
+				Storage::Register(RegisterClass::Integer, position) => {
+					integer_registers = integer_registers.max(position + 1)
+				},
+				Storage::Register(RegisterClass::Vector, position) => {
+					vector_registers = vector_registers.max(position + 1)
+				},
+				Storage::Stack(offset) => {
+					stack_slots = stack_slots.max(offset / STACK_SLOT_SIZE + 1)
+				},
+				Storage::IndirectResult => indirect_result = true,
+				_ => {},
+			}
+		}
+
+		if stack_slots > 0 {
+			integer_registers = arch::INTEGER_ARGUMENT_REGISTERS;
+			vector_registers = arch::VECTOR_ARGUMENT_REGISTERS;
+		}
+
+		Self {
+			moves,
+			returns,
+			buffered_return,
+			indirect_result,
+			integer_registers,
+			vector_registers,
+			stack_slots,
+		}
+	}
+
+	/// The number of libffi arguments
+	pub fn slot_count(&self) -> usize {
+		self.integer_registers + self.vector_registers + self.stack_slots
+	}
+
+	/// The index of the libffi argument for `storage`, if it is passed as one
+	pub fn slot_of(&self, storage: Storage) -> Option<usize> {
+		match storage {
+			Storage::Register(RegisterClass::Integer, position) => Some(position),
+			Storage::Register(RegisterClass::Vector, position) => {
+				Some(self.integer_registers + position)
+			},
+			Storage::Stack(offset) => {
+				Some(self.integer_registers + self.vector_registers + offset / STACK_SLOT_SIZE)
+			},
+			_ => None,
+		}
+	}
+
+	pub fn cif(&self) -> Cif {
+		let arguments = std::iter::repeat_n(Type::u64(), self.integer_registers)
+			.chain(std::iter::repeat_n(Type::f64(), self.vector_registers))
+			.chain(std::iter::repeat_n(Type::u64(), self.stack_slots))
+			.collect::<Vec<_>>();
+
+		let result = if self.indirect_result {
+			// Anything over 16 bytes is returned in memory
+			Type::structure([Type::u64(), Type::u64(), Type::u64()])
+		} else {
+			match (&*self.returns, self.buffered_return) {
+				([], _) => Type::void(),
+				([class], false) => class.ffi_type(),
+				(classes, _) => Type::structure(classes.iter().map(|class| class.ffi_type())),
+			}
+		};
+
+		Cif::new(arguments, result)
+	}
+}
+
+/// The raw bits of a Java value, as they would appear in a register
+pub fn operand_to_bits(ty: &FieldType, value: Operand<Reference>) -> u64 {
+	match ty {
+		FieldType::Float => u64::from(value.expect_float().to_bits()),
+		FieldType::Double => value.expect_double().to_bits(),
+		FieldType::Long => value.expect_long() as u64,
+		FieldType::Object(_) | FieldType::Array(_) => {
+			let object = value.expect_reference();
+			if object.is_null() {
+				return 0;
+			}
+
+			unsafe { object.field_base() as u64 }
+		},
+		FieldType::Void => unreachable!("void is not a value"),
+		_ => i64::from(value.expect_int()) as u64,
+	}
+}
+
+/// Create a Java value from the raw bits of a register
+pub fn bits_to_operand(ty: &FieldType, bits: u64) -> Option<Operand<Reference>> {
+	let operand = match ty {
+		FieldType::Boolean => Operand::Int(i32::from(bits as u8 != 0)),
+		FieldType::Byte => Operand::Int(i32::from(bits as i8)),
+		FieldType::Short => Operand::Int(i32::from(bits as i16)),
+		FieldType::Character => Operand::Int(i32::from(bits as u16)),
+		FieldType::Integer => Operand::Int(bits as i32),
+		FieldType::Long => Operand::Long(bits as i64),
+		FieldType::Float => Operand::Float(f32::from_bits(bits as u32)),
+		FieldType::Double => Operand::Double(f64::from_bits(bits)),
+		FieldType::Void => return None,
+		FieldType::Object(_) | FieldType::Array(_) => {
+			unreachable!("references cannot be passed from native code")
+		},
+	};
+
+	Some(operand)
+}
+
+/// Get the Java parameter and return types of a `java.lang.invoke.MethodType`
+pub fn method_type_signature(method_type: ClassInstanceRef) -> (Vec<FieldType>, FieldType) {
+	fn mirror_type(mirror: Reference) -> FieldType {
+		let mirror = mirror.extract_mirror();
+		if mirror.is_primitive() {
+			return mirror.primitive_target().clone();
+		}
+
+		FieldType::Object((*b"java/lang/Object").into())
+	}
+
+	let parameters = classes::java::lang::invoke::MethodType::ptypes(method_type)
+		.as_slice()
+		.iter()
+		.map(|mirror| mirror_type(*mirror))
+		.collect();
+	let return_type = mirror_type(classes::java::lang::invoke::MethodType::rtype(method_type));
+
+	(parameters, return_type)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::ffi::c_void;
+
+	use libffi::low::CodePtr;
+
+	#[test]
+	fn register_slots() {
+		let layout = CallLayout::new(
+			vec![
+				Storage::TargetAddress,
+				Storage::Register(RegisterClass::Integer, 0),
+				Storage::Register(RegisterClass::Vector, 1),
+				Storage::Register(RegisterClass::Integer, 2),
+			],
+			vec![RegisterClass::Integer],
+			false,
+		);
+
+		// Gaps are filled, and vector registers follow the integer registers
+		assert_eq!(layout.slot_count(), 5);
+		assert_eq!(
+			layout.slot_of(Storage::Register(RegisterClass::Integer, 2)),
+			Some(2)
+		);
+		assert_eq!(
+			layout.slot_of(Storage::Register(RegisterClass::Vector, 1)),
+			Some(4)
+		);
+		assert_eq!(layout.slot_of(Storage::TargetAddress), None);
+	}
+
+	#[test]
+	fn stack_arguments_are_spilled() {
+		extern "C" fn weighted_sum(
+			a0: u64,
+			a1: u64,
+			a2: u64,
+			a3: u64,
+			a4: u64,
+			a5: u64,
+			a6: u64,
+			a7: u64,
+			a8: u64,
+			a9: u64,
+		) -> u64 {
+			[a0, a1, a2, a3, a4, a5, a6, a7, a8, a9]
+				.into_iter()
+				.zip(1..)
+				.map(|(value, weight)| value * weight)
+				.sum()
+		}
+
+		let moves = (0..10)
+			.map(|index| {
+				if index < arch::INTEGER_ARGUMENT_REGISTERS {
+					Storage::Register(RegisterClass::Integer, index)
+				} else {
+					Storage::Stack((index - arch::INTEGER_ARGUMENT_REGISTERS) * STACK_SLOT_SIZE)
+				}
+			})
+			.collect::<Vec<_>>();
+		let layout = CallLayout::new(moves.clone(), vec![RegisterClass::Integer], false);
+
+		let mut slots = vec![0u64; layout.slot_count()];
+		for (value, storage) in (100..).zip(&moves) {
+			slots[layout.slot_of(*storage).unwrap()] = value;
+		}
+
+		let mut args = slots
+			.iter_mut()
+			.map(|slot| std::ptr::from_mut(slot).cast::<c_void>())
+			.collect::<Vec<_>>();
+		let mut result = 0u64;
+		unsafe {
+			libffi::low::call_return_into(
+				layout.cif().as_raw_ptr(),
+				CodePtr::from_ptr(weighted_sum as *const c_void),
+				args.as_mut_ptr(),
+				std::ptr::from_mut(&mut result).cast(),
+			);
+		}
+
+		let expected = (100..110)
+			.zip(1..)
+			.map(|(value, weight)| value * weight)
+			.sum::<u64>();
+		assert_eq!(result, expected);
+	}
+}
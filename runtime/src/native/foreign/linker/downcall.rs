@@ -0,0 +1,297 @@
+use super::abi::{Abi, CallLayout, MAX_RETURN_REGISTERS, Storage};
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::frame::Frame;
+
+use std::ffi::c_void;
+use std::ptr;
+
+use classfile::FieldType;
+use instructions::{Operand, StackLike};
+use libffi::low::CodePtr;
+use libffi::middle::Cif;
+
+/// `jdk.internal.foreign.abi.CapturableState#ERRNO` mask
+const CAPTURE_ERRNO: i32 = 1 << 2;
+
+/// A call from Java into native code, as set up by `NativeEntryPoint#makeDowncallStub`
+///
+/// The address of the stub is stored in `NativeEntryPoint#downcallStubAddress`, and invoked through
+/// `MethodHandle#linkToNative`.
+pub struct DowncallStub {
+	layout: CallLayout,
+	cif: Cif,
+	parameters: Vec<FieldType>,
+	return_type: FieldType,
+	captured_state_mask: i32,
+	needs_transition: bool,
+}
+
+impl DowncallStub {
+	pub fn new(
+		abi: &Abi,
+		(parameters, return_type): (Vec<FieldType>, FieldType),
+		arg_moves: &[Reference],
+		ret_moves: &[Reference],
+		needs_return_buffer: bool,
+		captured_state_mask: i32,
+		needs_transition: bool,
+	) -> Self {
+		let moves = arg_moves
+			.iter()
+			.map(|storage| abi.classify_argument(*storage))
+			.collect();
+		let returns = ret_moves
+			.iter()
+			.map(|storage| abi.classify_return(*storage))
+			.collect();
+
+		let layout = CallLayout::new(moves, returns, needs_return_buffer);
+		let cif = layout.cif();
+		Self {
+			layout,
+			cif,
+			parameters,
+			return_type,
+			captured_state_mask,
+			needs_transition,
+		}
+	}
+
+	/// Leak the stub, returning its address
+	pub fn into_raw(self) -> usize {
+		Box::into_raw(Box::new(self)) as usize
+	}
+
+	/// Release a stub created with [`DowncallStub::into_raw()`]
+	///
+	/// # Safety
+	///
+	/// `address` must have come from [`DowncallStub::into_raw()`], and not already be freed.
+	pub unsafe fn free(address: usize) {
+		drop(unsafe { Box::from_raw(address as *mut DowncallStub) });
+	}
+
+	/// Call the target function with `args`
+	///
+	/// If the stub needs a thread state transition, `thread` is marked as being in native code for
+	/// the duration of the call.
+	fn invoke(
+		&self,
+		thread: Option<&'static JavaThread>,
+		args: Vec<Operand<Reference>>,
+	) -> Option<Operand<Reference>> {
+		let mut slots = vec![0u64; self.layout.slot_count()];
+		let mut target = ptr::null_mut::<c_void>();
+		let mut return_buffer = ptr::null_mut::<u8>();
+		let mut captured_state = ptr::null_mut::<i32>();
+		let mut indirect_result = ptr::null_mut::<c_void>();
+
+		let mut previous_slot = None;
+		for ((storage, ty), arg) in self.layout.moves.iter().zip(&self.parameters).zip(args) {
+			let value = super::abi::operand_to_bits(ty, arg);
+			match *storage {
+				Storage::TargetAddress => target = value as *mut c_void,
+				Storage::ReturnBuffer => return_buffer = value as *mut u8,
+				Storage::CapturedState => captured_state = value as *mut i32,
+				Storage::IndirectResult => indirect_result = value as *mut c_void,
+				Storage::Implicit => {},
+				Storage::HeapOffset => {
+					let slot: usize =
+						previous_slot.expect("heap offsets must follow a base object");
+					slots[slot] = slots[slot].wrapping_add(value);
+				},
+				Storage::Register(..) | Storage::Stack(_) => {
+					let slot = self
+						.layout
+						.slot_of(*storage)
+						.expect("should be a libffi argument");
+					slots[slot] = value;
+					previous_slot = Some(slot);
+				},
+			}
+		}
+
+		let mut arg_ptrs = slots
+			.iter_mut()
+			.map(|slot| ptr::from_mut(slot).cast::<c_void>())
+			.collect::<Vec<_>>();
+
+		let mut registers = [0u64; MAX_RETURN_REGISTERS];
+		let result = if self.layout.indirect_result {
+			indirect_result
+		} else {
+			registers.as_mut_ptr().cast::<c_void>()
+		};
+
+		let errno;
+		{
+			// A critical call can't block or call back into Java, so there's no need to let other
+			// threads know that we're leaving
+			let _safe_region = thread.map(JavaThread::enter_safe_region);
+			unsafe {
+				libffi::low::call_return_into(
+					self.cif.as_raw_ptr(),
+					CodePtr::from_ptr(target),
+					arg_ptrs.as_mut_ptr(),
+					result,
+				);
+			}
+			errno = std::io::Error::last_os_error().raw_os_error();
+		}
+
+		if self.captured_state_mask & CAPTURE_ERRNO != 0 {
+			unsafe { captured_state.write(errno.unwrap_or(0)) };
+		}
+
+		if self.layout.buffered_return {
+			let mut offset = 0;
+			for (class, bits) in self.layout.returns.iter().zip(registers) {
+				unsafe {
+					return_buffer
+						.add(offset)
+						.cast::<u64>()
+						.write_unaligned(bits)
+				};
+				offset += class.buffer_size();
+			}
+
+			return None;
+		}
+
+		super::abi::bits_to_operand(&self.return_type, registers[0])
+	}
+}
+
+/// `MethodHandle#linkToNative`, with the `NativeEntryPoint` already popped
+pub fn invoke(frame: &mut Frame, stub_address: usize) {
+	let stub = unsafe { &*(stub_address as *const DowncallStub) };
+
+	let args = frame.popn(stub.parameters.len());
+	let thread = stub.needs_transition.then(|| frame.thread());
+	if let Some(ret) = stub.invoke(thread, args) {
+		frame.push_op(ret);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::native::foreign::linker::abi::RegisterClass;
+
+	fn stub(
+		moves: Vec<Storage>,
+		returns: Vec<RegisterClass>,
+		(parameters, return_type): (Vec<FieldType>, FieldType),
+		captured_state_mask: i32,
+	) -> DowncallStub {
+		let layout = CallLayout::new(moves, returns, false);
+		let cif = layout.cif();
+		DowncallStub {
+			layout,
+			cif,
+			parameters,
+			return_type,
+			captured_state_mask,
+			needs_transition: false,
+		}
+	}
+
+	#[test]
+	fn mixed_registers() {
+		extern "C" fn mix(a: i32, b: f32, c: i64, d: f64) -> f64 {
+			f64::from(a) + f64::from(b) + c as f64 + d
+		}
+
+		// Integer and vector registers are numbered separately, regardless of the parameter order
+		let stub = stub(
+			vec![
+				Storage::TargetAddress,
+				Storage::Register(RegisterClass::Integer, 0),
+				Storage::Register(RegisterClass::Vector, 0),
+				Storage::Register(RegisterClass::Integer, 1),
+				Storage::Register(RegisterClass::Vector, 1),
+			],
+			vec![RegisterClass::Vector],
+			(
+				vec![
+					FieldType::Long,
+					FieldType::Integer,
+					FieldType::Float,
+					FieldType::Long,
+					FieldType::Double,
+				],
+				FieldType::Double,
+			),
+			0,
+		);
+
+		let ret = stub.invoke(
+			None,
+			vec![
+				Operand::Long(mix as usize as i64),
+				Operand::Int(-2),
+				Operand::Float(0.5),
+				Operand::Long(1 << 40),
+				Operand::Double(0.25),
+			],
+		);
+		assert_eq!(
+			ret,
+			Some(Operand::Double(-2.0 + 0.5 + (1u64 << 40) as f64 + 0.25))
+		);
+	}
+
+	#[test]
+	fn narrow_return() {
+		extern "C" fn negate(value: i8) -> i8 {
+			-value
+		}
+
+		let stub = stub(
+			vec![
+				Storage::TargetAddress,
+				Storage::Register(RegisterClass::Integer, 0),
+			],
+			vec![RegisterClass::Integer],
+			(vec![FieldType::Long, FieldType::Byte], FieldType::Byte),
+			0,
+		);
+
+		// Only the low byte of the return register is defined, and it has to be sign extended
+		let ret = stub.invoke(
+			None,
+			vec![Operand::Long(negate as usize as i64), Operand::Int(5)],
+		);
+		assert_eq!(ret, Some(Operand::Int(-5)));
+	}
+
+	#[test]
+	fn captured_errno() {
+		let stub = stub(
+			vec![
+				Storage::TargetAddress,
+				Storage::CapturedState,
+				Storage::Register(RegisterClass::Integer, 0),
+			],
+			vec![RegisterClass::Integer],
+			(
+				vec![FieldType::Long, FieldType::Long, FieldType::Integer],
+				FieldType::Integer,
+			),
+			CAPTURE_ERRNO,
+		);
+
+		let mut errno = 0i32;
+		let ret = stub.invoke(
+			None,
+			vec![
+				Operand::Long(libc::close as usize as i64),
+				Operand::Long(ptr::from_mut(&mut errno) as i64),
+				Operand::Int(-1),
+			],
+		);
+		assert_eq!(ret, Some(Operand::Int(-1)));
+		assert_eq!(errno, libc::EBADF);
+	}
+}
@@ -0,0 +1,76 @@
+use crate::classes;
+use crate::objects::reference::Reference;
+use crate::thread::exceptions::Throws;
+use crate::thread::frame::Frame;
+use abi::Abi;
+use downcall::DowncallStub;
+
+mod abi;
+mod downcall;
+mod upcall;
+
+/// Create a downcall stub, for `NativeEntryPoint#makeDowncallStub`
+pub fn make_downcall_stub(
+	method_type: Reference,
+	abi: Reference,
+	arg_moves: Reference,
+	ret_moves: Reference,
+	needs_return_buffer: bool,
+	captured_state_mask: i32,
+	needs_transition: bool,
+) -> Throws<usize> {
+	classes::jdk::internal::foreign::abi::initialize()?;
+
+	let stub = DowncallStub::new(
+		&Abi::new(abi.extract_class()),
+		abi::method_type_signature(method_type.extract_class()),
+		arg_moves.extract_object_array().as_slice(),
+		ret_moves.extract_object_array().as_slice(),
+		needs_return_buffer,
+		captured_state_mask,
+		needs_transition,
+	);
+	Throws::Ok(stub.into_raw())
+}
+
+/// Release a stub created by [`make_downcall_stub()`]
+///
+/// # Safety
+///
+/// `address` must be a live downcall stub, which is never used again.
+pub unsafe fn free_downcall_stub(address: usize) {
+	unsafe { DowncallStub::free(address) }
+}
+
+/// Call the downcall stub at `address`, for `MethodHandle#linkToNative`
+pub fn link_to_native(frame: &mut Frame, address: usize) {
+	downcall::invoke(frame, address)
+}
+
+/// Create an upcall stub, for `UpcallLinker#makeUpcallStub`
+pub fn make_upcall_stub(
+	method_handle: Reference,
+	abi: Reference,
+	call_regs: Reference,
+	needs_return_buffer: bool,
+	return_buffer_size: usize,
+) -> Throws<usize> {
+	use classes::jdk::internal::foreign::abi::UpcallLinker::CallRegs;
+
+	classes::jdk::internal::foreign::abi::initialize()?;
+
+	let call_regs = call_regs.extract_class();
+	upcall::make(
+		&Abi::new(abi.extract_class()),
+		method_handle,
+		CallRegs::argRegs(call_regs).as_slice(),
+		CallRegs::retRegs(call_regs).as_slice(),
+		needs_return_buffer,
+		return_buffer_size,
+	)
+}
+
+/// Release a stub created by [`make_upcall_stub()`], returning `false` if there is no such stub
+pub fn free_upcall_stub(address: usize) -> bool {
+	upcall::free(address)
+}
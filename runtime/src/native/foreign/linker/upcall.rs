@@ -0,0 +1,314 @@
+use super::abi::{Abi, CallLayout, Storage};
+use crate::objects::method::Method;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+use crate::{classes, java_call};
+
+use std::collections::BTreeMap;
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::Mutex;
+
+use classfile::FieldType;
+use common::sync::ForceSendSync;
+use instructions::Operand;
+use libffi::low::ffi_cif;
+use libffi::middle::Closure;
+
+/// Live upcall stubs, keyed by their code address
+static UPCALL_STUBS: Mutex<BTreeMap<usize, ForceSendSync<UpcallStub>>> =
+	Mutex::new(BTreeMap::new());
+
+/// Where an upcall ends up, once its arguments have been converted to Java values
+trait UpcallReceiver {
+	fn call(&self, args: Vec<Operand<Reference>>) -> Option<Operand<Reference>>;
+}
+
+/// The method handle given to `UpcallLinker#makeUpcallStub`
+struct MethodHandleReceiver {
+	method_handle: Reference,
+	/// The method `method_handle` invokes, as if through `MethodHandle#invokeBasic`
+	method: &'static Method,
+}
+
+impl UpcallReceiver for MethodHandleReceiver {
+	fn call(&self, args: Vec<Operand<Reference>>) -> Option<Operand<Reference>> {
+		let thread = current_thread();
+
+		let stack = thread.stack();
+		stack.push_op(Operand::Reference(self.method_handle));
+		for arg in args {
+			stack.push_op(arg);
+		}
+
+		let ret = java_call!(thread, self.method);
+		if let Some(exception) = thread.take_pending_exception() {
+			eprintln!("Unrecoverable uncaught exception encountered. The VM will now exit");
+			classes::java::lang::Throwable::print_stack_trace_without_java_system(
+				exception, thread,
+			);
+			std::process::exit(1);
+		}
+
+		ret
+	}
+}
+
+/// Everything needed to call into Java once native code calls the stub
+struct UpcallTarget<R> {
+	receiver: R,
+	layout: CallLayout,
+	parameters: Vec<FieldType>,
+	return_type: FieldType,
+	return_buffer_size: usize,
+}
+
+impl<R: UpcallReceiver> UpcallTarget<R> {
+	/// Create the libffi closure that native code calls
+	fn closure(&'static self) -> Closure<'static> {
+		Closure::new(self.layout.cif(), upcall::<R>, self)
+	}
+}
+
+/// A call from native code into Java, as set up by `UpcallLinker#makeUpcallStub`
+struct UpcallStub {
+	closure: Closure<'static>,
+	target: *mut UpcallTarget<MethodHandleReceiver>,
+}
+
+/// Create an upcall stub, returning the address native code can call
+pub fn make(
+	abi: &Abi,
+	method_handle: Reference,
+	arg_regs: &[Reference],
+	ret_regs: &[Reference],
+	needs_return_buffer: bool,
+	return_buffer_size: usize,
+) -> Throws<usize> {
+	let handle = method_handle.extract_class();
+	let form = classes::java::lang::invoke::MethodHandle::form(handle);
+	let vmentry = classes::java::lang::invoke::LambdaForm::vmentry(form);
+	let method = classes::java::lang::invoke::MemberName::target_method(vmentry)?;
+
+	let (parameters, return_type) =
+		super::abi::method_type_signature(classes::java::lang::invoke::MethodHandle::type_(handle));
+
+	let moves = arg_regs
+		.iter()
+		.map(|storage| abi.classify_argument(*storage))
+		.collect();
+	let returns = ret_regs
+		.iter()
+		.map(|storage| abi.classify_return(*storage))
+		.collect();
+	let layout = CallLayout::new(moves, returns, needs_return_buffer);
+
+	let target = Box::leak(Box::new(UpcallTarget {
+		receiver: MethodHandleReceiver {
+			method_handle,
+			method,
+		},
+		layout,
+		parameters,
+		return_type,
+		return_buffer_size,
+	}));
+
+	let target_ptr = ptr::from_mut(target);
+	let closure = target.closure();
+	let address = *closure.code_ptr() as usize;
+
+	UPCALL_STUBS.lock().unwrap().insert(
+		address,
+		ForceSendSync(UpcallStub {
+			closure,
+			target: target_ptr,
+		}),
+	);
+
+	Throws::Ok(address)
+}
+
+/// Release an upcall stub created with [`make()`]
+///
+/// Returns `false` if `address` is not a live upcall stub.
+pub fn free(address: usize) -> bool {
+	let Some(ForceSendSync(stub)) = UPCALL_STUBS.lock().unwrap().remove(&address) else {
+		return false;
+	};
+
+	let UpcallStub { closure, target } = stub;
+	drop(closure);
+	drop(unsafe { Box::from_raw(target) });
+	true
+}
+
+/// The current thread, attaching it as a daemon if it was created outside the VM
+fn current_thread() -> &'static JavaThread {
+	if let Some(thread) = JavaThread::current_opt() {
+		return thread;
+	}
+
+	let vm = unsafe { crate::native::jni::invocation_api::main_java_vm() };
+	if vm.attach_current_thread_as_daemon().is_err() {
+		eprintln!("Failed to attach a foreign thread for an upcall");
+		std::process::abort();
+	}
+
+	JavaThread::current()
+}
+
+unsafe extern "C" fn upcall<R: UpcallReceiver>(
+	_cif: &ffi_cif,
+	result: &mut u64,
+	args: *const *const c_void,
+	target: &UpcallTarget<R>,
+) {
+	let result = ptr::from_mut(result);
+
+	let mut return_buffer = vec![0u64; target.return_buffer_size.div_ceil(8)];
+
+	let mut java_args = Vec::with_capacity(target.parameters.len());
+	for (storage, ty) in target.layout.moves.iter().zip(&target.parameters) {
+		let bits = match *storage {
+			Storage::ReturnBuffer => return_buffer.as_mut_ptr() as u64,
+			Storage::IndirectResult => result as u64,
+			Storage::Register(..) | Storage::Stack(_) => {
+				let slot = target
+					.layout
+					.slot_of(*storage)
+					.expect("should be a libffi argument");
+				unsafe { (*args.add(slot)).cast::<u64>().read() }
+			},
+			Storage::Implicit => 0,
+			Storage::TargetAddress | Storage::CapturedState | Storage::HeapOffset => {
+				unreachable!("only valid for downcalls")
+			},
+		};
+
+		java_args.push(super::abi::bits_to_operand(ty, bits).expect("parameters cannot be void"));
+	}
+
+	let ret = target.receiver.call(java_args);
+
+	if target.layout.buffered_return {
+		let buffer = return_buffer.as_ptr().cast::<u8>();
+		let mut offset = 0;
+		for (index, class) in target.layout.returns.iter().enumerate() {
+			unsafe {
+				let bits = buffer.add(offset).cast::<u64>().read_unaligned();
+				result.add(index).write(bits);
+			}
+			offset += class.buffer_size();
+		}
+
+		return;
+	}
+
+	if let Some(ret) = ret {
+		unsafe { result.write(super::abi::operand_to_bits(&target.return_type, ret)) };
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::native::foreign::linker::abi::RegisterClass;
+
+	use std::ffi::c_int;
+
+	impl<F> UpcallReceiver for F
+	where
+		F: Fn(Vec<Operand<Reference>>) -> Option<Operand<Reference>>,
+	{
+		fn call(&self, args: Vec<Operand<Reference>>) -> Option<Operand<Reference>> {
+			self(args)
+		}
+	}
+
+	fn target<R>(
+		receiver: R,
+		moves: Vec<Storage>,
+		returns: Vec<RegisterClass>,
+		(parameters, return_type): (Vec<FieldType>, FieldType),
+	) -> &'static UpcallTarget<R> {
+		Box::leak(Box::new(UpcallTarget {
+			receiver,
+			layout: CallLayout::new(moves, returns, false),
+			parameters,
+			return_type,
+			return_buffer_size: 0,
+		}))
+	}
+
+	#[test]
+	fn mixed_registers() {
+		let target = target(
+			|args: Vec<Operand<Reference>>| {
+				let [a, b, c] = &args[..] else { unreachable!() };
+				Some(Operand::Double(
+					f64::from(a.expect_int()) + b.expect_double() + c.expect_long() as f64,
+				))
+			},
+			vec![
+				Storage::Register(RegisterClass::Integer, 0),
+				Storage::Register(RegisterClass::Vector, 0),
+				Storage::Register(RegisterClass::Integer, 1),
+			],
+			vec![RegisterClass::Vector],
+			(
+				vec![FieldType::Integer, FieldType::Double, FieldType::Long],
+				FieldType::Double,
+			),
+		);
+
+		let closure = target.closure();
+		let stub: extern "C" fn(i32, f64, i64) -> f64 =
+			unsafe { std::mem::transmute(*closure.code_ptr()) };
+		assert_eq!(stub(-3, 0.5, 1 << 40), -3.0 + 0.5 + (1u64 << 40) as f64);
+	}
+
+	#[test]
+	fn called_from_native_code() {
+		// `qsort()` calls the comparator with pointers to the elements, which Java sees as `long`s
+		let target = target(
+			|args: Vec<Operand<Reference>>| {
+				let [a, b] = &args[..] else { unreachable!() };
+				let (a, b) = unsafe {
+					(
+						*(a.expect_long() as *const i32),
+						*(b.expect_long() as *const i32),
+					)
+				};
+				Some(Operand::Int(a.cmp(&b) as i32))
+			},
+			vec![
+				Storage::Register(RegisterClass::Integer, 0),
+				Storage::Register(RegisterClass::Integer, 1),
+			],
+			vec![RegisterClass::Integer],
+			(vec![FieldType::Long, FieldType::Long], FieldType::Integer),
+		);
+
+		let closure = target.closure();
+		let compare: unsafe extern "C" fn(*const c_void, *const c_void) -> c_int =
+			unsafe { std::mem::transmute(*closure.code_ptr()) };
+
+		let mut values = [5, -1, 42, 0, -7, 3];
+		unsafe {
+			libc::qsort(
+				values.as_mut_ptr().cast(),
+				values.len(),
+				size_of::<i32>(),
+				Some(compare),
+			);
+		}
+		assert_eq!(values, [-7, -1, 0, 3, 5, 42]);
+	}
+
+	#[test]
+	fn free_unknown_stub() {
+		assert!(!free(0));
+	}
+}
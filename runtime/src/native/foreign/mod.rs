@@ -0,0 +1,79 @@
+//! Downcall and upcall stubs for `java.lang.foreign.Linker`
+//!
+//! The JDK computes the calling convention itself, and hands us the exact register or stack slot
+//! for every value. Rather than generating machine code, the stubs describe an equivalent libffi
+//! call. See `linker::abi::CallLayout` for how the two are mapped.
+//!
+//! Only SysV x86-64 and AArch64 (Linux) are supported. Everywhere else, `jdk.internal.foreign`
+//! falls back to its own libffi-based linker, and none of the functions here are ever called.
+
+#[cfg(all(
+	feature = "libffi",
+	any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod linker;
+#[cfg(all(
+	feature = "libffi",
+	any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+pub use linker::*;
+#[cfg(not(all(
+	feature = "libffi",
+	any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+pub use unsupported::*;
+
+/// Whether the linker supports the current platform
+pub fn is_supported() -> bool {
+	cfg!(all(
+		feature = "libffi",
+		any(
+			all(target_arch = "x86_64", unix, not(target_os = "macos")),
+			all(target_arch = "aarch64", target_os = "linux")
+		)
+	))
+}
+
+#[cfg(not(all(
+	feature = "libffi",
+	any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+mod unsupported {
+	use crate::objects::reference::Reference;
+	use crate::thread::exceptions::{Throws, throw};
+	use crate::thread::frame::Frame;
+
+	pub fn make_downcall_stub(
+		_method_type: Reference,
+		_abi: Reference,
+		_arg_moves: Reference,
+		_ret_moves: Reference,
+		_needs_return_buffer: bool,
+		_captured_state_mask: i32,
+		_needs_transition: bool,
+	) -> Throws<usize> {
+		throw!(@DEFER InternalError, "Foreign linker is not supported on this platform");
+	}
+
+	pub unsafe fn free_downcall_stub(_address: usize) {
+		unreachable!("no downcall stubs can exist")
+	}
+
+	pub fn link_to_native(_frame: &mut Frame, _address: usize) {
+		unreachable!("no downcall stubs can exist")
+	}
+
+	pub fn make_upcall_stub(
+		_method_handle: Reference,
+		_abi: Reference,
+		_call_regs: Reference,
+		_needs_return_buffer: bool,
+		_return_buffer_size: usize,
+	) -> Throws<usize> {
+		throw!(@DEFER InternalError, "Foreign linker is not supported on this platform");
+	}
+
+	pub fn free_upcall_stub(_address: usize) -> bool {
+		false
+	}
+}
@@ -0,0 +1,315 @@
+//! `java.lang.System#arraycopy` and `java.util.Arrays#fill`
+
+use crate::objects::instance::array::Array;
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+
+use common::int_types::s4;
+use instructions::Operand;
+
+/// The implementation of `System#arraycopy`, shared with `JVM_ArrayCopy`
+pub(crate) fn array_copy(
+	src: Reference,
+	src_pos: s4,
+	dst: Reference,
+	dst_pos: s4,
+	length: s4,
+) -> Throws<()> {
+	unsafe fn do_copy<T: Array>(src: &T, src_pos: usize, dest: &T, dest_pos: usize, length: usize) {
+		unsafe {
+			src.copy_into(src_pos, dest, dest_pos, length);
+		}
+	}
+
+	unsafe fn do_copy_within<T: Array>(src: &T, src_pos: usize, dest_pos: usize, length: usize) {
+		unsafe {
+			src.copy_within(src_pos, dest_pos, length);
+		}
+	}
+
+	if src.is_null() || dst.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let src_class = src.extract_instance_class();
+	let dst_class = dst.extract_instance_class();
+	if !src_class.is_array() {
+		throw!(@DEFER ArrayStoreException, "arraycopy: source type {} is not an array", src_class.external_name());
+	}
+	if !dst_class.is_array() {
+		throw!(@DEFER ArrayStoreException, "arraycopy: destination type {} is not an array", dst_class.external_name());
+	}
+
+	// Primitive arrays can only be copied into arrays of the exact same primitive type
+	if src.is_object_array() != dst.is_object_array()
+		|| (src.is_primitive_array()
+			&& src.extract_primitive_array().type_code()
+				!= dst.extract_primitive_array().type_code())
+	{
+		throw!(@DEFER ArrayStoreException, "arraycopy: type mismatch: can not copy {} into {}", src_class.external_name(), dst_class.external_name());
+	}
+
+	let src_len = src.array_length()?;
+	let dest_len = dst.array_length()?;
+
+	if src_pos < 0
+		|| dst_pos < 0
+		|| length < 0
+		|| src_pos as usize + length as usize > src_len
+		|| dst_pos as usize + length as usize > dest_len
+	{
+		throw!(@DEFER IndexOutOfBoundsException);
+	}
+
+	if length == 0 {
+		return Throws::Ok(());
+	}
+
+	if src == dst {
+		if src.is_object_array() {
+			unsafe {
+				do_copy_within(
+					&src.extract_object_array(),
+					src_pos as usize,
+					dst_pos as usize,
+					length as usize,
+				)
+			}
+		} else {
+			unsafe {
+				do_copy_within(
+					&src.extract_primitive_array(),
+					src_pos as usize,
+					dst_pos as usize,
+					length as usize,
+				)
+			}
+		}
+
+		return Throws::Ok(());
+	}
+
+	if src.is_object_array() {
+		// Every element of the source is assignable to the destination, nothing to check
+		if src_class.can_cast_to(dst_class) {
+			unsafe {
+				do_copy(
+					&src.extract_object_array(),
+					src_pos as usize,
+					&dst.extract_object_array(),
+					dst_pos as usize,
+					length as usize,
+				)
+			}
+
+			return Throws::Ok(());
+		}
+
+		// Otherwise each element needs to be checked as it's stored. The elements preceding the
+		// first mismatch are still copied.
+		// It's impossible to get a reference to an unloaded class
+		let dst_component = dst_class
+			.loader()
+			.lookup_class(dst_class.array_component_name())
+			.unwrap();
+
+		let src_array = src.extract_object_array();
+		let dst_array = dst.extract_object_array();
+		for i in 0..length as usize {
+			// SAFETY: Bounds checked above
+			let value = unsafe { src_array.get_unchecked(src_pos as usize + i) };
+			if !value.is_null() && !value.is_instance_of(dst_component) {
+				throw!(@DEFER ArrayStoreException, "arraycopy: element type mismatch: can not cast one of the elements of {} to the type of the destination array, {}", src_class.external_name(), dst_component.external_name());
+			}
+
+			unsafe {
+				dst_array.store_unchecked(dst_pos as usize + i, value);
+			}
+		}
+
+		return Throws::Ok(());
+	}
+
+	unsafe {
+		do_copy(
+			&src.extract_primitive_array(),
+			src_pos as usize,
+			&dst.extract_primitive_array(),
+			dst_pos as usize,
+			length as usize,
+		)
+	}
+
+	Throws::Ok(())
+}
+
+pub(super) fn arraycopy(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [src, src_pos, dst, dst_pos, length] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	array_copy(
+		src.expect_reference(),
+		src_pos.expect_int(),
+		dst.expect_reference(),
+		dst_pos.expect_int(),
+		length.expect_int(),
+	)?;
+
+	Throws::Ok(None)
+}
+
+/// `Arrays.fill(T[] a, T val)` for every primitive type
+pub(super) fn fill(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [array, value] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let array = array.expect_reference();
+	if array.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let array = array.extract_primitive_array();
+	for index in 0..array.len() {
+		// SAFETY: The index is within bounds, and the value type is the array's component type
+		unsafe {
+			array.store_unchecked(index, *value);
+		}
+	}
+
+	Throws::Ok(None)
+}
+
+/// `Arrays.fill(T[] a, int fromIndex, int toIndex, T val)` for every primitive type
+pub(super) fn fill_range(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [array, from_index, to_index, value] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let array = array.expect_reference();
+	if array.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let array = array.extract_primitive_array();
+	let from_index = from_index.expect_int();
+	let to_index = to_index.expect_int();
+
+	// Same checks as `Arrays.rangeCheck()`
+	if from_index > to_index {
+		throw!(@DEFER IllegalArgumentException, "fromIndex({}) > toIndex({})", from_index, to_index);
+	}
+
+	if from_index < 0 {
+		throw!(@DEFER ArrayIndexOutOfBoundsException, "Array index out of range: {}", from_index);
+	}
+
+	if to_index as usize > array.len() {
+		throw!(@DEFER ArrayIndexOutOfBoundsException, "Array index out of range: {}", to_index);
+	}
+
+	for index in from_index as usize..to_index as usize {
+		// SAFETY: The range was just checked, and the value type is the array's component type
+		unsafe {
+			array.store_unchecked(index, *value);
+		}
+	}
+
+	Throws::Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::globals::classes;
+	use crate::native::java::lang::String::StringInterner;
+	use crate::objects::instance::array::{ObjectArrayInstance, PrimitiveArrayInstance};
+	use crate::test_utils::init_basic_shared_runtime;
+	use crate::thread::exceptions::ExceptionKind;
+
+	use jni::sys::jint;
+
+	fn assert_array_store_exception(result: Throws<()>) {
+		match result {
+			Throws::Exception(e) => assert_eq!(e.kind(), ExceptionKind::ArrayStoreException),
+			Throws::Ok(()) => panic!("arraycopy should have thrown an ArrayStoreException"),
+		}
+	}
+
+	#[test]
+	fn arraycopy_primitive_mismatch() {
+		init_basic_shared_runtime();
+
+		let ints = Reference::array(
+			PrimitiveArrayInstance::new::<jint>(&[1, 2, 3]).expect("should be a valid array"),
+		);
+		let objects = Reference::object_array(
+			ObjectArrayInstance::new(3, classes::java_lang_Object())
+				.expect("should be a valid array"),
+		);
+
+		assert_array_store_exception(array_copy(ints.clone(), 0, objects.clone(), 0, 3));
+		assert_array_store_exception(array_copy(objects, 0, ints, 0, 3));
+	}
+
+	#[test]
+	fn arraycopy_element_mismatch() {
+		init_basic_shared_runtime();
+
+		let objects = ObjectArrayInstance::new(3, classes::java_lang_Object())
+			.expect("should be a valid array");
+		let strings = ObjectArrayInstance::new(3, classes::java_lang_String())
+			.expect("should be a valid array");
+
+		// The mirror in the middle is not a String, so the copy stops there
+		let string =
+			Reference::class(StringInterner::intern("foo").expect("interning should work"));
+		objects.store(0, string.clone()).expect("store should work");
+		objects
+			.store(1, Reference::mirror(classes::java_lang_String().mirror()))
+			.expect("store should work");
+
+		assert_array_store_exception(array_copy(
+			Reference::object_array(objects),
+			0,
+			Reference::object_array(strings),
+			0,
+			3,
+		));
+
+		// Only the elements before the mismatch are copied
+		assert_eq!(strings.array_get(0).expect("get should work"), string);
+		assert_eq!(
+			strings.array_get(1).expect("get should work"),
+			Reference::null()
+		);
+		assert_eq!(
+			strings.array_get(2).expect("get should work"),
+			Reference::null()
+		);
+
+		// The other direction can never fail
+		let objects = ObjectArrayInstance::new(3, classes::java_lang_Object())
+			.expect("should be a valid array");
+		array_copy(
+			Reference::object_array(strings),
+			0,
+			Reference::object_array(objects),
+			0,
+			3,
+		)
+		.expect("String[] should be assignable to Object[]");
+	}
+}
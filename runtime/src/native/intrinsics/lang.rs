@@ -0,0 +1,29 @@
+//! `java.lang.Object` and `java.lang.Thread`
+
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+
+use instructions::Operand;
+
+pub(super) fn hash_code(
+	thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [this] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	// This will only calculate a hash if one isn't already cached in the header
+	let hash = this.expect_reference().hash(thread);
+	Throws::Ok(Some(Operand::Int(hash)))
+}
+
+pub(super) fn current_thread(
+	thread: &'static JavaThread,
+	_args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let obj = thread.obj().expect("current thread should exist");
+	Throws::Ok(Some(Operand::Reference(obj)))
+}
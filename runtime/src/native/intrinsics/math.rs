@@ -0,0 +1,179 @@
+//! `java.lang.Math` and `java.lang.Integer`
+
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+
+use instructions::Operand;
+
+/// Define intrinsics whose arguments all share a type, and that never throw
+macro_rules! simple_intrinsics {
+	($($name:ident($($arg:ident),+): $expect:ident -> $variant:ident => $body:expr;)+) => {
+		$(
+			pub(super) fn $name(
+				_thread: &'static JavaThread,
+				args: &[Operand<Reference>],
+			) -> Throws<Option<Operand<Reference>>> {
+				let [$($arg),+] = args else {
+					unreachable!("wrong number of arguments for intrinsic");
+				};
+				$(let $arg = $arg.$expect();)+
+
+				Throws::Ok(Some(Operand::$variant($body)))
+			}
+		)+
+	};
+}
+
+/// Define the `*Exact` intrinsics, which throw an `ArithmeticException` on overflow
+macro_rules! exact_intrinsics {
+	($($name:ident($($arg:ident),+): $expect:ident -> $variant:ident, $message:literal => $body:expr;)+) => {
+		$(
+			pub(super) fn $name(
+				_thread: &'static JavaThread,
+				args: &[Operand<Reference>],
+			) -> Throws<Option<Operand<Reference>>> {
+				let [$($arg),+] = args else {
+					unreachable!("wrong number of arguments for intrinsic");
+				};
+				$(let $arg = $arg.$expect();)+
+
+				match $body {
+					Some(result) => Throws::Ok(Some(Operand::$variant(result))),
+					None => throw!(@DEFER ArithmeticException, $message),
+				}
+			}
+		)+
+	};
+}
+
+// `Math.max` and `Math.min` for floating point values, which differ from `f32::max` and friends
+// in that NaN is contagious and `-0.0` is considered smaller than `0.0`.
+macro_rules! java_float_max_min {
+	($($max:ident, $min:ident: $ty:ty;)+) => {
+		$(
+			fn $max(a: $ty, b: $ty) -> $ty {
+				if a.is_nan() {
+					return a;
+				}
+
+				if a == 0.0 && b == 0.0 && a.is_sign_negative() {
+					return b;
+				}
+
+				if a >= b { a } else { b }
+			}
+
+			fn $min(a: $ty, b: $ty) -> $ty {
+				if a.is_nan() {
+					return a;
+				}
+
+				if a == 0.0 && b == 0.0 && b.is_sign_negative() {
+					return b;
+				}
+
+				if a <= b { a } else { b }
+			}
+		)+
+	};
+}
+
+java_float_max_min! {
+	java_max_f32, java_min_f32: f32;
+	java_max_f64, java_min_f64: f64;
+}
+
+/// `Math.pow`, which has different special cases than C's `pow`
+fn java_pow(x: f64, y: f64) -> f64 {
+	if y == 0.0 {
+		return 1.0;
+	}
+
+	// C's `pow` returns 1.0 for `pow(1.0, NaN)` and `pow(-1.0, ±Infinity)`
+	if y.is_nan() || (y.is_infinite() && x.abs().to_bits() == 1.0f64.to_bits()) {
+		return f64::NAN;
+	}
+
+	x.powf(y)
+}
+
+simple_intrinsics! {
+	abs_int(a): expect_int -> Int => a.wrapping_abs();
+	abs_long(a): expect_long -> Long => a.wrapping_abs();
+	abs_float(a): expect_float -> Float => a.abs();
+	abs_double(a): expect_double -> Double => a.abs();
+
+	max_int(a, b): expect_int -> Int => a.max(b);
+	max_long(a, b): expect_long -> Long => a.max(b);
+	max_float(a, b): expect_float -> Float => java_max_f32(a, b);
+	max_double(a, b): expect_double -> Double => java_max_f64(a, b);
+	min_int(a, b): expect_int -> Int => a.min(b);
+	min_long(a, b): expect_long -> Long => a.min(b);
+	min_float(a, b): expect_float -> Float => java_min_f32(a, b);
+	min_double(a, b): expect_double -> Double => java_min_f64(a, b);
+
+	sqrt(a): expect_double -> Double => a.sqrt();
+	sin(a): expect_double -> Double => a.sin();
+	cos(a): expect_double -> Double => a.cos();
+	tan(a): expect_double -> Double => a.tan();
+	log(a): expect_double -> Double => a.ln();
+	log10(a): expect_double -> Double => a.log10();
+	exp(a): expect_double -> Double => a.exp();
+	pow(a, b): expect_double -> Double => java_pow(a, b);
+	floor(a): expect_double -> Double => a.floor();
+	ceil(a): expect_double -> Double => a.ceil();
+	rint(a): expect_double -> Double => a.round_ties_even();
+	fma_double(a, b, c): expect_double -> Double => a.mul_add(b, c);
+	fma_float(a, b, c): expect_float -> Float => a.mul_add(b, c);
+
+	bit_count(i): expect_int -> Int => i.count_ones() as i32;
+	number_of_leading_zeros(i): expect_int -> Int => i.leading_zeros() as i32;
+	number_of_trailing_zeros(i): expect_int -> Int => i.trailing_zeros() as i32;
+	reverse_bytes(i): expect_int -> Int => i.swap_bytes();
+}
+
+exact_intrinsics! {
+	add_exact_int(x, y): expect_int -> Int, "integer overflow" => x.checked_add(y);
+	add_exact_long(x, y): expect_long -> Long, "long overflow" => x.checked_add(y);
+	subtract_exact_int(x, y): expect_int -> Int, "integer overflow" => x.checked_sub(y);
+	subtract_exact_long(x, y): expect_long -> Long, "long overflow" => x.checked_sub(y);
+	multiply_exact_int(x, y): expect_int -> Int, "integer overflow" => x.checked_mul(y);
+	multiply_exact_long(x, y): expect_long -> Long, "long overflow" => x.checked_mul(y);
+	increment_exact_int(a): expect_int -> Int, "integer overflow" => a.checked_add(1);
+	increment_exact_long(a): expect_long -> Long, "long overflow" => a.checked_add(1);
+	decrement_exact_int(a): expect_int -> Int, "integer overflow" => a.checked_sub(1);
+	decrement_exact_long(a): expect_long -> Long, "long overflow" => a.checked_sub(1);
+	negate_exact_int(a): expect_int -> Int, "integer overflow" => a.checked_neg();
+	negate_exact_long(a): expect_long -> Long, "long overflow" => a.checked_neg();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn float_max_min() {
+		assert!(java_max_f64(f64::NAN, 1.0).is_nan());
+		assert!(java_max_f64(1.0, f64::NAN).is_nan());
+		assert!(java_min_f32(f32::NAN, 1.0).is_nan());
+		assert!(java_min_f32(1.0, f32::NAN).is_nan());
+
+		assert!(java_max_f64(-0.0, 0.0).is_sign_positive());
+		assert!(java_max_f64(0.0, -0.0).is_sign_positive());
+		assert!(java_min_f64(-0.0, 0.0).is_sign_negative());
+		assert!(java_min_f64(0.0, -0.0).is_sign_negative());
+
+		assert_eq!(java_max_f32(1.0, 2.0), 2.0);
+		assert_eq!(java_min_f32(1.0, 2.0), 1.0);
+	}
+
+	#[test]
+	fn pow() {
+		assert_eq!(java_pow(f64::NAN, 0.0), 1.0);
+		assert!(java_pow(1.0, f64::NAN).is_nan());
+		assert!(java_pow(-1.0, f64::INFINITY).is_nan());
+		assert!(java_pow(1.0, f64::NEG_INFINITY).is_nan());
+		assert_eq!(java_pow(2.0, 10.0), 1024.0);
+	}
+}
@@ -0,0 +1,317 @@
+mod array;
+mod lang;
+mod math;
+mod string;
+mod zip;
+
+pub(crate) use array::array_copy;
+pub(crate) use zip::{crc32_update, crc32_update_bytes};
+
+use crate::objects::method::Method;
+use crate::objects::reference::Reference;
+use crate::options::JvmOptions;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+
+use std::sync::OnceLock;
+
+use classfile::accessflags::MethodAccessFlags;
+use instructions::Operand;
+
+/// The signature of a Rust implementation of an intrinsic
+///
+/// The arguments are in declaration order, with the receiver first (if any), and with none of the
+/// empty slots that follow `long`s and `double`s on the operand stack.
+pub type IntrinsicFn =
+	fn(&'static JavaThread, &[Operand<Reference>]) -> Throws<Option<Operand<Reference>>>;
+
+static REGISTERED_INTRINSICS: [OnceLock<IntrinsicEntry>; NUMBER_OF_INTRINSICS] =
+	[const { OnceLock::new() }; NUMBER_OF_INTRINSICS];
+
+/// Find the intrinsic registered for `method`, if any
+pub fn find_intrinsic(method: &Method, is_virtual: bool) -> Option<&'static IntrinsicEntry> {
+	let intrinsic_id = method.intrinsic_id();
+	if intrinsic_id == IntrinsicId::None {
+		return None;
+	}
+
+	let entry = REGISTERED_INTRINSICS[intrinsic_id as usize].get()?;
+	if !std::ptr::eq(entry.method, method) || entry.is_virtual != is_virtual {
+		return None;
+	}
+
+	Some(entry)
+}
+
+/// Register `method` as an intrinsic, if it is one and it has an implementation
+///
+/// This is done once the method's class is linked.
+pub(crate) fn register_intrinsic(method: &'static Method, is_virtual: bool) {
+	let intrinsic_id = method.intrinsic_id();
+	if intrinsic_id == IntrinsicId::None || !intrinsic_id.is_enabled() {
+		return;
+	}
+
+	let Some(implementation) = intrinsic_id.implementation() else {
+		return;
+	};
+
+	let entry = IntrinsicEntry::new(
+		method,
+		is_virtual,
+		intrinsic_id.does_virtual_dispatch(),
+		intrinsic_id,
+		implementation,
+	);
+
+	// The bootstrap loader only ever defines a class once, so this can only fail if the method is
+	// somehow linked twice. The first entry wins.
+	let _ = REGISTERED_INTRINSICS[intrinsic_id as usize].set(entry);
+}
+
+// The automatically generated intrinsic candidates
+include!("../../../../generated/native/intrinsics_generated.rs");
+
+impl IntrinsicId {
+	/// The name of the intrinsic, as accepted by `-XX:DisableIntrinsic`
+	pub fn name(self) -> &'static str {
+		// SAFETY: The names are generated from Rust identifiers
+		unsafe { std::str::from_utf8_unchecked(INTRINSIC_NAME_TABLE[self as usize]) }
+	}
+
+	pub fn does_virtual_dispatch(self) -> bool {
+		matches!(self, Self::Object_hashCode)
+	}
+
+	/// Whether the intrinsic is available, according to the platform and JVM flags
+	pub fn is_enabled(self) -> bool {
+		if self == Self::None {
+			return false;
+		}
+
+		let name = self.name();
+		!JvmOptions::get()
+			.disabled_intrinsics
+			.iter()
+			.any(|disabled| disabled == name)
+	}
+
+	/// The Rust implementation of the intrinsic, if there is one
+	fn implementation(self) -> Option<IntrinsicFn> {
+		let implementation: IntrinsicFn = match self {
+			Self::Math_abs_int => math::abs_int,
+			Self::Math_abs_long => math::abs_long,
+			Self::Math_abs_float => math::abs_float,
+			Self::Math_abs_double => math::abs_double,
+			Self::Math_max_int_int => math::max_int,
+			Self::Math_max_long_long => math::max_long,
+			Self::Math_max_float_float => math::max_float,
+			Self::Math_max_double_double => math::max_double,
+			Self::Math_min_int_int => math::min_int,
+			Self::Math_min_long_long => math::min_long,
+			Self::Math_min_float_float => math::min_float,
+			Self::Math_min_double_double => math::min_double,
+			Self::Math_sqrt => math::sqrt,
+			Self::Math_sin => math::sin,
+			Self::Math_cos => math::cos,
+			Self::Math_tan => math::tan,
+			Self::Math_log => math::log,
+			Self::Math_log10 => math::log10,
+			Self::Math_exp => math::exp,
+			Self::Math_pow => math::pow,
+			Self::Math_floor => math::floor,
+			Self::Math_ceil => math::ceil,
+			Self::Math_rint => math::rint,
+			Self::Math_fma_double_double_double => math::fma_double,
+			Self::Math_fma_float_float_float => math::fma_float,
+			Self::Math_addExact_int_int => math::add_exact_int,
+			Self::Math_addExact_long_long => math::add_exact_long,
+			Self::Math_subtractExact_int_int => math::subtract_exact_int,
+			Self::Math_subtractExact_long_long => math::subtract_exact_long,
+			Self::Math_multiplyExact_int_int => math::multiply_exact_int,
+			Self::Math_multiplyExact_long_long => math::multiply_exact_long,
+			Self::Math_incrementExact_int => math::increment_exact_int,
+			Self::Math_incrementExact_long => math::increment_exact_long,
+			Self::Math_decrementExact_int => math::decrement_exact_int,
+			Self::Math_decrementExact_long => math::decrement_exact_long,
+			Self::Math_negateExact_int => math::negate_exact_int,
+			Self::Math_negateExact_long => math::negate_exact_long,
+
+			Self::Integer_bitCount => math::bit_count,
+			Self::Integer_numberOfLeadingZeros => math::number_of_leading_zeros,
+			Self::Integer_numberOfTrailingZeros => math::number_of_trailing_zeros,
+			Self::Integer_reverseBytes => math::reverse_bytes,
+
+			Self::StringLatin1_equals => string::latin1_equals,
+			Self::StringLatin1_indexOfChar => string::latin1_index_of_char,
+			Self::StringUTF16_indexOfChar => string::utf16_index_of_char,
+
+			Self::System_arraycopy => array::arraycopy,
+			Self::Arrays_fill_long_array_long
+			| Self::Arrays_fill_int_array_int
+			| Self::Arrays_fill_short_array_short
+			| Self::Arrays_fill_char_array_char
+			| Self::Arrays_fill_byte_array_byte
+			| Self::Arrays_fill_bool_array_bool
+			| Self::Arrays_fill_double_array_double
+			| Self::Arrays_fill_float_array_float => array::fill,
+			Self::Arrays_fill_long_array_int_int_long
+			| Self::Arrays_fill_int_array_int_int_int
+			| Self::Arrays_fill_short_array_int_int_short
+			| Self::Arrays_fill_char_array_int_int_char
+			| Self::Arrays_fill_byte_array_int_int_byte
+			| Self::Arrays_fill_bool_array_int_int_bool
+			| Self::Arrays_fill_double_array_int_int_double
+			| Self::Arrays_fill_float_array_int_int_float => array::fill_range,
+
+			Self::CRC32_update => zip::update,
+			Self::CRC32_updateBytes0 => zip::update_bytes,
+
+			Self::Object_hashCode => lang::hash_code,
+			Self::Thread_currentThread => lang::current_thread,
+
+			_ => return None,
+		};
+
+		Some(implementation)
+	}
+}
+
+/// An intrinsic definition
+pub struct IntrinsicEntry {
+	is_virtual: bool,
+	does_virtual_dispatch: bool,
+	intrinsic_id: IntrinsicId,
+	method: &'static Method,
+	flags: IntrinsicFlags,
+	implementation: IntrinsicFn,
+}
+
+impl IntrinsicEntry {
+	pub fn new(
+		method: &'static Method,
+		is_virtual: bool,
+		does_virtual_dispatch: bool,
+		intrinsic_id: IntrinsicId,
+		implementation: IntrinsicFn,
+	) -> Self {
+		assert_ne!(
+			intrinsic_id,
+			IntrinsicId::None,
+			"Attempted to register an intrinsic entry for non-intrinsic method: {:?}",
+			method
+		);
+
+		Self {
+			is_virtual,
+			does_virtual_dispatch,
+			intrinsic_id,
+			method,
+			flags: IntrinsicFlags::from(method.access_flags),
+			implementation,
+		}
+	}
+
+	pub fn is_virtual(&self) -> bool {
+		self.is_virtual
+	}
+
+	pub fn does_virtual_dispatch(&self) -> bool {
+		self.does_virtual_dispatch
+	}
+
+	pub fn intrinsic_id(&self) -> IntrinsicId {
+		self.intrinsic_id
+	}
+
+	pub fn flags(&self) -> IntrinsicFlags {
+		self.flags
+	}
+
+	/// Call the implementation with the method's arguments
+	pub fn invoke(
+		&self,
+		thread: &'static JavaThread,
+		args: &[Operand<Reference>],
+	) -> Throws<Option<Operand<Reference>>> {
+		(self.implementation)(thread, args)
+	}
+}
+
+/// Access flag combinations relevant to intrinsic methods
+#[repr(u8)]
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
+pub enum IntrinsicFlags {
+	/// Not static, native, or synchronized
+	Regular,
+	/// Is: Static
+	/// Is not: Native, synchronized
+	Static,
+	/// Is: Synchronized
+	/// Is not: Static, native
+	Synchronized,
+	/// Is: Native
+	/// Is not: Static, synchronized
+	Native,
+	/// Is: Static, native
+	/// Is not: Synchronized
+	StaticNative,
+}
+
+impl IntrinsicFlags {
+	/// Whether the intrinsic flags contain the static access flag
+	pub fn is_static(self) -> bool {
+		matches!(self, Self::Static | Self::StaticNative)
+	}
+
+	/// Whether the intrinsic flags contain the native access flag
+	pub fn is_native(self) -> bool {
+		matches!(self, Self::Native | Self::StaticNative)
+	}
+}
+
+impl From<MethodAccessFlags> for IntrinsicFlags {
+	fn from(value: MethodAccessFlags) -> Self {
+		let is_static = value.is_static();
+		let is_native = value.is_native();
+		let is_synchronized = value.is_synchronized();
+
+		if !is_static && !is_native && !is_synchronized {
+			return Self::Regular;
+		}
+
+		if is_static {
+			assert!(
+				(!is_native && !is_synchronized) || is_native && !is_synchronized,
+				"Invalid intrinsic flags: {:?} (Must be either Static and Native OR Static)",
+				value
+			);
+
+			if !is_native && !is_synchronized {
+				return Self::Static;
+			}
+
+			if is_native && !is_synchronized {
+				return Self::StaticNative;
+			}
+		}
+
+		if is_native {
+			assert!(
+				!is_synchronized,
+				"Invalid intrinsic flags: {:?} (Must be Native AND !Synchronized)",
+				value
+			);
+
+			return Self::Native;
+		}
+
+		assert!(
+			is_synchronized,
+			"Invalid intrisic flags: {:?} (Must be Synchronized)",
+			value
+		);
+
+		Self::Synchronized
+	}
+}
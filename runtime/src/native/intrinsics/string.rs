@@ -0,0 +1,91 @@
+//! `java.lang.StringLatin1` and `java.lang.StringUTF16`
+
+use crate::objects::instance::array::PrimitiveArrayInstanceRef;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+
+use common::int_types::s4;
+use instructions::Operand;
+use jni::sys::jbyte;
+
+fn byte_array(reference: Reference) -> Throws<PrimitiveArrayInstanceRef> {
+	if reference.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	Throws::Ok(reference.extract_primitive_array())
+}
+
+pub(super) fn latin1_equals(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [value, other] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let value = byte_array(value.expect_reference())?;
+	let other = byte_array(other.expect_reference())?;
+
+	let equal = value.as_slice::<jbyte>() == other.as_slice::<jbyte>();
+	Throws::Ok(Some(Operand::Int(s4::from(equal))))
+}
+
+pub(super) fn latin1_index_of_char(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [value, ch, from_index, max] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let value = byte_array(value.expect_reference())?;
+	let value = value.as_slice::<jbyte>();
+
+	// The caller already verified that the character can be encoded in Latin-1
+	let ch = ch.expect_int() as jbyte;
+
+	for index in from_index.expect_int().max(0)..max.expect_int() {
+		match value.get(index as usize) {
+			Some(&c) if c == ch => return Throws::Ok(Some(Operand::Int(index))),
+			Some(_) => {},
+			None => throw!(@DEFER ArrayIndexOutOfBoundsException,
+				"Index {} out of bounds for length {}",
+				index,
+				value.len()
+			),
+		}
+	}
+
+	Throws::Ok(Some(Operand::Int(-1)))
+}
+
+pub(super) fn utf16_index_of_char(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [value, ch, from_index, max] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let value = byte_array(value.expect_reference())?;
+	let value = value.as_slice::<jbyte>();
+	let length = value.len() / 2;
+
+	// Characters are stored in native byte order, see `StringUTF16.isBigEndian()`
+	let ch = ch.expect_int();
+	for index in from_index.expect_int().max(0)..max.expect_int() {
+		let offset = index as usize * 2;
+		if index as usize >= length {
+			throw!(@DEFER StringIndexOutOfBoundsException, "index {}, length {}", index, length);
+		}
+
+		let c = u16::from_ne_bytes([value[offset] as u8, value[offset + 1] as u8]);
+		if s4::from(c) == ch {
+			return Throws::Ok(Some(Operand::Int(index)));
+		}
+	}
+
+	Throws::Ok(Some(Operand::Int(-1)))
+}
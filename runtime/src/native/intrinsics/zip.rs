@@ -0,0 +1,67 @@
+//! `java.util.zip.CRC32`
+
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+
+use common::int_types::s4;
+use instructions::Operand;
+
+/// Update `crc` with a single byte, the low 8 bits of `b`
+pub(crate) fn crc32_update(crc: s4, b: s4) -> s4 {
+	let mut hasher = crc32fast::Hasher::new_with_initial(crc as u32);
+	hasher.update(&[b as u8]);
+	hasher.finalize() as s4
+}
+
+/// Update `crc` with `len` bytes of the array `b`, starting at `off`
+pub(crate) fn crc32_update_bytes(crc: s4, b: Reference, off: s4, len: s4) -> Throws<s4> {
+	if b.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let array = b.extract_primitive_array();
+	let bytes = array.as_bytes();
+
+	// `CRC32` already checks the range before calling into the VM
+	let Some(bytes) = usize::try_from(off)
+		.ok()
+		.zip(usize::try_from(len).ok())
+		.and_then(|(off, len)| bytes.get(off..off.checked_add(len)?))
+	else {
+		throw!(@DEFER ArrayIndexOutOfBoundsException);
+	};
+
+	let mut hasher = crc32fast::Hasher::new_with_initial(crc as u32);
+	hasher.update(bytes);
+	Throws::Ok(hasher.finalize() as s4)
+}
+
+pub(super) fn update(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [crc, b] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let crc = crc32_update(crc.expect_int(), b.expect_int());
+	Throws::Ok(Some(Operand::Int(crc)))
+}
+
+pub(super) fn update_bytes(
+	_thread: &'static JavaThread,
+	args: &[Operand<Reference>],
+) -> Throws<Option<Operand<Reference>>> {
+	let [crc, b, off, len] = args else {
+		unreachable!("wrong number of arguments for intrinsic");
+	};
+
+	let crc = crc32_update_bytes(
+		crc.expect_int(),
+		b.expect_reference(),
+		off.expect_int(),
+		len.expect_int(),
+	)?;
+	Throws::Ok(Some(Operand::Int(crc)))
+}
@@ -6,7 +6,7 @@ use crate::objects::class::ClassPtr;
 use crate::objects::instance::array::Array;
 use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{throw, throw_with_ret};
+use crate::thread::exceptions::{handle_exception, throw, throw_with_ret};
 use crate::{classes, native};
 
 use std::fs;
@@ -37,7 +37,10 @@ pub fn open0(env: JniEnv, this: Reference, name: Reference /* java.lang.String *
 			let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 
 			let path_jstring = unsafe { JString::from_raw(name.into_jni()) };
-			let reason = Reference::class(StringInterner::intern(e.to_string()));
+			let reason = Reference::class(handle_exception!(
+				thread,
+				StringInterner::intern(e.to_string())
+			));
 			let reason_jstring = unsafe { JString::from_raw(reason.into_jni()) };
 
 			if let Some(exception) = native::class::construct_class(
@@ -6,7 +6,7 @@ use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
 use crate::symbols::sym;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{throw_and_return_null, throw_with_ret};
+use crate::thread::exceptions::{handle_exception, throw_and_return_null, throw_with_ret};
 
 use std::fs;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -31,8 +31,13 @@ pub fn canonicalize0(
 		throw_and_return_null!(thread, IOException);
 	};
 
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 	let new_path = path.to_string_lossy().into_owned();
-	Reference::class(StringInterner::intern(new_path.as_str()))
+	Reference::class(handle_exception!(
+		Reference::null(),
+		thread,
+		StringInterner::intern(new_path.as_str())
+	))
 }
 
 #[cfg(unix)]
@@ -1,4 +1,4 @@
-use crate::classpath::loader::ClassLoaderSet;
+use crate::classpath::loader::{ClassLoaderSet, SUPPORTED_MAJOR_UPPER_BOUND};
 use crate::native::java::lang::String::StringInterner;
 use crate::native::jni::{IntoJni, reference_from_jobject};
 use crate::objects::class::ClassPtr;
@@ -10,7 +10,7 @@ use crate::objects::reference::Reference;
 use crate::symbols::{Symbol, sym};
 use crate::thread::JavaThread;
 use crate::thread::exceptions::{
-	Throws, handle_exception, throw, throw_and_return_null, throw_with_ret,
+	ExceptionKind, Throws, handle_exception, throw, throw_and_return_null, throw_with_ret,
 };
 use crate::{classes, globals};
 
@@ -106,14 +106,20 @@ pub fn isPrimitive(_env: JniEnv, this: Reference /* java.lang.Class */) -> jbool
 }
 
 pub fn initClassName(
-	_env: JniEnv,
+	env: JniEnv,
 	this: Reference, // java.lang.Class
 ) -> Reference {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
 	let this_mirror = this.extract_mirror();
 	let this_mirror_target = this_mirror.target_class();
 	let this_name = this_mirror_target.name();
 	let this_binary_name = this_name.as_str().replace('/', ".");
-	let name_string = StringInterner::intern(&*this_binary_name);
+	let name_string = handle_exception!(
+		Reference::null(),
+		thread,
+		StringInterner::intern(&*this_binary_name)
+	);
 
 	classes::java::lang::Class::set_name(this_mirror, Reference::class(name_string));
 
@@ -175,16 +181,47 @@ pub fn getModifiers(_env: JniEnv, this: Reference /* java.lang.Class */) -> jint
 	let class = mirror.target_class();
 	jint::from(class.modifier_flags())
 }
-pub fn getSigners(_env: JniEnv, _this: Reference /* java.lang.Class */) -> Reference /* Object[] */
+pub fn getSigners(env: JniEnv, this: Reference /* java.lang.Class */) -> Reference /* Object[] */
 {
-	unimplemented!("Class#getSigners");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	let signers = classes::java::lang::Class::signers(mirror);
+	if signers.is_null() {
+		return Reference::null();
+	}
+
+	// Hand out a copy, so the caller can't modify the signers of the class
+	let signers = signers.extract_object_array();
+	let copy: ObjectArrayInstanceRef = handle_exception!(
+		Reference::null(),
+		thread,
+		ObjectArrayInstance::new(signers.len() as s4, globals::classes::java_lang_Object())
+	);
+
+	for (index, signer) in signers.as_slice().iter().enumerate() {
+		// SAFETY: The copy has the same length as the original array
+		unsafe {
+			copy.store_unchecked(index, *signer);
+		}
+	}
+
+	Reference::object_array(copy)
 }
 pub fn setSigners(
-	_env: JniEnv,
-	_this: Reference,    // java.lang.Class
-	_signers: Reference, // Object[]
+	env: JniEnv,
+	this: Reference,    // java.lang.Class
+	signers: Reference, // Object[]
 ) {
-	unimplemented!("Class#setSigners");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(thread, ensure_class_mirror(this)) else {
+		return;
+	};
+
+	classes::java::lang::Class::set_signers(mirror, signers);
 }
 
 // Returns Object[3] where:
@@ -229,12 +266,20 @@ pub fn getEnclosingMethod0(
 	unsafe {
 		array_instance.store_unchecked(
 			1,
-			Reference::class(StringInterner::intern(enclosing_method.name)),
+			Reference::class(handle_exception!(
+				Reference::null(),
+				thread,
+				StringInterner::intern(enclosing_method.name)
+			)),
 		);
 
 		array_instance.store_unchecked(
 			2,
-			Reference::class(StringInterner::intern(enclosing_method.descriptor_sym())),
+			Reference::class(handle_exception!(
+				Reference::null(),
+				thread,
+				StringInterner::intern(enclosing_method.descriptor_sym())
+			)),
 		);
 	}
 
@@ -316,7 +361,11 @@ pub fn getSimpleBinaryName0(
 		}
 
 		if let Some(name) = inner_class.inner_class_name {
-			return Reference::class(StringInterner::intern(name));
+			return Reference::class(handle_exception!(
+				Reference::null(),
+				thread,
+				StringInterner::intern(name)
+			));
 		}
 
 		break;
@@ -326,10 +375,17 @@ pub fn getSimpleBinaryName0(
 }
 pub fn getProtectionDomain0(
 	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	this: Reference, // java.lang.Class
 ) -> Reference /* java.security.ProtectionDomain */
 {
-	unimplemented!("Class#getProtectionDomain0");
+	let mirror = this.extract_mirror();
+
+	// Primitive types don't have a protection domain
+	if mirror.is_primitive() {
+		return Reference::null();
+	}
+
+	classes::java::lang::Class::protection_domain(mirror)
 }
 pub fn getPrimitiveClass(
 	env: JniEnv,
@@ -350,24 +406,67 @@ pub fn getPrimitiveClass(
 	throw_and_return_null!(thread, ClassNotFoundException, "{name_string}");
 }
 pub fn getGenericSignature0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* String */
 {
-	unimplemented!("Class#getGenericSignature0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	match mirror
+		.target_class()
+		.unwrap_class_instance()
+		.generic_signature
+	{
+		Some(signature) => Reference::class(handle_exception!(
+			Reference::null(),
+			thread,
+			StringInterner::intern(signature)
+		)),
+		None => Reference::null(),
+	}
 }
 pub fn getRawAnnotations(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* byte[] */
 {
-	unimplemented!("Class#getRawAnnotations");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	let annotations = mirror
+		.target_class()
+		.unwrap_class_instance()
+		.annotations_array();
+	match handle_exception!(Reference::null(), thread, annotations) {
+		Some(annotations) => Reference::array(annotations),
+		None => Reference::null(),
+	}
 }
 pub fn getRawTypeAnnotations(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* byte[] */ {
-	unimplemented!("Class#getRawTypeAnnotations");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	let type_annotations = mirror
+		.target_class()
+		.unwrap_class_instance()
+		.type_annotations_array();
+	match handle_exception!(Reference::null(), thread, type_annotations) {
+		Some(type_annotations) => Reference::array(type_annotations),
+		None => Reference::null(),
+	}
 }
 pub fn getConstantPool(
 	_env: JniEnv,
@@ -395,11 +494,57 @@ pub fn getConstantPool(
 	Reference::class(constant_pool)
 }
 pub fn getDeclaredFields0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
-	_public_only: jboolean,
+	env: JniEnv,
+	this: Reference, // java.lang.Class
+	public_only: jboolean,
 ) -> Reference /* Field[] */ {
-	unimplemented!("Class#getDeclaredFields0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let this_mirror = this.extract_mirror();
+
+	// Not applicable for primitive and array mirrors
+	if this_mirror.is_primitive() || this_mirror.is_array() {
+		let ret = handle_exception!(
+			Reference::null(),
+			thread,
+			ObjectArrayInstance::new(0, globals::classes::java_lang_reflect_Field())
+		);
+		return Reference::object_array(ret);
+	}
+
+	let target = this_mirror.target_class();
+	let fields = target
+		.fields()
+		.filter(|field| {
+			if field.class != target || field.is_injected() {
+				return false;
+			}
+
+			!public_only || field.access_flags.is_public()
+		})
+		.collect::<Vec<_>>();
+
+	let ret: ObjectArrayInstanceRef = handle_exception!(
+		Reference::null(),
+		thread,
+		ObjectArrayInstance::new(
+			fields.len() as jint,
+			globals::classes::java_lang_reflect_Field()
+		)
+	);
+
+	for (i, field) in fields.into_iter().enumerate() {
+		let field: ClassInstanceRef = handle_exception!(
+			Reference::null(),
+			thread,
+			classes::java::lang::reflect::Field::new(field)
+		);
+
+		// SAFETY: The array is known to have the correct length
+		unsafe { ret.store_unchecked(i, Reference::class(field)) };
+	}
+
+	Reference::object_array(ret)
 }
 pub fn getDeclaredMethods0(
 	env: JniEnv,
@@ -522,22 +667,85 @@ pub fn getDeclaredConstructors0(
 	Reference::object_array(ret)
 }
 pub fn getDeclaredClasses0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* Class<?>[] */
 {
-	unimplemented!("Class#getDeclaredClasses0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let mut declared_classes = Vec::new();
+	if let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this)) {
+		let target_class = mirror.target_class();
+		let inner_classes = target_class
+			.unwrap_class_instance()
+			.inner_classes()
+			.unwrap_or_default();
+
+		for inner_class in inner_classes {
+			// Only the classes that are direct members of this class
+			if inner_class.outer_class != Some(target_class.name())
+				|| inner_class.inner_class == target_class.name()
+			{
+				continue;
+			}
+
+			let inner: ClassPtr = handle_exception!(
+				Reference::null(),
+				thread,
+				target_class.loader().load(inner_class.inner_class)
+			);
+			declared_classes.push(inner);
+		}
+	}
+
+	let ret: ObjectArrayInstanceRef = handle_exception!(
+		Reference::null(),
+		thread,
+		ObjectArrayInstance::new(
+			declared_classes.len() as s4,
+			globals::classes::java_lang_Class()
+		)
+	);
+
+	for (index, class) in declared_classes.into_iter().enumerate() {
+		// SAFETY: The array is known to have the correct length
+		unsafe { ret.store_unchecked(index, Reference::mirror(class.mirror())) };
+	}
+
+	Reference::object_array(ret)
 }
 
 pub fn getRecordComponents0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* RecordComponent[] */
 {
-	unimplemented!("Class#getRecordComponents0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	let target_class = mirror.target_class();
+	let Some(components) = target_class.unwrap_class_instance().record_components() else {
+		// Not a record
+		return Reference::null();
+	};
+
+	let ret = handle_exception!(
+		Reference::null(),
+		thread,
+		classes::java::lang::reflect::RecordComponent::new_array(thread, target_class, components)
+	);
+	Reference::object_array(ret)
 }
-pub fn isRecord0(_env: JniEnv, _this: Reference /* java.lang.Class */) -> jboolean {
-	unimplemented!("Class#isRecord0");
+pub fn isRecord0(_env: JniEnv, this: Reference /* java.lang.Class */) -> jboolean {
+	let mirror = this.extract_mirror();
+	if mirror.is_primitive() || mirror.is_array() {
+		return false;
+	}
+
+	mirror.target_class().is_record()
 }
 
 // TODO: https://github.com/openjdk/jdk/blob/19373b2ff0cd795afa262c17dcb3388fd6a5be59/src/hotspot/share/classfile/javaAssertions.cpp#L195
@@ -554,19 +762,75 @@ pub fn desiredAssertionStatus0(
 }
 
 pub fn getNestHost0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* java/lang/Class */
 {
-	unimplemented!("Class#getNestHost0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		// Primitives and arrays are their own nest hosts
+		return this;
+	};
+
+	let nest_host = handle_exception!(
+		Reference::null(),
+		thread,
+		mirror.target_class().nest_host(thread)
+	);
+	Reference::mirror(nest_host.mirror())
 }
 
 pub fn getNestMembers0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* Class<?>[] */
 {
-	unimplemented!("Class#getNestMembers0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	// The nest host is always the first member
+	let mut members = Vec::new();
+	match handle_exception!(Reference::null(), thread, ensure_class_mirror(this)) {
+		Some(mirror) => {
+			let nest_host = handle_exception!(
+				Reference::null(),
+				thread,
+				mirror.target_class().nest_host(thread)
+			);
+			members.push(nest_host);
+
+			for member_name in nest_host.nest_members().unwrap_or_default() {
+				let Some(member) = handle_exception!(
+					Reference::null(),
+					thread,
+					load_or_skip(thread, nest_host, *member_name)
+				) else {
+					continue;
+				};
+
+				// Only include the members that agree on their nest host
+				let member_host =
+					handle_exception!(Reference::null(), thread, member.nest_host(thread));
+				if member_host == nest_host {
+					members.push(member);
+				}
+			}
+		},
+		None => members.push(this.extract_target_class()),
+	}
+
+	let ret: ObjectArrayInstanceRef = handle_exception!(
+		Reference::null(),
+		thread,
+		ObjectArrayInstance::new(members.len() as s4, globals::classes::java_lang_Class())
+	);
+
+	for (index, member) in members.into_iter().enumerate() {
+		// SAFETY: The array is known to have the correct length
+		unsafe { ret.store_unchecked(index, Reference::mirror(member.mirror())) };
+	}
+
+	Reference::object_array(ret)
 }
 
 pub fn isHidden(_env: JniEnv, this: Reference /* java.lang.Class */) -> jboolean {
@@ -575,15 +839,89 @@ pub fn isHidden(_env: JniEnv, this: Reference /* java.lang.Class */) -> jboolean
 }
 
 pub fn getPermittedSubclasses0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	env: JniEnv,
+	this: Reference, // java.lang.Class
 ) -> Reference /* Class<?>[] */ {
-	unimplemented!("Class#getPermittedSubclasses0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(mirror) = handle_exception!(Reference::null(), thread, ensure_class_mirror(this))
+	else {
+		return Reference::null();
+	};
+
+	let target_class = mirror.target_class();
+	let Some(permitted_subclasses) = target_class.unwrap_class_instance().permitted_subclasses()
+	else {
+		// Not a sealed class
+		return Reference::null();
+	};
+
+	let mut subclasses = Vec::with_capacity(permitted_subclasses.len());
+	for subclass_name in permitted_subclasses {
+		if let Some(subclass) = handle_exception!(
+			Reference::null(),
+			thread,
+			load_or_skip(thread, target_class, *subclass_name)
+		) {
+			subclasses.push(subclass);
+		}
+	}
+
+	let ret: ObjectArrayInstanceRef = handle_exception!(
+		Reference::null(),
+		thread,
+		ObjectArrayInstance::new(subclasses.len() as s4, globals::classes::java_lang_Class())
+	);
+
+	for (index, subclass) in subclasses.into_iter().enumerate() {
+		// SAFETY: The array is known to have the correct length
+		unsafe { ret.store_unchecked(index, Reference::mirror(subclass.mirror())) };
+	}
+
+	Reference::object_array(ret)
 }
 
 pub fn getClassFileVersion0(
 	_env: JniEnv,
-	_this: Reference, // java.lang.Class
+	this: Reference, // java.lang.Class
 ) -> jint {
-	unimplemented!("Class#getClassFileVersion0");
+	let mirror = this.extract_mirror();
+
+	// Primitive types are always considered to be of the latest version. Arrays are handled by
+	// `Class#getClassFileVersion()`, which asks the element type instead.
+	if mirror.is_primitive() || mirror.is_array() {
+		return jint::from(SUPPORTED_MAJOR_UPPER_BOUND);
+	}
+
+	let target_class = mirror.target_class();
+	let descriptor = target_class.unwrap_class_instance();
+	(jint::from(descriptor.minor_version) << 16) | jint::from(descriptor.major_version)
+}
+
+/// Load `name` through the loader of `class`, ignoring any failures other than `VirtualMachineError`s
+///
+/// This is used for the attributes where unresolvable entries are simply left out, like
+/// `NestMembers` and `PermittedSubclasses`.
+fn load_or_skip(
+	thread: &'static JavaThread,
+	class: ClassPtr,
+	name: Symbol,
+) -> Throws<Option<ClassPtr>> {
+	let e = match class.loader().load(name) {
+		Throws::Ok(class) => return Throws::Ok(Some(class)),
+		Throws::Exception(e) => e,
+	};
+
+	let is_vm_error = matches!(
+		e.kind().class(),
+		Throws::Ok(exception_class) if exception_class.is_subclass_of(globals::classes::java_lang_VirtualMachineError())
+	);
+	if is_vm_error {
+		return Throws::Exception(e);
+	}
+
+	if e.kind() == ExceptionKind::PendingException {
+		let _ = thread.take_pending_exception();
+	}
+
+	Throws::Ok(None)
 }
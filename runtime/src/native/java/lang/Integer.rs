@@ -0,0 +1 @@
+// Marker module, this class only defines intrinsics
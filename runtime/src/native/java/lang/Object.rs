@@ -0,0 +1,15 @@
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+
+use ::jni::env::JniEnv;
+use common::int_types::s4;
+
+include_generated!("native/java/lang/def/Object.definitions.rs");
+
+// NOTE: `java.lang.Object` registers its natives separately, see `classes::java::lang::Object`.
+//       This is only reachable if the intrinsic is disabled.
+pub fn hashCode(env: JniEnv, this: Reference /* java.lang.Object */) -> s4 {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	this.hash(thread)
+}
@@ -9,17 +9,16 @@ pub fn availableProcessors(
 	_: JniEnv,
 	_this: Reference, // java.lang.Runtime
 ) -> s4 {
-	num_cpus::get() as s4
+	crate::os::active_processor_count() as s4
 }
 pub fn freeMemory(_: JniEnv, _this: Reference /* java.lang.Runtime */) -> s8 {
-	unimplemented!("java.lang.Runtime#freeMemory")
+	crate::gc::heap::free_size() as s8
 }
 pub fn totalMemory(_: JniEnv, _this: Reference /* java.lang.Runtime */) -> s8 {
-	unimplemented!("java.lang.Runtime#totalMemory")
+	crate::gc::heap::total_size() as s8
 }
 pub fn maxMemory(_: JniEnv, _this: Reference /* java.lang.Runtime */) -> s8 {
-	// TODO: Xmx
-	s8::MAX
+	crate::gc::heap::max_size() as s8
 }
 pub fn gc(_: JniEnv, _this: Reference /* java.lang.Runtime */) {
 	unimplemented!("java.lang.Runtime#gc")
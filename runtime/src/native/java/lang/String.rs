@@ -2,6 +2,7 @@ use crate::classes;
 use crate::objects::instance::class::ClassInstanceRef;
 use crate::objects::reference::Reference;
 use crate::symbols::Symbol;
+use crate::thread::exceptions::Throws;
 
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -132,16 +133,16 @@ where
 	T: StringHashDerivable<T>,
 	T: Into<Symbol>,
 {
-	pub fn intern(string: T) -> ClassInstanceRef {
+	pub fn intern(string: T) -> Throws<ClassInstanceRef> {
 		let hash = <T as StringHashDerivable<T>>::string_hash(&string);
 
 		if let Some(entry) = lookup(hash) {
-			return entry;
+			return Throws::Ok(entry);
 		}
 
 		let symbol: Symbol = string.into();
-		let string = classes::java::lang::String::new(symbol);
-		do_intern(hash, string)
+		let string = classes::java::lang::String::new(symbol)?;
+		Throws::Ok(do_intern(hash, string))
 	}
 }
 
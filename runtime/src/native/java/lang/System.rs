@@ -0,0 +1,26 @@
+use crate::native::intrinsics;
+use crate::objects::class::ClassPtr;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
+
+use ::jni::env::JniEnv;
+use common::int_types::s4;
+
+include_generated!("native/java/lang/def/System.definitions.rs");
+
+pub fn arraycopy(
+	env: JniEnv,
+	_class: ClassPtr,
+	src: Reference, // java.lang.Object
+	src_pos: s4,
+	dest: Reference, // java.lang.Object
+	dest_pos: s4,
+	length: s4,
+) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		intrinsics::array_copy(src, src_pos, dest, dest_pos, length)
+	);
+}
@@ -1,11 +1,20 @@
+use crate::classes;
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
+use crate::thread::exceptions::handle_exception;
+use crate::thread::pool::ThreadPool;
+use crate::thread::{JavaThread, dump};
 
 use ::jni::env::JniEnv;
 use ::jni::sys::{jboolean, jlong};
 
 include_generated!("native/java/lang/def/Thread.definitions.rs");
 
+pub fn currentThread(env: JniEnv, _class: ClassPtr) -> Reference /* java.lang.Thread */ {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	thread.obj().expect("current thread should exist")
+}
+
 pub fn findScopedValueBindings(_env: JniEnv, _class: ClassPtr) -> Reference /* java.lang.Object */
 {
 	unimplemented!("java.lang.Thread#findScopedValueBindings");
@@ -55,25 +64,36 @@ pub fn holdsLock(
 }
 
 pub fn getStackTrace0(
-	_env: JniEnv,
-	_this: Reference, // java.lang.Thread
+	env: JniEnv,
+	this: Reference, // java.lang.Thread
 ) -> Reference /* java.lang.Object */
 {
-	unimplemented!("java.lang.Thread#getStackTrace0");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		Reference::null(),
+		thread,
+		dump::stack_trace_of(thread, this)
+	)
 }
 
 pub fn dumpThreads(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_threads: Reference, // []java.lang.Thread
+	threads: Reference, // []java.lang.Thread
 ) -> Reference /* [][]java.lang.StackTraceElement */
 {
-	unimplemented!("java.lang.Thread#dumpThreads");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		Reference::null(),
+		thread,
+		dump::stack_traces_of(thread, threads)
+	)
 }
 
-pub fn getThreads(_env: JniEnv, _class: ClassPtr) -> Reference /* []java.lang.Thread */
+pub fn getThreads(env: JniEnv, _class: ClassPtr) -> Reference /* []java.lang.Thread */
 {
-	unimplemented!("java.lang.Thread#getThreads");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(Reference::null(), thread, dump::live_thread_objects())
 }
 
 pub fn interrupt0(_env: JniEnv, _this: Reference /* java.lang.Thread */) {
@@ -86,8 +106,17 @@ pub fn clearInterruptEvent(_env: JniEnv, _class: ClassPtr) {
 
 pub fn setNativeName(
 	_env: JniEnv,
-	_this: Reference, // java.lang.Thread
-	_name: Reference, // java.lang.String
+	this: Reference, // java.lang.Thread
+	name: Reference, // java.lang.String
 ) {
-	unimplemented!("java.lang.Thread#setNativeName");
+	if name.is_null() {
+		return;
+	}
+
+	// Only the current thread can be renamed, see `JavaThread::set_native_name()`
+	let Some(thread) = ThreadPool::find_from_obj(this) else {
+		return;
+	};
+
+	thread.set_native_name(&classes::java::lang::String::extract(name.extract_class()));
 }
@@ -3,6 +3,7 @@ use crate::objects::method::Method;
 use crate::objects::reference::Reference;
 use crate::symbols::sym;
 use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
 use crate::thread::frame::stack::VisibleStackFrame;
 use crate::{classes, globals};
 
@@ -75,9 +76,9 @@ impl BackTrace {
 		self.inner.push(pc as jlong);
 	}
 
-	fn into_obj(self) -> Reference {
-		let array = PrimitiveArrayInstance::new::<jlong>(&self.inner);
-		Reference::array(array)
+	fn into_obj(self) -> Throws<Reference> {
+		let array = PrimitiveArrayInstance::new::<jlong>(&self.inner)?;
+		Throws::Ok(Reference::array(array))
 	}
 }
 
@@ -161,7 +162,13 @@ pub fn fillInStackTrace(
 		backtrace.push(frame);
 	}
 
-	classes::java::lang::Throwable::set_backtrace(this, backtrace.into_obj());
+	// Like Hotspot, a throwable without enough memory for its backtrace is left without one, rather
+	// than replaced with an `OutOfMemoryError`
+	let Throws::Ok(backtrace) = backtrace.into_obj() else {
+		return this;
+	};
+
+	classes::java::lang::Throwable::set_backtrace(this, backtrace);
 	classes::java::lang::Throwable::set_depth(this, backtrace_depth as jint);
 
 	this
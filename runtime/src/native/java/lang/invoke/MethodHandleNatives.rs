@@ -28,7 +28,7 @@ pub fn new_member_name(
 	descriptor: Symbol,
 	callee_class: ClassPtr,
 ) -> Throws<ClassInstanceRef> {
-	let member_name = ClassInstance::new(globals::classes::java_lang_invoke_MemberName());
+	let member_name = ClassInstance::new(globals::classes::java_lang_invoke_MemberName())?;
 
 	classes::java::lang::invoke::MemberName::set_clazz(
 		member_name,
@@ -36,12 +36,12 @@ pub fn new_member_name(
 	);
 	classes::java::lang::invoke::MemberName::set_name(
 		member_name,
-		Reference::class(StringInterner::intern(name)),
+		Reference::class(StringInterner::intern(name)?),
 	);
 	// TODO: Not correct for field members
 	classes::java::lang::invoke::MemberName::set_type(
 		member_name,
-		Reference::class(StringInterner::intern(descriptor)),
+		Reference::class(StringInterner::intern(descriptor)?),
 	);
 
 	Throws::Ok(member_name)
@@ -186,11 +186,11 @@ pub fn resolve_member_name(
 
 			classes::java::lang::invoke::MemberName::set_name(
 				member_name,
-				Reference::class(StringInterner::intern(field.name)),
+				Reference::class(StringInterner::intern(field.name)?),
 			);
 			classes::java::lang::invoke::MemberName::set_type(
 				member_name,
-				Reference::class(StringInterner::intern(&*field.descriptor.as_signature())),
+				Reference::class(StringInterner::intern(&*field.descriptor.as_signature())?),
 			);
 		},
 		ReferenceKind::InvokeVirtual
@@ -253,7 +253,8 @@ pub fn resolve_member_name(
 			}
 
 			// Create the java.lang.invoke.ResolvedMethodName instance
-			let resolved_method_name = classes::java::lang::invoke::ResolvedMethodName::new(method);
+			let resolved_method_name =
+				classes::java::lang::invoke::ResolvedMethodName::new(method)?;
 
 			classes::java::lang::invoke::MemberName::set_method(member_name, resolved_method_name);
 
@@ -281,7 +282,7 @@ enum FieldOrMethod {
 	Method(&'static Method),
 }
 
-fn init_member_name(member_name: ClassInstanceRef, member: FieldOrMethod) {
+fn init_member_name(member_name: ClassInstanceRef, member: FieldOrMethod) -> Throws<()> {
 	let mut flags = 0;
 	let resolved_method;
 	let vmindex;
@@ -319,7 +320,7 @@ fn init_member_name(member_name: ClassInstanceRef, member: FieldOrMethod) {
 				flags |= MethodHandleNatives::MN_CALLER_SENSITIVE;
 			}
 
-			resolved_method = classes::java::lang::invoke::ResolvedMethodName::new(method);
+			resolved_method = classes::java::lang::invoke::ResolvedMethodName::new(method)?;
 			vmindex = method
 				.class()
 				.vtable()
@@ -334,6 +335,8 @@ fn init_member_name(member_name: ClassInstanceRef, member: FieldOrMethod) {
 	classes::java::lang::invoke::MemberName::set_method(member_name, resolved_method);
 	classes::java::lang::invoke::MemberName::set_vmindex(member_name, vmindex as jlong);
 	classes::java::lang::invoke::MemberName::set_clazz(member_name, Reference::mirror(class));
+
+	Throws::Ok(())
 }
 
 // -- MemberName support --
@@ -368,7 +371,10 @@ pub fn init(
 			return;
 		}
 
-		init_member_name(self_.extract_class(), FieldOrMethod::Method(method))
+		handle_exception!(
+			thread,
+			init_member_name(self_.extract_class(), FieldOrMethod::Method(method))
+		);
 	}
 
 	if target_class == globals::classes::java_lang_reflect_Constructor() {
@@ -376,7 +382,10 @@ pub fn init(
 		let slot = classes::java::lang::reflect::Constructor::slot(target);
 
 		let method = &class.target_class().vtable()[slot as usize];
-		init_member_name(self_.extract_class(), FieldOrMethod::Method(method))
+		handle_exception!(
+			thread,
+			init_member_name(self_.extract_class(), FieldOrMethod::Method(method))
+		);
 	}
 }
 
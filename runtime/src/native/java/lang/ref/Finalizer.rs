@@ -1,3 +1,4 @@
+use crate::logging::debug;
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
 
@@ -13,7 +14,8 @@ pub fn isFinalizationEnabled(_: JniEnv, _class: ClassPtr) -> jboolean {
 pub fn reportComplete(
 	_: JniEnv,
 	_class: ClassPtr,
-	_finalizee: Reference, // java.lang.Object
+	finalizee: Reference, // java.lang.Object
 ) {
-	unimplemented!("java.lang.ref.Finalizer#reportComplete")
+	// HotSpot only uses this for finalizer statistics, which we don't keep
+	debug!(TARGETS: (Ref), "Finalization complete for {finalizee:?}");
 }
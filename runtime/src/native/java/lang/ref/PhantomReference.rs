@@ -6,8 +6,6 @@ use ::jni::sys::jboolean;
 
 include_generated!("native/java/lang/ref/def/PhantomReference.definitions.rs");
 
-// TODO: Actual implementations of PhantomReference
-
 pub fn refersTo0(
 	_: JniEnv,
 	this: Reference, // java.lang.ref.PhantomReference
@@ -1,6 +1,8 @@
 use crate::classes;
+use crate::gc::reference_processor;
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
 
 use jni::env::JniEnv;
 use jni::sys::jboolean;
@@ -9,15 +11,16 @@ include_generated!("native/java/lang/ref/def/Reference.definitions.rs");
 
 pub fn getAndClearReferencePendingList(_: JniEnv, _class: ClassPtr) -> Reference /* java.lang.ref.Reference */
 {
-	unimplemented!("java.lang.ref.Reference#getAndClearReferencePendingList")
+	reference_processor::get_and_clear_pending_list()
 }
 
 pub fn hasReferencePendingList(_: JniEnv, _class: ClassPtr) -> jboolean {
-	unimplemented!("java.lang.ref.Reference#hasReferencePendingList")
+	reference_processor::has_pending_list()
 }
 
-pub fn waitForReferencePendingList(_: JniEnv, _class: ClassPtr) {
-	unimplemented!("java.lang.ref.Reference#waitForReferencePendingList")
+pub fn waitForReferencePendingList(env: JniEnv, _class: ClassPtr) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	reference_processor::wait_for_pending_list(thread);
 }
 
 pub fn get0(
@@ -35,6 +38,6 @@ pub fn refersTo0(
 	classes::java::lang::r#ref::Reference::referent(this) == o
 }
 
-pub fn clear0(_: JniEnv, _this: Reference /* java.lang.ref.Reference */) {
-	unimplemented!("java.lang.ref.Reference#clear0")
+pub fn clear0(_: JniEnv, this: Reference /* java.lang.ref.Reference */) {
+	classes::java::lang::r#ref::Reference::set_referent(this, Reference::null());
 }
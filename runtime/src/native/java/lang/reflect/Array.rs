@@ -1,11 +1,17 @@
+use crate::classpath::loader::ClassLoader;
+use crate::objects::boxing;
 use crate::objects::class::ClassPtr;
 use crate::objects::instance::array::{
-	ObjectArrayInstance, PrimitiveArrayInstance, PrimitiveArrayInstanceRef,
+	Array, ObjectArrayInstance, PrimitiveArrayInstance, PrimitiveArrayInstanceRef,
 };
+use crate::objects::instance::object::Object;
 use crate::objects::reference::Reference;
+use crate::symbols::Symbol;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{handle_exception, throw_and_return_null};
+use crate::thread::exceptions::{Throws, handle_exception, throw, throw_and_return_null};
 
+use classfile::FieldType;
+use instructions::Operand;
 use jni::env::JniEnv;
 use jni::sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort};
 
@@ -14,202 +20,378 @@ include_generated!("native/java/lang/reflect/def/Array.definitions.rs");
 /// Maximum number of array dimensions
 pub const MAX_DIM: u8 = 255;
 
+/// Verify that `array` is a non-null array
+fn check_array(array: Reference) -> Throws<()> {
+	if array.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	if !array.is_primitive_array() && !array.is_object_array() {
+		throw!(@DEFER IllegalArgumentException, "Argument is not an array");
+	}
+
+	Throws::Ok(())
+}
+
+/// Verify that `array` is a non-null primitive array, returning it along with its component type
+fn check_primitive_array(array: Reference) -> Throws<(PrimitiveArrayInstanceRef, FieldType)> {
+	check_array(array)?;
+	if !array.is_primitive_array() {
+		throw!(@DEFER IllegalArgumentException, "Argument is not an array of primitive type");
+	}
+
+	let component_type = array.class().unwrap_array_instance().component.clone();
+	Throws::Ok((array.extract_primitive_array(), component_type))
+}
+
+/// Read the element at `index` of a primitive array, widened to `ty`
+fn get_primitive(array: Reference, index: jint, ty: &FieldType) -> Throws<Operand<Reference>> {
+	let (array, component_type) = check_primitive_array(array)?;
+	let value = array.array_get(index)?;
+
+	match boxing::widen(value, &component_type, ty) {
+		Some(value) => Throws::Ok(value),
+		None => throw!(@DEFER IllegalArgumentException, "argument type mismatch"),
+	}
+}
+
+/// Store `value`, of type `ty`, at `index` of a primitive array, widening it to the component type
+fn set_primitive(
+	array: Reference,
+	index: jint,
+	value: Operand<Reference>,
+	ty: &FieldType,
+) -> Throws<()> {
+	let (array, component_type) = check_primitive_array(array)?;
+	if index.is_negative() || index as usize >= array.len() {
+		throw!(@DEFER ArrayIndexOutOfBoundsException);
+	}
+
+	let Some(value) = boxing::widen(value, ty, &component_type) else {
+		throw!(@DEFER IllegalArgumentException, "argument type mismatch");
+	};
+
+	array.store(index, value)
+}
+
+fn get_length(array: Reference) -> Throws<jint> {
+	check_array(array)?;
+	Throws::Ok(array.array_length()? as jint)
+}
+
+fn get_element(thread: &'static JavaThread, array: Reference, index: jint) -> Throws<Reference> {
+	check_array(array)?;
+	if array.is_object_array() {
+		return array.extract_object_array().array_get(index);
+	}
+
+	let (array, component_type) = check_primitive_array(array)?;
+	let value = array.array_get(index)?;
+	boxing::box_primitive(thread, value, &component_type)
+}
+
+fn set_element(array: Reference, index: jint, value: Reference) -> Throws<()> {
+	check_array(array)?;
+	if array.is_object_array() {
+		let array = array.extract_object_array();
+		if index.is_negative() || index as usize >= array.len() {
+			throw!(@DEFER ArrayIndexOutOfBoundsException);
+		}
+
+		let array_class = array.class();
+		let element_class = array_class
+			.loader()
+			.load(array_class.array_component_name())?;
+		if !value.is_null() && !value.is_instance_of(element_class) {
+			throw!(@DEFER IllegalArgumentException, "array element type mismatch");
+		}
+
+		return array.store(index, value);
+	}
+
+	// Primitive arrays never hold `null`, and only accept boxes they can be widened from
+	let Some((ty, value)) = boxing::unbox(value) else {
+		throw!(@DEFER IllegalArgumentException, "argument type mismatch");
+	};
+
+	set_primitive(array, index, value, &ty)
+}
+
 // throws IllegalArgumentException
 pub fn getLength(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
+	array: Reference, // java.lang.Object
 ) -> jint {
-	unimplemented!("java.lang.reflect.Array#getLength");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(0, thread, get_length(array))
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn get(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> Reference /* java.lang.Object */ {
-	unimplemented!("java.lang.reflect.Array#get");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(Reference::null(), thread, get_element(thread, array, index))
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getBoolean(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jboolean {
-	unimplemented!("java.lang.reflect.Array#getBoolean");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(
+		false,
+		thread,
+		get_primitive(array, index, &FieldType::Boolean)
+	);
+	value.expect_int() != 0
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getByte(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jbyte {
-	unimplemented!("java.lang.reflect.Array#getByte");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0, thread, get_primitive(array, index, &FieldType::Byte));
+	value.expect_int() as jbyte
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getChar(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jchar {
-	unimplemented!("java.lang.reflect.Array#getChar");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(
+		0,
+		thread,
+		get_primitive(array, index, &FieldType::Character)
+	);
+	value.expect_int() as jchar
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getShort(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jshort {
-	unimplemented!("java.lang.reflect.Array#getShort");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0, thread, get_primitive(array, index, &FieldType::Short));
+	value.expect_int() as jshort
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getInt(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jint {
-	unimplemented!("java.lang.reflect.Array#getInt");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0, thread, get_primitive(array, index, &FieldType::Integer));
+	value.expect_int()
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getLong(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jlong {
-	unimplemented!("java.lang.reflect.Array#getLong");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0, thread, get_primitive(array, index, &FieldType::Long));
+	value.expect_long()
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getFloat(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jfloat {
-	unimplemented!("java.lang.reflect.Array#getFloat");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0.0, thread, get_primitive(array, index, &FieldType::Float));
+	value.expect_float()
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn getDouble(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
 ) -> jdouble {
-	unimplemented!("java.lang.reflect.Array#getDouble");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let value = handle_exception!(0.0, thread, get_primitive(array, index, &FieldType::Double));
+	value.expect_double()
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn set(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: Reference, // java.lang.Object
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: Reference, // java.lang.Object
 ) {
-	unimplemented!("java.lang.reflect.Array#set");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(thread, set_element(array, index, value));
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setBoolean(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jboolean,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jboolean,
 ) {
-	unimplemented!("java.lang.reflect.Array#setBoolean");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(
+			array,
+			index,
+			Operand::Int(jint::from(value)),
+			&FieldType::Boolean
+		)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setByte(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jbyte,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jbyte,
 ) {
-	unimplemented!("java.lang.reflect.Array#setByte");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(
+			array,
+			index,
+			Operand::Int(jint::from(value)),
+			&FieldType::Byte
+		)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setChar(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jchar,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jchar,
 ) {
-	unimplemented!("java.lang.reflect.Array#setChar");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(
+			array,
+			index,
+			Operand::Int(jint::from(value)),
+			&FieldType::Character
+		)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setShort(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jshort,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jshort,
 ) {
-	unimplemented!("java.lang.reflect.Array#setShort");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(
+			array,
+			index,
+			Operand::Int(jint::from(value)),
+			&FieldType::Short
+		)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setInt(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jint,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jint,
 ) {
-	unimplemented!("java.lang.reflect.Array#setInt");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(array, index, Operand::Int(value), &FieldType::Integer)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setLong(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jlong,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jlong,
 ) {
-	unimplemented!("java.lang.reflect.Array#setLong");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(array, index, Operand::Long(value), &FieldType::Long)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setFloat(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jfloat,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jfloat,
 ) {
-	unimplemented!("java.lang.reflect.Array#setFloat");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(array, index, Operand::Float(value), &FieldType::Float)
+	);
 }
 
 // throws IllegalArgumentException, ArrayIndexOutOfBoundsException
 pub fn setDouble(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_array: Reference, // java.lang.Object
-	_index: jint,
-	_value: jdouble,
+	array: Reference, // java.lang.Object
+	index: jint,
+	value: jdouble,
 ) {
-	unimplemented!("java.lang.reflect.Array#setDouble");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(
+		thread,
+		set_primitive(array, index, Operand::Double(value), &FieldType::Double)
+	);
 }
 
 // throws NegativeArraySizeException
@@ -231,6 +413,10 @@ pub fn newArray(
 
 	let mirror = component_type.extract_mirror();
 	if mirror.is_primitive() {
+		if mirror.primitive_target().is_void() {
+			throw_and_return_null!(thread, IllegalArgumentException);
+		}
+
 		let type_code = mirror
 			.primitive_target()
 			.as_array_type_code()
@@ -254,10 +440,59 @@ pub fn newArray(
 
 // throws IllegalArgumentException, NegativeArraySizeException
 pub fn multiNewArray(
-	_env: JniEnv,
+	env: JniEnv,
 	_class: ClassPtr,
-	_component_type: Reference, // java.lang.Class<?>
-	_dimensions: Reference,     // int[]
+	component_type: Reference, // java.lang.Class<?>
+	dimensions: Reference,     // int[]
 ) -> Reference /* java.lang.Object */ {
-	unimplemented!("java.lang.reflect.Array#multiNewArray");
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	if component_type.is_null() || dimensions.is_null() {
+		throw_and_return_null!(thread, NullPointerException);
+	}
+
+	let dimensions = dimensions.extract_primitive_array();
+	let dimensions = dimensions.as_slice::<jint>();
+	if dimensions.is_empty() || dimensions.len() > MAX_DIM as usize {
+		throw_and_return_null!(thread, IllegalArgumentException);
+	}
+
+	if let Some(negative) = dimensions.iter().find(|count| count.is_negative()) {
+		throw_and_return_null!(thread, NegativeArraySizeException, "{negative}");
+	}
+
+	let array_class = handle_exception!(
+		Reference::null(),
+		thread,
+		multi_array_class(component_type, dimensions.len())
+	);
+
+	let array = ObjectArrayInstance::new_multidimensional(dimensions.iter().copied(), array_class);
+	handle_exception!(Reference::null(), thread, array)
+}
+
+/// Get the array class with `dimensions` more dimensions than the class of `component_type`
+fn multi_array_class(component_type: Reference, dimensions: usize) -> Throws<ClassPtr> {
+	let mirror = component_type.extract_mirror();
+	let brackets = "[".repeat(dimensions);
+
+	if mirror.is_primitive() {
+		let primitive = mirror.primitive_target();
+		if primitive.is_void() {
+			throw!(@DEFER IllegalArgumentException);
+		}
+
+		let name = Symbol::intern(format!("{brackets}{}", primitive.as_signature()));
+		return ClassLoader::bootstrap().load(name);
+	}
+
+	let class = mirror.target_class();
+	if class.is_array()
+		&& usize::from(class.unwrap_array_instance().dimensions) + dimensions > MAX_DIM as usize
+	{
+		throw!(@DEFER IllegalArgumentException);
+	}
+
+	let name = Symbol::intern(format!("{brackets}{}", class.as_signature()));
+	class.loader().load(name)
 }
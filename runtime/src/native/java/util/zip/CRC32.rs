@@ -0,0 +1,26 @@
+use crate::native::intrinsics;
+use crate::objects::class::ClassPtr;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
+
+use ::jni::env::JniEnv;
+use common::int_types::s4;
+
+include_generated!("native/java/util/zip/def/CRC32.definitions.rs");
+
+pub fn update(_env: JniEnv, _class: ClassPtr, crc: s4, b: s4) -> s4 {
+	intrinsics::crc32_update(crc, b)
+}
+
+pub fn updateBytes0(
+	env: JniEnv,
+	_class: ClassPtr,
+	crc: s4,
+	b: Reference, // byte[]
+	off: s4,
+	len: s4,
+) -> s4 {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	handle_exception!(0, thread, intrinsics::crc32_update_bytes(crc, b, off, len))
+}
@@ -0,0 +1,45 @@
+use crate::native::foreign;
+use crate::objects::class::ClassPtr;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
+
+use ::jni::env::JniEnv;
+use ::jni::sys::{jboolean, jint, jlong};
+
+include_generated!("native/jdk/internal/foreign/abi/def/NativeEntryPoint.definitions.rs");
+include_generated!("native/jdk/internal/foreign/abi/def/NativeEntryPoint.registerNatives.rs");
+
+pub fn makeDowncallStub(
+	env: JniEnv,
+	_class: ClassPtr,
+	method_type: Reference,   // java.lang.invoke.MethodType
+	abi: Reference,           // jdk.internal.foreign.abi.ABIDescriptor
+	enc_arg_moves: Reference, // jdk.internal.foreign.abi.VMStorage[]
+	enc_ret_moves: Reference, // jdk.internal.foreign.abi.VMStorage[]
+	needs_return_buffer: jboolean,
+	captured_state_mask: jint,
+	needs_transition: jboolean,
+) -> jlong {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let address = handle_exception!(
+		0,
+		thread,
+		foreign::make_downcall_stub(
+			method_type,
+			abi,
+			enc_arg_moves,
+			enc_ret_moves,
+			needs_return_buffer,
+			captured_state_mask,
+			needs_transition,
+		)
+	);
+	address as jlong
+}
+
+pub fn freeDowncallStub0(_env: JniEnv, _class: ClassPtr, downcall_stub: jlong) -> jboolean {
+	unsafe { foreign::free_downcall_stub(downcall_stub as usize) };
+	true
+}
@@ -0,0 +1,36 @@
+use crate::native::foreign;
+use crate::objects::class::ClassPtr;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
+
+use ::jni::env::JniEnv;
+use ::jni::sys::{jboolean, jlong};
+
+include_generated!("native/jdk/internal/foreign/abi/def/UpcallLinker.definitions.rs");
+include_generated!("native/jdk/internal/foreign/abi/def/UpcallLinker.registerNatives.rs");
+
+pub fn makeUpcallStub(
+	env: JniEnv,
+	_class: ClassPtr,
+	mh: Reference,   // java.lang.invoke.MethodHandle
+	abi: Reference,  // jdk.internal.foreign.abi.ABIDescriptor
+	conv: Reference, // jdk.internal.foreign.abi.UpcallLinker$CallRegs
+	needs_return_buffer: jboolean,
+	return_buffer_size: jlong,
+) -> jlong {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let address = handle_exception!(
+		0,
+		thread,
+		foreign::make_upcall_stub(
+			mh,
+			abi,
+			conv,
+			needs_return_buffer,
+			return_buffer_size as usize,
+		)
+	);
+	address as jlong
+}
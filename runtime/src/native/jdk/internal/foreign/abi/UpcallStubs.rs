@@ -0,0 +1,12 @@
+use crate::native::foreign;
+use crate::objects::class::ClassPtr;
+
+use ::jni::env::JniEnv;
+use ::jni::sys::{jboolean, jlong};
+
+include_generated!("native/jdk/internal/foreign/abi/def/UpcallStubs.definitions.rs");
+include_generated!("native/jdk/internal/foreign/abi/def/UpcallStubs.registerNatives.rs");
+
+pub fn freeUpcallStub0(_env: JniEnv, _class: ClassPtr, addr: jlong) -> jboolean {
+	foreign::free_upcall_stub(addr as usize)
+}
@@ -1,5 +1,10 @@
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+use crate::{cds, classes};
+
+use std::path::PathBuf;
 
 use ::jni::env::JniEnv;
 use ::jni::sys::{jint, jlong};
@@ -7,20 +12,22 @@ use ::jni::sys::{jint, jlong};
 include_generated!("native/jdk/internal/misc/def/CDS.definitions.rs");
 
 pub fn getCDSConfigStatus(_: JniEnv, _class: ClassPtr) -> jint {
-	// TODO: Bitfield of:
-	//     private static final int IS_DUMPING_ARCHIVE              = 1 << 0;
-	//     private static final int IS_DUMPING_STATIC_ARCHIVE       = 1 << 1;
-	//     private static final int IS_LOGGING_LAMBDA_FORM_INVOKERS = 1 << 2;
-	//     private static final int IS_USING_ARCHIVE                = 1 << 3;
-	0
+	cds::config_status()
 }
 
-pub fn logLambdaFormInvoker(_: JniEnv, _class: ClassPtr, _line: Reference) {
-	unimplemented!("jdk.internal.misc.CDS#logLambdaFormInvoker")
+pub fn logLambdaFormInvoker(_: JniEnv, _class: ClassPtr, line: Reference) {
+	if line.is_null() {
+		return;
+	}
+
+	cds::class_list::log_lambda_form_invoker(classes::java::lang::String::extract(
+		line.extract_class(),
+	));
 }
 
 pub fn initializeFromArchive(_: JniEnv, _this_class: ClassPtr, _class: Reference) {
-	// TODO
+	// Heap objects are never archived, so there's nothing to initialize the static fields with.
+	// The callers check for `null` and compute the values themselves.
 }
 
 pub fn defineArchivedModules(
@@ -29,17 +36,56 @@ pub fn defineArchivedModules(
 	_platform_loader: Reference,
 	_system_loader: Reference,
 ) {
-	unimplemented!("jdk.internal.misc.CDS#defineArchivedModules")
+	// Only called when `initializeFromArchive()` provided an archived module graph
+	unreachable!("the module graph is never archived")
 }
 
 pub fn getRandomSeedForDumping(_: JniEnv, _class: ClassPtr) -> jlong {
-	// TODO: https://github.com/openjdk/jdk/blob/af564e46b006fcd57ec7391cd1438b3b9311b1d6/src/hotspot/share/prims/jvm.cpp#L3696
+	// Hotspot only provides a fixed seed when dumping a static archive, so that the archived
+	// `ImmutableCollections` are laid out deterministically. Static archives aren't supported.
 	0
 }
 
-pub fn dumpClassList(_: JniEnv, _class: ClassPtr, _list_file_name: Reference) {
-	unimplemented!("jdk.internal.misc.CDS#dumpClassList")
+pub fn dumpClassList(env: JniEnv, _class: ClassPtr, list_file_name: Reference) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	if let Throws::Exception(e) = dump_class_list(list_file_name) {
+		e.throw(thread);
+	}
 }
-pub fn dumpDynamicArchive(_: JniEnv, _class: ClassPtr, _archive_file_name: Reference) {
-	unimplemented!("jdk.internal.misc.CDS#dumpDynamicArchive")
+
+pub fn dumpDynamicArchive(env: JniEnv, _class: ClassPtr, archive_file_name: Reference) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	if let Throws::Exception(e) = dump_dynamic_archive(archive_file_name) {
+		e.throw(thread);
+	}
+}
+
+fn file_name(name: Reference) -> Throws<PathBuf> {
+	if name.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	Throws::Ok(PathBuf::from(classes::java::lang::String::extract(
+		name.extract_class(),
+	)))
+}
+
+/// The implementation of `CDS#dumpClassList`, shared with `JVM_DumpClassListToFile`
+pub fn dump_class_list(list_file_name: Reference) -> Throws<()> {
+	let path = file_name(list_file_name)?;
+	if let Err(e) = cds::class_list::dump(&path) {
+		throw!(@DEFER IOException, "Unable to write class list {}: {}", path.display(), e);
+	}
+
+	Throws::Ok(())
+}
+
+/// The implementation of `CDS#dumpDynamicArchive`, shared with `JVM_DumpDynamicArchive`
+pub fn dump_dynamic_archive(archive_file_name: Reference) -> Throws<()> {
+	let path = file_name(archive_file_name)?;
+	if let Err(e) = cds::dump_archive(&path) {
+		throw!(@DEFER IOException, "Unable to write shared archive {}: {}", path.display(), e);
+	}
+
+	Throws::Ok(())
 }
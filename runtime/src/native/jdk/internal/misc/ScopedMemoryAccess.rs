@@ -1,15 +1,81 @@
 use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::frame::stack::VisibleStackFrame;
+use crate::thread::pool::ThreadPool;
 
 use ::jni::env::JniEnv;
+use common::int_types::u1;
+use instructions::Operand;
 
 include_generated!("native/jdk/internal/misc/def/ScopedMemoryAccess.definitions.rs");
 include_generated!("native/jdk/internal/misc/def/ScopedMemoryAccess.registerNatives.rs");
 
+/// The number of frames to inspect on each thread
+///
+/// `@Scoped` methods are leaf calls into `Unsafe`, so they can only ever be near the top of the stack.
+const MAX_SCOPED_DEPTH: usize = 10;
+
+/// Whether `thread` is currently within a `@Scoped` method operating on `session`
+fn is_accessing(thread: &JavaThread, session: Reference) -> bool {
+	thread
+		.frame_stack()
+		.iter()
+		.take(MAX_SCOPED_DEPTH)
+		.any(|frame| {
+			let VisibleStackFrame::Regular(frame) = frame else {
+				return false;
+			};
+
+			let method = frame.method();
+			if !method.is_scoped() {
+				return false;
+			}
+
+			// The session is always passed as a parameter
+			let parameter_slots = method.parameter_stack_size() + usize::from(!method.is_static());
+			(0..parameter_slots).any(
+				|index| matches!(frame.local(index as u1), Operand::Reference(local) if local == session),
+			)
+		})
+}
+
 pub fn closeScope0(
-	_env: JniEnv,
-	_this: Reference,    // jdk.internal.misc.ScopedMemoryAccess
-	_session: Reference, // jdk.internal.foreign.MemorySessionImpl
-	_error: Reference,   // jdk.internal.misc.ScopedMemoryAccess.ScopedAccessError
-) -> Reference {
-	unimplemented!("jdk.internal.misc.ScopedMemoryAccess#closeScope0")
+	env: JniEnv,
+	_this: Reference,   // jdk.internal.misc.ScopedMemoryAccess
+	session: Reference, // jdk.internal.foreign.MemorySessionImpl
+	error: Reference,   // jdk.internal.misc.ScopedMemoryAccess.ScopedAccessError
+) {
+	let current = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	// Any thread that is in the middle of accessing the session gets `error` installed, which will
+	// be thrown as soon as it polls.
+	let mut accessing = Vec::new();
+	for thread in ThreadPool::live_threads() {
+		if thread == current {
+			continue;
+		}
+
+		let found = thread.handshake(|target| {
+			if !is_accessing(target, session) {
+				return false;
+			}
+
+			if !target.has_pending_exception() {
+				target.set_pending_exception(error);
+			}
+
+			true
+		});
+
+		if found {
+			accessing.push(thread);
+		}
+	}
+
+	// Threads in native code (e.g. an `Unsafe` copy) don't poll, so the handshake above ran without
+	// them stopping. The memory can't be released until they have all left their scoped methods.
+	while !accessing.is_empty() {
+		std::thread::yield_now();
+		accessing.retain(|thread| thread.handshake(|target| is_accessing(target, session)));
+	}
 }
@@ -1,22 +1,78 @@
 use crate::classes;
-use crate::objects::class::ClassInitializationState;
+use crate::classpath::loader::{ClassLoaderSet, LoaderLink};
+use crate::native::memory;
+use crate::objects::class::{ClassInitializationState, ClassPtr};
+use crate::objects::field::Field;
 use crate::objects::instance::array::{Array, ObjectArrayInstanceRef, PrimitiveType, TypeCode};
 use crate::objects::instance::class::ClassInstance;
 use crate::objects::instance::object::Object;
 use crate::objects::reference::Reference;
+use crate::symbols::Symbol;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{Throws, throw};
+use crate::thread::exceptions::{Throws, handle_exception, throw, throw_and_return_null};
 
+use std::convert::identity;
 use std::marker::PhantomData;
+use std::ptr;
+use std::sync::Mutex;
 use std::sync::atomic::Ordering;
 
 use ::jni::env::JniEnv;
 use ::jni::sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort};
 use common::atomic::{Atomic, AtomicCounterpart};
+use instructions::Operand;
 
 include_generated!("native/jdk/internal/misc/def/Unsafe.definitions.rs");
 include_generated!("native/jdk/internal/misc/def/Unsafe.registerNatives.rs");
 
+/// Marks the offsets handed out by `staticFieldOffset0`
+///
+/// Static fields don't live in the mirror returned by `staticFieldBase0`, they live in the slots of
+/// their class. The low bits of a tagged offset are the field's index into those slots, which keeps
+/// them distinguishable from the offsets of `java.lang.Class`'s own instance fields.
+const STATIC_FIELD_OFFSET_TAG: jlong = 1 << 62;
+
+/// Serializes compare-and-exchange operations on static fields, as their slots can't be updated atomically
+static STATIC_FIELD_CAS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Get the class and slot index of the static field referred to by `object` and `offset`, if any
+fn static_field_slot(object: Reference, offset: jlong) -> Option<(ClassPtr, usize)> {
+	if object.is_null() || !object.is_mirror() || offset & STATIC_FIELD_OFFSET_TAG == 0 {
+		return None;
+	}
+
+	let index = (offset & !STATIC_FIELD_OFFSET_TAG) as usize;
+	Some((object.extract_target_class(), index))
+}
+
+/// Compare-and-exchange for static fields, see [`STATIC_FIELD_CAS_LOCK`]
+fn static_field_compare_exchange(
+	class: ClassPtr,
+	index: usize,
+	expected: Operand<Reference>,
+	new: Operand<Reference>,
+) -> Operand<Reference> {
+	let _guard = STATIC_FIELD_CAS_LOCK.lock().unwrap();
+
+	let current = class.static_field_value_volatile(index);
+	if current == expected {
+		class.set_static_field_volatile(index, new);
+	}
+
+	current
+}
+
+/// Get the address that `offset` refers to within `object`
+///
+/// If `object` is null, the offset is treated as a raw pointer.
+unsafe fn address_of(object: Reference, offset: jlong) -> *mut u8 {
+	if object.is_null() {
+		return offset as *mut u8;
+	}
+
+	unsafe { object.field_base().byte_offset(offset as isize) }
+}
+
 /// Wrapper for unsafe operations
 ///
 /// This does all the work of and performing gets/sets.
@@ -45,7 +101,15 @@ where
 	T: PrimitiveType,
 	T: AtomicCounterpart,
 {
+	fn static_field_slot(&self) -> Option<(ClassPtr, usize)> {
+		static_field_slot(self.object, self.offset as jlong)
+	}
+
 	unsafe fn get(&self) -> T {
+		if let Some((class, index)) = self.static_field_slot() {
+			return T::from_operand(class.static_field_value(index));
+		}
+
 		if self.object.is_null() {
 			let offset = self.offset;
 			let ptr = offset as *const T;
@@ -56,6 +120,10 @@ where
 	}
 
 	unsafe fn get_volatile(&self) -> T {
+		if let Some((class, index)) = self.static_field_slot() {
+			return T::from_operand(class.static_field_value_volatile(index));
+		}
+
 		if self.object.is_null() {
 			let offset = self.offset;
 			let ptr = offset as *const T::Counterpart;
@@ -67,6 +135,10 @@ where
 	}
 
 	unsafe fn put(&self, value: T) {
+		if let Some((class, index)) = self.static_field_slot() {
+			return unsafe { class.set_static_field(index, value.into_operand()) };
+		}
+
 		if self.object.is_null() {
 			let offset = self.offset;
 			let ptr = offset as *mut T;
@@ -81,6 +153,10 @@ where
 	}
 
 	unsafe fn put_volatile(&self, value: T) {
+		if let Some((class, index)) = self.static_field_slot() {
+			return class.set_static_field_volatile(index, value.into_operand());
+		}
+
 		if self.object.is_null() {
 			return unsafe { self.__put_raw_volatile(value) };
 		}
@@ -89,23 +165,24 @@ where
 			return unsafe { self.__put_array_volatile(value) };
 		}
 
-		assert!(self.object.is_class());
+		assert!(self.object.is_class() || self.object.is_mirror());
 		unsafe { self.__put_field_volatile(value) }
 	}
 
 	#[doc(hidden)]
-	unsafe fn __put_raw_volatile(&self, _value: T) {
-		unimplemented!("Volatile raw pointer set")
+	unsafe fn __put_raw_volatile(&self, value: T) {
+		let ptr = self.offset as *const T::Counterpart;
+		unsafe { (&*ptr).store(value, Ordering::Release) }
 	}
 
 	#[doc(hidden)]
-	unsafe fn __put_array_volatile(&self, _value: T) {
-		unimplemented!("Volatile array set")
+	unsafe fn __put_array_volatile(&self, value: T) {
+		unsafe { self.object.atomic_store::<T>(value, self.offset as usize) }
 	}
 
 	#[doc(hidden)]
-	unsafe fn __put_field_volatile(&self, _value: T) {
-		unimplemented!("Volatile field put")
+	unsafe fn __put_field_volatile(&self, value: T) {
+		unsafe { self.object.atomic_store::<T>(value, self.offset as usize) }
 	}
 }
 
@@ -114,10 +191,15 @@ trait UnsafeOpImpl: Sized + AtomicCounterpart {
 
 	unsafe fn get_field_volatile_impl(field_value: *mut Self) -> Self::Output;
 	unsafe fn put_field_impl(field_value: *mut Self, value: Self::Output);
+
+	/// Convert the value of a static field slot
+	fn from_operand(operand: Operand<Reference>) -> Self::Output;
+	/// Convert to the value of a static field slot
+	fn into_operand(self) -> Operand<Reference>;
 }
 
 macro_rules! unsafe_ops {
-	($($ty:ident => $operand_ty:ident),+) => {
+	($($ty:ident => $operand_ty:ident ($convert_from:expr, $convert_into:expr)),+) => {
 		paste::paste! {
 			$(
 			impl UnsafeOpImpl for [<j $ty>] {
@@ -136,6 +218,14 @@ macro_rules! unsafe_ops {
 					let old = unsafe { field_value.replace(value) };
 					drop(old);
 				}
+
+				fn from_operand(operand: Operand<Reference>) -> Self::Output {
+					($convert_from)(operand.[<expect_ $operand_ty>]())
+				}
+
+				fn into_operand(self) -> Operand<Reference> {
+					Operand::[<$operand_ty:camel>](($convert_into)(self))
+				}
 			}
 			)+
 		}
@@ -143,14 +233,14 @@ macro_rules! unsafe_ops {
 }
 
 unsafe_ops! {
-	boolean => int,
-	byte => int,
-	short => int,
-	char => int,
-	int => int,
-	long => long,
-	float => float,
-	double => double
+	boolean => int (|value| value != 0, jint::from),
+	byte => int (|value| value as jbyte, jint::from),
+	short => int (|value| value as jshort, jint::from),
+	char => int (|value| value as jchar, jint::from),
+	int => int (identity, identity),
+	long => long (identity, identity),
+	float => float (identity, identity),
+	double => double (identity, identity)
 }
 
 pub fn getUncompressedObject(
@@ -182,16 +272,48 @@ pub fn writebackPostSync0(
 }
 
 pub fn defineClass0(
-	_env: JniEnv,
-	_this: Reference,  // jdk.internal.misc.Unsafe
-	_name: Reference,  // java.lang.String
-	_bytes: Reference, // [B
-	_offset: jint,
-	_length: jint,
-	_loader: Reference,            // java.lang.ClassLoader
-	_protection_domain: Reference, // java.security.ProtectionDomain
+	env: JniEnv,
+	_this: Reference, // jdk.internal.misc.Unsafe
+	name: Reference,  // java.lang.String
+	bytes: Reference, // [B
+	offset: jint,
+	length: jint,
+	loader: Reference,            // java.lang.ClassLoader
+	protection_domain: Reference, // java.security.ProtectionDomain
 ) -> Reference {
-	unimplemented!("jdk.internal.misc.Unsafe#defineClass0")
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	if bytes.is_null() {
+		throw_and_return_null!(thread, NullPointerException);
+	}
+
+	let bytes = bytes.extract_primitive_array();
+	let bytes = bytes.as_slice::<jbyte>();
+	if offset < 0 || length < 0 || offset as usize + length as usize > bytes.len() {
+		throw_and_return_null!(thread, ArrayIndexOutOfBoundsException);
+	}
+
+	let bytes = &bytes[offset as usize..(offset + length) as usize];
+	let buf = unsafe {
+		// SAFETY: `i8` and `u8` have the same size and alignment
+		std::slice::from_raw_parts(bytes.as_ptr().cast::<u8>(), bytes.len())
+	};
+
+	let name = if name.is_null() {
+		None
+	} else {
+		let name = classes::java::lang::String::extract(name.extract_class());
+		Some(Symbol::intern(name.replace('.', "/")))
+	};
+
+	let loader = ClassLoaderSet::find_or_add(loader, false);
+	match loader.define_class(name, buf, protection_domain, LoaderLink::Normal) {
+		Throws::Ok(class) => Reference::mirror(class.mirror()),
+		Throws::Exception(e) => {
+			e.throw(thread);
+			Reference::null()
+		},
+	}
 }
 
 // Creates an instance of `class` without running its constructor, and initializes the class
@@ -211,7 +333,11 @@ pub fn allocateInstance(
 		return Reference::null();
 	}
 
-	Reference::class(ClassInstance::new(target))
+	Reference::class(handle_exception!(
+		Reference::null(),
+		thread,
+		ClassInstance::new(target)
+	))
 }
 
 pub fn throwException(
@@ -241,6 +367,16 @@ pub fn compareAndExchangeInt(
 	expected: jint,
 	value: jint,
 ) -> jint {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return static_field_compare_exchange(
+			class,
+			index,
+			Operand::Int(expected),
+			Operand::Int(value),
+		)
+		.expect_int();
+	}
+
 	if object.is_null() {
 		let atomic = unsafe {
 			let raw = offset as *const <jint as AtomicCounterpart>::Counterpart;
@@ -273,6 +409,16 @@ pub fn compareAndExchangeLong(
 	expected: jlong,
 	value: jlong,
 ) -> jlong {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return static_field_compare_exchange(
+			class,
+			index,
+			Operand::Long(expected),
+			Operand::Long(value),
+		)
+		.expect_long();
+	}
+
 	if object.is_null() {
 		let atomic = unsafe {
 			let raw = offset as *const <jlong as AtomicCounterpart>::Counterpart;
@@ -305,6 +451,16 @@ pub fn compareAndExchangeReference(
 	expected: Reference, // Object
 	value: Reference,    // Object
 ) -> Reference {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return static_field_compare_exchange(
+			class,
+			index,
+			Operand::Reference(expected),
+			Operand::Reference(value),
+		)
+		.expect_reference();
+	}
+
 	let expected_usize = expected.raw_tagged() as usize;
 	let value_usize = value.raw_tagged() as usize;
 
@@ -321,6 +477,10 @@ pub fn getReference(
 	object: Reference, // Object
 	offset: jlong,
 ) -> Reference /* Object */ {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return class.static_field_value(index).expect_reference();
+	}
+
 	unsafe { object.get::<Reference>(offset as usize) }
 }
 
@@ -331,6 +491,10 @@ pub fn putReference(
 	offset: jlong,
 	value: Reference, // Object
 ) {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return unsafe { class.set_static_field(index, Operand::Reference(value)) };
+	}
+
 	unsafe { object.put::<Reference>(value, offset as usize) }
 }
 
@@ -340,6 +504,10 @@ pub fn getReferenceVolatile(
 	object: Reference, // Object
 	offset: jlong,
 ) -> Reference /* Object */ {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return class.static_field_value_volatile(index).expect_reference();
+	}
+
 	// SAFETY: Assuming that the caller provided the offset to a real, tagged reference
 	unsafe {
 		let raw_tagged_ref = object.atomic_get::<usize>(offset as usize);
@@ -354,6 +522,10 @@ pub fn putReferenceVolatile(
 	offset: jlong,
 	value: Reference, // java.lang.Object
 ) {
+	if let Some((class, index)) = static_field_slot(object, offset) {
+		return class.set_static_field_volatile(index, Operand::Reference(value));
+	}
+
 	unsafe { object.atomic_store::<usize>(value.raw_tagged() as usize, offset as usize) }
 }
 
@@ -437,37 +609,43 @@ pub fn fullFence(
 pub fn allocateMemory0(
 	_env: JniEnv,
 	_this: Reference, // jdk.internal.misc.Unsafe
-	_bytes: jlong,
+	bytes: jlong,
 ) -> jlong {
-	unimplemented!("jdk.internal.misc.Unsafe#allocateMemory0")
+	// `Unsafe#allocateMemory` throws its own `OutOfMemoryError` for a null address
+	memory::allocate(bytes as usize).map_or(0, |address| address as jlong)
 }
 
 pub fn reallocateMemory0(
 	_env: JniEnv,
 	_this: Reference, // jdk.internal.misc.Unsafe
-	_address: jlong,
-	_bytes: jlong,
+	address: jlong,
+	bytes: jlong,
 ) -> jlong {
-	unimplemented!("jdk.internal.misc.Unsafe#reallocateMemory0")
+	// `Unsafe#reallocateMemory` throws its own `OutOfMemoryError` for a null address
+	unsafe { memory::reallocate(address as *mut u8, bytes as usize) }
+		.map_or(0, |address| address as jlong)
 }
 
 pub fn freeMemory0(
 	_env: JniEnv,
 	_this: Reference, // jdk.internal.misc.Unsafe
-	_address: jlong,
+	address: jlong,
 ) {
-	unimplemented!("jdk.internal.misc.Unsafe#freeMemory0")
+	unsafe { memory::free(address as *mut u8) }
 }
 
 pub fn setMemory0(
 	_env: JniEnv,
-	_this: Reference,   // jdk.internal.misc.Unsafe
-	_object: Reference, // Object
-	_offset: jlong,
-	_bytes: jlong,
-	_value: jbyte,
+	_this: Reference,  // jdk.internal.misc.Unsafe
+	object: Reference, // Object
+	offset: jlong,
+	bytes: jlong,
+	value: jbyte,
 ) {
-	unimplemented!("jdk.internal.misc.Unsafe#setMemory0")
+	unsafe {
+		let dest = address_of(object, offset);
+		dest.write_bytes(value as u8, bytes as usize);
+	}
 }
 
 pub fn copyMemory0(
@@ -479,45 +657,79 @@ pub fn copyMemory0(
 	dest_offset: jlong,
 	bytes: jlong,
 ) {
-	let size = bytes as usize;
-
-	if src_base.is_primitive_array() {
-		let src_base = src_base.extract_primitive_array();
-		let dest_base = dest_base.extract_primitive_array();
-
-		unsafe { src_base.copy_into(src_offset as usize, &dest_base, dest_offset as usize, size) }
-		return;
+	unsafe {
+		let src = address_of(src_base, src_offset);
+		let dest = address_of(dest_base, dest_offset);
+		ptr::copy(src, dest, bytes as usize);
 	}
-
-	todo!()
-	// let src_base_ptr;
-	// let dest_base_ptr;
-	// unsafe {
-	// 	src_base_ptr = src_base.get(src_offset as usize);
-	// 	dest_base_ptr = dest_base.get(dest_offset as usize);
-	// 	src_base_ptr.copy_to(dest_base_ptr, size);
-	// }
 }
 
 pub fn copySwapMemory0(
 	_env: JniEnv,
-	_this: Reference,     // jdk.internal.misc.Unsafe
-	_src_base: Reference, // Object
-	_src_offset: jlong,
-	_dest_base: Reference, // Object,
-	_dest_offset: jlong,
-	_bytes: jlong,
-	_elem_size: jlong,
+	_this: Reference,    // jdk.internal.misc.Unsafe
+	src_base: Reference, // Object
+	src_offset: jlong,
+	dest_base: Reference, // Object,
+	dest_offset: jlong,
+	bytes: jlong,
+	elem_size: jlong,
 ) {
-	unimplemented!("jdk.internal.misc.Unsafe#copyMemory0")
+	fn copy_swap<T: Copy>(src: *const T, dest: *mut T, count: usize, swap: fn(T) -> T) {
+		let copy_one = |index: usize| unsafe {
+			let value = src.add(index).read_unaligned();
+			dest.add(index).write_unaligned(swap(value));
+		};
+
+		// The regions may overlap, copy in the direction that never reads an already written element
+		if dest.cast_const() > src {
+			(0..count).rev().for_each(copy_one);
+		} else {
+			(0..count).for_each(copy_one);
+		}
+	}
+
+	let (src, dest) = unsafe {
+		(
+			address_of(src_base, src_offset),
+			address_of(dest_base, dest_offset),
+		)
+	};
+
+	// Checked in the JDK
+	let count = (bytes / elem_size) as usize;
+	match elem_size {
+		2 => copy_swap(src.cast::<u16>(), dest.cast(), count, u16::swap_bytes),
+		4 => copy_swap(src.cast::<u32>(), dest.cast(), count, u32::swap_bytes),
+		8 => copy_swap(src.cast::<u64>(), dest.cast(), count, u64::swap_bytes),
+		_ => unreachable!("invalid element size: {elem_size}"),
+	}
+}
+
+/// Get the VM field for a `java.lang.reflect.Field`, verifying that its staticness matches `is_static`
+fn reflected_field(field: Reference, is_static: bool) -> Throws<&'static Field> {
+	if field.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let Some(field) = classes::java::lang::reflect::Field::vmtarget(field.extract_class()) else {
+		throw!(@DEFER InternalError, "field not found");
+	};
+
+	if field.is_static() != is_static {
+		throw!(@DEFER IllegalArgumentException);
+	}
+
+	Throws::Ok(field)
 }
 
 pub fn objectFieldOffset0(
-	_env: JniEnv,
-	_this: Reference,  // jdk.internal.misc.Unsafe
-	_field: Reference, // java.lang.reflect.Field
+	env: JniEnv,
+	_this: Reference, // jdk.internal.misc.Unsafe
+	field: Reference, // java.lang.reflect.Field
 ) -> jlong {
-	unimplemented!("jdk.internal.misc.Unsafe#objectFieldOffset0")
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let field = handle_exception!(0, thread, reflected_field(field, false));
+	field.offset() as jlong
 }
 
 pub fn knownObjectFieldOffset0(
@@ -549,19 +761,23 @@ pub fn knownObjectFieldOffset0(
 }
 
 pub fn staticFieldOffset0(
-	_env: JniEnv,
-	_this: Reference,  // jdk.internal.misc.Unsafe
-	_field: Reference, // java.lang.reflect.Field
+	env: JniEnv,
+	_this: Reference, // jdk.internal.misc.Unsafe
+	field: Reference, // java.lang.reflect.Field
 ) -> jlong {
-	unimplemented!("jdk.internal.misc.Unsafe#staticFieldOffset0")
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let field = handle_exception!(0, thread, reflected_field(field, true));
+	STATIC_FIELD_OFFSET_TAG | field.index() as jlong
 }
 
 pub fn staticFieldBase0(
-	_env: JniEnv,
-	_this: Reference,  // jdk.internal.misc.Unsafe
-	_field: Reference, // java.lang.reflect.Field
+	env: JniEnv,
+	_this: Reference, // jdk.internal.misc.Unsafe
+	field: Reference, // java.lang.reflect.Field
 ) -> Reference /* java.lang.Object */ {
-	unimplemented!("jdk.internal.misc.Unsafe#staticFieldBase0")
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	let field = handle_exception!(Reference::null(), thread, reflected_field(field, true));
+	Reference::mirror(field.class.mirror())
 }
 pub fn shouldBeInitialized0(
 	_env: JniEnv,
@@ -642,9 +858,22 @@ pub fn arrayIndexScale0(
 }
 pub fn getLoadAverage0(
 	_env: JniEnv,
-	_this: Reference,    // jdk.internal.misc.Unsafe
-	_loadavg: Reference, // [D
-	_nelems: jint,
+	_this: Reference,   // jdk.internal.misc.Unsafe
+	loadavg: Reference, // [D
+	nelems: jint,
 ) -> jint {
-	unimplemented!("jdk.internal.misc.Unsafe#getLoadAverage0")
+	let mut averages = [0.0; 3];
+
+	// Checked in the JDK, `nelems` is within 1..=3
+	let averages = &mut averages[..nelems as usize];
+	let Some(count) = platform::mem::load_average(averages) else {
+		return -1;
+	};
+
+	let loadavg = loadavg.extract_primitive_array();
+	for (index, average) in averages[..count].iter().enumerate() {
+		unsafe { loadavg.put::<jdouble>(*average, index * size_of::<jdouble>()) }
+	}
+
+	count as jint
 }
@@ -1,5 +1,9 @@
+use crate::classes;
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
+use crate::options::JvmOptions;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
 
 use ::jni::env::JniEnv;
 use common::int_types::s8;
@@ -11,23 +15,60 @@ pub fn latestUserDefinedLoader0(_env: JniEnv, _class: ClassPtr) -> Reference /*
 	unimplemented!("jdk.internal.misc.VM#latestUserDefinedLoader0")
 }
 pub fn getuid(_env: JniEnv, _class: ClassPtr) -> s8 {
-	unimplemented!("jdk.internal.misc.VM#getuid")
+	#[cfg(unix)]
+	{
+		s8::from(unsafe { libc::getuid() })
+	}
+	// Same as the JDK, -1 means the function isn't available
+	#[cfg(not(unix))]
+	{
+		-1
+	}
 }
 pub fn geteuid(_env: JniEnv, _class: ClassPtr) -> s8 {
-	unimplemented!("jdk.internal.misc.VM#geteuid")
+	#[cfg(unix)]
+	{
+		s8::from(unsafe { libc::geteuid() })
+	}
+	#[cfg(not(unix))]
+	{
+		-1
+	}
 }
 pub fn getgid(_env: JniEnv, _class: ClassPtr) -> s8 {
-	unimplemented!("jdk.internal.misc.VM#getgid")
+	#[cfg(unix)]
+	{
+		s8::from(unsafe { libc::getgid() })
+	}
+	#[cfg(not(unix))]
+	{
+		-1
+	}
 }
 pub fn getegid(_env: JniEnv, _class: ClassPtr) -> s8 {
-	unimplemented!("jdk.internal.misc.VM#getegid")
+	#[cfg(unix)]
+	{
+		s8::from(unsafe { libc::getegid() })
+	}
+	#[cfg(not(unix))]
+	{
+		-1
+	}
 }
 pub fn getNanoTimeAdjustment(_env: JniEnv, _class: ClassPtr, _offset: s8) -> s8 {
 	unimplemented!("jdk.internal.misc.VM#getNanoTimeAdjustment")
 }
-pub fn getRuntimeArguments(_env: JniEnv, _class: ClassPtr) -> Reference /* String[] */
+pub fn getRuntimeArguments(env: JniEnv, _class: ClassPtr) -> Reference /* String[] */
 {
-	unimplemented!("jdk.internal.misc.VM#getRuntimeArguments")
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let arguments = JvmOptions::get().vm_arguments.iter().map(String::as_str);
+	let arguments = handle_exception!(
+		Reference::null(),
+		thread,
+		classes::java::lang::String::new_array(arguments)
+	);
+	Reference::object_array(arguments)
 }
 pub fn initialize(_env: JniEnv, _class: ClassPtr) {
 	// https://github.com/openjdk/jdk/blob/7abe26935ab4356de54acee93390a0d8be1ea289/src/java.base/share/native/libjava/VM.c#L44
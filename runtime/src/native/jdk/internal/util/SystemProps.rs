@@ -73,8 +73,22 @@ pub mod Raw {
 
 		let mut index = 0;
 		for (key, val) in SYSTEM_PROPERTIES.lock().unwrap().iter() {
-			let interned_key_string = StringInterner::intern(&**key);
-			let interned_value_string = StringInterner::intern(&**val);
+			let (interned_key_string, interned_value_string);
+			match (
+				StringInterner::intern(&**key),
+				StringInterner::intern(&**val),
+			) {
+				(Throws::Ok(key), Throws::Ok(val)) => {
+					interned_key_string = key;
+					interned_value_string = val;
+				},
+				(Throws::Exception(e), _) | (_, Throws::Exception(e)) => {
+					let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+					e.throw(thread);
+					return Reference::null();
+				},
+			}
+
 			if let Throws::Exception(e) =
 				prop_array.store(index, Reference::class(interned_key_string))
 			{
@@ -104,7 +118,15 @@ pub mod Raw {
 			($prop_array:ident; $($index:expr => $value:expr),+ $(,)?) => {
 				$(
 				if let Some(val) = Option::<String>::from($value) {
-					let interned_string = StringInterner::intern(&*val);
+					let interned_string;
+					match StringInterner::intern(&*val) {
+						Throws::Ok(string) => interned_string = string,
+						Throws::Exception(e) => {
+							let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+							e.throw(thread);
+							return Reference::null();
+						},
+					}
 					if let Throws::Exception(e) = $prop_array.store($index, Reference::class(interned_string)) {
 						let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 						e.throw(thread);
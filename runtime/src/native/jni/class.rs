@@ -1,5 +1,6 @@
-use super::{IntoJni, reference_from_jobject};
-use crate::classpath::loader::ClassLoader;
+use super::{IntoJni, reference_from_jobject, reference_from_jobject_maybe_null};
+use crate::classpath::loader::{ClassLoader, ClassLoaderSet, LoaderLink};
+use crate::native::RawSymbolExt;
 use crate::objects::class::Class;
 use crate::objects::reference::Reference;
 use crate::symbols::Symbol;
@@ -18,7 +19,41 @@ pub unsafe extern "system" fn DefineClass(
 	buf: *const jbyte,
 	len: jsize,
 ) -> jclass {
-	unimplemented!("jni::DefineClass")
+	let thread = JavaThread::current();
+	assert_eq!(thread.env().raw(), env);
+
+	let name = if name.is_null() {
+		None
+	} else {
+		Some(unsafe { Symbol::intern_mutf_c_str(name) })
+	};
+
+	// Same as a truncated class file
+	let Ok(len) = usize::try_from(len) else {
+		throw_with_ret!(
+			core::ptr::null::<Reference>() as jclass,
+			thread,
+			ClassFormatError,
+			"Truncated class file"
+		);
+	};
+
+	let buf = unsafe {
+		// SAFETY: `i8` and `u8` have the same size and alignment
+		let ptr = buf.cast::<u8>();
+		core::slice::from_raw_parts(ptr, len)
+	};
+
+	let loader = unsafe { reference_from_jobject_maybe_null(loader) };
+	let loader = ClassLoaderSet::find_or_add(loader, false);
+
+	match loader.define_class(name, buf, Reference::null(), LoaderLink::Normal) {
+		Throws::Ok(class) => class.into_jni(),
+		Throws::Exception(e) => {
+			e.throw(thread);
+			core::ptr::null::<Reference>() as jclass
+		},
+	}
 }
 
 #[unsafe(no_mangle)]
@@ -3,7 +3,7 @@ use crate::native::jni::reference_from_jobject;
 use crate::objects::reference::Reference;
 use crate::symbols::sym;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::Throws;
+use crate::thread::exceptions::{Throws, handle_exception};
 use crate::{classes, java_call};
 
 use core::ffi::c_char;
@@ -72,7 +72,7 @@ pub unsafe extern "system" fn ThrowNew(
 	match constructor {
 		Throws::Ok(constructor) => match message {
 			Some(message) => {
-				let string = StringInterner::intern(message);
+				let string = handle_exception!(JNI_ERR, thread, StringInterner::intern(message));
 				java_call!(
 					thread,
 					constructor,
@@ -119,8 +119,17 @@ pub extern "system" fn ExceptionClear(env: *mut JNIEnv) {
 }
 
 #[unsafe(no_mangle)]
-pub extern "system" fn FatalError(env: *mut JNIEnv, msg: *const c_char) -> ! {
-	unimplemented!("jni::FatalError");
+pub unsafe extern "system" fn FatalError(env: *mut JNIEnv, msg: *const c_char) -> ! {
+	let msg = if msg.is_null() {
+		String::new()
+	} else {
+		unsafe { std::ffi::CStr::from_ptr(msg) }
+			.to_string_lossy()
+			.into_owned()
+	};
+
+	eprintln!("FATAL ERROR in native method: {msg}");
+	crate::shutdown::abort()
 }
 
 #[unsafe(no_mangle)]
@@ -39,9 +39,10 @@ pub unsafe extern "system" fn DestroyJavaVM(vm: *mut JavaVM) -> jint {
 		return ret;
 	}
 
-	// TODO: Also need to cleanup daemon threads when supported
 	{
-		JavaThread::current().exit(true)
+		let thread = JavaThread::current();
+		crate::shutdown::destroy_java_vm(thread);
+		thread.exit(true);
 	}
 
 	// SAFETY: No active references to the thread exist now
@@ -134,12 +135,7 @@ pub unsafe extern "system" fn JNI_CreateJavaVM(
 		// classes aren't loaded or the thread isn't initialized. Either way, we just print the
 		// little information we have.
 		Err(InitializationError::EarlyExceptionThrown(exception)) => {
-			eprintln!("Error occurred during initialization of VM");
-			eprintln!("{exception}");
-
-			// If a VM was created and initialized to the point that an exception was thrown,
-			// the entire process just gets aborted like Hotspot.
-			std::process::abort();
+			initialization::abort_during_initialization(&exception);
 		},
 		// Same as above, except we actually have an initialized thread and some classes loaded
 		Err(InitializationError::Other(JniError::ExceptionThrown)) => {
@@ -152,7 +148,7 @@ pub unsafe extern "system" fn JNI_CreateJavaVM(
 				);
 			}
 
-			std::process::abort();
+			crate::shutdown::abort();
 		},
 		Err(InitializationError::Other(e)) => {
 			// Otherwise, the error can just be returned to the caller
@@ -221,19 +217,19 @@ fn attach_current_thread_impl(
 		return JNI_ERR;
 	};
 
-	thread.attach_thread_obj(
+	if let Throws::Exception(_) = thread.attach_thread_obj(
 		name.as_deref(),
 		group.unwrap_or_else(crate::globals::threads::main_thread_group),
 		daemon,
-	);
+	) {
+		return JNI_ERR;
+	}
 
 	JavaThread::set_current_thread(thread);
 	unsafe {
 		*penv = thread.env().raw().cast();
 	}
 
-	thread.start();
-
 	JNI_OK
 }
 
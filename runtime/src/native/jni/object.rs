@@ -1,6 +1,8 @@
 use crate::native::jni::{IntoJni, reference_from_jobject};
 use crate::objects::instance::class::ClassInstance;
 use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
 use jni::sys::{JNIEnv, jboolean, jclass, jmethodID, jobject, jobjectRefType, jvalue, va_list};
 use std::ptr;
 
@@ -51,7 +53,12 @@ pub unsafe extern "system" fn NewObjectA(
 	};
 
 	let class = class_obj.extract_target_class();
-	let obj = Reference::class(ClassInstance::new(class));
+	let thread = JavaThread::current();
+	let obj = Reference::class(handle_exception!(
+		ptr::null_mut(),
+		thread,
+		ClassInstance::new(class)
+	));
 
 	let mut args_with_receiver = vec![jvalue { l: obj.into_jni() }];
 	for i in 0usize.. {
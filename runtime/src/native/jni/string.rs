@@ -3,7 +3,7 @@ use crate::native::java::lang::String::LATIN1;
 use crate::native::jni::{IntoJni, ReferenceJniExt, reference_from_jobject};
 use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{Throws, throw};
+use crate::thread::exceptions::{Throws, handle_exception, throw};
 
 use ::jni::sys::{JNIEnv, jboolean, jchar, jsize, jstring};
 use common::unicode;
@@ -22,7 +22,13 @@ pub unsafe extern "system" fn NewString(
 	// SAFETY: Have the trust that the caller gave us a valid buffer
 	let value = unsafe { slice::from_raw_parts(unicode, len as usize) };
 
-	Reference::class(classes::java::lang::String::new(value)).into_jstring()
+	let thread = JavaThread::current();
+	let string = handle_exception!(
+		ptr::null_mut(),
+		thread,
+		classes::java::lang::String::new(value)
+	);
+	Reference::class(string).into_jstring()
 }
 
 #[unsafe(no_mangle)]
@@ -69,7 +75,12 @@ pub unsafe extern "system" fn NewStringUTF(env: *mut JNIEnv, utf: *const c_char)
 		return ptr::null_mut();
 	};
 
-	let new_string = classes::java::lang::String::new(utf_8);
+	let thread = JavaThread::current();
+	let new_string = handle_exception!(
+		ptr::null_mut(),
+		thread,
+		classes::java::lang::String::new(utf_8)
+	);
 	Reference::class(new_string).into_jni() as _
 }
 
@@ -1,5 +1,12 @@
 #![native_macros::jni_fn_module]
 
+use crate::classes;
+use crate::native::jni::{IntoJni, reference_from_jobject};
+use crate::objects::reference::Reference;
+use crate::options::JvmOptions;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::handle_exception;
+
 use jni::env::JniEnv;
 use jni::objects::{JClass, JObject};
 use jni::sys::jboolean;
@@ -9,12 +16,33 @@ use native_macros::jni_call;
 pub extern "C" fn JVM_DesiredAssertionStatus(
 	_env: JniEnv,
 	_unused: JClass,
-	_class: JClass,
+	class: JClass,
 ) -> jboolean {
-	todo!()
+	let mirror = unsafe { reference_from_jobject(class.raw()) }
+		.expect("class should not be null")
+		.extract_mirror();
+
+	// `Class#desiredAssertionStatus` asks the VM about any class without a loader, which includes
+	// the primitive classes
+	if mirror.is_primitive() || mirror.is_array() {
+		return false;
+	}
+
+	let class = mirror.target_class();
+	let is_system_class = class.loader().is_bootstrap();
+	JvmOptions::get()
+		.assertions
+		.desired_status(class.external_name().as_str(), is_system_class)
 }
 
 #[jni_call]
-pub extern "C" fn JVM_AssertionStatusDirectives(_env: JniEnv, _unused: JClass) -> JObject {
-	todo!()
+pub extern "C" fn JVM_AssertionStatusDirectives(env: JniEnv, _unused: JClass) -> JObject {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let directives = handle_exception!(
+		JObject::null(),
+		thread,
+		classes::java::lang::AssertionStatusDirectives::new(thread, &JvmOptions::get().assertions)
+	);
+	Reference::class(directives).into_jni_safe()
 }
@@ -1,5 +1,18 @@
 #![native_macros::jni_fn_module]
 
+use crate::cds::class_list::{self, LambdaProxy};
+use crate::native::java::lang::invoke::MethodHandleNatives::{
+	self, MN_REFERENCE_KIND_MASK, MN_REFERENCE_KIND_SHIFT,
+};
+use crate::native::jdk::internal::misc::CDS;
+use crate::native::jni::reference_from_jobject;
+use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, handle_exception, throw};
+use crate::{cds, classes, globals};
+
+use classfile::constant_pool::types::ReferenceKind;
 use jni::env::JniEnv;
 use jni::objects::{JClass, JObject, JString};
 use jni::sys::{jboolean, jint, jlong};
@@ -7,21 +20,113 @@ use native_macros::jni_call;
 
 #[jni_call]
 pub extern "C" fn JVM_InitializeFromArchive(_env: JniEnv, _cls: JClass) {
-	todo!()
+	// Heap objects are never archived, see `CDS#initializeFromArchive`
 }
 
 #[jni_call]
 pub extern "C" fn JVM_RegisterLambdaProxyClassForArchiving(
-	_env: JniEnv,
-	_caller: JClass,
-	_interface_method_name: JString,
-	_factory_type: JObject,
-	_interface_method_type: JObject,
-	_implementation_member: JObject,
-	_dynamic_method_type: JObject,
+	env: JniEnv,
+	caller: JClass,
+	interface_method_name: JString,
+	factory_type: JObject,
+	interface_method_type: JObject,
+	implementation_member: JObject,
+	dynamic_method_type: JObject,
 	_lambda_proxy_class: JClass,
 ) {
-	todo!()
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let [
+		caller,
+		interface_method_name,
+		factory_type,
+		interface_method_type,
+		implementation_member,
+		dynamic_method_type,
+	] = [
+		caller.raw(),
+		interface_method_name.raw(),
+		factory_type.raw(),
+		interface_method_type.raw(),
+		implementation_member.raw(),
+		dynamic_method_type.raw(),
+	]
+	.map(|obj| unsafe { reference_from_jobject(obj) }.unwrap_or_else(Reference::null));
+
+	// The proxy class itself can't be archived, it's hidden and only makes sense alongside its
+	// caller. All that's kept is enough information to spin it up again, for the class list.
+	let proxy = handle_exception!(
+		thread,
+		lambda_proxy(
+			caller,
+			interface_method_name,
+			factory_type,
+			interface_method_type,
+			implementation_member,
+			dynamic_method_type,
+		)
+	);
+	class_list::register_lambda_proxy(proxy);
+}
+
+fn lambda_proxy(
+	caller: Reference,
+	interface_method_name: Reference,
+	factory_type: Reference,
+	interface_method_type: Reference,
+	implementation_member: Reference,
+	dynamic_method_type: Reference,
+) -> Throws<LambdaProxy> {
+	if caller.is_null() || interface_method_name.is_null() || implementation_member.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let member = implementation_member.extract_class();
+
+	let flags = classes::java::lang::invoke::MemberName::flags(member);
+	let Some(implementation_kind) =
+		ReferenceKind::from_u8(((flags >> MN_REFERENCE_KIND_SHIFT) & MN_REFERENCE_KIND_MASK) as u8)
+	else {
+		throw!(@DEFER InternalError, "obsolete MemberName format");
+	};
+
+	let implementation_class = classes::java::lang::invoke::MemberName::clazz(member)?
+		.target_class()
+		.name();
+
+	let member_name = classes::java::lang::invoke::MemberName::name(member);
+	if member_name.is_null() {
+		throw!(@DEFER InternalError, "mname not resolved");
+	}
+
+	let member_type = classes::java::lang::invoke::MemberName::type_(member);
+	let implementation_type = if member_type.is_instance_of(globals::classes::java_lang_String()) {
+		classes::java::lang::String::extract(member_type.extract_class())
+	} else {
+		MethodHandleNatives::method_type_signature(member_type)?
+			.as_str()
+			.to_string()
+	};
+
+	Throws::Ok(LambdaProxy {
+		caller: caller.extract_target_class().name().as_str().to_string(),
+		interface_method_name: classes::java::lang::String::extract(
+			interface_method_name.extract_class(),
+		),
+		factory_type: MethodHandleNatives::method_type_signature(factory_type)?
+			.as_str()
+			.to_string(),
+		interface_method_type: MethodHandleNatives::method_type_signature(interface_method_type)?
+			.as_str()
+			.to_string(),
+		implementation_kind,
+		implementation_class: implementation_class.as_str().to_string(),
+		implementation_name: classes::java::lang::String::extract(member_name.extract_class()),
+		implementation_type,
+		dynamic_method_type: MethodHandleNatives::method_type_signature(dynamic_method_type)?
+			.as_str()
+			.to_string(),
+	})
 }
 
 #[jni_call]
@@ -34,35 +139,50 @@ pub extern "C" fn JVM_LookupLambdaProxyClassFromArchive(
 	_implementation_member: JObject,
 	_dynamic_method_type: JObject,
 ) -> JClass {
-	todo!()
+	// Lambda proxy classes are never archived, the caller will spin up a new one
+	JClass::null()
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_GetRandomSeedForDumping() -> jlong {
-	todo!()
+	// See `CDS#getRandomSeedForDumping`
+	0
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_GetCDSConfigStatus() -> jint {
-	todo!()
+	cds::config_status()
 }
 
 #[jni_call]
-pub extern "C" fn JVM_LogLambdaFormInvoker(_env: JniEnv, _line: JString) {
-	todo!()
+pub extern "C" fn JVM_LogLambdaFormInvoker(_env: JniEnv, line: JString) {
+	if let Some(line) = unsafe { reference_from_jobject(line.raw()) } {
+		class_list::log_lambda_form_invoker(classes::java::lang::String::extract(
+			line.extract_class(),
+		));
+	}
 }
 
 #[jni_call]
-pub extern "C" fn JVM_DumpClassListToFile(_env: JniEnv, _list_file_name: JString) {
-	todo!()
+pub extern "C" fn JVM_DumpClassListToFile(env: JniEnv, list_file_name: JString) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let list_file_name =
+		unsafe { reference_from_jobject(list_file_name.raw()) }.unwrap_or_else(Reference::null);
+	handle_exception!(thread, CDS::dump_class_list(list_file_name));
 }
 
 #[jni_call]
-pub extern "C" fn JVM_DumpDynamicArchive(_env: JniEnv, _archive_name: JString) {
-	todo!()
+pub extern "C" fn JVM_DumpDynamicArchive(env: JniEnv, archive_name: JString) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let archive_name =
+		unsafe { reference_from_jobject(archive_name.raw()) }.unwrap_or_else(Reference::null);
+	handle_exception!(thread, CDS::dump_dynamic_archive(archive_name));
 }
 
 #[jni_call]
 pub extern "C" fn JVM_NeedsClassInitBarrierForCDS(_env: JniEnv, _cls: JClass) -> jboolean {
-	todo!()
+	// Only classes initialized at dump time in a static archive need a barrier
+	false
 }
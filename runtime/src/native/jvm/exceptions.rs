@@ -1,25 +1,21 @@
 #![native_macros::jni_fn_module]
 
 use crate::classes;
-use crate::classpath::loader::ClassLoader;
 use crate::native::java::lang::String::StringInterner;
 use crate::native::jni::{IntoJni, ReferenceJniExt, reference_from_jobject};
 use crate::objects::class::ClassPtr;
 use crate::objects::constant_pool::cp_types;
 use crate::objects::instance::array::Array;
-use crate::objects::instance::class::ClassInstanceRef;
 use crate::objects::method::Method;
 use crate::objects::reference::Reference;
 use crate::symbols::{Symbol, sym};
 use crate::thread::JavaThread;
-use crate::thread::exceptions::throw;
-
-use std::sync::atomic::{AtomicBool, Ordering};
+use crate::thread::exceptions::{Throws, handle_exception, throw};
 
 use ::jni::env::JniEnv;
 use ::jni::objects::{JObject, JObjectArray, JString, JThrowable};
 use ::jni::sys::{jint, jlong};
-use common::int_types::{s8, u2};
+use common::int_types::u2;
 use instructions::OpCode;
 use native_macros::jni_call;
 
@@ -138,7 +134,12 @@ pub extern "C" fn JVM_GetExtendedNPEMessage(_env: JniEnv, throwable: JThrowable)
 		return JString::null();
 	};
 
-	Reference::class(StringInterner::intern(description.as_str())).into_jstring_safe()
+	Reference::class(handle_exception!(
+		JString::null(),
+		JavaThread::current(),
+		StringInterner::intern(description.as_str())
+	))
+	.into_jstring_safe()
 }
 
 #[jni_call]
@@ -148,8 +149,10 @@ pub extern "C" fn JVM_InitStackTraceElementArray(
 	backtrace: JObject,
 	depth: jint,
 ) {
-	unsafe {
-		initialize_stack_trace_element();
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	if let Throws::Exception(e) = classes::java::lang::StackTraceElement::initialize(thread) {
+		e.throw(thread);
+		return;
 	}
 
 	let (Some(backtrace), Some(elements)) =
@@ -157,7 +160,6 @@ pub extern "C" fn JVM_InitStackTraceElementArray(
 			reference_from_jobject(elements.raw())
 		})
 	else {
-		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 		throw!(thread, NullPointerException);
 	};
 
@@ -165,7 +167,6 @@ pub extern "C" fn JVM_InitStackTraceElementArray(
 	let stacktrace_elements = stacktrace_elements.as_slice();
 
 	if stacktrace_elements.len() != depth as usize {
-		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 		throw!(thread, IndexOutOfBoundsException);
 	}
 
@@ -174,7 +175,6 @@ pub extern "C" fn JVM_InitStackTraceElementArray(
 	let backtrace_array_contents = backtrace_array.as_slice::<jlong>();
 
 	if !backtrace_array_contents.len().is_multiple_of(2) {
-		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
 		throw!(
 			thread,
 			InternalError,
@@ -190,82 +190,28 @@ pub extern "C" fn JVM_InitStackTraceElementArray(
 		.zip(stacktrace_elements.iter())
 	{
 		let method = unsafe { &*(method as *const Method) };
-		fill_in_stack_trace(stacktrace_element.extract_class(), method, pc);
+		handle_exception!(
+			thread,
+			classes::java::lang::StackTraceElement::fill_in(
+				stacktrace_element.extract_class(),
+				method,
+				pc,
+			)
+		);
 	}
 }
 
 #[jni_call]
 pub extern "C" fn JVM_InitStackTraceElement(
-	_env: JniEnv,
+	env: JniEnv,
 	_element: JObject,
 	_stack_frame_info: JObject,
 ) {
-	unsafe {
-		initialize_stack_trace_element();
-	}
-
-	unimplemented!("java.lang.StackTraceElement#initStackTraceElement");
-}
-
-unsafe fn initialize_stack_trace_element() {
-	static ONCE: AtomicBool = AtomicBool::new(false);
-	if ONCE
-		.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-		.is_err()
-	{
-		// Already initialized
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	if let Throws::Exception(e) = classes::java::lang::StackTraceElement::initialize(thread) {
+		e.throw(thread);
 		return;
 	}
 
-	let Some(class) = ClassLoader::bootstrap().lookup_class(sym!(java_lang_StackTraceElement))
-	else {
-		unreachable!("StackTraceElement methods can't be called if the class isn't loaded already");
-	};
-
-	unsafe {
-		crate::globals::classes::set_java_lang_StackTraceElement(class);
-		classes::java::lang::StackTraceElement::init_offsets();
-	}
-}
-
-fn fill_in_stack_trace(stacktrace_element: ClassInstanceRef, method: &Method, pc: s8) {
-	let declaring_class_object = method.class().mirror();
-	classes::java::lang::StackTraceElement::set_declaringClassObject(
-		stacktrace_element,
-		Reference::mirror(declaring_class_object),
-	);
-
-	// TODO: classLoaderName
-	// TODO: moduleName
-	// TODO: moduleVersion
-	let declaring_class = StringInterner::intern(method.class().name());
-	classes::java::lang::StackTraceElement::set_declaringClass(
-		stacktrace_element,
-		Reference::class(declaring_class),
-	);
-
-	let method_name = StringInterner::intern(method.name);
-	classes::java::lang::StackTraceElement::set_methodName(
-		stacktrace_element,
-		Reference::class(method_name),
-	);
-
-	match method.class().source_file_name() {
-		Some(name) => {
-			let file_name = StringInterner::intern(name);
-			classes::java::lang::StackTraceElement::set_fileName(
-				stacktrace_element,
-				Reference::class(file_name),
-			);
-		},
-		None => {
-			classes::java::lang::StackTraceElement::set_fileName(
-				stacktrace_element,
-				Reference::null(),
-			);
-		},
-	}
-
-	let line_number = method.line_number(pc as isize);
-	classes::java::lang::StackTraceElement::set_lineNumber(stacktrace_element, line_number);
+	unimplemented!("java.lang.StackTraceElement#initStackTraceElement");
 }
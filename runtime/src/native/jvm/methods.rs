@@ -1,66 +1,156 @@
 #![native_macros::jni_fn_module]
 
+use crate::native::jni::{IntoJni, reference_from_jobject, reference_from_jobject_maybe_null};
+use crate::objects::method::{Method, reflection};
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{handle_exception, throw, throw_with_ret};
+
+use std::ffi::{CString, c_char, c_int, c_uchar, c_ushort};
+
 use jni::env::JniEnv;
 use jni::objects::{JClass, JObject, JObjectArray};
 use jni::sys::{jboolean, jint};
 use native_macros::jni_call;
-use std::ffi::{c_char, c_int, c_uchar, c_ushort};
+
+/// The method modifiers that are exposed through `JVM_GetMethodIxModifiers`
+const JVM_RECOGNIZED_METHOD_MODIFIERS: jint = 0x1DFF;
 
 #[jni_call]
 pub extern "C" fn JVM_InvokeMethod(
-	_env: JniEnv,
-	_method: JObject,
-	_obj: JObject,
-	_args0: JObjectArray,
+	env: JniEnv,
+	method: JObject,
+	obj: JObject,
+	args0: JObjectArray,
 ) -> JObject {
-	todo!()
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let Some(method) = (unsafe { reference_from_jobject(method.raw()) }) else {
+		throw_with_ret!(JObject::null(), thread, NullPointerException);
+	};
+
+	let receiver = unsafe { reference_from_jobject_maybe_null(obj.raw()) };
+	let args = unsafe { reference_from_jobject_maybe_null(args0.raw()) };
+
+	let ret = handle_exception!(
+		JObject::null(),
+		thread,
+		reflection::invoke_method(thread, method.extract_class(), receiver, args)
+	);
+	ret.into_jni_safe()
 }
 
 #[jni_call]
 pub extern "C" fn JVM_NewInstanceFromConstructor(
-	_env: JniEnv,
-	_c: JObject,
-	_args0: JObjectArray,
+	env: JniEnv,
+	c: JObject,
+	args0: JObjectArray,
 ) -> JObject {
-	todo!()
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let Some(constructor) = (unsafe { reference_from_jobject(c.raw()) }) else {
+		throw_with_ret!(JObject::null(), thread, NullPointerException);
+	};
+
+	let args = unsafe { reference_from_jobject_maybe_null(args0.raw()) };
+
+	let ret = handle_exception!(
+		JObject::null(),
+		thread,
+		reflection::new_instance(thread, constructor.extract_class(), args)
+	);
+	ret.into_jni_safe()
+}
+
+/// Get the method at `method_index` in `cb`, in the order they appear in the class file
+///
+/// The `JVM_GetMethodIx*` functions are only used by the split verifier (`libverify`), which
+/// should always provide valid indices. Otherwise, an exception is thrown and `None` is returned.
+fn method_at(env: JniEnv, cb: JClass, method_index: jint) -> Option<&'static Method> {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let Some(class) = (unsafe { reference_from_jobject(cb.raw()) }) else {
+		throw_with_ret!(None, thread, NullPointerException);
+	};
+
+	let method = usize::try_from(method_index).ok().and_then(|method_index| {
+		class
+			.extract_target_class()
+			.vtable()
+			.iter_local()
+			.nth(method_index)
+	});
+	if method.is_none() {
+		throw_with_ret!(
+			None,
+			thread,
+			IllegalArgumentException,
+			"method index {method_index} out of bounds"
+		);
+	}
+
+	method
 }
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_GetMethodIxExceptionIndexes(
-	_env: JniEnv,
-	_cb: JClass,
-	_method_index: jint,
-	_exceptions: *mut c_ushort,
+	env: JniEnv,
+	cb: JClass,
+	method_index: jint,
+	exceptions: *mut c_ushort,
 ) {
-	todo!()
+	let Some(method) = method_at(env, cb, method_index) else {
+		return;
+	};
+	let indices = method.exception_indices();
+
+	// SAFETY: The caller allocates `exceptions` using `JVM_GetMethodIxExceptionsCount`
+	unsafe {
+		std::ptr::copy_nonoverlapping(indices.as_ptr(), exceptions, indices.len());
+	}
 }
 
 #[jni_call]
 pub extern "C" fn JVM_GetMethodIxExceptionsCount(
-	_env: JniEnv,
-	_cb: JClass,
-	_method_index: jint,
+	env: JniEnv,
+	cb: JClass,
+	method_index: jint,
 ) -> jint {
-	todo!()
+	let Some(method) = method_at(env, cb, method_index) else {
+		return 0;
+	};
+
+	method.exception_indices().len() as jint
 }
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_GetMethodIxByteCode(
-	_env: JniEnv,
-	_cb: JClass,
-	_method_index: jint,
-	_code: *mut c_uchar,
+	env: JniEnv,
+	cb: JClass,
+	method_index: jint,
+	code: *mut c_uchar,
 ) {
-	todo!()
+	let Some(method) = method_at(env, cb, method_index) else {
+		return;
+	};
+	let bytecode = &method.code.code;
+
+	// SAFETY: The caller allocates `code` using `JVM_GetMethodIxByteCodeLength`
+	unsafe {
+		std::ptr::copy_nonoverlapping(bytecode.as_ptr(), code, bytecode.len());
+	}
 }
 
 #[jni_call]
 pub extern "C" fn JVM_GetMethodIxByteCodeLength(
-	_env: JniEnv,
-	_cb: JClass,
-	_method_index: jint,
+	env: JniEnv,
+	cb: JClass,
+	method_index: jint,
 ) -> jint {
-	todo!()
+	let Some(method) = method_at(env, cb, method_index) else {
+		return 0;
+	};
+
+	method.code.code.len() as jint
 }
 
 #[repr(C)]
@@ -73,66 +163,125 @@ pub struct JVM_ExceptionTableEntryType {
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_GetMethodIxExceptionTableEntry(
-	_env: JniEnv,
-	_cb: JClass,
-	_method_index: jint,
-	_entry_index: jint,
-	_entry: *mut JVM_ExceptionTableEntryType,
+	env: JniEnv,
+	cb: JClass,
+	method_index: jint,
+	entry_index: jint,
+	entry: *mut JVM_ExceptionTableEntryType,
 ) {
-	todo!()
+	let Some(method) = method_at(env, cb, method_index) else {
+		return;
+	};
+
+	let exception = usize::try_from(entry_index)
+		.ok()
+		.and_then(|entry_index| method.code.exception_table.get(entry_index));
+	let Some(exception) = exception else {
+		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+		throw!(
+			thread,
+			IllegalArgumentException,
+			"exception table index {entry_index} out of bounds"
+		);
+	};
+
+	// SAFETY: The caller provides a valid entry to fill
+	unsafe {
+		*entry = JVM_ExceptionTableEntryType {
+			start_pc: jint::from(exception.start_pc),
+			end_pc: jint::from(exception.end_pc),
+			handler_pc: jint::from(exception.handler_pc),
+			catch_type: jint::from(exception.catch_type),
+		};
+	}
 }
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_GetMethodIxExceptionTableLength(
-	_env: JniEnv,
-	_cb: JClass,
-	_index: c_int,
+	env: JniEnv,
+	cb: JClass,
+	index: c_int,
 ) -> jint {
-	todo!()
+	let Some(method) = method_at(env, cb, index) else {
+		return 0;
+	};
+
+	method.code.exception_table.len() as jint
 }
 
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_GetMethodIxModifiers(_env: JniEnv, _cb: JClass, _index: c_int) -> jint {
-	todo!()
+pub extern "C" fn JVM_GetMethodIxModifiers(env: JniEnv, cb: JClass, index: c_int) -> jint {
+	let Some(method) = method_at(env, cb, index) else {
+		return 0;
+	};
+
+	jint::from(method.access_flags.as_u2()) & JVM_RECOGNIZED_METHOD_MODIFIERS
 }
 
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_GetMethodIxLocalsCount(_env: JniEnv, _cb: JClass, _index: c_int) -> jint {
-	todo!()
+pub extern "C" fn JVM_GetMethodIxLocalsCount(env: JniEnv, cb: JClass, index: c_int) -> jint {
+	let Some(method) = method_at(env, cb, index) else {
+		return 0;
+	};
+
+	jint::from(method.code.max_locals)
 }
 
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_GetMethodIxArgsSize(_env: JniEnv, _cb: JClass, _index: c_int) -> jint {
-	todo!()
+pub extern "C" fn JVM_GetMethodIxArgsSize(env: JniEnv, cb: JClass, index: c_int) -> jint {
+	let Some(method) = method_at(env, cb, index) else {
+		return 0;
+	};
+
+	method.parameter_stack_size() as jint
 }
 
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_GetMethodIxMaxStack(_env: JniEnv, _cb: JClass, _index: c_int) -> jint {
-	todo!()
+pub extern "C" fn JVM_GetMethodIxMaxStack(env: JniEnv, cb: JClass, index: c_int) -> jint {
+	let Some(method) = method_at(env, cb, index) else {
+		return 0;
+	};
+
+	jint::from(method.code.max_stack)
 }
 
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_IsConstructorIx(_env: JniEnv, _cb: JClass, _index: c_int) -> jboolean {
-	todo!()
+pub extern "C" fn JVM_IsConstructorIx(env: JniEnv, cb: JClass, index: c_int) -> jboolean {
+	method_at(env, cb, index).is_some_and(Method::is_constructor)
 }
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_IsVMGeneratedMethodIx(_env: JniEnv, _cb: JClass, _index: c_int) -> jboolean {
-	todo!()
+	// We never inject methods into classes
+	false
 }
 
+/// Returns a string to be freed with `JVM_ReleaseUTF`
 #[jni_call(no_strict_types)]
-pub extern "C" fn JVM_GetMethodIxNameUTF(_env: JniEnv, _cb: JClass, _index: jint) -> *const c_char {
-	todo!()
+pub extern "C" fn JVM_GetMethodIxNameUTF(env: JniEnv, cb: JClass, index: jint) -> *const c_char {
+	let Some(method) = method_at(env, cb, index) else {
+		return std::ptr::null();
+	};
+
+	CString::new(method.name.as_bytes())
+		.expect("method names cannot contain NUL")
+		.into_raw()
 }
 
+/// Returns a string to be freed with `JVM_ReleaseUTF`
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_GetMethodIxSignatureUTF(
-	_env: JniEnv,
-	_cb: JClass,
-	_index: jint,
+	env: JniEnv,
+	cb: JClass,
+	index: jint,
 ) -> *const c_char {
-	todo!()
+	let Some(method) = method_at(env, cb, index) else {
+		return std::ptr::null();
+	};
+
+	CString::new(method.descriptor_sym().as_bytes())
+		.expect("method descriptors cannot contain NUL")
+		.into_raw()
 }
 
 #[jni_call]
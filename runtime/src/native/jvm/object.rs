@@ -5,7 +5,7 @@ use crate::objects::instance::CloneableInstance;
 use crate::objects::instance::object::Object;
 use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{Throws, throw_with_ret};
+use crate::thread::exceptions::{Throws, handle_exception, throw_with_ret};
 
 use std::time::Duration;
 
@@ -82,12 +82,14 @@ pub extern "C" fn JVM_Clone(_env: JniEnv, handle: JObject) -> JObject {
 		if handle.is_primitive_array() {
 			let array = handle.extract_primitive_array();
 			let cloned = unsafe { CloneableInstance::clone(&array) };
+			let cloned = handle_exception!(JObject::null(), JavaThread::current(), cloned);
 			return Reference::array(cloned).into_jni_safe();
 		}
 
 		if handle.is_object_array() {
 			let array = handle.extract_object_array();
 			let cloned = unsafe { CloneableInstance::clone(&array) };
+			let cloned = handle_exception!(JObject::null(), JavaThread::current(), cloned);
 			return Reference::object_array(cloned).into_jni_safe();
 		}
 	}
@@ -102,5 +104,6 @@ pub extern "C" fn JVM_Clone(_env: JniEnv, handle: JObject) -> JObject {
 	}
 
 	let cloned = unsafe { CloneableInstance::clone(&instance) };
+	let cloned = handle_exception!(JObject::null(), JavaThread::current(), cloned);
 	Reference::class(cloned).into_jni_safe()
 }
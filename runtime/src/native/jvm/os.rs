@@ -1,5 +1,8 @@
 #![native_macros::jni_fn_module]
 
+use crate::options::JvmOptions;
+use crate::thread::signal_dispatcher;
+
 use std::ffi::{CStr, c_char, c_void};
 
 use common::unicode;
@@ -8,37 +11,38 @@ use native_macros::jni_call;
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_TotalMemory() -> jlong {
-	todo!()
+	crate::gc::heap::total_size() as jlong
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_FreeMemory() -> jlong {
-	todo!()
+	crate::gc::heap::free_size() as jlong
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_MaxMemory() -> jlong {
-	todo!()
+	crate::gc::heap::max_size() as jlong
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_ActiveProcessorCount() -> jint {
-	todo!()
+	crate::os::active_processor_count() as jint
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_IsUseContainerSupport() -> jboolean {
-	todo!()
+	crate::options::JvmOptions::get().use_container_support
 }
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_IsContainerized() -> jboolean {
-	todo!()
+	crate::os::is_containerized()
 }
 
 #[jni_call(no_env, no_strict_types)]
 pub extern "C" fn JVM_RegisterSignal(signal: jint, handler: *mut c_void) -> *mut c_void {
 	const FAILED: isize = -1;
+	const IGNORED: usize = 1;
 	const USER_HANDLER: usize = 2;
 
 	let signal = platform::Signal::from(signal);
@@ -47,6 +51,18 @@ pub extern "C" fn JVM_RegisterSignal(signal: jint, handler: *mut c_void) -> *mut
 		return FAILED as usize as *mut c_void;
 	}
 
+	if signal_dispatcher::is_shutdown_signal(signal) {
+		// With `-Xrs`, the user is responsible for running the shutdown hooks through `System#exit`
+		if JvmOptions::get().reduce_signal_usage {
+			return FAILED as usize as *mut c_void;
+		}
+
+		// The process was started with the signal ignored (e.g. through `nohup`), keep it that way
+		if signal.is_ignored() {
+			return IGNORED as *mut c_void;
+		}
+	}
+
 	let handler = match handler as usize {
 		USER_HANDLER => platform::SignalHandler::user_handler(),
 		other => unsafe { platform::SignalHandler::from_raw(other) },
@@ -66,8 +82,21 @@ pub extern "C" fn JVM_RegisterSignal(signal: jint, handler: *mut c_void) -> *mut
 }
 
 #[jni_call(no_env)]
-pub extern "C" fn JVM_RaiseSignal(_signal: jint) -> jboolean {
-	todo!()
+pub extern "C" fn JVM_RaiseSignal(signal: jint) -> jboolean {
+	let signal = platform::Signal::from(signal);
+
+	let is_shutdown_signal = signal_dispatcher::is_shutdown_signal(signal);
+	if JvmOptions::get().reduce_signal_usage {
+		// Nothing is listening for these with `-Xrs`
+		if is_shutdown_signal || Some(signal) == signal_dispatcher::thread_dump_signal() {
+			return false;
+		}
+	} else if is_shutdown_signal && signal.is_ignored() {
+		// No handler was ever installed for it, see `JVM_RegisterSignal`
+		return false;
+	}
+
+	signal.raise()
 }
 
 #[jni_call(no_env, no_strict_types)]
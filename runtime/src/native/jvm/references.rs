@@ -1,5 +1,11 @@
 #![native_macros::jni_fn_module]
 
+use crate::classes::java::lang::r#ref::Reference as JReference;
+use crate::gc::reference_processor;
+use crate::native::jni::{IntoJni, reference_from_jobject, reference_from_jobject_maybe_null};
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{throw, throw_with_ret};
+
 use jni::env::JniEnv;
 use jni::objects::JObject;
 use jni::sys::jboolean;
@@ -7,43 +13,61 @@ use native_macros::jni_call;
 
 #[jni_call]
 pub extern "C" fn JVM_GetAndClearReferencePendingList(_env: JniEnv) -> JObject {
-	todo!()
+	reference_processor::get_and_clear_pending_list().into_jni_safe()
 }
 
 #[jni_call]
 pub extern "C" fn JVM_HasReferencePendingList(_env: JniEnv) -> jboolean {
-	todo!()
+	reference_processor::has_pending_list()
 }
 
 #[jni_call]
-pub extern "C" fn JVM_WaitForReferencePendingList(_env: JniEnv) {
-	todo!()
+pub extern "C" fn JVM_WaitForReferencePendingList(env: JniEnv) {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	reference_processor::wait_for_pending_list(thread);
 }
 
 #[jni_call]
-pub extern "C" fn JVM_ReferenceGet(_env: JniEnv, _reference: JObject) -> JObject {
-	todo!()
+pub extern "C" fn JVM_ReferenceGet(env: JniEnv, reference: JObject) -> JObject {
+	let Some(reference) = (unsafe { reference_from_jobject(reference.raw()) }) else {
+		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+		throw_with_ret!(JObject::null(), thread, NullPointerException);
+	};
+
+	JReference::referent(reference).into_jni_safe()
 }
 
 #[jni_call]
-pub extern "C" fn JVM_ReferenceRefersTo(
-	_env: JniEnv,
-	_reference: JObject,
-	_obj: JObject,
-) -> jboolean {
-	todo!()
+pub extern "C" fn JVM_ReferenceRefersTo(env: JniEnv, reference: JObject, obj: JObject) -> jboolean {
+	refers_to(env, reference, obj)
 }
 
 #[jni_call]
-pub extern "C" fn JVM_ReferenceClear(_env: JniEnv, _reference: JObject) {
-	todo!()
+pub extern "C" fn JVM_ReferenceClear(env: JniEnv, reference: JObject) {
+	let Some(reference) = (unsafe { reference_from_jobject(reference.raw()) }) else {
+		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+		throw!(thread, NullPointerException);
+	};
+
+	JReference::set_referent(reference, crate::objects::reference::Reference::null());
 }
 
 #[jni_call]
 pub extern "C" fn JVM_PhantomReferenceRefersTo(
-	_env: JniEnv,
-	_reference: JObject,
-	_obj: JObject,
+	env: JniEnv,
+	reference: JObject,
+	obj: JObject,
 ) -> jboolean {
-	todo!()
+	// Phantom referents are unreachable through `get()`, but they can still be compared
+	refers_to(env, reference, obj)
+}
+
+fn refers_to(env: JniEnv, reference: JObject, obj: JObject) -> bool {
+	let Some(reference) = (unsafe { reference_from_jobject(reference.raw()) }) else {
+		let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+		throw_with_ret!(false, thread, NullPointerException);
+	};
+
+	let obj = unsafe { reference_from_jobject_maybe_null(obj.raw()) };
+	JReference::referent(reference) == obj
 }
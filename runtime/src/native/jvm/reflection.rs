@@ -1,7 +1,7 @@
 #![native_macros::jni_fn_module]
 
 use crate::classes;
-use crate::classpath::loader::{ClassLoader, ClassLoaderSet};
+use crate::classpath::loader::{ClassLoader, ClassLoaderSet, LoaderLink};
 use crate::native::RawSymbolExt;
 use crate::native::jni::{IntoJni, reference_from_jobject_maybe_null};
 use crate::objects::reference::Reference;
@@ -58,24 +58,95 @@ pub extern "C" fn JVM_FindClassFromLoader(
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_FindClassFromClass(
-	_env: JniEnv,
-	_name: *const c_char,
-	_init: jboolean,
-	_from: JClass,
+	env: JniEnv,
+	name: *const c_char,
+	init: jboolean,
+	from: JClass,
 ) -> JClass {
-	todo!()
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	assert_eq!(thread.env(), env);
+
+	let from = unsafe { reference_from_jobject_maybe_null(from.raw()) };
+	if from.is_null() {
+		throw_with_ret!(JClass::null(), thread, NullPointerException);
+	}
+
+	let name = unsafe { Symbol::intern_mutf_c_str(name) };
+
+	// The class is looked up in the defining loader of `from`
+	let loader = from.extract_target_class().loader();
+	let class = handle_exception!(JClass::null(), thread, loader.load(name));
+
+	if init {
+		handle_exception!(JClass::null(), thread, class.initialize(thread));
+	}
+
+	unsafe { JClass::from_raw(Reference::mirror(class.mirror()).into_jni()) }
 }
 
 #[jni_call(no_strict_types)]
 pub extern "C" fn JVM_DefineClass(
-	_env: JniEnv,
-	_name: *const c_char,
-	_loader: JObject,
-	_buf: *const jbyte,
-	_len: jsize,
-	_protection_domain: JObject,
+	env: JniEnv,
+	name: *const c_char,
+	loader: JObject,
+	buf: *const jbyte,
+	len: jsize,
+	protection_domain: JObject,
 ) -> JClass {
-	todo!()
+	define_class_common(env, name, loader, buf, len, protection_domain)
+}
+
+/// Shared implementation of `JVM_DefineClass` and `JVM_DefineClassWithSource`
+///
+/// `name` is a binary name (`java.lang.Object`), or null if the name should be taken from the
+/// class file.
+fn define_class_common(
+	env: JniEnv,
+	name: *const c_char,
+	loader: JObject,
+	buf: *const jbyte,
+	len: jsize,
+	protection_domain: JObject,
+) -> JClass {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+	assert_eq!(thread.env(), env);
+
+	let Ok(len) = usize::try_from(len) else {
+		throw_with_ret!(
+			JClass::null(),
+			thread,
+			IllegalArgumentException,
+			"negative class file length {len}"
+		);
+	};
+
+	let buf = unsafe {
+		// SAFETY: `i8` and `u8` have the same size and alignment
+		let ptr = buf.cast::<u8>();
+		std::slice::from_raw_parts(ptr, len)
+	};
+
+	let name_sym;
+	if name.is_null() {
+		name_sym = None;
+	} else {
+		let external_name_c = unsafe { CStr::from_ptr(name) };
+		let external_name = unicode::decode(external_name_c.to_bytes()).unwrap();
+		let internal_name = external_name.replace('.', "/");
+		name_sym = Some(Symbol::intern(internal_name));
+	}
+
+	let loader = unsafe { reference_from_jobject_maybe_null(loader.raw()) };
+	let protection_domain = unsafe { reference_from_jobject_maybe_null(protection_domain.raw()) };
+
+	let loader = ClassLoaderSet::find_or_add(loader, false);
+	let class = handle_exception!(
+		JClass::null(),
+		thread,
+		loader.define_class(name_sym, buf, protection_domain, LoaderLink::Normal)
+	);
+
+	unsafe { JClass::from_raw(Reference::mirror(class.mirror()).into_jni()) }
 }
 
 #[jni_call(no_strict_types)]
@@ -85,7 +156,7 @@ pub extern "C" fn JVM_LookupDefineClass(
 	name: *const c_char,
 	buf: *const jbyte,
 	len: jsize,
-	_protection_domain: JObject,
+	protection_domain: JObject,
 	initialize: jboolean,
 	flags: c_int,
 	class_data: JObject,
@@ -105,10 +176,19 @@ pub extern "C" fn JVM_LookupDefineClass(
 
 	let lookup = lookup.extract_target_class();
 
+	let Ok(len) = usize::try_from(len) else {
+		throw_with_ret!(
+			JClass::null(),
+			thread,
+			IllegalArgumentException,
+			"negative class file length {len}"
+		);
+	};
+
 	let buf = unsafe {
 		// SAFETY: `i8` and `u8` have the same size and alignment
 		let ptr = buf.cast::<u8>();
-		std::slice::from_raw_parts(ptr, len as usize)
+		std::slice::from_raw_parts(ptr, len)
 	};
 
 	let is_nestmate = (flags & MN_NESTMATE_CLASS) == MN_NESTMATE_CLASS;
@@ -171,15 +251,24 @@ pub extern "C" fn JVM_LookupDefineClass(
 		}
 	}
 
-	if name.is_null() {
-		// TODO: In this case, the name is just to be derived from the class file itself.
-		//       Will need to train the loader to be able to do that.
-		todo!()
-	}
+	let name = if name.is_null() {
+		None
+	} else {
+		Some(unsafe { Symbol::intern_mutf_c_str(name) })
+	};
 
-	let name = unsafe { Symbol::intern_mutf_c_str(name) };
+	let protection_domain = unsafe { reference_from_jobject_maybe_null(protection_domain.raw()) };
 
-	let class = match lookup.loader().derive_class(name, Some(buf), is_hidden) {
+	let link = if is_hidden {
+		LoaderLink::Hidden
+	} else {
+		LoaderLink::Normal
+	};
+
+	let class = match lookup
+		.loader()
+		.define_class(name, buf, protection_domain, link)
+	{
 		Throws::Ok(class) => class,
 		Throws::Exception(e) => {
 			let thread = unsafe { &*JavaThread::for_env(env.raw()) };
@@ -215,53 +304,11 @@ pub extern "C" fn JVM_DefineClassWithSource(
 	loader: JObject,
 	buf: *const jbyte,
 	len: jsize,
-	_protection_domain: JObject,
-	source: *const c_char,
+	protection_domain: JObject,
+	_source: *const c_char,
 ) -> JClass {
-	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
-	assert_eq!(thread.env(), env);
-
-	let buf = unsafe {
-		// SAFETY: `i8` and `u8` have the same size and alignment
-		let ptr = buf.cast::<u8>();
-		std::slice::from_raw_parts(ptr, len as usize)
-	};
-
-	let name_sym;
-	if name.is_null() {
-		// TODO: In this case, the name is just to be derived from the class file itself.
-		//       Will need to train the loader to be able to do that.
-		todo!()
-	} else {
-		let external_name_c = unsafe { CStr::from_ptr(name) };
-		let external_name = unicode::decode(external_name_c.to_bytes()).unwrap();
-		let internal_name = external_name.replace('.', "/");
-		name_sym = Symbol::intern(internal_name);
-	}
-
-	let loader = unsafe { reference_from_jobject_maybe_null(loader.raw()) };
-
-	let source_str;
-	if source.is_null() {
-		source_str = None;
-	} else {
-		let source_c = unsafe { CStr::from_ptr(source) };
-		source_str = unicode::decode(source_c.to_bytes()).ok(); // TODO: maybe panic?
-	}
-
-	// Not a thing in defineClass1
-	let is_hidden = false;
-
-	let loader = ClassLoaderSet::find_or_add(loader, is_hidden);
-	let class = match loader.derive_class(name_sym, Some(buf), is_hidden) {
-		Throws::Ok(class) => class,
-		Throws::Exception(e) => {
-			e.throw(thread);
-			return JClass::null();
-		},
-	};
-
-	unsafe { JClass::from_raw(Reference::mirror(class.mirror()).into_jni()) }
+	// TODO: Record the source, for `-Xlog:class+load`
+	define_class_common(env, name, loader, buf, len, protection_domain)
 }
 
 #[jni_call]
@@ -3,14 +3,22 @@
 use jni::env::JniEnv;
 use jni::objects::JString;
 use native_macros::jni_call;
-use std::ffi::c_char;
+use std::ffi::{CString, c_char};
 
 #[jni_call]
 pub extern "C" fn JVM_InternString(_env: JniEnv, _string: JString) -> JString {
 	todo!()
 }
 
+/// Free a string returned from one of the `JVM_*UTF` functions
+///
+/// All of those strings are allocated as a [`CString`].
 #[jni_call(no_env, no_strict_types)]
-pub extern "C" fn JVM_ReleaseUTF(_utf: *const c_char) {
-	todo!()
+pub extern "C" fn JVM_ReleaseUTF(utf: *const c_char) {
+	if utf.is_null() {
+		return;
+	}
+
+	// SAFETY: The caller must only provide strings that came from the VM
+	drop(unsafe { CString::from_raw(utf.cast_mut()) });
 }
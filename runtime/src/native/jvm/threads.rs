@@ -2,11 +2,12 @@
 
 use crate::classes;
 use crate::classes::java::lang::Thread::ThreadStatus;
-use crate::native::jni::{IntoJni, reference_from_jobject};
-use crate::thread::exceptions::{Throws, throw, throw_with_ret};
-use crate::thread::pool::ThreadPool;
-use crate::thread::{JavaThread, JavaThreadBuilder};
+use crate::native::jni::{IntoJni, reference_from_jobject, reference_from_jobject_maybe_null};
 use crate::objects::monitor::MonitorMap;
+use crate::objects::reference::Reference;
+use crate::thread::exceptions::{Throws, handle_exception, throw, throw_with_ret};
+use crate::thread::pool::ThreadPool;
+use crate::thread::{JavaThread, JavaThreadBuilder, dump};
 
 use std::cmp;
 use std::sync::atomic::AtomicUsize;
@@ -60,13 +61,11 @@ pub extern "C" fn JVM_SetThreadPriority(_env: JniEnv, this: JObject, priority: j
 	let holder = classes::java::lang::Thread::holder(this.extract_class());
 	classes::java::lang::Thread::holder::set_priority(holder.extract_class(), priority);
 
-	let java_thread = ThreadPool::find_from_obj(this);
-	let Some(_thread) = java_thread else {
-		return;
-	};
-
-	// Thread is alive...
-	todo!("Set priority on JavaThread?")
+	// If the thread is alive, the OS thread needs to be updated as well. Otherwise, the priority is
+	// applied once it starts.
+	if let Some(thread) = ThreadPool::find_from_obj(this) {
+		thread.set_native_priority(priority);
+	}
 }
 
 #[jni_call]
@@ -121,25 +120,63 @@ pub extern "C" fn JVM_HoldsLock(env: JniEnv, _class: JClass, obj: JObject) -> jb
 		throw_with_ret!(false, thread, NullPointerException);
 	};
 
-	match MonitorMap::find(&obj, thread) {
+	match MonitorMap::find(obj, thread) {
 		Some(monitor) => monitor.owner() == Some(thread),
 		None => false,
 	}
 }
 
 #[jni_call]
-pub extern "C" fn JVM_GetStackTrace(_env: JniEnv, _thread: JObject) -> JObject {
-	todo!()
+pub extern "C" fn JVM_GetStackTrace(env: JniEnv, thread: JObject) -> JObject {
+	let current = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(thread) = (unsafe { reference_from_jobject(thread.raw()) }) else {
+		throw_with_ret!(JObject::null(), current, NullPointerException);
+	};
+
+	let stack_trace = handle_exception!(
+		JObject::null(),
+		current,
+		dump::stack_trace_of(current, thread)
+	);
+	stack_trace.into_jni_safe()
 }
 
 #[jni_call]
-pub extern "C" fn JVM_CreateThreadSnapshot(_env: JniEnv, _thread: JObject) -> JObject {
-	todo!()
+pub extern "C" fn JVM_CreateThreadSnapshot(env: JniEnv, thread: JObject) -> JObject {
+	let current = unsafe { &*JavaThread::for_env(env.raw()) };
+	let Some(thread) = (unsafe { reference_from_jobject(thread.raw()) }) else {
+		throw_with_ret!(JObject::null(), current, NullPointerException);
+	};
+
+	// Terminated threads have no snapshot
+	let Some(thread) = ThreadPool::find_from_obj(thread) else {
+		return JObject::null();
+	};
+
+	let snapshot = dump::ThreadSnapshot::capture(thread);
+	let snapshot = handle_exception!(
+		JObject::null(),
+		current,
+		classes::jdk::internal::vm::ThreadSnapshot::new(current, &snapshot)
+	);
+	Reference::class(snapshot).into_jni_safe()
 }
 
 #[jni_call]
-pub extern "C" fn JVM_SetNativeThreadName(_env: JniEnv, _thread: JObject, _name: JString) {
-	todo!()
+pub extern "C" fn JVM_SetNativeThreadName(_env: JniEnv, thread: JObject, name: JString) {
+	let Some(thread) = (unsafe { reference_from_jobject(thread.raw()) }) else {
+		return;
+	};
+	let Some(name) = (unsafe { reference_from_jobject(name.raw()) }) else {
+		return;
+	};
+
+	// Only the current thread can be renamed, see `JavaThread::set_native_name()`
+	let Some(thread) = ThreadPool::find_from_obj(thread) else {
+		return;
+	};
+
+	thread.set_native_name(&classes::java::lang::String::extract(name.extract_class()));
 }
 
 #[jni_call]
@@ -153,17 +190,31 @@ pub extern "C" fn JVM_SetScopedValueCache(_env: JniEnv, _class: JClass, _cache:
 }
 
 #[jni_call]
-pub extern "C" fn JVM_GetAllThreads(_env: JniEnv, _dummy: JClass) -> JObjectArray {
-	todo!()
+pub extern "C" fn JVM_GetAllThreads(env: JniEnv, _dummy: JClass) -> JObjectArray {
+	let current = unsafe { &*JavaThread::for_env(env.raw()) };
+	let threads = handle_exception!(JObjectArray::null(), current, dump::live_thread_objects());
+
+	// SAFETY: `threads` is a `java.lang.Thread[]`
+	unsafe { JObjectArray::from_raw(threads.into_jni()) }
 }
 
 #[jni_call]
 pub extern "C" fn JVM_DumpThreads(
-	_env: JniEnv,
+	env: JniEnv,
 	_thread_class: JClass,
-	_threads: JObjectArray,
+	threads: JObjectArray,
 ) -> JObjectArray {
-	todo!()
+	let current = unsafe { &*JavaThread::for_env(env.raw()) };
+	let threads = unsafe { reference_from_jobject_maybe_null(threads.raw()) };
+
+	let stack_traces = handle_exception!(
+		JObjectArray::null(),
+		current,
+		dump::stack_traces_of(current, threads)
+	);
+
+	// SAFETY: `stack_traces` is a `java.lang.StackTraceElement[][]`
+	unsafe { JObjectArray::from_raw(stack_traces.into_jni()) }
 }
 
 #[jni_call]
@@ -1,5 +1,16 @@
 #![native_macros::jni_fn_module]
 
+use crate::native::java::lang::String::StringInterner;
+use crate::native::jdk::internal::util::SystemProps::Raw::SYSTEM_PROPERTIES;
+use crate::native::jni::{IntoJni, reference_from_jobject};
+use crate::objects::reference::Reference;
+use crate::options::JvmOptions;
+use crate::symbols::sym;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, handle_exception};
+use crate::{classes, java_call};
+
+use instructions::Operand;
 use jni::env::JniEnv;
 use jni::objects::{JClass, JObject, JObjectArray};
 use jni::sys::{jboolean, jint, jlong};
@@ -7,12 +18,12 @@ use native_macros::jni_call;
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_BeforeHalt() {
-	todo!()
+	// Nothing to do yet, this is where any exit-time dumps would happen
 }
 
 #[jni_call(no_env)]
-pub extern "C" fn JVM_Halt(_code: jint) {
-	todo!()
+pub extern "C" fn JVM_Halt(code: jint) {
+	crate::shutdown::halt(code)
 }
 
 #[jni_call(no_env)]
@@ -47,7 +58,7 @@ pub extern "C" fn JVM_IsContinuationsSupported() -> jboolean {
 
 #[jni_call(no_env)]
 pub extern "C" fn JVM_IsForeignLinkerSupported() -> jboolean {
-	todo!()
+	crate::native::foreign::is_supported()
 }
 
 #[jni_call(no_env)]
@@ -61,11 +72,68 @@ pub extern "C" fn JVM_LatestUserDefinedLoader(_env: JniEnv) -> JObject {
 }
 
 #[jni_call]
-pub extern "C" fn JVM_InitAgentProperties(_env: JniEnv, _properties: JObject) -> JObject {
-	todo!()
+pub extern "C" fn JVM_InitAgentProperties(env: JniEnv, properties: JObject) -> JObject {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let properties_ref =
+		unsafe { reference_from_jobject(properties.raw()) }.expect("properties should not be null");
+	handle_exception!(
+		JObject::null(),
+		thread,
+		init_agent_properties(thread, properties_ref)
+	);
+
+	properties
+}
+
+/// Set the properties that `jdk.internal.vm.VMSupport` reports to agents
+fn init_agent_properties(thread: &'static JavaThread, properties: Reference) -> Throws<()> {
+	let java_command = SYSTEM_PROPERTIES
+		.lock()
+		.unwrap()
+		.get("sun.java.command")
+		.cloned()
+		.unwrap_or_default();
+
+	// There are no flags files, so `sun.jvm.flags` is always empty
+	let agent_properties = [
+		("sun.java.command", java_command),
+		("sun.jvm.flags", String::new()),
+		("sun.jvm.args", JvmOptions::get().vm_arguments.join(" ")),
+	];
+
+	let put_method = properties
+		.extract_instance_class()
+		.resolve_method(sym!(put_name), sym!(Object_Object_Object_signature))?;
+	for (key, value) in agent_properties {
+		let key = StringInterner::intern(key)?;
+		let value = classes::java::lang::String::new(value)?;
+		java_call!(
+			thread,
+			put_method,
+			Operand::Reference(properties),
+			Operand::Reference(Reference::class(key)),
+			Operand::Reference(Reference::class(value)),
+		);
+
+		if thread.has_pending_exception() {
+			return Throws::PENDING_EXCEPTION;
+		}
+	}
+
+	Throws::Ok(())
 }
 
 #[jni_call]
-pub extern "C" fn JVM_GetVmArguments(_env: JniEnv) -> JObjectArray {
-	todo!()
+pub extern "C" fn JVM_GetVmArguments(env: JniEnv) -> JObjectArray {
+	let thread = unsafe { &*JavaThread::for_env(env.raw()) };
+
+	let arguments = JvmOptions::get().vm_arguments.iter().map(String::as_str);
+	let arguments = handle_exception!(
+		JObjectArray::null(),
+		thread,
+		classes::java::lang::String::new_array(arguments)
+	);
+
+	unsafe { JObjectArray::from_raw(Reference::object_array(arguments).into_jni()) }
 }
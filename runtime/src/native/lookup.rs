@@ -5,7 +5,7 @@ use crate::objects::method::{Method, MethodEntryPoint};
 use crate::objects::reference::Reference;
 use crate::symbols::sym;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::{Throws, throw};
+use crate::thread::exceptions::{Throws, handle_exception, throw};
 
 use std::ffi::{CString, c_void};
 use std::fmt::Write as _;
@@ -228,8 +228,8 @@ fn lookup_style(
 
 	let loader = method.class().loader().obj();
 	let clazz = method.class().mirror();
-	let entry_name = StringInterner::intern(&*jni_name);
-	let java_name = StringInterner::intern(method.name);
+	let entry_name = handle_exception!(None, thread, StringInterner::intern(&*jni_name));
+	let java_name = handle_exception!(None, thread, StringInterner::intern(method.name));
 
 	let address = java_call!(
 		thread,
@@ -0,0 +1,70 @@
+//! Off-heap memory allocated on behalf of Java code
+//!
+//! This backs `Unsafe#allocateMemory0` and friends, which is what direct `ByteBuffer`s and most
+//! off-heap allocators are built on.
+//!
+//! Like Hotspot, nothing here is capped by `-XX:MaxDirectMemorySize`. That limit only applies to
+//! direct buffers, and is enforced by `java.nio.Bits#reserveMemory` before it ever gets here.
+
+/// Allocate `size` bytes of uninitialized memory
+///
+/// The returned pointer is suitably aligned for any Java primitive type, and must be released with
+/// [`free()`]. This returns `None` if the platform allocator fails.
+pub fn allocate(size: usize) -> Option<*mut u8> {
+	// `malloc(0)` is allowed to return null, which would look like a failure
+	let ptr = unsafe { platform::mem::alloc(size.max(1)) };
+	(!ptr.is_null()).then_some(ptr)
+}
+
+/// Resize an allocation made with [`allocate()`], moving it if necessary
+///
+/// On failure, `None` is returned and `ptr` is left untouched.
+///
+/// # Safety
+///
+/// `ptr` must have been returned by [`allocate()`] or [`reallocate()`], and not yet freed.
+pub unsafe fn reallocate(ptr: *mut u8, size: usize) -> Option<*mut u8> {
+	let ptr = unsafe { platform::mem::realloc(ptr, size.max(1)) };
+	(!ptr.is_null()).then_some(ptr)
+}
+
+/// Release an allocation made with [`allocate()`] or [`reallocate()`]
+///
+/// # Safety
+///
+/// `ptr` must not be used after this call.
+pub unsafe fn free(ptr: *mut u8) {
+	unsafe { platform::mem::free(ptr) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn allocate_and_free() {
+		let ptr = allocate(24).unwrap();
+		assert!(ptr.is_aligned_to(8));
+
+		unsafe {
+			ptr.write_bytes(0xAB, 24);
+
+			let ptr = reallocate(ptr, 4096).unwrap();
+			assert_eq!(ptr.add(23).read(), 0xAB);
+
+			let ptr = reallocate(ptr, 8).unwrap();
+			assert_eq!(ptr.read(), 0xAB);
+
+			free(ptr);
+		}
+	}
+
+	#[test]
+	fn zero_sized() {
+		let ptr = allocate(0).unwrap();
+		unsafe {
+			let ptr = reallocate(ptr, 0).unwrap();
+			free(ptr);
+		}
+	}
+}
@@ -12,11 +12,13 @@ use platform::env::SystemPaths;
 use platform::libs::Library;
 
 mod class;
+pub mod foreign;
 pub mod intrinsics;
 pub mod jni;
 pub mod jvm;
 pub mod jvmti;
 pub mod lookup;
+pub mod memory;
 pub mod method;
 
 #[macro_export]
@@ -76,10 +78,10 @@ impl RawSymbolExt for Symbol {
 pub(crate) mod java {
 	pub(crate) mod io {
 		pub(crate) mod FileInputStream;
+		pub(crate) mod FileOutputStream;
 		pub(crate) mod UnixFileSystem;
-		pub(crate) mod FileDescriptor;
 		pub(crate) mod FileSystem;
-		pub(crate) mod FileOutputStream;
+		pub(crate) mod FileDescriptor;
 	}
 	pub(crate) mod lang {
 		pub(crate) mod invoke {
@@ -87,41 +89,59 @@ pub(crate) mod java {
 			pub(crate) mod MethodHandle;
 		}
 		pub(crate) mod r#ref {
-			pub(crate) mod PhantomReference;
 			pub(crate) mod Reference;
 			pub(crate) mod Finalizer;
+			pub(crate) mod PhantomReference;
 		}
 		pub(crate) mod reflect {
 			pub(crate) mod Array;
 		}
-		pub(crate) mod StringBuilder;
 		pub(crate) mod Runtime;
-		pub(crate) mod StringUTF16;
-		pub(crate) mod Throwable;
+		pub(crate) mod StringBuilder;
+		pub(crate) mod Class;
 		pub(crate) mod Thread;
 		pub(crate) mod String;
-		pub(crate) mod Class;
+		pub(crate) mod Throwable;
+		pub(crate) mod StringUTF16;
+		pub(crate) mod Math;
+		pub(crate) mod Integer;
+		pub(crate) mod StringLatin1;
+		pub(crate) mod System;
+		pub(crate) mod Object;
 	}
 	pub(crate) mod security {
 		pub(crate) mod AccessController;
 	}
+	pub(crate) mod util {
+		pub(crate) mod zip {
+			pub(crate) mod CRC32;
+		}
+		pub(crate) mod Arrays;
+	}
 }
 
 pub(crate) mod jdk {
 	pub(crate) mod internal {
+		pub(crate) mod foreign {
+			pub(crate) mod abi {
+				pub(crate) mod NativeEntryPoint;
+				pub(crate) mod UpcallLinker;
+				pub(crate) mod UpcallStubs;
+			}
+		}
 		pub(crate) mod misc {
-			pub(crate) mod ScopedMemoryAccess;
 			pub(crate) mod CDS;
 			pub(crate) mod VM;
+			pub(crate) mod ScopedMemoryAccess;
 			pub(crate) mod Unsafe;
 		}
-		pub(crate) mod util {
-			pub(crate) mod SystemProps;
-		}
 		pub(crate) mod reflect {
-			pub(crate) mod DirectConstructorHandleAccessor;
-			pub(crate) mod DirectMethodHandleAccessor;
 			pub(crate) mod Reflection;
+			pub(crate) mod DirectMethodHandleAccessor;
+			pub(crate) mod DirectConstructorHandleAccessor;
+		}
+		pub(crate) mod util {
+			pub(crate) mod SystemProps;
 		}
 	}
 }
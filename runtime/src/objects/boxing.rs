@@ -1,12 +1,13 @@
-use crate::java_call;
 use crate::objects::class::ClassPtr;
 use crate::objects::reference::Reference;
 use crate::symbols::{Symbol, sym};
 use crate::thread::JavaThread;
 use crate::thread::exceptions::Throws;
+use crate::{classes, globals, java_call};
 
+use classfile::FieldType;
 use instructions::Operand;
-use jni::sys::{jboolean, jdouble, jfloat, jint, jlong};
+use jni::sys::{jboolean, jbyte, jchar, jdouble, jfloat, jint, jlong, jshort};
 
 /// Box primitives into their object form (ex. `jint` -> `java.lang.Integer`)
 pub trait Boxable: Sized
@@ -83,3 +84,163 @@ impl Boxable for jfloat {
 		crate::globals::classes::java_lang_Float()
 	}
 }
+
+impl Boxable for jbyte {
+	const VALUE_OF_SIGNATURE: Symbol = sym!(Byte_valueOf_signature);
+
+	fn class() -> ClassPtr {
+		crate::globals::classes::java_lang_Byte()
+	}
+}
+
+impl Boxable for jshort {
+	const VALUE_OF_SIGNATURE: Symbol = sym!(Short_valueOf_signature);
+
+	fn class() -> ClassPtr {
+		crate::globals::classes::java_lang_Short()
+	}
+}
+
+impl Boxable for jchar {
+	const VALUE_OF_SIGNATURE: Symbol = sym!(Character_valueOf_signature);
+
+	fn class() -> ClassPtr {
+		crate::globals::classes::java_lang_Character()
+	}
+}
+
+/// Box `value`, which is of the primitive type `ty`
+///
+/// Unlike [`Boxable::into_box`] on an [`Operand`], this accounts for the types that are stored as
+/// an `int` on the stack. For example, a `boolean` becomes a `java.lang.Boolean`, rather than a
+/// `java.lang.Integer`.
+pub fn box_primitive(
+	thread: &'static JavaThread,
+	value: Operand<Reference>,
+	ty: &FieldType,
+) -> Throws<Reference> {
+	match ty {
+		FieldType::Boolean => (value.expect_int() != 0).into_box(thread),
+		FieldType::Byte => (value.expect_int() as jbyte).into_box(thread),
+		FieldType::Short => (value.expect_int() as jshort).into_box(thread),
+		FieldType::Character => (value.expect_int() as jchar).into_box(thread),
+		_ => value.into_box(thread),
+	}
+}
+
+/// Unbox `value` (ex. `java.lang.Integer` -> `jint`), and convert it to the primitive type `ty`
+///
+/// If the boxed type differs from `ty`, a [widening primitive conversion] is applied. This is the
+/// conversion used for reflective calls and array stores.
+///
+/// This will return `None` if `value` is null, not a primitive box, or can't be widened to `ty`.
+///
+/// [widening primitive conversion]: https://docs.oracle.com/javase/specs/jls/se23/html/jls-5.html#jls-5.1.2
+pub fn unbox_and_widen(value: Reference, ty: &FieldType) -> Option<Operand<Reference>> {
+	let (source_ty, value) = unbox(value)?;
+	widen(value, &source_ty, ty)
+}
+
+/// Unbox `value`, returning its primitive type along with its value
+///
+/// This will return `None` if `value` is null or not a primitive box.
+pub fn unbox(value: Reference) -> Option<(FieldType, Operand<Reference>)> {
+	if value.is_null() {
+		return None;
+	}
+
+	let class = value.extract_instance_class();
+	if class == globals::classes::java_lang_Boolean() {
+		let value = classes::java::lang::Boolean::value(value.extract_class());
+		return Some((FieldType::Boolean, Operand::Int(jint::from(value))));
+	}
+
+	if class == globals::classes::java_lang_Byte() {
+		let value = classes::java::lang::Byte::value(value.extract_class());
+		return Some((FieldType::Byte, Operand::Int(jint::from(value))));
+	}
+
+	if class == globals::classes::java_lang_Short() {
+		let value = classes::java::lang::Short::value(value.extract_class());
+		return Some((FieldType::Short, Operand::Int(jint::from(value))));
+	}
+
+	if class == globals::classes::java_lang_Character() {
+		let value = classes::java::lang::Character::value(value.extract_class());
+		return Some((FieldType::Character, Operand::Int(jint::from(value))));
+	}
+
+	if class == globals::classes::java_lang_Integer() {
+		let value = classes::java::lang::Integer::value(value.extract_class());
+		return Some((FieldType::Integer, Operand::Int(value)));
+	}
+
+	if class == globals::classes::java_lang_Long() {
+		let value = classes::java::lang::Long::value(value.extract_class());
+		return Some((FieldType::Long, Operand::Long(value)));
+	}
+
+	if class == globals::classes::java_lang_Float() {
+		let value = classes::java::lang::Float::value(value.extract_class());
+		return Some((FieldType::Float, Operand::Float(value)));
+	}
+
+	if class == globals::classes::java_lang_Double() {
+		let value = classes::java::lang::Double::value(value.extract_class());
+		return Some((FieldType::Double, Operand::Double(value)));
+	}
+
+	None
+}
+
+/// Apply a [widening primitive conversion] to `value`, from the primitive type `from` to `to`
+///
+/// This will return `None` if no such conversion exists.
+///
+/// [widening primitive conversion]: https://docs.oracle.com/javase/specs/jls/se23/html/jls-5.html#jls-5.1.2
+#[allow(clippy::cast_precision_loss)] // Precision loss is allowed by the spec for int/long -> float
+pub fn widen(
+	value: Operand<Reference>,
+	from: &FieldType,
+	to: &FieldType,
+) -> Option<Operand<Reference>> {
+	if from == to {
+		return Some(value);
+	}
+
+	let can_widen = match from {
+		FieldType::Byte => matches!(
+			to,
+			FieldType::Short
+				| FieldType::Integer
+				| FieldType::Long
+				| FieldType::Float
+				| FieldType::Double
+		),
+		FieldType::Short | FieldType::Character => matches!(
+			to,
+			FieldType::Integer | FieldType::Long | FieldType::Float | FieldType::Double
+		),
+		FieldType::Integer => matches!(to, FieldType::Long | FieldType::Float | FieldType::Double),
+		FieldType::Long => matches!(to, FieldType::Float | FieldType::Double),
+		FieldType::Float => matches!(to, FieldType::Double),
+		_ => false,
+	};
+
+	if !can_widen {
+		return None;
+	}
+
+	let widened = match (value, to) {
+		(Operand::Int(value), FieldType::Short | FieldType::Integer) => Operand::Int(value),
+		(Operand::Int(value), FieldType::Long) => Operand::Long(jlong::from(value)),
+		(Operand::Int(value), FieldType::Float) => Operand::Float(value as jfloat),
+		(Operand::Int(value), FieldType::Double) => Operand::Double(jdouble::from(value)),
+		(Operand::Long(value), FieldType::Float) => Operand::Float(value as jfloat),
+		(Operand::Long(value), FieldType::Double) => Operand::Double(value as jdouble),
+		(Operand::Float(value), FieldType::Double) => Operand::Double(jdouble::from(value)),
+		_ => unreachable!("operand type should match its field type"),
+	};
+
+	Some(widened)
+}
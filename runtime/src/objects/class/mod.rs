@@ -4,16 +4,18 @@ pub use ptr::ClassPtr;
 mod spec;
 pub mod vtable;
 
-pub use spec::ClassInitializationState;
 use spec::InitializationLock;
+pub use spec::{ClassInitializationState, MemberAccess};
 
 use super::constant_pool::ConstantPool;
 use super::field::Field;
 use super::method::Method;
 use crate::classpath::loader::ClassLoader;
 use crate::error::RuntimeError;
+use crate::gc::ReferenceType;
 use crate::modules::{Module, Package};
 use crate::objects::constant_pool::cp_types;
+use crate::objects::instance::array::{PrimitiveArrayInstance, PrimitiveArrayInstanceRef};
 use crate::objects::instance::mirror::MirrorInstanceRef;
 use crate::objects::reference::Reference;
 use crate::symbols::Symbol;
@@ -28,11 +30,13 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicPtr, Ordering};
 
 use classfile::accessflags::ClassAccessFlags;
+use classfile::attribute::Attribute;
 use classfile::attribute::resolved::ResolvedBootstrapMethod;
 use classfile::constant_pool::types::raw as raw_types;
 use classfile::{ClassFile, FieldType, MethodInfo};
+use common::array::IntoJByte;
 use common::box_slice;
-use common::int_types::{u1, u2, u4};
+use common::int_types::{s1, u1, u2, u4};
 use instructions::Operand;
 
 /// A cache for miscellaneous fields
@@ -148,6 +152,8 @@ pub struct Class {
 
 	class_ty: UnsafeCell<MaybeUninit<ClassType>>,
 
+	reference_type: ReferenceType,
+
 	init_lock: Arc<InitializationLock>,
 
 	// Used for fast path, initialization checks are needed for multiple instructions
@@ -191,18 +197,85 @@ pub struct InnerClassInfo {
 	pub inner_class_access_flags: u2,
 }
 
+pub struct RecordComponentInfo {
+	pub name: Symbol,
+	pub descriptor: FieldType,
+	pub descriptor_sym: Symbol,
+	pub signature: Option<Symbol>,
+	attributes: Box<[Attribute]>,
+}
+
+impl RecordComponentInfo {
+	pub fn annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
+			.iter()
+			.find_map(Attribute::runtime_visible_annotations)
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&attr.as_bytes().into_jbyte_array()).map(Some)
+	}
+
+	pub fn type_annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
+			.iter()
+			.find_map(Attribute::runtime_visible_type_annotations)
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&attr.as_bytes().into_jbyte_array()).map(Some)
+	}
+}
+
 pub struct ClassDescriptor {
 	pub source_file_index: Option<u2>,
 	pub constant_pool: ConstantPool,
 	pub enclosing_method: Option<EnclosingMethodInfo>,
 	inner_classes: Option<Vec<InnerClassInfo>>,
-	pub is_record: bool,
+	record_components: Option<Box<[RecordComponentInfo]>>,
+	permitted_subclasses: Option<Box<[Symbol]>>,
+	/// The generic signature of the class, from its `Signature` attribute
+	pub generic_signature: Option<Symbol>,
+	/// The encoded `RuntimeVisibleAnnotations` attribute
+	annotations: Option<Box<[s1]>>,
+	/// The encoded `RuntimeVisibleTypeAnnotations` attribute
+	type_annotations: Option<Box<[s1]>>,
+	pub minor_version: u2,
+	pub major_version: u2,
 }
 
 impl ClassDescriptor {
 	pub fn inner_classes(&self) -> Option<&[InnerClassInfo]> {
 		self.inner_classes.as_deref()
 	}
+
+	/// The components of the `Record` attribute, if this class is a record
+	pub fn record_components(&self) -> Option<&[RecordComponentInfo]> {
+		self.record_components.as_deref()
+	}
+
+	/// The names of the classes in the `PermittedSubclasses` attribute, if this class is sealed
+	pub fn permitted_subclasses(&self) -> Option<&[Symbol]> {
+		self.permitted_subclasses.as_deref()
+	}
+
+	pub fn annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		match self.annotations.as_deref() {
+			Some(annotations) => PrimitiveArrayInstance::new(annotations).map(Some),
+			None => Throws::Ok(None),
+		}
+	}
+
+	pub fn type_annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		match self.type_annotations.as_deref() {
+			Some(type_annotations) => PrimitiveArrayInstance::new(type_annotations).map(Some),
+			None => Throws::Ok(None),
+		}
+	}
 }
 
 impl Debug for ClassDescriptor {
@@ -222,7 +295,9 @@ impl Debug for ClassDescriptor {
 
 		debug_struct
 			.field("enclosing_method", &self.enclosing_method)
-			.field("is_record", &self.is_record)
+			.field("is_record", &self.record_components.is_some())
+			.field("major_version", &self.major_version)
+			.field("minor_version", &self.minor_version)
 			.finish_non_exhaustive()
 	}
 }
@@ -284,6 +359,11 @@ impl Class {
 		unsafe { (&*self.vtable.get()).assume_init_ref() }
 	}
 
+	/// Get the names of the classes in the `NestMembers` attribute, if this class is a nest host
+	pub fn nest_members(&self) -> Option<&[Symbol]> {
+		self.nest_members.as_deref()
+	}
+
 	/// Get a list of the interfaces this class implements
 	pub fn interfaces(&self) -> &[ClassPtr] {
 		self.interfaces.as_slice()
@@ -408,6 +488,10 @@ impl Class {
 	}
 
 	// TODO: This is expensive, requires locking. Should just be computed when the class is created.
+	/// The package of this class, if it is in a package defined to a named module
+	///
+	/// Packages of the unnamed module are never defined to the loader, so this will return `None`
+	/// for classes in the unnamed module.
 	pub fn package(&self) -> Option<&Package> {
 		let package_name = self.package_name().unwrap()?;
 
@@ -416,7 +500,6 @@ impl Class {
 			package = self.loader().lookup_package(guard, package_name);
 		});
 
-		assert!(package.is_some(), "Package not found in loader");
 		package
 	}
 
@@ -819,11 +902,16 @@ impl Class {
 	/// Whether the class is a record
 	pub fn is_record(&self) -> bool {
 		match self.class_ty() {
-			ClassType::Instance(instance) => instance.is_record,
+			ClassType::Instance(instance) => instance.record_components.is_some(),
 			_ => false,
 		}
 	}
 
+	/// The kind of `java.lang.ref.Reference` this class is, if any
+	pub fn reference_type(&self) -> ReferenceType {
+		self.reference_type
+	}
+
 	/// Whether the class is hidden
 	///
 	/// A hidden class is simply a class created by `java.lang.invoke.MethodHandles.Lookup#defineHiddenClass()`
@@ -968,11 +1056,66 @@ impl Class {
 			None => None,
 		};
 
-		// TODO: Actually retain the information from the record attribute
-		let is_record = parsed_file
+		let record_components = match parsed_file.record_components() {
+			Some(components) => {
+				let mut record_components = Vec::with_capacity(components.len());
+				for component in components {
+					let Ok(component) = component else {
+						throw!(@DEFER ClassFormatError, "Unable to resolve Record attribute in class file {name}");
+					};
+
+					let Ok(descriptor) = FieldType::parse(&mut &*component.descriptor) else {
+						throw!(@DEFER ClassFormatError, "Invalid record component descriptor in class file {name}");
+					};
+
+					record_components.push(RecordComponentInfo {
+						name: Symbol::intern(&*component.name),
+						descriptor,
+						descriptor_sym: Symbol::intern(&*component.descriptor),
+						signature: component
+							.signature
+							.map(|signature| Symbol::intern(&*signature)),
+						attributes: component.attributes.into(),
+					});
+				}
+
+				Some(record_components.into_boxed_slice())
+			},
+			None => None,
+		};
+
+		// Check the PermittedSubclasses attribute
+		let permitted_subclasses;
+		match parsed_file.permitted_subclasses() {
+			Some(classes) => {
+				permitted_subclasses = Some(classes
+                    .map(|entry| match entry {
+                        Ok(entry) => Throws::Ok(Symbol::intern(entry.name)),
+                        Err(_) => throw!(@DEFER ClassFormatError, "Invalid permitted subclass index in constant pool in class file {name}"),
+                    })
+                    .collect::<Throws<Box<[Symbol]>>>()?);
+			},
+			None => permitted_subclasses = None,
+		}
+
+		let Ok(generic_signature) = parsed_file.generic_signature() else {
+			throw!(@DEFER ClassFormatError, "Unable to resolve Signature attribute in class file {name}");
+		};
+		let generic_signature = generic_signature.map(|signature| Symbol::intern(&*signature));
+
+		let annotations = parsed_file
 			.attributes
 			.iter()
-			.any(|attr| attr.record().is_some());
+			.find_map(Attribute::runtime_visible_annotations)
+			.map(|attr| attr.as_bytes().into_jbyte_array());
+		let type_annotations = parsed_file
+			.attributes
+			.iter()
+			.find_map(Attribute::runtime_visible_type_annotations)
+			.map(|attr| attr.as_bytes().into_jbyte_array());
+
+		let minor_version = parsed_file.minor_version;
+		let major_version = parsed_file.major_version;
 
 		let static_field_count = parsed_file
 			.fields
@@ -987,6 +1130,8 @@ impl Class {
 
 		let static_field_slots = box_slice![UnsafeCell::new(Operand::Empty); static_field_count];
 
+		let reference_type = ReferenceType::for_class(name, loader, super_class);
+
 		let class = Self {
 			name: UnsafeCell::new(name),
 			access_flags,
@@ -1005,6 +1150,7 @@ impl Class {
 			nest_members,
 			bootstrap_methods,
 			class_ty: UnsafeCell::new(MaybeUninit::uninit()), // Set later
+			reference_type,
 			init_lock: Arc::new(InitializationLock::new()),
 			is_initialized: Cell::new(false),
 		};
@@ -1020,7 +1166,13 @@ impl Class {
 			constant_pool: ConstantPool::new(class_ptr, parsed_file.constant_pool),
 			inner_classes,
 			enclosing_method,
-			is_record,
+			record_components,
+			permitted_subclasses,
+			generic_signature,
+			annotations,
+			type_annotations,
+			minor_version,
+			major_version,
 		};
 		unsafe {
 			*class_ptr.class_ty.get() = MaybeUninit::new(ClassType::Instance(class_instance));
@@ -1126,6 +1278,7 @@ impl Class {
 			nest_members: None,
 			bootstrap_methods: None,
 			class_ty: UnsafeCell::new(MaybeUninit::new(ClassType::Array(array_instance))),
+			reference_type: ReferenceType::None,
 			init_lock: Arc::new(InitializationLock::new()),
 			is_initialized: Cell::new(false),
 		};
@@ -1,11 +1,12 @@
-use super::{Class, ClassType};
+use super::{Class, ClassType, MemberAccess};
 use crate::classpath::loader::ClassLoader;
 use crate::globals::classes;
+use crate::logging::info;
 use crate::objects::constant_pool::{ResolvedEntry, cp_types};
 use crate::objects::instance::mirror::{MirrorInstance, MirrorInstanceRef};
 use crate::symbols::Symbol;
 use crate::thread::JavaThread;
-use crate::thread::exceptions::Throws;
+use crate::thread::exceptions::{ExceptionKind, Throws};
 
 use std::fmt::Debug;
 use std::mem::MaybeUninit;
@@ -36,6 +37,17 @@ impl ClassPtr {
 		Self(class)
 	}
 
+	/// Construct a `ClassPtr` from a raw pointer
+	///
+	/// # Safety
+	///
+	/// The pointer must be non-null, and must point to a valid [`Class`] if the `ClassPtr` is ever
+	/// dereferenced.
+	#[cfg(test)]
+	pub(crate) unsafe fn from_raw(ptr: *mut Class) -> Self {
+		Self(ptr)
+	}
+
 	/// Raw access to the [`Class`], to be used sparingly
 	pub(super) fn raw(self) -> *mut Class {
 		self.0
@@ -68,17 +80,17 @@ impl ClassPtr {
 	///
 	/// This is only safe to call *before* the class is in use. It should never be used outside of
 	/// class loading.
-	pub unsafe fn set_mirror(self, mirror: Option<MirrorInstanceRef>) {
+	pub unsafe fn set_mirror(self, mirror: Option<MirrorInstanceRef>) -> Throws<()> {
 		let final_mirror = match mirror {
 			Some(mirror) => mirror,
 			None => match self.class_ty() {
 				ClassType::Instance(_) => {
-					let mirror = MirrorInstance::new(self);
+					let mirror = MirrorInstance::new(self)?;
 					mirror.set_module(self.module().obj());
 					mirror
 				},
 				ClassType::Array(_) => {
-					let mirror = MirrorInstance::new_array(self);
+					let mirror = MirrorInstance::new_array(self)?;
 					let bootstrap_loader = ClassLoader::bootstrap();
 					mirror.set_module(bootstrap_loader.java_base().obj());
 					mirror
@@ -89,6 +101,43 @@ impl ClassPtr {
 		unsafe {
 			*self.mirror.get() = MaybeUninit::new(final_mirror);
 		}
+
+		Throws::Ok(())
+	}
+
+	/// Free this class, along with all of the metadata it owns
+	///
+	/// This includes the methods and fields declared by this class (and with them, any native
+	/// method bindings), its constant pool, and its vtable. Inherited methods and fields belong to
+	/// their declaring classes, and are left untouched.
+	///
+	/// # Safety
+	///
+	/// The class must be unreachable. There can be no instances of it, no subclasses, and nothing
+	/// else may hold onto the class or any of its members for the rest of the VM's lifetime.
+	pub(crate) unsafe fn free(self) {
+		let class_ptr = (self.0 as usize & Self::POINTER_MASK) as *mut Class;
+
+		// SAFETY: Every `Class` is allocated by `ClassPtr::new()`
+		let mut class = unsafe { Box::from_raw(class_ptr) };
+
+		// SAFETY: The class was fully constructed, or it would have never been handed out
+		unsafe {
+			let vtable = class.vtable.get_mut().assume_init_read();
+			for method in vtable.iter_local() {
+				drop(Box::from_raw(std::ptr::from_ref(method).cast_mut()));
+			}
+			drop(vtable);
+
+			let fields = class.field_container.fields.get_mut().assume_init_read();
+			for field in fields {
+				if std::ptr::eq(&raw const *field.class, class_ptr) {
+					drop(Box::from_raw(std::ptr::from_ref(field).cast_mut()));
+				}
+			}
+
+			class.class_ty.get_mut().assume_init_drop();
+		}
 	}
 
 	/// Mangle the class name
@@ -119,13 +168,17 @@ impl ClassPtr {
 impl ClassPtr {
 	/// Fetch/resolve the nest host of this class
 	///
-	/// # Exceptions
+	/// If the nest host is invalid, `self` is used as the nest host instead. This happens when:
 	///
 	/// * Unable to resolve the nest host from the constant pool
 	/// * The nest host is invalid
 	///   * Not in the same package as `self`
 	///   * `self` isn't a member of the host
-	pub fn nest_host(self, _thread: &'static JavaThread) -> Throws<ClassPtr> {
+	///
+	/// # Exceptions
+	///
+	/// Only `VirtualMachineError`s encountered while resolving the nest host are propagated.
+	pub fn nest_host(self, thread: &'static JavaThread) -> Throws<ClassPtr> {
 		if let Some(nest_host) = unsafe { (*self.misc_cache.get()).nest_host } {
 			return Throws::Ok(nest_host);
 		}
@@ -151,28 +204,47 @@ impl ClassPtr {
 				let mut error = None;
 				if !self.shares_package_with(class) {
 					error = Some("types are in different packages");
-					todo!();
-				}
-
-				if !class.has_nest_member(self) {
+				} else if !class.has_nest_member(self) {
 					error = Some("current type is not listed as nest member");
-					todo!();
 				}
 
-				// Nest host resolved
-				if error.is_none() {
-					unsafe {
-						(*self.misc_cache.get()).nest_host = Some(class);
-					}
-					return Throws::Ok(class);
+				match error {
+					// Nest host resolved
+					None => {
+						unsafe {
+							(*self.misc_cache.get()).nest_host = Some(class);
+						}
+						return Throws::Ok(class);
+					},
+					Some(error) => {
+						info!(
+							TARGETS: (Class, Nestmates),
+							"Type {} (loader: {}) is not a nest member of type {} (loader: {}): {error}",
+							self.name(),
+							self.loader().name_and_id(),
+							class.name(),
+							class.loader().name_and_id()
+						);
+					},
 				}
 			},
 			Throws::Exception(e) => {
-				if e.kind().class()? == classes::java_lang_VirtualMachineError() {
-					return Throws::PENDING_EXCEPTION;
+				if e.kind()
+					.class()?
+					.is_subclass_of(classes::java_lang_VirtualMachineError())
+				{
+					return Throws::Exception(e);
+				}
+
+				if e.kind() == ExceptionKind::PendingException {
+					let _ = thread.take_pending_exception();
 				}
 
-				todo!("print nest host error")
+				info!(
+					TARGETS: (Class, Nestmates),
+					"Unable to resolve nest host of type {}, using self",
+					self.name()
+				);
 			},
 		}
 
@@ -185,7 +257,7 @@ impl ClassPtr {
 
 	/// If `self` is a nest host, check if `other` is a member of the nest
 	pub fn has_nest_member(self, other: Self) -> bool {
-		let Some(nest_members) = &self.nest_members else {
+		let Some(nest_members) = self.nest_members() else {
 			return false;
 		};
 
@@ -204,6 +276,47 @@ impl ClassPtr {
 	}
 }
 
+// Access control methods, since private access is granted to the whole nest
+impl ClassPtr {
+	/// Whether a member of this class, with the access level `access`, is accessible to the class `accessor`
+	///
+	/// `self` must be the class that declares the member. See [§5.4.4].
+	///
+	/// # Exceptions
+	///
+	/// See [`Self::nest_host()`]
+	///
+	/// [§5.4.4]: https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4.4
+	pub fn is_member_accessible_to(
+		self,
+		access: MemberAccess,
+		accessor: ClassPtr,
+		thread: &'static JavaThread,
+	) -> Throws<bool> {
+		if self == accessor {
+			return Throws::Ok(true);
+		}
+
+		// A field or method R is accessible to a class or interface D if and only if any of the following is true:
+		match access {
+			//     R is public.
+			MemberAccess::Public => Throws::Ok(true),
+			//     R is protected and is declared in a class C, and D is either a subclass of C or C itself.
+			//
+			//     R is either protected or has default access (that is, neither public nor protected nor private),
+			//     and is declared by a class in the same run-time package as D.
+			MemberAccess::Protected => Throws::Ok(
+				(!accessor.is_interface() && accessor.is_subclass_of(self))
+					|| self.shares_package_with(accessor),
+			),
+			MemberAccess::Package => Throws::Ok(self.shares_package_with(accessor)),
+			//     R is private and is declared by a class or interface C that belongs to the same nest as D, according
+			//     to the nestmate test below.
+			MemberAccess::Private => accessor.is_nestmate_of(self, thread),
+		}
+	}
+}
+
 impl Deref for ClassPtr {
 	type Target = Class;
 
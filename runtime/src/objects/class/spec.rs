@@ -1,6 +1,8 @@
+use crate::classpath::loader::constraints;
 use crate::globals::PRIMITIVES;
 use crate::java_call;
 use crate::logging::info;
+use crate::native::intrinsics::{self, IntrinsicId};
 use crate::native::java::lang::String::StringInterner;
 use crate::objects::class::{Class, ClassPtr};
 use crate::objects::constant_pool::cp_types;
@@ -15,7 +17,7 @@ use std::cell::UnsafeCell;
 use std::sync::{Condvar, Mutex, MutexGuard};
 
 use classfile::FieldType;
-use classfile::accessflags::MethodAccessFlags;
+use classfile::accessflags::{FieldAccessFlags, MethodAccessFlags};
 use instructions::Operand;
 
 // https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.5
@@ -32,6 +34,56 @@ pub enum ClassInitializationState {
 	Failed,
 }
 
+/// The access level of a field or method, used for access control (§5.4.4)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MemberAccess {
+	Public,
+	Protected,
+	/// No access flags set, accessible within the same run-time package
+	Package,
+	Private,
+}
+
+impl MemberAccess {
+	/// The modifier as it appears in error messages, with a trailing space
+	pub fn modifier(self) -> &'static str {
+		match self {
+			MemberAccess::Public => "public ",
+			MemberAccess::Protected => "protected ",
+			MemberAccess::Package => "",
+			MemberAccess::Private => "private ",
+		}
+	}
+}
+
+impl From<MethodAccessFlags> for MemberAccess {
+	fn from(flags: MethodAccessFlags) -> Self {
+		if flags.is_public() {
+			MemberAccess::Public
+		} else if flags.is_protected() {
+			MemberAccess::Protected
+		} else if flags.is_private() {
+			MemberAccess::Private
+		} else {
+			MemberAccess::Package
+		}
+	}
+}
+
+impl From<FieldAccessFlags> for MemberAccess {
+	fn from(flags: FieldAccessFlags) -> Self {
+		if flags.is_public() {
+			MemberAccess::Public
+		} else if flags.is_protected() {
+			MemberAccess::Protected
+		} else if flags.is_private() {
+			MemberAccess::Private
+		} else {
+			MemberAccess::Package
+		}
+	}
+}
+
 pub(super) struct InitializationLock(Mutex<InitializationGuard>, Condvar);
 
 impl InitializationLock {
@@ -167,11 +219,21 @@ impl Class {
 	}
 
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4
-	fn link(&self) {
+	pub(crate) fn link(&self) -> Throws<()> {
 		// Linking a class or interface involves verifying and preparing that class or interface, its direct superclass,
 		// its direct superinterfaces, and its element type (if it is an array type), if necessary.
 		// Linking also involves resolution of symbolic references in the class or interface, though
 		// not necessarily at the same time as the class or interface is verified and prepared.
+		self.prepare()?;
+
+		// Intrinsics are only ever consulted once the class is linked
+		for method in self.vtable().iter_local() {
+			if method.intrinsic_id() != IntrinsicId::None {
+				intrinsics::register_intrinsic(method, !method.is_static());
+			}
+		}
+
+		Throws::Ok(())
 	}
 
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4.2
@@ -187,19 +249,180 @@ impl Class {
 		}
 
 		// During preparation of a class or interface C, the Java Virtual Machine also imposes loading constraints (§5.3.4):
+		let loader = self.loader();
+		let super_interfaces = self.all_superinterfaces();
 
 		// Let L1 be the defining loader of C. For each instance method m declared in C that can override (§5.4.5) an instance
 		// method declared in a superclass or superinterface D = <N2, L2>, for each class or interface name N mentioned by the
 		// descriptor of m (§4.3.3), the Java Virtual Machine imposes the loading constraint NL1 = NL2.
+		for method in self.vtable().iter_local() {
+			if method.is_static() || method.is_private() || method.is_constructor() {
+				continue;
+			}
+
+			let super_types = self.parent_iter().chain(super_interfaces.iter().copied());
+			for super_type in super_types {
+				if super_type.loader() == loader {
+					continue;
+				}
+
+				let Some(super_method) = super_type.vtable().find_local(
+					method.name,
+					method.descriptor_sym(),
+					MethodAccessFlags::NONE,
+				) else {
+					continue;
+				};
+
+				if super_method.is_static() || !method.can_override(super_method) {
+					continue;
+				}
+
+				if let Err(name) = constraints::add_method_constraints(
+					&method.descriptor,
+					loader,
+					super_type.loader(),
+				) {
+					throw!(@DEFER LinkageError,
+						"loader constraint violation for class {}: when selecting overriding method '{}' the class loader {} of the selected method's type {}, and the class loader {} for its super type {} have different Class objects for the type {} used in the signature",
+						self.external_name(),
+						method.external_name(),
+						loader.name_and_id(),
+						self.external_name(),
+						super_type.loader().name_and_id(),
+						super_type.external_name(),
+						name.as_str().replace('/', ".")
+					);
+				}
+			}
+		}
 
 		// For each instance method m declared in a superinterface I = <N3, L3> of C, if C does not itself declare an instance
 		// method that can override m, then a method is selected (§5.4.6) with respect to C and the method m in I.
 		// Let D = <N2, L2> be the class or interface that declares the selected method. For each class or interface name N mentioned
 		// by the descriptor of m, the Java Virtual Machine imposes the loading constraint NL2 = NL3.
+		if self.is_interface() || self.is_array() {
+			return Throws::Ok(());
+		}
+
+		for interface in super_interfaces {
+			for method in interface.vtable().iter_local() {
+				if method.is_static() || method.is_private() || method.is_clinit() {
+					continue;
+				}
+
+				if self
+					.vtable()
+					.iter_local()
+					.any(|local| !local.is_static() && local.can_override(method))
+				{
+					continue;
+				}
+
+				let selected = self.select_method(method);
+				let selected_loader = selected.class().loader();
+				if selected_loader == interface.loader() {
+					continue;
+				}
+
+				if let Err(name) = constraints::add_method_constraints(
+					&method.descriptor,
+					selected_loader,
+					interface.loader(),
+				) {
+					throw!(@DEFER LinkageError,
+						"loader constraint violation for class {}: when selecting method '{}' the class loader {} for super interface {}, and the class loader {} of the selected method's {}, {} have different Class objects for the type {} used in the signature",
+						self.external_name(),
+						method.external_name(),
+						interface.loader().name_and_id(),
+						interface.external_name(),
+						selected_loader.name_and_id(),
+						selected.class().external_kind(),
+						selected.class().external_name(),
+						name.as_str().replace('/', ".")
+					);
+				}
+			}
+		}
 
 		Throws::Ok(())
 	}
 
+	/// Every superinterface of this class, direct or indirect, including those of its superclasses
+	fn all_superinterfaces(&self) -> Vec<ClassPtr> {
+		fn collect(interfaces: &[ClassPtr], out: &mut Vec<ClassPtr>) {
+			for interface in interfaces {
+				if out.contains(interface) {
+					continue;
+				}
+
+				out.push(*interface);
+				collect(interface.interfaces(), out);
+			}
+		}
+
+		let mut super_interfaces = Vec::new();
+		collect(self.interfaces(), &mut super_interfaces);
+		for super_class in self.parent_iter() {
+			collect(super_class.interfaces(), &mut super_interfaces);
+		}
+
+		super_interfaces
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4.4
+	/// Whether this class is accessible to the class `accessor`
+	pub fn is_accessible_to(&self, accessor: ClassPtr) -> bool {
+		if self == accessor {
+			return true;
+		}
+
+		// The accessibility of an array class is determined by the accessibility of its element type.
+		// Primitive arrays are accessible to all classes and interfaces.
+		if self.is_array() {
+			let FieldType::Object(element_name) = &self.unwrap_array_instance().component else {
+				return true;
+			};
+
+			// The array class shares a defining loader with its element type
+			return match self.loader().lookup_class(Symbol::intern(&**element_name)) {
+				Some(element) => element.is_accessible_to(accessor),
+				None => true,
+			};
+		}
+
+		// A class or interface C is accessible to a class or interface D if and only if any of the following is true:
+
+		//     C is not public, and C and D are members of the same run-time package (§5.3).
+		if !self.access_flags.is_public() {
+			return self.shares_package_with(accessor);
+		}
+
+		//     C is public, and a member of the same run-time module as D (§5.3.6).
+		let module = self.module();
+		let accessor_module = accessor.module();
+		if std::ptr::eq(module, accessor_module) {
+			return true;
+		}
+
+		//     C is public, and a member of a different run-time module than D, and C's run-time module is read by D's run-time module,
+		//     and C's run-time module exports C's run-time package to D's run-time module.
+		if !accessor_module.can_read(module) {
+			return false;
+		}
+
+		// Unnamed and open modules export all of their packages
+		if module.name().is_none() || module.is_open() {
+			return true;
+		}
+
+		let Some(package) = self.package() else {
+			return true;
+		};
+
+		crate::modules::with_module_lock(|guard| package.is_exported_to(guard, accessor_module))
+	}
+
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-5.html#jvms-5.4.3.2
 	pub fn resolve_field(&self, name: Symbol, descriptor: Symbol) -> Throws<&'static Field> {
 		fn inner(class: &Class, name: Symbol, descriptor: Symbol) -> Option<&'static Field> {
@@ -528,7 +751,7 @@ impl Class {
 						.constant_pool
 						.get::<cp_types::String>(constant_value_index)
 						.expect("string constants should always resolve");
-					let string_instance = StringInterner::intern(string);
+					let string_instance = StringInterner::intern(string)?;
 					let value = Operand::Reference(Reference::class(string_instance));
 					unsafe {
 						self.set_static_field(field.index(), value);
@@ -598,12 +821,19 @@ impl Class {
 		// A class or interface has at most one class or interface initialization method and is initialized
 		// by the Java Virtual Machine invoking that method (§5.5).
 
-		// TODO: Check version number for flags and parameters
+		// In a class file whose version number is 51.0 or above, the method has its ACC_STATIC flag set and takes no arguments (§4.6).
+		// In older class files, the flags of a method named <clinit> are irrelevant.
+		let flags = if self.unwrap_class_instance().major_version >= 51 {
+			MethodAccessFlags::ACC_STATIC
+		} else {
+			MethodAccessFlags::NONE
+		};
+
 		// A method is a class or interface initialization method if all of the following are true:
 		let method = self.vtable().find_local(
-			sym!(class_initializer_name),     /* It has the special name <clinit>. */
-			sym!(void_method_signature), /* It is void (§4.3.3). */
-			MethodAccessFlags::ACC_STATIC    /* In a class file whose version number is 51.0 or above, the method has its ACC_STATIC flag set and takes no arguments (§4.6). */
+			sym!(class_initializer_name), /* It has the special name <clinit>. */
+			sym!(void_method_signature),  /* It is void (§4.3.3). */
+			flags,
 		);
 
 		if let Some(method) = method {
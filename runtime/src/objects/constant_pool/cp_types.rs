@@ -1,8 +1,9 @@
 use super::entry::ResolvedEntry;
+use crate::classpath::loader::constraints;
 use crate::native::java::lang::String::StringInterner;
 use crate::native::java::lang::invoke::MethodHandleNatives;
 use crate::objects::boxing::Boxable;
-use crate::objects::class::ClassPtr;
+use crate::objects::class::{ClassPtr, MemberAccess};
 use crate::objects::constant_pool::ConstantPool;
 use crate::objects::field::Field;
 use crate::objects::instance::array::{Array, ObjectArrayInstance};
@@ -89,8 +90,20 @@ impl EntryType for Class {
 	) -> Throws<ResolvedEntry> {
 		let name = unsafe { cp.resolve_entry_with::<ConstantUtf8>(value.name_index, value.name)? };
 
-		let class = class.loader().load(name)?;
-		Throws::Ok(ResolvedEntry { class })
+		let resolved = class.loader().resolve_or_fail(name)?;
+
+		// Finally, access control is applied for the access from D to C (§5.4.4)
+		if !resolved.is_accessible_to(class) {
+			throw!(@DEFER IllegalAccessError,
+				"failed to access class {} from class {} ({}; {})",
+				resolved.external_name(),
+				class.external_name(),
+				resolved.in_module_of_loader(false, false),
+				class.in_module_of_loader(false, false)
+			);
+		}
+
+		Throws::Ok(ResolvedEntry { class: resolved })
 	}
 }
 
@@ -322,7 +335,7 @@ impl EntryType for InvokeDynamic {
 			cp.resolve_entry_with::<NameAndType>(value.name_and_type_index, value.name_and_type)?
 		};
 
-		let name_arg = StringInterner::intern(name);
+		let name_arg = StringInterner::intern(name)?;
 		let descriptor_str = descriptor.as_str();
 
 		let type_arg;
@@ -357,7 +370,7 @@ impl EntryType for InvokeDynamic {
 				LoadableConstantPoolValueInner::Class(_) => todo!("Class static argument"),
 				LoadableConstantPoolValueInner::String(ref val) => {
 					let sym = Symbol::intern(val);
-					r = Reference::class(StringInterner::intern(sym));
+					r = Reference::class(StringInterner::intern(sym)?);
 				},
 				LoadableConstantPoolValueInner::MethodHandle(_) => {
 					r = cp.get::<MethodHandle>(arg.index)?;
@@ -514,7 +527,7 @@ impl EntryType for MethodHandle {
 			Operand::Reference(Reference::mirror(invoking_class.mirror())),
 			Operand::Int(value.reference_kind as i32),
 			Operand::Reference(Reference::mirror(callee_class.mirror())),
-			Operand::Reference(Reference::class(StringInterner::intern(name))),
+			Operand::Reference(Reference::class(StringInterner::intern(name)?)),
 			Operand::Reference(ty_arg),
 		);
 
@@ -547,7 +560,7 @@ impl EntryType for FieldRef {
 	}
 
 	fn resolve_with(
-		_: ClassPtr,
+		accessor: ClassPtr,
 		cp: &ConstantPool,
 		_: u2,
 		value: <Self::RawEntryType as CpEntry<'_>>::Entry,
@@ -558,6 +571,41 @@ impl EntryType for FieldRef {
 		};
 
 		let field = class.resolve_field(name, descriptor)?;
+
+		let access = MemberAccess::from(field.access_flags);
+		if !field
+			.class
+			.is_member_accessible_to(access, accessor, JavaThread::current())?
+		{
+			throw!(@DEFER IllegalAccessError,
+				"class {} tried to access {}field {}.{} ({}; {})",
+				accessor.external_name(),
+				access.modifier(),
+				field.class.external_name(),
+				field.name,
+				accessor.in_module_of_loader(false, false),
+				field.class.in_module_of_loader(false, false)
+			);
+		}
+
+		if let Err(constrained_name) = constraints::add_field_constraint(
+			&field.descriptor,
+			accessor.loader(),
+			field.class.loader(),
+		) {
+			throw!(@DEFER LinkageError,
+				"loader constraint violation: when resolving field \"{}\" of type {}, the class loader {} of the current class, {}, and the class loader {} for the field's defining {}, {}, have different Class objects for type {}",
+				field.name,
+				field.descriptor.as_java_type(true),
+				accessor.loader().name_and_id(),
+				accessor.external_name(),
+				field.class.loader().name_and_id(),
+				field.class.external_kind(),
+				field.class.external_name(),
+				constrained_name.as_str().replace('/', ".")
+			);
+		}
+
 		Throws::Ok(ResolvedEntry { field_ref: field })
 	}
 }
@@ -605,7 +653,7 @@ impl EntryType for MethodRef {
 	}
 
 	fn resolve_with(
-		_: ClassPtr,
+		accessor: ClassPtr,
 		cp: &ConstantPool,
 		_: u2,
 		value: <Self::RawEntryType as CpEntry<'_>>::Entry,
@@ -622,6 +670,45 @@ impl EntryType for MethodRef {
 			method_ref = class.resolve_method(name, descriptor)?;
 		}
 
+		let declaring_class = method_ref.class();
+
+		// NON-SPEC: `Object#clone()` is protected, but is public for array classes
+		let mut access = MemberAccess::from(method_ref.access_flags);
+		if class.is_array()
+			&& declaring_class == crate::globals::classes::java_lang_Object()
+			&& method_ref.name == sym!(clone)
+		{
+			access = MemberAccess::Public;
+		}
+
+		if !declaring_class.is_member_accessible_to(access, accessor, JavaThread::current())? {
+			throw!(@DEFER IllegalAccessError,
+				"class {} tried to access {}method '{}' ({}; {})",
+				accessor.external_name(),
+				access.modifier(),
+				method_ref.external_name(),
+				accessor.in_module_of_loader(false, false),
+				declaring_class.in_module_of_loader(false, false)
+			);
+		}
+
+		if let Err(constrained_name) = constraints::add_method_constraints(
+			&method_ref.descriptor,
+			accessor.loader(),
+			declaring_class.loader(),
+		) {
+			throw!(@DEFER LinkageError,
+				"loader constraint violation: when resolving method '{}' the class loader {} of the current class, {}, and the class loader {} for the method's defining {}, {}, have different Class objects for the type {} used in the signature",
+				method_ref.external_name(),
+				accessor.loader().name_and_id(),
+				accessor.external_name(),
+				declaring_class.loader().name_and_id(),
+				declaring_class.external_kind(),
+				declaring_class.external_name(),
+				constrained_name.as_str().replace('/', ".")
+			);
+		}
+
 		let (parameter_count, parameters_stack_size) = if method_ref.is_var_args() {
 			let descriptor = MethodDescriptor::parse(&mut descriptor.as_bytes())
 				.expect("an invalid descriptor shouldn't make it this far");
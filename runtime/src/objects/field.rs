@@ -1,13 +1,19 @@
 use super::reference::Reference;
 use crate::objects::class::ClassPtr;
 use crate::objects::constant_pool::{ConstantPool, cp_types};
+use crate::objects::instance::array::{PrimitiveArrayInstance, PrimitiveArrayInstanceRef};
+use crate::objects::instance::mirror::MirrorInstanceRef;
+use crate::objects::method::field_type_mirror;
 use crate::symbols::Symbol;
+use crate::thread::exceptions::Throws;
 
 use std::cell::SyncUnsafeCell;
 use std::fmt::{Debug, Formatter};
 
 use classfile::accessflags::FieldAccessFlags;
+use classfile::attribute::Attribute;
 use classfile::{FieldInfo, FieldType};
+use common::array::IntoJByte;
 use common::int_types::u2;
 use instructions::Operand;
 
@@ -24,6 +30,8 @@ pub struct Field {
 	pub descriptor: FieldType,
 	pub descriptor_sym: Symbol,
 	pub constant_value_index: Option<u2>,
+	attributes: Box<[Attribute]>,
+	is_injected: bool,
 }
 
 impl Debug for Field {
@@ -59,6 +67,14 @@ impl Field {
 	pub fn is_trusted_final(&self) -> bool {
 		self.is_final() && (self.is_static() || self.class.is_record())
 	}
+
+	/// Whether this field was injected by the VM, rather than declared in the class file
+	///
+	/// Injected fields are never visible to Java code.
+	#[inline]
+	pub fn is_injected(&self) -> bool {
+		self.is_injected
+	}
 }
 
 impl Field {
@@ -102,6 +118,52 @@ impl Field {
 	}
 }
 
+impl Field {
+	/// Get the mirror for the type of this field
+	///
+	/// NOTE: This may load other classes, which will be done using the field's ClassLoader
+	pub fn type_mirror(&self) -> Throws<MirrorInstanceRef> {
+		field_type_mirror(self.class, &self.descriptor)
+	}
+
+	pub fn generic_signature(&self) -> Option<Symbol> {
+		self.attributes
+			.iter()
+			.find_map(Attribute::signature)
+			.map(|signature_attr| {
+				self.class
+					.constant_pool()
+					.unwrap()
+					.get::<cp_types::ConstantUtf8>(signature_attr.signature_index)
+					.expect("resolution of field signatures should not fail")
+			})
+	}
+
+	pub fn annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
+			.iter()
+			.find_map(Attribute::runtime_visible_annotations)
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&attr.as_bytes().into_jbyte_array()).map(Some)
+	}
+
+	pub fn type_annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
+			.iter()
+			.find_map(Attribute::runtime_visible_type_annotations)
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&attr.as_bytes().into_jbyte_array()).map(Some)
+	}
+}
+
 impl Field {
 	/// Create a new `Field` instance
 	///
@@ -145,6 +207,8 @@ impl Field {
 			descriptor,
 			descriptor_sym,
 			constant_value_index,
+			attributes: field_info.attributes.clone(),
+			is_injected: false,
 		}))
 	}
 
@@ -160,6 +224,8 @@ impl Field {
 			descriptor,
 			descriptor_sym,
 			constant_value_index: None,
+			attributes: Box::default(),
+			is_injected: true,
 		}))
 	}
 
@@ -6,6 +6,7 @@ pub mod object;
 use crate::objects::field::Field;
 use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
 
 use std::mem::offset_of;
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -212,14 +213,17 @@ pub trait Instance: object::Object {
 pub trait CloneableInstance {
 	type ReferenceTy;
 
-	// TODO: Throw for OOM
 	/// Clone the object that this reference points to
 	///
+	/// # Exceptions
+	///
+	/// This will throw `OutOfMemoryError` if the heap is exhausted.
+	///
 	/// # Safety
 	///
 	/// The caller **must** verify that the object is cloneable. This should rarely, if ever, be
 	/// used directly.
-	unsafe fn clone(&self) -> Self::ReferenceTy;
+	unsafe fn clone(&self) -> Throws<Self::ReferenceTy>;
 }
 
 #[repr(C)]
@@ -280,16 +284,22 @@ impl Header {
 			.is_ok()
 	}
 
-	// TODO: This only supports the HotSpot default hash code generation, there are still 4 other possible algorithms.
-	// https://github.com/openjdk/jdk/blob/807f6f7fb868240cba5ba117c7059216f69a53f9/src/hotspot/share/runtime/synchronizer.cpp#L935
+	/// Get the identity hash code, generating one if necessary
+	///
+	/// The algorithm is selected with `-XX:hashCode`, see [`JavaThread::next_identity_hash()`].
 	pub fn generate_hash(&self, thread: &'static JavaThread) -> jint {
+		let address = std::ptr::from_ref(self).addr();
+		self.generate_hash_with(|| thread.next_identity_hash(address))
+	}
+
+	fn generate_hash_with(&self, mut next_hash: impl FnMut() -> jint) -> jint {
 		loop {
 			let current_hash = self.hash();
 			if let Some(hash) = current_hash {
 				return hash;
 			}
 
-			let hash = thread.marsaglia_xor_shift_hash() as jint;
+			let hash = next_hash();
 			if self.set_hash(hash) {
 				return hash;
 			}
@@ -299,13 +309,61 @@ impl Header {
 
 #[cfg(test)]
 mod tests {
+	use super::Header;
+	use crate::thread::hash::{HashState, finish_hash};
+
+	use std::sync::{Arc, Barrier};
+
 	#[test]
 	fn header_hash_set_single_thread() {
-		todo!()
+		let header = Header::new();
+		assert_eq!(header.hash(), None);
+
+		let mut state = HashState::new(1);
+		let hash = header.generate_hash_with(|| finish_hash(state.next()));
+		assert_eq!(header.hash(), Some(hash));
+
+		// The same state must produce the same hash
+		assert_eq!(hash, finish_hash(HashState::new(1).next()));
+
+		// Once set, the hash never changes
+		let mut state = HashState::new(2);
+		assert_eq!(
+			header.generate_hash_with(|| finish_hash(state.next())),
+			hash
+		);
+		assert_eq!(header.hash(), Some(hash));
 	}
 
 	#[test]
 	fn header_hash_set_contention() {
-		todo!()
+		const THREADS: u32 = 16;
+
+		let header = Arc::new(Header::new());
+		let barrier = Arc::new(Barrier::new(THREADS as usize));
+
+		let threads = (0..THREADS)
+			.map(|seed| {
+				let header = Arc::clone(&header);
+				let barrier = Arc::clone(&barrier);
+				std::thread::spawn(move || {
+					let mut state = HashState::new(seed + 1);
+					let candidate = finish_hash(state.next());
+
+					barrier.wait();
+					(candidate, header.generate_hash_with(|| candidate))
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let results = threads
+			.into_iter()
+			.map(|thread| thread.join().unwrap())
+			.collect::<Vec<_>>();
+
+		// Every thread must have observed the same hash, which was generated by one of them
+		let hash = header.hash().expect("hash should be set");
+		assert!(results.iter().all(|(_, observed)| *observed == hash));
+		assert!(results.iter().any(|(candidate, _)| *candidate == hash));
 	}
 }
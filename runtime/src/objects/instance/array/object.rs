@@ -2,7 +2,7 @@
 mod tests;
 
 use crate::objects::class::ClassPtr;
-use crate::objects::instance::array::Array;
+use crate::objects::instance::array::{Array, PrimitiveArrayInstance};
 use crate::objects::instance::object::Object;
 use crate::objects::instance::{CloneableInstance, Header};
 use crate::objects::reference::Reference;
@@ -104,38 +104,57 @@ impl ObjectArrayInstance {
 		};
 		let elements_size = count * size_of::<<ObjectArrayInstanceRef as Array>::Component>();
 
-		let new_array = unsafe { ObjectArrayInstanceRef::allocate(header, elements_size) };
+		let new_array = unsafe { ObjectArrayInstanceRef::allocate(header, elements_size)? };
 
 		Throws::Ok(ObjectArrayInstanceRef(new_array))
 	}
 
+	/// Allocate a multi-dimensional array of type `array_class`, with the lengths of each dimension
+	/// specified in `counts`
+	///
+	/// Only the first `counts.len()` dimensions are allocated, any deeper components are left as
+	/// `null`. This returns a primitive array if `array_class` is one-dimensional.
+	///
+	/// # Exceptions
+	///
+	/// This will throw `NegativeArraySizeException` if any of the counts are negative.
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-6.html#jvms-6.5.multianewarray
 	pub fn new_multidimensional(
 		counts: impl IntoIterator<Item = s4>,
 		array_class: ClassPtr,
-	) -> Throws<ObjectArrayInstanceRef> {
-		fn inner(
-			parent: ObjectArrayInstanceRef,
-			parent_count: s4,
-			counts: &mut impl Iterator<Item = s4>,
-			array_class: ClassPtr,
-		) -> Throws<()> {
-			let Some(count) = counts.next() else {
-				return Throws::Ok(());
-			};
-
-			let component_name = array_class.array_component_name();
+	) -> Throws<Reference> {
+		fn inner(counts: &[s4], array_class: ClassPtr) -> Throws<Reference> {
+			let (&count, rest) = counts
+				.split_first()
+				.expect("multi-dimensional arrays must have at least one dimension");
+
+			let descriptor = array_class.unwrap_array_instance();
+			if descriptor.dimensions == 1
+				&& let Some(type_code) = descriptor.component.as_array_type_code()
+			{
+				debug_assert!(rest.is_empty(), "too many dimensions for {array_class:?}");
+				let array = PrimitiveArrayInstance::new_from_type(type_code, count)?;
+				return Throws::Ok(Reference::array(array));
+			}
+
+			let array = ObjectArrayInstance::new_inner(count as usize, array_class)?;
+			if rest.is_empty() {
+				return Throws::Ok(Reference::object_array(array));
+			}
+
 			let component_class = array_class
 				.loader()
-				.load(component_name)
-				.expect("component classes must exist");
-			for i in 0..parent_count {
-				let instance = ObjectArrayInstance::new(count, array_class)?;
-				inner(instance, parent_count, counts, component_class)?;
-				parent.store(i, Reference::object_array(instance))?;
+				.load(array_class.array_component_name())?;
+			for index in 0..count as usize {
+				let component = inner(rest, component_class)?;
+
+				// SAFETY: `index` is within the bounds of the newly created array
+				unsafe {
+					array.store_unchecked(index, component);
+				}
 			}
 
-			Throws::Ok(())
+			Throws::Ok(Reference::object_array(array))
 		}
 
 		assert!(
@@ -143,35 +162,21 @@ impl ObjectArrayInstance {
 			"multi-dimensional arrays must have array component types"
 		);
 
-		let mut counts = counts.into_iter();
-
-		let initial_count = counts
-			.next()
-			.expect("multi-dimensional arrays must have at least one element");
-		let initial_instance = Self::new(initial_count, array_class)?;
-
-		let component_name = array_class.array_component_name();
-		let component_class = array_class
-			.loader()
-			.load(component_name)
-			.expect("component classes must exist");
-		inner(
-			initial_instance,
-			initial_count,
-			&mut counts,
-			component_class,
-		)?;
+		// Every count is checked before anything gets allocated
+		let counts = counts.into_iter().collect::<Vec<_>>();
+		if counts.iter().any(|count| count.is_negative()) {
+			throw!(@DEFER NegativeArraySizeException);
+		}
 
-		Throws::Ok(initial_instance)
+		inner(&counts, array_class)
 	}
 }
 
 impl CloneableInstance for ObjectArrayInstanceRef {
 	type ReferenceTy = ObjectArrayInstanceRef;
 
-	unsafe fn clone(&self) -> Self::ReferenceTy {
-		let cloned_instance =
-			ObjectArrayInstance::new_inner(self.length as usize, self.class).expect("oom");
+	unsafe fn clone(&self) -> Throws<Self::ReferenceTy> {
+		let cloned_instance = ObjectArrayInstance::new_inner(self.length as usize, self.class)?;
 
 		// SAFETY: References are `Copy`
 		unsafe {
@@ -183,7 +188,7 @@ impl CloneableInstance for ObjectArrayInstanceRef {
 				);
 		}
 
-		cloned_instance
+		Throws::Ok(cloned_instance)
 	}
 }
 
@@ -13,7 +13,7 @@ fn clone() {
 
 	let array =
 		ObjectArrayInstance::new(20, classes::java_lang_Class()).expect("should be a valid array");
-	let cloned = unsafe { CloneableInstance::clone(&array) };
+	let cloned = unsafe { CloneableInstance::clone(&array) }.expect("clone should work");
 
 	assert_eq!(array.len(), 20);
 	assert_eq!(array.len(), cloned.len());
@@ -30,7 +30,7 @@ fn clone() {
 		"cloned array shouldn't be updated"
 	);
 
-	let cloned = unsafe { CloneableInstance::clone(&array) };
+	let cloned = unsafe { CloneableInstance::clone(&array) }.expect("clone should work");
 	assert_eq!(
 		cloned.array_get(1).expect("get should work"),
 		Reference::mirror(classes::java_lang_String().mirror()),
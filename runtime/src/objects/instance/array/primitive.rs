@@ -118,6 +118,11 @@ impl PrimitiveArrayInstanceRef {
 		}
 	}
 
+	/// The type of the elements in this array
+	pub fn type_code(&self) -> TypeCode {
+		self.ty
+	}
+
 	pub fn as_bytes(&self) -> &[u8] {
 		// SAFETY: Every primtive type can be represented as bytes
 		unsafe { slice::from_raw_parts(self.field_base().cast_const(), self.length as usize) }
@@ -187,16 +192,16 @@ pub struct PrimitiveArrayInstance {
 impl CloneableInstance for PrimitiveArrayInstanceRef {
 	type ReferenceTy = PrimitiveArrayInstanceRef;
 
-	unsafe fn clone(&self) -> Self::ReferenceTy {
+	unsafe fn clone(&self) -> Throws<Self::ReferenceTy> {
 		let cloned_instance =
-			PrimitiveArrayInstance::new_from_type(self.ty as _, self.length as s4).expect("oom");
+			PrimitiveArrayInstance::new_from_type(self.ty as _, self.length as s4)?;
 
 		// SAFETY: All primitive types are `Copy`
 		unsafe {
 			self.copy_into(0, &cloned_instance, 0, self.length as usize);
 		}
 
-		cloned_instance
+		Throws::Ok(cloned_instance)
 	}
 }
 
@@ -231,14 +236,16 @@ impl PrimitiveType for jlong {
 }
 
 impl PrimitiveArrayInstance {
-	// TODO: Should throw on OOM
 	/// Convenience constructor for allocating an array and copying `elements` into it
-	pub fn new<T>(elements: &[T]) -> PrimitiveArrayInstanceRef
+	///
+	/// # Exceptions
+	///
+	/// This will throw `OutOfMemoryError` if the heap is exhausted.
+	pub fn new<T>(elements: &[T]) -> Throws<PrimitiveArrayInstanceRef>
 	where
 		T: PrimitiveType,
 	{
-		let array = PrimitiveArrayInstance::new_from_type(T::TYPE_CODE as _, elements.len() as _)
-			.expect("valid");
+		let array = PrimitiveArrayInstance::new_from_type(T::TYPE_CODE as _, elements.len() as _)?;
 
 		{
 			// SAFETY: Array is guaranteed to be allocated with T's type code
@@ -248,7 +255,7 @@ impl PrimitiveArrayInstance {
 			new_array_slice.copy_from_slice(&elements);
 		}
 
-		array
+		Throws::Ok(array)
 	}
 
 	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-6.html#jvms-6.5.newarray
@@ -269,7 +276,7 @@ impl PrimitiveArrayInstance {
 		};
 		let array_size = type_code.size() * count as usize;
 
-		let array_ptr = unsafe { PrimitiveArrayInstanceRef::allocate(descriptor, array_size) };
+		let array_ptr = unsafe { PrimitiveArrayInstanceRef::allocate(descriptor, array_size)? };
 
 		Throws::Ok(PrimitiveArrayInstanceRef(array_ptr))
 	}
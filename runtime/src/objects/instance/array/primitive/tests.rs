@@ -11,8 +11,8 @@ use jni::sys::{jbyte, jint};
 fn clone() {
 	init_basic_shared_runtime();
 
-	let array = PrimitiveArrayInstance::new(&[0i8; 20]);
-	let cloned = unsafe { CloneableInstance::clone(&array) };
+	let array = PrimitiveArrayInstance::new(&[0i8; 20]).expect("should be a valid array");
+	let cloned = unsafe { CloneableInstance::clone(&array) }.expect("clone should work");
 
 	assert_eq!(array.len(), 20);
 	assert_eq!(array.len(), cloned.len());
@@ -27,7 +27,7 @@ fn clone() {
 		"cloned array shouldn't be updated"
 	);
 
-	let cloned = unsafe { CloneableInstance::clone(&array) };
+	let cloned = unsafe { CloneableInstance::clone(&array) }.expect("clone should work");
 	assert_eq!(
 		cloned.array_get(1).expect("get should work"),
 		Operand::Int(15),
@@ -39,7 +39,8 @@ fn clone() {
 fn slice() {
 	init_basic_shared_runtime();
 
-	let array = PrimitiveArrayInstance::new(&[1_i32, 2_i32, 3_i32, 4_i32, 5_i32]);
+	let array = PrimitiveArrayInstance::new(&[1_i32, 2_i32, 3_i32, 4_i32, 5_i32])
+		.expect("should be a valid array");
 	let slice = array.as_slice::<jint>();
 
 	for (value, expected) in slice.iter().zip(1..=5) {
@@ -52,7 +53,8 @@ fn slice() {
 fn slice_wrong_type() {
 	init_basic_shared_runtime();
 
-	let array = PrimitiveArrayInstance::new(&[1_i32, 2_i32, 3_i32, 4_i32, 5_i32]);
+	let array = PrimitiveArrayInstance::new(&[1_i32, 2_i32, 3_i32, 4_i32, 5_i32])
+		.expect("should be a valid array");
 	let _slice = array.as_slice::<jbyte>();
 }
 
@@ -60,7 +62,7 @@ fn slice_wrong_type() {
 fn volatile() {
 	init_basic_shared_runtime();
 
-	let array = PrimitiveArrayInstance::new(&[0_i32; 5]);
+	let array = PrimitiveArrayInstance::new(&[0_i32; 5]).expect("should be a valid array");
 
 	let values = box_slice![1_i32, 2_i32, 3_i32, 4_i32, 5_i32];
 
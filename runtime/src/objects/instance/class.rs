@@ -2,6 +2,7 @@ use crate::objects::class::ClassPtr;
 use crate::objects::instance::object::Object;
 use crate::objects::instance::{CloneableInstance, Header, Instance};
 use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
 
 use jni::sys::jint;
 use std::fmt::{Debug, Formatter};
@@ -82,15 +83,20 @@ impl Debug for ClassInstance {
 }
 
 impl ClassInstance {
-	pub fn new(class: ClassPtr) -> ClassInstanceRef {
+	/// Allocate a new instance of `class`, with all of its fields zeroed
+	///
+	/// # Exceptions
+	///
+	/// This will throw `OutOfMemoryError` if the heap is exhausted.
+	pub fn new(class: ClassPtr) -> Throws<ClassInstanceRef> {
 		let descriptor = ClassInstance {
 			header: Header::new(),
 			class,
 		};
 
 		let fields_size = class.size_of_instance_fields();
-		let instance_ptr = unsafe { ClassInstanceRef::allocate(descriptor, fields_size) };
-		ClassInstanceRef(instance_ptr)
+		let instance_ptr = unsafe { ClassInstanceRef::allocate(descriptor, fields_size)? };
+		Throws::Ok(ClassInstanceRef(instance_ptr))
 	}
 
 	pub fn is_subclass_of(&self, class: ClassPtr) -> bool {
@@ -105,8 +111,8 @@ impl ClassInstance {
 impl CloneableInstance for ClassInstanceRef {
 	type ReferenceTy = ClassInstanceRef;
 
-	unsafe fn clone(&self) -> Self::ReferenceTy {
-		let cloned_instance = ClassInstance::new(self.class);
+	unsafe fn clone(&self) -> Throws<Self::ReferenceTy> {
+		let cloned_instance = ClassInstance::new(self.class)?;
 		let fields_size = self.class.size_of_instance_fields();
 
 		// SAFETY: Every field type is `Copy`
@@ -115,7 +121,7 @@ impl CloneableInstance for ClassInstanceRef {
 				.copy_to_nonoverlapping(cloned_instance.field_base().cast(), fields_size);
 		}
 
-		cloned_instance
+		Throws::Ok(cloned_instance)
 	}
 }
 
@@ -5,6 +5,7 @@ use crate::objects::instance::object::Object;
 use crate::objects::instance::{Header, Instance};
 use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
 
 use classfile::FieldType;
 use instructions::Operand;
@@ -163,7 +164,7 @@ impl Debug for MirrorInstance {
 }
 
 impl MirrorInstance {
-	pub fn new(target: ClassPtr) -> MirrorInstanceRef {
+	pub fn new(target: ClassPtr) -> Throws<MirrorInstanceRef> {
 		let descriptor = MirrorInstance {
 			header: Header::new(),
 			target: MirrorTarget::Class(target),
@@ -171,12 +172,17 @@ impl MirrorInstance {
 
 		let mirror_class = crate::globals::classes::java_lang_Class();
 		let fields_size = mirror_class.size_of_instance_fields();
-		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size) };
-
-		MirrorInstanceRef::new(instance_ptr, target, target.access_flags().as_u2(), false)
+		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size)? };
+
+		Throws::Ok(MirrorInstanceRef::new(
+			instance_ptr,
+			target,
+			target.access_flags().as_u2(),
+			false,
+		))
 	}
 
-	pub fn new_array(target: ClassPtr) -> MirrorInstanceRef {
+	pub fn new_array(target: ClassPtr) -> Throws<MirrorInstanceRef> {
 		let descriptor = MirrorInstance {
 			header: Header::new(),
 			target: MirrorTarget::Class(target),
@@ -184,7 +190,7 @@ impl MirrorInstance {
 
 		let mirror_class = crate::globals::classes::java_lang_Class();
 		let fields_size = mirror_class.size_of_instance_fields();
-		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size) };
+		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size)? };
 
 		let ret =
 			MirrorInstanceRef::new(instance_ptr, target, target.access_flags().as_u2(), false);
@@ -200,7 +206,7 @@ impl MirrorInstance {
 				.expect("all primitives are covered");
 			component_type_mirror = crate::globals::mirrors::primitive_mirror_for(field_type);
 		} else {
-			let component_class = target.loader().load(component_type).unwrap(); // TODO: handle throws
+			let component_class = target.loader().load(component_type)?;
 			component_type_mirror = Reference::mirror(component_class.mirror());
 		}
 
@@ -208,7 +214,7 @@ impl MirrorInstance {
 			classes::java::lang::Class::componentType_field_index(),
 			Operand::Reference(component_type_mirror),
 		);
-		ret
+		Throws::Ok(ret)
 	}
 
 	/// Create a new mirror instance for a primitive type
@@ -218,7 +224,7 @@ impl MirrorInstance {
 	/// All primitive mirrors are available in [`crate::globals::mirrors`]. For example, [`primitive_int_mirror()`].
 	///
 	/// [`primitive_int_mirror()`]: crate::globals::mirrors::primitive_int_mirror
-	pub fn new_primitive(target: FieldType) -> MirrorInstanceRef {
+	pub fn new_primitive(target: FieldType) -> Throws<MirrorInstanceRef> {
 		assert!(
 			target.is_primitive(),
 			"`Array` and `Object` field types are incompatible with the primitive mirror"
@@ -232,11 +238,11 @@ impl MirrorInstance {
 
 		let mirror_class = crate::globals::classes::java_lang_Class();
 		let fields_size = mirror_class.size_of_instance_fields();
-		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size) };
+		let instance_ptr = unsafe { MirrorInstanceRef::allocate(descriptor, fields_size)? };
 
 		// TODO: Are these modifiers correct?
 		let ret = MirrorInstanceRef::new(instance_ptr, target_class, 1, true);
-		ret
+		Throws::Ok(ret)
 	}
 
 	pub fn is_primitive(&self) -> bool {
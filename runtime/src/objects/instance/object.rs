@@ -1,12 +1,11 @@
+use crate::gc::{heap, out_of_memory};
 use crate::objects::class::ClassPtr;
-use crate::objects::monitor::MonitorMap;
 use crate::thread::JavaThread;
 use crate::thread::exceptions::Throws;
 
 use std::alloc;
 use std::alloc::Layout;
 use std::sync::atomic::Ordering;
-use std::time::Duration;
 
 use common::atomic::{Atomic, AtomicCounterpart};
 use jni::sys::jint;
@@ -15,26 +14,49 @@ use jni::sys::jint;
 pub trait Object: Sized {
 	type Descriptor: Sized;
 
-	unsafe fn allocate(descriptor: Self::Descriptor, fields_size: usize) -> *mut Self::Descriptor {
+	/// Allocate an object with `fields_size` bytes of zeroed fields, and write `descriptor` into it
+	///
+	/// # Exceptions
+	///
+	/// This will throw `OutOfMemoryError` if the heap is exhausted.
+	///
+	/// # Safety
+	///
+	/// `fields_size` must be the size of the fields described by `descriptor`.
+	unsafe fn allocate(
+		descriptor: Self::Descriptor,
+		fields_size: usize,
+	) -> Throws<*mut Self::Descriptor> {
 		debug_assert!(
 			align_of::<Self::Descriptor>() == 8,
 			"Bad descriptor alignment"
 		);
 
 		let instance_size = size_of::<Self::Descriptor>() + fields_size;
-		let layout = Layout::array::<u8>(instance_size)
+		let Ok(layout) = Layout::array::<u8>(instance_size)
 			.and_then(|layout| layout.align_to(align_of::<Self::Descriptor>()))
-			.expect("valid layout");
+		else {
+			return Throws::Exception(out_of_memory::java_heap_space());
+		};
+
+		if !heap::reserve(instance_size) {
+			return Throws::Exception(out_of_memory::java_heap_space());
+		}
 
 		let instance_ptr;
 		unsafe {
 			// SAFETY: Every operand type has a specified default value of 0
 			let mem = alloc::alloc_zeroed(layout);
+			if mem.is_null() {
+				heap::release(instance_size);
+				return Throws::Exception(out_of_memory::java_heap_space());
+			}
+
 			instance_ptr = mem.cast::<Self::Descriptor>();
 			instance_ptr.write(descriptor);
 		}
 
-		instance_ptr
+		Throws::Ok(instance_ptr)
 	}
 
 	/// Fetch or generate a hash for this object
@@ -44,26 +66,6 @@ pub trait Object: Sized {
 	/// In the event that another thread is already generating a hash, this thread will spin until it finishes.
 	fn hash(&self, thread: &'static JavaThread) -> jint;
 
-	fn monitor_enter(&self, thread: &'static JavaThread) {
-		MonitorMap::find_or_add(self, thread).enter(thread);
-	}
-
-	fn monitor_exit(&self, thread: &'static JavaThread) {
-		MonitorMap::find_or_add(self, thread).exit(thread);
-	}
-
-	fn notify(&self, thread: &'static JavaThread) -> Throws<()> {
-		MonitorMap::find_or_add(self, thread).notify(thread)
-	}
-
-	fn notify_all(&self, thread: &'static JavaThread) -> Throws<()> {
-		MonitorMap::find_or_add(self, thread).notify_all(thread)
-	}
-
-	fn wait(&self, thread: &'static JavaThread, timeout: Option<Duration>) -> Throws<()> {
-		MonitorMap::find_or_add(self, thread).wait(thread, timeout)
-	}
-
 	/// The class backing the object
 	///
 	/// NOTE: For mirrors this will always be `java.lang.Class`, **NOT** the class that the mirror *targets*.
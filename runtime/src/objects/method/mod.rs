@@ -1,5 +1,7 @@
+pub mod reflection;
 pub mod spec;
 
+use crate::native::intrinsics::IntrinsicId;
 use crate::native::jni::reference_from_jobject;
 use crate::native::method::NativeMethodPtr;
 use crate::objects::class::ClassPtr;
@@ -27,7 +29,7 @@ use classfile::attribute::{Attribute, Code, LineNumber};
 use classfile::constant_pool::types::ConstantPoolEntryError;
 use classfile::{FieldType, MethodDescriptor, MethodInfo};
 use common::array::IntoJByte;
-use common::int_types::{s4, u1};
+use common::int_types::{s4, u1, u2};
 use instructions::Operand;
 use jni::sys::{jdouble, jint, jlong, jobject, jvalue};
 
@@ -35,6 +37,7 @@ use jni::sys::{jdouble, jint, jlong, jobject, jvalue};
 struct ExtraFlags {
 	caller_sensitive: bool,
 	intrinsic: bool,
+	scoped: bool,
 }
 
 impl ExtraFlags {
@@ -44,6 +47,7 @@ impl ExtraFlags {
 	) -> Throws<Self> {
 		const CALLER_SENSITIVE_TYPE: &str = "Ljdk/internal/reflect/CallerSensitive;";
 		const INTRINSIC_CANDIDATE_TYPE: &str = "Ljdk/internal/vm/annotation/IntrinsicCandidate;";
+		const SCOPED_TYPE: &str = "Ljdk/internal/misc/ScopedMemoryAccess$Scoped;";
 
 		let mut ret = Self::default();
 
@@ -55,6 +59,7 @@ impl ExtraFlags {
 			match &*annotation.name {
 				CALLER_SENSITIVE_TYPE => ret.caller_sensitive = true,
 				INTRINSIC_CANDIDATE_TYPE => ret.intrinsic = true,
+				SCOPED_TYPE => ret.scoped = true,
 				_ => {},
 			}
 		}
@@ -68,6 +73,7 @@ struct ExtraFields {
 	descriptor_sym: Symbol,
 	parameter_count: u1,
 	line_number_table: Vec<LineNumber>,
+	intrinsic_id: IntrinsicId,
 }
 
 impl PartialEq for ExtraFields {
@@ -168,11 +174,19 @@ impl Method {
 			.get_line_number_table_attribute()
 			.unwrap_or_default();
 
+		// Only methods of classes from the boot class path can be intrinsified
+		let intrinsic_id = if class.loader().is_bootstrap() {
+			IntrinsicId::for_method(class.name(), name, descriptor_sym, access_flags)
+		} else {
+			IntrinsicId::None
+		};
+
 		let extra_fields = ExtraFields {
 			parameter_stack_size,
 			descriptor_sym,
 			parameter_count,
 			line_number_table,
+			intrinsic_id,
 		};
 
 		let code = method_info.get_code_attribute().unwrap_or_default();
@@ -201,13 +215,14 @@ impl Method {
 			return -1;
 		}
 
-		for line_number in self.extra_fields.line_number_table.iter().copied() {
-			if (line_number.start_pc as isize) == pc {
-				return s4::from(line_number.line_number);
-			}
-		}
-
-		-1
+		// The line is the one with the closest start_pc at or before `pc`
+		self.extra_fields
+			.line_number_table
+			.iter()
+			.copied()
+			.filter(|line_number| (line_number.start_pc as isize) <= pc)
+			.max_by_key(|line_number| line_number.start_pc)
+			.map_or(-1, |line_number| s4::from(line_number.line_number))
 	}
 
 	// https://docs.oracle.com/javase/specs/jvms/se20/html/jvms-2.html#jvms-2.10
@@ -263,6 +278,16 @@ impl Method {
 		self.extra_fields.parameter_stack_size
 	}
 
+	/// The intrinsic this method maps to, or [`IntrinsicId::None`]
+	///
+	/// This doesn't mean that an intrinsic is actually registered for the method, see [`find_intrinsic()`].
+	///
+	/// [`find_intrinsic()`]: crate::native::intrinsics::find_intrinsic
+	#[inline]
+	pub fn intrinsic_id(&self) -> IntrinsicId {
+		self.extra_fields.intrinsic_id
+	}
+
 	pub fn set_entry_point(&self, entry_point: MethodEntryPoint) {
 		if matches!(entry_point, MethodEntryPoint::NativeMethod(_)) {
 			assert!(self.is_native(), "Method is not native");
@@ -330,6 +355,14 @@ impl Method {
 			})
 	}
 
+	/// The constant pool indices of the classes in this method's `Exceptions` attribute
+	pub fn exception_indices(&self) -> &[u2] {
+		self.attributes
+			.iter()
+			.find_map(|attr| attr.exceptions())
+			.map_or(&[], |exceptions| &exceptions.exception_index_table)
+	}
+
 	pub fn exception_types(&self) -> Throws<ObjectArrayInstanceRef> {
 		let Some(exceptions) = self.attributes.iter().find_map(|attr| attr.exceptions()) else {
 			return ObjectArrayInstance::new(0, globals::classes::java_lang_Class());
@@ -352,32 +385,52 @@ impl Method {
 		Throws::Ok(array)
 	}
 
-	pub fn annotations_array(&self) -> Option<PrimitiveArrayInstanceRef> {
-		self.attributes
+	pub fn annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
 			.iter()
 			.find_map(Attribute::runtime_visible_annotations)
-			.map(|attr| PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()))
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()).map(Some)
 	}
 
-	pub fn parameter_annotations_array(&self) -> Option<PrimitiveArrayInstanceRef> {
-		self.attributes
+	pub fn parameter_annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
 			.iter()
 			.find_map(Attribute::runtime_visible_parameter_annotations)
-			.map(|attr| PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()))
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()).map(Some)
 	}
 
-	pub fn type_annotations_array(&self) -> Option<PrimitiveArrayInstanceRef> {
-		self.attributes
+	pub fn type_annotations_array(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
 			.iter()
 			.find_map(Attribute::runtime_visible_type_annotations)
-			.map(|attr| PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()))
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()).map(Some)
 	}
 
-	pub fn annotation_default(&self) -> Option<PrimitiveArrayInstanceRef> {
-		self.attributes
+	pub fn annotation_default(&self) -> Throws<Option<PrimitiveArrayInstanceRef>> {
+		let Some(attr) = self
+			.attributes
 			.iter()
 			.find_map(Attribute::annotation_default)
-			.map(|attr| PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()))
+		else {
+			return Throws::Ok(None);
+		};
+
+		PrimitiveArrayInstance::new(&*attr.as_bytes().into_jbyte_array()).map(Some)
 	}
 }
 
@@ -432,6 +485,13 @@ impl Method {
 		self.extra_flags.caller_sensitive
 	}
 
+	/// Whether the method has the `@ScopedMemoryAccess.Scoped` annotation
+	///
+	/// These methods access memory belonging to a `MemorySessionImpl`, which they take as a parameter.
+	pub fn is_scoped(&self) -> bool {
+		self.extra_flags.scoped
+	}
+
 	pub fn is_stack_walk_ignored(&self) -> bool {
 		if self
 			.class
@@ -520,7 +580,8 @@ impl Method {
 	}
 }
 
-fn field_type_mirror(class: ClassPtr, ty: &FieldType) -> Throws<MirrorInstanceRef> {
+/// Get the mirror for `ty`, loading any classes through the loader of `class`
+pub(crate) fn field_type_mirror(class: ClassPtr, ty: &FieldType) -> Throws<MirrorInstanceRef> {
 	match ty {
 		FieldType::Byte
 		| FieldType::Character
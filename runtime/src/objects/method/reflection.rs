@@ -0,0 +1,216 @@
+//! Reflective method invocation
+//!
+//! This backs `java.lang.reflect.Method#invoke` and `java.lang.reflect.Constructor#newInstance`,
+//! through `JVM_InvokeMethod`/`JVM_NewInstanceFromConstructor` and the native accessors in
+//! `jdk.internal.reflect`.
+
+use crate::classpath::loader::ClassLoader;
+use crate::objects::boxing;
+use crate::objects::class::ClassPtr;
+use crate::objects::instance::array::{Array, ObjectArrayInstanceRef};
+use crate::objects::instance::class::{ClassInstance, ClassInstanceRef};
+use crate::objects::method::Method;
+use crate::objects::reference::Reference;
+use crate::symbols::sym;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::{Throws, throw};
+use crate::{classes, java_call};
+
+use classfile::FieldType;
+use classfile::accessflags::MethodAccessFlags;
+use instructions::Operand;
+
+/// Invoke the method represented by a `java.lang.reflect.Method`
+///
+/// For instance methods, the method is selected from the class of `receiver`, unless it is private.
+/// `receiver` is ignored for static methods.
+///
+/// The return value is boxed if necessary, and `void` methods return null.
+///
+/// # Exceptions
+///
+/// * `NullPointerException` if `receiver` is null for an instance method
+/// * `IllegalArgumentException` if `receiver` is of the wrong type, or `args` don't match the
+///   method's parameters
+/// * `InvocationTargetException` wrapping any exception thrown by the method itself
+pub fn invoke_method(
+	thread: &'static JavaThread,
+	method: ClassInstanceRef, // java.lang.reflect.Method
+	receiver: Reference,
+	args: Reference, // Object[]
+) -> Throws<Reference> {
+	let Some(target_method) = classes::java::lang::reflect::Method::vmtarget(method) else {
+		throw!(@DEFER InternalError, "invoke");
+	};
+
+	let declaring_class = target_method.class();
+	let parameter_types = classes::java::lang::reflect::Method::parameterTypes(method);
+
+	let mut selected_method = target_method;
+	if !target_method.is_static() {
+		if receiver.is_null() {
+			throw!(@DEFER NullPointerException);
+		}
+
+		if !receiver.is_instance_of(declaring_class) {
+			throw!(@DEFER IllegalArgumentException, "object is not an instance of declaring class");
+		}
+
+		selected_method = receiver
+			.extract_instance_class()
+			.select_method(target_method);
+		if selected_method.is_abstract() {
+			throw!(@DEFER AbstractMethodError, "{}", selected_method.external_name());
+		}
+	}
+
+	let ret = invoke(
+		thread,
+		declaring_class,
+		selected_method,
+		parameter_types,
+		args,
+		receiver,
+	)?;
+
+	match (ret, &target_method.descriptor.return_type) {
+		(None, _) | (_, FieldType::Void) => Throws::Ok(Reference::null()),
+		(Some(Operand::Reference(reference)), _) => Throws::Ok(reference),
+		(Some(value), return_type) => boxing::box_primitive(thread, value, return_type),
+	}
+}
+
+/// Create a new instance with the constructor represented by a `java.lang.reflect.Constructor`
+///
+/// # Exceptions
+///
+/// * `InstantiationException` if the declaring class is abstract
+/// * `IllegalArgumentException` if `args` don't match the constructor's parameters
+/// * `InvocationTargetException` wrapping any exception thrown by the constructor itself
+pub fn new_instance(
+	thread: &'static JavaThread,
+	constructor: ClassInstanceRef, // java.lang.reflect.Constructor
+	args: Reference,               // Object[]
+) -> Throws<Reference> {
+	let class = classes::java::lang::reflect::Constructor::clazz(constructor).target_class();
+	let slot = classes::java::lang::reflect::Constructor::slot(constructor);
+	let parameter_types = classes::java::lang::reflect::Constructor::parameterTypes(constructor);
+
+	let Some(method) = class.vtable().iter().nth(slot as usize) else {
+		throw!(@DEFER InternalError, "newInstance");
+	};
+
+	assert_eq!(method.name, sym!(object_initializer_name));
+
+	if class.is_abstract() || class.is_interface() {
+		throw!(@DEFER InstantiationException, "{}", class.external_name());
+	}
+
+	// The class needs to be initialized before the instance is allocated
+	class.initialize(thread)?;
+
+	let new_instance = Reference::class(ClassInstance::new(class)?);
+	invoke(thread, class, method, parameter_types, args, new_instance)?;
+
+	Throws::Ok(new_instance)
+}
+
+/// Call `method` with the (boxed) arguments in `args`
+///
+/// `receiver` is only used for instance methods, it's up to the caller to verify it.
+fn invoke(
+	thread: &'static JavaThread,
+	declaring_class: ClassPtr,
+	method: &'static Method,
+	parameter_types: ObjectArrayInstanceRef,
+	args: Reference,
+	receiver: Reference,
+) -> Throws<Option<Operand<Reference>>> {
+	// Static methods need their class initialized
+	declaring_class.initialize(thread)?;
+
+	// A null array is treated as no arguments
+	let args = (!args.is_null()).then(|| args.extract_object_array());
+	let args = args.as_ref().map_or(&[][..], |args| args.as_slice());
+
+	let expected_arg_count = method.descriptor.parameters.len();
+	if expected_arg_count != parameter_types.len() || args.len() != expected_arg_count {
+		throw!(@DEFER IllegalArgumentException,
+			"wrong number of arguments: {} expected: {expected_arg_count}",
+			args.len()
+		);
+	}
+
+	// Convert all the arguments before touching the stack, so nothing is left behind if one of them
+	// is invalid
+	let mut operands = Vec::with_capacity(args.len() + 1);
+	if !method.is_static() {
+		operands.push(Operand::Reference(receiver));
+	}
+
+	for (arg, parameter_mirror) in args.iter().zip(parameter_types.as_slice()) {
+		let parameter_mirror = parameter_mirror.extract_mirror();
+
+		if parameter_mirror.is_primitive() {
+			let Some(value) = boxing::unbox_and_widen(*arg, parameter_mirror.primitive_target())
+			else {
+				throw!(@DEFER IllegalArgumentException, "argument type mismatch");
+			};
+
+			operands.push(value);
+			continue;
+		}
+
+		if !arg.is_null() && !arg.is_instance_of(parameter_mirror.target_class()) {
+			throw!(@DEFER IllegalArgumentException, "argument type mismatch");
+		}
+
+		operands.push(Operand::Reference(*arg));
+	}
+
+	let stack = thread.stack();
+	for operand in operands {
+		stack.push_op(operand);
+	}
+
+	let ret = java_call!(thread, method);
+	if let Some(cause) = thread.take_pending_exception() {
+		wrap_in_invocation_target_exception(thread, cause)?;
+		return Throws::PENDING_EXCEPTION;
+	}
+
+	Throws::Ok(ret)
+}
+
+/// Set a new `java.lang.reflect.InvocationTargetException` with the given `cause` as pending
+fn wrap_in_invocation_target_exception(
+	thread: &'static JavaThread,
+	cause: Reference,
+) -> Throws<()> {
+	let class = ClassLoader::bootstrap().load(sym!(java_lang_reflect_InvocationTargetException))?;
+	class.initialize(thread)?;
+
+	let init_method = class
+		.vtable()
+		.find(
+			sym!(object_initializer_name),
+			sym!(Throwable_void_signature),
+			MethodAccessFlags::NONE,
+		)
+		.expect("method should exist");
+
+	let exception = Reference::class(ClassInstance::new(class)?);
+	java_call!(
+		thread,
+		init_method,
+		Operand::Reference(exception),
+		Operand::Reference(cause)
+	);
+
+	if thread.has_pending_exception() {
+		return Throws::PENDING_EXCEPTION;
+	}
+
+	thread.set_pending_exception(exception);
+	Throws::Ok(())
+}
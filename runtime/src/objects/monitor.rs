@@ -1,4 +1,6 @@
+use crate::classes::java::lang::Thread::ThreadStatus;
 use crate::objects::instance::object::Object;
+use crate::objects::reference::Reference;
 use crate::thread::JavaThread;
 use crate::thread::exceptions::{Throws, throw};
 
@@ -6,7 +8,7 @@ use jni::sys::jint;
 use std::cell::{Cell, SyncUnsafeCell, UnsafeCell};
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 use std::sync::{Condvar, LazyLock, Mutex, ReentrantLock, ReentrantLockGuard};
 use std::time::Duration;
 
@@ -25,12 +27,17 @@ static MONITOR_MAP: LazyLock<MonitorMap> = LazyLock::new(|| MonitorMap {
 });
 
 impl MonitorMap {
-	fn add(hash: jint) -> &'static Monitor {
+	fn add(hash: jint, obj: Reference) -> &'static Monitor {
 		let _guard = MONITOR_MAP.write_mutex.lock().unwrap();
 
-		let monitor = Box::leak(Box::new(Monitor::new()));
-
 		let list = unsafe { &mut *MONITOR_MAP.list.get() };
+		if let Some(existing) = list.get(&hash) {
+			// Another thread beat us to it
+			return existing;
+		}
+
+		let monitor = Box::leak(Box::new(Monitor::new(obj)));
+
 		list.insert(hash, monitor);
 
 		monitor
@@ -44,7 +51,7 @@ impl MonitorMap {
 	/// Find a monitor for the given object
 	///
 	/// See also: [`MonitorMap::find_or_add()`]
-	pub fn find<O: Object>(obj: &O, thread: &'static JavaThread) -> Option<&'static Monitor> {
+	pub fn find(obj: Reference, thread: &'static JavaThread) -> Option<&'static Monitor> {
 		let hash = obj.hash(thread);
 		Self::find_by_hash(hash)
 	}
@@ -52,14 +59,31 @@ impl MonitorMap {
 	/// Find a monitor for the given object or create one if it doesn't exist
 	///
 	/// See also: [`MonitorMap::find()`]
-	pub fn find_or_add<O: Object>(obj: &O, thread: &'static JavaThread) -> &'static Monitor {
+	pub fn find_or_add(obj: Reference, thread: &'static JavaThread) -> &'static Monitor {
 		let hash = obj.hash(thread);
 
 		match Self::find_by_hash(hash) {
 			Some(cl) => cl,
-			None => Self::add(hash),
+			None => Self::add(hash, obj),
 		}
 	}
+
+	/// Get all monitors currently owned by `thread`
+	///
+	/// The monitors are sorted by the depth at which they were entered, most recent first.
+	pub fn owned_by(thread: &'static JavaThread) -> Vec<&'static Monitor> {
+		let _guard = MONITOR_MAP.write_mutex.lock().unwrap();
+
+		let list = unsafe { &*MONITOR_MAP.list.get() };
+		let mut owned = list
+			.values()
+			.copied()
+			.filter(|monitor| monitor.owner() == Some(thread))
+			.collect::<Vec<_>>();
+
+		owned.sort_by_key(|monitor| std::cmp::Reverse(monitor.entry_depth()));
+		owned
+	}
 }
 
 type OwnerGuard<'a> = ReentrantLockGuard<'a, Cell<Option<&'static JavaThread>>>;
@@ -79,9 +103,14 @@ type OwnerGuard<'a> = ReentrantLockGuard<'a, Cell<Option<&'static JavaThread>>>;
 ///     * Yes - This thread blocks, waiting for the count to be zero
 ///     * No - This thread becomes the owner, and the count is 1
 pub struct Monitor {
+	object: Reference,
 	owner: ReentrantLock<Cell<Option<&'static JavaThread>>>,
 	// Always hold 1 live reference to the owner guard
 	_owner_guard: UnsafeCell<Option<OwnerGuard<'static>>>,
+	// The owner, readable without needing to take the lock
+	owner_thread: AtomicPtr<JavaThread>,
+	// The number of visible frames on the owner's stack when it first entered the monitor
+	entry_depth: AtomicUsize,
 	count: AtomicUsize,
 	_notify_lock: Mutex<()>,
 	_cond: Condvar,
@@ -94,50 +123,65 @@ unsafe impl Sync for Monitor {}
 impl Debug for Monitor {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		f.debug_struct("Monitor")
+			.field("object", &self.object)
 			.field("count", &self.count)
 			.finish_non_exhaustive()
 	}
 }
 
 impl Monitor {
-	/// Create a new `Monitor`
-	pub fn new() -> Monitor {
+	/// Create a new `Monitor` for `object`
+	pub fn new(object: Reference) -> Monitor {
 		Self {
+			object,
 			owner: ReentrantLock::new(Cell::new(None)),
 			_owner_guard: UnsafeCell::new(None),
+			owner_thread: AtomicPtr::new(std::ptr::null_mut()),
+			entry_depth: AtomicUsize::new(0),
 			count: AtomicUsize::new(0),
 			_notify_lock: Mutex::new(()),
 			_cond: Condvar::new(),
 		}
 	}
 
+	/// The object this monitor is associated with
+	pub fn object(&self) -> Reference {
+		self.object
+	}
+
 	/// Get the current owner of this monitor, if there is one
+	///
+	/// This will never block, and is only a snapshot. The owner can change at any time.
 	pub fn owner(&self) -> Option<&'static JavaThread> {
-		self.owner.lock().get()
+		let owner = self.owner_thread.load(Ordering::Acquire);
+		// SAFETY: `JavaThread`s are never deallocated
+		unsafe { owner.as_ref() }
+	}
+
+	/// The number of visible frames on the owner's stack when it entered this monitor
+	///
+	/// This is only meaningful while the monitor has an owner.
+	pub fn entry_depth(&self) -> usize {
+		self.entry_depth.load(Ordering::Acquire)
 	}
 
 	/// Enter a monitor
 	///
 	/// This will block if another thread owns this monitor.
 	pub fn enter(&self, thread: &'static JavaThread) {
-		let owner = self.owner.lock();
+		let owner = self.acquire(thread);
 
 		// sanity
 		assert!(owner.get() == Some(thread) || owner.get().is_none());
 
-		if owner.get().is_none() {
-			owner.set(Some(thread));
-			self.count.store(1, Ordering::SeqCst);
-			unsafe { self.set_guard(owner) };
-			return;
-		}
-
 		if owner.get() == Some(thread) {
 			self.count.fetch_add(1, Ordering::Acquire);
 			return;
 		}
 
+		owner.set(Some(thread));
 		self.count.store(1, Ordering::SeqCst);
+		self.set_owner_thread(Some(thread), thread.frame_stack().visible_depth());
 		unsafe { self.set_guard(owner) };
 	}
 
@@ -158,6 +202,8 @@ impl Monitor {
 		if count == 1 {
 			// We are the last holder
 			owner.set(None);
+			self.set_owner_thread(None, 0);
+			drop(owner);
 			unsafe { self.drop_guard() }
 		}
 	}
@@ -166,6 +212,7 @@ impl Monitor {
 	pub fn notify(&self, thread: &'static JavaThread) -> Throws<()> {
 		self.verify_current_thread_owner(thread)?;
 
+		let _guard = self._notify_lock.lock().unwrap();
 		self._cond.notify_one();
 		Throws::Ok(())
 	}
@@ -174,33 +221,79 @@ impl Monitor {
 	pub fn notify_all(&self, thread: &'static JavaThread) -> Throws<()> {
 		self.verify_current_thread_owner(thread)?;
 
+		let _guard = self._notify_lock.lock().unwrap();
 		self._cond.notify_all();
 		Throws::Ok(())
 	}
 
 	/// Wait `timeout` millis for the `Monitor` to become available
+	///
+	/// The monitor is released for the duration of the wait, and re-entered before returning.
 	pub fn wait(&self, thread: &'static JavaThread, timeout: Option<Duration>) -> Throws<()> {
 		self.verify_current_thread_owner(thread)?;
 
-		let count = self.count.swap(0, Ordering::Relaxed);
-		let owner = self.owner.lock().take().expect("should exist");
-
-		let guard = self._notify_lock.lock().unwrap();
-		match timeout {
-			Some(timeout) => {
-				drop(self._cond.wait_timeout(guard, timeout).unwrap());
-			},
-			None => {
-				drop(self._cond.wait(guard).unwrap());
-			},
-		}
+		{
+			let status = match timeout {
+				Some(_) => ThreadStatus::InObjectWaitTimed,
+				None => ThreadStatus::InObjectWait,
+			};
+			let blocker = MonitorBlocker::new(thread, self, BlockerKind::Waiting, status);
+
+			// Take the notify lock *before* releasing the monitor, so no notification can be missed
+			let notify_guard = self._notify_lock.lock().unwrap();
+
+			let count = self.count.swap(0, Ordering::Relaxed);
+			let entry_depth = self.entry_depth();
+			{
+				let owner = self.owner.lock();
+				owner.set(None);
+				self.set_owner_thread(None, 0);
+			}
+			// Release the lock held since the initial `enter()`
+			unsafe { self.drop_guard() }
 
-		self.count.store(count, Ordering::Relaxed);
-		self.owner.lock().replace(Some(owner));
+			match timeout {
+				Some(timeout) => {
+					drop(self._cond.wait_timeout(notify_guard, timeout).unwrap());
+				},
+				None => {
+					drop(self._cond.wait(notify_guard).unwrap());
+				},
+			}
+
+			drop(blocker);
+
+			let owner = self.acquire(thread);
+			owner.set(Some(thread));
+			self.count.store(count, Ordering::Relaxed);
+			self.set_owner_thread(Some(thread), entry_depth);
+			unsafe { self.set_guard(owner) };
+		}
 
 		Throws::Ok(())
 	}
 
+	/// Take the owner lock, marking `thread` as blocked if it has to wait for another owner
+	fn acquire(&self, thread: &'static JavaThread) -> OwnerGuard<'_> {
+		if let Some(owner) = self.owner.try_lock() {
+			return owner;
+		}
+
+		let _blocker = MonitorBlocker::new(
+			thread,
+			self,
+			BlockerKind::Contended,
+			ThreadStatus::BlockedOnMonitorEnter,
+		);
+		self.owner.lock()
+	}
+
+	fn set_owner_thread(&self, thread: Option<&'static JavaThread>, entry_depth: usize) {
+		let ptr = thread.map_or(std::ptr::null_mut(), |t| std::ptr::from_ref(t).cast_mut());
+		self.entry_depth.store(entry_depth, Ordering::Release);
+		self.owner_thread.store(ptr, Ordering::Release);
+	}
+
 	fn verify_current_thread_owner(&self, thread: &'static JavaThread) -> Throws<()> {
 		let current_thread_is_owner = self
 			.owner
@@ -234,3 +327,60 @@ impl Monitor {
 		let _ = unsafe { &mut *self._owner_guard.get() }.take();
 	}
 }
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum BlockerKind {
+	/// Blocked trying to enter a monitor owned by another thread
+	Contended,
+	/// In `Object#wait`
+	Waiting,
+}
+
+/// Marks a thread as blocked on a [`Monitor`] for the lifetime of the guard
+///
+/// While blocked, the thread is in a safe region, and its `java.lang.Thread` status reflects the
+/// reason it is blocked.
+struct MonitorBlocker {
+	thread: &'static JavaThread,
+	kind: BlockerKind,
+	previous_status: Option<ThreadStatus>,
+	_safe_region: crate::thread::SafeRegionGuard,
+}
+
+impl MonitorBlocker {
+	fn new(
+		thread: &'static JavaThread,
+		monitor: &Monitor,
+		kind: BlockerKind,
+		status: ThreadStatus,
+	) -> Self {
+		let monitor = std::ptr::from_ref(monitor).cast_mut();
+		match kind {
+			BlockerKind::Contended => thread.set_contended_monitor(monitor),
+			BlockerKind::Waiting => thread.set_waiting_monitor(monitor),
+		}
+
+		let previous_status = thread.thread_status();
+		thread.set_thread_status(status);
+
+		Self {
+			thread,
+			kind,
+			previous_status,
+			_safe_region: thread.enter_safe_region(),
+		}
+	}
+}
+
+impl Drop for MonitorBlocker {
+	fn drop(&mut self) {
+		match self.kind {
+			BlockerKind::Contended => self.thread.set_contended_monitor(std::ptr::null_mut()),
+			BlockerKind::Waiting => self.thread.set_waiting_monitor(std::ptr::null_mut()),
+		}
+
+		if let Some(previous_status) = self.previous_status {
+			self.thread.set_thread_status(previous_status);
+		}
+	}
+}
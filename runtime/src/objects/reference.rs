@@ -7,10 +7,12 @@ use crate::objects::instance::array::{Array, ObjectArrayInstanceRef, PrimitiveAr
 use crate::objects::instance::class::ClassInstanceRef;
 use crate::objects::instance::mirror::MirrorInstanceRef;
 use crate::objects::instance::object::Object;
+use crate::objects::monitor::MonitorMap;
 use crate::thread::JavaThread;
 use crate::thread::exceptions::{Throws, throw};
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 use ::jni::sys::jint;
 use instructions::Operand;
@@ -367,6 +369,29 @@ impl Reference {
 	}
 }
 
+// Monitor operations
+impl Reference {
+	pub fn monitor_enter(self, thread: &'static JavaThread) {
+		MonitorMap::find_or_add(self, thread).enter(thread);
+	}
+
+	pub fn monitor_exit(self, thread: &'static JavaThread) {
+		MonitorMap::find_or_add(self, thread).exit(thread);
+	}
+
+	pub fn notify(self, thread: &'static JavaThread) -> Throws<()> {
+		MonitorMap::find_or_add(self, thread).notify(thread)
+	}
+
+	pub fn notify_all(self, thread: &'static JavaThread) -> Throws<()> {
+		MonitorMap::find_or_add(self, thread).notify_all(thread)
+	}
+
+	pub fn wait(self, thread: &'static JavaThread, timeout: Option<Duration>) -> Throws<()> {
+		MonitorMap::find_or_add(self, thread).wait(thread, timeout)
+	}
+}
+
 impl Instance for Reference {
 	fn get_field_value(&self, field: &Field) -> Operand<Reference> {
 		match self.tag() {
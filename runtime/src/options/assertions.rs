@@ -0,0 +1,127 @@
+//! The `-ea`/`-da` and `-esa`/`-dsa` options
+
+/// A single `-ea:<name>` or `-da:<name>` option
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssertionDirective {
+	/// The binary name of the class or package, e.g. `java.lang`
+	///
+	/// An empty name refers to the unnamed package (`-ea:...`).
+	pub name: String,
+	pub is_package: bool,
+	pub enabled: bool,
+}
+
+/// The assertion status requested on the command line
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssertionOptions {
+	/// Whether assertions are enabled for classes not covered by a directive (`-ea`/`-da`)
+	pub user_default: bool,
+	/// Whether assertions are enabled for system classes not covered by a directive (`-esa`/`-dsa`)
+	pub system_default: bool,
+	/// The class and package directives, in the order they were given
+	pub directives: Vec<AssertionDirective>,
+}
+
+impl AssertionOptions {
+	/// Apply an assertion option, returning `false` if `option` isn't one
+	pub(super) fn apply_option(&mut self, option: &str) -> bool {
+		match option {
+			"-esa" | "-enablesystemassertions" => self.system_default = true,
+			"-dsa" | "-disablesystemassertions" => self.system_default = false,
+			_ => {
+				let Some((enabled, tail)) = ["-ea", "-enableassertions"]
+					.into_iter()
+					.find_map(|prefix| option.strip_prefix(prefix))
+					.map(|tail| (true, tail))
+					.or_else(|| {
+						["-da", "-disableassertions"]
+							.into_iter()
+							.find_map(|prefix| option.strip_prefix(prefix))
+							.map(|tail| (false, tail))
+					})
+				else {
+					return false;
+				};
+
+				if tail.is_empty() {
+					self.user_default = enabled;
+					return true;
+				}
+
+				let Some(name) = tail.strip_prefix(':') else {
+					return false;
+				};
+
+				let (name, is_package) = match name.strip_suffix("...") {
+					Some(package) => (package, true),
+					None => (name, false),
+				};
+
+				self.directives.push(AssertionDirective {
+					name: name.to_string(),
+					is_package,
+					enabled,
+				});
+			},
+		}
+
+		true
+	}
+
+	/// Whether assertions should be enabled for the class named `class_name`
+	///
+	/// `class_name` is in its binary form, e.g. `java.lang.Object`. The most recent directive for
+	/// the class itself wins, followed by the directives for its package, then any enclosing packages.
+	/// Otherwise, this falls back to the default for the kind of class.
+	pub fn desired_status(&self, class_name: &str, is_system_class: bool) -> bool {
+		let latest = |name: &str, is_package: bool| {
+			self.directives
+				.iter()
+				.rev()
+				.find(|directive| directive.is_package == is_package && directive.name == name)
+				.map(|directive| directive.enabled)
+		};
+
+		if let Some(enabled) = latest(class_name, false) {
+			return enabled;
+		}
+
+		let mut package = class_name;
+		loop {
+			package = match package.rfind('.') {
+				Some(end) => &package[..end],
+				// Only classes in the unnamed package are covered by `-ea:...`
+				None if package == class_name => "",
+				None => break,
+			};
+
+			if let Some(enabled) = latest(package, true) {
+				return enabled;
+			}
+
+			if package.is_empty() {
+				break;
+			}
+		}
+
+		if is_system_class {
+			self.system_default
+		} else {
+			self.user_default
+		}
+	}
+
+	/// The class directives, with the latest directive for each class last
+	pub fn classes(&self) -> impl Iterator<Item = &AssertionDirective> {
+		self.directives
+			.iter()
+			.filter(|directive| !directive.is_package)
+	}
+
+	/// The package directives, with the latest directive for each package last
+	pub fn packages(&self) -> impl Iterator<Item = &AssertionDirective> {
+		self.directives
+			.iter()
+			.filter(|directive| directive.is_package)
+	}
+}
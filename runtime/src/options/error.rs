@@ -5,6 +5,10 @@ use std::str::Utf8Error;
 
 pub enum OptionsError {
 	UnrecognizedOption(String),
+	/// An option was recognized, but its value was malformed
+	InvalidValue(String),
+	/// Two or more options conflict with each other
+	Incompatible(&'static str),
 	BadCstr(Utf8Error),
 	/// Failed to parse a `-Xlog` option
 	Logging(LogParseError),
@@ -14,6 +18,8 @@ impl Display for OptionsError {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		match self {
 			Self::UnrecognizedOption(opt) => write!(f, "Unrecognized VM init option: {opt}"),
+			Self::InvalidValue(opt) => write!(f, "Improperly specified VM option '{opt}'"),
+			Self::Incompatible(message) => f.write_str(message),
 			Self::BadCstr(err) => write!(f, "Encountered a bad C string: {err}"),
 			Self::Logging(err) => err.fmt(f),
 		}
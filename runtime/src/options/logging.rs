@@ -4,7 +4,6 @@ use crate::logging::{LogDecorator, LogDecoratorContext, LogDecoratorSet, LogLeve
 
 use std::fmt::Display;
 use std::str::FromStr;
-use std::sync::OnceLock;
 
 /// A single `tag[=level]` selection
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -395,18 +394,12 @@ impl From<LogOption> for LogOptions {
 	}
 }
 
-static OPTIONS: OnceLock<LogOptions> = OnceLock::new();
-
 impl LogOptions {
-	/// Apply the log options globally.
-	pub(crate) fn apply(self) {
-		OPTIONS
-			.set(self)
-			.expect("log options should not be initialized yet");
-	}
-
+	/// Get the global log options, see [`JvmOptions::get()`]
+	///
+	/// [`JvmOptions::get()`]: super::JvmOptions::get
 	pub fn get() -> &'static Self {
-		OPTIONS.get().expect("log options should be initialized")
+		&super::JvmOptions::get().logs
 	}
 
 	/// Whether all of the given tags in the set are enabled at the given [`LogLevel`] in at least one output.
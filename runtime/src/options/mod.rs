@@ -1,3 +1,4 @@
+pub mod assertions;
 mod error;
 pub mod logging;
 #[cfg(test)]
@@ -5,33 +6,61 @@ mod tests;
 
 use crate::classpath::{ClassPathEntry, add_classpath_entry};
 use crate::native::jdk::internal::util::SystemProps::Raw::SYSTEM_PROPERTIES;
+use crate::options::assertions::AssertionOptions;
 use crate::options::error::OptionsError;
 use crate::options::logging::{LogOptions, LogOptionsBuilder};
+use crate::thread::hash::HashCodeMode;
 
 use jni::java_vm::{AbortHookFn, ExitHookFn, VFPrintFHookFn};
 use jni::sys::JavaVMInitArgs;
 use std::ffi::{CStr, c_char, c_int, c_void};
 use std::mem;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 unsafe extern "C" fn vfprintf_default(_stream: *mut c_void, _format: *const c_char, _: ...) {
 	todo!("vfprintf")
 }
 
-extern "C" fn exit_default(_status: c_int) {
-	todo!("exit_default")
+extern "C" fn exit_default(status: c_int) {
+	std::process::exit(status)
 }
 
 extern "C" fn abort_default() {
-	todo!("abort_default")
+	std::process::abort()
 }
 
+/// The `vfprintf`, `exit`, and `abort` hooks provided through [`JavaVMInitArgs`]
 pub struct Hooks {
 	vfprintf: VFPrintFHookFn,
 	exit: ExitHookFn,
 	abort: AbortHookFn,
 }
 
+impl Hooks {
+	/// Get the global hooks, see [`JvmOptions::get()`]
+	pub fn get() -> &'static Self {
+		&JvmOptions::get().hooks
+	}
+
+	/// Call the `exit` hook, terminating the process with `status`
+	pub fn exit(&self, status: c_int) -> ! {
+		(self.exit)(status);
+
+		// The hook isn't supposed to return
+		std::process::exit(status)
+	}
+
+	/// Call the `abort` hook, abnormally terminating the process
+	pub fn abort(&self) -> ! {
+		(self.abort)();
+
+		// The hook isn't supposed to return
+		std::process::abort()
+	}
+}
+
 impl Default for Hooks {
 	fn default() -> Self {
 		Self {
@@ -51,10 +80,87 @@ pub enum Verbosity {
 	Jni,
 }
 
+/// Whether the shared class archive should be used (`-Xshare`)
+///
+/// `-Xshare:dump` isn't supported, archives can only be created with `-XX:ArchiveClassesAtExit`.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum ShareMode {
+	/// Never use an archive (`-Xshare:off`)
+	Off,
+	/// Use the archive if it's valid, otherwise continue without it (`-Xshare:auto`)
+	#[default]
+	Auto,
+	/// Fail to start if the archive can't be used (`-Xshare:on`)
+	On,
+}
+
+impl FromStr for ShareMode {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"off" => Ok(Self::Off),
+			"auto" => Ok(Self::Auto),
+			"on" => Ok(Self::On),
+			_ => Err(()),
+		}
+	}
+}
+
 pub struct JvmOptions {
-	hooks: Hooks,
+	pub hooks: Hooks,
 	verbosity: Option<Verbosity>,
 	pub logs: LogOptions,
+	/// The default stack size for new threads, in bytes (`-Xss`/`-XX:ThreadStackSize`)
+	pub thread_stack_size: usize,
+	/// The identity hash code algorithm (`-XX:hashCode`)
+	pub hash_code: HashCodeMode,
+	/// The maximum number of bytes that can be reserved for direct buffers, enforced by
+	/// `java.nio.Bits` (`-XX:MaxDirectMemorySize`)
+	pub max_direct_memory_size: Option<usize>,
+	/// Overrides the number of CPUs reported to Java code (`-XX:ActiveProcessorCount`)
+	pub active_processor_count: Option<usize>,
+	/// Whether cgroup limits are taken into account (`-XX:[+-]UseContainerSupport`)
+	pub use_container_support: bool,
+	/// The maximum heap size, in bytes (`-Xmx`/`-XX:MaxHeapSize`)
+	pub max_heap_size: Option<usize>,
+	/// The initial heap size, in bytes (`-Xms`/`-XX:InitialHeapSize`)
+	pub initial_heap_size: Option<usize>,
+	/// The size of the young generation, in bytes (`-Xmn`/`-XX:NewSize`)
+	///
+	/// There is no generational collector, so this is only validated against the heap size.
+	pub new_size: Option<usize>,
+	/// Whether to write a heap dump on the first `OutOfMemoryError`
+	/// (`-XX:[+-]HeapDumpOnOutOfMemoryError`)
+	pub heap_dump_on_out_of_memory_error: bool,
+	/// The file or directory to write heap dumps to (`-XX:HeapDumpPath`)
+	pub heap_dump_path: Option<String>,
+	/// Commands to run on the first `OutOfMemoryError`, separated by `;` (`-XX:OnOutOfMemoryError`)
+	pub on_out_of_memory_error: Option<String>,
+	/// Whether the VM should avoid using OS signals (`-Xrs`/`-XX:[+-]ReduceSignalUsage`)
+	///
+	/// When set, no thread dump is printed on `SIGQUIT`, and the shutdown signals (`SIGHUP`,
+	/// `SIGINT`, and `SIGTERM`) can't be handled from Java.
+	pub reduce_signal_usage: bool,
+	/// Which classes should have assertions enabled (`-ea`/`-da`/`-esa`/`-dsa`)
+	pub assertions: AssertionOptions,
+	/// The options the VM was created with, as reported by `RuntimeMXBean#getInputArguments`
+	///
+	/// Same as Hotspot, this excludes the hooks and the properties set by the launcher itself.
+	pub vm_arguments: Vec<String>,
+	/// The names of the intrinsics that should never be used (`-XX:DisableIntrinsic`)
+	///
+	/// This can be given multiple times, each with a comma separated list of names.
+	pub disabled_intrinsics: Vec<String>,
+	/// Whether the shared class archive should be used (`-Xshare`)
+	pub share: ShareMode,
+	/// The shared class archive to use at startup (`-XX:SharedArchiveFile`)
+	pub shared_archive_file: Option<PathBuf>,
+	/// Where to write a shared class archive of the loaded classes on exit
+	/// (`-XX:ArchiveClassesAtExit`)
+	pub archive_classes_at_exit: Option<PathBuf>,
+	/// Where to write the list of the loaded classes on exit (`-XX:DumpLoadedClassList`)
+	pub dump_loaded_class_list: Option<PathBuf>,
 }
 
 impl Default for JvmOptions {
@@ -63,11 +169,69 @@ impl Default for JvmOptions {
 			hooks: Hooks::default(),
 			verbosity: None,
 			logs: LogOptionsBuilder::default().build(),
+			thread_stack_size: DEFAULT_THREAD_STACK_SIZE,
+			hash_code: HashCodeMode::default(),
+			max_direct_memory_size: None,
+			active_processor_count: None,
+			use_container_support: cfg!(target_os = "linux"),
+			max_heap_size: None,
+			initial_heap_size: None,
+			new_size: None,
+			heap_dump_on_out_of_memory_error: false,
+			heap_dump_path: None,
+			on_out_of_memory_error: None,
+			reduce_signal_usage: false,
+			assertions: AssertionOptions::default(),
+			vm_arguments: Vec::new(),
+			disabled_intrinsics: Vec::new(),
+			share: ShareMode::default(),
+			shared_archive_file: None,
+			archive_classes_at_exit: None,
+			dump_loaded_class_list: None,
 		}
 	}
 }
 
+/// Whether `option` should be included in [`JvmOptions::vm_arguments`]
+fn is_reported_argument(option: &str) -> bool {
+	const LAUNCHER_PROPERTIES: &[&str] = &[
+		"-Djava.class.path=",
+		"-Dsun.java.command=",
+		"-Dsun.java.launcher",
+	];
+
+	!matches!(option, "vfprintf" | "exit" | "abort")
+		&& !LAUNCHER_PROPERTIES
+			.iter()
+			.any(|prefix| option.starts_with(prefix))
+}
+
+/// The default thread stack size, same as Hotspot on 64-bit platforms
+pub const DEFAULT_THREAD_STACK_SIZE: usize = 1024 * 1024;
+
+/// The smallest accepted maximum heap size
+pub const MIN_HEAP_SIZE: usize = 2 * 1024 * 1024;
+
+/// The smallest accepted thread stack size
+///
+/// Smaller sizes given through `-Xss` are rejected, and smaller sizes given to `java.lang.Thread`
+/// are rounded up.
+pub const MIN_THREAD_STACK_SIZE: usize = 136 * 1024;
+
+static OPTIONS: OnceLock<JvmOptions> = OnceLock::new();
+
 impl JvmOptions {
+	/// Apply the options globally.
+	pub(crate) fn apply(self) {
+		let result = OPTIONS.set(self);
+		assert!(result.is_ok(), "options should not be initialized yet");
+	}
+
+	/// Get the global options, or the defaults if none were applied
+	pub fn get() -> &'static Self {
+		OPTIONS.get_or_init(Self::default)
+	}
+
 	/// Parse the `JvmOptions` from the user-provided [`JavaVMInitArgs`]
 	///
 	/// # Safety
@@ -77,8 +241,7 @@ impl JvmOptions {
 	/// * All provided C strings are valid
 	/// * All provided function pointers are valid
 	pub unsafe fn load(init: &JavaVMInitArgs) -> Result<Self, OptionsError> {
-		let mut hooks = Hooks::default();
-		let mut verbosity = None;
+		let mut options = Self::default();
 		let mut logs = LogOptionsBuilder::default();
 
 		let mut system_props_guard = SYSTEM_PROPERTIES.lock().unwrap();
@@ -87,12 +250,50 @@ impl JvmOptions {
 			let option_string_c = unsafe { CStr::from_ptr(option.optionString) };
 			let option_string = option_string_c.to_str()?;
 
+			if is_reported_argument(option_string) {
+				options.vm_arguments.push(option_string.to_string());
+			}
+
 			// Special case for the crazy log syntax
 			if let Some(log_options) = option_string.strip_prefix("-Xlog") {
 				logs.apply_option(logging::LogOption::from_str(log_options)?);
 				continue;
 			}
 
+			if let Some(size) = option_string.strip_prefix("-Xss") {
+				options.thread_stack_size = parse_memory_size(size)
+					.filter(|size| *size >= MIN_THREAD_STACK_SIZE)
+					.ok_or_else(|| OptionsError::InvalidValue(option_string.to_string()))?;
+				continue;
+			}
+
+			// `-Xmx`, `-Xms`, and `-Xmn` are shorthands for `-XX` flags
+			let heap_flag = [
+				("-Xmx", "MaxHeapSize"),
+				("-Xms", "InitialHeapSize"),
+				("-Xmn", "NewSize"),
+			]
+			.into_iter()
+			.find_map(|(prefix, name)| Some((option_string.strip_prefix(prefix)?, name)));
+			if let Some((size, name)) = heap_flag {
+				options
+					.apply_heap_size(name, size)
+					.map_err(|_| OptionsError::InvalidValue(option_string.to_string()))?;
+				continue;
+			}
+
+			if let Some(flag) = option_string.strip_prefix("-XX:") {
+				match options.apply_xx_flag(flag) {
+					Err(OptionsError::UnrecognizedOption(_)) if init.ignoreUnrecognized => {},
+					result => result?,
+				}
+				continue;
+			}
+
+			if options.assertions.apply_option(option_string) {
+				continue;
+			}
+
 			let mut opt_split = option_string.splitn(2, '=');
 
 			let key = opt_split.next().unwrap();
@@ -100,19 +301,32 @@ impl JvmOptions {
 			// Special cases, no value
 			match key {
 				"vfprintf" => {
-					hooks.vfprintf =
-						unsafe { mem::transmute::<*mut c_void, VFPrintFHookFn>(option.extraInfo) }
+					options.hooks.vfprintf =
+						unsafe { mem::transmute::<*mut c_void, VFPrintFHookFn>(option.extraInfo) };
+					continue;
 				},
 				"exit" => {
-					hooks.exit =
-						unsafe { mem::transmute::<*mut c_void, ExitHookFn>(option.extraInfo) }
+					options.hooks.exit =
+						unsafe { mem::transmute::<*mut c_void, ExitHookFn>(option.extraInfo) };
+					continue;
 				},
 				"abort" => {
-					hooks.abort =
-						unsafe { mem::transmute::<*mut c_void, AbortHookFn>(option.extraInfo) }
+					options.hooks.abort =
+						unsafe { mem::transmute::<*mut c_void, AbortHookFn>(option.extraInfo) };
+					continue;
+				},
+				"-Xrs" => {
+					options.reduce_signal_usage = true;
+					continue;
+				},
+				_ if let Some(mode) = key.strip_prefix("-Xshare:") => {
+					options.share = mode
+						.parse()
+						.map_err(|()| OptionsError::InvalidValue(option_string.to_string()))?;
+					continue;
 				},
 				_ if let Some(verbosity_str) = key.strip_prefix("-verbose") => {
-					verbosity = Some(match verbosity_str.split_once(':') {
+					options.verbosity = Some(match verbosity_str.split_once(':') {
 						Some((_, target)) => match target {
 							"class" => Verbosity::Class,
 							"module" => Verbosity::Module,
@@ -122,11 +336,12 @@ impl JvmOptions {
 						},
 						None => Verbosity::default(),
 					});
+					continue;
 				},
 				_ => {},
 			}
 
-			let val = opt_split.next().unwrap();
+			let val = opt_split.next().unwrap_or_default();
 
 			match key {
 				"-Djava.class.path" => {
@@ -149,12 +364,166 @@ impl JvmOptions {
 			}
 		}
 
+		options.check_heap_sizes()?;
+
+		// Read by `jdk.internal.misc.VM` to size the direct buffer pool
+		if let Some(size) = options.max_direct_memory_size {
+			system_props_guard.insert(
+				String::from("sun.nio.MaxDirectMemorySize"),
+				size.to_string(),
+			);
+		}
+
 		system_props_guard.insert(String::from("java.vm.info"), vm_info_str());
-		Ok(Self {
-			hooks,
-			verbosity,
-			logs: logs.build(),
-		})
+		options.logs = logs.build();
+		Ok(options)
+	}
+
+	/// Apply a `-XX:` flag, in the form of `+Flag`, `-Flag`, or `Flag=value`
+	fn apply_xx_flag(&mut self, flag: &str) -> Result<(), OptionsError> {
+		let invalid = || OptionsError::InvalidValue(flag.to_string());
+
+		// `+Flag` and `-Flag` are shorthands for `Flag=true` and `Flag=false`
+		let (name, value) = match flag.split_once('=') {
+			Some((name, value)) => (name, value),
+			None if let Some(name) = flag.strip_prefix('+') => (name, "true"),
+			None if let Some(name) = flag.strip_prefix('-') => (name, "false"),
+			None => return Err(invalid()),
+		};
+
+		match name {
+			"ThreadStackSize" => {
+				self.thread_stack_size = match parse_thread_stack_size(value) {
+					// 0 means the platform default
+					Some(0) => DEFAULT_THREAD_STACK_SIZE,
+					Some(size) if size >= MIN_THREAD_STACK_SIZE => size,
+					_ => return Err(invalid()),
+				};
+			},
+			"hashCode" => {
+				self.hash_code = value
+					.parse::<u8>()
+					.ok()
+					.and_then(HashCodeMode::from_value)
+					.ok_or_else(invalid)?;
+			},
+			"MaxDirectMemorySize" => {
+				self.max_direct_memory_size = Some(parse_memory_size(value).ok_or_else(invalid)?);
+			},
+			"ActiveProcessorCount" => {
+				// -1 (the default) and 0 mean that the count should be detected
+				let count = value.parse::<i32>().ok().filter(|count| *count >= -1);
+				self.active_processor_count = match count {
+					Some(count) if count > 0 => Some(count as usize),
+					Some(_) => None,
+					None => return Err(invalid()),
+				};
+			},
+			"MaxHeapSize" | "InitialHeapSize" | "NewSize" => self.apply_heap_size(name, value)?,
+			"HeapDumpOnOutOfMemoryError" => {
+				self.heap_dump_on_out_of_memory_error = parse_bool(value).ok_or_else(invalid)?;
+			},
+			"HeapDumpPath" => self.heap_dump_path = Some(value.to_string()),
+			"OnOutOfMemoryError" => self.on_out_of_memory_error = Some(value.to_string()),
+			"ReduceSignalUsage" => {
+				self.reduce_signal_usage = parse_bool(value).ok_or_else(invalid)?;
+			},
+			"DisableIntrinsic" => self.disabled_intrinsics.extend(
+				value
+					.split(',')
+					.filter(|name| !name.is_empty())
+					.map(str::to_string),
+			),
+			"SharedArchiveFile" => self.shared_archive_file = Some(PathBuf::from(value)),
+			"ArchiveClassesAtExit" => self.archive_classes_at_exit = Some(PathBuf::from(value)),
+			"DumpLoadedClassList" => self.dump_loaded_class_list = Some(PathBuf::from(value)),
+			"UseContainerSupport" => {
+				// Containers are only detected on Linux, so it can't be enabled elsewhere
+				self.use_container_support =
+					parse_bool(value).ok_or_else(invalid)? && cfg!(target_os = "linux");
+			},
+			_ => return Err(OptionsError::UnrecognizedOption(format!("-XX:{flag}"))),
+		}
+
+		Ok(())
+	}
+
+	/// Set one of the heap sizes, as given through `-XX:{name}` or its `-X` shorthand
+	fn apply_heap_size(&mut self, name: &str, value: &str) -> Result<(), OptionsError> {
+		let invalid = || OptionsError::InvalidValue(format!("{name}={value}"));
+		let size = parse_memory_size(value).ok_or_else(invalid)?;
+
+		match name {
+			"MaxHeapSize" if size < MIN_HEAP_SIZE => return Err(invalid()),
+			"MaxHeapSize" => self.max_heap_size = Some(size),
+			"InitialHeapSize" => self.initial_heap_size = Some(size),
+			"NewSize" => self.new_size = Some(size),
+			_ => unreachable!("not a heap size flag: {name}"),
+		}
+
+		Ok(())
+	}
+
+	/// Verify that the heap sizes are consistent with each other
+	fn check_heap_sizes(&self) -> Result<(), OptionsError> {
+		let Some(max_heap_size) = self.max_heap_size else {
+			return Ok(());
+		};
+
+		if self
+			.initial_heap_size
+			.is_some_and(|size| size > max_heap_size)
+		{
+			return Err(OptionsError::Incompatible(
+				"Initial heap size set to a larger value than the maximum heap size",
+			));
+		}
+
+		if self.new_size.is_some_and(|size| size >= max_heap_size) {
+			return Err(OptionsError::Incompatible(
+				"Young generation size set to a larger value than the maximum heap size",
+			));
+		}
+
+		Ok(())
+	}
+}
+
+/// Parse the value of a boolean `-XX:` flag
+fn parse_bool(value: &str) -> Option<bool> {
+	match value {
+		"true" => Some(true),
+		"false" => Some(false),
+		_ => None,
+	}
+}
+
+/// Parse a memory size, such as `-Xss`, with an optional unit (`k`, `m`, `g`, or `t`)
+///
+/// Sizes without a unit are in bytes.
+pub fn parse_memory_size(size: &str) -> Option<usize> {
+	let (digits, multiplier) = match size.as_bytes().last()? {
+		b'k' | b'K' => (&size[..size.len() - 1], 1 << 10),
+		b'm' | b'M' => (&size[..size.len() - 1], 1 << 20),
+		b'g' | b'G' => (&size[..size.len() - 1], 1 << 30),
+		b't' | b'T' => (&size[..size.len() - 1], 1 << 40),
+		_ => (size, 1),
+	};
+
+	if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+		return None;
+	}
+
+	digits.parse::<usize>().ok()?.checked_mul(multiplier)
+}
+
+/// Parse the value of `-XX:ThreadStackSize`
+///
+/// Unlike `-Xss`, this is in KiB if no unit is specified. A size of 0 means the platform default.
+pub fn parse_thread_stack_size(size: &str) -> Option<usize> {
+	match size.parse::<usize>() {
+		Ok(kib) => kib.checked_mul(1024),
+		Err(_) => parse_memory_size(size),
 	}
 }
 
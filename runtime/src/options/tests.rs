@@ -1,9 +1,15 @@
 use crate::logging::{LogLevel, Tag};
+use crate::options::assertions::{AssertionDirective, AssertionOptions};
 use crate::options::logging::{
 	LogOption, LogOptions, LogOptionsBuilder, LogOutputName, LogOutputOptions, Selection,
 	Selections,
 };
+use crate::options::{
+	JvmOptions, MIN_HEAP_SIZE, ShareMode, is_reported_argument, parse_memory_size,
+};
+use crate::thread::hash::HashCodeMode;
 
+use std::path::Path;
 use std::str::FromStr;
 
 #[test]
@@ -49,3 +55,336 @@ fn jvm_log_options() {
 		assert_eq!(builder.build(), expected);
 	}
 }
+
+#[test]
+fn memory_sizes() {
+	let expectations = [
+		("1024", Some(1024)),
+		("512k", Some(512 * 1024)),
+		("512K", Some(512 * 1024)),
+		("2m", Some(2 * 1024 * 1024)),
+		("1g", Some(1024 * 1024 * 1024)),
+		("1t", Some(1024 * 1024 * 1024 * 1024)),
+		("", None),
+		("k", None),
+		("-1k", None),
+		("1.5m", None),
+		("1x", None),
+		("99999999999999999999t", None),
+	];
+
+	for (size, expected) in expectations {
+		assert_eq!(parse_memory_size(size), expected, "{size}");
+	}
+}
+
+#[test]
+fn hash_code() {
+	let expectations = [
+		("hashCode=0", Some(HashCodeMode::OsRandom)),
+		("hashCode=1", Some(HashCodeMode::AddressBased)),
+		("hashCode=2", Some(HashCodeMode::Constant)),
+		("hashCode=3", Some(HashCodeMode::Sequence)),
+		("hashCode=4", Some(HashCodeMode::Address)),
+		("hashCode=5", Some(HashCodeMode::Marsaglia)),
+		("hashCode=6", None),
+		("hashCode=-1", None),
+		("hashCode=", None),
+	];
+
+	for (flag, expected) in expectations {
+		let mut options = JvmOptions::default();
+		let result = options.apply_xx_flag(flag);
+		assert_eq!(result.ok().map(|()| options.hash_code), expected, "{flag}");
+	}
+}
+
+#[test]
+fn max_direct_memory_size() {
+	let expectations = [
+		("MaxDirectMemorySize=0", Some(0)),
+		("MaxDirectMemorySize=4096", Some(4096)),
+		("MaxDirectMemorySize=64m", Some(64 * 1024 * 1024)),
+		("MaxDirectMemorySize=", None),
+		("MaxDirectMemorySize=-1", None),
+	];
+
+	for (flag, expected) in expectations {
+		let mut options = JvmOptions::default();
+		let result = options.apply_xx_flag(flag);
+		assert_eq!(
+			result.ok().and_then(|()| options.max_direct_memory_size),
+			expected,
+			"{flag}"
+		);
+	}
+}
+
+#[test]
+fn active_processor_count() {
+	let expectations = [
+		("ActiveProcessorCount=4", Some(Some(4))),
+		("ActiveProcessorCount=1", Some(Some(1))),
+		("ActiveProcessorCount=0", Some(None)),
+		("ActiveProcessorCount=-1", Some(None)),
+		("ActiveProcessorCount=-2", None),
+		("ActiveProcessorCount=", None),
+		("ActiveProcessorCount=four", None),
+	];
+
+	for (flag, expected) in expectations {
+		let mut options = JvmOptions::default();
+		let result = options.apply_xx_flag(flag);
+		assert_eq!(
+			result.ok().map(|()| options.active_processor_count),
+			expected,
+			"{flag}"
+		);
+	}
+}
+
+#[test]
+fn use_container_support() {
+	let mut options = JvmOptions::default();
+	assert_eq!(options.use_container_support, cfg!(target_os = "linux"));
+
+	assert!(options.apply_xx_flag("-UseContainerSupport").is_ok());
+	assert!(!options.use_container_support);
+
+	assert!(options.apply_xx_flag("UseContainerSupport=true").is_ok());
+	assert_eq!(options.use_container_support, cfg!(target_os = "linux"));
+
+	assert!(options.apply_xx_flag("UseContainerSupport=yes").is_err());
+}
+
+#[test]
+fn heap_sizes() {
+	let expectations = [
+		("MaxHeapSize=64m", Some(64 * 1024 * 1024)),
+		("MaxHeapSize=1g", Some(1024 * 1024 * 1024)),
+		("MaxHeapSize=1m", None),
+		("MaxHeapSize=", None),
+		("MaxHeapSize=-1", None),
+	];
+
+	for (flag, expected) in expectations {
+		let mut options = JvmOptions::default();
+		let result = options.apply_xx_flag(flag);
+		assert_eq!(
+			result.ok().and_then(|()| options.max_heap_size),
+			expected,
+			"{flag}"
+		);
+	}
+
+	let mut options = JvmOptions::default();
+	assert!(
+		options
+			.apply_xx_flag(&format!("MaxHeapSize={MIN_HEAP_SIZE}"))
+			.is_ok()
+	);
+	assert!(options.apply_xx_flag("InitialHeapSize=16m").is_ok());
+	assert!(options.check_heap_sizes().is_err());
+
+	assert!(options.apply_xx_flag("MaxHeapSize=32m").is_ok());
+	assert!(options.check_heap_sizes().is_ok());
+
+	assert!(options.apply_xx_flag("NewSize=32m").is_ok());
+	assert!(options.check_heap_sizes().is_err());
+}
+
+#[test]
+fn heap_dump_on_out_of_memory_error() {
+	let mut options = JvmOptions::default();
+	assert!(!options.heap_dump_on_out_of_memory_error);
+
+	assert!(options.apply_xx_flag("+HeapDumpOnOutOfMemoryError").is_ok());
+	assert!(options.heap_dump_on_out_of_memory_error);
+
+	assert!(
+		options
+			.apply_xx_flag("HeapDumpOnOutOfMemoryError=maybe")
+			.is_err()
+	);
+}
+
+#[test]
+fn reduce_signal_usage() {
+	let mut options = JvmOptions::default();
+	assert!(!options.reduce_signal_usage);
+
+	assert!(options.apply_xx_flag("+ReduceSignalUsage").is_ok());
+	assert!(options.reduce_signal_usage);
+
+	assert!(options.apply_xx_flag("-ReduceSignalUsage").is_ok());
+	assert!(!options.reduce_signal_usage);
+}
+
+#[test]
+fn assertion_options() {
+	let mut assertions = AssertionOptions::default();
+	for option in [
+		"-ea",
+		"-dsa",
+		"-da:com.example...",
+		"-enableassertions:com.example.Main",
+		"-ea:...",
+		"-esa",
+	] {
+		assert!(assertions.apply_option(option), "{option}");
+	}
+
+	assert!(!assertions.apply_option("-eax"));
+	assert!(!assertions.apply_option("-Xmx1g"));
+
+	assert!(assertions.user_default);
+	assert!(assertions.system_default);
+	assert_eq!(
+		assertions.directives,
+		vec![
+			AssertionDirective {
+				name: String::from("com.example"),
+				is_package: true,
+				enabled: false,
+			},
+			AssertionDirective {
+				name: String::from("com.example.Main"),
+				is_package: false,
+				enabled: true,
+			},
+			AssertionDirective {
+				name: String::new(),
+				is_package: true,
+				enabled: true,
+			},
+		]
+	);
+}
+
+#[test]
+fn assertion_status() {
+	let mut assertions = AssertionOptions::default();
+	for option in [
+		"-ea:com.example...",
+		"-da:com.example.internal...",
+		"-da:com.example.Main",
+		"-ea:com.example.Main",
+		"-da:...",
+		"-da:com.example.Other",
+	] {
+		assert!(assertions.apply_option(option), "{option}");
+	}
+
+	let expectations = [
+		// The latest class directive wins
+		("com.example.Main", true),
+		("com.example.Other", false),
+		// Followed by the most specific package
+		("com.example.Util", true),
+		("com.example.internal.Impl", false),
+		("com.example.internal.deep.Impl", false),
+		("com.example.api.Impl", true),
+		// `-da:...` only applies to the unnamed package
+		("Main", false),
+		("org.example.Main", false),
+	];
+
+	for (class_name, expected) in expectations {
+		assert_eq!(
+			assertions.desired_status(class_name, false),
+			expected,
+			"{class_name}"
+		);
+	}
+
+	assertions.user_default = true;
+	assert!(assertions.desired_status("org.example.Main", false));
+	assert!(!assertions.desired_status("java.lang.Object", true));
+	assert!(!assertions.desired_status("Main", false));
+}
+
+#[test]
+fn reported_arguments() {
+	for option in [
+		"-Xmx1g",
+		"-XX:+UseContainerSupport",
+		"-ea",
+		"-Dfoo=bar",
+		"-Djava.class.pathx=1",
+	] {
+		assert!(is_reported_argument(option), "{option}");
+	}
+
+	for option in [
+		"vfprintf",
+		"exit",
+		"abort",
+		"-Djava.class.path=app.jar",
+		"-Dsun.java.command=Main",
+		"-Dsun.java.launcher=SUN_STANDARD",
+		"-Dsun.java.launcher.pid=1234",
+	] {
+		assert!(!is_reported_argument(option), "{option}");
+	}
+}
+
+#[test]
+fn disable_intrinsic() {
+	let mut options = JvmOptions::default();
+	assert!(options.disabled_intrinsics.is_empty());
+
+	assert!(
+		options
+			.apply_xx_flag("DisableIntrinsic=Math_sqrt,,System_arraycopy")
+			.is_ok()
+	);
+	assert!(
+		options
+			.apply_xx_flag("DisableIntrinsic=Object_hashCode")
+			.is_ok()
+	);
+	assert_eq!(
+		options.disabled_intrinsics,
+		["Math_sqrt", "System_arraycopy", "Object_hashCode"]
+	);
+}
+
+#[test]
+fn shared_archive_options() {
+	let mut options = JvmOptions::default();
+	assert_eq!(options.share, ShareMode::Auto);
+	assert!(options.shared_archive_file.is_none());
+
+	assert!(
+		options
+			.apply_xx_flag("SharedArchiveFile=/tmp/app.jsa")
+			.is_ok()
+	);
+	assert!(
+		options
+			.apply_xx_flag("ArchiveClassesAtExit=/tmp/dynamic.jsa")
+			.is_ok()
+	);
+	assert!(
+		options
+			.apply_xx_flag("DumpLoadedClassList=/tmp/classes.lst")
+			.is_ok()
+	);
+	assert_eq!(
+		options.shared_archive_file.as_deref(),
+		Some(Path::new("/tmp/app.jsa"))
+	);
+	assert_eq!(
+		options.archive_classes_at_exit.as_deref(),
+		Some(Path::new("/tmp/dynamic.jsa"))
+	);
+	assert_eq!(
+		options.dump_loaded_class_list.as_deref(),
+		Some(Path::new("/tmp/classes.lst"))
+	);
+
+	assert_eq!("off".parse(), Ok(ShareMode::Off));
+	assert_eq!("auto".parse(), Ok(ShareMode::Auto));
+	assert_eq!("on".parse(), Ok(ShareMode::On));
+	assert!("dump".parse::<ShareMode>().is_err());
+}
@@ -0,0 +1,104 @@
+//! The host's resources, as seen by the VM
+//!
+//! Unless disabled with `-XX:-UseContainerSupport`, the CPU and memory limits of the process's
+//! cgroup are taken into account. Otherwise, a container would report the resources of the entire
+//! node, and anything sized from them (thread pools, the heap, ...) would overwhelm it.
+
+use crate::logging::trace;
+use crate::options::JvmOptions;
+
+#[cfg(target_os = "linux")]
+use platform::linux::container::Cgroup;
+
+/// The cgroup of the process, if container support is enabled
+#[cfg(target_os = "linux")]
+fn cgroup() -> Option<&'static Cgroup> {
+	if !JvmOptions::get().use_container_support {
+		return None;
+	}
+
+	Cgroup::current()
+}
+
+/// The memory limit of the cgroup, if it is lower than `physical_memory`
+fn container_memory_limit(physical_memory: u64) -> Option<u64> {
+	#[cfg(target_os = "linux")]
+	if let Some(cgroup) = cgroup() {
+		// The v1 "unlimited" is a very large number, rather than a special value
+		return cgroup
+			.memory_limit()
+			.filter(|limit| *limit < physical_memory);
+	}
+
+	None
+}
+
+/// Whether the VM is running in a container
+///
+/// This is the case if the cgroup filesystem is mounted read-only, as container runtimes do, or if
+/// any CPU or memory limits are set.
+pub fn is_containerized() -> bool {
+	#[cfg(target_os = "linux")]
+	if let Some(cgroup) = cgroup() {
+		return cgroup.is_read_only()
+			|| cgroup.cpu_quota().is_some()
+			|| container_memory_limit(platform::mem::physical_memory()).is_some();
+	}
+
+	false
+}
+
+/// The number of CPUs available to the VM
+///
+/// This is the smallest of the CPU affinity mask, the cgroup's CPU quota, and its cpuset. It can be
+/// overridden with `-XX:ActiveProcessorCount`.
+pub fn active_processor_count() -> usize {
+	if let Some(count) = JvmOptions::get().active_processor_count {
+		trace!(TARGETS: (Os), "active_processor_count: determined by ActiveProcessorCount {count}");
+		return count;
+	}
+
+	let host = platform::cpu::processor_count();
+
+	#[cfg(target_os = "linux")]
+	if let Some(cgroup) = cgroup() {
+		let quota = cgroup.cpu_quota();
+		let cpuset = cgroup.cpuset_count();
+		trace!(
+			TARGETS: (Os, Container),
+			"CPU quota: {quota:?}, cpuset: {cpuset:?}, host: {host}"
+		);
+
+		let limit = quota.into_iter().chain(cpuset).min();
+		if let Some(limit) = limit {
+			return host.min(limit as usize);
+		}
+	}
+
+	host
+}
+
+/// The amount of physical memory available to the VM, in bytes
+pub fn physical_memory() -> u64 {
+	let host = platform::mem::physical_memory();
+	match container_memory_limit(host) {
+		Some(limit) => {
+			trace!(TARGETS: (Os, Container), "Memory limit: {limit}, host: {host}");
+			limit
+		},
+		None => host,
+	}
+}
+
+/// The amount of physical memory that is currently unused, in bytes
+pub fn available_memory() -> u64 {
+	let host = platform::mem::physical_memory();
+
+	#[cfg(target_os = "linux")]
+	if let Some(limit) = container_memory_limit(host) {
+		let usage = cgroup().and_then(Cgroup::memory_usage).unwrap_or(0);
+		return limit.saturating_sub(usage);
+	}
+
+	platform::mem::available_memory().unwrap_or(host)
+}
@@ -0,0 +1,93 @@
+//! VM shutdown
+//!
+//! The VM can go down in a few ways:
+//!
+//! * `DestroyJavaVM`, which waits for the last non-daemon thread to exit. See [`destroy_java_vm()`].
+//! * `Runtime#exit`/`Runtime#halt`, which run the shutdown hooks on the Java side, and end up in
+//!   `JVM_Halt`. See [`halt()`].
+//! * A fatal error. See [`abort()`].
+//!
+//! In the first two cases, any threads still running (namely daemon threads) are stopped the next
+//! time they reach a safe point, and any requested CDS archive or class list is written. See
+//! [`cds::dump_at_exit()`].
+
+use crate::classpath::loader::ClassLoader;
+use crate::options::Hooks;
+use crate::symbols::sym;
+use crate::thread::JavaThread;
+use crate::thread::exceptions::Throws;
+use crate::thread::pool::ThreadPool;
+use crate::{cds, java_call};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use jni::sys::jint;
+
+static VM_EXITING: AtomicBool = AtomicBool::new(false);
+
+/// Shut down the VM once all non-daemon threads have exited
+///
+/// This runs the shutdown hooks, and stops any remaining threads. The caller is expected to return
+/// to the embedder afterward, it's up to them to actually exit the process.
+pub fn destroy_java_vm(current: &'static JavaThread) {
+	ThreadPool::wait_for_non_daemon_threads(current);
+
+	if let Throws::Exception(e) = run_shutdown_hooks(current) {
+		e.throw(current);
+	}
+
+	// Hotspot ignores any exceptions thrown by the hooks
+	current.discard_pending_exception();
+
+	stop_threads(current);
+	cds::dump_at_exit();
+}
+
+/// Stop all other threads and exit the process with `code`, without running shutdown hooks
+///
+/// This is the final step of `Runtime#exit`/`Runtime#halt`. The process is exited through the
+/// `exit` hook, if one was provided.
+pub fn halt(code: jint) -> ! {
+	if let Some(current) = JavaThread::current_opt() {
+		stop_threads(current);
+	}
+
+	cds::dump_at_exit();
+	Hooks::get().exit(code)
+}
+
+/// Abnormally terminate the process
+///
+/// The process is aborted through the `abort` hook, if one was provided.
+pub fn abort() -> ! {
+	Hooks::get().abort()
+}
+
+/// Call `java.lang.Shutdown#shutdown`
+fn run_shutdown_hooks(thread: &'static JavaThread) -> Throws<()> {
+	let shutdown_class = ClassLoader::bootstrap().load(sym!(java_lang_Shutdown))?;
+	shutdown_class.initialize(thread)?;
+
+	let shutdown_method =
+		shutdown_class.resolve_method(sym!(shutdown_name), sym!(void_method_signature))?;
+	java_call!(thread, shutdown_method);
+
+	if thread.has_pending_exception() {
+		return Throws::PENDING_EXCEPTION;
+	}
+
+	Throws::Ok(())
+}
+
+fn stop_threads(current: &'static JavaThread) {
+	if VM_EXITING.swap(true, Ordering::AcqRel) {
+		// Someone else got here first
+		return;
+	}
+
+	for thread in ThreadPool::live_threads() {
+		if thread != current {
+			thread.stop_at_safe_point();
+		}
+	}
+}
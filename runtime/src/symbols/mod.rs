@@ -1,66 +1,8 @@
+mod table;
+use table::TABLE;
+
 use std::borrow::Cow;
 use std::fmt::Display;
-use std::sync::{LazyLock, Mutex};
-
-use fxhash::FxBuildHasher;
-use indexmap::IndexSet;
-
-static INTERNER: LazyLock<Mutex<SymbolInterner>> =
-	LazyLock::new(|| Mutex::new(SymbolInterner::initialize()));
-
-type FxIndexSet<T> = IndexSet<T, FxBuildHasher>;
-
-/// The symbol interner, responsible for mapping symbols to strings
-struct SymbolInterner {
-	arena: bumpalo::Bump,
-	set: FxIndexSet<&'static [u8]>,
-}
-
-impl SymbolInterner {
-	fn initialize() -> Self {
-		let mut this = Self {
-			arena: bumpalo::Bump::with_capacity(1024),
-			set: FxIndexSet::default(),
-		};
-
-		// Method generated by `vm_symbols::define_symbols`
-		this.preintern();
-		assert!(
-			this.set.len() <= Symbol::PRE_INTERNED_LIMIT,
-			"Too many symbols registered for pre-intern (> {})",
-			Symbol::PRE_INTERNED_LIMIT
-		);
-
-		this
-	}
-
-	/// Intern a string
-	#[allow(trivial_casts)]
-	fn intern<T: Internable>(&mut self, string: T) -> Symbol {
-		let bytes = string.as_bytes();
-
-		if let Some(symbol_idx) = self.set.get_index_of(bytes) {
-			assert!(symbol_idx < self.set.len());
-			return Symbol::new(symbol_idx as u32);
-		}
-
-		// We extend the lifetime of the string to `'static`, which is safe,
-		// as we only use the strings while the arena is alive
-		let string: &'static [u8] =
-			unsafe { &*std::ptr::from_ref::<[u8]>(self.arena.alloc_slice_copy(bytes)) };
-		self.intern_static(string)
-	}
-
-	fn intern_static(&mut self, bytes: &'static [u8]) -> Symbol {
-		let (index, _) = self.set.insert_full(bytes);
-		Symbol::new(index as u32)
-	}
-
-	/// Gets the string for a Symbol
-	fn get(&self, symbol: Symbol) -> &'static [u8] {
-		self.set[symbol.as_u32() as usize]
-	}
-}
 
 /// An index representation of an interned string
 ///
@@ -72,16 +14,13 @@ pub struct Symbol(u32);
 impl Symbol {
 	/// Access the actual string associated with this symbol
 	pub fn as_str(self) -> &'static str {
-		let guard = INTERNER.lock().unwrap();
-
 		// SAFETY: Symbols are **always** UTF-8
-		unsafe { std::str::from_utf8_unchecked(guard.get(self)) }
+		unsafe { std::str::from_utf8_unchecked(TABLE.get(self)) }
 	}
 
 	/// Access the byte string associated with this symbol
 	pub fn as_bytes(self) -> &'static [u8] {
-		let guard = INTERNER.lock().unwrap();
-		guard.get(self)
+		TABLE.get(self)
 	}
 
 	/// Access the `u32` representation of this symbol
@@ -103,8 +42,7 @@ impl Symbol {
 
 	/// Maps a string to its interned representation
 	pub fn intern<T: Internable>(string: T) -> Self {
-		let mut guard = INTERNER.lock().unwrap();
-		guard.intern(string)
+		TABLE.intern(string.as_bytes())
 	}
 }
 
@@ -217,10 +155,12 @@ vm_symbols::define_symbols! {
 	java_lang_String: "java/lang/String",
 	java_lang_Module: "java/lang/Module",
 	java_lang_System: "java/lang/System",
+	java_lang_Shutdown: "java/lang/Shutdown",
 	java_lang_Cloneable: "java/lang/Cloneable",
 	java_io_Serializable: "java/io/Serializable",
 	java_io_File: "java/io/File",
 	jdk_internal_misc_UnsafeConstants: "jdk/internal/misc/UnsafeConstants",
+	jdk_internal_misc_Signal: "jdk/internal/misc/Signal",
 	jdk_internal_reflect_MethodAccessorImpl: "jdk/internal/reflect/MethodAccessorImpl",
 	jdk_internal_reflect_ConstantPool: "jdk/internal/reflect/ConstantPool",
 	java_lang_invoke_MethodHandle: "java/lang/invoke/MethodHandle",
@@ -233,17 +173,29 @@ vm_symbols::define_symbols! {
 	java_lang_reflect_Constructor: "java/lang/reflect/Constructor",
 	java_lang_reflect_Method: "java/lang/reflect/Method",
 	java_lang_reflect_Field: "java/lang/reflect/Field",
+	java_lang_reflect_RecordComponent: "java/lang/reflect/RecordComponent",
 	java_lang_Thread: "java/lang/Thread",
 	java_lang_ThreadGroup: "java/lang/ThreadGroup",
 	java_lang_Thread_FieldHolder: "java/lang/Thread$FieldHolder",
 	java_lang_ref_Finalizer: "java/lang/ref/Finalizer",
+	java_lang_ref_SoftReference: "java/lang/ref/SoftReference",
+	java_lang_ref_WeakReference: "java/lang/ref/WeakReference",
+	java_lang_ref_FinalReference: "java/lang/ref/FinalReference",
 	jdk_internal_loader_ClassLoaders_PlatformClassLoader: "jdk/internal/loader/ClassLoaders$PlatformClassLoader",
+	jdk_internal_loader_ClassLoaders_AppClassLoader: "jdk/internal/loader/ClassLoaders$AppClassLoader",
 	jdk_internal_loader_NativeLibraries: "jdk/internal/loader/NativeLibraries",
 	jdk_internal_loader_NativeLibraries_NativeLibraryImpl: "jdk/internal/loader/NativeLibraries$NativeLibraryImpl",
+	jdk_internal_vm_ThreadSnapshot: "jdk/internal/vm/ThreadSnapshot",
+	jdk_internal_vm_ThreadSnapshot_ThreadLock: "jdk/internal/vm/ThreadSnapshot$ThreadLock",
+	jdk_internal_foreign_abi_VMStorage: "jdk/internal/foreign/abi/VMStorage",
+	jdk_internal_foreign_abi_ABIDescriptor: "jdk/internal/foreign/abi/ABIDescriptor",
+	jdk_internal_foreign_abi_NativeEntryPoint: "jdk/internal/foreign/abi/NativeEntryPoint",
+	jdk_internal_foreign_abi_UpcallLinker_CallRegs: "jdk/internal/foreign/abi/UpcallLinker$CallRegs",
 
 	// Throwables
 	java_lang_Throwable: "java/lang/Throwable",
 	java_lang_StackTraceElement: "java/lang/StackTraceElement",
+	java_lang_AssertionStatusDirectives: "java/lang/AssertionStatusDirectives",
 
 	java_lang_VirtualMachineError: "java/lang/VirtualMachineError",
 
@@ -253,16 +205,22 @@ vm_symbols::define_symbols! {
 	java_lang_ClassNotFoundException: "java/lang/ClassNotFoundException",
 	java_lang_ClassCastException: "java/lang/ClassCastException",
 	java_lang_InstantiationError: "java/lang/InstantiationError",
+	java_lang_InstantiationException: "java/lang/InstantiationException",
+	java_lang_reflect_InvocationTargetException: "java/lang/reflect/InvocationTargetException",
 
 	java_lang_LinkageError: "java/lang/LinkageError",
+	java_lang_ClassCircularityError: "java/lang/ClassCircularityError",
 	java_lang_UnsatisfiedLinkError: "java/lang/UnsatisfiedLinkError",
 	java_lang_IncompatibleClassChangeError: "java/lang/IncompatibleClassChangeError",
 	java_lang_NoSuchFieldError: "java/lang/NoSuchFieldError",
 	java_lang_NoSuchMethodError: "java/lang/NoSuchMethodError",
 	java_lang_AbstractMethodError: "java/lang/AbstractMethodError",
 
+	java_lang_ArithmeticException: "java/lang/ArithmeticException",
+
 	java_lang_NegativeArraySizeException: "java/lang/NegativeArraySizeException",
 	java_lang_ArrayIndexOutOfBoundsException: "java/lang/ArrayIndexOutOfBoundsException",
+	java_lang_ArrayStoreException: "java/lang/ArrayStoreException",
 
 	java_lang_StringIndexOutOfBoundsException: "java/lang/StringIndexOutOfBoundsException",
 
@@ -279,6 +237,7 @@ vm_symbols::define_symbols! {
 	java_lang_IllegalMonitorStateException: "java/lang/IllegalMonitorStateException",
 	java_lang_OutOfMemoryError: "java/lang/OutOfMemoryError",
 	java_lang_StackOverflowError: "java/lang/StackOverflowError",
+	java_lang_SecurityException: "java/lang/SecurityException",
 
 	java_io_IOException: "java/io/IOException",
 
@@ -301,8 +260,14 @@ vm_symbols::define_symbols! {
 	Boolean_valueOf_signature: "(Z)Ljava/lang/Boolean;",
 	Integer_valueOf_signature: "(I)Ljava/lang/Integer;",
 	Long_valueOf_signature: "(J)Ljava/lang/Long;",
-	Double_valueOf_signature: "(Z)Ljava/lang/Double;",
-	Float_valueOf_signature: "(Z)Ljava/lang/Float;",
+	Double_valueOf_signature: "(D)Ljava/lang/Double;",
+	Float_valueOf_signature: "(F)Ljava/lang/Float;",
+	Byte_valueOf_signature: "(B)Ljava/lang/Byte;",
+	Short_valueOf_signature: "(S)Ljava/lang/Short;",
+	Character_valueOf_signature: "(C)Ljava/lang/Character;",
+	Throwable_void_signature: "(Ljava/lang/Throwable;)V",
+	int_void_signature: "(I)V",
+	Object_Object_Object_signature: "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
 	// -- GENERATED METHOD SIGNATURE MARKER, DO NOT DELETE --
 
 	// Types
@@ -334,7 +299,7 @@ vm_symbols::define_symbols! {
 	athrow_name: "<athrow>",
 
 	getClass,
-	hashCode,
+	hashCode_name: "hashCode",
 	clone,
 	notify,
 	notifyAll,
@@ -350,6 +315,9 @@ vm_symbols::define_symbols! {
 	initPhase1_name: "initPhase1",
 	initPhase2_name: "initPhase2",
 	initPhase3_name: "initPhase3",
+	shutdown_name: "shutdown",
+	dispatch_name: "dispatch",
+	put_name: "put",
 
 	printStackTrace_name: "printStackTrace",
 	getCause,
@@ -378,6 +346,10 @@ vm_symbols::define_symbols! {
 	primitive,
 	componentType,
 	referent,
+	next,
+	discovered,
+	timestamp,
+	clock,
 	loader,
 	holder,
 	eetop,
@@ -398,6 +370,7 @@ vm_symbols::define_symbols! {
 	backtrace,
 	detailMessage,
 	depth,
+	cause,
 	declaringClassObject,
 	classLoaderName,
 	moduleName,
@@ -429,13 +402,38 @@ vm_symbols::define_symbols! {
 	annotations,
 	parameterAnnotations,
 	annotationDefault,
+	typeAnnotations,
+	accessor,
 	form,
 	constantPoolOop,
 	handle,
 	jniVersion,
+	tid,
+	locks,
+	typeOrdinal,
+	obj,
+	blockerTypeOrdinal,
+	blockerObject,
+	segmentMaskOrSize,
+	indexOrOffset,
+	inputStorage,
+	outputStorage,
+	targetAddrStorage,
+	retBufAddrStorage,
+	capturedStateStorage,
+	downcallStubAddress,
+	argRegs,
+	retRegs,
+	classes,
+	classEnabled,
+	packages,
+	packageEnabled,
+	deflt,
 
 	// Injected fields
 	loader_ptr,
 	module_ptr,
 	vmindex,
+	protection_domain,
+	signers,
 }
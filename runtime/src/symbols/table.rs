@@ -0,0 +1,232 @@
+//! The backing storage for [`Symbol`]s
+//!
+//! Symbols are stored in two structures:
+//!
+//! * A slot table, mapping a symbol index to its entry. This is what [`Symbol::as_str()`] goes
+//!   through, and is lock-free. It is made up of chunks that double in size, which are never moved
+//!   once allocated.
+//! * A set of shards, mapping strings to their symbols. A string is always assigned to the same shard
+//!   (by its hash), so lookups only ever contend with interning of strings in the same shard.
+//!
+//! Creating a new symbol additionally takes the (global) index allocator lock, which is uncontended
+//! so long as most lookups are hits.
+
+use super::{PREINTERED_SYMBOLS_COUNT, PREINTERNED_SYMBOLS, Symbol};
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
+
+use fxhash::FxBuildHasher;
+
+pub(super) static TABLE: LazyLock<SymbolTable> = LazyLock::new(SymbolTable::initialize);
+
+const SHARD_COUNT: usize = 64;
+
+/// The length of the first chunk, which will also hold all of the pre-interned symbols
+const FIRST_CHUNK_LEN: usize = Symbol::PRE_INTERNED_LIMIT;
+
+/// The number of chunks needed to cover the entire `u32` index space
+const CHUNK_COUNT: usize = (u32::BITS - FIRST_CHUNK_LEN.ilog2()) as usize + 1;
+
+struct SymbolEntry {
+	bytes: &'static [u8],
+}
+
+/// Hands out symbol indices
+struct IndexAllocator {
+	next: u32,
+}
+
+type Shard = RwLock<HashMap<&'static [u8], Symbol, FxBuildHasher>>;
+
+pub(super) struct SymbolTable {
+	chunks: [AtomicPtr<AtomicPtr<SymbolEntry>>; CHUNK_COUNT],
+	shards: [Shard; SHARD_COUNT],
+	allocator: Mutex<IndexAllocator>,
+}
+
+impl SymbolTable {
+	fn initialize() -> Self {
+		assert!(
+			PREINTERNED_SYMBOLS.len() <= Symbol::PRE_INTERNED_LIMIT,
+			"Too many symbols registered for pre-intern (> {})",
+			Symbol::PRE_INTERNED_LIMIT
+		);
+
+		let this = Self {
+			chunks: std::array::from_fn(|index| match index {
+				0 => AtomicPtr::new(new_chunk(FIRST_CHUNK_LEN)),
+				_ => AtomicPtr::new(ptr::null_mut()),
+			}),
+			shards: std::array::from_fn(|_| RwLock::default()),
+			allocator: Mutex::new(IndexAllocator {
+				next: PREINTERED_SYMBOLS_COUNT,
+			}),
+		};
+
+		for (index, bytes) in PREINTERNED_SYMBOLS.into_iter().enumerate() {
+			let symbol = Symbol::new(index as u32);
+			this.publish(symbol, bytes);
+
+			let previous = this.shard(bytes).write().unwrap().insert(bytes, symbol);
+			assert!(previous.is_none(), "duplicate pre-interned symbol");
+		}
+
+		this
+	}
+
+	/// Get the string for `symbol`
+	///
+	/// # Panics
+	///
+	/// This will panic if `symbol` was never interned.
+	pub(super) fn get(&self, symbol: Symbol) -> &'static [u8] {
+		let entry = self.slot(symbol).load(Ordering::Acquire);
+		assert!(!entry.is_null(), "invalid symbol index {}", symbol.as_u32());
+
+		// SAFETY: Entries are never freed
+		unsafe { (*entry).bytes }
+	}
+
+	/// Intern `bytes`
+	pub(super) fn intern(&self, bytes: &[u8]) -> Symbol {
+		let shard = self.shard(bytes);
+
+		if let Some(&symbol) = shard.read().unwrap().get(bytes) {
+			return symbol;
+		}
+
+		let mut map = shard.write().unwrap();
+
+		// Some other thread beat us here
+		if let Some(&symbol) = map.get(bytes) {
+			return symbol;
+		}
+
+		let bytes: &'static [u8] = Box::leak(Box::from(bytes));
+		let symbol = self.allocate_index();
+		self.publish(symbol, bytes);
+
+		map.insert(bytes, symbol);
+		symbol
+	}
+
+	fn shard(&self, bytes: &[u8]) -> &Shard {
+		// FxHash mixes poorly into the low bits, take the high bits instead
+		let hash = FxBuildHasher::default().hash_one(bytes);
+		&self.shards[(hash >> (u64::BITS - SHARD_COUNT.ilog2())) as usize]
+	}
+
+	fn allocate_index(&self) -> Symbol {
+		let mut allocator = self.allocator.lock().unwrap();
+		let index = allocator.next;
+		allocator.next = index.checked_add(1).expect("symbol table exhausted");
+
+		// Make sure the chunk exists, the allocator lock keeps other threads from racing us here
+		let (chunk_index, _) = locate(index);
+		let chunk = &self.chunks[chunk_index];
+		if chunk.load(Ordering::Acquire).is_null() {
+			chunk.store(new_chunk(chunk_len(chunk_index)), Ordering::Release);
+		}
+
+		Symbol::new(index)
+	}
+
+	fn publish(&self, symbol: Symbol, bytes: &'static [u8]) {
+		let entry = Box::new(SymbolEntry { bytes });
+
+		self.slot(symbol)
+			.store(Box::into_raw(entry), Ordering::Release);
+	}
+
+	fn slot(&self, symbol: Symbol) -> &AtomicPtr<SymbolEntry> {
+		let (chunk_index, offset) = locate(symbol.as_u32());
+
+		let chunk = self.chunks[chunk_index].load(Ordering::Acquire);
+		assert!(!chunk.is_null(), "invalid symbol index {}", symbol.as_u32());
+
+		// SAFETY: `offset` is always within the chunk, and chunks are never freed
+		unsafe { &*chunk.add(offset) }
+	}
+}
+
+/// Get the chunk and the offset within it for a symbol index
+fn locate(index: u32) -> (usize, usize) {
+	let index = index as usize;
+	if index < FIRST_CHUNK_LEN {
+		return (0, index);
+	}
+
+	let chunk_index = (index / FIRST_CHUNK_LEN).ilog2() as usize + 1;
+	(chunk_index, index - chunk_len(chunk_index))
+}
+
+/// The length of the chunk at `chunk_index`
+///
+/// Every chunk after the first is as long as all of the chunks before it combined.
+fn chunk_len(chunk_index: usize) -> usize {
+	if chunk_index == 0 {
+		return FIRST_CHUNK_LEN;
+	}
+
+	FIRST_CHUNK_LEN << (chunk_index - 1)
+}
+
+fn new_chunk(len: usize) -> *mut AtomicPtr<SymbolEntry> {
+	let chunk: Box<[AtomicPtr<SymbolEntry>]> =
+		(0..len).map(|_| AtomicPtr::new(ptr::null_mut())).collect();
+	Box::leak(chunk).as_mut_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::symbols::sym;
+
+	#[test]
+	fn chunk_layout() {
+		assert_eq!(locate(0), (0, 0));
+		assert_eq!(locate(FIRST_CHUNK_LEN as u32 - 1), (0, FIRST_CHUNK_LEN - 1));
+		assert_eq!(locate(FIRST_CHUNK_LEN as u32), (1, 0));
+		assert_eq!(locate(FIRST_CHUNK_LEN as u32 * 3), (2, FIRST_CHUNK_LEN));
+		assert_eq!(locate(u32::MAX).0, CHUNK_COUNT - 1);
+		assert!(locate(u32::MAX).1 < chunk_len(CHUNK_COUNT - 1));
+	}
+
+	#[test]
+	fn preinterned() {
+		assert_eq!(Symbol::intern("java/lang/Object"), sym!(java_lang_Object));
+		assert_eq!(sym!(java_lang_Object).as_str(), "java/lang/Object");
+	}
+
+	#[test]
+	fn concurrent_intern() {
+		let threads = (0..8)
+			.map(|_| {
+				std::thread::spawn(|| {
+					(0..1000)
+						.map(|i| Symbol::intern(format!("symbols::table::tests::concurrent{i}")))
+						.collect::<Vec<_>>()
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let results = threads
+			.into_iter()
+			.map(|thread| thread.join().unwrap())
+			.collect::<Vec<_>>();
+		for symbols in &results[1..] {
+			assert_eq!(symbols, &results[0]);
+		}
+
+		for (i, symbol) in results[0].iter().enumerate() {
+			assert_eq!(
+				symbol.as_str(),
+				format!("symbols::table::tests::concurrent{i}")
+			);
+		}
+	}
+}
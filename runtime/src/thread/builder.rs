@@ -1,16 +1,26 @@
+use crate::classes;
 use crate::objects::reference::Reference;
+use crate::options::{JvmOptions, MIN_THREAD_STACK_SIZE};
 use crate::thread::JavaThread;
 use crate::thread::exceptions::Throws;
 use crate::thread::pool::ThreadPool;
 
+use jni::sys::jlong;
+
 /// A builder for a `JavaThread`
 ///
 /// This is the only way to construct a `JavaThread`, and is responsible for spawning the associated
 /// OS thread, if applicable.
-#[derive(Default)]
 pub struct JavaThreadBuilder {
 	obj: Option<Reference>,
 	stack_size: usize,
+	entry_point: Option<fn(&'static JavaThread)>,
+}
+
+impl Default for JavaThreadBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl JavaThreadBuilder {
@@ -20,7 +30,8 @@ impl JavaThreadBuilder {
 	pub fn new() -> JavaThreadBuilder {
 		Self {
 			obj: None,
-			stack_size: 1024, // TODO: Default -Xss
+			stack_size: JvmOptions::get().thread_stack_size,
+			entry_point: None,
 		}
 	}
 
@@ -33,9 +44,21 @@ impl JavaThreadBuilder {
 		self
 	}
 
-	/// Set the stack size of the thread
+	/// Set the stack size of the thread, in bytes
+	///
+	/// By default, this is the size specified by `-Xss`. Sizes below [`MIN_THREAD_STACK_SIZE`] are
+	/// rounded up.
 	pub fn stack_size(mut self, size: usize) -> Self {
-		self.stack_size = size;
+		self.stack_size = std::cmp::max(size, MIN_THREAD_STACK_SIZE);
+		self
+	}
+
+	/// Set the function to run once the thread is started
+	///
+	/// By default, this will call `java.lang.Thread#run` on the associated `java.lang.Thread`. This
+	/// is used for VM-internal threads that never execute Java code of their own.
+	pub fn entry_point(mut self, entry_point: fn(&'static JavaThread)) -> Self {
+		self.entry_point = Some(entry_point);
 		self
 	}
 
@@ -48,8 +71,18 @@ impl JavaThreadBuilder {
 		let thread = JavaThread::new(self.obj, self.stack_size)?;
 
 		let thread = ThreadPool::push(thread);
+		if let Some(obj) = thread.obj() {
+			classes::java::lang::Thread::set_eetop(
+				obj.extract_class(),
+				std::ptr::from_ref(thread) as jlong,
+			);
+		}
+
 		if start {
-			thread.start();
+			match self.entry_point {
+				Some(entry_point) => thread.start_with(entry_point),
+				None => thread.start(),
+			}
 		}
 
 		Throws::Ok(thread)
@@ -0,0 +1,265 @@
+//! Stack traces and thread dumps
+//!
+//! Capturing the state of another thread is done through a [handshake](JavaThread::handshake), so
+//! the snapshots here are always consistent with some point in the target's execution.
+
+use super::JavaThread;
+use super::frame::stack::VisibleStackFrame;
+use super::pool::ThreadPool;
+use crate::classes;
+use crate::classes::java::lang::Thread::ThreadStatus;
+use crate::classpath::loader::ClassLoader;
+use crate::native::java::lang::Throwable::BackTraceElement;
+use crate::objects::instance::array::{Array, ObjectArrayInstance};
+use crate::objects::instance::object::Object;
+use crate::objects::monitor::{Monitor, MonitorMap};
+use crate::objects::reference::Reference;
+use crate::symbols::sym;
+use crate::thread::exceptions::{Throws, throw};
+
+use std::io::{self, Write};
+use std::sync::atomic::Ordering;
+
+use common::int_types::s4;
+use jni::sys::jlong;
+
+const VM_NAME: &str = env!("SYSTEM_PROPS_VM_NAME");
+const VM_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+impl JavaThread {
+	/// Capture the stack trace of this thread, top frame first
+	///
+	/// If this isn't the current thread, this will block until the thread reaches a safe point.
+	pub fn stack_trace(&'static self) -> Vec<BackTraceElement> {
+		self.handshake(JavaThread::collect_stack_trace)
+	}
+
+	// This must only be called on this thread, or while in a handshake with it.
+	pub(crate) fn collect_stack_trace(&self) -> Vec<BackTraceElement> {
+		// The pc of the top frame lives on the thread until it makes another call
+		let top_is_executing = self.frame_stack.current().is_some();
+
+		self.frame_stack
+			.iter()
+			.enumerate()
+			.map(|(index, frame)| {
+				let pc = match frame {
+					VisibleStackFrame::Regular(_) if index == 0 && top_is_executing => {
+						self.pc.load(Ordering::Relaxed)
+					},
+					VisibleStackFrame::Regular(frame) => frame.stashed_pc(),
+					VisibleStackFrame::Native(_) => -1,
+				};
+
+				BackTraceElement {
+					method: frame.method(),
+					pc: pc as jlong,
+				}
+			})
+			.collect()
+	}
+}
+
+/// A point-in-time view of a thread's execution state
+pub struct ThreadSnapshot {
+	pub thread: &'static JavaThread,
+	pub status: ThreadStatus,
+	/// The frames of the thread, top first
+	pub frames: Vec<BackTraceElement>,
+	/// Monitors owned by the thread, along with the index of the frame that entered them
+	pub locked_monitors: Vec<(usize, &'static Monitor)>,
+	/// The monitor the thread is blocked trying to enter
+	pub contended_monitor: Option<&'static Monitor>,
+	/// The monitor the thread is waiting on in `Object#wait`
+	pub waiting_monitor: Option<&'static Monitor>,
+}
+
+impl ThreadSnapshot {
+	/// Capture a snapshot of `thread`
+	///
+	/// If this isn't the current thread, this will block until the thread reaches a safe point.
+	pub fn capture(thread: &'static JavaThread) -> Self {
+		thread.handshake(|thread| {
+			let frames = thread.collect_stack_trace();
+			let locked_monitors = MonitorMap::owned_by(thread)
+				.into_iter()
+				.map(|monitor| (frames.len().saturating_sub(monitor.entry_depth()), monitor))
+				.collect();
+
+			Self {
+				thread,
+				status: thread.thread_status().unwrap_or(ThreadStatus::Terminated),
+				frames,
+				locked_monitors,
+				contended_monitor: thread.contended_monitor(),
+				waiting_monitor: thread.waiting_monitor(),
+			}
+		})
+	}
+
+	/// Print this snapshot in the same format as `jstack`
+	pub fn print<W: Write>(&self, out: &mut W) -> io::Result<()> {
+		let obj = self
+			.thread
+			.obj()
+			.expect("live threads should have an object");
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
+
+		write!(
+			out,
+			"\"{}\" #{} ",
+			self.thread.name(),
+			classes::java::lang::Thread::tid(obj.extract_class())
+		)?;
+		if classes::java::lang::Thread::holder::daemon(holder.extract_class()) {
+			write!(out, "daemon ")?;
+		}
+		writeln!(
+			out,
+			"prio={}",
+			classes::java::lang::Thread::holder::priority(holder.extract_class())
+		)?;
+
+		write!(
+			out,
+			"   java.lang.Thread.State: {}",
+			self.status.state_name()
+		)?;
+		if self.contended_monitor.is_some() || self.waiting_monitor.is_some() {
+			write!(out, " (on object monitor)")?;
+		}
+		writeln!(out)?;
+
+		for (index, frame) in self.frames.iter().enumerate() {
+			writeln!(out, "\tat {}", FrameDisplay(frame))?;
+
+			if index == 0 {
+				if let Some(monitor) = self.waiting_monitor {
+					writeln!(out, "\t- waiting on {}", MonitorDisplay(monitor))?;
+				}
+
+				if let Some(monitor) = self.contended_monitor {
+					writeln!(out, "\t- waiting to lock {}", MonitorDisplay(monitor))?;
+				}
+			}
+
+			for (_, monitor) in self
+				.locked_monitors
+				.iter()
+				.filter(|(frame_index, _)| *frame_index == index)
+			{
+				writeln!(out, "\t- locked {}", MonitorDisplay(monitor))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+struct FrameDisplay<'a>(&'a BackTraceElement);
+
+impl std::fmt::Display for FrameDisplay<'_> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let method = self.0.method;
+		write!(f, "{}.{}(", method.class().external_name(), method.name)?;
+
+		if method.is_native() {
+			return write!(f, "Native Method)");
+		}
+
+		let Some(source_file) = method.class().source_file_name() else {
+			return write!(f, "Unknown Source)");
+		};
+
+		let line_number = method.line_number(self.0.pc as isize);
+		if line_number >= 0 {
+			write!(f, "{source_file}:{line_number})")
+		} else {
+			write!(f, "{source_file})")
+		}
+	}
+}
+
+struct MonitorDisplay(&'static Monitor);
+
+impl std::fmt::Display for MonitorDisplay {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let object = self.0.object();
+		write!(
+			f,
+			"<{:#018x}> (a {})",
+			object.raw_tagged().addr(),
+			object.class().external_name()
+		)
+	}
+}
+
+/// Print a thread dump of all live threads to `out`, in the same format as `jstack`
+pub fn print_thread_dump<W: Write>(out: &mut W) -> io::Result<()> {
+	writeln!(out, "Full thread dump {VM_NAME} ({VM_VERSION}):")?;
+
+	for thread in ThreadPool::live_threads() {
+		writeln!(out)?;
+		ThreadSnapshot::capture(thread).print(out)?;
+	}
+
+	writeln!(out)?;
+	out.flush()
+}
+
+/// Create a `java.lang.Thread[]` of all live threads
+pub fn live_thread_objects() -> Throws<Reference> {
+	let threads = ThreadPool::live_threads();
+
+	let array = ObjectArrayInstance::new(
+		threads.len() as s4,
+		crate::globals::classes::java_lang_Thread(),
+	)?;
+	for (index, thread) in threads.into_iter().enumerate() {
+		let obj = thread.obj().expect("live threads should have an object");
+		unsafe {
+			array.store_unchecked(index, obj);
+		}
+	}
+
+	Throws::Ok(Reference::object_array(array))
+}
+
+/// Create a `java.lang.StackTraceElement[]` for the thread associated with `obj`
+///
+/// This will return `null` if the thread isn't alive.
+pub fn stack_trace_of(current: &'static JavaThread, obj: Reference) -> Throws<Reference> {
+	let Some(thread) = ThreadPool::find_from_obj(obj) else {
+		return Throws::Ok(Reference::null());
+	};
+
+	let frames = thread.stack_trace();
+	let array = classes::java::lang::StackTraceElement::new_array(current, &frames)?;
+	Throws::Ok(Reference::object_array(array))
+}
+
+/// Create a `java.lang.StackTraceElement[][]` for each of the `threads` (a `java.lang.Thread[]`)
+///
+/// Any threads that aren't alive will have a `null` entry.
+pub fn stack_traces_of(current: &'static JavaThread, threads: Reference) -> Throws<Reference> {
+	if threads.is_null() {
+		throw!(@DEFER NullPointerException);
+	}
+
+	let threads = threads.extract_object_array();
+
+	let stack_trace_array_class = ClassLoader::bootstrap().load(sym!(StackTraceElement_array))?;
+	let traces = ObjectArrayInstance::new(threads.len() as s4, stack_trace_array_class)?;
+	for (index, obj) in threads.as_slice().iter().copied().enumerate() {
+		if obj.is_null() {
+			continue;
+		}
+
+		let trace = stack_trace_of(current, obj)?;
+		unsafe {
+			traces.store_unchecked(index, trace);
+		}
+	}
+
+	Throws::Ok(Reference::object_array(traces))
+}
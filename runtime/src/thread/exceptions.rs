@@ -1,5 +1,6 @@
 use super::JavaThread;
 use crate::classpath::loader::ClassLoader;
+use crate::gc::out_of_memory;
 use crate::java_call;
 use crate::native::java::lang::String::StringInterner;
 use crate::objects::class::ClassPtr;
@@ -119,9 +120,13 @@ pub enum ExceptionKind {
 	ClassCastException,
 	/// java.lang.InstantiationError
 	InstantiationError,
+	/// java.lang.InstantiationException
+	InstantiationException,
 
 	/// java.lang.LinkageError
 	LinkageError,
+	/// java.lang.ClassCircularityError
+	ClassCircularityError,
 	/// java.lang.UnsatisfiedLinkError
 	UnsatisfiedLinkError,
 	/// java.lang.IncompatibleClassChangeError
@@ -133,10 +138,15 @@ pub enum ExceptionKind {
 	/// java.lang.AbstractMethodError
 	AbstractMethodError,
 
+	/// java.lang.ArithmeticException
+	ArithmeticException,
+
 	/// java.lang.NegativeArraySizeException
 	NegativeArraySizeException,
 	/// java.lang.ArrayIndexOutOfBoundsException
 	ArrayIndexOutOfBoundsException,
+	/// java.lang.ArrayStoreException
+	ArrayStoreException,
 
 	/// java.lang.StringIndexOutOfBoundsException
 	StringIndexOutOfBoundsException,
@@ -165,6 +175,8 @@ pub enum ExceptionKind {
 	OutOfMemoryError,
 	/// java.lang.StackOverflowError
 	StackOverflowError,
+	/// java.lang.SecurityException
+	SecurityException,
 
 	/// java.io.IOException
 	IOException,
@@ -190,8 +202,10 @@ impl ExceptionKind {
 			ExceptionKind::ClassNotFoundException => sym!(java_lang_ClassNotFoundException),
 			ExceptionKind::ClassCastException => sym!(java_lang_ClassCastException),
 			ExceptionKind::InstantiationError => sym!(java_lang_InstantiationError),
+			ExceptionKind::InstantiationException => sym!(java_lang_InstantiationException),
 
 			ExceptionKind::LinkageError => sym!(java_lang_LinkageError),
+			ExceptionKind::ClassCircularityError => sym!(java_lang_ClassCircularityError),
 			ExceptionKind::UnsatisfiedLinkError => sym!(java_lang_UnsatisfiedLinkError),
 			ExceptionKind::IncompatibleClassChangeError => {
 				sym!(java_lang_IncompatibleClassChangeError)
@@ -200,10 +214,13 @@ impl ExceptionKind {
 			ExceptionKind::NoSuchMethodError => sym!(java_lang_NoSuchMethodError),
 			ExceptionKind::AbstractMethodError => sym!(java_lang_AbstractMethodError),
 
+			ExceptionKind::ArithmeticException => sym!(java_lang_ArithmeticException),
+
 			ExceptionKind::NegativeArraySizeException => sym!(java_lang_NegativeArraySizeException),
 			ExceptionKind::ArrayIndexOutOfBoundsException => {
 				sym!(java_lang_ArrayIndexOutOfBoundsException)
 			},
+			ExceptionKind::ArrayStoreException => sym!(java_lang_ArrayStoreException),
 
 			ExceptionKind::StringIndexOutOfBoundsException => {
 				sym!(java_lang_StringIndexOutOfBoundsException)
@@ -226,6 +243,7 @@ impl ExceptionKind {
 			},
 			ExceptionKind::OutOfMemoryError => sym!(java_lang_OutOfMemoryError),
 			ExceptionKind::StackOverflowError => sym!(java_lang_StackOverflowError),
+			ExceptionKind::SecurityException => sym!(java_lang_SecurityException),
 
 			ExceptionKind::IOException => sym!(java_io_IOException),
 
@@ -289,9 +307,33 @@ impl Exception {
 			return;
 		}
 
-		let this = Reference::class(ClassInstance::new(
-			self.kind.class().expect("class should be loaded"),
-		));
+		match self.construct(thread) {
+			Throws::Ok(exception) => thread.set_pending_exception(exception),
+			// The heap was exhausted before the `OutOfMemoryError` could be preallocated. There's
+			// nothing to throw, so like Hotspot, the VM just exits.
+			Throws::Exception(e) if e.kind != ExceptionKind::PendingException => {
+				crate::initialization::abort_during_initialization(&e)
+			},
+			Throws::Exception(_) => {},
+		}
+	}
+
+	/// Construct the Java object for this exception
+	///
+	/// If there isn't enough memory left to construct it, the preallocated `OutOfMemoryError` is
+	/// returned instead. See [`out_of_memory::preallocated()`].
+	///
+	/// If the constructor threw, [`Throws::PENDING_EXCEPTION`] is returned and that exception is
+	/// now pending on `thread`.
+	pub fn construct(self, thread: &'static JavaThread) -> Throws<Reference> {
+		assert_ne!(self.kind, ExceptionKind::PendingException);
+
+		let Throws::Ok(instance) =
+			ClassInstance::new(self.kind.class().expect("class should be loaded"))
+		else {
+			return out_of_memory::preallocated();
+		};
+		let this = Reference::class(instance);
 
 		match self.message {
 			Some(message) => {
@@ -304,7 +346,9 @@ impl Exception {
 					)
 					.expect("method should exist");
 
-				let string_object = StringInterner::intern(message.as_str());
+				let Throws::Ok(string_object) = StringInterner::intern(message.as_str()) else {
+					return out_of_memory::preallocated();
+				};
 				java_call!(
 					thread,
 					init_method,
@@ -326,7 +370,11 @@ impl Exception {
 			},
 		}
 
-		thread.set_pending_exception(this);
+		if thread.has_pending_exception() {
+			return Throws::PENDING_EXCEPTION;
+		}
+
+		Throws::Ok(this)
 	}
 }
 
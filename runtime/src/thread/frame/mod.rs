@@ -357,6 +357,14 @@ impl Frame {
 		self.thread().stack().absolute(offset)
 	}
 
+	/// Get a copy of all of the local variables of this frame
+	pub fn locals(&self) -> Vec<Operand<Reference>> {
+		self.thread()
+			.stack()
+			.slice(self.locals_base, self.method.code.max_locals as usize)
+			.to_vec()
+	}
+
 	/// Set the operand at `index` to `op`
 	pub fn set_local(&self, index: u1, op: Operand<Reference>) {
 		self.verify_local_index(u2::from(index));
@@ -0,0 +1,346 @@
+//! Thread handshakes
+//!
+//! A handshake is an operation that one thread performs against another, while the target is
+//! guaranteed to not be modifying its own frame stack. This is needed for anything that inspects
+//! another thread's execution state, such as stack traces and thread dumps.
+//!
+//! A thread is either:
+//!
+//! * In a *safe region*, meaning it isn't executing Java code (e.g. it's in native code or blocked
+//!   on a monitor). Operations are run directly by the requesting thread, and the target is kept
+//!   from leaving the safe region until the operation completes.
+//! * Executing Java code, in which case the operation is left pending, and the target runs it
+//!   the next time it polls (between instructions).
+//!
+//! When the VM is going down, threads can also be [stopped](JavaThread::stop_at_safe_point), which
+//! parks them for good the next time they poll or try to leave a safe region.
+
+use super::JavaThread;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+
+type Operation = dyn FnMut(&'static JavaThread);
+
+/// An operation submitted by another thread
+///
+/// The submitting thread is blocked until the operation completes, so the lifetime of the
+/// operation is erased.
+struct PendingOperation(*mut Operation);
+
+// SAFETY: The operation is only ever run while the submitting thread is blocked on it
+unsafe impl Send for PendingOperation {}
+
+impl PendingOperation {
+	fn is(&self, other: *mut Operation) -> bool {
+		self.0.cast::<()>() == other.cast::<()>()
+	}
+}
+
+struct HandshakeInner {
+	pending: Option<PendingOperation>,
+	safe: bool,
+	stopped: bool,
+}
+
+pub(super) struct HandshakeState {
+	inner: Mutex<HandshakeInner>,
+	completed: Condvar,
+	armed: AtomicBool,
+}
+
+impl HandshakeState {
+	pub(super) fn new() -> Self {
+		Self {
+			inner: Mutex::new(HandshakeInner {
+				pending: None,
+				// A thread isn't running any Java code until it makes a call
+				safe: true,
+				stopped: false,
+			}),
+			completed: Condvar::new(),
+			armed: AtomicBool::new(false),
+		}
+	}
+
+	/// Whether there is an operation waiting for this thread to poll
+	#[inline]
+	pub(super) fn is_armed(&self) -> bool {
+		self.armed.load(Ordering::Acquire)
+	}
+
+	/// Run the pending operation, if any
+	///
+	/// This is only to be called by the thread owning this state.
+	pub(super) fn process(&self, thread: &'static JavaThread) {
+		let mut inner = self.inner.lock().unwrap();
+		self.run_pending(&mut inner, thread);
+
+		if inner.stopped {
+			// Other threads are still free to inspect us
+			inner.safe = true;
+			drop(inner);
+			park_forever();
+		}
+	}
+
+	/// Mark the owning thread as being in (or out of) a safe region, returning the previous state
+	///
+	/// Leaving a safe region will block if another thread is currently running an operation
+	/// against this one.
+	pub(super) fn set_safe(&self, thread: &'static JavaThread, safe: bool) -> bool {
+		let mut inner = self.inner.lock().unwrap();
+		if safe {
+			// Nobody else can run this now that we're going to be unavailable to poll
+			self.run_pending(&mut inner, thread);
+		} else if inner.stopped {
+			drop(inner);
+			park_forever();
+		}
+
+		std::mem::replace(&mut inner.safe, safe)
+	}
+
+	/// Stop the owning thread the next time it polls or leaves a safe region
+	fn stop(&self) {
+		let mut inner = self.inner.lock().unwrap();
+		inner.stopped = true;
+		self.armed.store(true, Ordering::Release);
+	}
+
+	/// Run `op` against `target`, blocking until it completes
+	pub(super) fn execute(
+		&self,
+		target: &'static JavaThread,
+		op: &mut (dyn FnMut(&'static JavaThread) + '_),
+	) {
+		let mut inner = self.inner.lock().unwrap();
+
+		// Only a single operation can be pending at a time
+		while inner.pending.is_some() && !inner.safe {
+			inner = self.completed.wait(inner).unwrap();
+		}
+
+		if inner.safe {
+			// The target can't leave the safe region while we hold the lock
+			op(target);
+			return;
+		}
+
+		// SAFETY: We don't return until the operation is run, so the lifetime can be erased
+		#[allow(clippy::transmute_ptr_to_ptr)] // An `as` cast can't extend the lifetime
+		let op = unsafe {
+			std::mem::transmute::<*mut (dyn FnMut(&'static JavaThread) + '_), *mut Operation>(op)
+		};
+		inner.pending = Some(PendingOperation(op));
+		self.armed.store(true, Ordering::Release);
+
+		while inner.pending.as_ref().is_some_and(|pending| pending.is(op)) {
+			inner = self.completed.wait(inner).unwrap();
+		}
+	}
+
+	fn run_pending(&self, inner: &mut HandshakeInner, thread: &'static JavaThread) {
+		let Some(PendingOperation(op)) = inner.pending.take() else {
+			return;
+		};
+
+		// SAFETY: The submitting thread is blocked until we notify it
+		unsafe { (*op)(thread) };
+
+		self.armed.store(false, Ordering::Release);
+		self.completed.notify_all();
+	}
+}
+
+fn park_forever() -> ! {
+	loop {
+		std::thread::park();
+	}
+}
+
+/// A guard that restores the previous safe region state of a thread when dropped
+///
+/// See [`JavaThread::enter_safe_region()`].
+pub struct SafeRegionGuard {
+	thread: &'static JavaThread,
+	previous: bool,
+}
+
+impl Drop for SafeRegionGuard {
+	fn drop(&mut self) {
+		self.thread.handshake.set_safe(self.thread, self.previous);
+	}
+}
+
+impl JavaThread {
+	/// Mark this thread as being in a safe region until the returned guard is dropped
+	///
+	/// While in a safe region, other threads are free to inspect this thread's frame stack. This
+	/// **must** only be called from this thread.
+	pub fn enter_safe_region(&'static self) -> SafeRegionGuard {
+		let previous = self.handshake.set_safe(self, true);
+		SafeRegionGuard {
+			thread: self,
+			previous,
+		}
+	}
+
+	/// Mark this thread as executing Java code until the returned guard is dropped
+	pub(super) fn leave_safe_region(&'static self) -> SafeRegionGuard {
+		let previous = self.handshake.set_safe(self, false);
+		SafeRegionGuard {
+			thread: self,
+			previous,
+		}
+	}
+
+	/// Run any operation that other threads have submitted against this thread
+	#[inline]
+	pub(super) fn poll_handshake(&'static self) {
+		if self.handshake.is_armed() {
+			self.handshake.process(self);
+		}
+	}
+
+	/// Stop this thread the next time it reaches a safe point
+	///
+	/// The thread will never run any more Java code. This is only used when the VM is exiting.
+	pub(crate) fn stop_at_safe_point(&self) {
+		self.handshake.stop();
+	}
+
+	/// Run `op` against this thread while it is at a safe point
+	///
+	/// If this is the current thread, `op` is run immediately. Otherwise, the calling thread will
+	/// block until `op` completes.
+	pub fn handshake<F, R>(&'static self, op: F) -> R
+	where
+		F: FnOnce(&'static JavaThread) -> R,
+	{
+		let current = JavaThread::current_opt();
+		if current == Some(self) {
+			return op(self);
+		}
+
+		let mut op = Some(op);
+		let mut ret = None;
+		let mut run = |target| {
+			let op = op
+				.take()
+				.expect("handshake operations should only run once");
+			ret = Some(op(target));
+		};
+
+		// Other threads may want to handshake *us* while we're waiting
+		let _safe_region = current.map(JavaThread::enter_safe_region);
+		self.handshake.execute(self, &mut run);
+
+		ret.expect("handshake operation should have completed")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::thread::{JavaThread, JavaThreadBuilder};
+
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use std::sync::{Arc, mpsc};
+	use std::time::Duration;
+
+	fn new_thread() -> &'static JavaThread {
+		JavaThreadBuilder::new()
+			.finish(false)
+			.expect("failed to allocate thread")
+	}
+
+	#[test]
+	fn handshake_parked_thread() {
+		let target = new_thread();
+
+		let (ready_tx, ready_rx) = mpsc::channel();
+		let worker = std::thread::spawn(move || {
+			let _running = target.leave_safe_region();
+
+			// Parking always happens in a safe region
+			let _safe = target.enter_safe_region();
+			ready_tx.send(()).unwrap();
+			std::thread::park();
+		});
+
+		ready_rx.recv().unwrap();
+
+		// The target is parked, so the operation runs on this thread
+		let current = std::thread::current().id();
+		let ran_on = target.handshake(|thread| {
+			assert!(std::ptr::eq(thread, target));
+			std::thread::current().id()
+		});
+		assert_eq!(ran_on, current);
+
+		worker.thread().unpark();
+		worker.join().unwrap();
+	}
+
+	#[test]
+	fn handshake_blocks_leaving_safe_region() {
+		let target = new_thread();
+		let left = Arc::new(AtomicBool::new(false));
+
+		let (ready_tx, ready_rx) = mpsc::channel();
+		let (release_tx, release_rx) = mpsc::channel::<()>();
+		let worker = std::thread::spawn({
+			let left = Arc::clone(&left);
+			move || {
+				let _running = target.leave_safe_region();
+				{
+					let _safe = target.enter_safe_region();
+					ready_tx.send(()).unwrap();
+					release_rx.recv().unwrap();
+				}
+
+				left.store(true, Ordering::SeqCst);
+			}
+		});
+
+		ready_rx.recv().unwrap();
+		target.handshake(|_| {
+			// Let the target try to leave the safe region, it has to wait for us to finish
+			release_tx.send(()).unwrap();
+			std::thread::sleep(Duration::from_millis(50));
+			assert!(!left.load(Ordering::SeqCst));
+		});
+
+		worker.join().unwrap();
+		assert!(left.load(Ordering::SeqCst));
+	}
+
+	#[test]
+	fn handshake_polling_thread() {
+		let target = new_thread();
+		let done = Arc::new(AtomicBool::new(false));
+
+		let (ready_tx, ready_rx) = mpsc::channel();
+		let worker = std::thread::spawn({
+			let done = Arc::clone(&done);
+			move || {
+				// Pretend to be executing Java code, which polls between instructions
+				let _running = target.leave_safe_region();
+				ready_tx.send(std::thread::current().id()).unwrap();
+				while !done.load(Ordering::SeqCst) {
+					target.poll_handshake();
+					std::thread::yield_now();
+				}
+			}
+		});
+
+		let worker_id = ready_rx.recv().unwrap();
+
+		// The target is busy, so the operation has to wait for it to poll
+		let ran_on = target.handshake(|_| std::thread::current().id());
+		assert_eq!(ran_on, worker_id);
+
+		done.store(true, Ordering::SeqCst);
+		worker.join().unwrap();
+	}
+}
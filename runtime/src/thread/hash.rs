@@ -1,4 +1,95 @@
+//! Identity hash code generation
+//!
+//! The algorithm is selected with `-XX:hashCode`, matching HotSpot:
+//!
+//! | Value | Algorithm                                                   |
+//! |-------|-------------------------------------------------------------|
+//! | 0     | A global Park-Miller random number generator                |
+//! | 1     | The object address, mixed with a random value               |
+//! | 2     | Always `1` (for sensitivity testing)                        |
+//! | 3     | A global sequence                                           |
+//! | 4     | The object address                                          |
+//! | 5     | Marsaglia's xor-shift scheme, with thread-specific state    |
+//!
+//! All of these are deterministic for a given run, provided threads are created in the same order.
+//!
+//! See <https://github.com/openjdk/jdk/blob/807f6f7fb868240cba5ba117c7059216f69a53f9/src/hotspot/share/runtime/synchronizer.cpp#L935>
+
 use super::JavaThread;
+use crate::options::JvmOptions;
+
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use jni::sys::jint;
+
+/// The algorithm used to generate identity hash codes (`-XX:hashCode`)
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub enum HashCodeMode {
+	OsRandom,
+	AddressBased,
+	Constant,
+	Sequence,
+	Address,
+	#[default]
+	Marsaglia,
+}
+
+impl HashCodeMode {
+	/// Get the mode for a `-XX:hashCode` value
+	pub fn from_value(value: u8) -> Option<Self> {
+		match value {
+			0 => Some(Self::OsRandom),
+			1 => Some(Self::AddressBased),
+			2 => Some(Self::Constant),
+			3 => Some(Self::Sequence),
+			4 => Some(Self::Address),
+			5 => Some(Self::Marsaglia),
+			_ => None,
+		}
+	}
+}
+
+/// Only the low 31 bits of a hash are kept, as in HotSpot's `markWord::hash_mask`
+const HASH_MASK: u32 = 0x7FFF_FFFF;
+
+/// Substituted for hashes of 0, since a 0 hash means that no hash has been generated yet
+const ZERO_HASH_REPLACEMENT: u32 = 0xBAD;
+
+/// Convert the raw output of one of the algorithms to an identity hash code
+pub(crate) fn finish_hash(value: u32) -> jint {
+	match value & HASH_MASK {
+		0 => ZERO_HASH_REPLACEMENT as jint,
+		hash => hash as jint,
+	}
+}
+
+static RANDOM_SEED: AtomicU32 = AtomicU32::new(1_234_567);
+
+/// The next value of the global Park-Miller random number generator, as in HotSpot's `os::random()`
+pub(crate) fn os_random() -> u32 {
+	fn next_random(seed: u32) -> u32 {
+		// Minimal standard generator, with a multiplier of 16807 and a modulus of 2^31 - 1
+		const A: u64 = 16807;
+		const M: u64 = 2_147_483_647;
+		((u64::from(seed) * A) % M) as u32
+	}
+
+	let previous = RANDOM_SEED
+		.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |seed| {
+			Some(next_random(seed))
+		})
+		.expect("the update always succeeds");
+	next_random(previous)
+}
+
+/// Mixed into address based hashes
+///
+/// HotSpot changes this on every safepoint, which would make the hashes unstable here, as objects are
+/// never moved.
+static STW_RANDOM: LazyLock<u32> = LazyLock::new(os_random);
+
+static HASH_SEQUENCE: AtomicU32 = AtomicU32::new(0);
 
 #[derive(Copy, Clone, Debug)]
 pub struct HashState {
@@ -13,7 +104,7 @@ impl HashState {
 	const INIT_STATE_Z: u32 = 0x8767; // (int)(3579807591LL & 0xffff);
 	const INIT_STATE_W: u32 = 273_326_509;
 
-	pub(super) fn new(seed: u32) -> Self {
+	pub(crate) fn new(seed: u32) -> Self {
 		HashState {
 			state_x: seed,
 			state_y: Self::INIT_STATE_Y,
@@ -21,25 +112,48 @@ impl HashState {
 			state_w: Self::INIT_STATE_W,
 		}
 	}
+
+	/// Advance the state, returning the next value of the sequence
+	pub(crate) fn next(&mut self) -> u32 {
+		let mut t = self.state_x;
+		t ^= t << 11;
+
+		let mut v = self.state_w;
+		v = (v ^ (v >> 19)) ^ (t ^ (t >> 8));
+
+		self.state_x = self.state_y;
+		self.state_y = self.state_z;
+		self.state_z = self.state_w;
+		self.state_w = v;
+
+		v
+	}
 }
 
 impl JavaThread {
 	pub fn marsaglia_xor_shift_hash(&self) -> u32 {
-		let hash_state = self.hash_state.get();
-		let mut t = hash_state.state_x;
-		t ^= t << 11;
+		let mut hash_state = self.hash_state.get();
+		let ret = hash_state.next();
+		self.hash_state.set(hash_state);
 
-		let mut v = hash_state.state_w;
-		v = (v ^ (v >> 19)) ^ (t ^ (t >> 8));
+		ret
+	}
 
-		let new_state = HashState {
-			state_x: hash_state.state_y,
-			state_y: hash_state.state_z,
-			state_z: hash_state.state_w,
-			state_w: v,
+	/// Generate a new identity hash code for the object at `address`, using the algorithm selected
+	/// by `-XX:hashCode`
+	pub fn next_identity_hash(&self, address: usize) -> jint {
+		let value = match JvmOptions::get().hash_code {
+			HashCodeMode::OsRandom => os_random(),
+			HashCodeMode::AddressBased => {
+				let address_bits = address >> 3;
+				(address_bits ^ (address_bits >> 5)) as u32 ^ *STW_RANDOM
+			},
+			HashCodeMode::Constant => 1,
+			HashCodeMode::Sequence => HASH_SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1,
+			HashCodeMode::Address => address as u32,
+			HashCodeMode::Marsaglia => self.marsaglia_xor_shift_hash(),
 		};
-		self.hash_state.set(new_state);
 
-		v
+		finish_hash(value)
 	}
 }
@@ -3,13 +3,18 @@ pub mod frame;
 use frame::stack::{FrameStack, StackFrame};
 mod builder;
 pub use builder::JavaThreadBuilder;
-mod hash;
+pub mod dump;
+mod handshake;
+pub use handshake::SafeRegionGuard;
+pub(crate) mod hash;
 pub mod pool;
+pub(crate) mod signal_dispatcher;
 pub mod stack;
 
 use crate::classes::java::lang::Thread::ThreadStatus;
 use crate::interpreter::Interpreter;
 use crate::logging::info;
+use crate::native::intrinsics::{self, IntrinsicEntry};
 use crate::native::java::lang::String::StringInterner;
 use crate::native::jni::IntoJni;
 use crate::native::jni::invocation_api::new_env;
@@ -17,6 +22,7 @@ use crate::native::method::NativeMethodPtr;
 use crate::objects::instance::class::ClassInstance;
 use crate::objects::instance::object::Object;
 use crate::objects::method::Method;
+use crate::objects::monitor::Monitor;
 use crate::objects::reference::Reference;
 use crate::stack::local_stack::LocalStack;
 use crate::symbols::sym;
@@ -28,14 +34,16 @@ use crate::{classes, enabled, globals, java_call};
 
 use std::cell::{Cell, UnsafeCell};
 use std::mem::MaybeUninit;
-use std::sync::atomic::{AtomicIsize, AtomicU8, Ordering};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicPtr, AtomicU8, Ordering};
 use std::thread::JoinHandle;
 
 use classfile::FieldType;
 use classfile::accessflags::MethodAccessFlags;
 use instructions::{Operand, StackLike};
 use jni::env::JniEnv;
-use jni::sys::JNIEnv;
+use jni::sys::{JNIEnv, jint};
+use platform::thread::NativeThreadId;
 
 #[thread_local]
 static CURRENT_JAVA_THREAD: Cell<Option<&'static JavaThread>> = Cell::new(None);
@@ -68,6 +76,12 @@ pub struct JavaThread {
 	env: JNIEnv,
 	obj: UnsafeCell<Option<Reference>>,
 	os_thread: UnsafeCell<Option<JoinHandle<()>>>,
+	// The size of the OS thread's stack in bytes, only used if the VM spawns the thread
+	stack_size: usize,
+	// Set once the thread is associated with an OS thread, see `JavaThread::set_current_thread()`
+	native_thread: OnceLock<NativeThreadId>,
+	// Whether the OS thread was spawned by the VM, rather than attached through JNI
+	started_by_vm: AtomicBool,
 
 	hash_state: Cell<hash::HashState>,
 
@@ -85,6 +99,12 @@ pub struct JavaThread {
 	pending_exception: UnsafeCell<Option<Reference>>,
 	state: AtomicU8,
 
+	handshake: handshake::HandshakeState,
+	// The monitor this thread is blocked trying to enter
+	contended_monitor: AtomicPtr<Monitor>,
+	// The monitor this thread is waiting on in `Object#wait`
+	waiting_monitor: AtomicPtr<Monitor>,
+
 	/// Used in tests to prevent this thread from actually running any Java code
 	#[cfg(test)]
 	pub sealed: AtomicBool,
@@ -104,13 +124,17 @@ impl PartialEq for JavaThread {
 impl JavaThread {
 	// Used in `JavaThreadBuilder::finish`
 	fn new(obj: Option<Reference>, stack_size: usize) -> Throws<Self> {
-		let seed = 1;
-		let operand_stack = UnsafeCell::new(ThreadStack::new(stack_size)?);
+		let seed = hash::os_random();
+		let operand_stack =
+			UnsafeCell::new(ThreadStack::new(stack_size / stack::BYTES_PER_STACK_SLOT)?);
 
 		Throws::Ok(JavaThread {
 			env: unsafe { new_env() },
 			obj: UnsafeCell::new(obj),
 			os_thread: UnsafeCell::new(None),
+			stack_size,
+			native_thread: OnceLock::new(),
+			started_by_vm: AtomicBool::new(false),
 
 			hash_state: Cell::new(hash::HashState::new(seed)),
 
@@ -124,6 +148,10 @@ impl JavaThread {
 			pending_exception: UnsafeCell::new(None),
 			state: AtomicU8::new(JavaThreadState::Running as u8),
 
+			handshake: handshake::HandshakeState::new(),
+			contended_monitor: AtomicPtr::new(std::ptr::null_mut()),
+			waiting_monitor: AtomicPtr::new(std::ptr::null_mut()),
+
 			#[cfg(test)]
 			sealed: AtomicBool::new(false),
 		})
@@ -181,6 +209,15 @@ impl JavaThread {
 			);
 			Some(thread)
 		});
+
+		let _ = thread.native_thread.set(NativeThreadId::current());
+	}
+
+	/// Get the ID of the OS thread associated with this `JavaThread`
+	///
+	/// This will return `None` if the thread hasn't started yet.
+	pub fn native_thread_id(&self) -> Option<NativeThreadId> {
+		self.native_thread.get().copied()
 	}
 
 	/// Remove the current [`JavaThread`] for this thread
@@ -206,26 +243,88 @@ impl JavaThread {
 impl JavaThread {
 	/// Spawn the thread and begin executing its Java code
 	///
-	/// This is used in [`JavaThreadBuilder::finish()`], which is called from the
-	/// JNI `AttachCurrentThread`. The `main` thread does NOT ever call this.
+	/// This is used in [`JavaThreadBuilder::finish()`], which is called from `JVM_StartThread`. The
+	/// `main` thread does NOT ever call this.
 	pub(crate) fn start(&'static self) {
-		let os_thread_ptr = self.os_thread.get();
-		let handle = std::thread::spawn(move || {
-			// Call `java.lang.Thread#run` with the obj associated with this `JavaThread`.
-			let obj = self.obj().expect("obj should exist");
+		self.start_with(Self::java_entry_point);
+	}
 
-			let thread_class = crate::globals::classes::java_lang_Thread();
-			let run_method = thread_class
-				.resolve_method_step_two(sym!(run_name), sym!(void_method_signature))
-				.unwrap();
+	/// Spawn the thread, running `entry_point` on it
+	///
+	/// See also: [`JavaThreadBuilder::entry_point()`]
+	pub(crate) fn start_with(&'static self, entry_point: fn(&'static JavaThread)) {
+		self.started_by_vm.store(true, Ordering::Release);
 
-			java_call!(self, run_method, Operand::Reference(obj));
-		});
+		let os_thread_ptr = self.os_thread.get();
+		let handle = std::thread::Builder::new()
+			.stack_size(self.stack_size)
+			.spawn(move || {
+				JavaThread::set_current_thread(self);
+				platform::thread::set_current_thread_name(&self.name());
+				if let Some(obj) = self.obj() {
+					let holder = classes::java::lang::Thread::holder(obj.extract_class());
+					let priority =
+						classes::java::lang::Thread::holder::priority(holder.extract_class());
+
+					// Leave the OS default alone (and any niceness inherited from the process)
+					// unless the priority was explicitly changed
+					if priority != classes::java::lang::Thread::NORM_PRIORITY {
+						self.set_native_priority(priority);
+					}
+				}
+
+				entry_point(self);
+				self.exit(false);
+			})
+			.expect("failed to spawn OS thread"); // TODO: Should throw OutOfMemoryError
 		unsafe {
 			*os_thread_ptr = Some(handle);
 		}
 	}
 
+	/// Rename the OS thread associated with this `JavaThread`
+	///
+	/// Like Hotspot, this is only supported on the current thread, and only if it was spawned by the
+	/// VM. Threads attached through JNI (including `main`) belong to the embedder, and are left alone.
+	pub fn set_native_name(&self, name: &str) {
+		if JavaThread::current_opt() != Some(self) || !self.started_by_vm.load(Ordering::Acquire) {
+			return;
+		}
+
+		platform::thread::set_current_thread_name(name);
+	}
+
+	/// Apply a Java thread priority (`1..=10`) to the OS thread associated with this `JavaThread`
+	///
+	/// This is best effort, some priorities are likely to require elevated privileges. Any failures
+	/// are ignored, as they are in Hotspot.
+	pub fn set_native_priority(&self, priority: jint) {
+		let Some(native_thread) = self.native_thread_id() else {
+			// Not started yet, the priority will be applied once it is
+			return;
+		};
+
+		if let Err(e) = platform::thread::set_priority(native_thread, priority) {
+			info!(
+				TARGETS: (Os, Thread),
+				"Failed to set the native priority of thread \"{}\": {e}",
+				self.name()
+			);
+		}
+	}
+
+	/// Call `java.lang.Thread#run` with the obj associated with this `JavaThread`
+	fn java_entry_point(&'static self) {
+		let obj = self.obj().expect("obj should exist");
+
+		let thread_class = crate::globals::classes::java_lang_Thread();
+		let run_method = thread_class
+			.resolve_method_step_two(sym!(run_name), sym!(void_method_signature))
+			.unwrap();
+
+		java_call!(self, run_method, Operand::Reference(obj));
+	}
+
 	/// Allocates a new `java.lang.Thread` for this `JavaThread`
 	///
 	/// This is called from the JNI `AttachCurrentThread`/`AttachCurrentThreadAsDaemon`.
@@ -234,9 +333,9 @@ impl JavaThread {
 		name: Option<&str>,
 		thread_group: Reference,
 		daemon: bool,
-	) {
+	) -> Throws<()> {
 		let thread_class = crate::globals::classes::java_lang_Thread();
-		let thread_instance = Reference::class(ClassInstance::new(thread_class));
+		let thread_instance = Reference::class(ClassInstance::new(thread_class)?);
 
 		// Set the obj early, since the java.lang.Thread constructor calls Thread#current.
 		self.set_obj(thread_instance);
@@ -244,11 +343,11 @@ impl JavaThread {
 
 		classes::java::lang::Thread::set_eetop(
 			obj.extract_class(),
-			JavaThread::current_ptr() as jni::sys::jlong,
+			std::ptr::from_ref(self) as jni::sys::jlong,
 		);
 
 		if let Some(name) = name {
-			let name_obj = StringInterner::intern(name);
+			let name_obj = StringInterner::intern(name)?;
 			let init_method = thread_class
 				.vtable()
 				.find(
@@ -284,15 +383,63 @@ impl JavaThread {
 			);
 		}
 
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
 		if daemon {
-			unimplemented!("jni::AttachCurrentThreadAsDaemon");
+			classes::java::lang::Thread::holder::set_daemon(holder.extract_class(), true);
 		}
 
-		let holder = classes::java::lang::Thread::holder(obj.extract_class());
 		classes::java::lang::Thread::holder::set_threadStatus(
 			holder.extract_class(),
 			ThreadStatus::Runnable,
 		);
+
+		Throws::Ok(())
+	}
+
+	/// Create and start a VM-internal daemon thread that runs `entry_point` instead of Java code
+	///
+	/// The thread is visible to Java as a member of the system thread group. Its `java.lang.Thread`
+	/// is constructed on `current`.
+	pub(crate) fn start_internal_daemon(
+		current: &'static JavaThread,
+		name: &str,
+		entry_point: fn(&'static JavaThread),
+	) -> Throws<&'static JavaThread> {
+		let thread_class = crate::globals::classes::java_lang_Thread();
+		let obj = Reference::class(ClassInstance::new(thread_class)?);
+
+		let init_method = thread_class
+			.vtable()
+			.find(
+				sym!(object_initializer_name),
+				sym!(ThreadGroup_String_void_signature),
+				MethodAccessFlags::NONE,
+			)
+			.expect("java.lang.Thread constructor should exist");
+
+		let name_obj = StringInterner::intern(name)?;
+		java_call!(
+			current,
+			init_method,
+			Operand::Reference(obj),
+			Operand::Reference(globals::threads::system_thread_group()),
+			Operand::Reference(Reference::class(name_obj)),
+		);
+
+		if current.has_pending_exception() {
+			return Throws::PENDING_EXCEPTION;
+		}
+
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
+		classes::java::lang::Thread::holder::set_daemon(holder.extract_class(), true);
+
+		let thread = JavaThreadBuilder::new()
+			.obj(obj)
+			.entry_point(entry_point)
+			.finish(true)?;
+
+		thread.set_thread_status(ThreadStatus::Runnable);
+		Throws::Ok(thread)
 	}
 
 	pub fn set_obj(&self, obj: Reference) {
@@ -327,6 +474,53 @@ impl JavaThread {
 
 		classes::java::lang::String::extract(name.extract_class())
 	}
+
+	/// Get the status of the associated `java.lang.Thread`
+	///
+	/// This will return `None` if the `java.lang.Thread` isn't fully constructed yet.
+	pub fn thread_status(&self) -> Option<ThreadStatus> {
+		let obj = self.obj()?;
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
+		if holder.is_null() {
+			return None;
+		}
+
+		Some(classes::java::lang::Thread::holder::threadStatus(
+			holder.extract_class(),
+		))
+	}
+
+	/// Set the status of the associated `java.lang.Thread`
+	///
+	/// This does nothing if the `java.lang.Thread` isn't fully constructed yet.
+	pub fn set_thread_status(&self, status: ThreadStatus) {
+		let Some(obj) = self.obj() else {
+			return;
+		};
+
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
+		if holder.is_null() {
+			return;
+		}
+
+		classes::java::lang::Thread::holder::set_threadStatus(holder.extract_class(), status);
+	}
+
+	/// Whether the associated `java.lang.Thread` is a daemon thread
+	///
+	/// This will return `false` if the `java.lang.Thread` isn't fully constructed yet.
+	pub fn is_daemon(&self) -> bool {
+		let Some(obj) = self.obj() else {
+			return false;
+		};
+
+		let holder = classes::java::lang::Thread::holder(obj.extract_class());
+		if holder.is_null() {
+			return false;
+		}
+
+		classes::java::lang::Thread::holder::daemon(holder.extract_class())
+	}
 }
 
 impl JavaThread {
@@ -363,6 +557,26 @@ impl JavaThread {
 		self.state.store(state as u8, Ordering::Relaxed);
 	}
 
+	/// The monitor this thread is currently blocked trying to enter, if any
+	pub fn contended_monitor(&self) -> Option<&'static Monitor> {
+		// SAFETY: Monitors are never deallocated
+		unsafe { self.contended_monitor.load(Ordering::Acquire).as_ref() }
+	}
+
+	pub(crate) fn set_contended_monitor(&self, monitor: *mut Monitor) {
+		self.contended_monitor.store(monitor, Ordering::Release);
+	}
+
+	/// The monitor this thread is currently waiting on in `Object#wait`, if any
+	pub fn waiting_monitor(&self) -> Option<&'static Monitor> {
+		// SAFETY: Monitors are never deallocated
+		unsafe { self.waiting_monitor.load(Ordering::Acquire).as_ref() }
+	}
+
+	pub(crate) fn set_waiting_monitor(&self, monitor: *mut Monitor) {
+		self.waiting_monitor.store(monitor, Ordering::Release);
+	}
+
 	pub fn set_remaining_operand(&self, operand: Option<Operand<Reference>>) {
 		let remaining_operand_ptr = self.remaining_operand.get();
 
@@ -397,6 +611,9 @@ impl JavaThread {
 			unimplemented!("Manual invocation of native methods");
 		}
 
+		// We may have been called from native code, other threads need to wait for us to poll now
+		let _java_region = self.leave_safe_region();
+
 		self.stash_and_reset_pc();
 
 		self.frame_stack.push(StackFrame::Fake);
@@ -405,6 +622,13 @@ impl JavaThread {
 		loop {
 			match self.control_flow() {
 				ControlFlow::Continue => {
+					self.poll_handshake();
+
+					// A handshake may have installed an asynchronous exception
+					if !matches!(self.control_flow(), ControlFlow::Continue) {
+						continue;
+					}
+
 					if let Some(current_frame) = self.frame_stack.current() {
 						Interpreter::instruction(current_frame);
 						continue;
@@ -443,6 +667,11 @@ impl JavaThread {
 			return;
 		}
 
+		if let Some(intrinsic) = intrinsics::find_intrinsic(method, !method.is_static()) {
+			self.invoke_intrinsic(method, intrinsic);
+			return;
+		}
+
 		if method.is_native() {
 			self.invoke_native(method);
 			return;
@@ -459,6 +688,24 @@ impl JavaThread {
 		}
 	}
 
+	/// Call the Rust implementation of an intrinsic, without pushing a new frame
+	fn invoke_intrinsic(&'static self, method: &'static Method, intrinsic: &IntrinsicEntry) {
+		// + 1 for receiver
+		let parameter_count =
+			method.parameter_count() as usize + if method.is_static() { 0 } else { 1 };
+
+		let mut args = self.stack().popn(parameter_count);
+		args.retain(|arg| !matches!(arg, Operand::Empty));
+
+		match intrinsic.invoke(self, &args) {
+			Throws::Ok(ret) => {
+				self.stash_and_reset_pc();
+				self.drop_to_previous_frame(ret, false);
+			},
+			Throws::Exception(e) => e.throw(self),
+		}
+	}
+
 	fn invoke_native(&'static self, method: &'static Method) {
 		// Try to lookup and set the method prior to calling
 		let fn_ptr;
@@ -484,6 +731,9 @@ impl JavaThread {
 		self.frame_stack
 			.push(StackFrame::Native(NativeFrame { method }));
 
+		// Our frame stack is stable until we return to Java, other threads are free to inspect it
+		let safe_region = self.enter_safe_region();
+
 		let ret;
 		match fn_ptr {
 			NativeMethodPtr::StaticInternal(func) => {
@@ -573,6 +823,8 @@ impl JavaThread {
 			},
 		}
 
+		drop(safe_region);
+
 		// There's a chance that the native frame was consumed while handling an exception, otherwise
 		// it should always be present.
 		let popped_native_frame = self.frame_stack.pop_native().is_some();
@@ -676,6 +928,15 @@ impl JavaThread {
 			holder.extract_class(),
 			ThreadStatus::Terminated,
 		);
+
+		// `Thread#isAlive` checks `eetop`, and `Thread#join` waits on the thread object until it is
+		// no longer alive.
+		obj.monitor_enter(self);
+		classes::java::lang::Thread::set_eetop(obj.extract_class(), 0);
+		let _ = obj.notify_all(self);
+		obj.monitor_exit(self);
+
+		pool::ThreadPool::notify_thread_exited();
 	}
 }
 
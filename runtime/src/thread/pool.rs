@@ -1,14 +1,17 @@
-use super::JavaThread;
+use super::{JavaThread, JavaThreadState};
 use crate::classes;
+use crate::classes::java::lang::Thread::ThreadStatus;
 use crate::objects::reference::Reference;
 
 use std::cell::SyncUnsafeCell;
 use std::collections::LinkedList;
-use std::sync::{LazyLock, Mutex};
+use std::sync::{Condvar, LazyLock, Mutex};
 
 static VM_THREAD_POOL: LazyLock<ThreadPool> = LazyLock::new(|| ThreadPool {
 	list: SyncUnsafeCell::new(LinkedList::new()),
 	write_mutex: Mutex::new(()),
+	exit_mutex: Mutex::new(()),
+	thread_exited: Condvar::new(),
 });
 
 pub struct ThreadPool {
@@ -18,6 +21,9 @@ pub struct ThreadPool {
 	// risk a realloc invalidating a reference.
 	list: SyncUnsafeCell<LinkedList<JavaThread>>,
 	write_mutex: Mutex<()>,
+	// Used to wait for threads to exit, see `ThreadPool::wait_for_non_daemon_threads()`
+	exit_mutex: Mutex<()>,
+	thread_exited: Condvar,
 }
 
 impl ThreadPool {
@@ -37,6 +43,44 @@ impl ThreadPool {
 		list.iter().any(|t| t.env == thread.env)
 	}
 
+	/// Get a snapshot of all threads that are currently alive
+	///
+	/// A thread is considered alive if it has an associated `java.lang.Thread` that has been started,
+	/// and it hasn't begun exiting.
+	pub fn live_threads() -> Vec<&'static JavaThread> {
+		let _guard = VM_THREAD_POOL.write_mutex.lock().unwrap();
+
+		let list = unsafe { &*VM_THREAD_POOL.list.get() };
+		list.iter()
+			.filter(|thread| thread.state() != JavaThreadState::Exiting)
+			.filter(|thread| thread.thread_status().is_some_and(ThreadStatus::is_alive))
+			.collect()
+	}
+
+	/// Block until `current` is the only non-daemon thread left alive
+	///
+	/// This is used in `DestroyJavaVM`, as the VM can't go down while any non-daemon threads are
+	/// still running.
+	pub fn wait_for_non_daemon_threads(current: &'static JavaThread) {
+		let _safe_region = current.enter_safe_region();
+
+		let mut guard = VM_THREAD_POOL.exit_mutex.lock().unwrap();
+		while Self::live_threads()
+			.into_iter()
+			.any(|thread| thread != current && !thread.is_daemon())
+		{
+			guard = VM_THREAD_POOL.thread_exited.wait(guard).unwrap();
+		}
+	}
+
+	/// Wake up anyone waiting in [`ThreadPool::wait_for_non_daemon_threads()`]
+	///
+	/// This must be called *after* the exiting thread is no longer considered alive.
+	pub(super) fn notify_thread_exited() {
+		let _guard = VM_THREAD_POOL.exit_mutex.lock().unwrap();
+		VM_THREAD_POOL.thread_exited.notify_all();
+	}
+
 	/// Find the [`JavaThread`] associated with `obj`
 	///
 	/// This is the only safe way to relate `java.lang.Thread` objects to their internal [`JavaThread`]
@@ -53,8 +97,9 @@ impl ThreadPool {
 		//         one that we set.
 		let java_thread = unsafe { &*(eetop as *mut JavaThread) };
 
-		let current = JavaThread::current();
-		if java_thread == current {
+		if let Some(current) = JavaThread::current_opt()
+			&& java_thread == current
+		{
 			return Some(current);
 		}
 
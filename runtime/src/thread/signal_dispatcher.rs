@@ -0,0 +1,104 @@
+//! The "Signal Dispatcher" thread
+//!
+//! Signals delivered through the VM's handlers are only marked as pending (see
+//! [`Signal::notify()`]), since very little can be done safely in a signal handler. This thread
+//! waits for those signals and acts on them.
+//!
+//! `SIGQUIT` is handled by the VM itself, and prints a thread dump. Everything else is passed on to
+//! `jdk.internal.misc.Signal#dispatch`, which runs the Java handler on a new thread. That includes
+//! the shutdown signals, which `java.lang.Terminator` registers to run the shutdown hooks.
+//!
+//! With `-Xrs`, none of this is set up. See [`JvmOptions::reduce_signal_usage`].
+
+use super::{JavaThread, dump};
+use crate::classpath::loader::ClassLoader;
+use crate::java_call;
+use crate::options::JvmOptions;
+use crate::symbols::sym;
+use crate::thread::exceptions::Throws;
+
+use std::sync::OnceLock;
+
+use instructions::Operand;
+use platform::{Signal, SignalHandler};
+
+/// The signals that `java.lang.Terminator` uses to run the shutdown hooks
+const SHUTDOWN_SIGNALS: [&str; 3] = ["HUP", "INT", "TERM"];
+
+static THREAD_DUMP_SIGNAL: OnceLock<Option<Signal>> = OnceLock::new();
+
+/// The signal that triggers a thread dump, if the platform has one
+pub(crate) fn thread_dump_signal() -> Option<Signal> {
+	*THREAD_DUMP_SIGNAL.get_or_init(|| Signal::from_name("QUIT"))
+}
+
+/// Whether `signal` is one of the signals used to shut down the VM
+pub(crate) fn is_shutdown_signal(signal: Signal) -> bool {
+	SHUTDOWN_SIGNALS
+		.into_iter()
+		.filter_map(Signal::from_name)
+		.any(|shutdown_signal| shutdown_signal == signal)
+}
+
+/// Install the VM signal handlers and start the "Signal Dispatcher" thread
+///
+/// This does nothing with `-Xrs`.
+pub(crate) fn start(current: &'static JavaThread) -> Throws<()> {
+	if JvmOptions::get().reduce_signal_usage {
+		return Throws::Ok(());
+	}
+
+	if let Some(signal) = thread_dump_signal() {
+		// SAFETY: The user handler is valid for any signal
+		unsafe {
+			signal.install(SignalHandler::user_handler());
+		}
+	}
+
+	JavaThread::start_internal_daemon(current, "Signal Dispatcher", dispatch_signals)?;
+	Throws::Ok(())
+}
+
+fn dispatch_signals(thread: &'static JavaThread) {
+	let thread_dump_signal = thread_dump_signal();
+
+	loop {
+		let signal = Signal::wait_pending();
+
+		if Some(signal) == thread_dump_signal {
+			let mut stdout = std::io::stdout().lock();
+			let _ = dump::print_thread_dump(&mut stdout);
+			continue;
+		}
+
+		if let Throws::Exception(e) = dispatch_to_java(thread, signal) {
+			e.throw(thread);
+		}
+
+		// Same as Hotspot, the exception is reported and otherwise ignored
+		if let Some(exception) = thread.take_pending_exception() {
+			eprintln!(
+				"Exception {} occurred dispatching signal {} to handler - the VM may need to be \
+				 forcibly terminated",
+				exception.extract_target_class().external_name(),
+				signal.value()
+			);
+		}
+	}
+}
+
+/// Call `jdk.internal.misc.Signal#dispatch`, which runs the Java handler for `signal`
+fn dispatch_to_java(thread: &'static JavaThread, signal: Signal) -> Throws<()> {
+	let signal_class = ClassLoader::bootstrap().load(sym!(jdk_internal_misc_Signal))?;
+	signal_class.initialize(thread)?;
+
+	let dispatch_method =
+		signal_class.resolve_method(sym!(dispatch_name), sym!(int_void_signature))?;
+	java_call!(thread, dispatch_method, Operand::Int(signal.value()));
+
+	if thread.has_pending_exception() {
+		return Throws::PENDING_EXCEPTION;
+	}
+
+	Throws::Ok(())
+}
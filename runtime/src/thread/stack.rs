@@ -138,6 +138,13 @@ impl Debug for ThreadStackHandle {
 	}
 }
 
+/// The number of bytes of a thread's stack size (`-Xss`) budgeted for each [`ThreadStack`] slot
+///
+/// The interpreter recurses on the native stack for every Java call, so a slot is budgeted very
+/// generously. This ensures that a `StackOverflowError` is thrown well before the native stack
+/// actually overflows. With the default 1 MiB stack size, this comes out to 1024 slots.
+pub const BYTES_PER_STACK_SLOT: usize = 1024;
+
 /// The [operand stack] and [local stack] of a [`JavaThread`]
 ///
 /// [operand stack]: https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-2.html#jvms-2.6.2
@@ -0,0 +1,841 @@
+use crate::class::{ClassWriter, Flag, modifiers};
+use crate::constant_pool::check_name;
+use crate::descriptor::{self, java_name};
+
+use std::fmt::Write;
+
+use classfile::attribute::{
+	Annotation, Attribute, AttributeType, BootstrapMethods, ElementValue, ElementValueType,
+	InnerClasses, MethodParameters, Module, ParameterAnnotations, Record, TypeAnnotation,
+	TypeAnnotationTargetInfo, TypePathKind,
+};
+use common::int_types::{u1, u2};
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.6
+#[rustfmt::skip]
+const INNER_CLASS_FLAGS: &[Flag] = &[
+	(0x0001, "ACC_PUBLIC",     Some("public")),
+	(0x0002, "ACC_PRIVATE",    Some("private")),
+	(0x0004, "ACC_PROTECTED",  Some("protected")),
+	(0x0008, "ACC_STATIC",     Some("static")),
+	(0x0010, "ACC_FINAL",      Some("final")),
+	(0x0200, "ACC_INTERFACE",  None),
+	(0x0400, "ACC_ABSTRACT",   Some("abstract")),
+	(0x1000, "ACC_SYNTHETIC",  None),
+	(0x2000, "ACC_ANNOTATION", None),
+	(0x4000, "ACC_ENUM",       None),
+];
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.24
+#[rustfmt::skip]
+const PARAMETER_FLAGS: &[Flag] = &[
+	(0x0010, "ACC_FINAL",     Some("final")),
+	(0x1000, "ACC_SYNTHETIC", Some("synthetic")),
+	(0x8000, "ACC_MANDATED",  Some("mandated")),
+];
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.25
+#[rustfmt::skip]
+const MODULE_FLAGS: &[Flag] = &[
+	(0x0020, "ACC_OPEN",      Some("open")),
+	(0x1000, "ACC_SYNTHETIC", None),
+	(0x8000, "ACC_MANDATED",  None),
+];
+
+#[rustfmt::skip]
+const REQUIRES_FLAGS: &[Flag] = &[
+	(0x0020, "ACC_TRANSITIVE",   Some("transitive")),
+	(0x0040, "ACC_STATIC_PHASE", Some("static")),
+	(0x1000, "ACC_SYNTHETIC",    None),
+	(0x8000, "ACC_MANDATED",     None),
+];
+
+#[rustfmt::skip]
+const EXPORTS_FLAGS: &[Flag] = &[
+	(0x1000, "ACC_SYNTHETIC", None),
+	(0x8000, "ACC_MANDATED",  None),
+];
+
+/// The names of the flags set in `flags`, as they appear in the comments of the `Module` attribute
+fn module_flag_names(flags: u2, table: &[Flag]) -> String {
+	table
+		.iter()
+		.filter(|(flag, ..)| flags & flag != 0)
+		.flat_map(|(_, name, _)| [" ", name])
+		.collect()
+}
+
+// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.20-400
+fn target_type_name(target_type: u1) -> &'static str {
+	match target_type {
+		0x00 => "CLASS_TYPE_PARAMETER",
+		0x01 => "METHOD_TYPE_PARAMETER",
+		0x10 => "CLASS_EXTENDS",
+		0x11 => "CLASS_TYPE_PARAMETER_BOUND",
+		0x12 => "METHOD_TYPE_PARAMETER_BOUND",
+		0x13 => "FIELD",
+		0x14 => "METHOD_RETURN",
+		0x15 => "METHOD_RECEIVER",
+		0x16 => "METHOD_FORMAL_PARAMETER",
+		0x17 => "THROWS",
+		0x40 => "LOCAL_VARIABLE",
+		0x41 => "RESOURCE_VARIABLE",
+		0x42 => "EXCEPTION_PARAMETER",
+		0x43 => "INSTANCEOF",
+		0x44 => "NEW",
+		0x45 => "CONSTRUCTOR_REFERENCE",
+		0x46 => "METHOD_REFERENCE",
+		0x47 => "CAST",
+		0x48 => "CONSTRUCTOR_INVOCATION_TYPE_ARGUMENT",
+		0x49 => "METHOD_INVOCATION_TYPE_ARGUMENT",
+		0x4A => "CONSTRUCTOR_REFERENCE_TYPE_ARGUMENT",
+		0x4B => "METHOD_REFERENCE_TYPE_ARGUMENT",
+		_ => "UNKNOWN",
+	}
+}
+
+impl ClassWriter<'_> {
+	pub(crate) fn write_attribute(&mut self, attribute: &Attribute) {
+		match &attribute.info {
+			AttributeType::ConstantValue(constant_value) => {
+				let value = self.describe_constant(constant_value.constantvalue_index);
+				self.p.println(format_args!("ConstantValue: {value}"));
+			},
+			// Handled by `write_method`
+			AttributeType::Code(_) => {},
+			AttributeType::StackMapTable(table) => self.write_stack_map_table(table),
+			AttributeType::Exceptions(exceptions) => {
+				let exceptions = exceptions
+					.exception_index_table
+					.iter()
+					.map(|index| java_name(&self.class_name(*index)))
+					.collect::<Vec<_>>();
+
+				self.p.println("Exceptions:");
+				self.p.indent();
+				self.p
+					.println(format_args!("throws {}", exceptions.join(", ")));
+				self.p.dedent();
+			},
+			AttributeType::InnerClasses(inner_classes) => self.write_inner_classes(inner_classes),
+			AttributeType::EnclosingMethod(enclosing_method) => {
+				let mut comment = java_name(&self.class_name(enclosing_method.class_index));
+				if enclosing_method.method_index != 0 {
+					let (name, _) = self.name_and_type(enclosing_method.method_index);
+					comment.push('.');
+					comment.push_str(&name);
+				}
+
+				self.p.print(format_args!(
+					"EnclosingMethod: #{}.#{}",
+					enclosing_method.class_index, enclosing_method.method_index
+				));
+				self.p.comment(comment);
+			},
+			AttributeType::Synthetic => self.p.println("Synthetic: true"),
+			AttributeType::Signature(signature) => {
+				let signature_index = signature.signature_index;
+				let value = self.utf8(signature_index);
+				self.p.print(format_args!("Signature: #{signature_index}"));
+				self.p.comment(value);
+			},
+			AttributeType::SourceFile(source_file) => {
+				let source_file = self.utf8(source_file.sourcefile_index);
+				self.p
+					.println(format_args!("SourceFile: \"{source_file}\""));
+			},
+			AttributeType::SourceDebugExtension(extension) => {
+				self.p.println("SourceDebugExtension:");
+				self.p.indent();
+				for line in String::from_utf8_lossy(&extension.debug_extension).lines() {
+					self.p.println(line);
+				}
+				self.p.dedent();
+			},
+			AttributeType::LineNumberTable(table) => self.write_line_number_table(table),
+			AttributeType::LocalVariableTable(table) => self.write_local_variable_table(table),
+			AttributeType::LocalVariableTypeTable(table) => {
+				self.write_local_variable_type_table(table)
+			},
+			AttributeType::Deprecated => self.p.println("Deprecated: true"),
+			AttributeType::RuntimeVisibleAnnotations(annotations) => {
+				self.write_annotations("RuntimeVisibleAnnotations", &annotations.annotations);
+			},
+			AttributeType::RuntimeInvisibleAnnotations(annotations) => {
+				self.write_annotations("RuntimeInvisibleAnnotations", &annotations.annotations);
+			},
+			AttributeType::RuntimeVisibleParameterAnnotations(annotations) => {
+				self.write_parameter_annotations(
+					"RuntimeVisibleParameterAnnotations",
+					&annotations.annotations,
+				);
+			},
+			AttributeType::RuntimeInvisibleParameterAnnotations(annotations) => {
+				self.write_parameter_annotations(
+					"RuntimeInvisibleParameterAnnotations",
+					&annotations.annotations,
+				);
+			},
+			AttributeType::RuntimeVisibleTypeAnnotations(annotations) => {
+				self.write_type_annotations(
+					"RuntimeVisibleTypeAnnotations",
+					&annotations.annotations,
+				);
+			},
+			AttributeType::RuntimeInvisibleTypeAnnotations(annotations) => {
+				self.write_type_annotations(
+					"RuntimeInvisibleTypeAnnotations",
+					&annotations.annotations,
+				);
+			},
+			AttributeType::AnnotationDefault(annotation_default) => {
+				let value = &annotation_default.default_value;
+				self.p.println("AnnotationDefault:");
+				self.p.indent();
+				self.p
+					.println(format_args!("default_value: {}", raw_element_value(value)));
+				self.p.indent();
+				self.write_element_value(value);
+				self.p.newline();
+				self.p.dedent();
+				self.p.dedent();
+			},
+			AttributeType::BootstrapMethods(bootstrap_methods) => {
+				self.write_bootstrap_methods(bootstrap_methods)
+			},
+			AttributeType::MethodParameters(parameters) => self.write_method_parameters(parameters),
+			AttributeType::Module(module) => self.write_module(module),
+			AttributeType::ModulePackages(packages) => {
+				self.p.println("ModulePackages:");
+				self.p.indent();
+				for index in &packages.package_index {
+					let name = self.module_or_package_name(*index);
+					self.p.print(format_args!("#{index}"));
+					self.p.comment(name);
+				}
+				self.p.dedent();
+			},
+			AttributeType::ModuleMainClass(main_class) => {
+				let index = main_class.main_class_index;
+				let name = self.class_name(index);
+				self.p.print(format_args!("ModuleMainClass: #{index}"));
+				self.p.comment(name);
+			},
+			AttributeType::NestHost(nest_host) => {
+				let host = self.describe_constant(nest_host.host_class_index);
+				self.p.println(format_args!("NestHost: {host}"));
+			},
+			AttributeType::NestMembers(nest_members) => {
+				self.write_class_list("NestMembers", &nest_members.classes)
+			},
+			AttributeType::Record(record) => self.write_record(record),
+			AttributeType::PermittedSubclasses(permitted_subclasses) => {
+				self.write_class_list("PermittedSubclasses", &permitted_subclasses.classes)
+			},
+		}
+	}
+
+	fn write_class_list(&mut self, name: &str, classes: &[u2]) {
+		self.p.println(format_args!("{name}:"));
+		self.p.indent();
+		for index in classes {
+			let class_name = self.class_name(*index);
+			self.p.println(class_name);
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.6
+	fn write_inner_classes(&mut self, inner_classes: &InnerClasses) {
+		self.p.println("InnerClasses:");
+		self.p.indent();
+		for inner_class in &inner_classes.classes {
+			let flags = inner_class.inner_class_access_flags;
+
+			let mut modifiers = modifiers(flags, INNER_CLASS_FLAGS);
+			if flags & 0x0200 != 0 {
+				// Interfaces are implicitly abstract
+				modifiers.retain(|modifier| *modifier != "abstract");
+			}
+
+			let mut declaration = modifiers.join(" ");
+			if !declaration.is_empty() {
+				declaration.push(' ');
+			}
+
+			let mut comment = String::new();
+			if inner_class.inner_name_index != 0 {
+				let _ = write!(declaration, "#{}= ", inner_class.inner_name_index);
+				comment.push_str(&self.utf8(inner_class.inner_name_index));
+				comment.push('=');
+			}
+
+			let _ = write!(declaration, "#{}", inner_class.inner_class_info_index);
+			comment.push_str(&self.describe_constant(inner_class.inner_class_info_index));
+
+			if inner_class.outer_class_info_index != 0 {
+				let _ = write!(declaration, " of #{}", inner_class.outer_class_info_index);
+				comment.push_str(" of ");
+				comment.push_str(&self.describe_constant(inner_class.outer_class_info_index));
+			}
+
+			self.p.print(format_args!("{declaration};"));
+			self.p.comment(comment);
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.23
+	fn write_bootstrap_methods(&mut self, bootstrap_methods: &BootstrapMethods) {
+		self.p.println("BootstrapMethods:");
+		self.p.indent();
+		for (index, bootstrap_method) in bootstrap_methods.bootstrap_methods.iter().enumerate() {
+			let method_ref = bootstrap_method.bootstrap_method_ref;
+			let method = self.constant_value(method_ref);
+			self.p
+				.println(format_args!("{index}: #{method_ref} {method}"));
+
+			self.p.indent();
+			self.p.println("Method arguments:");
+			self.p.indent();
+			for argument in &bootstrap_method.bootstrap_arguments {
+				let value = self.constant_value(*argument);
+				self.p.println(format_args!("#{argument} {value}"));
+			}
+			self.p.dedent();
+			self.p.dedent();
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.24
+	fn write_method_parameters(&mut self, parameters: &MethodParameters) {
+		self.p.println("MethodParameters:");
+		self.p.indent();
+		self.p.println(format_args!("{:<30} Flags", "Name"));
+		for parameter in &parameters.parameters {
+			let name = match parameter.name_index {
+				0 => String::from("<no name>"),
+				index => self.utf8(index),
+			};
+
+			let flags = modifiers(parameter.access_flags, PARAMETER_FLAGS);
+			if flags.is_empty() {
+				self.p.println(name);
+			} else {
+				self.p
+					.println(format_args!("{name:<30} {}", flags.join(" ")));
+			}
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.30
+	fn write_record(&mut self, record: &Record) {
+		self.p.println("Record:");
+		self.p.indent();
+		for component in &record.components {
+			let descriptor = self.utf8(component.descriptor_index);
+			let name = self.utf8(component.name_index);
+			self.p.println(format_args!(
+				"{} {name};",
+				descriptor::field_type(&descriptor)
+			));
+
+			self.p.indent();
+			self.p.println(format_args!("descriptor: {descriptor}"));
+			for attribute in &component.attributes {
+				self.write_attribute(attribute);
+			}
+			self.p.dedent();
+			self.p.newline();
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.25
+	fn write_module(&mut self, module: &Module) {
+		self.p.println("Module:");
+		self.p.indent();
+
+		let name = check_name(&self.module_or_package_name(module.module_name_index));
+		self.p.print(format_args!(
+			"#{},{:x}",
+			module.module_name_index, module.module_flags
+		));
+		self.p.comment(format_args!(
+			"{name}{}",
+			module_flag_names(module.module_flags, MODULE_FLAGS)
+		));
+		self.write_module_version(module.module_version_index);
+
+		self.p.print(module.requires.len());
+		self.p.comment("requires");
+		self.p.indent();
+		for requires in &module.requires {
+			let name = check_name(&self.module_or_package_name(requires.requires_index));
+			self.p.print(format_args!(
+				"#{},{:x}",
+				requires.requires_index, requires.requires_flags
+			));
+			self.p.comment(format_args!(
+				"{name}{}",
+				module_flag_names(requires.requires_flags, REQUIRES_FLAGS)
+			));
+			self.write_module_version(requires.requires_version_index);
+		}
+		self.p.dedent();
+
+		self.p.print(module.exports.len());
+		self.p.comment("exports");
+		self.p.indent();
+		for exports in &module.exports {
+			self.write_module_package(
+				exports.exports_index,
+				exports.exports_flags,
+				&exports.exports_to_index,
+			);
+		}
+		self.p.dedent();
+
+		self.p.print(module.opens.len());
+		self.p.comment("opens");
+		self.p.indent();
+		for opens in &module.opens {
+			self.write_module_package(opens.opens_index, opens.opens_flags, &opens.opens_to_index);
+		}
+		self.p.dedent();
+
+		self.p.print(module.uses_index.len());
+		self.p.comment("uses");
+		self.p.indent();
+		for uses in &module.uses_index {
+			let name = self.class_name(*uses);
+			self.p.print(format_args!("#{uses}"));
+			self.p.comment(name);
+		}
+		self.p.dedent();
+
+		self.p.print(module.provides.len());
+		self.p.comment("provides");
+		self.p.indent();
+		for provides in &module.provides {
+			let service = self.class_name(provides.provides_index);
+			self.p.print(format_args!("#{}", provides.provides_index));
+			self.p.comment(format_args!(
+				"{service} with ... {}",
+				provides.provides_with_index.len()
+			));
+
+			self.p.indent();
+			for implementation in &provides.provides_with_index {
+				let name = self.class_name(*implementation);
+				self.p.print(format_args!("#{implementation}"));
+				self.p.comment(format_args!("... with {name}"));
+			}
+			self.p.dedent();
+		}
+		self.p.dedent();
+
+		self.p.dedent();
+	}
+
+	fn write_module_version(&mut self, version_index: u2) {
+		self.p.print(format_args!("#{version_index}"));
+		if version_index == 0 {
+			self.p.newline();
+			return;
+		}
+
+		let version = self.utf8(version_index);
+		self.p.comment(version);
+	}
+
+	/// Write an `exports` or `opens` entry of the `Module` attribute
+	fn write_module_package(&mut self, package_index: u2, flags: u2, to: &[u2]) {
+		let package = self.module_or_package_name(package_index);
+		self.p.print(format_args!("#{package_index},{flags:x}"));
+
+		let flag_names = module_flag_names(flags, EXPORTS_FLAGS);
+		if to.is_empty() {
+			self.p.comment(format_args!("{package}{flag_names}"));
+			return;
+		}
+
+		self.p
+			.comment(format_args!("{package}{flag_names} to ... {}", to.len()));
+		self.p.indent();
+		for module in to {
+			let name = check_name(&self.module_or_package_name(*module));
+			self.p.print(format_args!("#{module}"));
+			self.p.comment(format_args!("... to {name}"));
+		}
+		self.p.dedent();
+	}
+
+	/// Write the declaration of a `module-info` class
+	pub(crate) fn write_module_header(&mut self) {
+		let class = self.class;
+		let Some(module) = class.attributes.iter().find_map(Attribute::module) else {
+			self.p.print("module <missing Module attribute>");
+			if self.options.verbose {
+				self.p.newline();
+			}
+			return;
+		};
+
+		let mut declaration = modifiers(module.module_flags, MODULE_FLAGS).join(" ");
+		if !declaration.is_empty() {
+			declaration.push(' ');
+		}
+
+		declaration.push_str("module ");
+		declaration.push_str(&self.module_or_package_name(module.module_name_index));
+		if module.module_version_index != 0 {
+			declaration.push('@');
+			declaration.push_str(&self.utf8(module.module_version_index));
+		}
+
+		self.p.print(declaration);
+		if self.options.verbose {
+			self.p.newline();
+		}
+	}
+
+	/// Write the directives of a `module-info` class, as they would appear in source form
+	pub(crate) fn write_module_directives(&mut self) {
+		let class = self.class;
+		let Some(module) = class.attributes.iter().find_map(Attribute::module) else {
+			return;
+		};
+
+		self.p.indent();
+
+		for requires in &module.requires {
+			let mut directive = vec!["requires"];
+			directive.extend(modifiers(requires.requires_flags, REQUIRES_FLAGS));
+
+			let name = self.module_or_package_name(requires.requires_index);
+			self.p
+				.println(format_args!("{} {name};", directive.join(" ")));
+		}
+
+		for exports in &module.exports {
+			self.write_package_directive(
+				"exports",
+				exports.exports_index,
+				&exports.exports_to_index,
+			);
+		}
+
+		for opens in &module.opens {
+			self.write_package_directive("opens", opens.opens_index, &opens.opens_to_index);
+		}
+
+		for uses in &module.uses_index {
+			let name = java_name(&self.class_name(*uses));
+			self.p.println(format_args!("uses {name};"));
+		}
+
+		for provides in &module.provides {
+			let service = java_name(&self.class_name(provides.provides_index));
+			let implementations = provides
+				.provides_with_index
+				.iter()
+				.map(|index| java_name(&self.class_name(*index)))
+				.collect::<Vec<_>>();
+
+			// The double space is a quirk of the JDK's javap, kept so that the output can be diffed
+			self.p.println(format_args!("provides  {service} with"));
+			self.write_directive_targets(&implementations);
+		}
+
+		self.p.dedent();
+	}
+
+	fn write_package_directive(&mut self, directive: &str, package_index: u2, to: &[u2]) {
+		let package = java_name(&self.module_or_package_name(package_index));
+		if to.is_empty() {
+			self.p.println(format_args!("{directive} {package};"));
+			return;
+		}
+
+		let modules = to
+			.iter()
+			.map(|index| self.module_or_package_name(*index))
+			.collect::<Vec<_>>();
+
+		self.p.println(format_args!("{directive} {package} to"));
+		self.write_directive_targets(&modules);
+	}
+
+	/// Write the targets of a directive, one per line
+	fn write_directive_targets(&mut self, targets: &[String]) {
+		self.p.indent();
+		for (index, target) in targets.iter().enumerate() {
+			let separator = if index + 1 == targets.len() { ';' } else { ',' };
+			self.p.println(format_args!("{target}{separator}"));
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.16
+	fn write_annotations(&mut self, name: &str, annotations: &[Annotation]) {
+		self.p.println(format_args!("{name}:"));
+		self.p.indent();
+		for (index, annotation) in annotations.iter().enumerate() {
+			self.p
+				.println(format_args!("{index}: {}", raw_annotation(annotation)));
+			self.p.indent();
+			self.write_annotation(annotation);
+			self.p.newline();
+			self.p.dedent();
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.18
+	fn write_parameter_annotations(&mut self, name: &str, parameters: &ParameterAnnotations) {
+		self.p.println(format_args!("{name}:"));
+		self.p.indent();
+		for (parameter, annotations) in parameters.annotations.iter().enumerate() {
+			self.p.println(format_args!("parameter {parameter}:"));
+			self.p.indent();
+			for (index, annotation) in annotations.iter().enumerate() {
+				self.p
+					.println(format_args!("{index}: {}", raw_annotation(annotation)));
+				self.p.indent();
+				self.write_annotation(annotation);
+				self.p.newline();
+				self.p.dedent();
+			}
+			self.p.dedent();
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.20
+	fn write_type_annotations(&mut self, name: &str, annotations: &[TypeAnnotation]) {
+		self.p.println(format_args!("{name}:"));
+		self.p.indent();
+		for (index, type_annotation) in annotations.iter().enumerate() {
+			self.p.println(format_args!(
+				"{index}: {}: {}",
+				raw_annotation(&type_annotation.annotation),
+				type_annotation_position(type_annotation)
+			));
+			self.p.indent();
+			self.write_annotation(&type_annotation.annotation);
+			self.p.newline();
+			self.p.dedent();
+		}
+		self.p.dedent();
+	}
+
+	/// Write an annotation with its constant pool references resolved
+	///
+	/// This does not end the line, since annotations can be nested in element values.
+	fn write_annotation(&mut self, annotation: &Annotation) {
+		let type_name = descriptor::field_type(&self.utf8(annotation.type_index));
+		self.p.print(type_name);
+		if annotation.element_value_pairs.is_empty() {
+			return;
+		}
+
+		self.p.println("(");
+		self.p.indent();
+		for pair in &annotation.element_value_pairs {
+			let name = self.utf8(pair.element_name_index);
+			self.p.print(format_args!("{name}="));
+			self.write_element_value(&pair.value);
+			self.p.newline();
+		}
+		self.p.dedent();
+		self.p.print(")");
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.16.1
+	fn write_element_value(&mut self, value: &ElementValue) {
+		match &value.ty {
+			ElementValueType::Byte { const_value_index } => {
+				let value = self.integer_constant(*const_value_index);
+				self.p.print(format_args!("(byte) {value}"));
+			},
+			ElementValueType::Short { const_value_index } => {
+				let value = self.integer_constant(*const_value_index);
+				self.p.print(format_args!("(short) {value}"));
+			},
+			ElementValueType::Char { const_value_index } => {
+				let value = match self.integer(*const_value_index) {
+					Some(value) => char::from_u32(value as u32).map_or_else(
+						|| format!("\\u{value:04x}"),
+						|c| c.escape_default().to_string(),
+					),
+					None => self.constant_value(*const_value_index),
+				};
+				self.p.print(format_args!("'{value}'"));
+			},
+			ElementValueType::Boolean { const_value_index } => {
+				let value = match self.integer(*const_value_index) {
+					Some(value) => (value != 0).to_string(),
+					None => self.constant_value(*const_value_index),
+				};
+				self.p.print(value);
+			},
+			ElementValueType::String { const_value_index } => {
+				let value = self.utf8(*const_value_index);
+				self.p.print(format_args!("\"{value}\""));
+			},
+			ElementValueType::Double { const_value_index }
+			| ElementValueType::Float { const_value_index }
+			| ElementValueType::Int { const_value_index }
+			| ElementValueType::Long { const_value_index } => {
+				let value = self.constant_value(*const_value_index);
+				self.p.print(value);
+			},
+			ElementValueType::Enum {
+				type_name_index,
+				const_value_index,
+			} => {
+				let type_name = self.utf8(*type_name_index);
+				let constant = self.utf8(*const_value_index);
+				self.p.print(format_args!("{type_name}.{constant}"));
+			},
+			ElementValueType::Class { class_info_index } => {
+				let class = self.utf8(*class_info_index);
+				self.p.print(format_args!("class {class}"));
+			},
+			ElementValueType::Annotation { annotation } => {
+				self.p.print("@");
+				self.write_annotation(annotation);
+			},
+			ElementValueType::Array { values } => {
+				self.p.print("[");
+				for (index, value) in values.iter().enumerate() {
+					if index > 0 {
+						self.p.print(",");
+					}
+					self.write_element_value(value);
+				}
+				self.p.print("]");
+			},
+		}
+	}
+
+	fn integer_constant(&self, index: u2) -> String {
+		match self.integer(index) {
+			Some(value) => value.to_string(),
+			None => self.constant_value(index),
+		}
+	}
+}
+
+/// Format an annotation with its constant pool indices, as it appears in the first line of an entry
+fn raw_annotation(annotation: &Annotation) -> String {
+	let pairs = annotation
+		.element_value_pairs
+		.iter()
+		.map(|pair| {
+			format!(
+				"#{}={}",
+				pair.element_name_index,
+				raw_element_value(&pair.value)
+			)
+		})
+		.collect::<Vec<_>>();
+
+	format!("#{}({})", annotation.type_index, pairs.join(","))
+}
+
+fn raw_element_value(value: &ElementValue) -> String {
+	let tag = char::from(value.tag as u1);
+	match &value.ty {
+		ElementValueType::Byte { const_value_index }
+		| ElementValueType::Char { const_value_index }
+		| ElementValueType::Double { const_value_index }
+		| ElementValueType::Float { const_value_index }
+		| ElementValueType::Int { const_value_index }
+		| ElementValueType::Long { const_value_index }
+		| ElementValueType::Short { const_value_index }
+		| ElementValueType::Boolean { const_value_index }
+		| ElementValueType::String { const_value_index } => format!("{tag}#{const_value_index}"),
+		ElementValueType::Enum {
+			type_name_index,
+			const_value_index,
+		} => format!("{tag}#{type_name_index}.#{const_value_index}"),
+		ElementValueType::Class { class_info_index } => format!("{tag}#{class_info_index}"),
+		ElementValueType::Annotation { annotation } => {
+			format!("{tag}{}", raw_annotation(annotation))
+		},
+		ElementValueType::Array { values } => {
+			let values = values.iter().map(raw_element_value).collect::<Vec<_>>();
+			format!("{tag}{}]", values.join(","))
+		},
+	}
+}
+
+/// Describe the target and type path of a type annotation (e.g. `METHOD_FORMAL_PARAMETER, param_index=2`)
+fn type_annotation_position(type_annotation: &TypeAnnotation) -> String {
+	let target = match &type_annotation.target_info {
+		TypeAnnotationTargetInfo::TypeParameter {
+			type_parameter_index: parameter_index,
+		}
+		| TypeAnnotationTargetInfo::FormalParameter {
+			formal_parameter_index: parameter_index,
+		} => format!(", param_index={parameter_index}"),
+		TypeAnnotationTargetInfo::Supertype {
+			supertype_index: type_index,
+		}
+		| TypeAnnotationTargetInfo::Throws {
+			throws_type_index: type_index,
+		} => format!(", type_index={type_index}"),
+		TypeAnnotationTargetInfo::TypeParameterBound {
+			type_parameter_index,
+			bound_index,
+		} => format!(", param_index={type_parameter_index}, bound_index={bound_index}"),
+		TypeAnnotationTargetInfo::Empty => String::new(),
+		TypeAnnotationTargetInfo::LocalVar(entries) => {
+			let entries = entries
+				.iter()
+				.map(|entry| {
+					format!(
+						"start_pc={}, length={}, index={}",
+						entry.start_pc, entry.length, entry.index
+					)
+				})
+				.collect::<Vec<_>>();
+			format!(", {{{}}}", entries.join("; "))
+		},
+		TypeAnnotationTargetInfo::Catch {
+			exception_table_index,
+		} => format!(", exception_index={exception_table_index}"),
+		TypeAnnotationTargetInfo::Offset(offset) => format!(", offset={offset}"),
+		TypeAnnotationTargetInfo::TypeArgument {
+			offset,
+			type_argument_index,
+		} => format!(", offset={offset}, type_index={type_argument_index}"),
+	};
+
+	let path = &type_annotation.type_path.path;
+	let location = if path.is_empty() {
+		String::new()
+	} else {
+		let locations = path
+			.iter()
+			.map(|entry| match entry.kind {
+				TypePathKind::InArrayType => String::from("ARRAY"),
+				TypePathKind::InNestedType => String::from("INNER_TYPE"),
+				TypePathKind::InWildcardTypeBound => String::from("WILDCARD"),
+				TypePathKind::InTypeArgument => {
+					format!("TYPE_ARGUMENT({})", entry.type_argument_index)
+				},
+			})
+			.collect::<Vec<_>>();
+		format!(", location=[{}]", locations.join(", "))
+	};
+
+	format!(
+		"{}{target}{location}",
+		target_type_name(type_annotation.target_type)
+	)
+}
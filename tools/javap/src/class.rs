@@ -0,0 +1,460 @@
+use crate::descriptor::{self, MethodSignature, java_name};
+use crate::printer::Printer;
+
+use std::path::Path;
+
+use classfile::accessflags::{ClassAccessFlags, FieldAccessFlags, MethodAccessFlags};
+use classfile::attribute::{Attribute, AttributeType};
+use classfile::{ClassFile, FieldInfo, MethodInfo};
+use common::int_types::u2;
+
+/// What to include in the output
+#[derive(Copy, Clone, Default)]
+pub struct Options {
+	pub verbose: bool,
+	pub line_numbers: bool,
+	pub private: bool,
+	pub disassemble: bool,
+	pub signatures: bool,
+}
+
+/// A flag, its name in the JVMS, and its modifier in source form (if any)
+pub(crate) type Flag = (u2, &'static str, Option<&'static str>);
+
+#[rustfmt::skip]
+const CLASS_FLAGS: &[Flag] = &[
+	(ClassAccessFlags::ACC_PUBLIC.as_u2(),     "ACC_PUBLIC",     Some("public")),
+	(ClassAccessFlags::ACC_FINAL.as_u2(),      "ACC_FINAL",      Some("final")),
+	(ClassAccessFlags::ACC_SUPER.as_u2(),      "ACC_SUPER",      None),
+	(ClassAccessFlags::ACC_INTERFACE.as_u2(),  "ACC_INTERFACE",  None),
+	(ClassAccessFlags::ACC_ABSTRACT.as_u2(),   "ACC_ABSTRACT",   Some("abstract")),
+	(ClassAccessFlags::ACC_SYNTHETIC.as_u2(),  "ACC_SYNTHETIC",  None),
+	(ClassAccessFlags::ACC_ANNOTATION.as_u2(), "ACC_ANNOTATION", None),
+	(ClassAccessFlags::ACC_ENUM.as_u2(),       "ACC_ENUM",       None),
+	(ClassAccessFlags::ACC_MODULE.as_u2(),     "ACC_MODULE",     None),
+];
+
+#[rustfmt::skip]
+const FIELD_FLAGS: &[Flag] = &[
+	(FieldAccessFlags::ACC_PUBLIC.as_u2(),    "ACC_PUBLIC",    Some("public")),
+	(FieldAccessFlags::ACC_PRIVATE.as_u2(),   "ACC_PRIVATE",   Some("private")),
+	(FieldAccessFlags::ACC_PROTECTED.as_u2(), "ACC_PROTECTED", Some("protected")),
+	(FieldAccessFlags::ACC_STATIC.as_u2(),    "ACC_STATIC",    Some("static")),
+	(FieldAccessFlags::ACC_FINAL.as_u2(),     "ACC_FINAL",     Some("final")),
+	(FieldAccessFlags::ACC_VOLATILE.as_u2(),  "ACC_VOLATILE",  Some("volatile")),
+	(FieldAccessFlags::ACC_TRANSIENT.as_u2(), "ACC_TRANSIENT", Some("transient")),
+	(FieldAccessFlags::ACC_SYNTHETIC.as_u2(), "ACC_SYNTHETIC", None),
+	(FieldAccessFlags::ACC_ENUM.as_u2(),      "ACC_ENUM",      None),
+];
+
+#[rustfmt::skip]
+const METHOD_FLAGS: &[Flag] = &[
+	(MethodAccessFlags::ACC_PUBLIC.as_u2(),       "ACC_PUBLIC",       Some("public")),
+	(MethodAccessFlags::ACC_PRIVATE.as_u2(),      "ACC_PRIVATE",      Some("private")),
+	(MethodAccessFlags::ACC_PROTECTED.as_u2(),    "ACC_PROTECTED",    Some("protected")),
+	(MethodAccessFlags::ACC_STATIC.as_u2(),       "ACC_STATIC",       Some("static")),
+	(MethodAccessFlags::ACC_FINAL.as_u2(),        "ACC_FINAL",        Some("final")),
+	(MethodAccessFlags::ACC_SYNCHRONIZED.as_u2(), "ACC_SYNCHRONIZED", Some("synchronized")),
+	(MethodAccessFlags::ACC_BRIDGE.as_u2(),       "ACC_BRIDGE",       None),
+	(MethodAccessFlags::ACC_VARARGS.as_u2(),      "ACC_VARARGS",      None),
+	(MethodAccessFlags::ACC_NATIVE.as_u2(),       "ACC_NATIVE",       Some("native")),
+	(MethodAccessFlags::ACC_ABSTRACT.as_u2(),     "ACC_ABSTRACT",     Some("abstract")),
+	(MethodAccessFlags::ACC_STRICT.as_u2(),       "ACC_STRICT",       Some("strictfp")),
+	(MethodAccessFlags::ACC_SYNTHETIC.as_u2(),    "ACC_SYNTHETIC",    None),
+];
+
+pub(crate) fn flag_names(flags: u2, table: &[Flag]) -> String {
+	let names = table
+		.iter()
+		.filter(|(flag, ..)| flags & flag != 0)
+		.map(|(_, name, _)| *name)
+		.collect::<Vec<_>>();
+
+	if names.is_empty() {
+		return format!("({flags:#06x})");
+	}
+
+	format!("({flags:#06x}) {}", names.join(", "))
+}
+
+pub(crate) fn modifiers(flags: u2, table: &[Flag]) -> Vec<&'static str> {
+	table
+		.iter()
+		.filter(|(flag, ..)| flags & flag != 0)
+		.filter_map(|(_, _, modifier)| *modifier)
+		.collect()
+}
+
+/// Disassembles a single class file
+pub struct ClassWriter<'a> {
+	pub(crate) class: &'a ClassFile,
+	pub(crate) options: Options,
+	pub(crate) p: Printer,
+}
+
+impl<'a> ClassWriter<'a> {
+	pub fn new(class: &'a ClassFile, options: Options) -> Self {
+		Self {
+			class,
+			options,
+			p: Printer::new(),
+		}
+	}
+
+	pub fn write(mut self, path: &Path) -> String {
+		let class = self.class;
+
+		if self.options.verbose {
+			let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+			self.p.println(format_args!("Classfile {}", path.display()));
+			self.p.indent();
+		}
+
+		if let Some(source_file) = class.source_file_index() {
+			self.p
+				.println(format_args!("Compiled from \"{}\"", self.utf8(source_file)));
+		}
+
+		if self.options.verbose {
+			self.p.dedent();
+		}
+
+		if class.access_flags.is_module() {
+			self.write_module_header();
+		} else {
+			self.write_class_header();
+		}
+
+		if self.options.verbose {
+			self.p.indent();
+			self.p
+				.println(format_args!("minor version: {}", class.minor_version));
+			self.p
+				.println(format_args!("major version: {}", class.major_version));
+			self.p.println(format_args!(
+				"flags: {}",
+				flag_names(class.access_flags.as_u2(), CLASS_FLAGS)
+			));
+
+			let this_class = self.constant_value(class.this_class);
+			self.p
+				.print(format_args!("this_class: #{}", class.this_class));
+			self.p.comment(this_class);
+
+			self.p
+				.print(format_args!("super_class: #{}", class.super_class));
+			if class.super_class == 0 {
+				self.p.newline();
+			} else {
+				let super_class = self.constant_value(class.super_class);
+				self.p.comment(super_class);
+			}
+
+			self.p.println(format_args!(
+				"interfaces: {}, fields: {}, methods: {}, attributes: {}",
+				class.interfaces.len(),
+				class.fields.len(),
+				class.methods.len(),
+				class.attributes.len()
+			));
+			self.p.dedent();
+
+			self.write_constant_pool();
+			self.p.println("{");
+		} else {
+			self.p.println(" {");
+		}
+
+		if class.access_flags.is_module() && !self.options.verbose {
+			self.write_module_directives();
+		}
+
+		// Matches the spacing of the JDK's javap: fields are followed by a blank line when their code
+		// would be shown, and methods are separated by one when anything beyond the declaration is shown
+		let options = self.options;
+		let field_spacing = options.verbose || options.disassemble || options.line_numbers;
+		let method_spacing = field_spacing || options.signatures;
+
+		for field in &class.fields {
+			if !self.is_shown(field.access_flags.is_private()) {
+				continue;
+			}
+
+			self.write_field(field);
+			if field_spacing {
+				self.p.newline();
+			}
+		}
+
+		let mut first = true;
+		for method in &class.methods {
+			if !self.is_shown(method.access_flags.is_private()) {
+				continue;
+			}
+
+			if method_spacing && !first {
+				self.p.newline();
+			}
+
+			first = false;
+			self.write_method(method);
+		}
+
+		self.p.println("}");
+
+		if self.options.verbose {
+			for attribute in &class.attributes {
+				self.write_attribute(attribute);
+			}
+		}
+
+		self.p.finish()
+	}
+
+	/// Get the contents of the `Signature` attribute in `attributes`, if present
+	fn signature(&self, attributes: &[Attribute]) -> Option<String> {
+		attributes
+			.iter()
+			.find_map(Attribute::signature)
+			.map(|signature| self.utf8(signature.signature_index))
+	}
+
+	/// Private members are only shown with `-p`
+	fn is_shown(&self, is_private: bool) -> bool {
+		!is_private || self.options.private
+	}
+
+	fn write_class_header(&mut self) {
+		let class = self.class;
+		let flags = class.access_flags;
+
+		let mut declaration = modifiers(flags.as_u2(), CLASS_FLAGS);
+		if flags.is_interface() {
+			// Interfaces are implicitly abstract
+			declaration.retain(|modifier| *modifier != "abstract");
+			declaration.push("interface");
+		} else {
+			declaration.push("class");
+		}
+
+		let mut declaration = declaration.join(" ");
+		declaration.push(' ');
+		declaration.push_str(&java_name(&self.class_name(class.this_class)));
+
+		let signature = self
+			.signature(&class.attributes)
+			.and_then(|signature| descriptor::class_signature(&signature, self.options.verbose));
+
+		let (superclass, interfaces, separator) = match signature {
+			Some(signature) => {
+				declaration.push_str(&signature.type_parameters);
+
+				let superclass = (self.options.verbose
+					|| signature.superclass != "java.lang.Object")
+					.then_some(signature.superclass);
+				(superclass, signature.interfaces, ", ")
+			},
+			None => {
+				let superclass = match class.super_class {
+					0 => None,
+					index => Some(self.class_name(index))
+						.filter(|name| name != "java/lang/Object")
+						.map(|name| java_name(&name)),
+				};
+
+				let interfaces = class
+					.interfaces
+					.iter()
+					.map(|index| java_name(&self.class_name(*index)))
+					.collect::<Vec<_>>();
+
+				// The JDK's javap doesn't space out interfaces that aren't from a signature
+				(superclass, interfaces, ",")
+			},
+		};
+
+		if flags.is_interface() {
+			if !interfaces.is_empty() {
+				declaration.push_str(" extends ");
+				declaration.push_str(&interfaces.join(separator));
+			}
+		} else {
+			if let Some(superclass) = superclass {
+				declaration.push_str(" extends ");
+				declaration.push_str(&superclass);
+			}
+
+			if !interfaces.is_empty() {
+				declaration.push_str(" implements ");
+				declaration.push_str(&interfaces.join(separator));
+			}
+		}
+
+		self.p.print(declaration);
+		if self.options.verbose {
+			self.p.newline();
+		}
+	}
+
+	fn write_field(&mut self, field: &FieldInfo) {
+		let flags = field.access_flags.as_u2();
+		let descriptor = self.utf8(field.descriptor_index);
+
+		let mut declaration = modifiers(flags, FIELD_FLAGS).join(" ");
+		if !declaration.is_empty() {
+			declaration.push(' ');
+		}
+
+		let field_type = self
+			.signature(&field.attributes)
+			.and_then(|signature| descriptor::field_signature(&signature))
+			.unwrap_or_else(|| descriptor::field_type(&descriptor));
+
+		declaration.push_str(&field_type);
+		declaration.push(' ');
+		declaration.push_str(&self.utf8(field.name_index));
+
+		self.p.indent();
+		self.p.println(format_args!("{declaration};"));
+
+		self.p.indent();
+		if self.options.signatures || self.options.verbose {
+			self.p.println(format_args!("descriptor: {descriptor}"));
+		}
+
+		if self.options.verbose {
+			self.p
+				.println(format_args!("flags: {}", flag_names(flags, FIELD_FLAGS)));
+
+			for attribute in &field.attributes {
+				self.write_attribute(attribute);
+			}
+		}
+
+		self.p.dedent();
+		self.p.dedent();
+	}
+
+	fn method_declaration(&self, method: &MethodInfo) -> String {
+		let flags = method.access_flags;
+		let name = self.utf8(method.name_index);
+		if name == "<clinit>" {
+			return String::from("static {}");
+		}
+
+		let mut declaration = modifiers(flags.as_u2(), METHOD_FLAGS);
+
+		// Non-abstract instance methods in interfaces are default methods
+		if self.class.access_flags.is_interface()
+			&& !flags.is_static()
+			&& !flags.is_abstract()
+			&& !flags.is_private()
+		{
+			declaration.push("default");
+		}
+
+		let signature = self
+			.signature(&method.attributes)
+			.and_then(|signature| descriptor::method_signature(&signature, self.options.verbose));
+
+		let descriptor = self.utf8(method.descriptor_index);
+		let signature = match (signature, descriptor::method_type(&descriptor)) {
+			(Some(signature), _) => signature,
+			(None, Some(method_type)) => MethodSignature {
+				type_parameters: String::new(),
+				parameters: method_type.parameters,
+				return_type: method_type.return_type,
+				throws: Vec::new(),
+			},
+			(None, None) => {
+				return format!("{} {name} /* {descriptor} */", declaration.join(" "));
+			},
+		};
+
+		let mut parameters = signature.parameters;
+		if flags.is_varargs()
+			&& let Some(last) = parameters.last_mut()
+			&& let Some(element) = last.strip_suffix("[]")
+		{
+			*last = format!("{element}...");
+		}
+
+		let mut declaration = declaration.join(" ");
+		if !declaration.is_empty() {
+			declaration.push(' ');
+		}
+
+		if !signature.type_parameters.is_empty() {
+			declaration.push_str(&signature.type_parameters);
+			declaration.push(' ');
+		}
+
+		if name == "<init>" {
+			declaration.push_str(&java_name(&self.class_name(self.class.this_class)));
+		} else {
+			declaration.push_str(&signature.return_type);
+			declaration.push(' ');
+			declaration.push_str(&name);
+		}
+
+		declaration.push('(');
+		declaration.push_str(&parameters.join(", "));
+		declaration.push(')');
+
+		// Thrown type variables only appear in the signature
+		let mut exceptions = signature.throws;
+		if exceptions.is_empty()
+			&& let Some(attribute) = method.attributes.iter().find_map(Attribute::exceptions)
+		{
+			exceptions = attribute
+				.exception_index_table
+				.iter()
+				.map(|index| java_name(&self.class_name(*index)))
+				.collect();
+		}
+
+		if !exceptions.is_empty() {
+			declaration.push_str(" throws ");
+			declaration.push_str(&exceptions.join(", "));
+		}
+
+		declaration
+	}
+
+	fn write_method(&mut self, method: &MethodInfo) {
+		let flags = method.access_flags;
+		let descriptor = self.utf8(method.descriptor_index);
+
+		let declaration = self.method_declaration(method);
+		self.p.indent();
+		self.p.println(format_args!("{declaration};"));
+
+		self.p.indent();
+		if self.options.signatures || self.options.verbose {
+			self.p.println(format_args!("descriptor: {descriptor}"));
+		}
+
+		if self.options.verbose {
+			self.p.println(format_args!(
+				"flags: {}",
+				flag_names(flags.as_u2(), METHOD_FLAGS)
+			));
+		}
+
+		// Like the JDK's javap, this counts the parameters (and the receiver) rather than the local
+		// variable slots they take up
+		let args_size = descriptor::method_type(&descriptor)
+			.map(|method_type| method_type.parameters.len() as u2 + u2::from(!flags.is_static()));
+
+		for attribute in &method.attributes {
+			match &attribute.info {
+				AttributeType::Code(code) => self.write_code(code, args_size),
+				_ if self.options.verbose => self.write_attribute(attribute),
+				_ => {},
+			}
+		}
+
+		self.p.dedent();
+		self.p.dedent();
+	}
+}
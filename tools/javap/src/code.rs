@@ -0,0 +1,548 @@
+use crate::class::ClassWriter;
+use crate::constant_pool::check_name;
+
+use std::fmt::Display;
+
+use classfile::attribute::{
+	AttributeType, Code, LineNumberTable, LocalVariableTable, LocalVariableTypeTable,
+	StackMapFrame, StackMapTable, VerificationTypeInfo,
+};
+use common::int_types::{s1, s2, s4, u1, u2};
+use instructions::OpCode;
+
+/// A cursor over the bytecode of a method
+///
+/// Every read returns `None` if the code ends early.
+struct Bytecode<'a> {
+	code: &'a [u1],
+	position: usize,
+}
+
+impl Bytecode<'_> {
+	fn u1(&mut self) -> Option<u1> {
+		let value = *self.code.get(self.position)?;
+		self.position += 1;
+		Some(value)
+	}
+
+	fn s1(&mut self) -> Option<s1> {
+		self.u1().map(|value| value as s1)
+	}
+
+	fn u2(&mut self) -> Option<u2> {
+		Some(u2::from_be_bytes([self.u1()?, self.u1()?]))
+	}
+
+	fn s2(&mut self) -> Option<s2> {
+		self.u2().map(|value| value as s2)
+	}
+
+	fn s4(&mut self) -> Option<s4> {
+		Some(s4::from_be_bytes([
+			self.u1()?,
+			self.u1()?,
+			self.u1()?,
+			self.u1()?,
+		]))
+	}
+
+	/// Skip the padding before the operands of `tableswitch` and `lookupswitch`
+	fn align(&mut self) {
+		while !self.position.is_multiple_of(4) {
+			self.position += 1;
+		}
+	}
+}
+
+/// The operand of `newarray`
+fn array_type_name(atype: u1) -> Option<&'static str> {
+	match atype {
+		4 => Some("boolean"),
+		5 => Some("char"),
+		6 => Some("float"),
+		7 => Some("double"),
+		8 => Some("byte"),
+		9 => Some("short"),
+		10 => Some("int"),
+		11 => Some("long"),
+		_ => None,
+	}
+}
+
+/// Calculate the target of a branch at `pc`
+fn branch_target(pc: usize, offset: s4) -> i64 {
+	pc as i64 + i64::from(offset)
+}
+
+impl ClassWriter<'_> {
+	pub(crate) fn write_code(&mut self, code: &Code, args_size: Option<u2>) {
+		let verbose = self.options.verbose;
+
+		if self.options.disassemble || verbose {
+			self.p.println("Code:");
+			if verbose {
+				self.p.indent();
+				let args_size =
+					args_size.map_or_else(|| String::from("?"), |size| size.to_string());
+				self.p.println(format_args!(
+					"stack={}, locals={}, args_size={args_size}",
+					code.max_stack, code.max_locals
+				));
+			}
+
+			self.disassemble(&code.code);
+			self.write_exception_table(code);
+		}
+
+		for attribute in &code.attributes {
+			let is_table = matches!(
+				attribute.info,
+				AttributeType::LineNumberTable(_) | AttributeType::LocalVariableTable(_)
+			);
+			if verbose || (self.options.line_numbers && is_table) {
+				self.write_attribute(attribute);
+			}
+		}
+
+		if verbose {
+			self.p.dedent();
+		}
+	}
+
+	fn disassemble(&mut self, code: &[u1]) {
+		let mut bytecode = Bytecode { code, position: 0 };
+		while bytecode.position < code.len() {
+			let pc = bytecode.position;
+			if self.write_instruction(&mut bytecode).is_none() {
+				self.p
+					.println(format_args!("{pc:>4}: <truncated instruction>"));
+				break;
+			}
+		}
+	}
+
+	fn write_operands(&mut self, pc: usize, mnemonic: &str, operands: impl Display) {
+		self.p
+			.println(format_args!("{pc:>4}: {mnemonic:<13} {operands}"));
+	}
+
+	/// Write an instruction that refers to the constant pool entry at `index`
+	fn write_constant_operand(
+		&mut self,
+		pc: usize,
+		mnemonic: &str,
+		index: u2,
+		extra_operands: impl Display,
+	) {
+		let constant = self.describe_constant(index);
+		self.p.print(format_args!(
+			"{pc:>4}: {mnemonic:<13} #{index}{extra_operands}"
+		));
+		self.p.comment(constant);
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-6.html#jvms-6.5
+	fn write_instruction(&mut self, bytecode: &mut Bytecode<'_>) -> Option<()> {
+		let pc = bytecode.position;
+		let byte = bytecode.u1()?;
+		let opcode = OpCode::from(byte);
+		let mnemonic = format!("{opcode:?}");
+
+		match opcode {
+			OpCode::unknown => {
+				self.p
+					.println(format_args!("{pc:>4}: <unknown opcode {byte:#04x}>"));
+			},
+			OpCode::bipush => {
+				let value = bytecode.s1()?;
+				self.write_operands(pc, &mnemonic, value);
+			},
+			OpCode::sipush => {
+				let value = bytecode.s2()?;
+				self.write_operands(pc, &mnemonic, value);
+			},
+			OpCode::ldc => {
+				let index = u2::from(bytecode.u1()?);
+				self.write_constant_operand(pc, &mnemonic, index, "");
+			},
+			OpCode::ldc_w
+			| OpCode::ldc2_w
+			| OpCode::getstatic
+			| OpCode::putstatic
+			| OpCode::getfield
+			| OpCode::putfield
+			| OpCode::invokevirtual
+			| OpCode::invokespecial
+			| OpCode::invokestatic
+			| OpCode::new
+			| OpCode::anewarray
+			| OpCode::checkcast
+			| OpCode::instanceof => {
+				let index = bytecode.u2()?;
+				self.write_constant_operand(pc, &mnemonic, index, "");
+			},
+			OpCode::invokeinterface => {
+				let index = bytecode.u2()?;
+				let count = bytecode.u1()?;
+				let _zero = bytecode.u1()?;
+				self.write_constant_operand(pc, &mnemonic, index, format_args!(",  {count}"));
+			},
+			OpCode::invokedynamic => {
+				let index = bytecode.u2()?;
+				let _zero = bytecode.u2()?;
+				self.write_constant_operand(pc, &mnemonic, index, ",  0");
+			},
+			OpCode::multianewarray => {
+				let index = bytecode.u2()?;
+				let dimensions = bytecode.u1()?;
+				self.write_constant_operand(pc, &mnemonic, index, format_args!(",  {dimensions}"));
+			},
+			OpCode::iload
+			| OpCode::lload
+			| OpCode::fload
+			| OpCode::dload
+			| OpCode::aload
+			| OpCode::istore
+			| OpCode::lstore
+			| OpCode::fstore
+			| OpCode::dstore
+			| OpCode::astore
+			| OpCode::ret => {
+				let index = bytecode.u1()?;
+				self.write_operands(pc, &mnemonic, index);
+			},
+			OpCode::iinc => {
+				let index = bytecode.u1()?;
+				let value = bytecode.s1()?;
+				self.write_operands(pc, &mnemonic, format_args!("{index}, {value}"));
+			},
+			OpCode::ifeq
+			| OpCode::ifne
+			| OpCode::iflt
+			| OpCode::ifge
+			| OpCode::ifgt
+			| OpCode::ifle
+			| OpCode::if_icmpeq
+			| OpCode::if_icmpne
+			| OpCode::if_icmplt
+			| OpCode::if_icmpge
+			| OpCode::if_icmpgt
+			| OpCode::if_icmple
+			| OpCode::if_acmpeq
+			| OpCode::if_acmpne
+			| OpCode::goto
+			| OpCode::jsr
+			| OpCode::ifnull
+			| OpCode::ifnonnull => {
+				let offset = bytecode.s2()?;
+				let target = branch_target(pc, s4::from(offset));
+				self.write_operands(pc, &mnemonic, target);
+			},
+			OpCode::goto_w | OpCode::jsr_w => {
+				let offset = bytecode.s4()?;
+				self.write_operands(pc, &mnemonic, branch_target(pc, offset));
+			},
+			OpCode::newarray => {
+				let atype = bytecode.u1()?;
+				match array_type_name(atype) {
+					Some(name) => self.write_operands(pc, &mnemonic, format_args!(" {name}")),
+					None => {
+						self.write_operands(pc, &mnemonic, format_args!(" <unknown type {atype}>"))
+					},
+				}
+			},
+			OpCode::tableswitch => self.write_tableswitch(pc, bytecode)?,
+			OpCode::lookupswitch => self.write_lookupswitch(pc, bytecode)?,
+			OpCode::wide => self.write_wide(pc, bytecode)?,
+			_ => self.p.println(format_args!("{pc:>4}: {opcode:?}")),
+		}
+
+		Some(())
+	}
+
+	fn write_tableswitch(&mut self, pc: usize, bytecode: &mut Bytecode<'_>) -> Option<()> {
+		bytecode.align();
+		let default = bytecode.s4()?;
+		let low = bytecode.s4()?;
+		let high = bytecode.s4()?;
+
+		// Anything larger than the remaining code is necessarily truncated
+		let count = i64::from(high) - i64::from(low) + 1;
+		if count < 0 || count as usize > bytecode.code.len() {
+			return None;
+		}
+
+		let mut offsets = Vec::with_capacity(count as usize);
+		for _ in 0..count {
+			offsets.push(bytecode.s4()?);
+		}
+
+		self.p.println(format_args!(
+			"{pc:>4}: {:<13} {{ // {low} to {high}",
+			"tableswitch"
+		));
+		for (key, offset) in (i64::from(low)..).zip(offsets) {
+			self.p
+				.println(format_args!("{key:>18}: {}", branch_target(pc, offset)));
+		}
+
+		self.write_switch_end(pc, default);
+		Some(())
+	}
+
+	fn write_lookupswitch(&mut self, pc: usize, bytecode: &mut Bytecode<'_>) -> Option<()> {
+		bytecode.align();
+		let default = bytecode.s4()?;
+		let npairs = bytecode.s4()?;
+
+		if npairs < 0 || npairs as usize > bytecode.code.len() {
+			return None;
+		}
+
+		let mut pairs = Vec::with_capacity(npairs as usize);
+		for _ in 0..npairs {
+			pairs.push((bytecode.s4()?, bytecode.s4()?));
+		}
+
+		self.p.println(format_args!(
+			"{pc:>4}: {:<13} {{ // {npairs}",
+			"lookupswitch"
+		));
+		for (key, offset) in pairs {
+			self.p
+				.println(format_args!("{key:>18}: {}", branch_target(pc, offset)));
+		}
+
+		self.write_switch_end(pc, default);
+		Some(())
+	}
+
+	fn write_switch_end(&mut self, pc: usize, default: s4) {
+		self.p.println(format_args!(
+			"{:>18}: {}",
+			"default",
+			branch_target(pc, default)
+		));
+		self.p.println("      }");
+	}
+
+	fn write_wide(&mut self, pc: usize, bytecode: &mut Bytecode<'_>) -> Option<()> {
+		let opcode = OpCode::from(bytecode.u1()?);
+		let mnemonic = format!("{opcode:?}");
+		match opcode {
+			OpCode::iinc => {
+				let index = bytecode.u2()?;
+				let value = bytecode.s2()?;
+				self.write_operands(
+					pc,
+					&format!("{mnemonic}_w"),
+					format_args!("{index}, {value}"),
+				);
+			},
+			OpCode::iload
+			| OpCode::lload
+			| OpCode::fload
+			| OpCode::dload
+			| OpCode::aload
+			| OpCode::istore
+			| OpCode::lstore
+			| OpCode::fstore
+			| OpCode::dstore
+			| OpCode::astore
+			| OpCode::ret => {
+				let index = bytecode.u2()?;
+				self.write_operands(pc, &format!("{mnemonic}_w"), index);
+			},
+			_ => self
+				.p
+				.println(format_args!("{pc:>4}: wide <cannot modify {opcode:?}>")),
+		}
+
+		Some(())
+	}
+
+	fn write_exception_table(&mut self, code: &Code) {
+		if code.exception_table.is_empty() {
+			return;
+		}
+
+		self.p.println("Exception table:");
+		self.p.println("   from    to  target type");
+		for exception in &code.exception_table {
+			let catch_type = match exception.catch_type {
+				0 => String::from("any"),
+				index => format!("Class {}", check_name(&self.class_name(index))),
+			};
+
+			self.p.println(format_args!(
+				"{:>8}{:>6}{:>6}   {catch_type}",
+				exception.start_pc, exception.end_pc, exception.handler_pc
+			));
+		}
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.12
+	pub(crate) fn write_line_number_table(&mut self, table: &LineNumberTable) {
+		self.p.println("LineNumberTable:");
+		self.p.indent();
+		for entry in &table.line_number_table {
+			self.p.println(format_args!(
+				"line {}: {}",
+				entry.line_number, entry.start_pc
+			));
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.13
+	pub(crate) fn write_local_variable_table(&mut self, table: &LocalVariableTable) {
+		self.p.println("LocalVariableTable:");
+		self.p.indent();
+		self.p.println("Start  Length  Slot  Name   Signature");
+		for entry in &table.local_variable_table {
+			let name = self.utf8(entry.name_index);
+			let descriptor = self.utf8(entry.descriptor_index);
+			self.p.println(format_args!(
+				"{:>5} {:>7} {:>5} {name:>5}   {descriptor}",
+				entry.start_pc, entry.length, entry.index
+			));
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.14
+	pub(crate) fn write_local_variable_type_table(&mut self, table: &LocalVariableTypeTable) {
+		self.p.println("LocalVariableTypeTable:");
+		self.p.indent();
+		self.p.println("Start  Length  Slot  Name   Signature");
+		for entry in &table.local_variable_type_table {
+			let name = self.utf8(entry.name_index);
+			let signature = self.utf8(entry.signature_index);
+			self.p.println(format_args!(
+				"{:>5} {:>7} {:>5} {name:>5}   {signature}",
+				entry.start_pc, entry.length, entry.index
+			));
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.4
+	pub(crate) fn write_stack_map_table(&mut self, table: &StackMapTable) {
+		self.p.println(format_args!(
+			"StackMapTable: number_of_entries = {}",
+			table.entries.len()
+		));
+
+		self.p.indent();
+		for frame in &table.entries {
+			match frame {
+				StackMapFrame::SameFrame { offset_delta } => {
+					self.p
+						.println(format_args!("frame_type = {offset_delta} /* same */"));
+				},
+				StackMapFrame::SameLocals1StackItemFrame {
+					offset_delta,
+					verification_type_info,
+				} => {
+					self.p.println(format_args!(
+						"frame_type = {} /* same_locals_1_stack_item */",
+						offset_delta + 64
+					));
+					self.p.indent();
+					self.write_verification_types("stack", verification_type_info);
+					self.p.dedent();
+				},
+				StackMapFrame::SameLocals1StackItemFrameExtended {
+					offset_delta,
+					verification_type_info,
+				} => {
+					self.p
+						.println("frame_type = 247 /* same_locals_1_stack_item_frame_extended */");
+					self.p.indent();
+					self.p
+						.println(format_args!("offset_delta = {offset_delta}"));
+					self.write_verification_types("stack", verification_type_info);
+					self.p.dedent();
+				},
+				StackMapFrame::ChopFrame {
+					offset_delta,
+					absent_locals,
+				} => {
+					self.p.println(format_args!(
+						"frame_type = {} /* chop */",
+						251 - u2::from(*absent_locals)
+					));
+					self.p.indent();
+					self.p
+						.println(format_args!("offset_delta = {offset_delta}"));
+					self.p.dedent();
+				},
+				StackMapFrame::SameFrameExtended { offset_delta } => {
+					self.p.println("frame_type = 251 /* same_frame_extended */");
+					self.p.indent();
+					self.p
+						.println(format_args!("offset_delta = {offset_delta}"));
+					self.p.dedent();
+				},
+				StackMapFrame::AppendFrame {
+					offset_delta,
+					locals,
+				} => {
+					self.p.println(format_args!(
+						"frame_type = {} /* append */",
+						251 + locals.len()
+					));
+					self.p.indent();
+					self.p
+						.println(format_args!("offset_delta = {offset_delta}"));
+					self.write_verification_types("locals", locals);
+					self.p.dedent();
+				},
+				StackMapFrame::FullFrame {
+					offset_delta,
+					locals,
+					stack,
+				} => {
+					self.p.println("frame_type = 255 /* full_frame */");
+					self.p.indent();
+					self.p
+						.println(format_args!("offset_delta = {offset_delta}"));
+					self.write_verification_types("locals", locals);
+					self.write_verification_types("stack", stack);
+					self.p.dedent();
+				},
+			}
+		}
+		self.p.dedent();
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.7.4
+	fn write_verification_types(&mut self, label: &str, types: &[VerificationTypeInfo]) {
+		if types.is_empty() {
+			self.p.println(format_args!("{label} = []"));
+			return;
+		}
+
+		let types = types
+			.iter()
+			.map(|ty| match ty {
+				VerificationTypeInfo::TopVariableInfo => String::from("top"),
+				VerificationTypeInfo::IntegerVariableInfo => String::from("int"),
+				VerificationTypeInfo::FloatVariableInfo => String::from("float"),
+				VerificationTypeInfo::LongVariableInfo => String::from("long"),
+				VerificationTypeInfo::DoubleVariableInfo => String::from("double"),
+				VerificationTypeInfo::NullVariableInfo => String::from("null"),
+				VerificationTypeInfo::UninitializedThisVariableInfo => String::from("this"),
+				VerificationTypeInfo::ObjectVariableInfo { cpool_index } => {
+					format!("class {}", check_name(&self.class_name(*cpool_index)))
+				},
+				VerificationTypeInfo::UninitializedVariableInfo { offset } => {
+					format!("uninitialized {offset}")
+				},
+			})
+			.collect::<Vec<_>>();
+
+		self.p
+			.println(format_args!("{label} = [ {} ]", types.join(", ")));
+	}
+}
@@ -0,0 +1,440 @@
+use crate::class::ClassWriter;
+
+use std::fmt::Write;
+
+use classfile::constant_pool::ConstantPoolValueInfo;
+use classfile::constant_pool::types::ReferenceKind;
+use common::int_types::{u1, u2, u4};
+
+impl ClassWriter<'_> {
+	fn entry(&self, index: u2) -> Option<&ConstantPoolValueInfo> {
+		let constant_pool: &[ConstantPoolValueInfo] = &self.class.constant_pool;
+		match constant_pool.get(index as usize) {
+			None | Some(ConstantPoolValueInfo::Unusable) => None,
+			entry => entry,
+		}
+	}
+
+	/// Get the contents of the `CONSTANT_Utf8_info` entry at `index`, with any special characters
+	/// escaped
+	pub(crate) fn utf8(&self, index: u2) -> String {
+		match self.entry(index) {
+			Some(ConstantPoolValueInfo::Utf8 { bytes, .. }) => escape(bytes),
+			_ => invalid(index),
+		}
+	}
+
+	/// Get the internal name of the `CONSTANT_Class_info` entry at `index`
+	pub(crate) fn class_name(&self, index: u2) -> String {
+		match self.entry(index) {
+			Some(ConstantPoolValueInfo::Class { name_index }) => self.utf8(*name_index),
+			_ => invalid(index),
+		}
+	}
+
+	/// Get the name of the `CONSTANT_Module_info` or `CONSTANT_Package_info` entry at `index`
+	pub(crate) fn module_or_package_name(&self, index: u2) -> String {
+		match self.entry(index) {
+			Some(
+				ConstantPoolValueInfo::Module { name_index }
+				| ConstantPoolValueInfo::Package { name_index },
+			) => self.utf8(*name_index),
+			_ => invalid(index),
+		}
+	}
+
+	/// Get the name and descriptor of the `CONSTANT_NameAndType_info` entry at `index`
+	pub(crate) fn name_and_type(&self, index: u2) -> (String, String) {
+		match self.entry(index) {
+			Some(ConstantPoolValueInfo::NameAndType {
+				name_index,
+				descriptor_index,
+			}) => (self.utf8(*name_index), self.utf8(*descriptor_index)),
+			_ => (invalid(index), invalid(index)),
+		}
+	}
+
+	/// Get the value of the `CONSTANT_Integer_info` entry at `index`
+	pub(crate) fn integer(&self, index: u2) -> Option<i32> {
+		match self.entry(index) {
+			Some(ConstantPoolValueInfo::Integer { bytes }) => Some(*bytes as i32),
+			_ => None,
+		}
+	}
+
+	/// Format a member reference as `Class.name:descriptor`
+	///
+	/// If `omit_this_class` is set, references to the current class are formatted as `name:descriptor`.
+	fn member(&self, class_index: u2, name_and_type_index: u2, omit_this_class: bool) -> String {
+		let class_name = self.class_name(class_index);
+		let (name, descriptor) = self.name_and_type(name_and_type_index);
+
+		if omit_this_class && class_name == self.class_name(self.class.this_class) {
+			return format!("{}:{descriptor}", check_name(&name));
+		}
+
+		format!(
+			"{}.{}:{descriptor}",
+			check_name(&class_name),
+			check_name(&name)
+		)
+	}
+
+	/// Get the value of the entry at `index`, as it appears in the constant pool listing
+	pub(crate) fn constant_value(&self, index: u2) -> String {
+		self.format_constant(index, false)
+	}
+
+	fn format_constant(&self, index: u2, omit_this_class: bool) -> String {
+		let Some(entry) = self.entry(index) else {
+			return invalid(index);
+		};
+
+		match entry {
+			ConstantPoolValueInfo::Unusable => invalid(index),
+			ConstantPoolValueInfo::Class { name_index } => check_name(&self.utf8(*name_index)),
+			ConstantPoolValueInfo::Fieldref {
+				class_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::Methodref {
+				class_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::InterfaceMethodref {
+				class_index,
+				name_and_type_index,
+			} => self.member(*class_index, *name_and_type_index, omit_this_class),
+			ConstantPoolValueInfo::String { string_index } => self.utf8(*string_index),
+			ConstantPoolValueInfo::Integer { bytes } => (*bytes as i32).to_string(),
+			ConstantPoolValueInfo::Float { bytes } => {
+				format!("{}f", float_string(f32::from_bits(*bytes)))
+			},
+			ConstantPoolValueInfo::Long {
+				high_bytes,
+				low_bytes,
+			} => format!("{}l", wide_bits(*high_bytes, *low_bytes) as i64),
+			ConstantPoolValueInfo::Double {
+				high_bytes,
+				low_bytes,
+			} => format!(
+				"{}d",
+				double_string(f64::from_bits(wide_bits(*high_bytes, *low_bytes)))
+			),
+			ConstantPoolValueInfo::NameAndType {
+				name_index,
+				descriptor_index,
+			} => format!(
+				"{}:{}",
+				check_name(&self.utf8(*name_index)),
+				self.utf8(*descriptor_index)
+			),
+			ConstantPoolValueInfo::Utf8 { bytes, .. } => escape(bytes),
+			ConstantPoolValueInfo::MethodHandle {
+				reference_kind,
+				reference_index,
+			} => format!(
+				"{} {}",
+				reference_kind_name(*reference_kind),
+				self.format_constant(*reference_index, omit_this_class)
+			),
+			ConstantPoolValueInfo::MethodType { descriptor_index } => self.utf8(*descriptor_index),
+			ConstantPoolValueInfo::Dynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			}
+			| ConstantPoolValueInfo::InvokeDynamic {
+				bootstrap_method_attr_index,
+				name_and_type_index,
+			} => {
+				let (name, descriptor) = self.name_and_type(*name_and_type_index);
+				format!(
+					"#{bootstrap_method_attr_index}:{}:{descriptor}",
+					check_name(&name)
+				)
+			},
+			ConstantPoolValueInfo::Module { name_index } => check_name(&self.utf8(*name_index)),
+			ConstantPoolValueInfo::Package { name_index } => self.utf8(*name_index),
+		}
+	}
+
+	/// Describe the entry at `index`, as it appears in the comments of instructions and attributes
+	///
+	/// This is the value of the entry, prefixed with its kind (e.g. `Method java/lang/Object."<init>":()V`).
+	pub(crate) fn describe_constant(&self, index: u2) -> String {
+		let Some(entry) = self.entry(index) else {
+			return invalid(index);
+		};
+
+		let kind = match entry {
+			ConstantPoolValueInfo::Class { .. } => "class",
+			ConstantPoolValueInfo::Fieldref { .. } => "Field",
+			ConstantPoolValueInfo::Methodref { .. } => "Method",
+			ConstantPoolValueInfo::InterfaceMethodref { .. } => "InterfaceMethod",
+			ConstantPoolValueInfo::String { .. } => "String",
+			ConstantPoolValueInfo::Integer { .. } => "int",
+			ConstantPoolValueInfo::Float { .. } => "float",
+			ConstantPoolValueInfo::Long { .. } => "long",
+			ConstantPoolValueInfo::Double { .. } => "double",
+			entry => tag_name(entry),
+		};
+
+		format!("{kind} {}", self.format_constant(index, true))
+	}
+
+	// https://docs.oracle.com/javase/specs/jvms/se23/html/jvms-4.html#jvms-4.4
+	pub(crate) fn write_constant_pool(&mut self) {
+		let class = self.class;
+		let constant_pool: &[ConstantPoolValueInfo] = &class.constant_pool;
+
+		self.p.println("Constant pool:");
+		self.p.indent();
+
+		// Sized for the constant_pool_count, like the JDK's javap
+		let width = constant_pool.len().to_string().len() + 1;
+		for (index, entry) in constant_pool.iter().enumerate() {
+			// Covers the zeroth entry, and the second slot of longs and doubles
+			if matches!(entry, ConstantPoolValueInfo::Unusable) {
+				continue;
+			}
+
+			let index = index as u2;
+			let (operands, comment) = match entry {
+				ConstantPoolValueInfo::Unusable => unreachable!(),
+				ConstantPoolValueInfo::Class { name_index }
+				| ConstantPoolValueInfo::String {
+					string_index: name_index,
+				}
+				| ConstantPoolValueInfo::Module { name_index }
+				| ConstantPoolValueInfo::Package { name_index } => {
+					(format!("#{name_index}"), Some(self.constant_value(index)))
+				},
+				ConstantPoolValueInfo::Fieldref {
+					class_index,
+					name_and_type_index,
+				}
+				| ConstantPoolValueInfo::Methodref {
+					class_index,
+					name_and_type_index,
+				}
+				| ConstantPoolValueInfo::InterfaceMethodref {
+					class_index,
+					name_and_type_index,
+				} => (
+					format!("#{class_index}.#{name_and_type_index}"),
+					Some(self.constant_value(index)),
+				),
+				ConstantPoolValueInfo::NameAndType {
+					name_index,
+					descriptor_index,
+				} => (
+					format!("#{name_index}:#{descriptor_index}"),
+					Some(self.constant_value(index)),
+				),
+				ConstantPoolValueInfo::Integer { .. }
+				| ConstantPoolValueInfo::Float { .. }
+				| ConstantPoolValueInfo::Long { .. }
+				| ConstantPoolValueInfo::Double { .. }
+				| ConstantPoolValueInfo::Utf8 { .. } => (self.constant_value(index), None),
+				ConstantPoolValueInfo::MethodHandle {
+					reference_kind,
+					reference_index,
+				} => (
+					format!("{reference_kind}:#{reference_index}"),
+					Some(self.constant_value(index)),
+				),
+				ConstantPoolValueInfo::MethodType { descriptor_index } => (
+					format!("#{descriptor_index}"),
+					Some(format!(" {}", self.constant_value(index))),
+				),
+				ConstantPoolValueInfo::Dynamic {
+					bootstrap_method_attr_index,
+					name_and_type_index,
+				}
+				| ConstantPoolValueInfo::InvokeDynamic {
+					bootstrap_method_attr_index,
+					name_and_type_index,
+				} => (
+					format!("#{bootstrap_method_attr_index}:#{name_and_type_index}"),
+					Some(self.constant_value(index)),
+				),
+			};
+
+			self.p.print(format_args!(
+				"{:>width$} = {:<18} {operands}",
+				format!("#{index}"),
+				tag_name(entry)
+			));
+			match comment {
+				Some(comment) => self.p.comment(comment),
+				None => self.p.newline(),
+			}
+		}
+
+		self.p.dedent();
+	}
+}
+
+fn invalid(index: u2) -> String {
+	format!("<invalid #{index}>")
+}
+
+fn wide_bits(high_bytes: u4, low_bytes: u4) -> u64 {
+	(u64::from(high_bytes) << 32) | u64::from(low_bytes)
+}
+
+/// The name of an entry, as it appears in the constant pool listing
+fn tag_name(entry: &ConstantPoolValueInfo) -> &'static str {
+	match entry {
+		ConstantPoolValueInfo::Unusable => "Unusable",
+		ConstantPoolValueInfo::Class { .. } => "Class",
+		ConstantPoolValueInfo::Fieldref { .. } => "Fieldref",
+		ConstantPoolValueInfo::Methodref { .. } => "Methodref",
+		ConstantPoolValueInfo::InterfaceMethodref { .. } => "InterfaceMethodref",
+		ConstantPoolValueInfo::String { .. } => "String",
+		ConstantPoolValueInfo::Integer { .. } => "Integer",
+		ConstantPoolValueInfo::Float { .. } => "Float",
+		ConstantPoolValueInfo::Long { .. } => "Long",
+		ConstantPoolValueInfo::Double { .. } => "Double",
+		ConstantPoolValueInfo::NameAndType { .. } => "NameAndType",
+		ConstantPoolValueInfo::Utf8 { .. } => "Utf8",
+		ConstantPoolValueInfo::MethodHandle { .. } => "MethodHandle",
+		ConstantPoolValueInfo::MethodType { .. } => "MethodType",
+		ConstantPoolValueInfo::Dynamic { .. } => "Dynamic",
+		ConstantPoolValueInfo::InvokeDynamic { .. } => "InvokeDynamic",
+		ConstantPoolValueInfo::Module { .. } => "Module",
+		ConstantPoolValueInfo::Package { .. } => "Package",
+	}
+}
+
+pub(crate) fn reference_kind_name(reference_kind: u1) -> String {
+	let name = match ReferenceKind::from_u8(reference_kind) {
+		Some(ReferenceKind::GetField) => "REF_getField",
+		Some(ReferenceKind::GetStatic) => "REF_getStatic",
+		Some(ReferenceKind::PutField) => "REF_putField",
+		Some(ReferenceKind::PutStatic) => "REF_putStatic",
+		Some(ReferenceKind::InvokeVirtual) => "REF_invokeVirtual",
+		Some(ReferenceKind::InvokeStatic) => "REF_invokeStatic",
+		Some(ReferenceKind::InvokeSpecial) => "REF_invokeSpecial",
+		Some(ReferenceKind::NewInvokeSpecial) => "REF_newInvokeSpecial",
+		Some(ReferenceKind::InvokeInterface) => "REF_invokeInterface",
+		None => return format!("<invalid reference kind {reference_kind}>"),
+	};
+
+	String::from(name)
+}
+
+/// Quote `name`, unless it is made up of Java identifiers separated by `/`
+///
+/// This is used to set apart special names, such as `"<init>"` and array classes.
+pub(crate) fn check_name(name: &str) -> String {
+	let mut previous = '/';
+	for c in name.chars() {
+		let is_start = c.is_alphabetic() || c == '_' || c == '$';
+		let is_part = is_start || c.is_numeric();
+		if (previous == '/' && !is_start) || (c != '/' && !is_part) {
+			return format!("\"{name}\"");
+		}
+
+		previous = c;
+	}
+
+	if name.is_empty() {
+		return String::from("\"\"");
+	}
+
+	name.to_string()
+}
+
+/// Decode a modified UTF-8 string (§4.4.7), escaping special and unrepresentable characters
+///
+/// This is lenient, since the string could be the reason a class file is being inspected in the
+/// first place. Malformed bytes are replaced with U+FFFD, and unpaired surrogates are escaped.
+fn escape(bytes: &[u1]) -> String {
+	let is_continuation = |index: usize| bytes.get(index).is_some_and(|b| b & 0xC0 == 0x80);
+
+	let mut units = Vec::with_capacity(bytes.len());
+	let mut index = 0;
+	while index < bytes.len() {
+		let byte = u16::from(bytes[index]);
+		let (unit, length) = match bytes[index] {
+			0x00..=0x7F => (byte, 1),
+			0xC0..=0xDF if is_continuation(index + 1) => {
+				let second = u16::from(bytes[index + 1]);
+				(((byte & 0x1F) << 6) | (second & 0x3F), 2)
+			},
+			0xE0..=0xEF if is_continuation(index + 1) && is_continuation(index + 2) => {
+				let second = u16::from(bytes[index + 1]);
+				let third = u16::from(bytes[index + 2]);
+				(
+					((byte & 0x0F) << 12) | ((second & 0x3F) << 6) | (third & 0x3F),
+					3,
+				)
+			},
+			_ => (0xFFFD, 1),
+		};
+
+		units.push(unit);
+		index += length;
+	}
+
+	let mut escaped = String::with_capacity(units.len());
+	for c in char::decode_utf16(units) {
+		match c {
+			Ok('\t') => escaped.push_str("\\t"),
+			Ok('\n') => escaped.push_str("\\n"),
+			Ok('\r') => escaped.push_str("\\r"),
+			Ok('\u{8}') => escaped.push_str("\\b"),
+			Ok('\u{C}') => escaped.push_str("\\f"),
+			Ok('"') => escaped.push_str("\\\""),
+			Ok('\'') => escaped.push_str("\\'"),
+			Ok('\\') => escaped.push_str("\\\\"),
+			Ok(c) if c.is_control() => {
+				let _ = write!(escaped, "\\u{:04x}", c as u32);
+			},
+			Ok(c) => escaped.push(c),
+			Err(e) => {
+				let _ = write!(escaped, "\\u{:04x}", e.unpaired_surrogate());
+			},
+		}
+	}
+
+	escaped
+}
+
+/// Format a floating point value the same way as `Float#toString()` and `Double#toString()`
+macro_rules! java_float_string {
+	($name:ident, $ty:ty) => {
+		pub(crate) fn $name(value: $ty) -> String {
+			if value.is_nan() {
+				return String::from("NaN");
+			}
+
+			if value.is_infinite() {
+				let sign = if value.is_sign_negative() { "-" } else { "" };
+				return format!("{sign}Infinity");
+			}
+
+			let magnitude = value.abs();
+			if value == 0.0 || (1e-3..1e7).contains(&magnitude) {
+				let plain = value.to_string();
+				if plain.contains('.') {
+					return plain;
+				}
+
+				return format!("{plain}.0");
+			}
+
+			let scientific = format!("{value:e}");
+			let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+			if mantissa.contains('.') {
+				return format!("{mantissa}E{exponent}");
+			}
+
+			format!("{mantissa}.0E{exponent}")
+		}
+	};
+}
+
+java_float_string!(float_string, f32);
+java_float_string!(double_string, f64);
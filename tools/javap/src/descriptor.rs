@@ -0,0 +1,312 @@
+//! Conversion of descriptors (§4.3) and signatures (§4.7.9.1) to their Java source forms
+
+/// A method descriptor, converted to Java types
+pub struct MethodType {
+	pub parameters: Vec<String>,
+	pub return_type: String,
+}
+
+/// Convert the field type at the start of `descriptor`
+///
+/// Returns the type and the rest of the descriptor. This returns `None` if `descriptor` is malformed.
+fn next_type(descriptor: &str) -> Option<(String, &str)> {
+	let dimensions = descriptor.bytes().take_while(|b| *b == b'[').count();
+	let rest = &descriptor[dimensions..];
+
+	let (name, rest) = match rest.as_bytes().first()? {
+		b'B' => (String::from("byte"), &rest[1..]),
+		b'C' => (String::from("char"), &rest[1..]),
+		b'D' => (String::from("double"), &rest[1..]),
+		b'F' => (String::from("float"), &rest[1..]),
+		b'I' => (String::from("int"), &rest[1..]),
+		b'J' => (String::from("long"), &rest[1..]),
+		b'S' => (String::from("short"), &rest[1..]),
+		b'Z' => (String::from("boolean"), &rest[1..]),
+		b'V' => (String::from("void"), &rest[1..]),
+		b'L' => {
+			let end = rest.find(';')?;
+			(java_name(&rest[1..end]), &rest[end + 1..])
+		},
+		_ => return None,
+	};
+
+	Some((name + &"[]".repeat(dimensions), rest))
+}
+
+/// Convert an internal name (`java/lang/Object`) to its Java form (`java.lang.Object`)
+pub fn java_name(internal_name: &str) -> String {
+	internal_name.replace('/', ".")
+}
+
+/// Convert a field descriptor to its Java type
+///
+/// Malformed descriptors are returned as-is.
+pub fn field_type(descriptor: &str) -> String {
+	match next_type(descriptor) {
+		Some((ty, "")) => ty,
+		_ => descriptor.to_string(),
+	}
+}
+
+/// Convert a method descriptor to its Java types
+///
+/// Returns `None` if `descriptor` is malformed.
+pub fn method_type(descriptor: &str) -> Option<MethodType> {
+	let mut rest = descriptor.strip_prefix('(')?;
+
+	let mut parameters = Vec::new();
+	while !rest.starts_with(')') {
+		let (ty, remaining) = next_type(rest)?;
+		parameters.push(ty);
+		rest = remaining;
+	}
+
+	let (return_type, "") = next_type(&rest[1..])? else {
+		return None;
+	};
+
+	Some(MethodType {
+		parameters,
+		return_type,
+	})
+}
+
+/// A class signature, converted to Java types
+pub struct ClassSignature {
+	/// The type parameters, including the angle brackets (e.g. `<T extends java.lang.Number>`)
+	///
+	/// This is empty for non-generic classes.
+	pub type_parameters: String,
+	pub superclass: String,
+	pub interfaces: Vec<String>,
+}
+
+/// A method signature, converted to Java types
+pub struct MethodSignature {
+	/// The type parameters, including the angle brackets (e.g. `<T extends java.lang.Number>`)
+	///
+	/// This is empty for non-generic methods.
+	pub type_parameters: String,
+	pub parameters: Vec<String>,
+	pub return_type: String,
+	pub throws: Vec<String>,
+}
+
+/// Convert a class signature to its Java types
+///
+/// Bounds of `java.lang.Object` are only included when `verbose` is set. This returns `None`
+/// if `signature` is malformed.
+pub fn class_signature(signature: &str, verbose: bool) -> Option<ClassSignature> {
+	let mut parser = SignatureParser {
+		rest: signature,
+		verbose,
+	};
+
+	let type_parameters = parser.type_parameters()?;
+	let superclass = parser.reference_type()?;
+
+	let mut interfaces = Vec::new();
+	while !parser.rest.is_empty() {
+		interfaces.push(parser.reference_type()?);
+	}
+
+	Some(ClassSignature {
+		type_parameters,
+		superclass,
+		interfaces,
+	})
+}
+
+/// Convert a method signature to its Java types
+///
+/// Bounds of `java.lang.Object` are only included when `verbose` is set. This returns `None`
+/// if `signature` is malformed.
+pub fn method_signature(signature: &str, verbose: bool) -> Option<MethodSignature> {
+	let mut parser = SignatureParser {
+		rest: signature,
+		verbose,
+	};
+
+	let type_parameters = parser.type_parameters()?;
+
+	parser.expect('(')?;
+	let mut parameters = Vec::new();
+	while !parser.eat(')') {
+		parameters.push(parser.java_type()?);
+	}
+
+	let return_type = if parser.eat('V') {
+		String::from("void")
+	} else {
+		parser.java_type()?
+	};
+
+	let mut throws = Vec::new();
+	while parser.eat('^') {
+		throws.push(parser.reference_type()?);
+	}
+
+	if !parser.rest.is_empty() {
+		return None;
+	}
+
+	Some(MethodSignature {
+		type_parameters,
+		parameters,
+		return_type,
+		throws,
+	})
+}
+
+/// Convert a field signature to its Java type
+///
+/// Returns `None` if `signature` is malformed.
+pub fn field_signature(signature: &str) -> Option<String> {
+	let mut parser = SignatureParser {
+		rest: signature,
+		verbose: false,
+	};
+
+	let ty = parser.reference_type()?;
+	parser.rest.is_empty().then_some(ty)
+}
+
+struct SignatureParser<'a> {
+	rest: &'a str,
+	verbose: bool,
+}
+
+impl SignatureParser<'_> {
+	fn peek(&self) -> Option<char> {
+		self.rest.chars().next()
+	}
+
+	fn eat(&mut self, c: char) -> bool {
+		match self.rest.strip_prefix(c) {
+			Some(rest) => {
+				self.rest = rest;
+				true
+			},
+			None => false,
+		}
+	}
+
+	fn expect(&mut self, c: char) -> Option<()> {
+		self.eat(c).then_some(())
+	}
+
+	/// Read an identifier, up to (but not including) any of `terminators`
+	fn identifier(&mut self, terminators: &[char]) -> Option<&str> {
+		let end = self.rest.find(terminators)?;
+		if end == 0 {
+			return None;
+		}
+
+		let (identifier, rest) = self.rest.split_at(end);
+		self.rest = rest;
+		Some(identifier)
+	}
+
+	fn type_parameters(&mut self) -> Option<String> {
+		if !self.eat('<') {
+			return Some(String::new());
+		}
+
+		let mut parameters = Vec::new();
+		while !self.eat('>') {
+			let mut parameter = self.identifier(&[':'])?.to_string();
+
+			// The class bound may be empty, but the colon is always present
+			self.expect(':')?;
+
+			let mut bounds = Vec::new();
+			if matches!(self.peek(), Some('L' | 'T' | '[')) {
+				let class_bound = self.reference_type()?;
+				if self.verbose || class_bound != "java.lang.Object" {
+					bounds.push(class_bound);
+				}
+			}
+
+			while self.eat(':') {
+				bounds.push(self.reference_type()?);
+			}
+
+			if !bounds.is_empty() {
+				parameter.push_str(" extends ");
+				parameter.push_str(&bounds.join(" & "));
+			}
+
+			parameters.push(parameter);
+		}
+
+		Some(format!("<{}>", parameters.join(", ")))
+	}
+
+	fn java_type(&mut self) -> Option<String> {
+		let name = match self.peek()? {
+			'B' => "byte",
+			'C' => "char",
+			'D' => "double",
+			'F' => "float",
+			'I' => "int",
+			'J' => "long",
+			'S' => "short",
+			'Z' => "boolean",
+			_ => return self.reference_type(),
+		};
+
+		self.rest = &self.rest[1..];
+		Some(String::from(name))
+	}
+
+	fn reference_type(&mut self) -> Option<String> {
+		if self.eat('[') {
+			return Some(self.java_type()? + "[]");
+		}
+
+		if self.eat('T') {
+			let name = self.identifier(&[';'])?.to_string();
+			self.expect(';')?;
+			return Some(name);
+		}
+
+		self.expect('L')?;
+
+		let mut ty = java_name(self.identifier(&['<', '.', ';'])?);
+		loop {
+			if self.peek() == Some('<') {
+				ty.push_str(&self.type_arguments()?);
+			}
+
+			if self.eat(';') {
+				return Some(ty);
+			}
+
+			// An inner class of a parameterized type
+			self.expect('.')?;
+			ty.push('.');
+			ty.push_str(self.identifier(&['<', '.', ';'])?);
+		}
+	}
+
+	fn type_arguments(&mut self) -> Option<String> {
+		self.expect('<')?;
+
+		let mut arguments = Vec::new();
+		while !self.eat('>') {
+			let argument = if self.eat('*') {
+				String::from("?")
+			} else if self.eat('+') {
+				format!("? extends {}", self.reference_type()?)
+			} else if self.eat('-') {
+				format!("? super {}", self.reference_type()?)
+			} else {
+				self.reference_type()?
+			};
+
+			arguments.push(argument);
+		}
+
+		Some(format!("<{}>", arguments.join(", ")))
+	}
+}
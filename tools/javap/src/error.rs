@@ -0,0 +1,30 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug)]
+pub enum Error {
+	ClassFile(classfile::error::ClassFileParseError),
+	Io(std::io::Error),
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Error::ClassFile(e) => f.write_fmt(format_args!("{e}")),
+			Error::Io(e) => f.write_fmt(format_args!("{e}")),
+		}
+	}
+}
+
+impl core::error::Error for Error {}
+
+impl From<classfile::error::ClassFileParseError> for Error {
+	fn from(value: classfile::error::ClassFileParseError) -> Self {
+		Error::ClassFile(value)
+	}
+}
+
+impl From<std::io::Error> for Error {
+	fn from(value: std::io::Error) -> Self {
+		Error::Io(value)
+	}
+}
@@ -0,0 +1,140 @@
+mod attributes;
+mod class;
+mod code;
+mod constant_pool;
+mod descriptor;
+mod error;
+mod printer;
+
+use class::{ClassWriter, Options};
+use error::Error;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use classfile::ClassFile;
+
+#[derive(Parser)]
+#[command(
+	name = "javap",
+	bin_name = "javap",
+	author = "Serial-ATA",
+	version,
+	long_about = None,
+)]
+struct Command {
+	#[arg(
+		short,
+		long,
+		help = "Print additional information (implies -c, -l and -s)"
+	)]
+	verbose: bool,
+	#[arg(short = 'l', help = "Print line number and local variable tables")]
+	line_numbers: bool,
+	#[arg(short, long, help = "Show all classes and members")]
+	private: bool,
+	#[arg(short = 'c', help = "Disassemble the code")]
+	disassemble: bool,
+	#[arg(short = 's', help = "Print internal type signatures")]
+	signatures: bool,
+	#[arg(required = true, help = "Paths to the class files to disassemble")]
+	classes: Vec<PathBuf>,
+}
+
+fn main() -> ExitCode {
+	let args = Command::parse();
+
+	let options = Options {
+		verbose: args.verbose,
+		line_numbers: args.line_numbers,
+		private: args.private,
+		disassemble: args.disassemble,
+		signatures: args.signatures,
+	};
+
+	let mut status = ExitCode::SUCCESS;
+	for path in &args.classes {
+		if let Err(e) = disassemble(path, options) {
+			eprintln!("Error: {}: {e}", path.display());
+			status = ExitCode::FAILURE;
+		}
+	}
+
+	status
+}
+
+fn disassemble(path: &Path, options: Options) -> Result<(), Error> {
+	let bytes = std::fs::read(path)?;
+	let class = ClassFile::read_from(&mut &bytes[..])?;
+
+	print!("{}", ClassWriter::new(&class, options).write(path));
+
+	// The class is still printed, since a malformed class is often why it is being inspected
+	if let Err(e) = class.check_format() {
+		eprintln!("Warning: {}: {e}", path.display());
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The expected output was produced by the JDK's `javap`, with the file path trimmed and the
+	// "Last modified" and "SHA-256 checksum" lines removed
+	macro_rules! assert_golden {
+		($class:literal, $options:expr, $expected:literal) => {{
+			let bytes = include_bytes!(concat!("../../../classfile/tests/data/", $class));
+			let class = ClassFile::read_from(&mut &bytes[..]).unwrap();
+
+			// The file name doesn't exist relative to the test, so it's printed as-is
+			let file_name = Path::new($class).file_name().unwrap();
+			let output = ClassWriter::new(&class, $options).write(Path::new(file_name));
+			assert_eq!(
+				output,
+				include_str!(concat!("../tests/data/", $expected)),
+				"`{}` output changed",
+				$expected
+			);
+		}};
+	}
+
+	#[test]
+	fn disassemble() {
+		let options = Options {
+			disassemble: true,
+			..Options::default()
+		};
+		assert_golden!("RoundTrip.class", options, "RoundTrip.c.txt");
+	}
+
+	#[test]
+	fn private() {
+		let options = Options {
+			private: true,
+			..Options::default()
+		};
+		assert_golden!("RoundTrip.class", options, "RoundTrip.p.txt");
+	}
+
+	#[test]
+	fn signatures() {
+		let options = Options {
+			signatures: true,
+			..Options::default()
+		};
+		assert_golden!("RoundTrip.class", options, "RoundTrip.s.txt");
+	}
+
+	#[test]
+	fn verbose() {
+		let options = Options {
+			verbose: true,
+			..Options::default()
+		};
+		assert_golden!("RoundTrip.class", options, "RoundTrip.v.txt");
+		assert_golden!("module/module-info.class", options, "module-info.v.txt");
+	}
+}
@@ -0,0 +1,78 @@
+use std::fmt::{Display, Write};
+
+/// The width of a single level of indentation
+const INDENT_WIDTH: usize = 2;
+/// The column, relative to the current indentation, that comments are aligned to
+const COMMENT_COLUMN: usize = 40;
+
+/// An indentation-aware output buffer
+///
+/// This mirrors the layout of the JDK's `javap`, so the output of the two can be diffed.
+pub struct Printer {
+	out: String,
+	indent: usize,
+	line_start: usize,
+}
+
+impl Printer {
+	pub fn new() -> Self {
+		Self {
+			out: String::new(),
+			indent: 0,
+			line_start: 0,
+		}
+	}
+
+	pub fn indent(&mut self) {
+		self.indent += 1;
+	}
+
+	pub fn dedent(&mut self) {
+		self.indent = self.indent.saturating_sub(1);
+	}
+
+	pub fn print(&mut self, value: impl Display) {
+		if self.out.len() == self.line_start {
+			self.out
+				.extend(std::iter::repeat_n(' ', self.indent * INDENT_WIDTH));
+		}
+
+		let _ = write!(self.out, "{value}");
+	}
+
+	pub fn println(&mut self, value: impl Display) {
+		self.print(value);
+		self.newline();
+	}
+
+	/// End the current line
+	///
+	/// Trailing whitespace is removed, as the JDK's `javap` does.
+	pub fn newline(&mut self) {
+		let trimmed = self.out[self.line_start..].trim_end_matches(' ').len();
+		self.out.truncate(self.line_start + trimmed);
+		self.out.push('\n');
+		self.line_start = self.out.len();
+	}
+
+	/// Pad the current line up to the comment column
+	///
+	/// If the line is already past the column, this will only add a single space.
+	pub fn tab(&mut self) {
+		let column = self.out[self.line_start..].chars().count();
+		let target = (self.indent * INDENT_WIDTH) + COMMENT_COLUMN;
+
+		let padding = target.saturating_sub(column).max(1);
+		self.out.extend(std::iter::repeat_n(' ', padding));
+	}
+
+	/// Print a `// comment`, aligned to the comment column, and end the line
+	pub fn comment(&mut self, comment: impl Display) {
+		self.tab();
+		self.println(format_args!("// {comment}"));
+	}
+
+	pub fn finish(self) -> String {
+		self.out
+	}
+}
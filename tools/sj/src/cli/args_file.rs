@@ -0,0 +1,495 @@
+//! `@argfile` expansion, and splitting of options from environment variables
+//!
+//! These follow the same rules as the `java` launcher, see the "java Command-Line Argument Files"
+//! section of the `java` documentation.
+
+use crate::error::Error;
+
+use std::fs;
+
+/// Options that take their value as the next argument
+const WHITESPACE_OPTIONS: &[&str] = &[
+	"-cp",
+	"-classpath",
+	"--class-path",
+	"-p",
+	"--module-path",
+	"--upgrade-module-path",
+	"--add-modules",
+	"--enable-native-access",
+	"--limit-modules",
+	"--add-exports",
+	"--add-opens",
+	"--add-reads",
+	"--patch-module",
+	"--source",
+];
+
+/// Options that either specify the main class, or exit the launcher before running it
+///
+/// These aren't allowed in `JDK_JAVA_OPTIONS`.
+const TERMINAL_OPTIONS: &[&str] = &[
+	"-jar",
+	"-m",
+	"--module",
+	"--dry-run",
+	"-h",
+	"-?",
+	"-help",
+	"--help",
+	"-X",
+	"--help-extra",
+	"-version",
+	"--version",
+	"-fullversion",
+	"--full-version",
+];
+
+/// The file referenced by `arg`, if it's an `@argfile`
+///
+/// A lone `@` is a regular argument.
+fn args_file(arg: &str) -> Option<&str> {
+	arg.strip_prefix('@')
+		.filter(|file| !file.is_empty() && !file.starts_with('@'))
+}
+
+fn is_terminal_option(arg: &str) -> bool {
+	TERMINAL_OPTIONS.contains(&arg) || arg.starts_with("--module=")
+}
+
+/// Expands `@argfile` arguments
+///
+/// Expansion stops at the main class (or the `-jar` file), so application arguments are always
+/// passed through as-is. It can also be turned off entirely with `--disable-@files`.
+#[derive(Default)]
+pub struct ArgExpander {
+	/// Set by `--disable-@files`
+	disabled: bool,
+	/// Whether the previous argument was an option that takes a value
+	expecting_value: bool,
+	/// Whether the previous argument was `-jar` or `--module`, making the next one the main class
+	expecting_main_class: bool,
+	/// Whether the main class has been seen, making every following argument an application argument
+	found_main_class: bool,
+}
+
+impl ArgExpander {
+	/// Expand `arg`, pushing the result to `out`
+	pub fn expand(&mut self, arg: String, out: &mut Vec<String>) -> Result<(), Error> {
+		if self.found_main_class {
+			out.push(arg);
+			return Ok(());
+		}
+
+		for arg in self.preprocess(arg)? {
+			self.check_arg(&arg);
+			out.push(arg);
+		}
+
+		Ok(())
+	}
+
+	/// Split `JDK_JAVA_OPTIONS`, expanding any `@argfile`s, and push the result to `out`
+	///
+	/// Unlike the command line, the variable can't specify the main class or any option that would
+	/// stop the launcher from running it.
+	pub fn expand_env(
+		&mut self,
+		var_name: &'static str,
+		value: &str,
+		out: &mut Vec<String>,
+	) -> Result<(), Error> {
+		for arg in split_env_options(var_name, value)? {
+			let file = args_file(&arg).filter(|_| !self.disabled).map(String::from);
+
+			for expanded in self.preprocess(arg)? {
+				if is_terminal_option(&expanded) {
+					return Err(Error::EnvOptionNotAllowed {
+						option: expanded,
+						file,
+						var_name,
+					});
+				}
+
+				self.check_arg(&expanded);
+				out.push(expanded);
+			}
+		}
+
+		if self.found_main_class {
+			return Err(Error::EnvMainClass(var_name));
+		}
+
+		Ok(())
+	}
+
+	/// Replace an `@argfile` with its contents
+	///
+	/// `@@` escapes an argument that should start with `@`.
+	fn preprocess(&self, arg: String) -> Result<Vec<String>, Error> {
+		if self.disabled {
+			return Ok(vec![arg]);
+		}
+
+		if let Some(file) = args_file(&arg) {
+			let Ok(contents) = fs::read_to_string(file) else {
+				return Err(Error::CouldNotOpen(file.to_string()));
+			};
+
+			return Ok(split_args_file(&contents));
+		}
+
+		match arg.strip_prefix("@@") {
+			Some(escaped) => Ok(vec![format!("@{escaped}")]),
+			None => Ok(vec![arg]),
+		}
+	}
+
+	/// Keep track of where the options end, and whether expansion was disabled
+	fn check_arg(&mut self, arg: &str) {
+		if self.expecting_main_class {
+			self.expecting_main_class = false;
+			self.found_main_class = true;
+			return;
+		}
+
+		if !arg.starts_with('-') {
+			if !self.expecting_value {
+				self.found_main_class = true;
+			}
+
+			self.expecting_value = false;
+			return;
+		}
+
+		self.expecting_value = false;
+		match arg {
+			// The next argument is the main class (or jar), so everything after it is left alone
+			"-jar" | "-m" | "--module" => self.expecting_main_class = true,
+			"--disable-@files" => self.disabled = true,
+			_ if arg.starts_with("--module=") => self.found_main_class = true,
+			_ if WHITESPACE_OPTIONS.contains(&arg) => self.expecting_value = true,
+			_ => {},
+		}
+	}
+}
+
+/// Split the contents of an `@argfile` into arguments
+///
+/// Arguments are separated by whitespace, and `#` starts a comment that runs to the end of the line.
+/// Either kind of quote can be used to include whitespace in an argument, though a quoted section
+/// can't span multiple lines. Within quotes, `\` escapes the next character, with `\n`, `\r`, `\t`,
+/// and `\f` being translated. A `\` at the end of a line within quotes joins the line with the next
+/// one, skipping its leading whitespace.
+pub fn split_args_file(contents: &str) -> Vec<String> {
+	enum State {
+		FindNext,
+		InToken,
+		InQuote(char),
+		InEscape(char),
+		SkipLeadingWhitespace(char),
+		InComment,
+	}
+
+	let mut args = Vec::new();
+	let mut current = String::new();
+	let mut state = State::FindNext;
+
+	for c in contents.chars() {
+		match state {
+			State::FindNext | State::SkipLeadingWhitespace(_) if is_whitespace(c) => continue,
+			State::FindNext if c == '#' => {
+				state = State::InComment;
+				continue;
+			},
+			State::FindNext => state = State::InToken,
+			State::SkipLeadingWhitespace(quote) => state = State::InQuote(quote),
+			State::InComment => {
+				if c == '\n' || c == '\r' {
+					state = State::FindNext;
+				}
+				continue;
+			},
+			State::InEscape(quote) => {
+				if c == '\n' || c == '\r' {
+					// `\r\n` is treated as a single line break
+					state = State::SkipLeadingWhitespace(quote);
+					continue;
+				}
+
+				current.push(match c {
+					'n' => '\n',
+					'r' => '\r',
+					't' => '\t',
+					'f' => '\x0C',
+					c => c,
+				});
+				state = State::InQuote(quote);
+				continue;
+			},
+			State::InToken | State::InQuote(_) => {},
+		}
+
+		match (c, &state) {
+			(' ' | '\t' | '\x0C', State::InQuote(_)) => current.push(c),
+			(' ' | '\t' | '\x0C' | '\n' | '\r', _) => {
+				args.push(std::mem::take(&mut current));
+				state = State::FindNext;
+			},
+			('#', State::InToken) => {
+				args.push(std::mem::take(&mut current));
+				state = State::InComment;
+			},
+			('\\', State::InQuote(quote)) => state = State::InEscape(*quote),
+			('\'' | '"', State::InToken) => state = State::InQuote(c),
+			('\'' | '"', State::InQuote(quote)) if c == *quote => state = State::InToken,
+			_ => current.push(c),
+		}
+	}
+
+	if matches!(
+		state,
+		State::InToken | State::InQuote(_) | State::InEscape(_) | State::SkipLeadingWhitespace(_)
+	) {
+		args.push(current);
+	}
+
+	args
+}
+
+/// Split the options in an environment variable
+///
+/// Options are separated by whitespace, and either kind of quote can be used to include whitespace
+/// in an option. There are no escapes.
+pub fn split_env_options(var_name: &'static str, value: &str) -> Result<Vec<String>, Error> {
+	let mut options = Vec::new();
+
+	let mut chars = value.chars().peekable();
+	loop {
+		while chars.next_if(char::is_ascii_whitespace).is_some() {}
+		if chars.peek().is_none() {
+			break;
+		}
+
+		let mut option = String::new();
+		while let Some(c) = chars.next_if(|c| !c.is_ascii_whitespace()) {
+			if c != '"' && c != '\'' {
+				option.push(c);
+				continue;
+			}
+
+			loop {
+				match chars.next() {
+					Some(next) if next == c => break,
+					Some(next) => option.push(next),
+					None => return Err(Error::UnmatchedQuote(var_name)),
+				}
+			}
+		}
+
+		options.push(option);
+	}
+
+	Ok(options)
+}
+
+fn is_whitespace(c: char) -> bool {
+	matches!(c, ' ' | '\t' | '\x0C' | '\n' | '\r')
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::path::PathBuf;
+
+	/// Write an `@argfile` to the temp directory, returning its path
+	fn write_args_file(name: &str, contents: &str) -> PathBuf {
+		let path = std::env::temp_dir().join(format!("sj-{}-{name}", std::process::id()));
+		fs::write(&path, contents).unwrap();
+		path
+	}
+
+	fn expand(expander: &mut ArgExpander, args: &[&str]) -> Result<Vec<String>, Error> {
+		let mut out = Vec::new();
+		for arg in args {
+			expander.expand((*arg).to_string(), &mut out)?;
+		}
+
+		Ok(out)
+	}
+
+	#[test]
+	fn args_file_quotes() {
+		assert_eq!(
+			split_args_file(r#"-cp "a b"c 'd"e' -Dfoo="bar baz""#),
+			["-cp", "a bc", "d\"e", "-Dfoo=bar baz"]
+		);
+
+		// Escapes only apply within quotes
+		assert_eq!(split_args_file(r#""a\tb\\c" d\e"#), ["a\tb\\c", "d\\e"]);
+
+		// An unterminated quote ends with the file
+		assert_eq!(split_args_file("-a \"b c"), ["-a", "b c"]);
+	}
+
+	#[test]
+	fn args_file_line_continuation() {
+		assert_eq!(
+			split_args_file("\"-Dfoo=bar \\\n    baz\" -a"),
+			["-Dfoo=bar baz", "-a"]
+		);
+		assert_eq!(split_args_file("'-cp a:\\\r\n\tb:\\\n c'"), ["-cp a:b:c"]);
+	}
+
+	#[test]
+	fn args_file_comments() {
+		assert_eq!(
+			split_args_file("-a # comment\n-b#c\r\n# full line\n  -d"),
+			["-a", "-b", "-d"]
+		);
+
+		// A quoted `#` is part of the argument
+		assert_eq!(split_args_file("\"#not-a-comment\""), ["#not-a-comment"]);
+	}
+
+	#[test]
+	fn args_file_expansion() {
+		let path = write_args_file("expansion", "-cp lib.jar\n# the main class\nMain");
+		let file = format!("@{}", path.display());
+
+		let args = expand(&mut ArgExpander::default(), &["-Xmx1g", &file, "app"]);
+		fs::remove_file(&path).unwrap();
+		assert_eq!(args.unwrap(), ["-Xmx1g", "-cp", "lib.jar", "Main", "app"]);
+
+		let missing = expand(&mut ArgExpander::default(), &["@/sj/missing/args"]);
+		assert!(matches!(missing, Err(Error::CouldNotOpen(file)) if file == "/sj/missing/args"));
+	}
+
+	#[test]
+	fn at_escaping() {
+		assert_eq!(
+			expand(
+				&mut ArgExpander::default(),
+				&["@@foo", "@", "Main", "@@bar"]
+			)
+			.unwrap(),
+			["@foo", "@", "Main", "@@bar"]
+		);
+	}
+
+	#[test]
+	fn disable_at_files() {
+		assert_eq!(
+			expand(
+				&mut ArgExpander::default(),
+				&["--disable-@files", "@/sj/missing/args", "@@foo", "Main"]
+			)
+			.unwrap(),
+			["--disable-@files", "@/sj/missing/args", "@@foo", "Main"]
+		);
+	}
+
+	#[test]
+	fn expansion_stops_after_main_class() {
+		// Option values aren't mistaken for the main class
+		assert_eq!(
+			expand(
+				&mut ArgExpander::default(),
+				&["-cp", "lib", "Main", "@/sj/missing/args", "@@foo"]
+			)
+			.unwrap(),
+			["-cp", "lib", "Main", "@/sj/missing/args", "@@foo"]
+		);
+		assert_eq!(
+			expand(
+				&mut ArgExpander::default(),
+				&["-jar", "app.jar", "@/sj/missing/args"]
+			)
+			.unwrap(),
+			["-jar", "app.jar", "@/sj/missing/args"]
+		);
+		assert_eq!(
+			expand(
+				&mut ArgExpander::default(),
+				&["--module=app/Main", "@/sj/missing/args"]
+			)
+			.unwrap(),
+			["--module=app/Main", "@/sj/missing/args"]
+		);
+	}
+
+	#[test]
+	fn main_class_args_file() {
+		// The jar or module itself can come from an `@argfile`, expansion stops after it
+		let path = write_args_file("main_class", "app.jar --verbose");
+		let file = format!("@{}", path.display());
+
+		let jar = expand(
+			&mut ArgExpander::default(),
+			&["-jar", &file, "@/sj/missing/args"],
+		);
+		let module = expand(
+			&mut ArgExpander::default(),
+			&["-m", &file, "@/sj/missing/args"],
+		);
+		fs::remove_file(&path).unwrap();
+		assert_eq!(
+			jar.unwrap(),
+			["-jar", "app.jar", "--verbose", "@/sj/missing/args"]
+		);
+		assert_eq!(
+			module.unwrap(),
+			["-m", "app.jar", "--verbose", "@/sj/missing/args"]
+		);
+	}
+
+	#[test]
+	fn env_options() {
+		assert_eq!(
+			split_env_options("VAR", r#"  -Dfoo="a b"  'c'd -e  "#).unwrap(),
+			["-Dfoo=a b", "cd", "-e"]
+		);
+		assert!(split_env_options("VAR", "").unwrap().is_empty());
+		assert!(matches!(
+			split_env_options("VAR", "-Dfoo=\"bar"),
+			Err(Error::UnmatchedQuote("VAR"))
+		));
+	}
+
+	#[test]
+	fn env_terminal_options() {
+		let expand_env = |value: &str| {
+			let mut out = Vec::new();
+			ArgExpander::default()
+				.expand_env("JDK_JAVA_OPTIONS", value, &mut out)
+				.map(|()| out)
+		};
+
+		assert_eq!(
+			expand_env("-Xmx1g -cp lib").unwrap(),
+			["-Xmx1g", "-cp", "lib"]
+		);
+
+		for value in ["-Xmx1g -jar app.jar", "--version", "--module=app/Main"] {
+			assert!(matches!(
+				expand_env(value),
+				Err(Error::EnvOptionNotAllowed { file: None, .. })
+			));
+		}
+		assert!(matches!(
+			expand_env("-cp lib Main"),
+			Err(Error::EnvMainClass("JDK_JAVA_OPTIONS"))
+		));
+
+		// Terminal options from an `@argfile` report the file they came from
+		let path = write_args_file("env", "-Xmx1g\n-jar app.jar");
+		let result = expand_env(&format!("@{}", path.display()));
+		fs::remove_file(&path).unwrap();
+		assert!(matches!(
+			result,
+			Err(Error::EnvOptionNotAllowed { option, file: Some(file), .. })
+				if option == "-jar" && file == path.display().to_string()
+		));
+	}
+}
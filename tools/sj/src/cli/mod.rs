@@ -1,3 +1,4 @@
+mod args_file;
 mod options;
 
 use crate::error::Error;
@@ -6,6 +7,9 @@ use crate::native::LaunchMode;
 use jni::version::JniVersion;
 use std::path::Path;
 
+/// Options for the launcher, prepended to the command line
+const JDK_JAVA_OPTIONS_ENV: &str = "JDK_JAVA_OPTIONS";
+/// Options for the VM, which take precedence over those on the command line
 const JAVA_OPTIONS_ENV: &str = "_JAVA_OPTIONS";
 
 pub enum HelpFlag {
@@ -59,13 +63,22 @@ pub struct Args {
 
 impl Args {
 	pub fn parse() -> Result<Self, Error> {
-		let cli_args_raw = std::env::args().skip(1).collect::<Vec<_>>();
-		let mut cli_args = cli_args_raw.into_iter().peekable();
+		let mut expander = args_file::ArgExpander::default();
+		let mut cli_args_raw = Vec::new();
+
+		if let Ok(jdk_java_options) = std::env::var(JDK_JAVA_OPTIONS_ENV)
+			&& !jdk_java_options.is_empty()
+		{
+			eprintln!("NOTE: Picked up {JDK_JAVA_OPTIONS_ENV}: {jdk_java_options}");
+			expander.expand_env(JDK_JAVA_OPTIONS_ENV, &jdk_java_options, &mut cli_args_raw)?;
+		}
 
-		if let Ok(_java_options) = std::env::var(JAVA_OPTIONS_ENV) {
-			// TODO: Actually load _JAVA_OPTIONS env
+		for arg in std::env::args().skip(1) {
+			expander.expand(arg, &mut cli_args_raw)?;
 		}
 
+		let mut cli_args = cli_args_raw.into_iter().peekable();
+
 		let mut args = Args::default();
 		loop {
 			let Some(arg) = cli_args.peek() else {
@@ -83,6 +96,11 @@ impl Args {
 				continue;
 			}
 
+			// Already handled by the `ArgExpander`
+			if arg == "--disable-@files" {
+				continue;
+			}
+
 			let Some(opt) = options::OPTIONS.find(&arg) else {
 				args.options.extra_args.push(arg);
 				continue;
@@ -95,6 +113,16 @@ impl Args {
 			}
 		}
 
+		// These are passed to the VM after everything else, so they take precedence
+		if let Ok(java_options) = std::env::var(JAVA_OPTIONS_ENV)
+			&& !java_options.is_empty()
+		{
+			eprintln!("Picked up {JAVA_OPTIONS_ENV}: {java_options}");
+			for option in args_file::split_env_options(JAVA_OPTIONS_ENV, &java_options)? {
+				args.options.add_vm_option(option);
+			}
+		}
+
 		match &mut args.launch_target {
 			Some(LaunchTarget::Jar(jar_name)) => {
 				args.options.set_classpath(&jar_name);
@@ -151,6 +179,34 @@ impl JVMOptions {
 		self.add_system_property(prop);
 	}
 
+	/// The stack size requested with `-Xss` or `-XX:ThreadStackSize`, if any
+	///
+	/// This is needed before the VM is created, as the main thread is spawned by the launcher. The
+	/// last option given wins, same as in the VM.
+	pub fn thread_stack_size(&self) -> Option<usize> {
+		let size = self.extra_args.iter().rev().find_map(|arg| {
+			if let Some(size) = arg.strip_prefix("-Xss") {
+				return Some(jvm::options::parse_memory_size(size));
+			}
+
+			let size = arg.strip_prefix("-XX:ThreadStackSize=")?;
+			Some(jvm::options::parse_thread_stack_size(size))
+		})??;
+
+		// 0 means the platform default
+		(size != 0).then_some(size)
+	}
+
+	/// Add an option to be passed to the VM as-is
+	fn add_vm_option(&mut self, option: String) {
+		if option.starts_with("-D") {
+			self.add_system_property(option);
+			return;
+		}
+
+		self.extra_args.push(option);
+	}
+
 	fn add_system_property(&mut self, property: impl Into<String>) {
 		match self.system_properties.as_mut() {
 			Some(system_props) => system_props.push(property.into()),
@@ -171,3 +227,44 @@ impl Into<jni::java_vm::VmInitArgs> for JVMOptions {
 		)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn options(args: &[&str]) -> JVMOptions {
+		let mut options = JVMOptions::default();
+		for arg in args {
+			options.add_vm_option((*arg).to_string());
+		}
+
+		options
+	}
+
+	#[test]
+	fn thread_stack_size() {
+		assert_eq!(options(&[]).thread_stack_size(), None);
+		assert_eq!(options(&["-Xss4m"]).thread_stack_size(), Some(4 << 20));
+		assert_eq!(
+			options(&["-XX:ThreadStackSize=2048"]).thread_stack_size(),
+			Some(2 << 20)
+		);
+
+		// The last option wins
+		assert_eq!(
+			options(&["-Xss4m", "-XX:ThreadStackSize=1m"]).thread_stack_size(),
+			Some(1 << 20)
+		);
+		assert_eq!(
+			options(&["-XX:ThreadStackSize=1m", "-Xss4m"]).thread_stack_size(),
+			Some(4 << 20)
+		);
+
+		// 0 and invalid sizes are left for the VM to deal with
+		assert_eq!(
+			options(&["-XX:ThreadStackSize=0"]).thread_stack_size(),
+			None
+		);
+		assert_eq!(options(&["-Xssbig"]).thread_stack_size(), None);
+	}
+}
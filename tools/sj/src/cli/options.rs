@@ -8,6 +8,13 @@ pub trait CliOption: Send + Sync + 'static {
 	/// All possible variants of this flag (long and short)
 	fn variants(&self) -> &'static [&'static str];
 
+	/// Whether `opt` is this flag
+	///
+	/// By default, this checks for an exact match with one of [`Self::variants()`].
+	fn matches(&self, opt: &str) -> bool {
+		self.variants().contains(&opt)
+	}
+
 	/// Returns the variable name describing the type of value this flag accepts.
 	///
 	/// For example, the `--class-path` options has its variable name set to "<class search path of directories and zip/jar files>".
@@ -48,10 +55,7 @@ pub struct Options {
 
 impl Options {
 	pub fn find(&self, opt: &str) -> Option<&dyn CliOption> {
-		self.options
-			.iter()
-			.find(|o| o.variants().contains(&opt))
-			.map(|v| &**v)
+		self.options.iter().find(|o| o.matches(opt)).map(|v| &**v)
 	}
 }
 
@@ -66,6 +70,10 @@ pub static OPTIONS: LazyLock<Options> = LazyLock::new(|| Options {
 		Box::new(ClassPath),
 		Box::new(DryRun),
 		Box::new(Jar),
+		Box::new(EnableAssertions),
+		Box::new(DisableAssertions),
+		Box::new(EnableSystemAssertions),
+		Box::new(DisableSystemAssertions),
 	],
 });
 
@@ -295,3 +303,115 @@ impl CliOption for Jar {
 		Ok(())
 	}
 }
+
+/// Whether `opt` is one of `variants`, optionally followed by `:<value>`
+fn matches_with_value(variants: &[&str], opt: &str) -> bool {
+	variants.iter().any(|variant| {
+		opt.strip_prefix(variant)
+			.is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+	})
+}
+
+pub struct EnableAssertions;
+
+impl CliOption for EnableAssertions {
+	fn variants(&self) -> &'static [&'static str] {
+		&["-enableassertions", "-ea"]
+	}
+
+	fn matches(&self, opt: &str) -> bool {
+		matches_with_value(self.variants(), opt)
+	}
+
+	fn doc_variable(&self) -> Option<&'static str> {
+		Some("[:<packagename>...|:<classname>]")
+	}
+
+	fn doc_short(&self) -> &'static str {
+		"Enable assertions with specified granularity"
+	}
+
+	fn act(
+		&self,
+		variant: String,
+		_cli_args: &mut Peekable<std::vec::IntoIter<String>>,
+		args: &mut Args,
+	) -> Result<(), Error> {
+		args.options.extra_args.push(variant);
+		Ok(())
+	}
+}
+
+pub struct DisableAssertions;
+
+impl CliOption for DisableAssertions {
+	fn variants(&self) -> &'static [&'static str] {
+		&["-disableassertions", "-da"]
+	}
+
+	fn matches(&self, opt: &str) -> bool {
+		matches_with_value(self.variants(), opt)
+	}
+
+	fn doc_variable(&self) -> Option<&'static str> {
+		Some("[:<packagename>...|:<classname>]")
+	}
+
+	fn doc_short(&self) -> &'static str {
+		"Disable assertions with specified granularity"
+	}
+
+	fn act(
+		&self,
+		variant: String,
+		_cli_args: &mut Peekable<std::vec::IntoIter<String>>,
+		args: &mut Args,
+	) -> Result<(), Error> {
+		args.options.extra_args.push(variant);
+		Ok(())
+	}
+}
+
+pub struct EnableSystemAssertions;
+
+impl CliOption for EnableSystemAssertions {
+	fn variants(&self) -> &'static [&'static str] {
+		&["-enablesystemassertions", "-esa"]
+	}
+
+	fn doc_short(&self) -> &'static str {
+		"Enable system assertions"
+	}
+
+	fn act(
+		&self,
+		variant: String,
+		_cli_args: &mut Peekable<std::vec::IntoIter<String>>,
+		args: &mut Args,
+	) -> Result<(), Error> {
+		args.options.extra_args.push(variant);
+		Ok(())
+	}
+}
+
+pub struct DisableSystemAssertions;
+
+impl CliOption for DisableSystemAssertions {
+	fn variants(&self) -> &'static [&'static str] {
+		&["-disablesystemassertions", "-dsa"]
+	}
+
+	fn doc_short(&self) -> &'static str {
+		"Disable system assertions"
+	}
+
+	fn act(
+		&self,
+		variant: String,
+		_cli_args: &mut Peekable<std::vec::IntoIter<String>>,
+		args: &mut Args,
+	) -> Result<(), Error> {
+		args.options.extra_args.push(variant);
+		Ok(())
+	}
+}
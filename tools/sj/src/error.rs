@@ -7,6 +7,14 @@ pub enum Error {
 	MissingClasspath(String),
 	MissingJar(String),
 	NonUtf8Path,
+	CouldNotOpen(String),
+	UnmatchedQuote(&'static str),
+	EnvOptionNotAllowed {
+		option: String,
+		file: Option<String>,
+		var_name: &'static str,
+	},
+	EnvMainClass(&'static str),
 
 	NoJarMain,
 	Jni(jni::error::JniError),
@@ -24,6 +32,38 @@ impl core::fmt::Display for Error {
 			Self::NonUtf8Path => {
 				write!(f, "Error: not a valid UTF-8 path")
 			},
+			Self::CouldNotOpen(file) => {
+				write!(f, "Error: could not open `{file}'")
+			},
+			Self::UnmatchedQuote(var_name) => {
+				write!(
+					f,
+					"Error: Unmatched quote in environment variable {var_name}"
+				)
+			},
+			Self::EnvOptionNotAllowed {
+				option,
+				file: Some(file),
+				var_name,
+			} => write!(
+				f,
+				"Error: Option {option} in {file} is not allowed in environment variable \
+				 {var_name}"
+			),
+			Self::EnvOptionNotAllowed {
+				option,
+				file: None,
+				var_name,
+			} => write!(
+				f,
+				"Error: Option {option} is not allowed in environment variable {var_name}"
+			),
+			Self::EnvMainClass(var_name) => {
+				write!(
+					f,
+					"Error: Cannot specify main class in environment variable {var_name}"
+				)
+			},
 
 			Self::NoJarMain => f.write_str("Unable to find main class in jar manifest"),
 			Self::Jni(e) => e.fmt(f),
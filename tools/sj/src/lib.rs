@@ -24,6 +24,12 @@ const ARGS_NOTICE: &str = r" Arguments following the main class, source file, -j
 
 const AUTHOR: &str = env!("SYSTEM_PROPS_VM_VENDOR");
 
+/// The stack size of the main thread, unless one is given with `-Xss` or `-XX:ThreadStackSize`
+///
+/// The interpreter recurses on the native stack for every Java call, so this is larger than
+/// [`jvm::options::DEFAULT_THREAD_STACK_SIZE`], matching the main thread of a process on Linux.
+const DEFAULT_MAIN_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
+
 const USAGE_STRING: &str = const_format::formatcp!("{ABOUT}\n\nUsage: {USAGE}\n\n{ARGS_NOTICE}");
 
 /// Launch a Java application
@@ -49,7 +55,18 @@ pub fn launch() -> Result<i32> {
 		return Ok(0);
 	}
 
-	std::thread::spawn(move || main(args))
+	// The main thread is spawned here, rather than by the VM, so it needs to respect `-Xss` as well.
+	// An invalid size is reported by the VM later on.
+	let stack_size = args
+		.options
+		.thread_stack_size()
+		.unwrap_or(DEFAULT_MAIN_THREAD_STACK_SIZE);
+
+	std::thread::Builder::new()
+		.name(String::from("main"))
+		.stack_size(stack_size)
+		.spawn(move || main(args))
+		.expect("failed to spawn main thread")
 		.join()
 		.unwrap_or_else(|_| {
 			eprintln!("Main thread panicked");